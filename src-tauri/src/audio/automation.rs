@@ -0,0 +1,136 @@
+//! Parameter gesture recording
+//!
+//! Captures the parameter changes a plugin reports to the host (knob turns in
+//! its GUI) with sample-accurate timestamps, so a gesture can be exported as a
+//! test vector and replayed deterministically by the project's test harness.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use super::plugin::clap_sys::ParamOutputEvent;
+
+/// Events reserved up front so recording rarely allocates on the audio thread
+const RECORD_CAPACITY: usize = 1 << 16;
+
+/// A recorded parameter event; `time` is in samples since recording started
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordedParamEvent {
+    Value { time: u64, param_id: u32, value: f64 },
+    GestureBegin { time: u64, param_id: u32 },
+    GestureEnd { time: u64, param_id: u32 },
+}
+
+impl RecordedParamEvent {
+    fn from_output(event: &ParamOutputEvent, block_start: u64) -> Self {
+        match *event {
+            ParamOutputEvent::Value { param_id, value, time } => Self::Value {
+                time: block_start + time as u64,
+                param_id,
+                value,
+            },
+            ParamOutputEvent::GestureBegin { param_id, time } => Self::GestureBegin {
+                time: block_start + time as u64,
+                param_id,
+            },
+            ParamOutputEvent::GestureEnd { param_id, time } => Self::GestureEnd {
+                time: block_start + time as u64,
+                param_id,
+            },
+        }
+    }
+}
+
+/// Recorder shared with the audio thread
+pub struct ParamRecorder {
+    recording: AtomicBool,
+    /// Samples processed since recording started
+    position: AtomicU64,
+    events: Mutex<Vec<RecordedParamEvent>>,
+}
+
+impl ParamRecorder {
+    pub fn new() -> Self {
+        Self {
+            recording: AtomicBool::new(false),
+            position: AtomicU64::new(0),
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.load(Ordering::Relaxed)
+    }
+
+    /// Discard any previous recording and start a new one
+    pub fn start(&self) {
+        {
+            let mut events = self.events.lock();
+            events.clear();
+            events.reserve(RECORD_CAPACITY);
+        }
+        self.position.store(0, Ordering::Relaxed);
+        self.recording.store(true, Ordering::Release);
+    }
+
+    /// Stop recording, returning the events and the recording length in samples
+    pub fn stop(&self) -> (Vec<RecordedParamEvent>, u64) {
+        self.recording.store(false, Ordering::Release);
+        let events = std::mem::take(&mut *self.events.lock());
+        (events, self.position.load(Ordering::Relaxed))
+    }
+
+    /// Record one processed block's parameter events (audio thread)
+    pub fn record(&self, events: &[ParamOutputEvent], frames: usize) {
+        if !self.is_recording() {
+            return;
+        }
+        let block_start = self.position.fetch_add(frames as u64, Ordering::Relaxed);
+        if events.is_empty() {
+            return;
+        }
+        // Never block the audio thread; the lock is only contended during start/stop
+        if let Some(mut recorded) = self.events.try_lock() {
+            recorded.extend(events.iter().map(|e| RecordedParamEvent::from_output(e, block_start)));
+        }
+    }
+}
+
+impl Default for ParamRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_are_timestamped_across_blocks() {
+        let recorder = ParamRecorder::new();
+        let block = [ParamOutputEvent::Value {
+            param_id: 7,
+            value: 0.5,
+            time: 10,
+        }];
+
+        // Ignored while not recording
+        recorder.record(&block, 512);
+        recorder.start();
+        recorder.record(&[], 512);
+        recorder.record(&block, 512);
+
+        let (events, length) = recorder.stop();
+        assert_eq!(length, 1024);
+        assert_eq!(
+            events,
+            vec![RecordedParamEvent::Value {
+                time: 522,
+                param_id: 7,
+                value: 0.5
+            }]
+        );
+    }
+}
@@ -9,11 +9,31 @@ pub struct AudioDeviceInfo {
     pub is_default: bool,
 }
 
+/// Smallest buffer size offered in low-latency mode
+pub const MIN_LOW_LATENCY_BUFFER: u32 = 32;
+
+/// Smallest buffer size used outside low-latency mode
+pub const MIN_STANDARD_BUFFER: u32 = 128;
+
+/// Buffer sizes an output device accepts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BufferSizeRange {
+    pub min: u32,
+    pub max: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioConfig {
     pub sample_rate: u32,
     pub channels: u16,
     pub buffer_size: u32,
+    /// Allow buffers down to `MIN_LOW_LATENCY_BUFFER` (clamped to what the device supports)
+    #[serde(default)]
+    pub low_latency: bool,
+    /// Exclusive device access (CoreAudio hog mode / WASAPI exclusive)
+    /// Not supported yet - see `check_stream_mode`.
+    #[serde(default)]
+    pub exclusive: bool,
 }
 
 impl Default for AudioConfig {
@@ -22,6 +42,8 @@ impl Default for AudioConfig {
             sample_rate: 44100,
             channels: 2,
             buffer_size: 512,
+            low_latency: false,
+            exclusive: false,
         }
     }
 }
@@ -79,6 +101,29 @@ pub fn get_output_device(name: Option<&str>) -> Result<cpal::Device, String> {
     }
 }
 
+/// Name of the current default output device
+pub fn get_default_output_device_name() -> Option<String> {
+    cpal::default_host()
+        .default_output_device()
+        .and_then(|d| d.name().ok())
+}
+
+/// Check whether an output device with this name is still connected
+/// Returns true if enumeration fails so a flaky host API doesn't trigger a
+/// spurious device switch.
+pub fn output_device_available(name: &str) -> bool {
+    match cpal::default_host().output_devices() {
+        Ok(mut devices) => devices.any(|d| d.name().map(|n| n == name).unwrap_or(false)),
+        Err(_) => true,
+    }
+}
+
+/// Whether a stream error means the device is gone (unplugged, asleep, or
+/// taken away by the OS) rather than a transient glitch
+pub fn is_device_lost_error(err: &cpal::StreamError) -> bool {
+    matches!(err, cpal::StreamError::DeviceNotAvailable)
+}
+
 /// Get the default output device's sample rate
 pub fn get_default_sample_rate() -> Result<u32, String> {
     let device = get_default_output_device()?;
@@ -88,6 +133,49 @@ pub fn get_default_sample_rate() -> Result<u32, String> {
     Ok(config.sample_rate().0)
 }
 
+/// Get the buffer sizes a device accepts, if the host reports them
+pub fn get_buffer_size_range(device: &cpal::Device) -> Option<BufferSizeRange> {
+    let configs = device.supported_output_configs().ok()?;
+    configs
+        .filter_map(|c| match *c.buffer_size() {
+            cpal::SupportedBufferSize::Range { min, max } => Some(BufferSizeRange { min, max }),
+            cpal::SupportedBufferSize::Unknown => None,
+        })
+        .reduce(|a, b| BufferSizeRange {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        })
+}
+
+/// Pick the buffer size to request from the device
+/// Low-latency mode allows going down to `MIN_LOW_LATENCY_BUFFER`; both modes are
+/// clamped to the device's range so the stream doesn't fail to open.
+pub fn resolve_buffer_size(requested: u32, low_latency: bool, range: Option<&BufferSizeRange>) -> u32 {
+    let floor = if low_latency {
+        MIN_LOW_LATENCY_BUFFER
+    } else {
+        MIN_STANDARD_BUFFER
+    };
+    let size = requested.max(floor);
+    match range {
+        Some(range) => size.clamp(range.min.max(1), range.max.max(range.min)),
+        None => size,
+    }
+}
+
+/// Reject stream modes the audio backend can't open
+/// cpal only opens shared-mode streams, so exclusive access (hog mode on macOS,
+/// WASAPI exclusive on Windows) is refused rather than silently ignored.
+pub fn check_stream_mode(config: &AudioConfig) -> Result<(), String> {
+    if config.exclusive {
+        return Err(
+            "Exclusive (hog mode) device access isn't supported - use low-latency mode for smaller buffers"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
 /// Get supported config for a device
 pub fn get_supported_config(
     device: &cpal::Device,
@@ -97,6 +185,12 @@ pub fn get_supported_config(
         .supported_output_configs()
         .map_err(|e| format!("Failed to get supported configs: {}", e))?;
 
+    let buffer_size = resolve_buffer_size(
+        preferred.buffer_size,
+        preferred.low_latency,
+        get_buffer_size_range(device).as_ref(),
+    );
+
     // Try to find a config matching our preferences
     for config in supported_configs {
         let min_rate = config.min_sample_rate().0;
@@ -109,7 +203,7 @@ pub fn get_supported_config(
             return Ok(cpal::StreamConfig {
                 channels: preferred.channels,
                 sample_rate: cpal::SampleRate(preferred.sample_rate),
-                buffer_size: cpal::BufferSize::Fixed(preferred.buffer_size),
+                buffer_size: cpal::BufferSize::Fixed(buffer_size),
             });
         }
     }
@@ -245,3 +339,33 @@ pub fn get_supported_input_config(
         buffer_size: cpal::BufferSize::Default,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_buffer_size() {
+        let range = BufferSizeRange { min: 64, max: 4096 };
+
+        // Standard mode keeps the usual floor
+        assert_eq!(resolve_buffer_size(32, false, None), MIN_STANDARD_BUFFER);
+        assert_eq!(resolve_buffer_size(512, false, Some(&range)), 512);
+
+        // Low-latency mode goes lower, but never below what the device accepts
+        assert_eq!(resolve_buffer_size(32, true, None), 32);
+        assert_eq!(resolve_buffer_size(32, true, Some(&range)), 64);
+        assert_eq!(resolve_buffer_size(16, true, None), MIN_LOW_LATENCY_BUFFER);
+        assert_eq!(resolve_buffer_size(8192, true, Some(&range)), 4096);
+    }
+
+    #[test]
+    fn test_exclusive_mode_is_refused() {
+        assert!(check_stream_mode(&AudioConfig::default()).is_ok());
+        let exclusive = AudioConfig {
+            exclusive: true,
+            ..AudioConfig::default()
+        };
+        assert!(check_stream_mode(&exclusive).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! THD+N and SNR measurement
+//!
+//! A plugin is driven with a pure sine and its steady-state output is analysed
+//! with a single large FFT. The test frequency is snapped to an FFT bin so the
+//! fundamental and its harmonics land exactly on bins - with a Hann window they
+//! then only spill into the neighbouring bin, which keeps the measurement floor
+//! well below what any real distortion plugin produces.
+
+use realfft::RealFftPlanner;
+use serde::Serialize;
+
+use super::buffer::StereoSample;
+
+/// FFT length for the measurement (~0.7s at 48kHz)
+pub const ANALYSIS_SIZE: usize = 32768;
+
+/// Signal played before the analysis window so filters/compressors settle (seconds)
+const SETTLE_SECS: f32 = 0.5;
+
+/// Bins either side of a coherent tone that belong to it (Hann main lobe)
+const TONE_HALF_WIDTH: usize = 2;
+
+/// Highest harmonic reported individually
+const MAX_HARMONIC: usize = 10;
+
+/// Measurement band (Hz) - the usual audio band for THD+N
+const BAND_LOW_HZ: f32 = 20.0;
+const BAND_HIGH_HZ: f32 = 20000.0;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct HarmonicLevel {
+    /// 2 = second harmonic, ...
+    pub order: usize,
+    pub frequency: f32,
+    /// Level relative to the fundamental (dBc)
+    pub level_db: f32,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ChannelDistortion {
+    /// Fundamental level at the output (dBFS, full-scale sine = 0)
+    pub fundamental_db: f32,
+    /// Everything except the fundamental, relative to it (dB)
+    pub thd_n_db: f32,
+    pub thd_n_percent: f32,
+    /// Harmonics only, relative to the fundamental (dB)
+    pub thd_db: f32,
+    pub thd_percent: f32,
+    /// Fundamental relative to the non-harmonic residue (dB)
+    pub snr_db: f32,
+    /// Non-harmonic residue level (dBFS)
+    pub noise_floor_db: f32,
+    pub harmonics: Vec<HarmonicLevel>,
+}
+
+/// Snap a frequency to the nearest FFT bin center for coherent sampling
+pub fn coherent_frequency(frequency: f32, sample_rate: u32) -> f32 {
+    let bin_width = sample_rate as f32 / ANALYSIS_SIZE as f32;
+    (frequency / bin_width).round().max(1.0) * bin_width
+}
+
+/// Generate the stimulus: a stereo sine long enough to settle and fill one analysis window
+pub fn test_tone(frequency: f32, level_db: f32, sample_rate: u32) -> Vec<StereoSample> {
+    let amplitude = 10f64.powf(level_db as f64 / 20.0);
+    let settle = (SETTLE_SECS * sample_rate as f32) as usize;
+    let step = std::f64::consts::TAU * frequency as f64 / sample_rate as f64;
+
+    (0..settle + ANALYSIS_SIZE)
+        .map(|i| {
+            let s = (amplitude * (step * i as f64).sin()) as f32;
+            StereoSample::new(s, s)
+        })
+        .collect()
+}
+
+/// Index of the first analysed sample in a render of `test_tone`
+pub fn analysis_start(sample_rate: u32) -> usize {
+    (SETTLE_SECS * sample_rate as f32) as usize
+}
+
+fn power_to_db(power: f64) -> f32 {
+    if power > 0.0 && power.is_finite() {
+        (10.0 * power.log10()).max(-200.0) as f32
+    } else {
+        -200.0
+    }
+}
+
+/// Measure one channel of steady-state output
+/// `samples` must hold at least `ANALYSIS_SIZE` samples; `frequency` should come
+/// from `coherent_frequency`.
+pub fn measure_channel(samples: &[f32], frequency: f32, sample_rate: u32) -> Result<ChannelDistortion, String> {
+    if samples.len() < ANALYSIS_SIZE {
+        return Err("Not enough output to analyse".to_string());
+    }
+
+    let mut windowed: Vec<f32> = samples[..ANALYSIS_SIZE]
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| {
+            let w = 0.5 * (1.0 - (std::f32::consts::TAU * i as f32 / ANALYSIS_SIZE as f32).cos());
+            if s.is_finite() { s * w } else { 0.0 }
+        })
+        .collect();
+    let window_power: f64 = (0..ANALYSIS_SIZE)
+        .map(|i| {
+            let w = 0.5 * (1.0 - (std::f64::consts::TAU * i as f64 / ANALYSIS_SIZE as f64).cos());
+            w * w
+        })
+        .sum();
+
+    let fft = RealFftPlanner::<f32>::new().plan_fft_forward(ANALYSIS_SIZE);
+    let mut spectrum = fft.make_output_vec();
+    fft.process(&mut windowed, &mut spectrum)
+        .map_err(|e| format!("FFT failed: {}", e))?;
+
+    // Per-bin mean-square power, scaled so a sine of amplitude A sums to A²/2
+    let scale = 2.0 / (ANALYSIS_SIZE as f64 * window_power);
+    let power: Vec<f64> = spectrum.iter().map(|c| c.norm_sqr() as f64 * scale).collect();
+
+    let bin_width = sample_rate as f32 / ANALYSIS_SIZE as f32;
+    let nyquist_bin = power.len() - 1;
+    let low_bin = ((BAND_LOW_HZ / bin_width).ceil() as usize).max(1);
+    let high_bin = ((BAND_HIGH_HZ.min(sample_rate as f32 / 2.0) / bin_width) as usize).min(nyquist_bin);
+    let tone_power = |bin: usize| -> f64 {
+        let start = bin.saturating_sub(TONE_HALF_WIDTH).max(low_bin);
+        let end = (bin + TONE_HALF_WIDTH).min(high_bin);
+        if start > end {
+            0.0
+        } else {
+            power[start..=end].iter().sum()
+        }
+    };
+
+    let fundamental_bin = (frequency / bin_width).round() as usize;
+    if fundamental_bin < low_bin || fundamental_bin > high_bin {
+        return Err(format!("Test frequency {:.0} Hz is outside the measurement band", frequency));
+    }
+    let fundamental = tone_power(fundamental_bin);
+    if fundamental <= 1e-20 {
+        return Err("Plugin output contains no signal at the test frequency".to_string());
+    }
+
+    let total: f64 = power[low_bin..=high_bin].iter().sum();
+    let residue = (total - fundamental).max(0.0);
+
+    let mut harmonics = Vec::new();
+    let mut harmonic_total = 0.0f64;
+    for order in 2..=MAX_HARMONIC {
+        let bin = fundamental_bin * order;
+        if bin + TONE_HALF_WIDTH > high_bin {
+            break;
+        }
+        let p = tone_power(bin);
+        harmonic_total += p;
+        harmonics.push(HarmonicLevel {
+            order,
+            frequency: bin as f32 * bin_width,
+            level_db: power_to_db(p / fundamental),
+        });
+    }
+    let noise = (residue - harmonic_total).max(0.0);
+
+    Ok(ChannelDistortion {
+        fundamental_db: power_to_db(fundamental / 0.5),
+        thd_n_db: power_to_db(residue / fundamental),
+        thd_n_percent: ((residue / fundamental).sqrt() * 100.0) as f32,
+        thd_db: power_to_db(harmonic_total / fundamental),
+        thd_percent: ((harmonic_total / fundamental).sqrt() * 100.0) as f32,
+        snr_db: -power_to_db(noise / fundamental),
+        noise_floor_db: power_to_db(noise / 0.5),
+        harmonics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48000;
+
+    fn tone(frequency: f32, amplitude: f32, second_harmonic: f32) -> Vec<f32> {
+        let step = std::f64::consts::TAU * frequency as f64 / RATE as f64;
+        (0..ANALYSIS_SIZE)
+            .map(|i| {
+                let t = step * i as f64;
+                (amplitude as f64 * t.sin() + second_harmonic as f64 * (2.0 * t).sin()) as f32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pure_sine_measures_clean() {
+        let frequency = coherent_frequency(1000.0, RATE);
+        let result = measure_channel(&tone(frequency, 0.5, 0.0), frequency, RATE).unwrap();
+        assert!((result.fundamental_db - -6.02).abs() < 0.1);
+        assert!(result.thd_n_db < -100.0);
+    }
+
+    #[test]
+    fn test_second_harmonic_is_measured() {
+        let frequency = coherent_frequency(1000.0, RATE);
+        // 1% second harmonic = -40 dB
+        let result = measure_channel(&tone(frequency, 0.5, 0.005), frequency, RATE).unwrap();
+        assert!((result.thd_db - -40.0).abs() < 0.2);
+        assert!((result.thd_percent - 1.0).abs() < 0.05);
+        assert!((result.harmonics[0].level_db - -40.0).abs() < 0.2);
+        assert!(result.harmonics[1].level_db < -100.0);
+        // Nothing but harmonics, so THD+N matches THD
+        assert!((result.thd_n_db - result.thd_db).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_silence_is_an_error() {
+        let frequency = coherent_frequency(1000.0, RATE);
+        assert!(measure_channel(&vec![0.0; ANALYSIS_SIZE], frequency, RATE).is_err());
+        assert!(measure_channel(&[0.0; 16], frequency, RATE).is_err());
+    }
+}
@@ -9,15 +9,24 @@ use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
 
+use super::automation::{ParamRecorder, RecordedParamEvent};
 use super::buffer::StereoSample;
-use super::device::{get_output_device, get_supported_config, AudioConfig};
+use super::device::{get_output_device, get_supported_config, is_device_lost_error, AudioConfig};
 use super::input::{get_input_handle, start_input_capture, stop_input_capture};
+use super::limiter::{db_to_linear, SafetyLimiter, DANGEROUS_REDUCTION_DB, DEFAULT_CEILING_DB, MIN_CEILING_DB};
 use super::midi::MidiEventQueue;
-use super::plugin::{PluginInstance, PluginState};
-use super::samples::{AudioSample, SamplePlayer};
+use super::mixer::{ChannelStrip, Mixer, MixerSettings, MixerSource};
+use super::plugin::editor::EditorWindowState;
+use super::plugin::isolated::IsolatedPlugin;
+use super::plugin::{PluginInstance, PluginParamInfo, PluginState};
+use super::sample_stream::{should_stream, SampleStream};
+use super::samples::{AudioSample, PlayheadInfo, SamplePlayer, TempoMode};
 use super::signals::{GatePattern, SignalConfig, SignalGenerator};
-use super::spectrum::{SpectrumAnalyzer, NUM_BANDS};
-use super::stereo::{StereoAnalyzer, STEREO_HISTORY_SIZE};
+use super::spectrum::{
+    SpectrogramRow, SpectrumAnalyzer, SpectrumHistory, HISTORY_ROWS_PER_SEC, HISTORY_SECONDS, NUM_BANDS,
+};
+use super::stereo::{StereoAnalyzer, LISSAJOUS_SIZE, STEREO_HISTORY_SIZE};
+use super::watchdog::{DspHealth, DspHealthReport};
 
 /// Current state of the audio engine
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -190,10 +199,24 @@ struct SharedState {
     input_source: RwLock<InputSource>,
     signal_generator: RwLock<SignalGenerator>,
     sample_player: RwLock<SamplePlayer>,
+    // Multi-source input mixer (bypassed unless enabled)
+    mixer: Mixer,
+    // Watchdog counting NaNs/clicks/overloads in plugin output
+    dsp_health: DspHealth,
+    // Records parameter gestures reported by the plugin GUI
+    param_recorder: ParamRecorder,
     is_playing: AtomicBool,
     is_looping: AtomicBool,
+    // Set by the stream error callback when the output device disappears
+    device_lost: AtomicBool,
+    // Callback-to-playback delay reported by the host (0 = not reported)
+    output_latency_ns: AtomicU64,
+    // Frames delivered in the most recent callback (the buffer size actually in use)
+    callback_frames: AtomicU32,
     // Master volume (0.0 - 1.0) stored as u32 bits for lock-free access
     master_volume: AtomicU32,
+    // Safety limiter ceiling (dBFS) stored as u32 bits
+    output_ceiling_db: AtomicU32,
     // Output levels for metering - using AtomicU32 with f32 bit patterns for lock-free access
     output_level_left: AtomicU32,
     output_level_right: AtomicU32,
@@ -212,6 +235,8 @@ struct SharedState {
     spectrum_bands: [AtomicU32; NUM_BANDS],
     // Input spectrum (pre-FX) for comparison overlay
     spectrum_bands_input: [AtomicU32; NUM_BANDS],
+    // Recent spectrum frames for spectrogram export (written with try_lock from the audio thread)
+    spectrum_history: Mutex<SpectrumHistory>,
     // Waveform display buffers (separate L/R for stereo visualization)
     waveform_buffer_left: [AtomicU32; WAVEFORM_SAMPLES],
     waveform_buffer_right: [AtomicU32; WAVEFORM_SAMPLES],
@@ -232,8 +257,17 @@ struct SharedState {
     // INPUT stereo (pre-FX for comparison)
     stereo_positions_input: [AtomicU32; STEREO_HISTORY_SIZE * 2],
     stereo_correlation_input: AtomicU32,
+    // Mid/side RMS levels (post-FX and pre-FX)
+    stereo_mid_level: AtomicU32,
+    stereo_side_level: AtomicU32,
+    stereo_mid_level_input: AtomicU32,
+    stereo_side_level_input: AtomicU32,
+    // Raw L/R pairs for the goniometer - flat array [l0, r0, l1, r1, ...] (post-FX)
+    lissajous_points: [AtomicU32; LISSAJOUS_SIZE * 2],
     // Plugin hosting
     plugin_instance: RwLock<Option<PluginInstance>>,
+    // Plugin running in a helper process (process isolation mode) - audio only
+    isolated_plugin: RwLock<Option<IsolatedPlugin>>,
     plugin_state: RwLock<PluginState>,
     // MIDI queue reference (separate from plugin lock for lock-free MIDI access)
     // Updated when plugin is loaded/unloaded
@@ -243,9 +277,9 @@ struct SharedState {
     // Crossfade for hot reload
     crossfade_state: AtomicU8,
     crossfade_position: AtomicU32,
-    // Plugin editor window position (persists across plugin reload for hot reload)
+    // Plugin editor window state (persists across plugin reload for hot reload)
     // This is stored at engine level so it survives plugin unload/reload cycles
-    last_editor_position: RwLock<Option<(f64, f64)>>,
+    editor_window_state: RwLock<EditorWindowState>,
     // Performance monitoring (disabled by default for zero overhead)
     // When enabled, clap_host measures plugin.process() call duration
     perf_monitoring_enabled: AtomicBool,
@@ -267,11 +301,115 @@ fn u32_to_f32(u: u32) -> f32 {
     f32::from_bits(u)
 }
 
+/// Render one input source into an interleaved buffer (called from the audio thread)
+fn render_source(shared: &SharedState, source: MixerSource, data: &mut [f32], channels: usize) {
+    match source {
+        MixerSource::Signal => {
+            let mut generator = shared.signal_generator.write();
+            for chunk in data.chunks_mut(channels) {
+                let sample = generator.next_sample();
+                chunk[0] = sample.left;
+                if channels > 1 {
+                    chunk[1] = sample.right;
+                }
+            }
+        }
+        MixerSource::Sample => {
+            let mut player = shared.sample_player.write();
+            for chunk in data.chunks_mut(channels) {
+                let sample = player.next_sample();
+                chunk[0] = sample.left;
+                if channels > 1 {
+                    chunk[1] = sample.right;
+                }
+            }
+        }
+        MixerSource::Live => {
+            // Check if paused - if so, output silence
+            let is_paused = shared.live_paused.load(Ordering::SeqCst);
+            if is_paused {
+                for sample in data.iter_mut() {
+                    *sample = 0.0;
+                }
+            } else if let Some(input_handle) = crate::audio::input::get_input_handle() {
+                let mut peak_left = 0.0f32;
+                let mut peak_right = 0.0f32;
+
+                // Check if we need to resample
+                let mut resampler_guard = shared.live_resampler.lock();
+
+                if let Some(ref mut resampler) = *resampler_guard {
+                    // Resampling mode: read input samples, resample, then output
+                    let frames_needed = data.len() / channels;
+
+                    // Read enough input samples and feed to resampler
+                    // We may need to read more samples than output frames due to rate difference
+                    let available = input_handle.available_samples();
+                    for _ in 0..available.min(frames_needed * 2) {
+                        let sample = input_handle.read_sample();
+                        resampler.push_input(sample.left, sample.right);
+                        // Track input levels from raw input
+                        peak_left = peak_left.max(sample.left.abs());
+                        peak_right = peak_right.max(sample.right.abs());
+                    }
+
+                    // Process resampler to generate output
+                    while resampler.available_output() < frames_needed {
+                        if !resampler.process() {
+                            break; // Not enough input yet
+                        }
+                    }
+
+                    // Read resampled output
+                    for chunk in data.chunks_mut(channels) {
+                        if let Some(sample) = resampler.pop_output() {
+                            chunk[0] = sample.left;
+                            if channels > 1 {
+                                chunk[1] = sample.right;
+                            }
+                        } else {
+                            // No resampled data available yet, output silence
+                            chunk[0] = 0.0;
+                            if channels > 1 {
+                                chunk[1] = 0.0;
+                            }
+                        }
+                    }
+                } else {
+                    // No resampling needed - direct passthrough
+                    for chunk in data.chunks_mut(channels) {
+                        let sample = input_handle.read_sample();
+                        chunk[0] = sample.left;
+                        if channels > 1 {
+                            chunk[1] = sample.right;
+                        }
+                        // Track input levels
+                        peak_left = peak_left.max(sample.left.abs());
+                        peak_right = peak_right.max(sample.right.abs());
+                    }
+                }
+
+                drop(resampler_guard); // Release lock
+                // Input levels are captured universally after input_buffer copy
+            } else {
+                // No input handle available, output silence
+                for sample in data.iter_mut() {
+                    *sample = 0.0;
+                }
+            }
+        }
+    }
+}
+
 /// Handle to control the audio engine from other threads
 #[derive(Clone)]
 pub struct AudioEngineHandle {
     shared: Arc<SharedState>,
     sample_rate: u32,
+    /// Output device the stream was opened on
+    device_name: String,
+    /// Settings the engine was created with (used to rebuild it on device loss)
+    config: AudioConfig,
 }
 
 impl AudioEngineHandle {
@@ -296,16 +434,50 @@ impl AudioEngineHandle {
         self.shared.sample_player.write().pause();
     }
 
+    /// Name of the output device this engine is running on
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    /// Current buffer and latency figures for the running stream
+    pub fn latency_report(&self) -> LatencyReport {
+        let frames = match self.shared.callback_frames.load(Ordering::Relaxed) {
+            0 => self.config.buffer_size,
+            frames => frames,
+        };
+        estimate_latency(
+            self.sample_rate,
+            frames,
+            self.shared.output_latency_ns.load(Ordering::Relaxed),
+            self.config.low_latency,
+        )
+    }
+
+    /// Whether the output stream reported that its device went away
+    pub fn device_lost(&self) -> bool {
+        self.shared.device_lost.load(Ordering::SeqCst)
+    }
+
     pub fn is_playing(&self) -> bool {
         self.shared.is_playing.load(Ordering::SeqCst)
     }
 
+    pub fn get_input_source(&self) -> InputSource {
+        self.shared.input_source.read().clone()
+    }
+
     pub fn set_input_source(&self, source: InputSource) {
         log::info!("AudioEngine: set_input_source called with {:?}", source);
 
         // Stop any existing live input capture when switching away from Live
         let current_source = self.shared.input_source.read().clone();
-        if matches!(current_source, InputSource::Live { .. }) && !matches!(source, InputSource::Live { .. }) {
+        // (unless the mixer is still using it)
+        let mixer_uses_live = self.shared.mixer.is_enabled()
+            && self.shared.mixer.channel(MixerSource::Live).enabled;
+        if matches!(current_source, InputSource::Live { .. })
+            && !matches!(source, InputSource::Live { .. })
+            && !mixer_uses_live
+        {
             log::info!("AudioEngine: Stopping live input capture");
             stop_input_capture();
             // Clear resampler
@@ -330,53 +502,7 @@ impl AudioEngineHandle {
                 }
             }
             InputSource::Live { device, chunk_size } => {
-                log::info!("AudioEngine: Starting live input capture, device: {:?}, chunk_size: {:?}", device, chunk_size);
-                // Start the input capture using the device's native sample rate
-                // This avoids CoreAudio conflicts - resampling is done in the engine if needed
-                match start_input_capture(device.as_deref()) {
-                    Ok(handle) => {
-                        // Clear buffer to avoid stale data
-                        handle.clear_buffer();
-                        // Reset paused state
-                        self.shared.live_paused.store(false, Ordering::SeqCst);
-
-                        // Check if input device's native rate differs from output rate
-                        // We always use the input's native rate to avoid CoreAudio conflicts
-                        let input_rate = handle.sample_rate();
-                        log::info!("AudioEngine: Input device native sample rate: {} Hz, output: {} Hz", input_rate, self.sample_rate);
-
-                        if input_rate != self.sample_rate {
-                            // Use provided chunk size or default to 256 (good balance of latency vs efficiency)
-                            // Smaller values (64, 128) = lower latency but more CPU
-                            // Larger values (512, 1024) = higher latency but more efficient
-                            let resampler_chunk_size = chunk_size.unwrap_or(256);
-                            log::info!(
-                                "AudioEngine: Sample rate mismatch detected. Input: {} Hz, Output: {} Hz. Creating resampler with chunk size {}.",
-                                input_rate, self.sample_rate, resampler_chunk_size
-                            );
-                            match LiveInputResampler::new(input_rate, self.sample_rate, resampler_chunk_size) {
-                                Ok(resampler) => {
-                                    *self.shared.live_resampler.lock() = Some(resampler);
-                                    log::info!("AudioEngine: Resampler created successfully");
-                                }
-                                Err(e) => {
-                                    log::error!("AudioEngine: Failed to create resampler: {}. Audio will be distorted!", e);
-                                    *self.shared.live_resampler.lock() = None;
-                                }
-                            }
-                        } else {
-                            // Same sample rate - no resampling needed
-                            log::info!("AudioEngine: Sample rates match ({}Hz), no resampling needed", input_rate);
-                            *self.shared.live_resampler.lock() = None;
-                        }
-
-                        log::info!("AudioEngine: Live input capture started successfully");
-                    }
-                    Err(e) => {
-                        log::error!("AudioEngine: Failed to start live input capture: {}", e);
-                        *self.shared.live_resampler.lock() = None;
-                    }
-                }
+                self.start_live_input(device.as_deref(), *chunk_size);
             }
             InputSource::None => {
                 log::info!("AudioEngine: Setting input source to None");
@@ -386,6 +512,96 @@ impl AudioEngineHandle {
         *self.shared.input_source.write() = source;
     }
 
+    /// Start live input capture, creating a resampler if the device rate differs
+    fn start_live_input(&self, device: Option<&str>, chunk_size: Option<usize>) {
+        log::info!("AudioEngine: Starting live input capture, device: {:?}, chunk_size: {:?}", device, chunk_size);
+        // Start the input capture using the device's native sample rate
+        // This avoids CoreAudio conflicts - resampling is done in the engine if needed
+        match start_input_capture(device) {
+            Ok(handle) => {
+                // Clear buffer to avoid stale data
+                handle.clear_buffer();
+                // Reset paused state
+                self.shared.live_paused.store(false, Ordering::SeqCst);
+
+                // Check if input device's native rate differs from output rate
+                // We always use the input's native rate to avoid CoreAudio conflicts
+                let input_rate = handle.sample_rate();
+                log::info!("AudioEngine: Input device native sample rate: {} Hz, output: {} Hz", input_rate, self.sample_rate);
+
+                if input_rate != self.sample_rate {
+                    // Use provided chunk size or default to 256 (good balance of latency vs efficiency)
+                    // Smaller values (64, 128) = lower latency but more CPU
+                    // Larger values (512, 1024) = higher latency but more efficient
+                    let resampler_chunk_size = chunk_size.unwrap_or(256);
+                    log::info!(
+                        "AudioEngine: Sample rate mismatch detected. Input: {} Hz, Output: {} Hz. Creating resampler with chunk size {}.",
+                        input_rate, self.sample_rate, resampler_chunk_size
+                    );
+                    match LiveInputResampler::new(input_rate, self.sample_rate, resampler_chunk_size) {
+                        Ok(resampler) => {
+                            *self.shared.live_resampler.lock() = Some(resampler);
+                            log::info!("AudioEngine: Resampler created successfully");
+                        }
+                        Err(e) => {
+                            log::error!("AudioEngine: Failed to create resampler: {}. Audio will be distorted!", e);
+                            *self.shared.live_resampler.lock() = None;
+                        }
+                    }
+                } else {
+                    // Same sample rate - no resampling needed
+                    log::info!("AudioEngine: Sample rates match ({}Hz), no resampling needed", input_rate);
+                    *self.shared.live_resampler.lock() = None;
+                }
+
+                log::info!("AudioEngine: Live input capture started successfully");
+            }
+            Err(e) => {
+                log::error!("AudioEngine: Failed to start live input capture: {}", e);
+                *self.shared.live_resampler.lock() = None;
+            }
+        }
+    }
+
+    /// Current mixer configuration
+    pub fn get_mixer(&self) -> MixerSettings {
+        self.shared.mixer.settings()
+    }
+
+    /// Turn multi-source mixing on or off
+    pub fn set_mixer_enabled(&self, enabled: bool) {
+        self.shared.mixer.set_enabled(enabled);
+        self.sync_mixer_live_input();
+    }
+
+    /// Update one mixer channel
+    pub fn set_mixer_channel(&self, source: MixerSource, strip: ChannelStrip) {
+        self.shared.mixer.set_channel(source, strip);
+        if source == MixerSource::Live {
+            self.sync_mixer_live_input();
+        }
+    }
+
+    pub fn set_mixer_master_gain(&self, gain_db: f32) {
+        self.shared.mixer.set_master_gain_db(gain_db);
+    }
+
+    /// Start or stop live capture to match the mixer's live channel
+    fn sync_mixer_live_input(&self) {
+        let wants_live = self.shared.mixer.is_enabled()
+            && self.shared.mixer.channel(MixerSource::Live).enabled;
+        let source_is_live = matches!(*self.shared.input_source.read(), InputSource::Live { .. });
+
+        if wants_live && get_input_handle().is_none() {
+            log::info!("AudioEngine: Starting live input for mixer");
+            self.start_live_input(None, None);
+        } else if !wants_live && !source_is_live && get_input_handle().is_some() {
+            log::info!("AudioEngine: Stopping live input (no longer mixed)");
+            stop_input_capture();
+            *self.shared.live_resampler.lock() = None;
+        }
+    }
+
     pub fn set_frequency(&self, frequency: f32) {
         self.shared.signal_generator.write().set_frequency(frequency);
     }
@@ -415,7 +631,19 @@ impl AudioEngineHandle {
         let path_ref = path.as_ref();
         log::info!("Loading sample from: {:?}", path_ref);
 
-        let sample = AudioSample::load(path_ref)?;
+        // Very long files are decoded on the fly instead of held in memory
+        if should_stream(path_ref) {
+            let looping = self.shared.is_looping.load(Ordering::SeqCst);
+            let stream = SampleStream::open(path_ref, looping)?;
+            let speed_ratio = stream.info.sample_rate as f32 / self.sample_rate as f32;
+
+            let mut player = self.shared.sample_player.write();
+            player.load_stream(stream);
+            player.set_speed_ratio(speed_ratio);
+            return Ok(());
+        }
+
+        let mut sample = AudioSample::load(path_ref)?;
         log::info!(
             "Sample loaded: {} samples, {} Hz, {} channels, {:.2}s",
             sample.info.num_samples,
@@ -424,6 +652,16 @@ impl AudioEngineHandle {
             sample.info.duration_secs
         );
 
+        // Convert to the engine rate up front; fall back to playback-time
+        // interpolation via the speed ratio if that fails
+        if sample.info.sample_rate != self.sample_rate {
+            let source_rate = sample.info.sample_rate;
+            match sample.resample_to(self.sample_rate) {
+                Ok(()) => log::info!("Resampled sample {}Hz -> {}Hz", source_rate, self.sample_rate),
+                Err(e) => log::warn!("Sample resampling failed, using interpolation: {}", e),
+            }
+        }
+
         // Calculate speed ratio for resampling if needed
         let speed_ratio = sample.info.sample_rate as f32 / self.sample_rate as f32;
         log::info!("Speed ratio: {} (sample {}Hz -> engine {}Hz)", speed_ratio, sample.info.sample_rate, self.sample_rate);
@@ -435,6 +673,37 @@ impl AudioEngineHandle {
         Ok(())
     }
 
+    /// Seek sample playback to a position (seconds)
+    pub fn seek_sample(&self, position_secs: f32) {
+        self.shared.sample_player.write().seek(position_secs);
+    }
+
+    /// Set the sample loop region (seconds)
+    pub fn set_sample_loop_region(&self, start_secs: f32, end_secs: f32) -> Result<(), String> {
+        self.shared.sample_player.write().set_loop_region(start_secs, end_secs)
+    }
+
+    /// Clear the sample loop region
+    pub fn clear_sample_loop_region(&self) {
+        self.shared.sample_player.write().clear_loop_region();
+    }
+
+    /// Set sample playback tempo and whether pitch is preserved
+    pub fn set_sample_tempo(&self, tempo: f32, mode: TempoMode) {
+        self.shared.sample_player.write().set_tempo(tempo, mode);
+    }
+
+    /// Current sample playback tempo and mode
+    pub fn get_sample_tempo(&self) -> (f32, TempoMode) {
+        self.shared.sample_player.read().tempo()
+    }
+
+    /// Get the sample playhead position
+    /// Uses try_read so UI polling never stalls the audio thread's writer
+    pub fn get_sample_playhead(&self) -> Option<PlayheadInfo> {
+        self.shared.sample_player.try_read()?.playhead()
+    }
+
     pub fn get_output_levels(&self) -> (f32, f32) {
         let left = u32_to_f32(self.shared.output_level_left.load(Ordering::Relaxed));
         let right = u32_to_f32(self.shared.output_level_right.load(Ordering::Relaxed));
@@ -474,6 +743,21 @@ impl AudioEngineHandle {
         self.shared.master_volume.store(f32_to_u32(clamped), Ordering::SeqCst);
     }
 
+    /// Set the safety limiter ceiling (dBFS, clamped to -24..0)
+    pub fn set_output_ceiling_db(&self, ceiling_db: f32) {
+        let clamped = if ceiling_db.is_finite() {
+            ceiling_db.clamp(MIN_CEILING_DB, 0.0)
+        } else {
+            DEFAULT_CEILING_DB
+        };
+        self.shared.output_ceiling_db.store(f32_to_u32(clamped), Ordering::Relaxed);
+    }
+
+    /// Get the safety limiter ceiling (dBFS)
+    pub fn get_output_ceiling_db(&self) -> f32 {
+        u32_to_f32(self.shared.output_ceiling_db.load(Ordering::Relaxed))
+    }
+
     /// Get master volume (0.0 - 1.0)
     pub fn get_master_volume(&self) -> f32 {
         u32_to_f32(self.shared.master_volume.load(Ordering::SeqCst))
@@ -497,6 +781,23 @@ impl AudioEngineHandle {
         bands
     }
 
+    /// Get the last `seconds` of spectrum history as (input, output) rows, oldest first
+    pub fn get_spectrum_history(&self, seconds: f32) -> (Vec<[f32; NUM_BANDS]>, Vec<[f32; NUM_BANDS]>) {
+        let count = (seconds.max(0.0) * HISTORY_ROWS_PER_SEC as f32).round() as usize;
+        self.shared
+            .spectrum_history
+            .lock()
+            .latest(count)
+            .into_iter()
+            .map(|row| (row.input, row.output))
+            .unzip()
+    }
+
+    /// Forget recorded spectrum history (e.g. before capturing a fresh "after" image)
+    pub fn clear_spectrum_history(&self) {
+        self.shared.spectrum_history.lock().clear();
+    }
+
     /// Get and clear clipping indicators (returns true if clipping occurred since last check)
     pub fn get_clipping(&self) -> (bool, bool) {
         let left = self.shared.clipping_left.swap(false, Ordering::Relaxed);
@@ -548,6 +849,34 @@ impl AudioEngineHandle {
         u32_to_f32(self.shared.stereo_correlation.load(Ordering::Relaxed))
     }
 
+    /// Get (mid, side) RMS levels of the output (linear, 0.0 - 1.0)
+    pub fn get_mid_side_levels(&self) -> (f32, f32) {
+        (
+            u32_to_f32(self.shared.stereo_mid_level.load(Ordering::Relaxed)),
+            u32_to_f32(self.shared.stereo_side_level.load(Ordering::Relaxed)),
+        )
+    }
+
+    /// Get (mid, side) RMS levels of the INPUT (pre-FX)
+    pub fn get_mid_side_levels_input(&self) -> (f32, f32) {
+        (
+            u32_to_f32(self.shared.stereo_mid_level_input.load(Ordering::Relaxed)),
+            u32_to_f32(self.shared.stereo_side_level_input.load(Ordering::Relaxed)),
+        )
+    }
+
+    /// Get raw [left, right] sample pairs for the goniometer, oldest to newest
+    pub fn get_lissajous_points(&self) -> Vec<[f32; 2]> {
+        (0..LISSAJOUS_SIZE)
+            .map(|i| {
+                [
+                    u32_to_f32(self.shared.lissajous_points[i * 2].load(Ordering::Relaxed)),
+                    u32_to_f32(self.shared.lissajous_points[i * 2 + 1].load(Ordering::Relaxed)),
+                ]
+            })
+            .collect()
+    }
+
     /// Get INPUT (pre-FX) waveform display buffers for comparison
     /// Returns (left_channel, right_channel) vectors
     pub fn get_waveform_input_data(&self) -> (Vec<f32>, Vec<f32>) {
@@ -646,6 +975,38 @@ impl AudioEngineHandle {
         }
     }
 
+    /// Load a plugin into a helper process instead of the app, so a crash or
+    /// memory corruption in it can't take the app down (see `plugin::isolated`)
+    /// Audio only - no editor, parameters, state, or MIDI while isolated.
+    pub fn load_plugin_isolated(&self, path: &Path) -> Result<(), String> {
+        log::info!("Loading plugin out of process from: {:?}", path);
+
+        *self.shared.plugin_state.write() = PluginState::Loading {
+            path: path.display().to_string(),
+        };
+        self.unload_plugin();
+
+        match IsolatedPlugin::spawn(path, self.sample_rate as f64, 4096) {
+            Ok(plugin) => {
+                let name = plugin.name.clone();
+                *self.shared.isolated_plugin.write() = Some(plugin);
+                *self.shared.plugin_state.write() = PluginState::Active {
+                    name: name.clone(),
+                    path: path.display().to_string(),
+                    has_editor: false,
+                };
+                log::info!("Plugin loaded out of process: {}", name);
+                Ok(())
+            }
+            Err(e) => {
+                *self.shared.plugin_state.write() = PluginState::Error {
+                    message: e.clone(),
+                };
+                Err(e)
+            }
+        }
+    }
+
     /// Unload the current plugin
     pub fn unload_plugin(&self) {
         // Clear MIDI queue reference first (allows immediate MIDI rejection)
@@ -660,6 +1021,14 @@ impl AudioEngineHandle {
             plugin.stop_processing();
             log::info!("Plugin unloaded");
         }
+        drop(plugin_lock);
+
+        // Take it out of the lock first - stopping the helper can take a moment
+        let isolated = self.shared.isolated_plugin.write().take();
+        if let Some(plugin) = isolated {
+            drop(plugin);
+            log::info!("Isolated plugin unloaded");
+        }
         *self.shared.plugin_state.write() = PluginState::Unloaded;
     }
 
@@ -670,18 +1039,27 @@ impl AudioEngineHandle {
 
     /// Check if a plugin is loaded
     pub fn has_plugin(&self) -> bool {
-        self.shared.plugin_instance.read().is_some()
+        self.shared.plugin_instance.read().is_some() || self.shared.isolated_plugin.read().is_some()
     }
 
     /// Check if the loaded plugin has crashed during audio processing
     /// Returns false if no plugin is loaded
     pub fn plugin_has_crashed(&self) -> bool {
-        self.shared
+        let in_process = self
+            .shared
             .plugin_instance
             .read()
             .as_ref()
             .map(|p| p.has_crashed())
-            .unwrap_or(false)
+            .unwrap_or(false);
+        in_process
+            || self
+                .shared
+                .isolated_plugin
+                .read()
+                .as_ref()
+                .map(|p| p.has_crashed())
+                .unwrap_or(false)
     }
 
     /// Check if the loaded plugin has a GUI
@@ -696,61 +1074,113 @@ impl AudioEngineHandle {
 
     /// Open the plugin's editor window
     ///
-    /// Uses stored position if available, otherwise centers the window.
+    /// Uses stored window state if available, otherwise centers the window.
     pub fn open_plugin_editor(&self) -> Result<(), String> {
         log::info!("AudioEngineHandle::open_plugin_editor called");
 
-        // First, check if user manually closed the window (clicked X) and save position
+        // First, check if user manually closed the window (clicked X) and save its geometry
         // This handles the edge case where window was closed without calling close_plugin_editor
         {
             let plugin_lock = self.shared.plugin_instance.read();
             if let Some(plugin) = plugin_lock.as_ref() {
                 // If editor is marked as open but window is not visible, user closed it manually
                 if plugin.is_editor_open() && !plugin.is_editor_window_visible() {
-                    if let Some(position) = plugin.get_editor_position() {
-                        log::info!("open_plugin_editor: saving position from manually closed window {:?}", position);
-                        *self.shared.last_editor_position.write() = Some(position);
-                    }
+                    log::info!("open_plugin_editor: saving geometry from manually closed window");
+                    self.save_editor_geometry(plugin);
                 }
             }
         }
 
-        // Get stored position from engine level (survives plugin reload)
-        let position = self.shared.last_editor_position.read().clone();
-        log::info!("AudioEngineHandle::open_plugin_editor: position={:?}", position);
+        // Get stored window state from engine level (survives plugin reload)
+        let state = *self.shared.editor_window_state.read();
+        log::info!("AudioEngineHandle::open_plugin_editor: state={:?}", state);
 
         let mut plugin_lock = self.shared.plugin_instance.write();
         log::info!("AudioEngineHandle::open_plugin_editor: got plugin lock");
         if let Some(plugin) = plugin_lock.as_mut() {
             log::info!("AudioEngineHandle::open_plugin_editor: calling plugin.open_editor_at()");
-            plugin.open_editor_at(position)
+            plugin.open_editor_at(&state)
         } else {
             log::warn!("AudioEngineHandle::open_plugin_editor: no plugin loaded");
             Err("No plugin loaded".to_string())
         }
     }
 
-    /// Close the plugin's editor window and save its position
+    /// Show the open editor GUI at a scale factor, for checking it at other pixel densities
+    /// The scale isn't saved - the editor reopens at 100%.
+    pub fn set_plugin_editor_scale(&self, scale: f64) -> Result<(u32, u32), String> {
+        let mut plugin_lock = self.shared.plugin_instance.write();
+        match plugin_lock.as_mut() {
+            Some(plugin) => plugin.set_editor_scale(scale),
+            None => Err("No plugin loaded".to_string()),
+        }
+    }
+
+    /// Close the plugin's editor window and save its geometry
     pub fn close_plugin_editor(&self) {
         let mut plugin_lock = self.shared.plugin_instance.write();
         if let Some(plugin) = plugin_lock.as_mut() {
-            // Save window position before closing (survives plugin reload)
-            if let Some(position) = plugin.get_editor_position() {
-                log::info!("AudioEngineHandle::close_plugin_editor: saving position {:?}", position);
-                *self.shared.last_editor_position.write() = Some(position);
-            }
+            // Save window geometry before closing (survives plugin reload)
+            self.save_editor_geometry(plugin);
             plugin.close_editor();
         }
     }
 
+    /// Copy the open editor window's position and size into the stored window state
+    fn save_editor_geometry(&self, plugin: &PluginInstance) {
+        let position = plugin.get_editor_position();
+        let size = plugin.get_editor_size();
+        let mut state = self.shared.editor_window_state.write();
+        if position.is_some() {
+            log::info!("AudioEngineHandle: saving editor position {:?}", position);
+            state.position = position;
+        }
+        if size.is_some() {
+            state.size = size;
+        }
+    }
+
     /// Get the stored editor window position
     pub fn get_editor_position(&self) -> Option<(f64, f64)> {
-        self.shared.last_editor_position.read().clone()
+        self.shared.editor_window_state.read().position
     }
 
     /// Set the stored editor window position
     pub fn set_editor_position(&self, position: Option<(f64, f64)>) {
-        *self.shared.last_editor_position.write() = position;
+        self.shared.editor_window_state.write().position = position;
+    }
+
+    /// Get the stored editor window state, including the open window's current geometry
+    pub fn get_editor_window_state(&self) -> EditorWindowState {
+        if let Some(plugin) = self.shared.plugin_instance.read().as_ref() {
+            if plugin.is_editor_open() {
+                self.save_editor_geometry(plugin);
+            }
+        }
+        *self.shared.editor_window_state.read()
+    }
+
+    /// Replace the stored editor window state (applied the next time the editor opens)
+    pub fn set_editor_window_state(&self, state: EditorWindowState) {
+        *self.shared.editor_window_state.write() = state;
+    }
+
+    /// Set whether the editor window stays above other windows
+    /// Applies to the open window immediately and is remembered for later opens
+    pub fn set_editor_always_on_top(&self, always_on_top: bool) {
+        self.shared.editor_window_state.write().always_on_top = always_on_top;
+        if let Some(plugin) = self.shared.plugin_instance.read().as_ref() {
+            plugin.set_editor_always_on_top(always_on_top);
+        }
+    }
+
+    /// Set whether the editor window sends every key to the plugin, host shortcuts included
+    /// Applies to the open window immediately and is remembered for later opens
+    pub fn set_editor_plugin_gets_all_keys(&self, all_keys: bool) {
+        self.shared.editor_window_state.write().plugin_gets_all_keys = all_keys;
+        if let Some(plugin) = self.shared.plugin_instance.read().as_ref() {
+            plugin.set_editor_plugin_gets_all_keys(all_keys);
+        }
     }
 
     /// Check if the plugin editor is open AND visible
@@ -829,6 +1259,124 @@ impl AudioEngineHandle {
         self.shared.is_instrument_plugin.store(is_instrument, Ordering::SeqCst);
     }
 
+    /// Check whether the loaded plugin is flagged as an instrument
+    pub fn is_instrument(&self) -> bool {
+        self.shared.is_instrument_plugin.load(Ordering::SeqCst)
+    }
+
+    /// Get the loaded plugin's CLAP ID
+    pub fn plugin_id(&self) -> Option<String> {
+        self.shared
+            .plugin_instance
+            .read()
+            .as_ref()
+            .map(|p| p.plugin_id.clone())
+    }
+
+    /// Get the loaded plugin's (name, vendor)
+    pub fn plugin_descriptor(&self) -> Option<(String, String)> {
+        self.shared
+            .plugin_instance
+            .read()
+            .as_ref()
+            .map(|p| (p.name.clone(), p.vendor.clone()))
+    }
+
+    /// Snapshot the loaded plugin's state via the CLAP state extension
+    pub fn save_plugin_state(&self) -> Result<Vec<u8>, String> {
+        self.shared
+            .plugin_instance
+            .read()
+            .as_ref()
+            .ok_or_else(|| "No plugin loaded".to_string())?
+            .save_state()
+    }
+
+    /// Restore the loaded plugin's state via the CLAP state extension
+    pub fn load_plugin_state(&self, data: &[u8]) -> Result<(), String> {
+        self.shared
+            .plugin_instance
+            .write()
+            .as_mut()
+            .ok_or_else(|| "No plugin loaded".to_string())?
+            .load_state(data)
+    }
+
+    /// List the loaded plugin's parameters
+    pub fn list_plugin_params(&self) -> Vec<PluginParamInfo> {
+        self.shared
+            .plugin_instance
+            .read()
+            .as_ref()
+            .map(|p| p.list_params())
+            .unwrap_or_default()
+    }
+
+    /// Current values for the given parameter IDs (missing ones are skipped)
+    pub fn get_plugin_param_values(&self, ids: &[u32]) -> Vec<(u32, f64)> {
+        let guard = self.shared.plugin_instance.read();
+        match guard.as_ref() {
+            Some(plugin) => ids
+                .iter()
+                .filter_map(|&id| plugin.get_param_value(id).map(|v| (id, v)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Format a parameter value the way the plugin displays it (e.g. "-6.0 dB")
+    pub fn format_plugin_param_value(&self, param_id: u32, value: f64) -> Option<String> {
+        self.shared
+            .plugin_instance
+            .read()
+            .as_ref()?
+            .param_value_to_text(param_id, value)
+    }
+
+    /// Queue a parameter change (applied on the next audio callback)
+    pub fn set_plugin_param(&self, param_id: u32, value: f64) -> Result<(), String> {
+        self.shared
+            .plugin_instance
+            .read()
+            .as_ref()
+            .ok_or_else(|| "No plugin loaded".to_string())?
+            .queue_param_value(param_id, value);
+        Ok(())
+    }
+
+    /// Cumulative DSP health counters for plugin output
+    pub fn dsp_health(&self) -> DspHealthReport {
+        self.shared.dsp_health.snapshot()
+    }
+
+    /// Start recording parameter gestures from the plugin GUI
+    pub fn start_param_recording(&self) -> Result<(), String> {
+        if self.shared.plugin_instance.read().is_none() {
+            return Err("No plugin loaded".to_string());
+        }
+        self.shared.param_recorder.start();
+        Ok(())
+    }
+
+    /// Stop recording; returns the events and recording length in samples
+    pub fn stop_param_recording(&self) -> (Vec<RecordedParamEvent>, u64) {
+        self.shared.param_recorder.stop()
+    }
+
+    pub fn is_param_recording(&self) -> bool {
+        self.shared.param_recorder.is_recording()
+    }
+
+    /// Name of the loaded plugin
+    pub fn plugin_name(&self) -> Option<String> {
+        self.shared.plugin_instance.read().as_ref().map(|p| p.name.clone())
+    }
+
+    /// Loaded plugin binary and its load address (for debugger attach)
+    pub fn plugin_module_info(&self) -> Option<(std::path::PathBuf, Option<usize>)> {
+        self.shared.plugin_instance.read().as_ref().map(|p| p.module_info())
+    }
+
     /// Get the current plugin's MIDI queue (for pattern player)
     /// Uses the separate midi_queue reference to avoid plugin lock
     pub fn get_plugin_midi_queue(&self) -> Option<Arc<MidiEventQueue>> {
@@ -922,6 +1470,47 @@ impl AudioEngineHandle {
     }
 }
 
+/// Buffer and latency figures for the running output stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyReport {
+    pub sample_rate: u32,
+    /// Frames per callback actually delivered by the device
+    pub buffer_size: u32,
+    /// Duration of one buffer in milliseconds
+    pub buffer_ms: f64,
+    /// Callback-to-speaker delay reported by the host, if any
+    pub output_latency_ms: Option<f64>,
+    /// Estimated input-to-output latency for live input through a plugin
+    /// The input path isn't timestamped, so it's assumed to mirror the output path.
+    pub round_trip_ms: f64,
+    pub low_latency: bool,
+    /// Whether the stream has exclusive (hog mode / WASAPI exclusive) device access
+    /// cpal only opens shared-mode streams, so this is currently always false.
+    pub exclusive: bool,
+}
+
+/// Build a latency report from the stream's buffer size and reported output delay
+fn estimate_latency(sample_rate: u32, frames: u32, output_latency_ns: u64, low_latency: bool) -> LatencyReport {
+    let buffer_ms = if sample_rate > 0 {
+        frames as f64 * 1000.0 / sample_rate as f64
+    } else {
+        0.0
+    };
+    let output_latency_ms = (output_latency_ns > 0).then(|| output_latency_ns as f64 / 1_000_000.0);
+    // Hosts that don't report a delay still cost at least one buffer each way
+    let one_way_ms = output_latency_ms.unwrap_or(0.0).max(buffer_ms);
+
+    LatencyReport {
+        sample_rate,
+        buffer_size: frames,
+        buffer_ms,
+        output_latency_ms,
+        round_trip_ms: one_way_ms * 2.0,
+        low_latency,
+        exclusive: false,
+    }
+}
+
 /// Plugin performance metrics (only populated when monitoring is enabled)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginPerformance {
@@ -951,6 +1540,7 @@ impl AudioEngine {
     pub fn new(device_name: Option<&str>, config: AudioConfig) -> Result<Self, String> {
         let device = get_output_device(device_name)?;
         let stream_config = get_supported_config(&device, &config)?;
+        let resolved_device_name = device.name().unwrap_or_else(|_| "Unknown device".to_string());
 
         let sample_rate = stream_config.sample_rate.0;
         let channels = stream_config.channels as usize;
@@ -971,9 +1561,16 @@ impl AudioEngine {
             input_source: RwLock::new(InputSource::None),
             signal_generator: RwLock::new(SignalGenerator::new(sample_rate)),
             sample_player: RwLock::new(SamplePlayer::new()),
+            mixer: Mixer::new(),
+            dsp_health: DspHealth::new(),
+            param_recorder: ParamRecorder::new(),
             is_playing: AtomicBool::new(false),
             is_looping: AtomicBool::new(true),
+            device_lost: AtomicBool::new(false),
+            output_latency_ns: AtomicU64::new(0),
+            callback_frames: AtomicU32::new(0),
             master_volume: AtomicU32::new(f32_to_u32(0.75)), // Default 75% volume
+            output_ceiling_db: AtomicU32::new(f32_to_u32(DEFAULT_CEILING_DB)),
             output_level_left: AtomicU32::new(f32_to_u32(0.0)),
             output_level_right: AtomicU32::new(f32_to_u32(0.0)),
             input_level_left: AtomicU32::new(f32_to_u32(0.0)),
@@ -984,6 +1581,9 @@ impl AudioEngine {
             clipping_right: AtomicBool::new(false),
             spectrum_bands: [INIT_BAND; NUM_BANDS],
             spectrum_bands_input: [INIT_BAND; NUM_BANDS],
+            spectrum_history: Mutex::new(SpectrumHistory::new(
+                (HISTORY_ROWS_PER_SEC * HISTORY_SECONDS) as usize,
+            )),
             waveform_buffer_left: [INIT_WAVEFORM; WAVEFORM_SAMPLES],
             waveform_buffer_right: [INIT_WAVEFORM; WAVEFORM_SAMPLES],
             waveform_write_pos: AtomicU32::new(0),
@@ -1001,13 +1601,19 @@ impl AudioEngine {
             // Input stereo (pre-FX) for comparison
             stereo_positions_input: [INIT_STEREO; STEREO_HISTORY_SIZE * 2],
             stereo_correlation_input: AtomicU32::new(f32_to_u32(1.0)), // Start at mono
+            stereo_mid_level: AtomicU32::new(f32_to_u32(0.0)),
+            stereo_side_level: AtomicU32::new(f32_to_u32(0.0)),
+            stereo_mid_level_input: AtomicU32::new(f32_to_u32(0.0)),
+            stereo_side_level_input: AtomicU32::new(f32_to_u32(0.0)),
+            lissajous_points: [INIT_STEREO; LISSAJOUS_SIZE * 2],
             plugin_instance: RwLock::new(None),
+            isolated_plugin: RwLock::new(None),
             plugin_state: RwLock::new(PluginState::Unloaded),
             midi_queue: RwLock::new(None),
             is_instrument_plugin: AtomicBool::new(false),
             crossfade_state: AtomicU8::new(CROSSFADE_NONE),
             crossfade_position: AtomicU32::new(0),
-            last_editor_position: RwLock::new(None),
+            editor_window_state: RwLock::new(EditorWindowState::default()),
             // Performance monitoring disabled by default (zero overhead when off)
             perf_monitoring_enabled: AtomicBool::new(false),
             perf_plugin_process_ns: AtomicU64::new(0),
@@ -1015,6 +1621,7 @@ impl AudioEngine {
         });
 
         let shared_clone = Arc::clone(&shared);
+        let shared_for_errors = Arc::clone(&shared);
 
         // Level smoothing factor
         let level_smoothing = 0.1f32;
@@ -1026,6 +1633,8 @@ impl AudioEngine {
         let max_buffer_size = max_frames * channels; // 8192 for stereo
         let mut input_buffer = vec![0.0f32; max_buffer_size];
         let mut output_buffer = vec![0.0f32; max_buffer_size];
+        // Scratch buffer for rendering each mixer source before summing
+        let mut mix_buffer = vec![0.0f32; max_buffer_size];
         // Pre-allocate buffers for metering/analysis (avoid allocation in audio callback)
         let mut pre_limited_buffer = vec![0.0f32; max_buffer_size];
         let mut mono_output_buffer = vec![0.0f32; max_frames];
@@ -1036,23 +1645,40 @@ impl AudioEngine {
         let mut spectrum_analyzer_input = SpectrumAnalyzer::new(sample_rate);
         // Counter for throttling spectrum updates (every N callbacks)
         let mut spectrum_update_counter = 0u32;
+        // Frames between spectrogram history rows
+        let history_row_frames = (sample_rate / HISTORY_ROWS_PER_SEC).max(1) as usize;
+        let mut frames_since_history_row = 0usize;
 
         // Create stereo analyzers for stereo imaging visualization (input = pre-FX, output = post-FX)
         let mut stereo_analyzer = StereoAnalyzer::new();
         let mut stereo_analyzer_input = StereoAnalyzer::new();
 
+        // Brickwall limiter protecting ears/speakers from runaway plugin output
+        let mut safety_limiter = SafetyLimiter::new(sample_rate);
+
         // Build the output stream
         let stream = device
             .build_output_stream(
                 &stream_config,
-                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+                    // Latency bookkeeping for the low-latency report (two relaxed stores, RT-safe)
+                    let timestamp = info.timestamp();
+                    if let Some(latency) = timestamp.playback.duration_since(&timestamp.callback) {
+                        shared_clone.output_latency_ns.store(latency.as_nanos() as u64, Ordering::Relaxed);
+                    }
+                    shared_clone.callback_frames.store((data.len() / channels) as u32, Ordering::Relaxed);
+
                     let is_playing = shared_clone.is_playing.load(Ordering::SeqCst);
                     // Use try_read to avoid blocking audio thread if main thread holds write lock
                     // during plugin load/unload. If we can't read, assume no plugin.
                     let has_plugin = shared_clone.plugin_instance
                         .try_read()
                         .map(|guard| guard.is_some())
-                        .unwrap_or(false);
+                        .unwrap_or(false)
+                        || shared_clone.isolated_plugin
+                            .try_read()
+                            .map(|guard| guard.is_some())
+                            .unwrap_or(false);
                     let is_instrument = shared_clone.is_instrument_plugin.load(Ordering::SeqCst);
 
                     // For instrument plugins, we need to process even when not "playing"
@@ -1076,106 +1702,50 @@ impl AudioEngine {
                         .unwrap_or(InputSource::None);
 
                     // Generate input samples
-                    match input_source {
-                        InputSource::Signal { .. } => {
-                            let mut generator = shared_clone.signal_generator.write();
-                            for chunk in data.chunks_mut(channels) {
-                                let sample = generator.next_sample();
-                                chunk[0] = sample.left;
-                                if channels > 1 {
-                                    chunk[1] = sample.right;
-                                }
-                            }
+                    if shared_clone.mixer.is_enabled() {
+                        // Mix every active source with its own gain/pan
+                        let len = data.len().min(max_buffer_size);
+                        for sample in data.iter_mut() {
+                            *sample = 0.0;
                         }
-                        InputSource::Sample { .. } => {
-                            let mut player = shared_clone.sample_player.write();
-                            for chunk in data.chunks_mut(channels) {
-                                let sample = player.next_sample();
-                                chunk[0] = sample.left;
+                        for source in MixerSource::ALL {
+                            let Some((gain_left, gain_right)) = shared_clone.mixer.channel(source).gains() else {
+                                continue;
+                            };
+                            render_source(&shared_clone, source, &mut mix_buffer[..len], channels);
+                            for (out, src) in data[..len]
+                                .chunks_mut(channels)
+                                .zip(mix_buffer[..len].chunks(channels))
+                            {
                                 if channels > 1 {
-                                    chunk[1] = sample.right;
+                                    out[0] += src[0] * gain_left;
+                                    out[1] += src[1] * gain_right;
+                                } else {
+                                    out[0] += src[0] * (gain_left + gain_right) * 0.5;
                                 }
                             }
                         }
-                        InputSource::Live { .. } => {
-                            // Check if paused - if so, output silence
-                            let is_paused = shared_clone.live_paused.load(Ordering::SeqCst);
-                            if is_paused {
-                                for sample in data.iter_mut() {
-                                    *sample = 0.0;
-                                }
-                            } else if let Some(input_handle) = crate::audio::input::get_input_handle() {
-                                let mut peak_left = 0.0f32;
-                                let mut peak_right = 0.0f32;
-
-                                // Check if we need to resample
-                                let mut resampler_guard = shared_clone.live_resampler.lock();
-
-                                if let Some(ref mut resampler) = *resampler_guard {
-                                    // Resampling mode: read input samples, resample, then output
-                                    let frames_needed = data.len() / channels;
-
-                                    // Read enough input samples and feed to resampler
-                                    // We may need to read more samples than output frames due to rate difference
-                                    let available = input_handle.available_samples();
-                                    for _ in 0..available.min(frames_needed * 2) {
-                                        let sample = input_handle.read_sample();
-                                        resampler.push_input(sample.left, sample.right);
-                                        // Track input levels from raw input
-                                        peak_left = peak_left.max(sample.left.abs());
-                                        peak_right = peak_right.max(sample.right.abs());
-                                    }
-
-                                    // Process resampler to generate output
-                                    while resampler.available_output() < frames_needed {
-                                        if !resampler.process() {
-                                            break; // Not enough input yet
-                                        }
-                                    }
-
-                                    // Read resampled output
-                                    for chunk in data.chunks_mut(channels) {
-                                        if let Some(sample) = resampler.pop_output() {
-                                            chunk[0] = sample.left;
-                                            if channels > 1 {
-                                                chunk[1] = sample.right;
-                                            }
-                                        } else {
-                                            // No resampled data available yet, output silence
-                                            chunk[0] = 0.0;
-                                            if channels > 1 {
-                                                chunk[1] = 0.0;
-                                            }
-                                        }
-                                    }
-                                } else {
-                                    // No resampling needed - direct passthrough
-                                    for chunk in data.chunks_mut(channels) {
-                                        let sample = input_handle.read_sample();
-                                        chunk[0] = sample.left;
-                                        if channels > 1 {
-                                            chunk[1] = sample.right;
-                                        }
-                                        // Track input levels
-                                        peak_left = peak_left.max(sample.left.abs());
-                                        peak_right = peak_right.max(sample.right.abs());
-                                    }
-                                }
-
-                                drop(resampler_guard); // Release lock
-                                // Input levels are captured universally after input_buffer copy
-                            } else {
-                                // No input handle available, output silence
+                        let master_gain = shared_clone.mixer.master_gain();
+                        for sample in data.iter_mut() {
+                            *sample *= master_gain;
+                        }
+                    } else {
+                        match input_source {
+                            InputSource::Signal { .. } => {
+                                render_source(&shared_clone, MixerSource::Signal, data, channels);
+                            }
+                            InputSource::Sample { .. } => {
+                                render_source(&shared_clone, MixerSource::Sample, data, channels);
+                            }
+                            InputSource::Live { .. } => {
+                                render_source(&shared_clone, MixerSource::Live, data, channels);
+                            }
+                            InputSource::None => {
                                 for sample in data.iter_mut() {
                                     *sample = 0.0;
                                 }
                             }
                         }
-                        InputSource::None => {
-                            for sample in data.iter_mut() {
-                                *sample = 0.0;
-                            }
-                        }
                     }
 
                     // Process through plugin if loaded
@@ -1246,7 +1816,15 @@ impl AudioEngine {
                     if has_plugin && data.len() <= max_buffer_size {
                         // Try to process through plugin using try_write to avoid blocking
                         // If main thread holds the lock (during reload/param update), pass through input unchanged
-                        let plugin_processed = if let Some(mut plugin_lock) = shared_clone.plugin_instance.try_write() {
+                        // An isolated plugin only exchanges blocks with its helper (never blocks)
+                        let isolated_processed = shared_clone.isolated_plugin.try_read().and_then(|guard| {
+                            guard.as_ref().map(|plugin| {
+                                plugin.process(&input_buffer[..data.len()], &mut output_buffer[..data.len()])
+                            })
+                        });
+                        let plugin_processed = if let Some(processed) = isolated_processed {
+                            processed
+                        } else if let Some(mut plugin_lock) = shared_clone.plugin_instance.try_write() {
                             if let Some(ref mut plugin) = *plugin_lock {
                                 // Performance monitoring: time only the plugin.process() call
                                 // Check flag first to avoid Instant::now() overhead when disabled
@@ -1260,6 +1838,9 @@ impl AudioEngine {
                                 let result = plugin
                                     .process(&input_buffer[..data.len()], &mut output_buffer[..data.len()])
                                     .is_ok();
+                                shared_clone
+                                    .param_recorder
+                                    .record(plugin.param_output_events(), data.len() / channels);
 
                                 // Store timing if monitoring was enabled
                                 if let Some(start) = start_time {
@@ -1386,17 +1967,21 @@ impl AudioEngine {
                     pre_limited_buffer[..data_len].copy_from_slice(data);
                     let pre_limited_data = &pre_limited_buffer[..data_len];
 
+                    // Watch the raw plugin output for NaNs, clicks, and runaway levels
+                    if has_plugin {
+                        shared_clone.dsp_health.check(pre_limited_data, channels);
+                    }
+
                     // ========================================
                     // SAFETY LIMITER (for speaker protection)
                     // ========================================
-                    // Clamp all output to prevent speaker/ear damage
+                    // Brickwall limit all output to the ceiling to prevent speaker/ear damage
                     // This protects against poorly written plugins that output >0dB
-                    for sample in data.iter_mut() {
-                        if !sample.is_finite() {
-                            *sample = 0.0;
-                        } else {
-                            *sample = sample.clamp(-1.0, 1.0);
-                        }
+                    // (e.g. a delay with runaway feedback)
+                    let ceiling = db_to_linear(u32_to_f32(shared_clone.output_ceiling_db.load(Ordering::Relaxed)));
+                    let reduction_db = safety_limiter.process(data, channels, ceiling);
+                    if reduction_db >= DANGEROUS_REDUCTION_DB {
+                        shared_clone.dsp_health.record_dangerous_level();
                     }
 
                     // ========================================
@@ -1503,6 +2088,9 @@ impl AudioEngine {
                     // Update spectrum analyzer (mono mix of L/R for analysis)
                     // Update every 2 callbacks for smoother visuals (~6ms at 44.1kHz/512)
                     spectrum_update_counter += 1;
+                    if is_playing || is_instrument {
+                        frames_since_history_row += data.len() / channels;
+                    }
                     if spectrum_update_counter >= 2 {
                         spectrum_update_counter = 0;
 
@@ -1547,6 +2135,18 @@ impl AudioEngine {
                             shared_clone.spectrum_bands_input[i].store(f32_to_u32(mag), Ordering::Relaxed);
                         }
 
+                        // Record spectrogram history at a fixed rate while audio is running
+                        // (pre-allocated ring, skipped if an export is reading it)
+                        if frames_since_history_row >= history_row_frames {
+                            frames_since_history_row = 0;
+                            if let Some(mut history) = shared_clone.spectrum_history.try_lock() {
+                                history.push(SpectrogramRow {
+                                    input: magnitudes_input,
+                                    output: magnitudes,
+                                });
+                            }
+                        }
+
                         // Stereo analysis - push stereo samples (not mono)
                         // OUTPUT stereo: Uses PRE-LIMITED data to show true stereo field
                         // INPUT stereo: Uses input_buffer for pre-FX comparison
@@ -1565,6 +2165,15 @@ impl AudioEngine {
                             let correlation = stereo_analyzer.get_correlation();
                             shared_clone.stereo_correlation.store(f32_to_u32(correlation), Ordering::Relaxed);
 
+                            // Store output mid/side levels and goniometer pairs
+                            let (mid, side) = stereo_analyzer.get_mid_side_levels();
+                            shared_clone.stereo_mid_level.store(f32_to_u32(mid), Ordering::Relaxed);
+                            shared_clone.stereo_side_level.store(f32_to_u32(side), Ordering::Relaxed);
+                            for (i, &(left, right)) in stereo_analyzer.get_lissajous().iter().enumerate() {
+                                shared_clone.lissajous_points[i * 2].store(f32_to_u32(left), Ordering::Relaxed);
+                                shared_clone.lissajous_points[i * 2 + 1].store(f32_to_u32(right), Ordering::Relaxed);
+                            }
+
                             // Input stereo analysis (pre-FX)
                             stereo_analyzer_input.push_samples(&input_buffer[..data.len()]);
 
@@ -1578,6 +2187,11 @@ impl AudioEngine {
                             // Store input correlation
                             let correlation_input = stereo_analyzer_input.get_correlation();
                             shared_clone.stereo_correlation_input.store(f32_to_u32(correlation_input), Ordering::Relaxed);
+
+                            // Store input mid/side levels
+                            let (mid_input, side_input) = stereo_analyzer_input.get_mid_side_levels();
+                            shared_clone.stereo_mid_level_input.store(f32_to_u32(mid_input), Ordering::Relaxed);
+                            shared_clone.stereo_side_level_input.store(f32_to_u32(side_input), Ordering::Relaxed);
                         } else {
                             // Mono audio: reset stereo analyzers and clear positions to center
                             // This prevents stale stereo particles from displaying when switching from stereo to mono
@@ -1598,11 +2212,27 @@ impl AudioEngine {
                             // Correlation is 1.0 for mono (perfect correlation)
                             shared_clone.stereo_correlation.store(f32_to_u32(1.0), Ordering::Relaxed);
                             shared_clone.stereo_correlation_input.store(f32_to_u32(1.0), Ordering::Relaxed);
+
+                            // No side signal in mono, and nothing to draw on the goniometer
+                            for level in [
+                                &shared_clone.stereo_mid_level,
+                                &shared_clone.stereo_side_level,
+                                &shared_clone.stereo_mid_level_input,
+                                &shared_clone.stereo_side_level_input,
+                            ] {
+                                level.store(f32_to_u32(0.0), Ordering::Relaxed);
+                            }
+                            for point in shared_clone.lissajous_points.iter() {
+                                point.store(f32_to_u32(0.0), Ordering::Relaxed);
+                            }
                         }
                     }
                 },
                 move |err| {
                     log::error!("Audio stream error: {}", err);
+                    if is_device_lost_error(&err) {
+                        shared_for_errors.device_lost.store(true, Ordering::SeqCst);
+                    }
                 },
                 None, // No timeout
             )
@@ -1616,6 +2246,8 @@ impl AudioEngine {
         let handle = AudioEngineHandle {
             shared,
             sample_rate,
+            device_name: resolved_device_name,
+            config: config.clone(),
         };
 
         Ok(Self {
@@ -1725,6 +2357,124 @@ pub fn reinit_engine(device_name: Option<&str>, config: AudioConfig) -> Result<(
     Ok(())
 }
 
+/// Result of moving the engine to a new output device after the old one was lost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceChange {
+    pub previous_device: String,
+    pub current_device: String,
+    pub previous_sample_rate: u32,
+    pub sample_rate: u32,
+    /// Whether a loaded plugin was re-activated on the new engine
+    pub plugin_reloaded: bool,
+}
+
+/// Rebuild the engine on the default output device after the current one was lost
+/// The loaded plugin is re-activated at the new device's sample rate with its
+/// state restored, and the input source, mixer, sample (loop region, tempo,
+/// position) and transport carry over, so the preview sounds as it did.
+pub fn recover_from_device_loss() -> Result<DeviceChange, String> {
+    let old = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+
+    // Capture what needs to survive the rebuild before the old engine goes away
+    // (transport first - stopping resets the playhead)
+    let was_playing = old.is_playing();
+    let playhead = old.get_sample_playhead();
+    old.stop();
+    let input_source = old.get_input_source();
+    let mixer = old.get_mixer();
+    let looping = old.shared.is_looping.load(Ordering::SeqCst);
+    let (tempo, tempo_mode) = old.get_sample_tempo();
+    let plugin_path = match old.get_plugin_state() {
+        PluginState::Active { path, .. } => Some(path),
+        _ => None,
+    };
+    let plugin_state = plugin_path.as_ref().and_then(|_| old.save_plugin_state().ok());
+    let isolated = old.shared.isolated_plugin.read().is_some();
+    let is_instrument = old.is_instrument();
+    let master_volume = old.get_master_volume();
+    let output_ceiling_db = old.get_output_ceiling_db();
+    let editor_window_state = old.get_editor_window_state();
+
+    if plugin_path.is_some() {
+        old.close_plugin_editor();
+        old.unload_plugin();
+    }
+
+    // Keep the old buffer size but let the new device pick its own rate
+    let config = AudioConfig {
+        sample_rate: super::device::get_default_sample_rate()?,
+        ..old.config.clone()
+    };
+    reinit_engine(None, config)?;
+
+    let new = get_engine_handle().ok_or_else(|| "Audio engine failed to restart".to_string())?;
+    new.set_master_volume(master_volume);
+    new.set_output_ceiling_db(output_ceiling_db);
+    new.set_is_instrument(is_instrument);
+    new.set_editor_window_state(editor_window_state);
+
+    // Input before the mixer, so a mixed live channel reuses the source's capture
+    new.set_looping(looping);
+    let sample_loaded = matches!(input_source, InputSource::Sample { .. });
+    new.set_input_source(input_source);
+    if sample_loaded {
+        new.set_sample_tempo(tempo, tempo_mode);
+        if let Some(playhead) = playhead {
+            if let (Some(start), Some(end)) = (playhead.loop_start_secs, playhead.loop_end_secs) {
+                if let Err(e) = new.set_sample_loop_region(start, end) {
+                    log::warn!("Failed to restore sample loop region after device change: {}", e);
+                }
+            }
+            new.seek_sample(playhead.position_secs);
+        }
+    }
+    new.set_mixer_master_gain(mixer.master_gain_db);
+    new.set_mixer_channel(MixerSource::Signal, mixer.signal);
+    new.set_mixer_channel(MixerSource::Sample, mixer.sample);
+    new.set_mixer_channel(MixerSource::Live, mixer.live);
+    new.set_mixer_enabled(mixer.enabled);
+
+    let mut plugin_reloaded = false;
+    if let Some(path) = plugin_path {
+        let loaded = if isolated {
+            new.load_plugin_isolated(Path::new(&path))
+        } else {
+            new.load_plugin(Path::new(&path))
+        };
+        match loaded {
+            Ok(()) => {
+                if let Some(data) = plugin_state {
+                    if let Err(e) = new.load_plugin_state(&data) {
+                        log::warn!("Failed to restore plugin state after device change: {}", e);
+                    }
+                }
+                plugin_reloaded = true;
+            }
+            Err(e) => log::error!("Failed to re-activate plugin after device change: {}", e),
+        }
+    }
+
+    if was_playing {
+        new.play();
+    }
+
+    log::info!(
+        "Recovered from lost device '{}' -> '{}' ({} Hz -> {} Hz)",
+        old.device_name,
+        new.device_name,
+        old.sample_rate,
+        new.sample_rate
+    );
+
+    Ok(DeviceChange {
+        previous_device: old.device_name.clone(),
+        current_device: new.device_name.clone(),
+        previous_sample_rate: old.sample_rate,
+        sample_rate: new.sample_rate,
+        plugin_reloaded,
+    })
+}
+
 /// Get the current audio engine sample rate
 pub fn get_engine_sample_rate() -> Option<u32> {
     get_engine_handle().map(|h| h.sample_rate)
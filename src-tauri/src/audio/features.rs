@@ -0,0 +1,360 @@
+//! Tonal and dynamic fingerprint of a piece of audio
+//!
+//! Used to compare a plugin's output against a reference file ("make it sound
+//! like this"). Everything is level-independent where it can be - band
+//! energies are relative to the total, tilt is a slope - so a reference
+//! mastered 10 dB louder still gives useful tonal targets.
+
+use realfft::RealFftPlanner;
+use serde::Serialize;
+
+use super::buffer::StereoSample;
+
+/// FFT length for the long-term spectrum
+const FFT_SIZE: usize = 4096;
+
+/// Window for the short-term loudness used by the dynamic range (seconds)
+const SHORT_TERM_SECS: f32 = 0.4;
+
+/// Short-term windows quieter than this are ignored as silence (dBFS)
+const SILENCE_GATE_DB: f32 = -70.0;
+
+/// Bark critical band edges (Zwicker) - 24 bands up to 15.5 kHz
+const BARK_EDGES: [f32; 25] = [
+    0.0, 100.0, 200.0, 300.0, 400.0, 510.0, 630.0, 770.0, 920.0, 1080.0, 1270.0, 1480.0, 1720.0, 2000.0, 2320.0,
+    2700.0, 3150.0, 3700.0, 4400.0, 5300.0, 6400.0, 7700.0, 9500.0, 12000.0, 15500.0,
+];
+
+/// Band differences smaller than this aren't worth mentioning (dB)
+const BAND_TARGET_THRESHOLD_DB: f32 = 3.0;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BarkBand {
+    pub low_hz: f32,
+    pub high_hz: f32,
+    /// Share of the total energy (dB, 0 = all of it)
+    pub energy_db: f32,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AudioFeatures {
+    pub duration_secs: f32,
+    pub rms_db: f32,
+    pub peak_db: f32,
+    /// Peak to RMS (dB) - low for heavily compressed or limited material
+    pub crest_factor_db: f32,
+    /// Spread between loud and quiet passages (dB, 95th - 10th percentile of short-term RMS)
+    pub dynamic_range_db: f32,
+    /// Slope of the spectrum (dB per octave, pink noise = -3)
+    pub spectral_tilt_db_per_octave: f32,
+    pub spectral_centroid_hz: f32,
+    /// Side energy relative to mid (dB, -inf = mono)
+    pub stereo_width_db: f32,
+    pub bark_bands: Vec<BarkBand>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FeatureDiff {
+    /// All differences are plugin minus reference
+    pub rms_db: f32,
+    pub crest_factor_db: f32,
+    pub dynamic_range_db: f32,
+    pub spectral_tilt_db_per_octave: f32,
+    pub spectral_centroid_hz: f32,
+    pub stereo_width_db: f32,
+    /// Per-band energy difference, in the reference's band order
+    pub bark_bands_db: Vec<f32>,
+    /// Plain-language targets for closing the gap, biggest first
+    pub targets: Vec<String>,
+}
+
+fn to_db(power: f64) -> f32 {
+    if power > 0.0 {
+        (10.0 * power.log10()).max(-200.0) as f32
+    } else {
+        -200.0
+    }
+}
+
+/// Percentile of an unsorted list (0.0 - 1.0)
+fn percentile(values: &mut [f32], p: f32) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    values[((values.len() - 1) as f32 * p).round() as usize]
+}
+
+/// Average power spectrum of the mono sum (Hann-windowed, 50% overlap)
+fn power_spectrum(data: &[StereoSample]) -> Vec<f64> {
+    let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+    let window: Vec<f32> = (0..FFT_SIZE)
+        .map(|i| 0.5 * (1.0 - (std::f32::consts::TAU * i as f32 / FFT_SIZE as f32).cos()))
+        .collect();
+    let mut input = fft.make_input_vec();
+    let mut output = fft.make_output_vec();
+    let mut power = vec![0.0f64; output.len()];
+
+    let mut start = 0;
+    loop {
+        for (i, x) in input.iter_mut().enumerate() {
+            *x = data
+                .get(start + i)
+                .map_or(0.0, |s| (s.left + s.right) * 0.5 * window[i]);
+        }
+        if fft.process(&mut input, &mut output).is_ok() {
+            for (p, c) in power.iter_mut().zip(&output) {
+                *p += c.norm_sqr() as f64;
+            }
+        }
+        start += FFT_SIZE / 2;
+        if start + FFT_SIZE / 2 >= data.len() {
+            break;
+        }
+    }
+    power
+}
+
+/// Extract the features of a stereo buffer
+pub fn extract_features(data: &[StereoSample], sample_rate: u32) -> AudioFeatures {
+    let len = data.len().max(1);
+    let mut sum = 0.0f64;
+    let mut peak = 0.0f32;
+    let mut mid = 0.0f64;
+    let mut side = 0.0f64;
+    for s in data {
+        sum += (s.left as f64).powi(2) + (s.right as f64).powi(2);
+        peak = peak.max(s.left.abs()).max(s.right.abs());
+        mid += (((s.left + s.right) * 0.5) as f64).powi(2);
+        side += (((s.left - s.right) * 0.5) as f64).powi(2);
+    }
+    let rms_db = to_db(sum / (len * 2) as f64);
+    let peak_db = to_db((peak as f64).powi(2));
+
+    let window = ((SHORT_TERM_SECS * sample_rate as f32) as usize).max(1);
+    let mut short_term: Vec<f32> = data
+        .chunks(window)
+        .map(|chunk| {
+            let sum: f64 = chunk.iter().map(|s| (s.left as f64).powi(2) + (s.right as f64).powi(2)).sum();
+            to_db(sum / (chunk.len() * 2) as f64)
+        })
+        .filter(|&db| db > SILENCE_GATE_DB)
+        .collect();
+    let dynamic_range_db = percentile(&mut short_term, 0.95) - percentile(&mut short_term, 0.1);
+
+    let power = power_spectrum(data);
+    let bin_hz = sample_rate as f32 / FFT_SIZE as f32;
+    let total: f64 = power.iter().skip(1).sum();
+    let centroid = power
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, p)| i as f64 * bin_hz as f64 * p)
+        .sum::<f64>()
+        / total.max(f64::MIN_POSITIVE);
+
+    let nyquist = sample_rate as f32 / 2.0;
+    let mut bark_bands = Vec::new();
+    // (log2 of band center, power density in dB) for the tilt fit
+    let mut tilt_points = Vec::new();
+    for edges in BARK_EDGES.windows(2) {
+        let (low, high) = (edges[0], edges[1].min(nyquist));
+        if low >= high {
+            break;
+        }
+        let energy: f64 = power
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(i, _)| {
+                let f = *i as f32 * bin_hz;
+                f >= low && f < high
+            })
+            .map(|(_, p)| *p)
+            .sum();
+        bark_bands.push(BarkBand {
+            low_hz: low,
+            high_hz: high,
+            energy_db: to_db(energy / total.max(f64::MIN_POSITIVE)),
+        });
+        if energy > 0.0 && low > 0.0 {
+            tilt_points.push(((low * high).sqrt().log2(), to_db(energy / (high - low) as f64)));
+        }
+    }
+
+    // Least-squares slope of density against octave
+    let n = tilt_points.len() as f32;
+    let spectral_tilt_db_per_octave = if n >= 2.0 {
+        let mean_x = tilt_points.iter().map(|(x, _)| x).sum::<f32>() / n;
+        let mean_y = tilt_points.iter().map(|(_, y)| y).sum::<f32>() / n;
+        let cov: f32 = tilt_points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let var: f32 = tilt_points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        if var > 0.0 {
+            cov / var
+        } else {
+            0.0
+        }
+    } else {
+        0.0
+    };
+
+    AudioFeatures {
+        duration_secs: data.len() as f32 / sample_rate as f32,
+        rms_db,
+        peak_db,
+        crest_factor_db: peak_db - rms_db,
+        dynamic_range_db,
+        spectral_tilt_db_per_octave,
+        spectral_centroid_hz: centroid as f32,
+        stereo_width_db: to_db(side / mid.max(f64::MIN_POSITIVE)),
+        bark_bands,
+    }
+}
+
+fn format_hz(hz: f32) -> String {
+    if hz >= 1000.0 {
+        format!("{:.1} kHz", hz / 1000.0)
+    } else {
+        format!("{:.0} Hz", hz)
+    }
+}
+
+/// Compare a plugin's output features against a reference
+pub fn diff_features(reference: &AudioFeatures, plugin: &AudioFeatures) -> FeatureDiff {
+    let bark_bands_db: Vec<f32> = reference
+        .bark_bands
+        .iter()
+        .zip(&plugin.bark_bands)
+        .map(|(r, p)| p.energy_db - r.energy_db)
+        .collect();
+
+    // (size of the gap, description) so the biggest gaps come first
+    let mut targets: Vec<(f32, String)> = Vec::new();
+    let tilt = plugin.spectral_tilt_db_per_octave - reference.spectral_tilt_db_per_octave;
+    if tilt.abs() >= 0.5 {
+        targets.push((
+            tilt.abs() * 4.0,
+            format!(
+                "Overall tone is {} than the reference: tilt the spectrum by {:+.1} dB/octave",
+                if tilt > 0.0 { "brighter" } else { "darker" },
+                -tilt
+            ),
+        ));
+    }
+    for (band, diff) in reference.bark_bands.iter().zip(&bark_bands_db) {
+        // Bands the reference barely uses don't make good targets
+        if diff.abs() >= BAND_TARGET_THRESHOLD_DB && band.energy_db > -60.0 {
+            targets.push((
+                diff.abs(),
+                format!(
+                    "{} {}–{} by about {:.0} dB",
+                    if *diff > 0.0 { "Cut" } else { "Boost" },
+                    format_hz(band.low_hz),
+                    format_hz(band.high_hz),
+                    diff.abs()
+                ),
+            ));
+        }
+    }
+    let crest = plugin.crest_factor_db - reference.crest_factor_db;
+    if crest.abs() >= 2.0 {
+        targets.push((
+            crest.abs(),
+            if crest > 0.0 {
+                format!("Peaks stand {:.1} dB further above the average: compress or limit more", crest)
+            } else {
+                format!("Peaks are {:.1} dB more squashed than the reference: compress less", -crest)
+            },
+        ));
+    }
+    let width = plugin.stereo_width_db - reference.stereo_width_db;
+    if width.abs() >= 3.0 && reference.stereo_width_db > -60.0 {
+        targets.push((
+            width.abs(),
+            format!(
+                "Stereo image is {} than the reference (side/mid {:+.1} dB)",
+                if width > 0.0 { "wider" } else { "narrower" },
+                width
+            ),
+        ));
+    }
+    targets.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    FeatureDiff {
+        rms_db: plugin.rms_db - reference.rms_db,
+        crest_factor_db: crest,
+        dynamic_range_db: plugin.dynamic_range_db - reference.dynamic_range_db,
+        spectral_tilt_db_per_octave: tilt,
+        spectral_centroid_hz: plugin.spectral_centroid_hz - reference.spectral_centroid_hz,
+        stereo_width_db: width,
+        bark_bands_db,
+        targets: targets.into_iter().map(|(_, t)| t).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noise(len: usize, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sine_features() {
+        let rate = 48000;
+        let sine: Vec<StereoSample> = (0..rate)
+            .map(|i| {
+                let s = 0.5 * (std::f32::consts::TAU * 1000.0 * i as f32 / rate as f32).sin();
+                StereoSample::new(s, s)
+            })
+            .collect();
+        let features = extract_features(&sine, rate);
+
+        // A sine's crest factor is 3 dB and a mono signal has no side
+        assert!((features.crest_factor_db - 3.01).abs() < 0.1);
+        assert!((features.spectral_centroid_hz - 1000.0).abs() < 50.0);
+        assert!(features.stereo_width_db < -100.0);
+        assert!(features.dynamic_range_db < 0.5);
+        let loudest = features
+            .bark_bands
+            .iter()
+            .max_by(|a, b| a.energy_db.total_cmp(&b.energy_db))
+            .unwrap();
+        assert_eq!((loudest.low_hz, loudest.high_hz), (920.0, 1080.0));
+    }
+
+    #[test]
+    fn test_darker_output_gets_targets() {
+        let rate = 48000;
+        let white: Vec<StereoSample> = noise(rate as usize * 2, 1)
+            .into_iter()
+            .zip(noise(rate as usize * 2, 2))
+            .map(|(l, r)| StereoSample::new(l * 0.3, r * 0.3))
+            .collect();
+        // One-pole lowpass around 1 kHz
+        let mut state = (0.0, 0.0);
+        let dark: Vec<StereoSample> = white
+            .iter()
+            .map(|s| {
+                state.0 += 0.12 * (s.left - state.0);
+                state.1 += 0.12 * (s.right - state.1);
+                StereoSample::new(state.0, state.1)
+            })
+            .collect();
+
+        let reference = extract_features(&white, rate);
+        assert!(reference.spectral_tilt_db_per_octave.abs() < 1.0);
+
+        let diff = diff_features(&reference, &extract_features(&dark, rate));
+        assert!(diff.spectral_tilt_db_per_octave < -2.0);
+        assert!(diff.targets[0].starts_with("Overall tone is darker"));
+        assert!(diff.targets.iter().any(|t| t.starts_with("Boost 12.0 kHz–15.5 kHz")));
+    }
+}
@@ -0,0 +1,122 @@
+//! Safety brickwall limiter for the preview output
+//!
+//! Sits between the plugin and the speakers. Gain drops instantly when a peak
+//! would cross the ceiling and recovers smoothly afterwards, and a final hard
+//! clamp guarantees nothing above the ceiling ever reaches the device - a
+//! runaway feedback loop in a delay plugin is held at the ceiling instead of
+//! blasting the user's ears.
+
+/// Default output ceiling (dBFS)
+pub const DEFAULT_CEILING_DB: f32 = -1.0;
+
+/// Lowest ceiling that can be configured (dBFS)
+pub const MIN_CEILING_DB: f32 = -24.0;
+
+/// Gain reduction at which a peak counts as a dangerous level (dB)
+pub const DANGEROUS_REDUCTION_DB: f32 = 6.0;
+
+/// Release time for gain recovery (seconds)
+const RELEASE_SECS: f32 = 0.05;
+
+pub fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Stereo-linked peak limiter (audio thread, no allocation)
+pub struct SafetyLimiter {
+    gain: f32,
+    release_coeff: f32,
+}
+
+impl SafetyLimiter {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            gain: 1.0,
+            release_coeff: (-1.0 / (RELEASE_SECS * sample_rate.max(1) as f32)).exp(),
+        }
+    }
+
+    /// Limit an interleaved buffer in place to `ceiling` (linear)
+    /// Non-finite samples are replaced with silence. Returns the deepest gain
+    /// reduction applied in this buffer, in dB (0.0 = untouched).
+    pub fn process(&mut self, data: &mut [f32], channels: usize, ceiling: f32) -> f32 {
+        if channels == 0 {
+            return 0.0;
+        }
+
+        let mut min_gain = 1.0f32;
+        for frame in data.chunks_mut(channels) {
+            let mut peak = 0.0f32;
+            for sample in frame.iter_mut() {
+                if !sample.is_finite() {
+                    *sample = 0.0;
+                }
+                peak = peak.max(sample.abs());
+            }
+
+            let desired = if peak > ceiling { ceiling / peak } else { 1.0 };
+            self.gain = if desired < self.gain {
+                desired
+            } else {
+                desired + (self.gain - desired) * self.release_coeff
+            };
+            min_gain = min_gain.min(self.gain);
+
+            for sample in frame.iter_mut() {
+                *sample = (*sample * self.gain).clamp(-ceiling, ceiling);
+            }
+        }
+
+        if min_gain < 1.0 {
+            -20.0 * min_gain.max(1e-6).log10()
+        } else {
+            0.0
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.gain = 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_never_exceeds_ceiling() {
+        let mut limiter = SafetyLimiter::new(48000);
+        let ceiling = db_to_linear(DEFAULT_CEILING_DB);
+
+        // Runaway feedback: +20 dBFS, plus a NaN
+        let mut data: Vec<f32> = (0..512).map(|i| (i as f32 * 0.1).sin() * 10.0).collect();
+        data[7] = f32::NAN;
+        let reduction = limiter.process(&mut data, 2, ceiling);
+
+        assert!(data.iter().all(|s| s.is_finite() && s.abs() <= ceiling));
+        assert!(reduction >= DANGEROUS_REDUCTION_DB);
+    }
+
+    #[test]
+    fn test_quiet_signal_passes_untouched() {
+        let mut limiter = SafetyLimiter::new(48000);
+        let original: Vec<f32> = (0..512).map(|i| (i as f32 * 0.1).sin() * 0.5).collect();
+        let mut data = original.clone();
+        assert_eq!(limiter.process(&mut data, 2, db_to_linear(DEFAULT_CEILING_DB)), 0.0);
+        assert_eq!(data, original);
+    }
+
+    #[test]
+    fn test_gain_recovers_after_peak() {
+        let mut limiter = SafetyLimiter::new(48000);
+        let ceiling = db_to_linear(DEFAULT_CEILING_DB);
+
+        let mut spike = vec![4.0f32, 4.0];
+        limiter.process(&mut spike, 2, ceiling);
+
+        // A second of quiet signal lets the gain return to unity
+        let mut quiet = vec![0.1f32; 48000 * 2];
+        limiter.process(&mut quiet, 2, ceiling);
+        assert!((quiet[quiet.len() - 1] - 0.1).abs() < 1e-4);
+    }
+}
@@ -1,13 +1,15 @@
 //! MIDI device input handling
 //!
 //! Provides enumeration and connection to MIDI input devices (hardware and virtual).
-//! Received MIDI messages are forwarded to the plugin's MIDI event queue.
+//! Received MIDI messages are forwarded to the plugin's MIDI event queue, except
+//! CCs used by MIDI learn.
 
 use std::sync::Arc;
 use parking_lot::Mutex;
 use midir::{MidiInput, MidiInputConnection};
 
 use super::events::{MidiEvent, MidiEventQueue};
+use super::learn::MIDI_LEARN;
 
 /// Information about a MIDI input device
 #[derive(Debug, Clone, serde::Serialize)]
@@ -247,6 +249,9 @@ impl MidiInputManager {
                     if cc == 123 {
                         queue.push(MidiEvent::AllNotesOff);
                         log::debug!("MIDI All Notes Off CC received");
+                    } else if MIDI_LEARN.handle_cc(channel, cc, value) {
+                        // Learned or mapped to a parameter
+                        log::trace!("MIDI CC mapped: cc={}, value={}, ch={}", cc, value, channel);
                     } else {
                         // Forward other CC messages (sustain=64, mod wheel=1, etc.)
                         queue.push(MidiEvent::ControlChange {
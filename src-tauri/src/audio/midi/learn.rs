@@ -0,0 +1,170 @@
+//! MIDI learn: hardware controller CCs mapped to plugin parameters
+//!
+//! While learning, the next CC from the connected MIDI device is bound to the
+//! chosen parameter. Mapped CCs then set that parameter (scaled to its range)
+//! instead of reaching the plugin as MIDI. Mappings are kept here for the
+//! loaded plugin; the preview commands persist them per project.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::oneshot;
+
+use crate::audio::engine::get_engine_handle;
+use crate::audio::plugin::PluginParamInfo;
+
+/// A controller bound to a parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CcMapping {
+    pub param_id: u32,
+    pub param_name: String,
+    /// MIDI channel (0-15)
+    pub channel: u8,
+    /// Controller number (0-127)
+    pub controller: u8,
+}
+
+/// What a CC value is scaled to
+#[derive(Debug, Clone, Copy)]
+struct ParamRange {
+    min: f64,
+    max: f64,
+    stepped: bool,
+}
+
+/// Plain parameter value for a 7-bit CC value
+fn cc_to_value(value: u8, range: ParamRange) -> f64 {
+    let scaled = range.min + (range.max - range.min) * (value.min(127) as f64 / 127.0);
+    if range.stepped {
+        scaled.round()
+    } else {
+        scaled
+    }
+}
+
+/// Parameter waiting for its controller
+struct PendingLearn {
+    param_id: u32,
+    param_name: String,
+    sender: oneshot::Sender<CcMapping>,
+}
+
+pub struct MidiLearn {
+    mappings: Mutex<Vec<CcMapping>>,
+    /// Ranges of the loaded plugin's parameters
+    ranges: Mutex<HashMap<u32, ParamRange>>,
+    pending: Mutex<Option<PendingLearn>>,
+}
+
+impl MidiLearn {
+    fn new() -> Self {
+        Self {
+            mappings: Mutex::new(Vec::new()),
+            ranges: Mutex::new(HashMap::new()),
+            pending: Mutex::new(None),
+        }
+    }
+
+    pub fn mappings(&self) -> Vec<CcMapping> {
+        self.mappings.lock().clone()
+    }
+
+    /// Replace the mappings (on project load)
+    pub fn set_mappings(&self, mappings: Vec<CcMapping>) {
+        *self.mappings.lock() = mappings;
+    }
+
+    /// Take the parameter ranges from a newly loaded plugin
+    pub fn set_params(&self, params: &[PluginParamInfo]) {
+        *self.ranges.lock() = params
+            .iter()
+            .filter(|p| !p.is_read_only)
+            .map(|p| {
+                let range = ParamRange {
+                    min: p.min_value,
+                    max: p.max_value,
+                    stepped: p.is_stepped,
+                };
+                (p.id, range)
+            })
+            .collect();
+    }
+
+    /// Bind the next incoming CC to a parameter; the receiver gets the new mapping
+    pub fn start(&self, param_id: u32, param_name: String) -> oneshot::Receiver<CcMapping> {
+        let (sender, receiver) = oneshot::channel();
+        *self.pending.lock() = Some(PendingLearn {
+            param_id,
+            param_name,
+            sender,
+        });
+        receiver
+    }
+
+    pub fn cancel(&self) {
+        self.pending.lock().take();
+    }
+
+    /// Handle a CC from the MIDI device; returns true when it was used for
+    /// learning or a mapping (and shouldn't also go to the plugin as MIDI)
+    pub fn handle_cc(&self, channel: u8, controller: u8, value: u8) -> bool {
+        if let Some(pending) = self.pending.lock().take() {
+            let mapping = CcMapping {
+                param_id: pending.param_id,
+                param_name: pending.param_name,
+                channel,
+                controller,
+            };
+            // One controller per parameter and one parameter per controller
+            let mut mappings = self.mappings.lock();
+            mappings.retain(|m| m.param_id != mapping.param_id && (m.channel, m.controller) != (channel, controller));
+            mappings.push(mapping.clone());
+            let _ = pending.sender.send(mapping);
+            return true;
+        }
+
+        let Some(param_id) = self
+            .mappings
+            .lock()
+            .iter()
+            .find(|m| m.channel == channel && m.controller == controller)
+            .map(|m| m.param_id)
+        else {
+            return false;
+        };
+        if let (Some(range), Some(handle)) = (self.ranges.lock().get(&param_id).copied(), get_engine_handle()) {
+            let _ = handle.set_plugin_param(param_id, cc_to_value(value, range));
+        }
+        true
+    }
+}
+
+/// MIDI learn state for the preview host
+pub static MIDI_LEARN: Lazy<MidiLearn> = Lazy::new(MidiLearn::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cc_to_value() {
+        let gain = ParamRange {
+            min: -24.0,
+            max: 24.0,
+            stepped: false,
+        };
+        assert_eq!(cc_to_value(0, gain), -24.0);
+        assert_eq!(cc_to_value(127, gain), 24.0);
+        assert!((cc_to_value(64, gain) - 0.189).abs() < 0.001);
+
+        let mode = ParamRange {
+            min: 0.0,
+            max: 3.0,
+            stepped: true,
+        };
+        assert_eq!(cc_to_value(50, mode), 1.0);
+        assert_eq!(cc_to_value(127, mode), 3.0);
+    }
+}
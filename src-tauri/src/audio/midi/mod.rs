@@ -1,15 +1,18 @@
 //! MIDI handling for instrument plugins
 //!
-//! Provides MIDI event types, queuing, pattern playback, MIDI file support, and live device input.
+//! Provides MIDI event types, queuing, pattern playback, MIDI file support, live device input,
+//! and MIDI learn.
 
 mod events;
 pub mod file;
 pub mod patterns;
 mod player;
 mod device;
+pub mod learn;
 
 pub use events::{MidiEvent, MidiEventQueue};
 pub use file::{MidiFileInfo, MidiFileNote, MidiTrackInfo, ParsedMidiFile, TempoEvent, parse_midi_file, get_midi_file_info};
 pub use patterns::{PatternCategory, PatternInfo, list_patterns, get_pattern};
 pub use player::{MidiPlayer, PlaybackSource};
 pub use device::{MidiDeviceInfo, MidiInputManager};
+pub use learn::{CcMapping, MIDI_LEARN};
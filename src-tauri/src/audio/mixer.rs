@@ -0,0 +1,194 @@
+//! Input mixer for the preview engine
+//!
+//! Lets the signal generator, sample player, and live input play at the same
+//! time (e.g. a test tone under program material). Each source has its own
+//! gain/pan/mute; the sum goes through a master gain before the plugin.
+//! All settings are atomics so the audio thread never blocks on them.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// A source that can feed the mixer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MixerSource {
+    Signal,
+    Sample,
+    Live,
+}
+
+impl MixerSource {
+    pub const ALL: [MixerSource; 3] = [MixerSource::Signal, MixerSource::Sample, MixerSource::Live];
+}
+
+/// Settings for one mixer channel
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChannelStrip {
+    /// Whether the source is part of the mix at all
+    pub enabled: bool,
+    /// Gain in dB (-60 to +12)
+    pub gain_db: f32,
+    /// Pan (-1.0 = left, 0.0 = center, 1.0 = right)
+    pub pan: f32,
+    pub mute: bool,
+}
+
+impl Default for ChannelStrip {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            gain_db: 0.0,
+            pan: 0.0,
+            mute: false,
+        }
+    }
+}
+
+impl ChannelStrip {
+    fn clamped(self) -> Self {
+        Self {
+            gain_db: self.gain_db.clamp(-60.0, 12.0),
+            pan: self.pan.clamp(-1.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Linear (left, right) gains, or None if the channel is silent
+    ///
+    /// Uses a constant-power pan law normalized so center pan is unity gain.
+    pub fn gains(&self) -> Option<(f32, f32)> {
+        if !self.enabled || self.mute {
+            return None;
+        }
+        let gain = db_to_linear(self.gain_db);
+        let angle = (self.pan + 1.0) * std::f32::consts::FRAC_PI_4;
+        let norm = std::f32::consts::SQRT_2;
+        Some((gain * angle.cos() * norm, gain * angle.sin() * norm))
+    }
+}
+
+/// Complete mixer configuration (as exchanged with the frontend)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MixerSettings {
+    /// When false the engine plays only the selected input source (legacy behavior)
+    pub enabled: bool,
+    pub master_gain_db: f32,
+    pub signal: ChannelStrip,
+    pub sample: ChannelStrip,
+    pub live: ChannelStrip,
+}
+
+#[inline]
+pub fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// Lock-free storage for one channel strip
+struct AtomicStrip {
+    enabled: AtomicBool,
+    gain_db: AtomicU32,
+    pan: AtomicU32,
+    mute: AtomicBool,
+}
+
+impl AtomicStrip {
+    fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            gain_db: AtomicU32::new(0f32.to_bits()),
+            pan: AtomicU32::new(0f32.to_bits()),
+            mute: AtomicBool::new(false),
+        }
+    }
+
+    fn load(&self) -> ChannelStrip {
+        ChannelStrip {
+            enabled: self.enabled.load(Ordering::Relaxed),
+            gain_db: f32::from_bits(self.gain_db.load(Ordering::Relaxed)),
+            pan: f32::from_bits(self.pan.load(Ordering::Relaxed)),
+            mute: self.mute.load(Ordering::Relaxed),
+        }
+    }
+
+    fn store(&self, strip: ChannelStrip) {
+        let strip = strip.clamped();
+        self.gain_db.store(strip.gain_db.to_bits(), Ordering::Relaxed);
+        self.pan.store(strip.pan.to_bits(), Ordering::Relaxed);
+        self.mute.store(strip.mute, Ordering::Relaxed);
+        self.enabled.store(strip.enabled, Ordering::Relaxed);
+    }
+}
+
+/// Mixer state shared with the audio thread
+pub struct Mixer {
+    enabled: AtomicBool,
+    master_gain_db: AtomicU32,
+    signal: AtomicStrip,
+    sample: AtomicStrip,
+    live: AtomicStrip,
+}
+
+impl Mixer {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            master_gain_db: AtomicU32::new(0f32.to_bits()),
+            signal: AtomicStrip::new(),
+            sample: AtomicStrip::new(),
+            live: AtomicStrip::new(),
+        }
+    }
+
+    fn strip(&self, source: MixerSource) -> &AtomicStrip {
+        match source {
+            MixerSource::Signal => &self.signal,
+            MixerSource::Sample => &self.sample,
+            MixerSource::Live => &self.live,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn channel(&self, source: MixerSource) -> ChannelStrip {
+        self.strip(source).load()
+    }
+
+    pub fn set_channel(&self, source: MixerSource, strip: ChannelStrip) {
+        self.strip(source).store(strip);
+    }
+
+    pub fn master_gain(&self) -> f32 {
+        db_to_linear(self.master_gain_db())
+    }
+
+    pub fn master_gain_db(&self) -> f32 {
+        f32::from_bits(self.master_gain_db.load(Ordering::Relaxed))
+    }
+
+    pub fn set_master_gain_db(&self, gain_db: f32) {
+        self.master_gain_db
+            .store(gain_db.clamp(-60.0, 12.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn settings(&self) -> MixerSettings {
+        MixerSettings {
+            enabled: self.is_enabled(),
+            master_gain_db: self.master_gain_db(),
+            signal: self.signal.load(),
+            sample: self.sample.load(),
+            live: self.live.load(),
+        }
+    }
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
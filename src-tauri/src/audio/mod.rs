@@ -7,13 +7,22 @@
 //! - CLAP plugin hosting with hot reload
 //! - MIDI input for instrument plugins
 
+pub mod automation;
 pub mod buffer;
 pub mod device;
+pub mod distortion;
 pub mod engine;
+pub mod features;
 pub mod input;
+pub mod limiter;
 pub mod midi;
+pub mod mixer;
+pub mod offline;
 pub mod plugin;
+pub mod sample_stream;
 pub mod samples;
 pub mod signals;
 pub mod spectrum;
 pub mod stereo;
+pub mod stretch;
+pub mod watchdog;
@@ -0,0 +1,277 @@
+//! Offline rendering through a CLAP plugin
+//!
+//! Renders a buffer through a plugin bundle faster than real time, outside the
+//! preview engine, so two builds of the same plugin can be compared on
+//! identical input.
+
+use serde::Serialize;
+use std::path::Path;
+
+use super::buffer::StereoSample;
+use super::plugin::PluginInstance;
+use super::signals::{SignalConfig, SignalGenerator, SignalType};
+use super::spectrum::{SpectrumAnalyzer, NUM_BANDS};
+
+/// Block size used for offline processing
+const RENDER_BLOCK_SIZE: usize = 512;
+
+/// Silence appended to the input so reverb/delay tails are compared too (seconds)
+const RENDER_TAIL_SECS: f32 = 1.0;
+
+/// Analysis frame length for the spectrogram comparison (matches the analyzer FFT)
+const SPECTROGRAM_FRAME: usize = 2048;
+
+/// Error from a render cancelled by its `on_block` callback
+pub const RENDER_CANCELLED: &str = "Cancelled";
+
+/// Scratch buffers for pushing blocks of stereo samples through a plugin,
+/// which processes interleaved audio
+pub struct BlockProcessor {
+    in_block: Vec<f32>,
+    out_block: Vec<f32>,
+}
+
+impl BlockProcessor {
+    pub fn new(max_block_size: usize) -> Self {
+        Self {
+            in_block: vec![0.0; max_block_size * 2],
+            out_block: vec![0.0; max_block_size * 2],
+        }
+    }
+
+    /// Process one block (at most the max block size), returning the interleaved output
+    /// Doesn't check for a crash - callers decide whether that's an error.
+    pub fn process(&mut self, plugin: &mut PluginInstance, input: &[StereoSample]) -> Result<&[f32], String> {
+        let samples = input.len() * 2;
+        for (i, s) in input.iter().enumerate() {
+            self.in_block[i * 2] = s.left;
+            self.in_block[i * 2 + 1] = s.right;
+        }
+        plugin.process(&self.in_block[..samples], &mut self.out_block[..samples])?;
+        Ok(&self.out_block[..samples])
+    }
+}
+
+/// Interleaved plugin output as stereo samples
+pub fn deinterleave(block: &[f32]) -> impl Iterator<Item = StereoSample> + '_ {
+    block.chunks_exact(2).map(|s| StereoSample::new(s[0], s[1]))
+}
+
+/// Render a stereo buffer through a plugin bundle
+///
+/// The plugin is loaded fresh, fed `input` followed by a short silent tail, and
+/// unloaded again. Output has the same length as input + tail.
+/// `on_block(frames_done, total_frames)` is called after every block; returning
+/// false cancels the render.
+pub fn render_through_plugin(
+    bundle_path: &Path,
+    input: &[StereoSample],
+    sample_rate: u32,
+    on_block: impl FnMut(usize, usize) -> bool,
+) -> Result<Vec<StereoSample>, String> {
+    render_with_state(bundle_path, None, input, sample_rate, on_block)
+}
+
+/// Like `render_through_plugin`, but restores `state` (a CLAP state snapshot,
+/// e.g. the preview's current settings) before processing starts
+pub fn render_with_state(
+    bundle_path: &Path,
+    state: Option<&[u8]>,
+    input: &[StereoSample],
+    sample_rate: u32,
+    mut on_block: impl FnMut(usize, usize) -> bool,
+) -> Result<Vec<StereoSample>, String> {
+    let mut plugin = PluginInstance::load(bundle_path, sample_rate as f64, RENDER_BLOCK_SIZE as u32)?;
+    if let Some(state) = state {
+        plugin.load_state(state)?;
+    }
+    plugin.start_processing()?;
+
+    let tail = (RENDER_TAIL_SECS * sample_rate as f32) as usize;
+    let total = input.len() + tail;
+
+    let mut output = Vec::with_capacity(total);
+    let mut processor = BlockProcessor::new(RENDER_BLOCK_SIZE);
+    let mut block = Vec::with_capacity(RENDER_BLOCK_SIZE);
+
+    let mut pos = 0;
+    while pos < total {
+        let frames = RENDER_BLOCK_SIZE.min(total - pos);
+        block.clear();
+        block.extend((pos..pos + frames).map(|i| input.get(i).copied().unwrap_or_else(StereoSample::silence)));
+
+        let processed = processor.process(&mut plugin, &block)?;
+        if plugin.has_crashed() {
+            return Err(format!("Plugin crashed while rendering: {:?}", bundle_path));
+        }
+        output.extend(deinterleave(processed));
+        pos += frames;
+
+        if !on_block(pos, total) {
+            plugin.stop_processing();
+            return Err(RENDER_CANCELLED.to_string());
+        }
+    }
+
+    plugin.stop_processing();
+    Ok(output)
+}
+
+/// Built-in comparison material: a full-range sine sweep followed by pink noise
+pub fn default_test_program(sample_rate: u32) -> Vec<StereoSample> {
+    let mut generator = SignalGenerator::new(sample_rate);
+    let mut out = Vec::new();
+
+    let sweep_secs = 3.0;
+    generator.set_config(SignalConfig {
+        signal_type: SignalType::Sweep,
+        amplitude: 0.5,
+        sweep_start: 20.0,
+        sweep_end: 20000.0f32.min(sample_rate as f32 / 2.0),
+        sweep_duration: sweep_secs,
+        ..SignalConfig::default()
+    });
+    for _ in 0..(sweep_secs * sample_rate as f32) as usize {
+        out.push(generator.next_sample());
+    }
+
+    generator.set_config(SignalConfig {
+        signal_type: SignalType::PinkNoise,
+        amplitude: 0.3,
+        ..SignalConfig::default()
+    });
+    for _ in 0..(2 * sample_rate) as usize {
+        out.push(generator.next_sample());
+    }
+
+    out
+}
+
+/// Difference metrics between two renders
+#[derive(Serialize, Clone, Debug)]
+pub struct RenderComparison {
+    /// Largest absolute sample difference (dBFS)
+    pub peak_difference_db: f32,
+    /// RMS of the difference signal (dBFS) - the null test residual
+    pub null_rms_db: f32,
+    /// RMS of the reference render (dBFS), for judging the null depth
+    pub reference_rms_db: f32,
+    /// Largest per-band spectral difference across all frames (dB)
+    pub max_spectral_difference_db: f32,
+    /// Per-frame, per-band spectral difference (candidate minus reference, dB)
+    pub spectrogram_diff_db: Vec<Vec<f32>>,
+    /// Band center frequencies for the spectrogram rows
+    pub band_frequencies: Vec<f32>,
+}
+
+fn to_db(linear: f32) -> f32 {
+    if linear > 0.0 && linear.is_finite() {
+        (20.0 * linear.log10()).max(-200.0)
+    } else {
+        -200.0
+    }
+}
+
+/// Per-frame band magnitudes (dB) of a render, mono-summed
+fn band_frames(data: &[StereoSample], sample_rate: u32) -> (Vec<[f32; NUM_BANDS]>, [f32; NUM_BANDS]) {
+    let mut analyzer = SpectrumAnalyzer::new(sample_rate);
+    analyzer.set_smoothing(0.0);
+    let mut mono = vec![0.0f32; SPECTROGRAM_FRAME];
+
+    let frames = data
+        .chunks(SPECTROGRAM_FRAME)
+        .map(|chunk| {
+            mono.fill(0.0);
+            for (m, s) in mono.iter_mut().zip(chunk) {
+                *m = (s.left + s.right) * 0.5;
+            }
+            analyzer.push_samples(&mono);
+            analyzer.analyze();
+            analyzer.get_magnitudes().map(SpectrumAnalyzer::magnitude_to_db)
+        })
+        .collect();
+
+    (frames, analyzer.get_frequencies())
+}
+
+/// Compare a candidate render against a reference render
+pub fn compare_renders(
+    reference: &[StereoSample],
+    candidate: &[StereoSample],
+    sample_rate: u32,
+) -> RenderComparison {
+    let len = reference.len().max(candidate.len());
+    let at = |data: &[StereoSample], i: usize| data.get(i).copied().unwrap_or_else(StereoSample::silence);
+
+    let mut peak_diff = 0.0f32;
+    let mut diff_sum = 0.0f64;
+    let mut ref_sum = 0.0f64;
+    for i in 0..len {
+        let a = at(reference, i);
+        let b = at(candidate, i);
+        for (x, y) in [(a.left, b.left), (a.right, b.right)] {
+            let d = y - x;
+            peak_diff = peak_diff.max(d.abs());
+            diff_sum += (d as f64) * (d as f64);
+            ref_sum += (x as f64) * (x as f64);
+        }
+    }
+    let count = (len * 2).max(1) as f64;
+
+    // The two analyses are independent; run them on separate cores
+    let ((ref_bands, frequencies), (cand_bands, _)) = rayon::join(
+        || band_frames(reference, sample_rate),
+        || band_frames(candidate, sample_rate),
+    );
+    let mut max_spectral = 0.0f32;
+    let spectrogram_diff_db: Vec<Vec<f32>> = ref_bands
+        .iter()
+        .zip(&cand_bands)
+        .map(|(r, c)| {
+            r.iter()
+                .zip(c)
+                .map(|(r, c)| {
+                    let d = c - r;
+                    max_spectral = max_spectral.max(d.abs());
+                    d
+                })
+                .collect()
+        })
+        .collect();
+
+    RenderComparison {
+        peak_difference_db: to_db(peak_diff),
+        null_rms_db: to_db((diff_sum / count).sqrt() as f32),
+        reference_rms_db: to_db((ref_sum / count).sqrt() as f32),
+        max_spectral_difference_db: max_spectral,
+        spectrogram_diff_db,
+        band_frequencies: frequencies.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_renders_null_completely() {
+        let program = default_test_program(8000);
+        let result = compare_renders(&program, &program, 8000);
+        assert_eq!(result.peak_difference_db, -200.0);
+        assert_eq!(result.max_spectral_difference_db, 0.0);
+        assert!(result.reference_rms_db > -30.0);
+    }
+
+    #[test]
+    fn test_gain_change_is_detected() {
+        let program = default_test_program(8000);
+        let louder: Vec<StereoSample> = program
+            .iter()
+            .map(|s| StereoSample::new(s.left * 2.0, s.right * 2.0))
+            .collect();
+        let result = compare_renders(&program, &louder, 8000);
+        // Difference equals the reference itself
+        assert!((result.null_rms_db - result.reference_rms_db).abs() < 0.1);
+        assert!(result.max_spectral_difference_db > 5.0);
+    }
+}
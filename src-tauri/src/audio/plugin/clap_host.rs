@@ -5,6 +5,7 @@
 use super::clap_sys::*;
 #[cfg(target_os = "macos")]
 use super::editor;
+use super::editor::EditorWindowState;
 use crate::audio::midi::{MidiEvent, MidiEventQueue};
 use libloading::{Library, Symbol};
 use std::ffi::{CStr, CString};
@@ -72,6 +73,9 @@ pub struct PluginInstance {
     // Plugin path (kept for potential editor host use)
     _plugin_path: PathBuf,
 
+    // Binary actually loaded (inside the temp bundle copy)
+    dylib_path: PathBuf,
+
     // Temp bundle path (if we copied to avoid dylib caching)
     temp_bundle_path: Option<PathBuf>,
 
@@ -86,6 +90,9 @@ pub struct PluginInstance {
     // Direct editor window state (for in-process hosting by editor_host binary)
     #[cfg(target_os = "macos")]
     editor_window: Option<*mut std::ffi::c_void>,
+    // GUI size at 100% while the editor is shown at another scale
+    #[cfg(target_os = "macos")]
+    editor_base_size: Option<(u32, u32)>,
 
     // MIDI event handling
     /// Queue for incoming MIDI events (from commands, patterns, devices)
@@ -94,12 +101,30 @@ pub struct PluginInstance {
     midi_context: MidiEventContext,
     /// Pre-allocated buffer for draining MIDI events (avoids allocation in audio thread)
     midi_drain_buffer: Vec<MidiEvent>,
+    /// Parameter changes queued by the host, sent with the next process call
+    pending_param_changes: Mutex<Vec<(u32, f64)>>,
+    /// Parameter events the plugin reported during the last process call
+    param_output: ParamOutputContext,
 
     // Safety
     /// Set to true if the plugin panics during process - we'll output silence instead of crashing
     crashed: bool,
 }
 
+/// Parameter description reported to the frontend
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PluginParamInfo {
+    pub id: u32,
+    pub name: String,
+    pub module: String,
+    pub min_value: f64,
+    pub max_value: f64,
+    pub default_value: f64,
+    pub is_stepped: bool,
+    pub is_read_only: bool,
+    pub is_bypass: bool,
+}
+
 // Host callback structure (renamed to avoid conflict with ClapHost struct)
 #[repr(C)]
 struct ClapHost_ {
@@ -382,6 +407,7 @@ impl PluginInstance {
             input_data,
             output_data,
             _plugin_path: bundle_path.to_path_buf(),
+            dylib_path,
             temp_bundle_path,
             _editor_process: None,
             editor_open: false,
@@ -389,10 +415,14 @@ impl PluginInstance {
             state_apply_count: AtomicU32::new(0),
             #[cfg(target_os = "macos")]
             editor_window: None,
+            #[cfg(target_os = "macos")]
+            editor_base_size: None,
             midi_queue: Arc::new(MidiEventQueue::new(1024)),
             midi_context: MidiEventContext::new(),
             // Pre-allocate buffer for 256 events (covers typical usage without reallocation)
             midi_drain_buffer: Vec::with_capacity(256),
+            pending_param_changes: Mutex::new(Vec::with_capacity(64)),
+            param_output: ParamOutputContext::new(),
             crashed: false,
         };
 
@@ -553,6 +583,28 @@ impl PluginInstance {
         Ok(())
     }
 
+    /// Deactivate and re-activate at a new sample rate / max block size
+    /// Not for the audio thread - channel buffers are reallocated.
+    pub fn reactivate(&mut self, sample_rate: f64, max_frames: u32) -> Result<(), String> {
+        self.stop_processing();
+
+        if self.is_active {
+            let plugin_ref = unsafe { &*self.plugin };
+            if let Some(deactivate) = plugin_ref.deactivate {
+                unsafe { deactivate(self.plugin) };
+            }
+            self.is_active = false;
+        }
+
+        for ch in self.input_data.iter_mut().chain(self.output_data.iter_mut()) {
+            ch.clear();
+            ch.resize(max_frames as usize, 0.0);
+        }
+
+        self.activate(sample_rate, max_frames)?;
+        self.start_processing()
+    }
+
     /// Start audio processing
     pub fn start_processing(&mut self) -> Result<(), String> {
         if !self.is_active {
@@ -595,6 +647,8 @@ impl PluginInstance {
     /// Takes stereo input samples and returns stereo output samples.
     /// Input/output are interleaved: [L, R, L, R, ...]
     pub fn process(&mut self, input: &[f32], output: &mut [f32]) -> Result<(), String> {
+        self.param_output.clear();
+
         // If plugin has crashed, output silence to prevent repeated crashes
         if self.crashed {
             output.fill(0.0);
@@ -667,6 +721,13 @@ impl PluginInstance {
 
         // Convert MIDI events to CLAP format
         self.midi_context.clear();
+
+        // Host parameter changes go first (try_lock - never block the audio thread)
+        if let Ok(mut changes) = self.pending_param_changes.try_lock() {
+            for (param_id, value) in changes.drain(..) {
+                self.midi_context.add_param_value(param_id, value, 0);
+            }
+        }
         for event in self.midi_drain_buffer.iter() {
             match event {
                 MidiEvent::NoteOn { note, velocity, channel } => {
@@ -697,9 +758,10 @@ impl PluginInstance {
             get: Some(midi_input_events_get),
         };
 
+        // Collect parameter events the plugin sends back (GUI edits)
         let output_events = ClapOutputEvents {
-            ctx: ptr::null_mut(),
-            try_push: Some(empty_output_events_push),
+            ctx: &mut self.param_output as *mut ParamOutputContext as *mut std::ffi::c_void,
+            try_push: Some(param_output_events_push),
         };
 
         // Create process structure
@@ -776,6 +838,24 @@ impl PluginInstance {
         Ok(())
     }
 
+    /// The loaded binary and its load address, for attaching a debugger
+    pub fn module_info(&self) -> (PathBuf, Option<usize>) {
+        (self.dylib_path.clone(), self.module_base())
+    }
+
+    #[cfg(unix)]
+    fn module_base(&self) -> Option<usize> {
+        let mut info: libc::Dl_info = unsafe { std::mem::zeroed() };
+        // clap_entry lives in the plugin binary, so dladdr reports that image's base
+        let found = unsafe { libc::dladdr(self.entry as *const libc::c_void, &mut info) };
+        (found != 0 && !info.dli_fbase.is_null()).then(|| info.dli_fbase as usize)
+    }
+
+    #[cfg(not(unix))]
+    fn module_base(&self) -> Option<usize> {
+        None
+    }
+
     /// Check if the plugin has crashed during processing
     /// If true, the plugin will output silence until reloaded
     pub fn has_crashed(&self) -> bool {
@@ -833,6 +913,102 @@ impl PluginInstance {
         false
     }
 
+    /// Get the plugin's params extension (null if unsupported)
+    fn params_ext(&self) -> *const ClapPluginParams {
+        let plugin_ref = unsafe { &*self.plugin };
+        match plugin_ref.get_extension {
+            Some(get_ext) => unsafe {
+                get_ext(self.plugin, CLAP_EXT_PARAMS.as_ptr() as *const _) as *const ClapPluginParams
+            },
+            None => ptr::null(),
+        }
+    }
+
+    /// List the plugin's parameters
+    pub fn list_params(&self) -> Vec<PluginParamInfo> {
+        let params_ext = self.params_ext();
+        if params_ext.is_null() {
+            return Vec::new();
+        }
+        let (count_fn, get_info_fn) = match unsafe { ((*params_ext).count, (*params_ext).get_info) } {
+            (Some(c), Some(g)) => (c, g),
+            _ => return Vec::new(),
+        };
+
+        let count = unsafe { count_fn(self.plugin) };
+        let mut params = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            let mut info: ClapParamInfo = unsafe { std::mem::zeroed() };
+            if !unsafe { get_info_fn(self.plugin, index, &mut info) } {
+                continue;
+            }
+            let name = unsafe { CStr::from_ptr(info.name.as_ptr()) }
+                .to_string_lossy()
+                .to_string();
+            let module = unsafe { CStr::from_ptr(info.module.as_ptr()) }
+                .to_string_lossy()
+                .to_string();
+            params.push(PluginParamInfo {
+                id: info.id,
+                name,
+                module,
+                min_value: info.min_value,
+                max_value: info.max_value,
+                default_value: info.default_value,
+                is_stepped: info.flags & CLAP_PARAM_IS_STEPPED != 0,
+                is_read_only: info.flags & CLAP_PARAM_IS_READONLY != 0,
+                is_bypass: info.flags & CLAP_PARAM_IS_BYPASS != 0,
+            });
+        }
+        params
+    }
+
+    /// Get a parameter's current (plain) value
+    pub fn get_param_value(&self, param_id: u32) -> Option<f64> {
+        let params_ext = self.params_ext();
+        if params_ext.is_null() {
+            return None;
+        }
+        let get_value_fn = unsafe { (*params_ext).get_value }?;
+        let mut value = 0.0f64;
+        if unsafe { get_value_fn(self.plugin, param_id, &mut value) } {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Format a parameter value the way the plugin displays it (e.g. "-6.0 dB")
+    pub fn param_value_to_text(&self, param_id: u32, value: f64) -> Option<String> {
+        let params_ext = self.params_ext();
+        if params_ext.is_null() {
+            return None;
+        }
+        let value_to_text_fn = unsafe { (*params_ext).value_to_text }?;
+        let mut buffer = [0 as std::ffi::c_char; 256];
+        if !unsafe { value_to_text_fn(self.plugin, param_id, value, buffer.as_mut_ptr(), buffer.len() as u32) } {
+            return None;
+        }
+        let text = unsafe { CStr::from_ptr(buffer.as_ptr()) }
+            .to_string_lossy()
+            .trim()
+            .to_string();
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// Parameter events (values, gesture begin/end) the plugin reported during
+    /// the last process() call
+    pub fn param_output_events(&self) -> &[ParamOutputEvent] {
+        &self.param_output.events
+    }
+
+    /// Queue a parameter change to be delivered with the next process() call
+    pub fn queue_param_value(&self, param_id: u32, value: f64) {
+        if let Ok(mut changes) = self.pending_param_changes.lock() {
+            changes.push((param_id, value));
+        }
+    }
+
     /// Flush parameter changes without processing audio
     /// This is needed for the editor host where we don't call process()
     /// When the plugin's GUI changes a parameter, it calls host->request_flush()
@@ -1048,15 +1224,15 @@ impl PluginInstance {
         self.editor_open
     }
 
-    /// Open the plugin's editor window at a specific position (IN-PROCESS)
+    /// Open the plugin's editor window with saved placement (IN-PROCESS)
     ///
     /// This is the correct architecture: the plugin's GUI runs in the same process as audio,
     /// sharing the same atomic parameters. No IPC or state sync needed.
     ///
-    /// Position is (x, y) in macOS screen coordinates. Pass None to center the window.
+    /// Position is (x, y) in macOS screen coordinates. No position centers the window.
     #[cfg(target_os = "macos")]
-    pub fn open_editor_at(&mut self, position: Option<(f64, f64)>) -> Result<(), String> {
-        log::info!("open_editor_at: Called, editor_open={}, position={:?}", self.editor_open, position);
+    pub fn open_editor_at(&mut self, state: &EditorWindowState) -> Result<(), String> {
+        log::info!("open_editor_at: Called, editor_open={}, state={:?}", self.editor_open, state);
 
         // Check if editor is already open
         if self.editor_open {
@@ -1084,8 +1260,8 @@ impl PluginInstance {
 
         // Open the editor window in-process (same plugin instance as audio)
         // This means GUI parameter changes directly affect audio via shared atomics
-        log::info!("open_editor_at: Opening editor window in-process at position {:?}", position);
-        self.open_editor_window_at(position)?;
+        log::info!("open_editor_at: Opening editor window in-process");
+        self.open_editor_window_at(state)?;
 
         self.editor_open = true;
         log::info!("open_editor_at: Editor opened successfully (in-process, shared atomics)");
@@ -1096,12 +1272,12 @@ impl PluginInstance {
     /// Open the plugin's editor window centered (IN-PROCESS)
     #[cfg(target_os = "macos")]
     pub fn open_editor(&mut self) -> Result<(), String> {
-        self.open_editor_at(None)
+        self.open_editor_at(&EditorWindowState::default())
     }
 
     /// Open the plugin's editor window (stub for non-macOS)
     #[cfg(not(target_os = "macos"))]
-    pub fn open_editor_at(&mut self, _position: Option<(f64, f64)>) -> Result<(), String> {
+    pub fn open_editor_at(&mut self, _state: &EditorWindowState) -> Result<(), String> {
         Err("Plugin editor not supported on this platform".to_string())
     }
 
@@ -1117,6 +1293,67 @@ impl PluginInstance {
         self.get_editor_window_position()
     }
 
+    /// Get the current size of the editor GUI, or None if no window
+    /// While the GUI is shown at another scale this is its 100% size, so the
+    /// saved window size isn't affected by scale testing.
+    #[cfg(target_os = "macos")]
+    pub fn get_editor_size(&self) -> Option<(u32, u32)> {
+        self.editor_window?;
+        self.editor_base_size.or_else(|| unsafe { editor::get_gui_size(self.plugin) })
+    }
+
+    /// Stub for non-macOS platforms
+    #[cfg(not(target_os = "macos"))]
+    pub fn get_editor_size(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    /// Show the editor GUI at a scale factor (1.0 = the plugin's normal size)
+    /// Returns the GUI size at that scale.
+    #[cfg(target_os = "macos")]
+    pub fn set_editor_scale(&mut self, scale: f64) -> Result<(u32, u32), String> {
+        let window = self.editor_window.ok_or_else(|| "Plugin editor is not open".to_string())?;
+        let base_size = match self.editor_base_size {
+            Some(size) => size,
+            None => unsafe { editor::get_gui_size(self.plugin) }
+                .ok_or_else(|| "Failed to get the plugin GUI size".to_string())?,
+        };
+        let size = unsafe { editor::rescale_editor_window(self.plugin, window, base_size, scale)? };
+        self.editor_base_size = if scale == 1.0 { None } else { Some(base_size) };
+        log::info!("set_editor_scale: {}x -> {}x{}", scale, size.0, size.1);
+        Ok(size)
+    }
+
+    /// Stub for non-macOS platforms
+    #[cfg(not(target_os = "macos"))]
+    pub fn set_editor_scale(&mut self, _scale: f64) -> Result<(u32, u32), String> {
+        Err("Plugin editor not supported on this platform".to_string())
+    }
+
+    /// Change whether the open editor window stays above other windows
+    #[cfg(target_os = "macos")]
+    pub fn set_editor_always_on_top(&self, always_on_top: bool) {
+        if let Some(window) = self.editor_window {
+            editor::set_window_always_on_top(window, always_on_top);
+        }
+    }
+
+    /// Stub for non-macOS platforms
+    #[cfg(not(target_os = "macos"))]
+    pub fn set_editor_always_on_top(&self, _always_on_top: bool) {}
+
+    /// Change whether the open editor window sends every key to the plugin
+    #[cfg(target_os = "macos")]
+    pub fn set_editor_plugin_gets_all_keys(&self, all_keys: bool) {
+        if let Some(window) = self.editor_window {
+            editor::set_window_plugin_gets_all_keys(window, all_keys);
+        }
+    }
+
+    /// Stub for non-macOS platforms
+    #[cfg(not(target_os = "macos"))]
+    pub fn set_editor_plugin_gets_all_keys(&self, _all_keys: bool) {}
+
     /// Close the plugin's editor window (IN-PROCESS)
     /// Note: Position is NOT saved here - caller (AudioEngineHandle) should save it
     #[cfg(target_os = "macos")]
@@ -1151,15 +1388,15 @@ impl PluginInstance {
     /// Should only be called from the freqlab-editor-host binary.
     #[cfg(target_os = "macos")]
     pub fn open_editor_window(&mut self) -> Result<(), String> {
-        self.open_editor_window_at(None)
+        self.open_editor_window_at(&EditorWindowState::default())
     }
 
-    /// Open the plugin's editor window at a specific position
+    /// Open the plugin's editor window with saved placement
     ///
-    /// If position is None, the window will be centered on screen.
+    /// If the state has no position, the window will be centered on screen.
     #[cfg(target_os = "macos")]
-    pub fn open_editor_window_at(&mut self, position: Option<(f64, f64)>) -> Result<(), String> {
-        log::info!("open_editor_window_at: Called with position {:?}", position);
+    pub fn open_editor_window_at(&mut self, state: &EditorWindowState) -> Result<(), String> {
+        log::info!("open_editor_window_at: Called with state {:?}", state);
 
         if self.editor_window.is_some() {
             log::info!("open_editor_window_at: Window already exists");
@@ -1172,10 +1409,11 @@ impl PluginInstance {
 
         // Create the editor window directly using the editor module
         let (window, _content_view) = unsafe {
-            editor::create_editor_window_at(self.plugin, &self.name, position)?
+            editor::create_editor_window_at(self.plugin, &self.name, state)?
         };
 
         self.editor_window = Some(window);
+        editor::register_window(editor::PreviewWindowKind::PluginEditor, window);
         log::info!("open_editor_window_at: Window created successfully");
         Ok(())
     }
@@ -1193,7 +1431,9 @@ impl PluginInstance {
     pub fn close_editor_window(&mut self) {
         log::info!("close_editor_window: Called (direct/in-process)");
 
+        self.editor_base_size = None;
         if let Some(window) = self.editor_window.take() {
+            editor::unregister_window(editor::PreviewWindowKind::PluginEditor);
             unsafe {
                 editor::destroy_editor_window(self.plugin, window);
             }
@@ -1221,7 +1461,7 @@ impl PluginInstance {
 
     /// Stub for non-macOS platforms
     #[cfg(not(target_os = "macos"))]
-    pub fn open_editor_window_at(&mut self, _position: Option<(f64, f64)>) -> Result<(), String> {
+    pub fn open_editor_window_at(&mut self, _state: &EditorWindowState) -> Result<(), String> {
         Err("GUI not supported on this platform".to_string())
     }
 
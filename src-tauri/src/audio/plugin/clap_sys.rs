@@ -257,6 +257,24 @@ pub struct ClapEventMidi {
     pub data: [u8; 3],
 }
 
+#[repr(C)]
+pub struct ClapEventParamValue {
+    pub header: ClapEventHeader,
+    pub param_id: u32,
+    pub cookie: *mut c_void,
+    pub note_id: i32,
+    pub port_index: i16,
+    pub channel: i16,
+    pub key: i16,
+    pub value: f64,
+}
+
+#[repr(C)]
+pub struct ClapEventParamGesture {
+    pub header: ClapEventHeader,
+    pub param_id: u32,
+}
+
 // =============================================================================
 // Null implementations for input/output events (empty event lists)
 // =============================================================================
@@ -289,6 +307,8 @@ pub unsafe extern "C" fn empty_output_events_push(
 /// Context structure for passing MIDI events to the plugin
 /// This is stored and passed via the ClapInputEvents ctx field
 pub struct MidiEventContext {
+    /// Pre-allocated storage for parameter value events (host-driven automation)
+    pub param_events: Vec<ClapEventParamValue>,
     /// Pre-allocated storage for note events
     pub note_events: Vec<ClapEventNote>,
     /// Pre-allocated storage for raw MIDI events (CC, pitch bend, etc.)
@@ -298,6 +318,7 @@ pub struct MidiEventContext {
 impl MidiEventContext {
     pub fn new() -> Self {
         Self {
+            param_events: Vec::with_capacity(64),
             note_events: Vec::with_capacity(64), // Pre-allocate for typical use
             midi_events: Vec::with_capacity(32), // CC and pitch bend
         }
@@ -305,13 +326,35 @@ impl MidiEventContext {
 
     /// Clear events for next process cycle
     pub fn clear(&mut self) {
+        self.param_events.clear();
         self.note_events.clear();
         self.midi_events.clear();
     }
 
     /// Get total event count (for callback)
     pub fn len(&self) -> usize {
-        self.note_events.len() + self.midi_events.len()
+        self.param_events.len() + self.note_events.len() + self.midi_events.len()
+    }
+
+    /// Add a parameter value change (plain value, not normalized)
+    pub fn add_param_value(&mut self, param_id: u32, value: f64, time: u32) {
+        self.param_events.push(ClapEventParamValue {
+            header: ClapEventHeader {
+                size: std::mem::size_of::<ClapEventParamValue>() as u32,
+                time,
+                space_id: 0,
+                type_: CLAP_EVENT_PARAM_VALUE,
+                flags: 0,
+            },
+            param_id,
+            cookie: std::ptr::null_mut(),
+            // -1 = applies to all notes/ports/channels/keys
+            note_id: -1,
+            port_index: -1,
+            channel: -1,
+            key: -1,
+            value,
+        });
     }
 
     /// Add a note on event
@@ -395,7 +438,8 @@ pub unsafe extern "C" fn midi_input_events_size(list: *const ClapInputEvents) ->
 }
 
 /// Callback: return event at index from the context
-/// Events are indexed: note_events first, then midi_events
+/// Events are indexed: param_events first (so notes see new values), then
+/// note_events, then midi_events
 pub unsafe extern "C" fn midi_input_events_get(
     list: *const ClapInputEvents,
     index: u32,
@@ -404,7 +448,11 @@ pub unsafe extern "C" fn midi_input_events_get(
     if ctx.is_null() {
         return std::ptr::null();
     }
-    let idx = index as usize;
+    let param_count = (*ctx).param_events.len();
+    if (index as usize) < param_count {
+        return &(&(*ctx).param_events)[index as usize].header as *const ClapEventHeader;
+    }
+    let idx = index as usize - param_count;
     let note_count = (*ctx).note_events.len();
 
     if idx < note_count {
@@ -418,6 +466,81 @@ pub unsafe extern "C" fn midi_input_events_get(
     }
 }
 
+// =============================================================================
+// Parameter Output Events (plugin -> host)
+// =============================================================================
+
+/// Parameter event the plugin reported to the host (e.g. a GUI knob turn)
+/// `time` is the sample offset within the process block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamOutputEvent {
+    Value { param_id: u32, value: f64, time: u32 },
+    GestureBegin { param_id: u32, time: u32 },
+    GestureEnd { param_id: u32, time: u32 },
+}
+
+/// Context collecting parameter events pushed to the output event list
+/// This is stored and passed via the ClapOutputEvents ctx field
+pub struct ParamOutputContext {
+    /// Pre-allocated storage - events beyond capacity are rejected, not allocated
+    pub events: Vec<ParamOutputEvent>,
+}
+
+impl ParamOutputContext {
+    pub fn new() -> Self {
+        Self {
+            events: Vec::with_capacity(256),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+/// Callback: collect parameter events, ignore everything else
+pub unsafe extern "C" fn param_output_events_push(
+    list: *const ClapOutputEvents,
+    event: *const ClapEventHeader,
+) -> bool {
+    let ctx = (*list).ctx as *mut ParamOutputContext;
+    if ctx.is_null() || event.is_null() || (*event).space_id != 0 {
+        return true;
+    }
+    let events = &mut (*ctx).events;
+    if events.len() == events.capacity() {
+        return false;
+    }
+
+    let time = (*event).time;
+    match (*event).type_ {
+        CLAP_EVENT_PARAM_VALUE => {
+            let e = &*(event as *const ClapEventParamValue);
+            events.push(ParamOutputEvent::Value {
+                param_id: e.param_id,
+                value: e.value,
+                time,
+            });
+        }
+        CLAP_EVENT_PARAM_GESTURE_BEGIN => {
+            let e = &*(event as *const ClapEventParamGesture);
+            events.push(ParamOutputEvent::GestureBegin {
+                param_id: e.param_id,
+                time,
+            });
+        }
+        CLAP_EVENT_PARAM_GESTURE_END => {
+            let e = &*(event as *const ClapEventParamGesture);
+            events.push(ParamOutputEvent::GestureEnd {
+                param_id: e.param_id,
+                time,
+            });
+        }
+        _ => {}
+    }
+    true
+}
+
 // =============================================================================
 // GUI Extension
 // =============================================================================
@@ -668,6 +791,10 @@ pub struct ClapPluginParams {
     >,
 }
 
+pub const CLAP_PARAM_IS_STEPPED: u32 = 1 << 0;
+pub const CLAP_PARAM_IS_READONLY: u32 = 1 << 3;
+pub const CLAP_PARAM_IS_BYPASS: u32 = 1 << 4;
+
 /// Parameter information structure
 #[repr(C)]
 pub struct ClapParamInfo {
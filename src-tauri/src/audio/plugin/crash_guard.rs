@@ -4,12 +4,15 @@
 //! during plugin processing. This allows the host to survive plugin crashes and
 //! notify the user instead of crashing the entire application.
 //!
+//! The handler also records the faulting address and the raw stack frames so
+//! the crash can be symbolicated against the plugin's debug symbols afterwards.
+//!
 //! # Safety
 //! This module uses unsafe signal handling. It's designed specifically for the
 //! audio processing context where a crash would otherwise terminate the app.
 
 use std::cell::UnsafeCell;
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
 
 // sigjmp_buf size varies by platform and architecture
 // Using a conservative size that should work on all platforms
@@ -67,6 +70,24 @@ static CRASH_CAUGHT: AtomicBool = AtomicBool::new(false);
 /// The signal that was caught
 static CRASH_SIGNAL: AtomicI32 = AtomicI32::new(0);
 
+/// Stack frames kept from a caught crash
+const MAX_CRASH_FRAMES: usize = 64;
+
+/// Address that faulted (si_addr) in the last caught crash
+static CRASH_FAULT_ADDRESS: AtomicUsize = AtomicUsize::new(0);
+
+/// Array initializer for `CRASH_FRAMES` (inline `const {}` blocks need Rust 1.79)
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO_FRAME: AtomicUsize = AtomicUsize::new(0);
+
+/// Return addresses captured in the signal handler for the last caught crash
+static CRASH_FRAMES: [AtomicUsize; MAX_CRASH_FRAMES] = [ZERO_FRAME; MAX_CRASH_FRAMES];
+static CRASH_FRAME_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" {
+    fn backtrace(buffer: *mut *mut libc::c_void, size: libc::c_int) -> libc::c_int;
+}
+
 /// Previous SIGABRT handler
 static mut PREV_SIGABRT: libc::sigaction = unsafe { std::mem::zeroed() };
 
@@ -79,12 +100,26 @@ static mut PREV_SIGBUS: libc::sigaction = unsafe { std::mem::zeroed() };
 /// Whether handlers are installed
 static HANDLERS_INSTALLED: AtomicBool = AtomicBool::new(false);
 
+/// Record where the crash happened (fixed buffers only - we're in a signal handler)
+unsafe fn record_crash_context(info: *mut libc::siginfo_t) {
+    let fault_address = if info.is_null() { 0 } else { (*info).si_addr() as usize };
+    CRASH_FAULT_ADDRESS.store(fault_address, Ordering::SeqCst);
+
+    let mut frames = [std::ptr::null_mut::<libc::c_void>(); MAX_CRASH_FRAMES];
+    let count = backtrace(frames.as_mut_ptr(), MAX_CRASH_FRAMES as libc::c_int).max(0) as usize;
+    for (slot, frame) in CRASH_FRAMES.iter().zip(&frames[..count]) {
+        slot.store(*frame as usize, Ordering::SeqCst);
+    }
+    CRASH_FRAME_COUNT.store(count, Ordering::SeqCst);
+}
+
 /// Signal handler that jumps back to safety
-extern "C" fn crash_signal_handler(sig: libc::c_int) {
+extern "C" fn crash_signal_handler(sig: libc::c_int, info: *mut libc::siginfo_t, context: *mut libc::c_void) {
     // Only jump if we have an active guard
     if JUMP_BUFFER.active.load(Ordering::SeqCst) {
         CRASH_CAUGHT.store(true, Ordering::SeqCst);
         CRASH_SIGNAL.store(sig, Ordering::SeqCst);
+        unsafe { record_crash_context(info) };
 
         // SAFETY: We're jumping back to a valid setjmp point set up by with_crash_guard
         unsafe {
@@ -106,9 +141,15 @@ extern "C" fn crash_signal_handler(sig: libc::c_int) {
         let handler = (*prev).sa_sigaction as usize;
 
         if handler != libc::SIG_DFL && handler != libc::SIG_IGN {
-            // Call the previous handler
-            let func: extern "C" fn(libc::c_int) = std::mem::transmute(handler);
-            func(sig);
+            // Call the previous handler with the signature it was installed with
+            if (*prev).sa_flags & libc::SA_SIGINFO != 0 {
+                let func: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                    std::mem::transmute(handler);
+                func(sig, info, context);
+            } else {
+                let func: extern "C" fn(libc::c_int) = std::mem::transmute(handler);
+                func(sig);
+            }
         } else {
             // Re-raise with default handler
             libc::signal(sig, libc::SIG_DFL);
@@ -127,10 +168,9 @@ fn install_handlers() {
         let mut action: libc::sigaction = std::mem::zeroed();
 
         // Set the signal handler
-        // We use sa_sigaction field but WITHOUT SA_SIGINFO flag,
-        // which means the kernel will call it as a simple 1-arg handler
+        // SA_SIGINFO so the handler gets the faulting address
         action.sa_sigaction = crash_signal_handler as usize;
-        action.sa_flags = 0; // No SA_SIGINFO - use simple 1-arg handler
+        action.sa_flags = libc::SA_SIGINFO;
         libc::sigemptyset(&mut action.sa_mask);
 
         // Install handlers and save previous ones
@@ -220,8 +260,31 @@ where
     }
 }
 
+/// Where the last caught crash happened
+#[derive(Debug, Clone)]
+pub struct CrashContext {
+    pub signal: i32,
+    /// Faulting memory address (0 when the signal doesn't carry one, e.g. SIGABRT)
+    pub fault_address: usize,
+    /// Raw return addresses, innermost first (starts inside the signal handler)
+    pub frames: Vec<usize>,
+}
+
+/// Details of the most recent crash caught by `with_crash_guard`
+pub fn last_crash_context() -> Option<CrashContext> {
+    let count = CRASH_FRAME_COUNT.load(Ordering::SeqCst);
+    if count == 0 {
+        return None;
+    }
+    Some(CrashContext {
+        signal: CRASH_SIGNAL.load(Ordering::SeqCst),
+        fault_address: CRASH_FAULT_ADDRESS.load(Ordering::SeqCst),
+        frames: CRASH_FRAMES[..count].iter().map(|f| f.load(Ordering::SeqCst)).collect(),
+    })
+}
+
 /// Get a human-readable name for a signal
-fn signal_name(sig: i32) -> &'static str {
+pub fn signal_name(sig: i32) -> &'static str {
     match sig {
         libc::SIGABRT => "SIGABRT (abort)",
         libc::SIGSEGV => "SIGSEGV (segmentation fault)",
@@ -2,7 +2,14 @@
 //!
 //! Opens the plugin's native GUI in a standalone window.
 //! On macOS, this creates an NSWindow and passes the content view to the plugin.
+//!
+//! A small window registry tracks every native preview window (plugin editor,
+//! analyzer) so they can be open side by side and managed independently.
 
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::ffi::c_void;
 
 use super::clap_sys::{ClapPlugin, ClapPluginGui, ClapWindow, CLAP_EXT_GUI};
@@ -10,6 +17,145 @@ use super::clap_sys::{ClapPlugin, ClapPluginGui, ClapWindow, CLAP_EXT_GUI};
 #[cfg(target_os = "macos")]
 use super::clap_sys::CLAP_WINDOW_API_COCOA;
 
+/// Editor window placement, restored whenever the window is (re)created
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorWindowState {
+    /// Window origin in screen coordinates (centered if None)
+    pub position: Option<(f64, f64)>,
+    /// GUI size - only applied if the plugin GUI can resize
+    pub size: Option<(u32, u32)>,
+    /// Keep the window above other windows
+    #[serde(rename = "alwaysOnTop")]
+    pub always_on_top: bool,
+    /// Send every key to the plugin, including host-reserved shortcuts
+    #[serde(rename = "pluginGetsAllKeys")]
+    pub plugin_gets_all_keys: bool,
+}
+
+impl Default for EditorWindowState {
+    fn default() -> Self {
+        Self {
+            position: None,
+            size: None,
+            // Matches the floating level editor windows have always used
+            always_on_top: true,
+            plugin_gets_all_keys: false,
+        }
+    }
+}
+
+/// Host actions bound to keys in the plugin editor window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostShortcut {
+    /// Start or stop preview playback
+    TogglePlayback,
+    /// Close the editor window
+    CloseEditor,
+}
+
+/// Keys the host keeps for itself unless the plugin gets all keys:
+/// (key as typed without modifiers, needs Cmd/Ctrl, action)
+pub const HOST_RESERVED_SHORTCUTS: [(&str, bool, HostShortcut); 2] = [
+    (" ", false, HostShortcut::TogglePlayback),
+    ("w", true, HostShortcut::CloseEditor),
+];
+
+/// Host shortcut for a key press, if it's reserved
+/// Presses with other modifiers (Shift+Space, Cmd+Shift+W) go to the plugin.
+pub fn host_shortcut_for(key: &str, command: bool, other_modifiers: bool) -> Option<HostShortcut> {
+    if other_modifiers {
+        return None;
+    }
+    let key = key.to_lowercase();
+    HOST_RESERVED_SHORTCUTS
+        .iter()
+        .find(|(k, cmd, _)| *k == key && *cmd == command)
+        .map(|(_, _, action)| *action)
+}
+
+type ShortcutHandler = Box<dyn Fn(HostShortcut) + Send + Sync>;
+
+/// Runs host shortcuts that need more than the window (set once by the preview)
+static SHORTCUT_HANDLER: Lazy<Mutex<Option<ShortcutHandler>>> = Lazy::new(|| Mutex::new(None));
+
+/// Set the function that performs host shortcuts pressed in the editor window
+pub fn set_host_shortcut_handler(handler: impl Fn(HostShortcut) + Send + Sync + 'static) {
+    *SHORTCUT_HANDLER.lock() = Some(Box::new(handler));
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn run_shortcut_handler(action: HostShortcut) {
+    match SHORTCUT_HANDLER.lock().as_ref() {
+        Some(handler) => handler(action),
+        None => log::warn!("No handler for editor shortcut {:?}", action),
+    }
+}
+
+/// Native preview windows that can be open at the same time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewWindowKind {
+    /// The hosted plugin's own GUI
+    PluginEditor,
+    /// Output level and spectrum meters
+    Analyzer,
+}
+
+/// Meter values pushed into the analyzer window
+pub struct AnalyzerFrame<'a> {
+    pub left_db: f32,
+    pub right_db: f32,
+    /// Band magnitudes (0.0 - 1.0), as produced by the spectrum analyzer
+    pub spectrum: &'a [f32],
+}
+
+/// Open native windows by kind
+/// Pointers are stored as usize so the registry can live in a static
+static WINDOW_REGISTRY: Lazy<Mutex<HashMap<PreviewWindowKind, usize>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Track a newly created window
+pub fn register_window(kind: PreviewWindowKind, window: *mut c_void) {
+    WINDOW_REGISTRY.lock().insert(kind, window as usize);
+}
+
+/// Stop tracking a window, returning its pointer if it was registered
+pub fn unregister_window(kind: PreviewWindowKind) -> Option<*mut c_void> {
+    WINDOW_REGISTRY.lock().remove(&kind).map(|w| w as *mut c_void)
+}
+
+/// Get the window registered for a kind
+pub fn registered_window(kind: PreviewWindowKind) -> Option<*mut c_void> {
+    WINDOW_REGISTRY.lock().get(&kind).map(|&w| w as *mut c_void)
+}
+
+/// Kinds of all registered windows (including ones the user has since closed)
+pub fn registered_windows() -> Vec<PreviewWindowKind> {
+    WINDOW_REGISTRY.lock().keys().copied().collect()
+}
+
+/// Registered windows the user closed with the close button
+/// They stay registered until their owner tears them down
+pub fn manually_closed_windows() -> Vec<PreviewWindowKind> {
+    // Copy out first - the visibility check dispatches to the main thread
+    let windows: Vec<(PreviewWindowKind, usize)> =
+        WINDOW_REGISTRY.lock().iter().map(|(&k, &w)| (k, w)).collect();
+    windows
+        .into_iter()
+        .filter(|&(_, window)| !is_window_visible(window as *mut c_void))
+        .map(|(kind, _)| kind)
+        .collect()
+}
+
+/// Bring a registered window to the front (restoring it from the dock if minimized)
+pub fn focus_window(kind: PreviewWindowKind) -> Result<(), String> {
+    let window = registered_window(kind).ok_or_else(|| format!("{:?} window is not open", kind))?;
+    restore_window(window);
+    Ok(())
+}
+
 /// Get the GUI extension from a plugin
 pub unsafe fn get_gui_extension(plugin: *const ClapPlugin) -> Option<*const ClapPluginGui> {
     let get_extension = (*plugin).get_extension?;
@@ -43,6 +189,37 @@ pub unsafe fn supports_gui(plugin: *const ClapPlugin) -> bool {
     }
 }
 
+/// Scale factors offered for checking a GUI at other pixel densities
+pub const EDITOR_SCALES: [f64; 3] = [0.75, 1.0, 1.5];
+
+/// Size a GUI should take at a scale factor, from its size at 100%
+pub fn scaled_gui_size(base: (u32, u32), scale: f64) -> (u32, u32) {
+    let scale_dim = |d: u32| ((d as f64 * scale).round() as u32).max(1);
+    (scale_dim(base.0), scale_dim(base.1))
+}
+
+/// Ask a resizable plugin GUI to take a saved size (adjusted to its constraints)
+/// Returns false if the GUI can't resize or rejected the size
+pub unsafe fn restore_gui_size(plugin: *const ClapPlugin, width: u32, height: u32) -> bool {
+    let gui = match get_gui_extension(plugin) {
+        Some(g) => g,
+        None => return false,
+    };
+    let can_resize = (*gui).can_resize.map(|f| f(plugin)).unwrap_or(false);
+    if !can_resize {
+        return false;
+    }
+
+    let (mut width, mut height) = (width, height);
+    if let Some(adjust_size) = (*gui).adjust_size {
+        adjust_size(plugin, &mut width, &mut height);
+    }
+    match (*gui).set_size {
+        Some(set_size) => set_size(plugin, width, height),
+        None => false,
+    }
+}
+
 /// Get the preferred size of the plugin GUI
 pub unsafe fn get_gui_size(plugin: *const ClapPlugin) -> Option<(u32, u32)> {
     let gui = get_gui_extension(plugin)?;
@@ -66,12 +243,111 @@ pub unsafe fn get_gui_size(plugin: *const ClapPlugin) -> Option<(u32, u32)> {
 mod macos {
     use super::*;
     use objc2::rc::{autoreleasepool, Retained};
-    use objc2::{MainThreadMarker, MainThreadOnly};
+    use objc2::{define_class, msg_send, MainThreadMarker, MainThreadOnly};
+    use crate::audio::spectrum::NUM_BANDS;
     use objc2_app_kit::{
-        NSApplication, NSApplicationActivationPolicy, NSBackingStoreType, NSColor, NSWindow,
-        NSWindowStyleMask,
+        NSApplication, NSApplicationActivationPolicy, NSBackingStoreType, NSColor, NSEvent,
+        NSEventModifierFlags, NSEventType, NSLevelIndicator, NSLevelIndicatorStyle, NSResponder,
+        NSView, NSWindow, NSWindowOcclusionState, NSWindowStyleMask,
     };
-    use objc2_foundation::{NSPoint, NSRect, NSSize, NSThread};
+    use objc2_foundation::{NSObject, NSPoint, NSRect, NSSize, NSThread};
+    use std::cell::Cell;
+
+    /// State each editor window keeps for itself
+    struct EditorWindowIvars {
+        /// Send every key to the plugin, host shortcuts included
+        plugin_gets_all_keys: Cell<bool>,
+    }
+
+    define_class!(
+        /// Editor window that routes key presses: host shortcuts are handled here,
+        /// everything else goes to the plugin's view
+        #[unsafe(super(NSWindow, NSResponder, NSObject))]
+        #[thread_kind = MainThreadOnly]
+        #[name = "FreqlabEditorWindow"]
+        #[ivars = EditorWindowIvars]
+        struct EditorNSWindow;
+
+        impl EditorNSWindow {
+            #[unsafe(method(sendEvent:))]
+            fn send_event(&self, event: &NSEvent) {
+                if event.r#type() == NSEventType::KeyDown {
+                    if !self.plugin_gets_all_keys() && self.perform_host_shortcut(event) {
+                        return;
+                    }
+                    self.focus_plugin_view();
+                }
+                unsafe { msg_send![super(self), sendEvent: event] }
+            }
+
+            /// Cmd/Ctrl combinations arrive here before the app menu sees them
+            #[unsafe(method(performKeyEquivalent:))]
+            fn perform_key_equivalent(&self, event: &NSEvent) -> bool {
+                if self.plugin_gets_all_keys() {
+                    self.focus_plugin_view();
+                    if let Some(responder) = self.firstResponder() {
+                        responder.keyDown(event);
+                        return true;
+                    }
+                }
+                if self.perform_host_shortcut(event) {
+                    return true;
+                }
+                unsafe { msg_send![super(self), performKeyEquivalent: event] }
+            }
+        }
+    );
+
+    impl EditorNSWindow {
+        fn plugin_gets_all_keys(&self) -> bool {
+            self.ivars().plugin_gets_all_keys.get()
+        }
+
+        /// Run the host shortcut for a key press, returning false if it isn't one
+        fn perform_host_shortcut(&self, event: &NSEvent) -> bool {
+            let key = match event.charactersIgnoringModifiers() {
+                Some(chars) => chars.to_string(),
+                None => return false,
+            };
+            let flags = event.modifierFlags();
+            let command = flags.contains(NSEventModifierFlags::Command);
+            let other_modifiers = flags.intersects(
+                NSEventModifierFlags::Shift | NSEventModifierFlags::Option | NSEventModifierFlags::Control,
+            );
+            let action = match host_shortcut_for(&key, command, other_modifiers) {
+                Some(action) => action,
+                None => return false,
+            };
+            // Holding space shouldn't flip playback on every repeat
+            if event.isARepeat() {
+                return true;
+            }
+            log::info!("Editor window shortcut: {:?}", action);
+            match action {
+                HostShortcut::CloseEditor => unsafe { self.performClose(None) },
+                HostShortcut::TogglePlayback => run_shortcut_handler(action),
+            }
+            true
+        }
+
+        /// Make the plugin's view first responder if focus sits on the window itself
+        /// Plugins that don't claim focus when shown would otherwise never see keys
+        fn focus_plugin_view(&self) {
+            let content_view = match self.contentView() {
+                Some(v) => v,
+                None => return,
+            };
+            let focused = self.firstResponder().map(|r| Retained::as_ptr(&r) as usize);
+            let window_ptr = self as *const Self as usize;
+            let content_ptr = Retained::as_ptr(&content_view) as usize;
+            if focused.is_some_and(|f| f != window_ptr && f != content_ptr) {
+                return;
+            }
+            if let Some(plugin_view) = content_view.subviews().firstObject() {
+                self.makeFirstResponder(Some(&plugin_view));
+            }
+        }
+    }
 
     // FFI bindings for Grand Central Dispatch
     #[repr(C)]
@@ -109,14 +385,14 @@ mod macos {
         plugin: *const ClapPlugin,
         title: &str,
     ) -> Result<(*mut c_void, *mut c_void), String> {
-        create_editor_window_at(plugin, title, None)
+        create_editor_window_at(plugin, title, &EditorWindowState::default())
     }
 
     /// Context for main thread dispatch
     struct EditorWindowContext {
         plugin: *const ClapPlugin,
         title: String,
-        position: Option<(f64, f64)>,
+        state: EditorWindowState,
         result: Option<Result<(*mut c_void, *mut c_void), String>>,
     }
 
@@ -131,7 +407,7 @@ mod macos {
 
             log::info!("create_editor_window_on_main: Running on main thread");
 
-            ctx.result = Some(unsafe { create_editor_window_inner(ctx.plugin, &ctx.title, ctx.position) });
+            ctx.result = Some(unsafe { create_editor_window_inner(ctx.plugin, &ctx.title, &ctx.state) });
 
             log::info!(
                 "create_editor_window_on_main: Complete (success={})",
@@ -140,19 +416,20 @@ mod macos {
         });
     }
 
-    /// Create a native NSWindow for the plugin editor at a specific position
+    /// Create a native NSWindow for the plugin editor with saved placement
     ///
-    /// If position is None, the window will be centered on screen.
-    /// Position is (x, y) in screen coordinates.
+    /// If the state has no position, the window will be centered on screen.
+    /// Position is (x, y) in screen coordinates. A saved size is only restored
+    /// for resizable plugin GUIs.
     ///
     /// This function automatically dispatches to the main thread if not already on it,
     /// which is required for WebView-based plugins.
     pub unsafe fn create_editor_window_at(
         plugin: *const ClapPlugin,
         title: &str,
-        position: Option<(f64, f64)>,
+        state: &EditorWindowState,
     ) -> Result<(*mut c_void, *mut c_void), String> {
-        log::info!("create_editor_window_at called, position: {:?}", position);
+        log::info!("create_editor_window_at called, state: {:?}", state);
 
         let on_main = is_main_thread();
         log::info!("create_editor_window_at: on_main_thread = {}", on_main);
@@ -160,7 +437,7 @@ mod macos {
         if on_main {
             // Already on main thread, run directly
             log::info!("create_editor_window_at: Already on main thread, running directly");
-            create_editor_window_inner(plugin, title, position)
+            create_editor_window_inner(plugin, title, state)
         } else {
             // Dispatch to main thread synchronously
             log::info!("create_editor_window_at: Dispatching to main thread via GCD");
@@ -168,7 +445,7 @@ mod macos {
             let mut ctx = EditorWindowContext {
                 plugin,
                 title: title.to_string(),
-                position,
+                state: *state,
                 result: None,
             };
 
@@ -186,7 +463,7 @@ mod macos {
     unsafe fn create_editor_window_inner(
         plugin: *const ClapPlugin,
         title: &str,
-        position: Option<(f64, f64)>,
+        state: &EditorWindowState,
     ) -> Result<(*mut c_void, *mut c_void), String> {
         log::info!("create_editor_window_inner: Getting GUI extension");
         let gui = get_gui_extension(plugin)
@@ -213,6 +490,13 @@ mod macos {
             return Err("Failed to create plugin GUI".to_string());
         }
 
+        // Restore the saved size before asking for it (resizable GUIs only)
+        if let Some((width, height)) = state.size {
+            if restore_gui_size(plugin, width, height) {
+                log::info!("create_editor_window_inner: Restored GUI size {}x{}", width, height);
+            }
+        }
+
         log::info!("create_editor_window_inner: Getting GUI size");
         // Get the size
         let (width, height) = get_gui_size(plugin).unwrap_or((800, 600));
@@ -235,14 +519,18 @@ mod macos {
             | NSWindowStyleMask::Closable
             | NSWindowStyleMask::Miniaturizable;
 
-        // Create the window
-        let window = NSWindow::initWithContentRect_styleMask_backing_defer(
-            NSWindow::alloc(mtm),
-            frame,
-            style,
-            NSBackingStoreType::Buffered,
-            false,
-        );
+        // Create the window (our subclass, so key presses reach the plugin)
+        let window = EditorNSWindow::alloc(mtm).set_ivars(EditorWindowIvars {
+            plugin_gets_all_keys: Cell::new(state.plugin_gets_all_keys),
+        });
+        let window: Retained<EditorNSWindow> = msg_send![
+            super(window),
+            initWithContentRect: frame,
+            styleMask: style,
+            backing: NSBackingStoreType::Buffered,
+            defer: false
+        ];
+        let window: Retained<NSWindow> = Retained::into_super(window);
 
         log::info!("create_editor_window_inner: NSWindow created successfully");
 
@@ -318,7 +606,7 @@ mod macos {
         log::info!("create_editor_window_inner: Activated app");
 
         // Position the window
-        if let Some((x, y)) = position {
+        if let Some((x, y)) = state.position {
             // Set specific position
             let origin = NSPoint::new(x, y);
             window.setFrameOrigin(origin);
@@ -333,10 +621,14 @@ mod macos {
         window.makeKeyAndOrderFront(None);
         log::info!("create_editor_window_inner: makeKeyAndOrderFront called");
 
-        // Set window level to floating to ensure it appears above other windows
-        // NSFloatingWindowLevel = 3
-        window.setLevel(3);
-        log::info!("create_editor_window_inner: Set window level to floating");
+        // Start with the plugin's view focused so the first key press reaches it
+        if let Some(plugin_view) = content_view.subviews().firstObject() {
+            window.makeFirstResponder(Some(&plugin_view));
+        }
+
+        // Floating level keeps the editor above other windows when always-on-top
+        window.setLevel(window_level(state.always_on_top));
+        log::info!("create_editor_window_inner: always_on_top = {}", state.always_on_top);
 
         // Force display update
         window.display();
@@ -469,6 +761,155 @@ mod macos {
         Some((frame.origin.x, frame.origin.y))
     }
 
+    /// Get a window's frame as (x, y, width, height) with a top-left origin
+    /// relative to the primary display, the rect `screencapture -R` expects
+    pub fn get_window_capture_rect(window: *mut c_void) -> Option<(f64, f64, f64, f64)> {
+        if window.is_null() {
+            return None;
+        }
+
+        let mut rect = None;
+        run_on_main(|| {
+            let mtm = match MainThreadMarker::new() {
+                Some(m) => m,
+                None => return,
+            };
+            // Cocoa frames are bottom-left based on the primary (first) screen
+            let primary_height = match objc2_app_kit::NSScreen::screens(mtm).firstObject() {
+                Some(screen) => screen.frame().size.height,
+                None => return,
+            };
+            let frame = unsafe { (&*(window as *const NSWindow)).frame() };
+            let top = primary_height - (frame.origin.y + frame.size.height);
+            rect = Some((frame.origin.x, top, frame.size.width, frame.size.height));
+        });
+        rect
+    }
+
+    /// Rescale a plugin GUI and fit its window to the new size
+    /// Asks the plugin through the GUI extension's set_scale first. Cocoa GUIs
+    /// usually ignore it (they work in points), so when the size doesn't change
+    /// a resizable GUI is resized to the scaled size instead.
+    pub unsafe fn rescale_editor_window(
+        plugin: *const ClapPlugin,
+        window: *mut c_void,
+        base_size: (u32, u32),
+        scale: f64,
+    ) -> Result<(u32, u32), String> {
+        if window.is_null() {
+            return Err("Editor window is not open".to_string());
+        }
+        let gui = get_gui_extension(plugin).ok_or_else(|| "Plugin does not have GUI extension".to_string())?;
+
+        // CLAP GUI calls belong on the main thread
+        let mut result = Err("Editor rescale did not run".to_string());
+        run_on_main(|| {
+            let before = get_gui_size(plugin);
+            let accepted = (*gui).set_scale.map(|f| f(plugin, scale)).unwrap_or(false);
+            let rescaled = accepted && get_gui_size(plugin) != before;
+            if !rescaled {
+                let (width, height) = scaled_gui_size(base_size, scale);
+                if !restore_gui_size(plugin, width, height) {
+                    result = Err("The plugin ignored the scale request and its GUI can't be resized".to_string());
+                    return;
+                }
+            }
+            result = match get_gui_size(plugin) {
+                Some((width, height)) => {
+                    let window_ref = &*(window as *const NSWindow);
+                    window_ref.setContentSize(NSSize::new(width as f64, height as f64));
+                    Ok((width, height))
+                }
+                None => Err("Failed to get the plugin GUI size".to_string()),
+            };
+        });
+        result
+    }
+
+    /// NSFloatingWindowLevel = 3, NSNormalWindowLevel = 0
+    fn window_level(always_on_top: bool) -> isize {
+        if always_on_top {
+            3
+        } else {
+            0
+        }
+    }
+
+    /// Context for setting the window level on the main thread
+    struct WindowLevelContext {
+        window: *mut c_void,
+        always_on_top: bool,
+    }
+
+    extern "C" fn set_window_level_on_main(ctx: *mut std::ffi::c_void) {
+        let ctx = unsafe { &*(ctx as *const WindowLevelContext) };
+        if ctx.window.is_null() {
+            return;
+        }
+        unsafe {
+            let window_ref = &*(ctx.window as *const NSWindow);
+            window_ref.setLevel(window_level(ctx.always_on_top));
+        }
+    }
+
+    /// Toggle whether an editor window stays above other windows
+    /// This function dispatches to the main thread if needed
+    pub fn set_window_always_on_top(window: *mut c_void, always_on_top: bool) {
+        if window.is_null() {
+            return;
+        }
+
+        let mut ctx = WindowLevelContext { window, always_on_top };
+        if is_main_thread() {
+            set_window_level_on_main(&mut ctx as *mut WindowLevelContext as *mut std::ffi::c_void);
+        } else {
+            unsafe {
+                dispatch_sync_f(
+                    main_queue(),
+                    &mut ctx as *mut WindowLevelContext as *mut std::ffi::c_void,
+                    set_window_level_on_main,
+                );
+            }
+        }
+    }
+
+    /// Context for changing an editor window's key routing on the main thread
+    struct KeyRoutingContext {
+        window: *mut c_void,
+        all_keys: bool,
+    }
+
+    extern "C" fn set_key_routing_on_main(ctx: *mut std::ffi::c_void) {
+        let ctx = unsafe { &*(ctx as *const KeyRoutingContext) };
+        if ctx.window.is_null() {
+            return;
+        }
+        // Editor windows are always created as EditorNSWindow
+        let window_ref = unsafe { &*(ctx.window as *const EditorNSWindow) };
+        window_ref.ivars().plugin_gets_all_keys.set(ctx.all_keys);
+    }
+
+    /// Set whether an editor window forwards every key to its plugin
+    /// `window` must come from create_editor_window. Dispatches to the main thread if needed.
+    pub fn set_window_plugin_gets_all_keys(window: *mut c_void, all_keys: bool) {
+        if window.is_null() {
+            return;
+        }
+
+        let mut ctx = KeyRoutingContext { window, all_keys };
+        if is_main_thread() {
+            set_key_routing_on_main(&mut ctx as *mut KeyRoutingContext as *mut std::ffi::c_void);
+        } else {
+            unsafe {
+                dispatch_sync_f(
+                    main_queue(),
+                    &mut ctx as *mut KeyRoutingContext as *mut std::ffi::c_void,
+                    set_key_routing_on_main,
+                );
+            }
+        }
+    }
+
     /// Context for main thread window visibility check
     struct WindowVisibleContext {
         window: *mut c_void,
@@ -527,6 +968,40 @@ mod macos {
         }
     }
 
+    /// Callback for checking whether any part of a window is on screen
+    extern "C" fn check_window_on_screen_on_main(ctx: *mut std::ffi::c_void) {
+        let ctx = unsafe { &mut *(ctx as *mut WindowVisibleContext) };
+        if ctx.window.is_null() {
+            ctx.result = false;
+            return;
+        }
+        unsafe {
+            let window_ref = &*(ctx.window as *const NSWindow);
+            ctx.result = window_ref.occlusionState().contains(NSWindowOcclusionState::Visible);
+        }
+    }
+
+    /// Check if any part of a window is actually on screen (not minimized, hidden,
+    /// or covered) - web content stops rendering otherwise
+    pub fn is_window_on_screen(window: *mut c_void) -> bool {
+        if window.is_null() {
+            return false;
+        }
+        let mut ctx = WindowVisibleContext {
+            window,
+            result: false,
+        };
+        let ctx_ptr = &mut ctx as *mut WindowVisibleContext as *mut std::ffi::c_void;
+        if is_main_thread() {
+            check_window_on_screen_on_main(ctx_ptr);
+        } else {
+            unsafe {
+                dispatch_sync_f(main_queue(), ctx_ptr, check_window_on_screen_on_main);
+            }
+        }
+        ctx.result
+    }
+
     /// Context for deminiaturizing a window
     struct DeminiaturizeContext {
         window: *mut c_void,
@@ -575,6 +1050,211 @@ mod macos {
             }
         }
     }
+
+    // -------------------------------------------------------------------------
+    // Analyzer window
+    // -------------------------------------------------------------------------
+
+    /// Analyzer window layout
+    const ANALYZER_WIDTH: f64 = 480.0;
+    const ANALYZER_HEIGHT: f64 = 260.0;
+    const ANALYZER_MARGIN: f64 = 12.0;
+    const LEVEL_METER_HEIGHT: f64 = 14.0;
+    /// Level meters show -60..0 dBFS
+    const METER_FLOOR_DB: f64 = -60.0;
+
+    /// Level indicators inside the analyzer window (pointers owned by the window's view tree)
+    struct AnalyzerViews {
+        left: usize,
+        right: usize,
+        bands: Vec<usize>,
+    }
+
+    static ANALYZER_VIEWS: Lazy<Mutex<Option<AnalyzerViews>>> = Lazy::new(|| Mutex::new(None));
+
+    /// Run a closure on the main thread, blocking until it completes
+    fn run_on_main<F: FnOnce()>(f: F) {
+        if is_main_thread() {
+            f();
+            return;
+        }
+
+        extern "C" fn trampoline<F: FnOnce()>(ctx: *mut std::ffi::c_void) {
+            let slot = unsafe { &mut *(ctx as *mut Option<F>) };
+            if let Some(f) = slot.take() {
+                autoreleasepool(|_pool| f());
+            }
+        }
+
+        let mut slot = Some(f);
+        unsafe {
+            dispatch_sync_f(
+                main_queue(),
+                &mut slot as *mut Option<F> as *mut std::ffi::c_void,
+                trampoline::<F>,
+            );
+        }
+    }
+
+    /// Create a level indicator at a frame inside the content view
+    unsafe fn add_level_indicator(
+        mtm: MainThreadMarker,
+        content_view: &NSView,
+        frame: NSRect,
+        max_value: f64,
+    ) -> usize {
+        let indicator = NSLevelIndicator::initWithFrame(NSLevelIndicator::alloc(mtm), frame);
+        indicator.setLevelIndicatorStyle(NSLevelIndicatorStyle::ContinuousCapacity);
+        indicator.setMinValue(0.0);
+        indicator.setMaxValue(max_value);
+        content_view.addSubview(&indicator);
+        // The view tree holds a strong reference for the window's lifetime
+        Retained::as_ptr(&indicator) as usize
+    }
+
+    /// Open the analyzer window, or bring it to front if already open
+    pub fn open_analyzer_window(position: Option<(f64, f64)>) -> Result<(), String> {
+        if let Some(window) = registered_window(PreviewWindowKind::Analyzer) {
+            if is_window_visible(window) {
+                restore_window(window);
+                return Ok(());
+            }
+            // The user closed it with X - rebuild it
+            close_analyzer_window();
+        }
+
+        let mut result = Err("Analyzer window was not created".to_string());
+        run_on_main(|| {
+            result = unsafe { create_analyzer_window_inner(position) };
+        });
+        let window = result?;
+        register_window(PreviewWindowKind::Analyzer, window);
+        Ok(())
+    }
+
+    unsafe fn create_analyzer_window_inner(position: Option<(f64, f64)>) -> Result<*mut c_void, String> {
+        let mtm = MainThreadMarker::new().ok_or_else(|| "Not on main thread".to_string())?;
+
+        let frame = NSRect::new(
+            NSPoint::new(100.0, 100.0),
+            NSSize::new(ANALYZER_WIDTH, ANALYZER_HEIGHT),
+        );
+        let style = NSWindowStyleMask::Titled
+            | NSWindowStyleMask::Closable
+            | NSWindowStyleMask::Miniaturizable;
+        let window = NSWindow::initWithContentRect_styleMask_backing_defer(
+            NSWindow::alloc(mtm),
+            frame,
+            style,
+            NSBackingStoreType::Buffered,
+            false,
+        );
+        // Same lifetime rule as editor windows: we release it ourselves
+        window.setReleasedWhenClosed(false);
+        window.setTitle(&objc2_foundation::NSString::from_str("Analyzer"));
+
+        let content_view = window
+            .contentView()
+            .ok_or_else(|| "Failed to get content view".to_string())?;
+
+        // L/R output level meters along the top
+        let meter_width = ANALYZER_WIDTH - 2.0 * ANALYZER_MARGIN;
+        let top = ANALYZER_HEIGHT - ANALYZER_MARGIN - LEVEL_METER_HEIGHT;
+        let level_range = -METER_FLOOR_DB;
+        let left = add_level_indicator(
+            mtm,
+            &content_view,
+            NSRect::new(NSPoint::new(ANALYZER_MARGIN, top), NSSize::new(meter_width, LEVEL_METER_HEIGHT)),
+            level_range,
+        );
+        let right = add_level_indicator(
+            mtm,
+            &content_view,
+            NSRect::new(
+                NSPoint::new(ANALYZER_MARGIN, top - LEVEL_METER_HEIGHT - 4.0),
+                NSSize::new(meter_width, LEVEL_METER_HEIGHT),
+            ),
+            level_range,
+        );
+
+        // Spectrum bands as vertical bars below the level meters
+        let spectrum_height = top - LEVEL_METER_HEIGHT - 2.0 * ANALYZER_MARGIN;
+        let band_width = meter_width / NUM_BANDS as f64;
+        let mut bands = Vec::with_capacity(NUM_BANDS);
+        for i in 0..NUM_BANDS {
+            // Level indicators only fill horizontally, so build each bar on its
+            // side and rotate it about its center
+            let center_x = ANALYZER_MARGIN + band_width * (i as f64 + 0.5);
+            let center_y = ANALYZER_MARGIN + spectrum_height / 2.0;
+            let bar_frame = NSRect::new(
+                NSPoint::new(center_x - spectrum_height / 2.0, center_y - (band_width - 2.0) / 2.0),
+                NSSize::new(spectrum_height, band_width - 2.0),
+            );
+            let bar = add_level_indicator(mtm, &content_view, bar_frame, 1.0);
+            (&*(bar as *const NSLevelIndicator)).setFrameCenterRotation(90.0);
+            bands.push(bar);
+        }
+
+        *ANALYZER_VIEWS.lock() = Some(AnalyzerViews { left, right, bands });
+
+        match position {
+            Some((x, y)) => window.setFrameOrigin(NSPoint::new(x, y)),
+            None => window.center(),
+        }
+        window.makeKeyAndOrderFront(None);
+        window.orderFrontRegardless();
+
+        Ok(Retained::into_raw(window) as *mut c_void)
+    }
+
+    /// Push new meter values into the analyzer window (no-op if it isn't open)
+    pub fn update_analyzer_window(frame: &AnalyzerFrame) {
+        let level = |db: f32| (db as f64 - METER_FLOOR_DB).clamp(0.0, -METER_FLOOR_DB);
+        let (left_value, right_value) = (level(frame.left_db), level(frame.right_db));
+        run_on_main(|| {
+            let views = ANALYZER_VIEWS.lock();
+            let views = match views.as_ref() {
+                Some(v) => v,
+                None => return,
+            };
+            unsafe {
+                (&*(views.left as *const NSLevelIndicator)).setDoubleValue(left_value);
+                (&*(views.right as *const NSLevelIndicator)).setDoubleValue(right_value);
+                for (bar, magnitude) in views.bands.iter().zip(frame.spectrum) {
+                    (&*(*bar as *const NSLevelIndicator)).setDoubleValue(*magnitude as f64);
+                }
+            }
+        });
+    }
+
+    /// Get the analyzer window's position, for reopening it in the same place
+    pub fn get_analyzer_position() -> Option<(f64, f64)> {
+        let window = registered_window(PreviewWindowKind::Analyzer)?;
+        let mut position = None;
+        run_on_main(|| {
+            position = unsafe { get_window_position(window) };
+        });
+        position
+    }
+
+    /// Close and release the analyzer window
+    pub fn close_analyzer_window() {
+        let window = match unregister_window(PreviewWindowKind::Analyzer) {
+            Some(w) => w,
+            None => return,
+        };
+        run_on_main(|| {
+            // Drop the views before the window that owns them
+            *ANALYZER_VIEWS.lock() = None;
+            unsafe {
+                if let Some(window) = Retained::from_raw(window as *mut NSWindow) {
+                    if window.isVisible() {
+                        window.close();
+                    }
+                }
+            }
+        });
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -593,7 +1273,7 @@ pub unsafe fn create_editor_window(
 pub unsafe fn create_editor_window_at(
     _plugin: *const ClapPlugin,
     _title: &str,
-    _position: Option<(f64, f64)>,
+    _state: &EditorWindowState,
 ) -> Result<(*mut c_void, *mut c_void), String> {
     Err("GUI not implemented for this platform".to_string())
 }
@@ -606,10 +1286,81 @@ pub unsafe fn get_window_position(_window: *mut c_void) -> Option<(f64, f64)> {
     None
 }
 
+#[cfg(not(target_os = "macos"))]
+pub unsafe fn rescale_editor_window(
+    _plugin: *const ClapPlugin,
+    _window: *mut c_void,
+    _base_size: (u32, u32),
+    _scale: f64,
+) -> Result<(u32, u32), String> {
+    Err("GUI not implemented for this platform".to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn get_window_capture_rect(_window: *mut c_void) -> Option<(f64, f64, f64, f64)> {
+    None
+}
+
 #[cfg(not(target_os = "macos"))]
 pub fn is_window_visible(_window: *mut c_void) -> bool {
     false
 }
 
+#[cfg(not(target_os = "macos"))]
+pub fn is_window_on_screen(_window: *mut c_void) -> bool {
+    false
+}
+
 #[cfg(not(target_os = "macos"))]
 pub fn restore_window(_window: *mut c_void) {}
+
+#[cfg(not(target_os = "macos"))]
+pub fn set_window_always_on_top(_window: *mut c_void, _always_on_top: bool) {}
+
+#[cfg(not(target_os = "macos"))]
+pub fn set_window_plugin_gets_all_keys(_window: *mut c_void, _all_keys: bool) {}
+
+#[cfg(not(target_os = "macos"))]
+pub fn open_analyzer_window(_position: Option<(f64, f64)>) -> Result<(), String> {
+    Err("Analyzer window not implemented for this platform".to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn update_analyzer_window(_frame: &AnalyzerFrame) {}
+
+#[cfg(not(target_os = "macos"))]
+pub fn get_analyzer_position() -> Option<(f64, f64)> {
+    None
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn close_analyzer_window() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_shortcut_for() {
+        assert_eq!(host_shortcut_for(" ", false, false), Some(HostShortcut::TogglePlayback));
+        assert_eq!(host_shortcut_for("W", true, false), Some(HostShortcut::CloseEditor));
+        // Modified or unreserved keys belong to the plugin
+        assert_eq!(host_shortcut_for(" ", false, true), None);
+        assert_eq!(host_shortcut_for("w", false, false), None);
+        assert_eq!(host_shortcut_for("a", false, false), None);
+    }
+
+    #[test]
+    fn test_scaled_gui_size() {
+        assert_eq!(scaled_gui_size((600, 401), 0.75), (450, 301));
+        assert_eq!(scaled_gui_size((600, 400), 1.5), (900, 600));
+        assert_eq!(scaled_gui_size((1, 1), 0.75), (1, 1));
+    }
+
+    #[test]
+    fn test_window_state_defaults_missing_fields() {
+        let state: EditorWindowState = serde_json::from_str(r#"{"alwaysOnTop":false}"#).unwrap();
+        assert!(!state.always_on_top);
+        assert!(!state.plugin_gets_all_keys);
+    }
+}
@@ -1,181 +1,236 @@
 //! File watcher for hot reload
 //!
-//! Watches the plugin .clap bundle for changes and triggers reload.
+//! Watches one or more built plugin artifacts (.clap, .vst3 bundles) for
+//! changes and triggers reload once writes have settled. Bundling deletes and
+//! recreates bundles, so a path that disappears is reported as waiting and
+//! re-attached when it comes back instead of silently ending the watch.
 
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher, Event, EventKind};
-use std::path::PathBuf;
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use parking_lot::Mutex;
 
 /// Debounce timeout - wait this long after last change before reloading
 const DEBOUNCE_MS: u64 = 500;
 
-/// Callback type for reload events
-pub type ReloadCallback = Box<dyn Fn(PathBuf) + Send + Sync>;
+/// How often missing paths are checked for (re)appearing
+const RECONCILE_INTERVAL_MS: u64 = 250;
+
+/// Files that never trigger a reload
+const DEFAULT_IGNORE_PATTERNS: &[&str] = &[".DS_Store", "*.tmp", "*.swp", "*~"];
+
+/// Callback type for reload events (receives the watched paths that changed)
+pub type ReloadCallback = Box<dyn Fn(Vec<PathBuf>) + Send + Sync>;
+
+/// Callback type for watcher status changes
+pub type StatusCallback = Box<dyn Fn(WatcherStatus) + Send + Sync>;
+
+/// Current state of the watcher
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum WatcherStatus {
+    Stopped,
+    /// All paths exist and are being watched
+    Watching { paths: Vec<PathBuf> },
+    /// Some paths are missing (e.g. mid-build); they're re-attached when they reappear
+    Waiting { missing: Vec<PathBuf> },
+    /// The file system watcher reported an error (watching continues)
+    Error { message: String },
+}
+
+/// Watcher settings
+#[derive(Debug, Clone)]
+pub struct WatcherConfig {
+    /// Quiet period after the last change before reloading (ms)
+    pub debounce_ms: u64,
+    /// Glob patterns for files to ignore (`*` and `?`); patterns without a `/`
+    /// match any path component, others match the path relative to the watched root
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for WatcherConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: DEBOUNCE_MS,
+            ignore_patterns: DEFAULT_IGNORE_PATTERNS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+/// Messages from the notify callback to the debounce thread
+enum WatchMessage {
+    Changed(PathBuf),
+    Error(String),
+}
 
 /// Plugin file watcher state
 pub struct PluginWatcher {
-    /// The file system watcher
-    watcher: Option<RecommendedWatcher>,
-    /// Path being watched
-    watched_path: Option<PathBuf>,
-    /// Callback for reload events
-    callback: Option<Arc<ReloadCallback>>,
-    /// Debounce state
-    last_event_time: Arc<Mutex<Option<Instant>>>,
+    /// The file system watcher (shared with the debounce thread for re-attaching)
+    watcher: Option<Arc<Mutex<RecommendedWatcher>>>,
+    /// Paths being watched
+    watched_paths: Vec<PathBuf>,
+    /// Latest status (shared with debounce thread)
+    status: Arc<Mutex<WatcherStatus>>,
     /// Sender to shutdown the debounce thread
     shutdown_tx: Option<Sender<()>>,
-    /// Path to pass to callback (shared with debounce thread)
-    callback_path: Arc<Mutex<Option<PathBuf>>>,
 }
 
 impl PluginWatcher {
     pub fn new() -> Self {
         Self {
             watcher: None,
-            watched_path: None,
-            callback: None,
-            last_event_time: Arc::new(Mutex::new(None)),
+            watched_paths: Vec::new(),
+            status: Arc::new(Mutex::new(WatcherStatus::Stopped)),
             shutdown_tx: None,
-            callback_path: Arc::new(Mutex::new(None)),
         }
     }
 
-    /// Start watching a plugin file/directory for changes
+    /// Start watching plugin artifacts for changes
+    ///
+    /// Paths that don't exist yet are picked up once they're created.
     pub fn watch(
         &mut self,
-        path: PathBuf,
+        paths: Vec<PathBuf>,
+        config: WatcherConfig,
         callback: ReloadCallback,
+        status_callback: StatusCallback,
     ) -> Result<(), String> {
+        if paths.is_empty() {
+            return Err("No paths to watch".to_string());
+        }
+
         // Stop any existing watch
         self.unwatch();
 
-        log::info!("Starting file watcher for: {:?}", path);
-
-        let callback = Arc::new(callback);
-        let callback_clone = callback.clone();
-        let last_event_time = self.last_event_time.clone();
-        let path_clone = path.clone();
+        log::info!("Starting file watcher for: {:?}", paths);
 
         // Create channel for events
-        let (tx, rx) = channel::<PathBuf>();
+        let (tx, rx) = channel::<WatchMessage>();
+        let roots = paths.clone();
+        let ignore_patterns = config.ignore_patterns.clone();
 
         // Create the watcher
         let watcher = RecommendedWatcher::new(
-            move |result: Result<Event, notify::Error>| {
-                if let Ok(event) = result {
-                    // Only trigger on modify/create events
-                    match event.kind {
-                        EventKind::Modify(_) | EventKind::Create(_) => {
-                            // Update last event time for debouncing
-                            *last_event_time.lock() = Some(Instant::now());
-                            let _ = tx.send(path_clone.clone());
+            move |result: Result<Event, notify::Error>| match result {
+                Ok(event) => {
+                    // Removes matter too: bundling deletes the old bundle first
+                    if !matches!(
+                        event.kind,
+                        EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
+                    ) {
+                        return;
+                    }
+                    for path in &event.paths {
+                        let Some(root) = roots.iter().find(|r| path.starts_with(r)) else {
+                            continue;
+                        };
+                        let relative = path.strip_prefix(root).unwrap_or(path);
+                        if !is_ignored(relative, &ignore_patterns) {
+                            let _ = tx.send(WatchMessage::Changed(root.clone()));
                         }
-                        _ => {}
                     }
                 }
+                Err(e) => {
+                    let _ = tx.send(WatchMessage::Error(e.to_string()));
+                }
             },
             Config::default(),
-        ).map_err(|e| format!("Failed to create file watcher: {}", e))?;
+        )
+        .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+        let watcher = Arc::new(Mutex::new(watcher));
 
-        // Start the debounce thread
+        // Start the debounce thread (it attaches the paths on its first pass)
         let (shutdown_tx, shutdown_rx) = channel::<()>();
-        let last_event_time_clone = self.last_event_time.clone();
-        let callback_path_clone = self.callback_path.clone();
-
-        // Store the path for the callback
-        *self.callback_path.lock() = Some(path.clone());
+        let watcher_clone = watcher.clone();
+        let status_clone = self.status.clone();
+        let thread_paths = paths.clone();
 
         std::thread::spawn(move || {
-            Self::debounce_thread(rx, shutdown_rx, callback_clone, last_event_time_clone, callback_path_clone);
+            Self::debounce_thread(
+                rx,
+                shutdown_rx,
+                watcher_clone,
+                thread_paths,
+                Duration::from_millis(config.debounce_ms),
+                callback,
+                status_callback,
+                status_clone,
+            );
         });
 
         self.watcher = Some(watcher);
+        self.watched_paths = paths;
         self.shutdown_tx = Some(shutdown_tx);
 
-        // Start watching
-        if let Some(ref mut w) = self.watcher {
-            // Watch the parent directory of the .clap bundle for file changes
-            let watch_path = if path.is_dir() {
-                // For .clap bundles (directories), watch the bundle itself
-                &path
-            } else {
-                // For single files, watch the file
-                &path
-            };
-
-            w.watch(watch_path, RecursiveMode::Recursive)
-                .map_err(|e| format!("Failed to watch path: {}", e))?;
-        }
-
-        self.watched_path = Some(path);
-        self.callback = Some(callback);
-
         log::info!("File watcher started successfully");
         Ok(())
     }
 
     /// Stop watching
     pub fn unwatch(&mut self) {
-        if let Some(ref mut watcher) = self.watcher {
-            if let Some(ref path) = self.watched_path {
-                let _ = watcher.unwatch(path);
-            }
-        }
-
         // Signal shutdown to debounce thread
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(());
         }
 
-        self.watcher = None;
-        self.watched_path = None;
-        self.callback = None;
-        *self.last_event_time.lock() = None;
-        *self.callback_path.lock() = None;
+        if let Some(watcher) = self.watcher.take() {
+            let mut watcher = watcher.lock();
+            for path in &self.watched_paths {
+                let _ = watcher.unwatch(path);
+            }
+            log::info!("File watcher stopped");
+        }
 
-        log::info!("File watcher stopped");
+        self.watched_paths.clear();
+        *self.status.lock() = WatcherStatus::Stopped;
     }
 
     /// Debounce thread - waits for changes to settle before triggering callback
+    /// and keeps missing paths re-attached
+    #[allow(clippy::too_many_arguments)]
     fn debounce_thread(
-        rx: Receiver<PathBuf>,
+        rx: Receiver<WatchMessage>,
         shutdown_rx: Receiver<()>,
-        callback: Arc<ReloadCallback>,
-        last_event_time: Arc<Mutex<Option<Instant>>>,
-        callback_path: Arc<Mutex<Option<PathBuf>>>,
+        watcher: Arc<Mutex<RecommendedWatcher>>,
+        paths: Vec<PathBuf>,
+        debounce: Duration,
+        callback: ReloadCallback,
+        status_callback: StatusCallback,
+        status: Arc<Mutex<WatcherStatus>>,
     ) {
+        let mut attached: HashSet<PathBuf> = HashSet::new();
+        let mut pending: HashSet<PathBuf> = HashSet::new();
+        let mut last_event: Option<Instant> = None;
+        let mut last_reconcile: Option<Instant> = None;
+        let mut first_pass = true;
+
+        let set_status = |new_status: WatcherStatus| {
+            let mut current = status.lock();
+            if *current != new_status {
+                *current = new_status.clone();
+                drop(current);
+                status_callback(new_status);
+            }
+        };
+
         loop {
             // Wait for an event or shutdown signal
             match rx.recv_timeout(Duration::from_millis(100)) {
-                Ok(_path) => {
-                    // Event received, start debouncing
+                Ok(WatchMessage::Changed(root)) => {
+                    pending.insert(root);
+                    last_event = Some(Instant::now());
                 }
-                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
-                    // Check if we should trigger a reload
-                    let should_reload = {
-                        let mut last_time = last_event_time.lock();
-                        if let Some(instant) = *last_time {
-                            if instant.elapsed() > Duration::from_millis(DEBOUNCE_MS) {
-                                *last_time = None; // Clear the event
-                                true
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
-                        }
-                    };
-
-                    if should_reload {
-                        log::info!("Debounce complete, triggering reload");
-                        // Get the watched path from shared state
-                        let path = callback_path.lock().clone().unwrap_or_default();
-                        callback(path);
-                    }
+                Ok(WatchMessage::Error(message)) => {
+                    log::warn!("File watcher error: {}", message);
+                    set_status(WatcherStatus::Error { message });
+                    // Force a reconcile so the status recovers once things settle
+                    last_reconcile = None;
                 }
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
                 Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
                     log::info!("File watcher channel disconnected");
                     break;
@@ -187,6 +242,58 @@ impl PluginWatcher {
                 log::info!("File watcher shutdown signal received");
                 break;
             }
+
+            // Attach paths that (re)appeared, drop ones that vanished
+            let reconcile_due = last_reconcile
+                .map(|t| t.elapsed() >= Duration::from_millis(RECONCILE_INTERVAL_MS))
+                .unwrap_or(true);
+            if reconcile_due {
+                last_reconcile = Some(Instant::now());
+                let mut missing = Vec::new();
+                let mut watch_error = None;
+
+                for path in &paths {
+                    let exists = path.exists();
+                    if exists && !attached.contains(path) {
+                        match watcher.lock().watch(path, RecursiveMode::Recursive) {
+                            Ok(()) => {
+                                attached.insert(path.clone());
+                                // A rebuilt bundle reappearing counts as a change
+                                if !first_pass {
+                                    log::info!("Watched path reappeared: {:?}", path);
+                                    pending.insert(path.clone());
+                                    last_event = Some(Instant::now());
+                                }
+                            }
+                            Err(e) => {
+                                watch_error = Some(format!("Failed to watch {:?}: {}", path, e));
+                                missing.push(path.clone());
+                            }
+                        }
+                    } else if !exists {
+                        if attached.remove(path) {
+                            log::info!("Watched path disappeared, waiting for it: {:?}", path);
+                            let _ = watcher.lock().unwatch(path);
+                        }
+                        missing.push(path.clone());
+                    }
+                }
+                first_pass = false;
+
+                set_status(match watch_error {
+                    Some(message) => WatcherStatus::Error { message },
+                    None if missing.is_empty() => WatcherStatus::Watching { paths: paths.clone() },
+                    None => WatcherStatus::Waiting { missing },
+                });
+            }
+
+            // Reload once changes settle and every changed artifact is back in place
+            let settled = last_event.map(|t| t.elapsed() > debounce).unwrap_or(false);
+            if settled && !pending.is_empty() && pending.iter().all(|p| p.exists()) {
+                last_event = None;
+                log::info!("Debounce complete, triggering reload");
+                callback(pending.drain().collect());
+            }
         }
     }
 
@@ -195,9 +302,20 @@ impl PluginWatcher {
         self.watcher.is_some()
     }
 
-    /// Get the path being watched
-    pub fn watched_path(&self) -> Option<&PathBuf> {
-        self.watched_path.as_ref()
+    /// Get the paths being watched
+    pub fn watched_paths(&self) -> &[PathBuf] {
+        &self.watched_paths
+    }
+
+    /// Get the current watcher status
+    pub fn status(&self) -> WatcherStatus {
+        self.status.lock().clone()
+    }
+}
+
+impl Default for PluginWatcher {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -206,3 +324,66 @@ impl Drop for PluginWatcher {
         self.unwatch();
     }
 }
+
+/// Check a path (relative to its watched root) against ignore patterns
+fn is_ignored(relative: &Path, patterns: &[String]) -> bool {
+    let components: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let joined = components.join("/");
+
+    patterns.iter().any(|pattern| {
+        if pattern.contains('/') {
+            glob_match(pattern, &joined)
+        } else {
+            components.iter().any(|c| glob_match(pattern, c))
+        }
+    })
+}
+
+/// Minimal glob matching: `*` matches any run of characters, `?` matches one
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if let Some((star_pi, star_ti)) = backtrack {
+            // Let the last `*` swallow one more character
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            backtrack = Some((star_pi, star_ti + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore_patterns() {
+        assert!(glob_match("*.tmp", "lib.tmp"));
+        assert!(glob_match("a?c*", "abcdef"));
+        assert!(!glob_match("*.tmp", "lib.dylib"));
+
+        let patterns: Vec<String> = ["*.dSYM", "Contents/_CodeSignature/*"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert!(is_ignored(Path::new("Contents/MacOS/foo.dSYM/x"), &patterns));
+        assert!(is_ignored(Path::new("Contents/_CodeSignature/CodeResources"), &patterns));
+        assert!(!is_ignored(Path::new("Contents/MacOS/foo"), &patterns));
+    }
+}
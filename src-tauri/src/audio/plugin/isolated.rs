@@ -0,0 +1,431 @@
+//! Out-of-process plugin hosting
+//!
+//! Runs a CLAP in a `freqlab-cli host-plugin` helper process, so a plugin that
+//! corrupts memory takes down the helper instead of the app. Audio moves through
+//! two single-producer/single-consumer rings in a memory-mapped file: the audio
+//! callback writes each input block and reads back whatever the helper has
+//! finished. The plugin runs a block behind, and an underrun plays silence
+//! rather than blocking the callback.
+//!
+//! Isolation is audio only - there's no editor, parameter list, state, or MIDI
+//! in this mode.
+
+use parking_lot::Mutex;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use super::PluginInstance;
+
+/// Identifies a freqlab shared-memory region ("FQIS")
+const MAGIC: u32 = 0x4651_4953;
+
+/// Samples per ring (interleaved stereo) - room for four max-size blocks
+const RING_SAMPLES: usize = 4096 * 2 * 4;
+/// Bytes reserved for the plugin name or load error
+const MESSAGE_LEN: usize = 256;
+
+const STATE_STARTING: u32 = 0;
+const STATE_READY: u32 = 1;
+const STATE_FAILED: u32 = 2;
+const STATE_CRASHED: u32 = 3;
+const STATE_SHUTDOWN: u32 = 4;
+
+/// How long the helper gets to load the plugin
+const LOAD_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long the helper gets to exit on its own before it's killed
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(500);
+/// Helper poll interval while waiting for input
+const IDLE_SLEEP: Duration = Duration::from_micros(100);
+
+/// Read/write positions of one ring; monotonically increasing sample counts
+#[repr(C)]
+struct RingPositions {
+    write: AtomicU64,
+    read: AtomicU64,
+}
+
+/// Start of the shared region, followed by the message bytes and the
+/// input and output sample rings
+#[repr(C)]
+struct Header {
+    magic: u32,
+    sample_rate: u32,
+    max_frames: u32,
+    state: AtomicU32,
+    message_len: AtomicU32,
+    input: RingPositions,
+    output: RingPositions,
+}
+
+const MESSAGE_OFFSET: usize = std::mem::size_of::<Header>();
+const INPUT_OFFSET: usize = (MESSAGE_OFFSET + MESSAGE_LEN + 7) & !7;
+const OUTPUT_OFFSET: usize = INPUT_OFFSET + RING_SAMPLES * std::mem::size_of::<f32>();
+const REGION_LEN: usize = OUTPUT_OFFSET + RING_SAMPLES * std::mem::size_of::<f32>();
+
+/// One direction of the audio transport
+struct Ring {
+    positions: *const RingPositions,
+    data: *mut f32,
+    capacity: usize,
+}
+
+impl Ring {
+    /// # Safety
+    /// `positions` and `capacity` samples at `data` must outlive the ring, and
+    /// only one side may push and one side pop.
+    unsafe fn new(positions: *const RingPositions, data: *mut f32, capacity: usize) -> Self {
+        Self {
+            positions,
+            data,
+            capacity,
+        }
+    }
+
+    fn positions(&self) -> &RingPositions {
+        unsafe { &*self.positions }
+    }
+
+    /// Write a whole block, or nothing if it doesn't fit (producer side)
+    fn push(&self, samples: &[f32]) -> bool {
+        let positions = self.positions();
+        let write = positions.write.load(Ordering::Relaxed);
+        let read = positions.read.load(Ordering::Acquire);
+        let free = self.capacity - (write - read) as usize;
+        if samples.len() > free {
+            return false;
+        }
+        for (i, &sample) in samples.iter().enumerate() {
+            let index = (write as usize + i) % self.capacity;
+            unsafe { *self.data.add(index) = sample };
+        }
+        positions.write.store(write + samples.len() as u64, Ordering::Release);
+        true
+    }
+
+    /// Read up to `out.len()` samples, returning how many were read (consumer side)
+    fn pop(&self, out: &mut [f32]) -> usize {
+        let positions = self.positions();
+        let read = positions.read.load(Ordering::Relaxed);
+        let write = positions.write.load(Ordering::Acquire);
+        let count = ((write - read) as usize).min(out.len());
+        for (i, sample) in out[..count].iter_mut().enumerate() {
+            let index = (read as usize + i) % self.capacity;
+            *sample = unsafe { *self.data.add(index) };
+        }
+        positions.read.store(read + count as u64, Ordering::Release);
+        count
+    }
+}
+
+/// The memory-mapped file shared by the app and the helper
+struct SharedRegion {
+    base: *mut u8,
+    path: PathBuf,
+}
+
+// The region is only touched through atomics and the SPSC rings
+unsafe impl Send for SharedRegion {}
+unsafe impl Sync for SharedRegion {}
+
+impl SharedRegion {
+    #[cfg(unix)]
+    fn map(path: &Path, create: bool) -> Result<Self, String> {
+        use std::os::unix::io::AsRawFd;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(create)
+            .truncate(create)
+            .open(path)
+            .map_err(|e| format!("Failed to open shared audio buffer: {}", e))?;
+        if create {
+            file.set_len(REGION_LEN as u64)
+                .map_err(|e| format!("Failed to size shared audio buffer: {}", e))?;
+        }
+        let base = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                REGION_LEN,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if base == libc::MAP_FAILED {
+            return Err(format!(
+                "Failed to map shared audio buffer: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(Self {
+            base: base as *mut u8,
+            path: path.to_path_buf(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn map(_path: &Path, _create: bool) -> Result<Self, String> {
+        Err("Isolated plugin hosting isn't supported on this platform yet".to_string())
+    }
+
+    fn header(&self) -> &Header {
+        unsafe { &*(self.base as *const Header) }
+    }
+
+    fn input(&self) -> Ring {
+        unsafe {
+            Ring::new(
+                &self.header().input,
+                self.base.add(INPUT_OFFSET) as *mut f32,
+                RING_SAMPLES,
+            )
+        }
+    }
+
+    fn output(&self) -> Ring {
+        unsafe {
+            Ring::new(
+                &self.header().output,
+                self.base.add(OUTPUT_OFFSET) as *mut f32,
+                RING_SAMPLES,
+            )
+        }
+    }
+
+    fn state(&self) -> u32 {
+        self.header().state.load(Ordering::Acquire)
+    }
+
+    fn set_state(&self, state: u32) {
+        self.header().state.store(state, Ordering::Release);
+    }
+
+    /// Store the plugin name or load error for the other side (before `set_state`)
+    fn set_message(&self, message: &str) {
+        let bytes = &message.as_bytes()[..message.len().min(MESSAGE_LEN)];
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.base.add(MESSAGE_OFFSET), bytes.len());
+        }
+        self.header().message_len.store(bytes.len() as u32, Ordering::Release);
+    }
+
+    fn message(&self) -> String {
+        let len = self.header().message_len.load(Ordering::Acquire) as usize;
+        let bytes = unsafe { std::slice::from_raw_parts(self.base.add(MESSAGE_OFFSET), len.min(MESSAGE_LEN)) };
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+impl Drop for SharedRegion {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        unsafe {
+            libc::munmap(self.base as *mut libc::c_void, REGION_LEN);
+        }
+    }
+}
+
+/// A plugin running in a helper process
+pub struct IsolatedPlugin {
+    pub name: String,
+    pub path: PathBuf,
+    region: SharedRegion,
+    child: Mutex<Child>,
+    crashed: AtomicBool,
+}
+
+impl IsolatedPlugin {
+    /// Start a helper process and wait for it to load the plugin
+    pub fn spawn(bundle: &Path, sample_rate: f64, max_frames: u32) -> Result<Self, String> {
+        let shm_path = std::env::temp_dir().join(format!("freqlab-isolated-{}.shm", uuid::Uuid::new_v4()));
+        let region = SharedRegion::map(&shm_path, true)?;
+        // The header is written before the helper exists, so plain writes are fine
+        unsafe {
+            let header = region.base as *mut Header;
+            (*header).magic = MAGIC;
+            (*header).sample_rate = sample_rate as u32;
+            (*header).max_frames = max_frames;
+        }
+        region.set_state(STATE_STARTING);
+
+        let cli = crate::cli::cli_binary();
+        let child = Command::new(&cli)
+            .arg("host-plugin")
+            .arg(bundle)
+            .arg(&shm_path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                let _ = fs::remove_file(&shm_path);
+                format!("Failed to start plugin host {}: {}", cli.display(), e)
+            })?;
+        let mut plugin = Self {
+            name: String::new(),
+            path: bundle.to_path_buf(),
+            region,
+            child: Mutex::new(child),
+            crashed: AtomicBool::new(false),
+        };
+
+        let started = Instant::now();
+        loop {
+            match plugin.region.state() {
+                STATE_READY => break,
+                STATE_FAILED => return Err(plugin.region.message()),
+                _ => {}
+            }
+            if let Ok(Some(status)) = plugin.child.lock().try_wait() {
+                return Err(format!("Plugin host exited while loading the plugin ({})", status));
+            }
+            if started.elapsed() > LOAD_TIMEOUT {
+                return Err("Plugin host timed out loading the plugin".to_string());
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        plugin.name = plugin.region.message();
+        log::info!("Plugin {} running in helper process {}", plugin.name, plugin.child.lock().id());
+        Ok(plugin)
+    }
+
+    /// Send a block to the helper and fill `output` with the latest processed audio
+    /// Real-time safe: never blocks; missing audio is silence. Returns false once
+    /// the helper is gone.
+    pub fn process(&self, input: &[f32], output: &mut [f32]) -> bool {
+        if self.crashed.load(Ordering::Relaxed) || self.region.state() != STATE_READY {
+            output.fill(0.0);
+            return false;
+        }
+        // A full input ring means the helper has stalled - drop the block
+        self.region.input().push(input);
+        let count = self.region.output().pop(output);
+        output[count..].fill(0.0);
+        true
+    }
+
+    /// Whether the helper crashed or exited (not real-time safe - polls the process)
+    pub fn has_crashed(&self) -> bool {
+        if self.crashed.load(Ordering::Relaxed) {
+            return true;
+        }
+        let exited = matches!(self.child.lock().try_wait(), Ok(Some(_)));
+        if exited || self.region.state() == STATE_CRASHED {
+            log::warn!("Isolated plugin host for {} went away", self.name);
+            self.crashed.store(true, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+}
+
+impl Drop for IsolatedPlugin {
+    fn drop(&mut self) {
+        self.region.set_state(STATE_SHUTDOWN);
+        let child = self.child.get_mut();
+        let started = Instant::now();
+        while matches!(child.try_wait(), Ok(None)) {
+            if started.elapsed() > SHUTDOWN_TIMEOUT {
+                let _ = child.kill();
+                let _ = child.wait();
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let _ = fs::remove_file(&self.region.path);
+    }
+}
+
+#[cfg(unix)]
+fn parent_pid() -> u32 {
+    unsafe { libc::getppid() as u32 }
+}
+
+#[cfg(not(unix))]
+fn parent_pid() -> u32 {
+    0
+}
+
+/// Helper process side: load the plugin and process audio from the shared region
+/// until the app asks it to stop or goes away
+pub fn run_helper(bundle: &Path, shm_path: &Path) -> Result<(), String> {
+    let region = SharedRegion::map(shm_path, false)?;
+    let header = region.header();
+    if header.magic != MAGIC {
+        return Err(format!("{} isn't a freqlab audio buffer", shm_path.display()));
+    }
+
+    let mut plugin = match PluginInstance::load(bundle, header.sample_rate as f64, header.max_frames) {
+        Ok(plugin) => plugin,
+        Err(e) => {
+            region.set_message(&e);
+            region.set_state(STATE_FAILED);
+            return Err(e);
+        }
+    };
+    if let Err(e) = plugin.start_processing() {
+        log::warn!("Plugin start_processing failed: {}", e);
+    }
+    region.set_message(&plugin.name);
+    region.set_state(STATE_READY);
+
+    let parent = parent_pid();
+    let block = header.max_frames as usize * 2;
+    let mut input = vec![0.0f32; block];
+    let mut output = vec![0.0f32; block];
+    let (input_ring, output_ring) = (region.input(), region.output());
+
+    while region.state() == STATE_READY {
+        let count = input_ring.pop(&mut input);
+        if count == 0 {
+            // Reparented means the app died without shutting us down
+            if parent_pid() != parent {
+                break;
+            }
+            std::thread::sleep(IDLE_SLEEP);
+            continue;
+        }
+        if plugin.process(&input[..count], &mut output[..count]).is_err() || plugin.has_crashed() {
+            region.set_state(STATE_CRASHED);
+            return Err(format!("{} crashed while processing", plugin.name));
+        }
+        // A full output ring means the app stopped reading - drop the block
+        output_ring.push(&output[..count]);
+    }
+
+    plugin.stop_processing();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_push_pop_wraps() {
+        let positions = RingPositions {
+            write: AtomicU64::new(0),
+            read: AtomicU64::new(0),
+        };
+        let mut data = vec![0.0f32; 8];
+        let ring = unsafe { Ring::new(&positions, data.as_mut_ptr(), data.len()) };
+
+        assert!(ring.push(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+        assert!(!ring.push(&[7.0, 8.0, 9.0, 10.0]), "whole blocks only");
+        let mut out = [0.0f32; 4];
+        assert_eq!(ring.pop(&mut out), 4);
+        assert_eq!(out, [1.0, 2.0, 3.0, 4.0]);
+
+        // Wraps around the end of the buffer
+        assert!(ring.push(&[7.0, 8.0, 9.0, 10.0]));
+        let mut out = [0.0f32; 8];
+        assert_eq!(ring.pop(&mut out), 6);
+        assert_eq!(&out[..6], &[5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
+        assert_eq!(ring.pop(&mut out), 0);
+    }
+}
@@ -10,6 +10,9 @@ pub mod clap_sys;
 pub mod crash_guard;
 pub mod editor;
 pub mod file_watcher;
+pub mod isolated;
+pub mod preset;
+pub mod ui_heartbeat;
 
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -17,7 +20,7 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
-pub use clap_host::{cleanup_temp_bundles, PluginInstance};
+pub use clap_host::{cleanup_temp_bundles, PluginInstance, PluginParamInfo};
 
 /// Plugin type determines audio routing
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
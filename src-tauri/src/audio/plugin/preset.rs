@@ -0,0 +1,404 @@
+//! Preset file conversion (.vstpreset / .aupreset)
+//!
+//! Converts a CLAP state snapshot from the preview host into standard preset
+//! files and back. nih-plug serializes the same state blob for its CLAP and
+//! VST3 wrappers, so the CLAP state can be stored directly as the VST3
+//! component state chunk.
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// Supported preset file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresetFormat {
+    Vst3,
+    Au,
+}
+
+impl PresetFormat {
+    /// File extension for this format (without dot)
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Vst3 => "vstpreset",
+            Self::Au => "aupreset",
+        }
+    }
+
+    /// Detect the format from a file path's extension
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "vstpreset" => Some(Self::Vst3),
+            "aupreset" => Some(Self::Au),
+            _ => None,
+        }
+    }
+}
+
+/// A preset decoded from a preset file
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedPreset {
+    /// Preset name (AU presets only - VST3 presets are named by filename)
+    pub name: Option<String>,
+    /// VST3 class ID (32 hex chars) or AU "type/subtype/manufacturer" identifier
+    pub plugin_id: String,
+    /// Raw plugin state
+    pub state: Vec<u8>,
+}
+
+impl DecodedPreset {
+    /// Fail unless the preset was saved by the plugin with this identifier
+    /// (`vst3_class_id_hex` for VST3 presets, `AuComponent::identifier` for AU)
+    pub fn check_plugin_id(&self, expected: &str) -> Result<(), String> {
+        if self.plugin_id.eq_ignore_ascii_case(expected) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Preset belongs to a different plugin ({}, expected {})",
+                self.plugin_id, expected
+            ))
+        }
+    }
+}
+
+// =============================================================================
+// VST3 (.vstpreset)
+// =============================================================================
+//
+// Layout (all integers little-endian):
+//   "VST3"            4 bytes
+//   version           i32 (1)
+//   class ID          32 ASCII hex chars
+//   chunk list offset i64
+//   ...chunk data...
+//   "List"            4 bytes
+//   entry count       i32
+//   entries           [id: 4 bytes, offset: i64, size: i64]
+
+const VST3_HEADER_SIZE: usize = 4 + 4 + 32 + 8;
+
+/// Format a 16-byte VST3 class ID as the 32-char hex string used in .vstpreset headers
+pub fn vst3_class_id_hex(class_id: &[u8; 16]) -> String {
+    class_id.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+/// Encode plugin state as a .vstpreset file
+pub fn encode_vstpreset(class_id: &[u8; 16], state: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(VST3_HEADER_SIZE + state.len() + 32);
+
+    let list_offset = (VST3_HEADER_SIZE + state.len()) as i64;
+
+    out.extend_from_slice(b"VST3");
+    out.extend_from_slice(&1i32.to_le_bytes());
+    out.extend_from_slice(vst3_class_id_hex(class_id).as_bytes());
+    out.extend_from_slice(&list_offset.to_le_bytes());
+
+    // Component state chunk
+    out.extend_from_slice(state);
+
+    // Chunk list
+    out.extend_from_slice(b"List");
+    out.extend_from_slice(&1i32.to_le_bytes());
+    out.extend_from_slice(b"Comp");
+    out.extend_from_slice(&(VST3_HEADER_SIZE as i64).to_le_bytes());
+    out.extend_from_slice(&(state.len() as i64).to_le_bytes());
+
+    out
+}
+
+fn read_i32(data: &[u8], offset: usize) -> Result<i32, String> {
+    data.get(offset..offset.saturating_add(4))
+        .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Preset file is truncated".to_string())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> Result<i64, String> {
+    data.get(offset..offset.saturating_add(8))
+        .map(|b| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(b);
+            i64::from_le_bytes(bytes)
+        })
+        .ok_or_else(|| "Preset file is truncated".to_string())
+}
+
+/// Decode a .vstpreset file, returning the component state chunk
+pub fn decode_vstpreset(data: &[u8]) -> Result<DecodedPreset, String> {
+    if data.len() < VST3_HEADER_SIZE || &data[0..4] != b"VST3" {
+        return Err("Not a VST3 preset file".to_string());
+    }
+
+    let class_id = String::from_utf8_lossy(&data[8..40]).to_string();
+    let list_offset = usize::try_from(read_i64(data, 40)?)
+        .map_err(|_| "VST3 preset has an invalid chunk list offset".to_string())?;
+    if data.get(list_offset..list_offset.saturating_add(4)) != Some(b"List".as_slice()) {
+        return Err("VST3 preset is missing its chunk list".to_string());
+    }
+    let count = usize::try_from(read_i32(data, list_offset + 4)?)
+        .map_err(|_| "VST3 preset has an invalid chunk count".to_string())?;
+
+    for i in 0..count {
+        let entry = i
+            .checked_mul(20)
+            .and_then(|e| e.checked_add(list_offset + 8))
+            .ok_or("VST3 preset chunk list is truncated")?;
+        let id = data
+            .get(entry..entry.saturating_add(4))
+            .ok_or("VST3 preset chunk list is truncated")?;
+        if id != b"Comp" {
+            continue;
+        }
+        let invalid = || "VST3 preset has an invalid component chunk".to_string();
+        let offset = usize::try_from(read_i64(data, entry + 4)?).map_err(|_| invalid())?;
+        let size = usize::try_from(read_i64(data, entry + 12)?).map_err(|_| invalid())?;
+        let end = offset.checked_add(size).ok_or_else(invalid)?;
+        let state = data
+            .get(offset..end)
+            .ok_or("VST3 preset component chunk is out of bounds")?;
+        return Ok(DecodedPreset {
+            name: None,
+            plugin_id: class_id,
+            state: state.to_vec(),
+        });
+    }
+
+    Err("VST3 preset has no component state".to_string())
+}
+
+/// Extract the VST3 class ID from a nih-plug project's lib.rs
+/// Matches `const VST3_CLASS_ID: [u8; 16] = *b"...";`
+pub fn find_vst3_class_id(source: &str) -> Option<[u8; 16]> {
+    let idx = source.find("VST3_CLASS_ID")?;
+    let rest = &source[idx..];
+    let start = rest.find("*b\"")? + 3;
+    let end = start + rest[start..].find('"')?;
+    let bytes = rest[start..end].as_bytes();
+    if bytes.len() != 16 {
+        return None;
+    }
+    let mut id = [0u8; 16];
+    id.copy_from_slice(bytes);
+    Some(id)
+}
+
+// =============================================================================
+// Audio Unit (.aupreset)
+// =============================================================================
+//
+// An XML property list with the AU component codes and the state blob under
+// the `data` key (the same key AU hosts use for ClassInfo state).
+
+/// AU component identity written into .aupreset files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuComponent {
+    /// Four-char component type ("aufx" for effects, "aumu" for instruments)
+    #[serde(rename = "type")]
+    pub component_type: String,
+    pub subtype: String,
+    pub manufacturer: String,
+}
+
+impl AuComponent {
+    /// Derive component codes for a project: type from plugin kind, subtype from the
+    /// plugin name, manufacturer from the vendor
+    pub fn for_plugin(is_instrument: bool, plugin_name: &str, vendor: &str) -> Self {
+        Self {
+            component_type: if is_instrument { "aumu" } else { "aufx" }.to_string(),
+            subtype: four_char_code(plugin_name, "Plug"),
+            manufacturer: four_char_code(vendor, "Frql"),
+        }
+    }
+
+    /// "type/subtype/manufacturer", as in `DecodedPreset::plugin_id`
+    pub fn identifier(&self) -> String {
+        format!("{}/{}/{}", self.component_type, self.subtype, self.manufacturer)
+    }
+}
+
+/// Build a four-char code from the first alphanumeric chars of a string
+fn four_char_code(s: &str, fallback: &str) -> String {
+    let chars: String = s.chars().filter(|c| c.is_ascii_alphanumeric()).take(4).collect();
+    if chars.is_empty() {
+        return fallback.to_string();
+    }
+    let mut code = String::new();
+    for (i, c) in chars.chars().enumerate() {
+        code.push(if i == 0 { c.to_ascii_uppercase() } else { c.to_ascii_lowercase() });
+    }
+    while code.len() < 4 {
+        code.push(' ');
+    }
+    code
+}
+
+/// Pack a four-char code into the big-endian integer form AU plists use
+fn four_cc_to_int(code: &str) -> u32 {
+    let bytes = code.as_bytes();
+    let mut value = 0u32;
+    for i in 0..4 {
+        value = (value << 8) | *bytes.get(i).unwrap_or(&b' ') as u32;
+    }
+    value
+}
+
+fn int_to_four_cc(value: i64) -> String {
+    let v = value as u32;
+    [(v >> 24) as u8, (v >> 16) as u8, (v >> 8) as u8, v as u8]
+        .iter()
+        .map(|b| *b as char)
+        .collect()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Encode plugin state as an .aupreset property list
+pub fn encode_aupreset(component: &AuComponent, name: &str, state: &[u8]) -> String {
+    let data = base64::engine::general_purpose::STANDARD.encode(state);
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>data</key>
+	<data>{data}</data>
+	<key>manufacturer</key>
+	<integer>{manufacturer}</integer>
+	<key>name</key>
+	<string>{name}</string>
+	<key>subtype</key>
+	<integer>{subtype}</integer>
+	<key>type</key>
+	<integer>{component_type}</integer>
+	<key>version</key>
+	<integer>0</integer>
+</dict>
+</plist>
+"#,
+        data = data,
+        manufacturer = four_cc_to_int(&component.manufacturer),
+        name = xml_escape(name),
+        subtype = four_cc_to_int(&component.subtype),
+        component_type = four_cc_to_int(&component.component_type),
+    )
+}
+
+/// Find the value element following `<key>{key}</key>` in a flat plist dict
+fn plist_value<'a>(xml: &'a str, key: &str, tag: &str) -> Option<&'a str> {
+    let key_tag = format!("<key>{}</key>", key);
+    let after_key = &xml[xml.find(&key_tag)? + key_tag.len()..];
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = after_key.find(&open)?;
+    // The value must be the very next element after the key
+    if !after_key[..start].trim().is_empty() {
+        return None;
+    }
+    let value = &after_key[start + open.len()..];
+    Some(&value[..value.find(&close)?])
+}
+
+/// Decode an .aupreset property list
+pub fn decode_aupreset(xml: &str) -> Result<DecodedPreset, String> {
+    if !xml.contains("<plist") {
+        return Err("Not an AU preset file".to_string());
+    }
+
+    let data = plist_value(xml, "data", "data").ok_or("AU preset has no state data")?;
+    let cleaned: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    let state = base64::engine::general_purpose::STANDARD
+        .decode(cleaned)
+        .map_err(|e| format!("AU preset state is not valid base64: {}", e))?;
+
+    let code = |key: &str| {
+        plist_value(xml, key, "integer")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+            .map(int_to_four_cc)
+            .unwrap_or_default()
+    };
+
+    Ok(DecodedPreset {
+        name: plist_value(xml, "name", "string").map(xml_unescape),
+        plugin_id: format!("{}/{}/{}", code("type"), code("subtype"), code("manufacturer")),
+        state,
+    })
+}
+
+/// Decode a preset file of either format
+pub fn decode_preset_file(path: &std::path::Path) -> Result<DecodedPreset, String> {
+    let format = PresetFormat::from_path(path)
+        .ok_or("Unsupported preset format (expected .vstpreset or .aupreset)")?;
+    let data = std::fs::read(path).map_err(|e| format!("Failed to read preset: {}", e))?;
+
+    match format {
+        PresetFormat::Vst3 => decode_vstpreset(&data),
+        PresetFormat::Au => decode_aupreset(&String::from_utf8_lossy(&data)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vstpreset_round_trip() {
+        let class_id = *b"VSTWorkshop12345";
+        let state = br#"{"params":{"gain":0.5}}"#.to_vec();
+
+        let encoded = encode_vstpreset(&class_id, &state);
+        let decoded = decode_vstpreset(&encoded).unwrap();
+
+        assert_eq!(decoded.state, state);
+        assert_eq!(decoded.plugin_id, vst3_class_id_hex(&class_id));
+    }
+
+    #[test]
+    fn test_vstpreset_rejects_garbage() {
+        assert!(decode_vstpreset(b"not a preset").is_err());
+
+        // Offsets and sizes that overflow or point past the end are errors, not panics
+        let encoded = encode_vstpreset(b"VSTWorkshop12345", b"state");
+        let mut bad_list = encoded.clone();
+        bad_list[40..48].copy_from_slice(&(i64::MAX - 2).to_le_bytes());
+        assert!(decode_vstpreset(&bad_list).is_err());
+
+        let entry = encoded.len() - 20;
+        let mut bad_chunk = encoded.clone();
+        bad_chunk[entry + 4..entry + 12].copy_from_slice(&(u64::MAX as i64 - 1).to_le_bytes());
+        bad_chunk[entry + 12..entry + 20].copy_from_slice(&16i64.to_le_bytes());
+        assert!(decode_vstpreset(&bad_chunk).is_err());
+
+        let decoded = decode_vstpreset(&encoded).unwrap();
+        assert!(decoded.check_plugin_id(&vst3_class_id_hex(b"VSTWorkshop12345")).is_ok());
+        assert!(decoded.check_plugin_id(&vst3_class_id_hex(b"VSTWorkshop99999")).is_err());
+    }
+
+    #[test]
+    fn test_aupreset_round_trip() {
+        let component = AuComponent::for_plugin(false, "my_comp", "freqlab");
+        let state = vec![0u8, 1, 2, 255, 128];
+
+        let xml = encode_aupreset(&component, "Warm & Punchy", &state);
+        let decoded = decode_aupreset(&xml).unwrap();
+
+        assert_eq!(decoded.state, state);
+        assert_eq!(decoded.name.as_deref(), Some("Warm & Punchy"));
+        assert_eq!(decoded.plugin_id, "aufx/Myco/Freq");
+    }
+
+    #[test]
+    fn test_find_vst3_class_id() {
+        let source = r#"const VST3_CLASS_ID: [u8; 16] = *b"VSTWorkshop00042";"#;
+        assert_eq!(find_vst3_class_id(source), Some(*b"VSTWorkshop00042"));
+        assert_eq!(find_vst3_class_id("no id here"), None);
+    }
+}
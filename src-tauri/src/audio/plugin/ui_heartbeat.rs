@@ -0,0 +1,108 @@
+//! Webview UI heartbeat
+//!
+//! WKWebView plugin UIs sometimes stop compositing after a hot reload, leaving
+//! a blank editor window that still looks open. Generated webview plugins post
+//! a heartbeat from `requestAnimationFrame`, which only fires while the page is
+//! rendering, and the plugin touches a file named after the host's PID. The
+//! plugin only touches the file if it exists, so in a DAW it's a no-op.
+//!
+//! A UI that has never sent a heartbeat (older projects, non-webview plugins)
+//! is never considered stalled.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+/// A rendering UI beats about once a second
+pub const STALL_TIMEOUT: Duration = Duration::from_secs(4);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatStatus {
+    /// No heartbeat since the editor opened - the UI doesn't send them
+    Unarmed,
+    Alive,
+    Stalled,
+}
+
+/// File the plugin touches; see the webview templates in `commands::projects`
+pub fn heartbeat_path() -> PathBuf {
+    std::env::temp_dir().join(format!("freqlab-ui-heartbeat-{}", std::process::id()))
+}
+
+fn status(
+    opened_at: SystemTime,
+    last_beat: Option<SystemTime>,
+    now: SystemTime,
+    on_screen_for: Option<Duration>,
+) -> HeartbeatStatus {
+    let beat = match last_beat {
+        Some(beat) if beat > opened_at => beat,
+        _ => return HeartbeatStatus::Unarmed,
+    };
+    // Pages off screen don't render, so silence only counts once the window
+    // has been visible long enough to have beaten
+    let silent = now.duration_since(beat).unwrap_or_default() > STALL_TIMEOUT;
+    let visible = on_screen_for.is_some_and(|d| d > STALL_TIMEOUT);
+    if silent && visible {
+        HeartbeatStatus::Stalled
+    } else {
+        HeartbeatStatus::Alive
+    }
+}
+
+/// Watches the heartbeat of one open editor window
+pub struct HeartbeatMonitor {
+    opened_at: SystemTime,
+    on_screen_since: Option<Instant>,
+}
+
+impl HeartbeatMonitor {
+    /// Start watching a freshly opened editor (creates the heartbeat file)
+    pub fn start() -> Self {
+        let path = heartbeat_path();
+        if let Err(e) = fs::write(&path, b"") {
+            log::warn!("Failed to create UI heartbeat file {:?}: {}", path, e);
+        }
+        // Beats count only once they're newer than the file itself
+        let opened_at = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .unwrap_or_else(|_| SystemTime::now());
+        Self {
+            opened_at,
+            on_screen_since: None,
+        }
+    }
+
+    /// `on_screen`: whether any part of the window is currently visible
+    pub fn check(&mut self, on_screen: bool) -> HeartbeatStatus {
+        self.on_screen_since = on_screen.then(|| self.on_screen_since.unwrap_or_else(Instant::now));
+        let last_beat = fs::metadata(heartbeat_path()).and_then(|m| m.modified()).ok();
+        let on_screen_for = self.on_screen_since.map(|since| since.elapsed());
+        status(self.opened_at, last_beat, SystemTime::now(), on_screen_for)
+    }
+}
+
+/// Remove the heartbeat file so plugins stop touching it
+pub fn stop() {
+    let _ = fs::remove_file(heartbeat_path());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let opened = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        let at = |secs: u64| opened + Duration::from_secs(secs);
+        let visible = Some(Duration::from_secs(30));
+
+        assert_eq!(status(opened, None, at(60), visible), HeartbeatStatus::Unarmed);
+        assert_eq!(status(opened, Some(opened), at(60), visible), HeartbeatStatus::Unarmed);
+        assert_eq!(status(opened, Some(at(10)), at(11), visible), HeartbeatStatus::Alive);
+        assert_eq!(status(opened, Some(at(10)), at(20), visible), HeartbeatStatus::Stalled);
+        // Covered or minimized, then only just back on screen
+        assert_eq!(status(opened, Some(at(10)), at(20), None), HeartbeatStatus::Alive);
+        assert_eq!(status(opened, Some(at(10)), at(20), Some(Duration::from_secs(1))), HeartbeatStatus::Alive);
+    }
+}
@@ -0,0 +1,347 @@
+//! Streaming sample playback for files too large to decode into memory
+//!
+//! A background thread decodes the file with Symphonia into a ring buffer that
+//! the audio thread drains. Looping is handled by the decoder (it seeks back to
+//! the start at end of stream), so wrap-around is gapless as long as the ring
+//! buffer stays ahead of playback.
+
+use parking_lot::Mutex;
+use ringbuf::{traits::*, HeapRb};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use super::buffer::StereoSample;
+use super::samples::{DecoderSource, PlayheadInfo, SampleInfo};
+
+/// Files whose decoded audio is larger than this are streamed instead of fully decoded (bytes)
+pub const STREAMING_THRESHOLD_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Ring buffer length in seconds of source audio
+const BUFFER_SECS: u32 = 4;
+
+/// Sentinel for "no seek pending"
+const NO_SEEK: u64 = u64::MAX;
+
+/// Memory the fully decoded audio takes (f32 per channel per frame)
+fn decoded_bytes(frames: u64, channels: u32) -> u64 {
+    frames
+        .saturating_mul(channels as u64)
+        .saturating_mul(std::mem::size_of::<f32>() as u64)
+}
+
+/// Whether a file should be streamed rather than loaded into memory
+/// Compressed files decode to many times their size, so this goes by the
+/// decoded length; the file size is the fallback when the header has none.
+pub fn should_stream(path: &Path) -> bool {
+    let decoded = match DecoderSource::open(path) {
+        Ok(source) => source.n_frames.map(|frames| decoded_bytes(frames, source.channels)),
+        // The full loader reports the error
+        Err(_) => return false,
+    };
+    decoded
+        .or_else(|| std::fs::metadata(path).ok().map(|m| m.len()))
+        .is_some_and(|bytes| bytes > STREAMING_THRESHOLD_BYTES)
+}
+
+/// State shared between the audio thread and the decoder thread
+struct StreamControl {
+    /// Frame to seek to (NO_SEEK if none pending)
+    seek_request: AtomicU64,
+    /// Incremented by the audio thread for every seek request
+    seek_generation: AtomicU64,
+    /// Last seek generation the decoder has applied
+    seek_ack: AtomicU64,
+    /// Total frames pushed when the last seek was applied - frames before this are stale
+    seek_boundary: AtomicU64,
+    /// Total frames the decoder has pushed into the ring
+    pushed_total: AtomicU64,
+    looping: AtomicBool,
+    /// Decoder reached the end and isn't looping
+    finished: AtomicBool,
+    shutdown: AtomicBool,
+}
+
+/// A sample decoded on the fly from disk
+pub struct SampleStream {
+    pub info: SampleInfo,
+    /// Mutex is used because ringbuf's consumer isn't Sync (only ever locked by the audio thread)
+    consumer: Mutex<ringbuf::HeapCons<StereoSample>>,
+    control: Arc<StreamControl>,
+    thread: Option<JoinHandle<()>>,
+    /// Frames the audio thread has taken from the ring
+    popped_total: u64,
+    /// Seek generation the audio thread is waiting on (discarding until acked)
+    pending_generation: u64,
+    /// Source frame of the current playback position
+    position: u64,
+    /// Interpolation state
+    current: StereoSample,
+    next: StereoSample,
+    fractional_position: f32,
+    primed: bool,
+}
+
+impl SampleStream {
+    /// Open a file and start decoding it in the background
+    pub fn open(path: &Path, looping: bool) -> Result<Self, String> {
+        let source = DecoderSource::open(path)?;
+        let sample_rate = source.sample_rate;
+
+        let num_samples = source.n_frames.unwrap_or(0) as usize;
+        let info = SampleInfo {
+            name: path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown")
+                .to_string(),
+            path: path.to_string_lossy().to_string(),
+            sample_rate,
+            channels: source.channels,
+            duration_secs: num_samples as f32 / sample_rate as f32,
+            num_samples,
+        };
+
+        let capacity = (sample_rate * BUFFER_SECS) as usize;
+        let (producer, consumer) = HeapRb::<StereoSample>::new(capacity).split();
+
+        let control = Arc::new(StreamControl {
+            seek_request: AtomicU64::new(NO_SEEK),
+            seek_generation: AtomicU64::new(0),
+            seek_ack: AtomicU64::new(0),
+            seek_boundary: AtomicU64::new(0),
+            pushed_total: AtomicU64::new(0),
+            looping: AtomicBool::new(looping),
+            finished: AtomicBool::new(false),
+            shutdown: AtomicBool::new(false),
+        });
+
+        let thread_control = Arc::clone(&control);
+        let thread = std::thread::Builder::new()
+            .name("sample-stream".to_string())
+            .spawn(move || decode_loop(source, producer, thread_control))
+            .map_err(|e| format!("Failed to start stream decoder: {}", e))?;
+
+        log::info!(
+            "Streaming sample {:?} ({} Hz, {:.1}s)",
+            info.name,
+            sample_rate,
+            info.duration_secs
+        );
+
+        Ok(Self {
+            info,
+            consumer: Mutex::new(consumer),
+            control,
+            thread: Some(thread),
+            popped_total: 0,
+            pending_generation: 0,
+            position: 0,
+            current: StereoSample::silence(),
+            next: StereoSample::silence(),
+            fractional_position: 0.0,
+            primed: false,
+        })
+    }
+
+    pub fn set_looping(&self, looping: bool) {
+        self.control.looping.store(looping, Ordering::Relaxed);
+    }
+
+    /// Jump to a position (seconds). Audio already buffered is discarded.
+    pub fn seek(&mut self, position_secs: f32) {
+        let mut frame = (position_secs.max(0.0) * self.info.sample_rate as f32) as u64;
+        if self.info.num_samples > 0 {
+            frame = frame.min(self.info.num_samples as u64);
+        }
+        self.pending_generation = self.control.seek_generation.fetch_add(1, Ordering::AcqRel) + 1;
+        self.control.seek_request.store(frame, Ordering::Release);
+        self.position = frame;
+        self.fractional_position = 0.0;
+        self.primed = false;
+    }
+
+    /// Pop one frame from the ring, skipping audio decoded before a pending seek
+    fn pop_frame(&mut self) -> Option<StereoSample> {
+        let mut consumer = self.consumer.lock();
+
+        if self.pending_generation > 0 {
+            if self.control.seek_ack.load(Ordering::Acquire) < self.pending_generation {
+                // Decoder hasn't seeked yet - drop whatever is buffered
+                self.popped_total += consumer.skip(consumer.occupied_len()) as u64;
+                return None;
+            }
+            let boundary = self.control.seek_boundary.load(Ordering::Acquire);
+            while self.popped_total < boundary {
+                if consumer.try_pop().is_none() {
+                    return None;
+                }
+                self.popped_total += 1;
+            }
+            self.pending_generation = 0;
+        }
+
+        let frame = consumer.try_pop()?;
+        self.popped_total += 1;
+        Some(frame)
+    }
+
+    /// Whether playback has run past the end of a non-looping stream
+    fn is_exhausted(&self) -> bool {
+        self.pending_generation == 0
+            && self.control.finished.load(Ordering::Acquire)
+            && self.consumer.lock().is_empty()
+    }
+
+    /// Produce the next output frame at the given speed (source/engine rate ratio)
+    ///
+    /// Returns None once a non-looping stream has ended. On underrun the last frame
+    /// is held without advancing the playhead.
+    pub fn next_sample(&mut self, speed: f32) -> Option<StereoSample> {
+        if !self.primed {
+            match (self.pop_frame(), self.pop_frame()) {
+                (Some(a), Some(b)) => {
+                    self.current = a;
+                    self.next = b;
+                    self.primed = true;
+                }
+                _ => {
+                    return if self.is_exhausted() { None } else { Some(StereoSample::silence()) };
+                }
+            }
+        }
+
+        let frac = self.fractional_position;
+        let out = StereoSample::new(
+            self.current.left * (1.0 - frac) + self.next.left * frac,
+            self.current.right * (1.0 - frac) + self.next.right * frac,
+        );
+
+        self.fractional_position += speed;
+        while self.fractional_position >= 1.0 {
+            match self.pop_frame() {
+                Some(frame) => {
+                    self.current = self.next;
+                    self.next = frame;
+                    self.fractional_position -= 1.0;
+                    self.position += 1;
+                    if self.info.num_samples > 0 && self.position >= self.info.num_samples as u64 {
+                        // The decoder wrapped back to the start
+                        self.position = 0;
+                    }
+                }
+                None => {
+                    if self.is_exhausted() {
+                        return None;
+                    }
+                    // Underrun - hold position until the decoder catches up
+                    self.fractional_position = self.fractional_position.min(1.0);
+                    break;
+                }
+            }
+        }
+
+        Some(out)
+    }
+
+    pub fn playhead(&self, is_playing: bool) -> PlayheadInfo {
+        PlayheadInfo {
+            position_secs: self.position as f32 / self.info.sample_rate as f32,
+            duration_secs: self.info.duration_secs,
+            loop_start_secs: None,
+            loop_end_secs: None,
+            is_playing,
+        }
+    }
+}
+
+impl Drop for SampleStream {
+    fn drop(&mut self) {
+        self.control.shutdown.store(true, Ordering::Release);
+        // Don't block the caller (possibly holding the player lock) on the join
+        if let Some(thread) = self.thread.take() {
+            std::thread::spawn(move || {
+                let _ = thread.join();
+            });
+        }
+    }
+}
+
+/// Background decoder: keeps the ring buffer full, handles seeks and looping
+fn decode_loop(
+    mut source: DecoderSource,
+    mut producer: ringbuf::HeapProd<StereoSample>,
+    control: Arc<StreamControl>,
+) {
+    let mut decoded: Vec<StereoSample> = Vec::new();
+    let mut offset = 0;
+    let mut skip: u64 = 0;
+
+    while !control.shutdown.load(Ordering::Acquire) {
+        // Apply the latest seek request (take the request before reading the
+        // generation so the ack never lags the request it answers)
+        let request = control.seek_request.swap(NO_SEEK, Ordering::AcqRel);
+        if request != NO_SEEK {
+            let generation = control.seek_generation.load(Ordering::Acquire);
+            match source.seek(request) {
+                Ok(extra) => skip = extra,
+                Err(e) => log::warn!("Sample stream: {}", e),
+            }
+            decoded.clear();
+            offset = 0;
+            control.finished.store(false, Ordering::Release);
+            control
+                .seek_boundary
+                .store(control.pushed_total.load(Ordering::Acquire), Ordering::Release);
+            control.seek_ack.store(generation, Ordering::Release);
+        }
+
+        // Push what we have
+        if offset < decoded.len() {
+            let pushed = producer.push_slice(&decoded[offset..]);
+            offset += pushed;
+            control.pushed_total.fetch_add(pushed as u64, Ordering::AcqRel);
+            if offset < decoded.len() {
+                // Ring is full
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            continue;
+        }
+
+        if control.finished.load(Ordering::Acquire) {
+            std::thread::sleep(Duration::from_millis(10));
+            continue;
+        }
+
+        decoded.clear();
+        offset = 0;
+        match source.decode_next(&mut decoded) {
+            Ok(true) => {
+                if skip > 0 {
+                    let n = (skip as usize).min(decoded.len());
+                    decoded.drain(..n);
+                    skip -= n as u64;
+                }
+            }
+            Ok(false) => {
+                if control.looping.load(Ordering::Relaxed) {
+                    match source.seek(0) {
+                        Ok(extra) => skip = extra,
+                        Err(e) => {
+                            log::warn!("Sample stream: failed to loop: {}", e);
+                            control.finished.store(true, Ordering::Release);
+                        }
+                    }
+                } else {
+                    control.finished.store(true, Ordering::Release);
+                }
+            }
+            Err(e) => {
+                log::error!("Sample stream decode failed: {}", e);
+                control.finished.store(true, Ordering::Release);
+            }
+        }
+    }
+}
@@ -1,16 +1,21 @@
 //! Audio sample loading and playback using Symphonia
 
+use rubato::{
+    Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction,
+};
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::path::Path;
 use symphonia::core::audio::{AudioBufferRef, Signal};
-use symphonia::core::codecs::DecoderOptions;
-use symphonia::core::formats::FormatOptions;
+use symphonia::core::codecs::{Decoder, DecoderOptions};
+use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
 use symphonia::core::io::MediaSourceStream;
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 
 use super::buffer::StereoSample;
+use super::sample_stream::SampleStream;
+use super::stretch::WsolaStretcher;
 
 /// Information about a loaded sample
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,16 +35,20 @@ pub struct AudioSample {
     pub data: Vec<StereoSample>,
 }
 
-impl AudioSample {
-    /// Load an audio file from disk
-    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
-        let path = path.as_ref();
-        let name = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+/// An opened audio file positioned at its first packet
+pub(super) struct DecoderSource {
+    pub format: Box<dyn FormatReader>,
+    pub decoder: Box<dyn Decoder>,
+    pub track_id: u32,
+    pub sample_rate: u32,
+    pub channels: u32,
+    /// Total frame count if the container reports it
+    pub n_frames: Option<u64>,
+}
 
+impl DecoderSource {
+    /// Probe a file and create a decoder for its default track
+    pub fn open(path: &Path) -> Result<Self, String> {
         let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
 
         let mss = MediaSourceStream::new(Box::new(file), Default::default());
@@ -60,7 +69,7 @@ impl AudioSample {
             )
             .map_err(|e| format!("Failed to probe format: {}", e))?;
 
-        let mut format = probed.format;
+        let format = probed.format;
 
         // Get the default track
         let track = format
@@ -77,25 +86,37 @@ impl AudioSample {
             .channels
             .map(|c| c.count() as u32)
             .unwrap_or(2);
+        let n_frames = track.codec_params.n_frames;
 
         // Create decoder
-        let mut decoder = symphonia::default::get_codecs()
+        let decoder = symphonia::default::get_codecs()
             .make(&track.codec_params, &DecoderOptions::default())
             .map_err(|e| format!("Failed to create decoder: {}", e))?;
 
-        // Decode all samples
-        let mut samples: Vec<StereoSample> = Vec::new();
+        Ok(Self {
+            format,
+            decoder,
+            track_id,
+            sample_rate,
+            channels,
+            n_frames,
+        })
+    }
 
+    /// Decode the next packet of our track, appending frames to `output`
+    /// Returns false at end of stream
+    pub fn decode_next(&mut self, output: &mut Vec<StereoSample>) -> Result<bool, String> {
         loop {
-            match format.next_packet() {
+            match self.format.next_packet() {
                 Ok(packet) => {
-                    if packet.track_id() != track_id {
+                    if packet.track_id() != self.track_id {
                         continue;
                     }
 
-                    match decoder.decode(&packet) {
+                    match self.decoder.decode(&packet) {
                         Ok(audio_buf) => {
-                            append_audio_buffer(&audio_buf, &mut samples, channels);
+                            append_audio_buffer(&audio_buf, output, self.channels);
+                            return Ok(true);
                         }
                         Err(symphonia::core::errors::Error::DecodeError(_)) => {
                             // Skip decode errors
@@ -110,13 +131,49 @@ impl AudioSample {
                     if e.kind() == std::io::ErrorKind::UnexpectedEof =>
                 {
                     // End of stream
-                    break;
+                    return Ok(false);
                 }
                 Err(e) => {
                     return Err(format!("Format error: {}", e));
                 }
             }
         }
+    }
+
+    /// Seek to a frame. Returns how many decoded frames to discard to land exactly on it.
+    pub fn seek(&mut self, frame: u64) -> Result<u64, String> {
+        let seeked = self
+            .format
+            .seek(
+                SeekMode::Accurate,
+                SeekTo::TimeStamp {
+                    ts: frame,
+                    track_id: self.track_id,
+                },
+            )
+            .map_err(|e| format!("Seek failed: {}", e))?;
+        self.decoder.reset();
+        Ok(seeked.required_ts.saturating_sub(seeked.actual_ts))
+    }
+}
+
+impl AudioSample {
+    /// Load an audio file from disk
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let mut source = DecoderSource::open(path)?;
+        let sample_rate = source.sample_rate;
+        let channels = source.channels;
+
+        // Decode all samples
+        let mut samples: Vec<StereoSample> = Vec::new();
+        while source.decode_next(&mut samples)? {}
 
         let duration_secs = samples.len() as f32 / sample_rate as f32;
 
@@ -143,6 +200,182 @@ impl AudioSample {
     }
 }
 
+/// Frames per chunk fed to the offline resampler
+const RESAMPLE_CHUNK_SIZE: usize = 1024;
+
+impl AudioSample {
+    /// Convert the sample to a new sample rate with a windowed-sinc resampler
+    ///
+    /// Done once at load time so playback at the engine rate needs no
+    /// interpolation (and 44.1 kHz material doesn't play detuned at 48 kHz).
+    pub fn resample_to(&mut self, target_rate: u32) -> Result<(), String> {
+        let source_rate = self.info.sample_rate;
+        if source_rate == target_rate || self.data.is_empty() {
+            return Ok(());
+        }
+        if source_rate == 0 || target_rate == 0 {
+            return Err("Invalid sample rate for resampling".to_string());
+        }
+
+        let ratio = target_rate as f64 / source_rate as f64;
+        let params = SincInterpolationParameters {
+            sinc_len: 256,
+            f_cutoff: 0.95,
+            interpolation: SincInterpolationType::Cubic,
+            oversampling_factor: 256,
+            window: WindowFunction::BlackmanHarris2,
+        };
+        let mut resampler = SincFixedIn::<f32>::new(ratio, 1.0, params, RESAMPLE_CHUNK_SIZE, 2)
+            .map_err(|e| format!("Failed to create resampler: {}", e))?;
+
+        let left: Vec<f32> = self.data.iter().map(|s| s.left).collect();
+        let right: Vec<f32> = self.data.iter().map(|s| s.right).collect();
+
+        let expected_len = (self.data.len() as f64 * ratio).round() as usize;
+        let delay = resampler.output_delay();
+        let mut out_left = Vec::with_capacity(expected_len + delay + RESAMPLE_CHUNK_SIZE);
+        let mut out_right = Vec::with_capacity(expected_len + delay + RESAMPLE_CHUNK_SIZE);
+
+        let mut pos = 0;
+        while pos < left.len() {
+            let end = (pos + RESAMPLE_CHUNK_SIZE).min(left.len());
+            let chunk = [&left[pos..end], &right[pos..end]];
+            let output = if end - pos == RESAMPLE_CHUNK_SIZE {
+                resampler.process(&chunk, None)
+            } else {
+                resampler.process_partial(Some(&chunk), None)
+            }
+            .map_err(|e| format!("Resampling failed: {}", e))?;
+            out_left.extend_from_slice(&output[0]);
+            out_right.extend_from_slice(&output[1]);
+            pos = end;
+        }
+
+        // Flush the filter tail so the end of the file isn't cut off by the delay
+        while out_left.len() < expected_len + delay {
+            let output = resampler
+                .process_partial(None::<&[&[f32]]>, None)
+                .map_err(|e| format!("Resampling failed: {}", e))?;
+            if output[0].is_empty() {
+                break;
+            }
+            out_left.extend_from_slice(&output[0]);
+            out_right.extend_from_slice(&output[1]);
+        }
+
+        let data: Vec<StereoSample> = out_left
+            .into_iter()
+            .zip(out_right)
+            .skip(delay)
+            .take(expected_len)
+            .map(|(l, r)| StereoSample::new(l, r))
+            .collect();
+
+        self.info.sample_rate = target_rate;
+        self.info.num_samples = data.len();
+        self.info.duration_secs = data.len() as f32 / target_rate as f32;
+        self.data = data;
+        Ok(())
+    }
+
+    /// Measure loudness as RMS in dBFS across both channels (ungated)
+    /// Returns -inf for digital silence
+    pub fn rms_db(&self) -> f32 {
+        if self.data.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+        let sum: f64 = self
+            .data
+            .iter()
+            .map(|s| (s.left as f64).powi(2) + (s.right as f64).powi(2))
+            .sum();
+        let mean = sum / (self.data.len() as f64 * 2.0);
+        if mean <= 0.0 {
+            f32::NEG_INFINITY
+        } else {
+            (10.0 * mean.log10()) as f32
+        }
+    }
+
+    /// Absolute sample peak in dBFS
+    pub fn peak_db(&self) -> f32 {
+        let peak = self
+            .data
+            .iter()
+            .map(|s| s.left.abs().max(s.right.abs()))
+            .fold(0.0f32, f32::max);
+        if peak <= 0.0 {
+            f32::NEG_INFINITY
+        } else {
+            20.0 * peak.log10()
+        }
+    }
+
+    /// Compute the gain (dB) that brings this sample to a target RMS level
+    /// without pushing peaks above `peak_ceiling_db`
+    pub fn normalization_gain_db(&self, target_rms_db: f32, peak_ceiling_db: f32) -> f32 {
+        let rms = self.rms_db();
+        if !rms.is_finite() {
+            return 0.0;
+        }
+        let gain = target_rms_db - rms;
+        let peak = self.peak_db();
+        if peak.is_finite() {
+            gain.min(peak_ceiling_db - peak)
+        } else {
+            gain
+        }
+    }
+
+    /// Apply a gain in dB to every sample
+    pub fn apply_gain_db(&mut self, gain_db: f32) {
+        let gain = 10f32.powf(gain_db / 20.0);
+        for s in self.data.iter_mut() {
+            s.left *= gain;
+            s.right *= gain;
+        }
+    }
+
+    /// Write the sample as a 32-bit float stereo WAV file
+    pub fn write_wav<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        write_wav_f32(path.as_ref(), &self.data, self.info.sample_rate)
+    }
+}
+
+/// Write stereo samples as a 32-bit float WAV file
+pub fn write_wav_f32(path: &Path, data: &[StereoSample], sample_rate: u32) -> Result<(), String> {
+    use std::io::Write;
+
+    let channels: u16 = 2;
+    let bits: u16 = 32;
+    let block_align = channels * bits / 8;
+    let byte_rate = sample_rate * block_align as u32;
+    let data_len = (data.len() * block_align as usize) as u32;
+
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&3u16.to_le_bytes()); // WAVE_FORMAT_IEEE_FLOAT
+    out.extend_from_slice(&channels.to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&byte_rate.to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&bits.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for s in data {
+        out.extend_from_slice(&s.left.to_le_bytes());
+        out.extend_from_slice(&s.right.to_le_bytes());
+    }
+
+    let mut file = File::create(path).map_err(|e| format!("Failed to create WAV file: {}", e))?;
+    file.write_all(&out)
+        .map_err(|e| format!("Failed to write WAV file: {}", e))
+}
+
 /// Append decoded audio buffer to our sample vector
 fn append_audio_buffer(buf: &AudioBufferRef, output: &mut Vec<StereoSample>, channels: u32) {
     match buf {
@@ -283,39 +516,97 @@ fn append_audio_buffer(buf: &AudioBufferRef, output: &mut Vec<StereoSample>, cha
     }
 }
 
+/// Playback position report for the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayheadInfo {
+    pub position_secs: f32,
+    pub duration_secs: f32,
+    /// Loop region start (None = start of sample)
+    pub loop_start_secs: Option<f32>,
+    /// Loop region end (None = end of sample)
+    pub loop_end_secs: Option<f32>,
+    pub is_playing: bool,
+}
+
+/// How tempo changes are applied during sample playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TempoMode {
+    /// Play faster/slower by resampling (pitch follows tempo)
+    Resample,
+    /// WSOLA time-stretch (pitch stays the same)
+    Stretch,
+}
+
+impl Default for TempoMode {
+    fn default() -> Self {
+        Self::Resample
+    }
+}
+
 /// Sample player that handles playback position and looping
 pub struct SamplePlayer {
     sample: Option<AudioSample>,
+    /// Disk-streamed sample (used instead of `sample` for very large files)
+    stream: Option<SampleStream>,
     position: usize,
     is_playing: bool,
     is_looping: bool,
     /// Playback speed ratio (for resampling)
     speed_ratio: f32,
     fractional_position: f32,
+    /// Loop region in source frames (start inclusive, end exclusive)
+    loop_region: Option<(usize, usize)>,
+    /// Playback tempo (1.0 = original speed)
+    tempo: f32,
+    tempo_mode: TempoMode,
+    stretcher: WsolaStretcher,
 }
 
 impl SamplePlayer {
     pub fn new() -> Self {
         Self {
             sample: None,
+            stream: None,
             position: 0,
             is_playing: false,
             is_looping: true,
             speed_ratio: 1.0,
             fractional_position: 0.0,
+            loop_region: None,
+            tempo: 1.0,
+            tempo_mode: TempoMode::Resample,
+            stretcher: WsolaStretcher::new(),
         }
     }
 
     pub fn load_sample(&mut self, sample: AudioSample) {
         self.sample = Some(sample);
+        self.stream = None;
         self.position = 0;
         self.fractional_position = 0.0;
+        // A loop region only makes sense for the sample it was set on
+        self.loop_region = None;
+        self.stretcher.reset(0.0);
+    }
+
+    /// Play a sample streamed from disk
+    /// Loop regions and time-stretching need random access, so they're unavailable
+    pub fn load_stream(&mut self, stream: SampleStream) {
+        stream.set_looping(self.is_looping);
+        self.stream = Some(stream);
+        self.sample = None;
+        self.position = 0;
+        self.fractional_position = 0.0;
+        self.loop_region = None;
     }
 
     pub fn unload(&mut self) {
         self.sample = None;
+        self.stream = None;
         self.position = 0;
         self.is_playing = false;
+        self.loop_region = None;
     }
 
     pub fn play(&mut self) {
@@ -324,8 +615,99 @@ impl SamplePlayer {
 
     pub fn stop(&mut self) {
         self.is_playing = false;
-        self.position = 0;
+        self.position = self.loop_region.map(|(start, _)| start).unwrap_or(0);
         self.fractional_position = 0.0;
+        self.stretcher.reset(self.position as f64);
+        if let Some(stream) = self.stream.as_mut() {
+            stream.seek(0.0);
+        }
+    }
+
+    /// Set playback tempo (0.25 - 2.0) and how it's applied
+    /// In Stretch mode the pitch is preserved; in Resample mode it follows the tempo
+    pub fn set_tempo(&mut self, tempo: f32, mode: TempoMode) {
+        self.tempo = tempo.clamp(0.25, 2.0);
+        if mode != self.tempo_mode {
+            self.stretcher.reset(self.position as f64);
+        }
+        self.tempo_mode = mode;
+    }
+
+    pub fn tempo(&self) -> (f32, TempoMode) {
+        (self.tempo, self.tempo_mode)
+    }
+
+    /// Whether playback currently goes through the time-stretcher
+    fn is_stretching(&self) -> bool {
+        self.tempo_mode == TempoMode::Stretch && (self.tempo - 1.0).abs() > f32::EPSILON
+    }
+
+    /// Convert seconds to a source frame index, clamped to the sample length
+    fn secs_to_frame(&self, secs: f32) -> Option<usize> {
+        let sample = self.sample.as_ref()?;
+        let frame = (secs.max(0.0) * sample.info.sample_rate as f32) as usize;
+        Some(frame.min(sample.data.len()))
+    }
+
+    fn frame_to_secs(&self, frame: usize) -> f32 {
+        match &self.sample {
+            Some(s) if s.info.sample_rate > 0 => frame as f32 / s.info.sample_rate as f32,
+            _ => 0.0,
+        }
+    }
+
+    /// Jump to a position in the sample (seconds)
+    pub fn seek(&mut self, position_secs: f32) {
+        if let Some(stream) = self.stream.as_mut() {
+            stream.seek(position_secs);
+            return;
+        }
+        if let Some(frame) = self.secs_to_frame(position_secs) {
+            self.position = frame;
+            self.fractional_position = 0.0;
+            self.stretcher.reset(frame as f64);
+        }
+    }
+
+    /// Set the loop region (seconds). When looping is enabled, playback wraps from
+    /// `end_secs` back to `start_secs` instead of covering the whole sample.
+    pub fn set_loop_region(&mut self, start_secs: f32, end_secs: f32) -> Result<(), String> {
+        if self.stream.is_some() {
+            return Err("Loop regions aren't available for streamed files".to_string());
+        }
+        let start = self.secs_to_frame(start_secs).ok_or("No sample loaded")?;
+        let end = self.secs_to_frame(end_secs).ok_or("No sample loaded")?;
+        if end <= start {
+            return Err("Loop end must be after loop start".to_string());
+        }
+        self.loop_region = Some((start, end));
+        // Keep the playhead inside the new region
+        if self.position < start || self.position >= end {
+            self.position = start;
+            self.fractional_position = 0.0;
+            self.stretcher.reset(start as f64);
+        }
+        Ok(())
+    }
+
+    /// Clear the loop region (loop the whole sample again)
+    pub fn clear_loop_region(&mut self) {
+        self.loop_region = None;
+    }
+
+    /// Current playback position report (None if no sample is loaded)
+    pub fn playhead(&self) -> Option<PlayheadInfo> {
+        if let Some(stream) = &self.stream {
+            return Some(stream.playhead(self.is_playing));
+        }
+        let sample = self.sample.as_ref()?;
+        Some(PlayheadInfo {
+            position_secs: self.frame_to_secs(self.position),
+            duration_secs: sample.info.duration_secs,
+            loop_start_secs: self.loop_region.map(|(s, _)| self.frame_to_secs(s)),
+            loop_end_secs: self.loop_region.map(|(_, e)| self.frame_to_secs(e)),
+            is_playing: self.is_playing,
+        })
     }
 
     pub fn pause(&mut self) {
@@ -334,6 +716,9 @@ impl SamplePlayer {
 
     pub fn set_looping(&mut self, looping: bool) {
         self.is_looping = looping;
+        if let Some(stream) = &self.stream {
+            stream.set_looping(looping);
+        }
     }
 
     pub fn set_speed_ratio(&mut self, ratio: f32) {
@@ -345,7 +730,7 @@ impl SamplePlayer {
     }
 
     pub fn has_sample(&self) -> bool {
-        self.sample.is_some()
+        self.sample.is_some() || self.stream.is_some()
     }
 
     /// Get the next sample (with resampling if needed)
@@ -354,14 +739,31 @@ impl SamplePlayer {
             return StereoSample::silence();
         }
 
+        if let Some(stream) = self.stream.as_mut() {
+            // Tempo can only resample here - the stretcher needs random access
+            return match stream.next_sample(self.speed_ratio * self.tempo) {
+                Some(out) => out,
+                None => {
+                    self.is_playing = false;
+                    StereoSample::silence()
+                }
+            };
+        }
+
         let sample = match &self.sample {
             Some(s) => s,
             None => return StereoSample::silence(),
         };
 
-        if self.position >= sample.data.len() {
+        // Only honour the loop region while looping - otherwise play through to the end
+        let (loop_start, loop_end) = match self.loop_region {
+            Some((start, end)) if self.is_looping => (start, end.min(sample.data.len())),
+            _ => (0, sample.data.len()),
+        };
+
+        if self.position >= loop_end {
             if self.is_looping {
-                self.position = 0;
+                self.position = loop_start;
                 self.fractional_position = 0.0;
             } else {
                 self.is_playing = false;
@@ -369,6 +771,25 @@ impl SamplePlayer {
             }
         }
 
+        if self.is_stretching() {
+            return match self.stretcher.next_sample(
+                sample,
+                (loop_start, loop_end),
+                self.is_looping,
+                self.speed_ratio,
+                self.tempo,
+            ) {
+                Some(out) => {
+                    self.position = self.stretcher.position() as usize;
+                    out
+                }
+                None => {
+                    self.is_playing = false;
+                    StereoSample::silence()
+                }
+            };
+        }
+
         // Linear interpolation for resampling
         let current = sample.get_sample(self.position);
         let next = sample.get_sample(self.position + 1);
@@ -379,8 +800,8 @@ impl SamplePlayer {
             current.right * (1.0 - frac) + next.right * frac,
         );
 
-        // Advance position
-        self.fractional_position += self.speed_ratio;
+        // Advance position (tempo scales the step in resample mode, changing pitch)
+        self.fractional_position += self.speed_ratio * self.tempo;
         while self.fractional_position >= 1.0 {
             self.fractional_position -= 1.0;
             self.position += 1;
@@ -402,3 +823,89 @@ impl Default for SamplePlayer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a mono ramp sample (value = frame index) at 10 Hz for easy second math
+    fn ramp_sample(frames: usize) -> AudioSample {
+        AudioSample {
+            info: SampleInfo {
+                name: "ramp".to_string(),
+                path: String::new(),
+                sample_rate: 10,
+                channels: 1,
+                duration_secs: frames as f32 / 10.0,
+                num_samples: frames,
+            },
+            data: (0..frames).map(|i| StereoSample::mono(i as f32)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_loop_region_wraps() {
+        let mut player = SamplePlayer::new();
+        player.load_sample(ramp_sample(100));
+        player.set_loop_region(1.0, 1.5).unwrap(); // frames 10..15
+        player.play();
+
+        let values: Vec<f32> = (0..7).map(|_| player.next_sample().left).collect();
+        assert_eq!(values, vec![10.0, 11.0, 12.0, 13.0, 14.0, 10.0, 11.0]);
+    }
+
+    #[test]
+    fn test_seek_and_playhead() {
+        let mut player = SamplePlayer::new();
+        player.load_sample(ramp_sample(100));
+        player.seek(5.0);
+
+        let playhead = player.playhead().unwrap();
+        assert!((playhead.position_secs - 5.0).abs() < 1e-6);
+        assert!((playhead.duration_secs - 10.0).abs() < 1e-6);
+        assert!(playhead.loop_start_secs.is_none());
+    }
+
+    #[test]
+    fn test_resample_to_engine_rate() {
+        let rate = 44100;
+        let mut sample = AudioSample {
+            info: SampleInfo {
+                name: "sine".to_string(),
+                path: String::new(),
+                sample_rate: rate,
+                channels: 1,
+                duration_secs: 1.0,
+                num_samples: rate as usize,
+            },
+            data: (0..rate as usize)
+                .map(|i| {
+                    StereoSample::mono(
+                        (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / rate as f32).sin(),
+                    )
+                })
+                .collect(),
+        };
+
+        sample.resample_to(48000).unwrap();
+        assert_eq!(sample.info.sample_rate, 48000);
+        assert_eq!(sample.data.len(), 48000);
+
+        // Still 1 kHz at the new rate (no detuning) and no level loss
+        let crossings = sample
+            .data
+            .windows(2)
+            .filter(|w| w[0].left <= 0.0 && w[1].left > 0.0)
+            .count();
+        assert!((crossings as i32 - 1000).abs() <= 2, "got {} crossings", crossings);
+        assert!((sample.peak_db()).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_invalid_loop_region_rejected() {
+        let mut player = SamplePlayer::new();
+        assert!(player.set_loop_region(0.0, 1.0).is_err()); // no sample
+        player.load_sample(ramp_sample(100));
+        assert!(player.set_loop_region(2.0, 1.0).is_err());
+    }
+}
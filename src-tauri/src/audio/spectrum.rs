@@ -1,6 +1,7 @@
 //! FFT-based spectrum analyzer for real-time audio visualization
 
 use realfft::{RealFftPlanner, RealToComplex};
+use rayon::prelude::*;
 use std::sync::Arc;
 
 /// Number of frequency bands for visualization
@@ -147,6 +148,11 @@ impl SpectrumAnalyzer {
         }
     }
 
+    /// Set band smoothing (0.0 = none, each analysis stands alone)
+    pub fn set_smoothing(&mut self, smoothing: f32) {
+        self.smoothing = smoothing.clamp(0.0, 0.99);
+    }
+
     /// Get current band magnitudes (0.0 - 1.0)
     pub fn get_magnitudes(&self) -> [f32; NUM_BANDS] {
         self.band_magnitudes
@@ -174,3 +180,174 @@ impl SpectrumAnalyzer {
         self.band_magnitudes.fill(0.0);
     }
 }
+
+// ============================================================================
+// Spectrogram History
+// ============================================================================
+
+/// Rows recorded per second of audio in the spectrogram history
+pub const HISTORY_ROWS_PER_SEC: u32 = 20;
+
+/// Seconds of spectrum history kept for spectrogram export
+pub const HISTORY_SECONDS: u32 = 60;
+
+/// Width in pixels of one history row in a rendered spectrogram
+const COLUMN_WIDTH: usize = 4;
+
+/// Height in pixels of one band in a rendered spectrogram
+const BAND_HEIGHT: usize = 8;
+
+/// Pixels between stacked panels (input above output)
+const PANEL_GAP: usize = 4;
+
+/// dB floor of the spectrogram color scale
+const SPECTROGRAM_FLOOR_DB: f32 = -60.0;
+
+/// One analysis frame of input (pre-FX) and output (post-FX) band magnitudes
+#[derive(Clone, Copy)]
+pub struct SpectrogramRow {
+    pub input: [f32; NUM_BANDS],
+    pub output: [f32; NUM_BANDS],
+}
+
+/// Fixed-size ring of recent spectrum frames
+/// Storage is allocated up front so the audio thread can push without allocating.
+pub struct SpectrumHistory {
+    rows: Vec<SpectrogramRow>,
+    write_pos: usize,
+    len: usize,
+}
+
+impl SpectrumHistory {
+    pub fn new(capacity: usize) -> Self {
+        let empty = SpectrogramRow {
+            input: [0.0; NUM_BANDS],
+            output: [0.0; NUM_BANDS],
+        };
+        Self {
+            rows: vec![empty; capacity.max(1)],
+            write_pos: 0,
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, row: SpectrogramRow) {
+        self.rows[self.write_pos] = row;
+        self.write_pos = (self.write_pos + 1) % self.rows.len();
+        self.len = (self.len + 1).min(self.rows.len());
+    }
+
+    /// The most recent `count` rows, oldest first
+    pub fn latest(&self, count: usize) -> Vec<SpectrogramRow> {
+        let count = count.min(self.len);
+        let capacity = self.rows.len();
+        let start = (self.write_pos + capacity - count) % capacity;
+        (0..count).map(|i| self.rows[(start + i) % capacity]).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.write_pos = 0;
+        self.len = 0;
+    }
+}
+
+/// Map a band magnitude to a heat-map color (black -> purple -> orange -> yellow)
+fn spectrogram_color(magnitude: f32) -> [u8; 3] {
+    let db = SpectrumAnalyzer::magnitude_to_db(magnitude).max(SPECTROGRAM_FLOOR_DB);
+    let t = (1.0 - db / SPECTROGRAM_FLOOR_DB).clamp(0.0, 1.0);
+
+    const STOPS: [(f32, [f32; 3]); 4] = [
+        (0.0, [0.0, 0.0, 0.0]),
+        (0.4, [90.0, 20.0, 130.0]),
+        (0.75, [240.0, 110.0, 30.0]),
+        (1.0, [255.0, 240.0, 120.0]),
+    ];
+    for pair in STOPS.windows(2) {
+        let (t0, c0) = pair[0];
+        let (t1, c1) = pair[1];
+        if t <= t1 {
+            let f = (t - t0) / (t1 - t0);
+            return [0, 1, 2].map(|i| (c0[i] + (c1[i] - c0[i]) * f).round() as u8);
+        }
+    }
+    [255, 240, 120]
+}
+
+/// Render spectrogram panels into an RGB8 image, stacked top to bottom
+/// Time runs left to right and low frequencies sit at the bottom of each panel.
+/// Lines are colored in parallel on `pool`. Returns (width, height, pixels).
+pub fn render_spectrogram(pool: &rayon::ThreadPool, panels: &[&[[f32; NUM_BANDS]]]) -> (u32, u32, Vec<u8>) {
+    let columns = panels.iter().map(|p| p.len()).max().unwrap_or(0).max(1);
+    let width = columns * COLUMN_WIDTH;
+    let panel_height = NUM_BANDS * BAND_HEIGHT;
+    let height = panels.len().max(1) * panel_height + panels.len().saturating_sub(1) * PANEL_GAP;
+
+    // Gaps between panels stay dark grey
+    let mut pixels = vec![40u8; width * height * 3];
+
+    // Image lines are independent, so they're colored in parallel
+    pool.install(|| {
+        pixels.par_chunks_mut(width * 3).enumerate().for_each(|(line_idx, line)| {
+            let panel_idx = line_idx / (panel_height + PANEL_GAP);
+            let y = line_idx % (panel_height + PANEL_GAP);
+            let Some(rows) = panels.get(panel_idx).filter(|_| y < panel_height) else {
+                return;
+            };
+            let band = NUM_BANDS - 1 - y / BAND_HEIGHT;
+            for (x, pixel) in line.chunks_exact_mut(3).enumerate() {
+                let color = rows
+                    .get(x / COLUMN_WIDTH)
+                    .map(|row| spectrogram_color(row[band]))
+                    .unwrap_or([0, 0, 0]);
+                pixel.copy_from_slice(&color);
+            }
+        })
+    });
+
+    (width as u32, height as u32, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(value: f32) -> SpectrogramRow {
+        SpectrogramRow {
+            input: [value; NUM_BANDS],
+            output: [value; NUM_BANDS],
+        }
+    }
+
+    #[test]
+    fn test_spectrum_history_wraps() {
+        let mut history = SpectrumHistory::new(3);
+        assert!(history.latest(10).is_empty());
+
+        for i in 1..=5 {
+            history.push(row(i as f32));
+        }
+        let latest: Vec<f32> = history.latest(10).iter().map(|r| r.output[0]).collect();
+        assert_eq!(latest, vec![3.0, 4.0, 5.0]);
+
+        let last_two: Vec<f32> = history.latest(2).iter().map(|r| r.output[0]).collect();
+        assert_eq!(last_two, vec![4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_render_spectrogram() {
+        let mut loud_low = [0.0f32; NUM_BANDS];
+        loud_low[0] = 1.0;
+        let rows = vec![loud_low; 10];
+
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let (width, height, pixels) = render_spectrogram(&pool, &[&rows, &rows]);
+        assert_eq!(width as usize, 10 * COLUMN_WIDTH);
+        assert_eq!(height as usize, 2 * NUM_BANDS * BAND_HEIGHT + PANEL_GAP);
+        assert_eq!(pixels.len(), (width * height * 3) as usize);
+
+        // Lowest band (bottom row of the first panel) is bright, top row is black
+        let bottom = (NUM_BANDS * BAND_HEIGHT - 1) * width as usize * 3;
+        assert_eq!(&pixels[bottom..bottom + 3], &[255, 240, 120]);
+        assert_eq!(&pixels[0..3], &[0, 0, 0]);
+    }
+}
@@ -1,12 +1,19 @@
 //! Stereo field analyzer for real-time stereo imaging visualization
 //!
 //! Computes polar sample positions and stereo correlation coefficient
-//! for visualizing stereo width in a semicircular "sound field" display.
+//! for visualizing stereo width in a semicircular "sound field" display,
+//! plus mid/side levels and raw L/R pairs for a Lissajous goniometer.
 
 /// Number of sample positions to track for particle visualization
 /// Larger = denser particle cloud, but more memory/bandwidth
 pub const STEREO_HISTORY_SIZE: usize = 2048;
 
+/// Number of raw L/R sample pairs kept for the Lissajous/goniometer display
+pub const LISSAJOUS_SIZE: usize = 512;
+
+/// Keep every Nth sample pair for the Lissajous display (~90ms of audio at 44.1kHz)
+const LISSAJOUS_DECIMATION: usize = 8;
+
 /// Stereo field analyzer
 ///
 /// Tracks sample positions in polar coordinates and computes
@@ -32,6 +39,20 @@ pub struct StereoAnalyzer {
 
     /// Window size for correlation calculation
     correlation_window: usize,
+
+    /// Ring buffer of decimated raw (left, right) pairs for the goniometer
+    lissajous: [(f32, f32); LISSAJOUS_SIZE],
+    lissajous_pos: usize,
+    lissajous_skip: usize,
+
+    /// Running sums for mid/side RMS (every sample, including quiet ones)
+    sum_m2: f32,
+    sum_s2: f32,
+    ms_count: usize,
+
+    /// Smoothed mid/side RMS levels (linear)
+    mid_level: f32,
+    side_level: f32,
 }
 
 impl StereoAnalyzer {
@@ -46,6 +67,14 @@ impl StereoAnalyzer {
             correlation: 1.0, // Start at mono
             smoothing: 0.95,  // Smooth correlation to prevent jitter
             correlation_window: 4096, // About 100ms at 44.1kHz
+            lissajous: [(0.0, 0.0); LISSAJOUS_SIZE],
+            lissajous_pos: 0,
+            lissajous_skip: 0,
+            sum_m2: 0.0,
+            sum_s2: 0.0,
+            ms_count: 0,
+            mid_level: 0.0,
+            side_level: 0.0,
         }
     }
 
@@ -61,6 +90,8 @@ impl StereoAnalyzer {
             return;
         }
 
+        self.push_mid_side(left, right);
+
         // Calculate magnitude (Euclidean distance)
         let magnitude = (left * left + right * right).sqrt();
 
@@ -95,6 +126,35 @@ impl StereoAnalyzer {
         }
     }
 
+    /// Update mid/side levels and the Lissajous buffer
+    /// Runs for every sample so silence pulls the levels down instead of freezing them.
+    fn push_mid_side(&mut self, left: f32, right: f32) {
+        self.lissajous_skip += 1;
+        if self.lissajous_skip >= LISSAJOUS_DECIMATION {
+            self.lissajous_skip = 0;
+            self.lissajous[self.lissajous_pos] = (left, right);
+            self.lissajous_pos = (self.lissajous_pos + 1) % LISSAJOUS_SIZE;
+        }
+
+        let mid = (left + right) * 0.5;
+        let side = (left - right) * 0.5;
+        self.sum_m2 += mid * mid;
+        self.sum_s2 += side * side;
+        self.ms_count += 1;
+
+        if self.ms_count >= self.correlation_window {
+            let count = self.ms_count as f32;
+            let mid_rms = (self.sum_m2 / count).sqrt();
+            let side_rms = (self.sum_s2 / count).sqrt();
+            // Lighter smoothing than correlation so level meters stay responsive
+            self.mid_level = self.mid_level * 0.5 + mid_rms * 0.5;
+            self.side_level = self.side_level * 0.5 + side_rms * 0.5;
+            self.sum_m2 = 0.0;
+            self.sum_s2 = 0.0;
+            self.ms_count = 0;
+        }
+    }
+
     /// Push multiple stereo sample pairs (interleaved L/R)
     pub fn push_samples(&mut self, samples: &[f32]) {
         for chunk in samples.chunks(2) {
@@ -149,6 +209,20 @@ impl StereoAnalyzer {
         self.correlation
     }
 
+    /// Get smoothed (mid, side) RMS levels (linear, 0.0 - 1.0)
+    pub fn get_mid_side_levels(&self) -> (f32, f32) {
+        (self.mid_level, self.side_level)
+    }
+
+    /// Get raw (left, right) pairs for the Lissajous display, oldest to newest
+    pub fn get_lissajous(&self) -> [(f32, f32); LISSAJOUS_SIZE] {
+        let mut result = [(0.0f32, 0.0f32); LISSAJOUS_SIZE];
+        for (i, point) in result.iter_mut().enumerate() {
+            *point = self.lissajous[(self.lissajous_pos + i) % LISSAJOUS_SIZE];
+        }
+        result
+    }
+
     /// Reset the analyzer state
     pub fn reset(&mut self) {
         self.positions.fill((std::f32::consts::FRAC_PI_2, 0.0));
@@ -158,6 +232,25 @@ impl StereoAnalyzer {
         self.sum_r2 = 0.0;
         self.sample_count = 0;
         self.correlation = 1.0;
+        self.lissajous.fill((0.0, 0.0));
+        self.lissajous_pos = 0;
+        self.lissajous_skip = 0;
+        self.sum_m2 = 0.0;
+        self.sum_s2 = 0.0;
+        self.ms_count = 0;
+        self.mid_level = 0.0;
+        self.side_level = 0.0;
+    }
+}
+
+/// Side-to-mid balance from M/S levels: 0.0 = mono, 0.5 = equal, 1.0 = side only
+/// Silence reads as mono.
+pub fn mid_side_balance(mid: f32, side: f32) -> f32 {
+    let total = mid + side;
+    if total > 1e-6 && total.is_finite() {
+        (side / total).clamp(0.0, 1.0)
+    } else {
+        0.0
     }
 }
 
@@ -166,3 +259,41 @@ impl Default for StereoAnalyzer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mid_side_levels() {
+        // Identical channels: all mid, no side
+        let mut analyzer = StereoAnalyzer::new();
+        for _ in 0..4096 {
+            analyzer.push_sample(0.5, 0.5);
+        }
+        let (mid, side) = analyzer.get_mid_side_levels();
+        assert!(mid > 0.2 && side < 1e-6);
+        assert_eq!(mid_side_balance(mid, side), 0.0);
+
+        // Polarity-inverted channels: all side (collapses to silence in mono)
+        let mut analyzer = StereoAnalyzer::new();
+        for _ in 0..4096 {
+            analyzer.push_sample(0.5, -0.5);
+        }
+        let (mid, side) = analyzer.get_mid_side_levels();
+        assert!(side > 0.2 && mid < 1e-6);
+        assert_eq!(mid_side_balance(mid, side), 1.0);
+
+        assert_eq!(mid_side_balance(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_lissajous_order() {
+        let mut analyzer = StereoAnalyzer::new();
+        for i in 0..(LISSAJOUS_SIZE * LISSAJOUS_DECIMATION * 2) {
+            analyzer.push_sample(i as f32 * 1e-6, 0.0);
+        }
+        let points = analyzer.get_lissajous();
+        assert!(points.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+}
@@ -0,0 +1,165 @@
+//! DSP health watchdog
+//!
+//! Inspects the plugin's raw output (before the safety limiter) on the audio
+//! thread and counts problems: non-finite samples (NaN/Inf), discontinuities
+//! large enough to be audible clicks, and runaway levels. The safety limiter
+//! reports peaks it had to pull down hard as dangerous levels. Counters only
+//! ever increase, so callers compare snapshots to see what happened in between.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// Sample-to-sample jump treated as a click (half of full scale peak-to-peak)
+const CLICK_THRESHOLD: f32 = 1.0;
+
+/// Absolute level treated as runaway output (+12 dBFS)
+const OVERLOAD_THRESHOLD: f32 = 4.0;
+
+/// Counts of problems seen so far
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct DspHealthReport {
+    pub non_finite: u64,
+    pub clicks: u64,
+    pub overloads: u64,
+    /// Buffers where the safety limiter caught a dangerous level
+    #[serde(default)]
+    pub dangerous_levels: u64,
+}
+
+impl DspHealthReport {
+    /// Problems that occurred between an earlier snapshot and this one
+    pub fn since(&self, earlier: &DspHealthReport) -> DspHealthReport {
+        DspHealthReport {
+            non_finite: self.non_finite.saturating_sub(earlier.non_finite),
+            clicks: self.clicks.saturating_sub(earlier.clicks),
+            overloads: self.overloads.saturating_sub(earlier.overloads),
+            dangerous_levels: self.dangerous_levels.saturating_sub(earlier.dangerous_levels),
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.non_finite == 0 && self.clicks == 0 && self.overloads == 0 && self.dangerous_levels == 0
+    }
+}
+
+/// Watchdog state shared with the audio thread
+pub struct DspHealth {
+    /// Number of buffers containing NaN/Inf
+    non_finite: AtomicU64,
+    clicks: AtomicU64,
+    /// Number of buffers exceeding the overload level
+    overloads: AtomicU64,
+    /// Number of buffers where the safety limiter caught a dangerous level
+    dangerous_levels: AtomicU64,
+    /// Last sample of the previous buffer per channel (f32 bits) for click detection
+    last_left: AtomicU32,
+    last_right: AtomicU32,
+}
+
+impl DspHealth {
+    pub fn new() -> Self {
+        Self {
+            non_finite: AtomicU64::new(0),
+            clicks: AtomicU64::new(0),
+            overloads: AtomicU64::new(0),
+            dangerous_levels: AtomicU64::new(0),
+            last_left: AtomicU32::new(0),
+            last_right: AtomicU32::new(0),
+        }
+    }
+
+    /// Check one interleaved output buffer (audio thread)
+    pub fn check(&self, data: &[f32], channels: usize) {
+        if channels == 0 {
+            return;
+        }
+
+        let mut prev = [
+            f32::from_bits(self.last_left.load(Ordering::Relaxed)),
+            f32::from_bits(self.last_right.load(Ordering::Relaxed)),
+        ];
+        let mut non_finite = false;
+        let mut overload = false;
+        let mut clicks = 0u64;
+
+        for frame in data.chunks(channels) {
+            for (ch, &sample) in frame.iter().take(2).enumerate() {
+                if !sample.is_finite() {
+                    non_finite = true;
+                    prev[ch] = 0.0;
+                    continue;
+                }
+                if sample.abs() > OVERLOAD_THRESHOLD {
+                    overload = true;
+                }
+                if (sample - prev[ch]).abs() > CLICK_THRESHOLD {
+                    clicks += 1;
+                }
+                prev[ch] = sample;
+            }
+        }
+
+        self.last_left.store(prev[0].to_bits(), Ordering::Relaxed);
+        self.last_right.store(prev[1].to_bits(), Ordering::Relaxed);
+        if non_finite {
+            self.non_finite.fetch_add(1, Ordering::Relaxed);
+        }
+        if overload {
+            self.overloads.fetch_add(1, Ordering::Relaxed);
+        }
+        if clicks > 0 {
+            self.clicks.fetch_add(clicks, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a buffer where the safety limiter caught a dangerous level (audio thread)
+    pub fn record_dangerous_level(&self) {
+        self.dangerous_levels.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> DspHealthReport {
+        DspHealthReport {
+            non_finite: self.non_finite.load(Ordering::Relaxed),
+            clicks: self.clicks.load(Ordering::Relaxed),
+            overloads: self.overloads.load(Ordering::Relaxed),
+            dangerous_levels: self.dangerous_levels.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for DspHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_nan_clicks_and_overloads() {
+        let health = DspHealth::new();
+        let start = health.snapshot();
+
+        // Clean stereo sine-ish buffer
+        let clean: Vec<f32> = (0..64).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+        health.check(&clean, 2);
+        assert!(health.snapshot().since(&start).is_clean());
+
+        health.check(&[0.0, 0.0, f32::NAN, 0.0], 2);
+        health.check(&[0.0, 0.0, 0.9, -0.9, -0.9, 0.9], 2);
+        health.check(&[5.0, 0.0], 2);
+
+        let report = health.snapshot().since(&start);
+        assert_eq!(report.non_finite, 1);
+        assert!(report.clicks >= 2);
+        assert_eq!(report.overloads, 1);
+        assert_eq!(report.dangerous_levels, 0);
+
+        health.record_dangerous_level();
+        let report = health.snapshot().since(&start);
+        assert_eq!(report.dangerous_levels, 1);
+        assert!(!report.is_clean());
+    }
+}
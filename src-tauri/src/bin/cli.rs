@@ -0,0 +1,12 @@
+//! freqlab-cli
+//!
+//! Headless entry point for scripting project builds and validation.
+//! Run `freqlab-cli help` for the commands; see `freqlab_lib::cli`.
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
+        .format_timestamp_millis()
+        .init();
+
+    std::process::exit(freqlab_lib::cli::run(std::env::args().collect()));
+}
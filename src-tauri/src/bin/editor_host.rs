@@ -27,6 +27,7 @@ use std::time::Duration;
 
 // Import the library crate
 use freqlab_lib::audio::plugin::clap_host::{take_callback_request, PluginInstance};
+use freqlab_lib::audio::plugin::editor::EditorWindowState;
 
 /// Interval for state sync (in event loop iterations, ~16ms each)
 /// 3 iterations = ~50ms between state syncs
@@ -106,7 +107,10 @@ fn run_editor(plugin_path: &str, position: Option<(f64, f64)>) -> Result<(), Str
 
     // Open the editor window directly (in-process, not spawning another process)
     log::info!("Opening editor window...");
-    plugin.open_editor_window_at(position)?;
+    plugin.open_editor_window_at(&EditorWindowState {
+        position,
+        ..EditorWindowState::default()
+    })?;
 
     // Signal to parent that we're ready
     // Explicit flush ensures the message is sent immediately over the pipe
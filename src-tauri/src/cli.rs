@@ -0,0 +1,444 @@
+//! Headless command line interface (the `freqlab-cli` binary)
+//!
+//! Runs the same project operations as the app - create, build, validate,
+//! package - against the workspace at ~/VSTWorkshop, so builds and the offline
+//! validation suite (torture, denormal, state round-trip, block-size, smoothing,
+//! plus a distortion measurement) can be scripted or run in CI. `build --reload`
+//! hands the build to the running app instead, which reloads its preview. `--json` prints results as
+//! JSON; the exit code is 0 on success, 1 on failure, and 2 for usage errors.
+
+use serde::Serialize;
+
+use crate::commands::automation_api::{send_automation_request, AutomationAction, AutomationRequest};
+use crate::commands::block_size_audit::{block_size_audit_bundle, run_block_size_audit};
+use crate::commands::build::{find_clap_in, get_sanitized_output_path, run_build, BuildStreamEvent};
+use crate::commands::chat::resolve_project_version;
+use crate::commands::denormal_test::{denormal_test_bundle, run_denormal_test};
+use crate::commands::distortion::{measure_distortion_bundle, resolve_plugin_bundle};
+use crate::commands::projects::{list_projects, run_create_project, CreateProjectInput, ProjectCreateEvent};
+use crate::commands::publish::package_plugins;
+use crate::commands::smoothing_audit::{smoothing_audit_all_bundle, sync_smoothing_issues};
+use crate::commands::state_roundtrip::{run_state_roundtrip_test, state_roundtrip_bundle, DEFAULT_ITERATIONS};
+use crate::commands::torture_test::{sync_torture_issues, torture_test_bundle};
+
+const USAGE: &str = "\
+Usage: freqlab-cli <command> [options]
+
+Commands:
+  list                              List projects in the workspace
+  create <name> [--template effect|instrument] [--ui webview|egui|native]
+                [--description <text>]
+                                    Create a project
+  build <name> [--version <n>] [--reload]
+                                    Build and bundle a project
+                                    (--reload: have the running app build it and
+                                    reload the preview - needs the automation API)
+  validate <name> [--version <n>] [--sanitized]
+                                    Run the offline validation suite on a build
+                                    (every validator, smoothing on each continuous
+                                    parameter, and a 1 kHz distortion reading)
+                                    (--sanitized: the AddressSanitizer build)
+  package <name> [--version <n>] [--out <dir>]
+                                    Zip a build's plugins for distribution
+
+Options:
+  --json                            Print results as JSON
+  --version <n>                     Plugin version (defaults to the current one)";
+
+#[derive(Debug, PartialEq)]
+enum CliCommand {
+    List,
+    Create {
+        name: String,
+        template: String,
+        ui_framework: String,
+        description: String,
+    },
+    Build {
+        name: String,
+        version: Option<u32>,
+        reload: bool,
+    },
+    Validate {
+        name: String,
+        version: Option<u32>,
+        sanitized: bool,
+    },
+    Package {
+        name: String,
+        version: Option<u32>,
+        out: Option<String>,
+    },
+    /// Internal: run a plugin for the app's process-isolated preview
+    HostPlugin { bundle: String, shm: String },
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+struct CliArgs {
+    command: CliCommand,
+    json: bool,
+}
+
+/// Parse arguments (without the program name)
+fn parse_args(args: &[String]) -> Result<CliArgs, String> {
+    let mut positional = Vec::new();
+    let mut options = std::collections::HashMap::new();
+    let mut json = false;
+    let mut sanitized = false;
+    let mut reload = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--sanitized" => sanitized = true,
+            "--reload" => reload = true,
+            "-h" | "--help" => {
+                return Ok(CliArgs {
+                    command: CliCommand::Help,
+                    json,
+                })
+            }
+            flag if flag.starts_with("--") => {
+                let value = iter.next().ok_or_else(|| format!("{} needs a value", flag))?;
+                options.insert(flag.trim_start_matches("--").to_string(), value.clone());
+            }
+            _ => positional.push(arg.clone()),
+        }
+    }
+
+    let option = |key: &str| options.get(key).cloned();
+    let version = match option("version") {
+        Some(v) => Some(v.parse::<u32>().map_err(|_| format!("Invalid version: {}", v))?),
+        None => None,
+    };
+    let name = || {
+        positional
+            .get(1)
+            .cloned()
+            .ok_or_else(|| format!("{} needs a project name", positional[0]))
+    };
+
+    let command = match positional.first().map(String::as_str) {
+        None | Some("help") => CliCommand::Help,
+        Some("list") => CliCommand::List,
+        Some("create") => CliCommand::Create {
+            name: name()?,
+            template: option("template").unwrap_or_else(|| "effect".to_string()),
+            ui_framework: option("ui").unwrap_or_else(|| "webview".to_string()),
+            description: option("description").unwrap_or_default(),
+        },
+        Some("build") => CliCommand::Build {
+            name: name()?,
+            version,
+            reload,
+        },
+        Some("validate") => CliCommand::Validate {
+            name: name()?,
+            version,
+            sanitized,
+        },
+        Some("package") => CliCommand::Package {
+            name: name()?,
+            version,
+            out: option("out"),
+        },
+        Some("host-plugin") => match (positional.get(1), positional.get(2)) {
+            (Some(bundle), Some(shm)) => CliCommand::HostPlugin {
+                bundle: bundle.clone(),
+                shm: shm.clone(),
+            },
+            _ => return Err("host-plugin needs a bundle and a shared buffer path".to_string()),
+        },
+        Some(other) => return Err(format!("Unknown command: {}", other)),
+    };
+    Ok(CliArgs { command, json })
+}
+
+fn print_result<T: Serialize>(json: bool, value: &T, text: impl FnOnce(&T) -> String) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+    } else {
+        println!("{}", text(value));
+    }
+}
+
+#[derive(Serialize)]
+struct ValidationSummary {
+    passed: bool,
+    torture: crate::commands::torture_test::TortureReport,
+    denormal: crate::commands::denormal_test::DenormalReport,
+    state_roundtrip: crate::commands::state_roundtrip::RoundTripReport,
+    block_size: crate::commands::block_size_audit::BlockSizeAuditReport,
+    smoothing: Vec<crate::commands::smoothing_audit::SmoothingAuditReport>,
+    /// Measurement only - it doesn't affect `passed`
+    distortion: crate::commands::distortion::DistortionReport,
+}
+
+async fn execute(command: CliCommand, json: bool) -> Result<bool, String> {
+    match command {
+        CliCommand::Help => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        CliCommand::List => {
+            let projects = list_projects().await?;
+            print_result(json, &projects, |projects| {
+                projects
+                    .iter()
+                    .map(|p| format!("{}\t{}", p.name, p.description))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
+            Ok(true)
+        }
+        CliCommand::Create {
+            name,
+            template,
+            ui_framework,
+            description,
+        } => {
+            let project = run_create_project(CreateProjectInput {
+                name,
+                display_name: None,
+                description,
+                template,
+                ui_framework,
+                vendor_name: None,
+                vendor_url: None,
+                vendor_email: None,
+                components: None,
+            }, |event| {
+                // Progress goes to stderr so stdout stays clean for --json
+                if let ProjectCreateEvent::Phase { message, .. } = event {
+                    eprintln!("{}...", message);
+                }
+            })
+            .await?;
+            print_result(json, &project, |p| format!("Created {} at {}", p.name, p.path));
+            Ok(true)
+        }
+        CliCommand::Build {
+            name,
+            version,
+            reload: true,
+        } => {
+            eprintln!("Asking freqlab to build and reload {}...", name);
+            let result = send_automation_request(&AutomationRequest {
+                action: AutomationAction::BuildAndReload,
+                project: name.clone(),
+                version,
+            })?;
+            print_result(json, &result, |r| {
+                format!("Built and reloaded {} v{}", name, r["version"].as_u64().unwrap_or_default())
+            });
+            Ok(true)
+        }
+        CliCommand::Build { name, version, .. } => {
+            let version = resolve_project_version(&name, version).await?;
+            let result = run_build(name.clone(), version, |event| {
+                // Stream cargo's output to stderr so stdout stays clean for --json
+                if let BuildStreamEvent::Output { line } | BuildStreamEvent::Error { message: line } = event {
+                    eprintln!("{}", line);
+                }
+            })
+            .await?;
+            print_result(json, &result, |r| match &r.output_path {
+                Some(path) if r.success => format!("Built {} v{} -> {}", name, version, path),
+                _ => format!("Build of {} v{} failed", name, version),
+            });
+            Ok(result.success)
+        }
+        CliCommand::Validate {
+            name,
+            version,
+            sanitized,
+        } => {
+            let version = resolve_project_version(&name, version).await?;
+            let bundle = if sanitized {
+                // Run in a process with the ASan runtime preloaded (see commands::sanitizer)
+                find_clap_in(&get_sanitized_output_path(&name))
+                    .ok_or_else(|| format!("No sanitized build found for {}", name))?
+            } else {
+                resolve_plugin_bundle(Some(name.clone()), Some(version))?
+            };
+            let (denormal, state_roundtrip, block_size) = if sanitized {
+                (
+                    denormal_test_bundle(bundle.clone()).await?,
+                    state_roundtrip_bundle(bundle.clone(), DEFAULT_ITERATIONS).await?,
+                    block_size_audit_bundle(bundle.clone()).await?,
+                )
+            } else {
+                (
+                    run_denormal_test(Some(name.clone()), Some(version)).await?,
+                    run_state_roundtrip_test(Some(name.clone()), Some(version), None).await?,
+                    run_block_size_audit(Some(name.clone()), Some(version)).await?,
+                )
+            };
+            let torture = torture_test_bundle(bundle.clone(), None).await?;
+            let smoothing = smoothing_audit_all_bundle(bundle.clone()).await?;
+            let distortion = measure_distortion_bundle(bundle, None, None, None).await?;
+            if !sanitized {
+                sync_torture_issues(&name, &torture);
+                for report in &smoothing {
+                    sync_smoothing_issues(&name, report);
+                }
+            }
+
+            let summary = ValidationSummary {
+                passed: torture.passed
+                    && !denormal.crashed
+                    && !denormal.denormals_detected
+                    && state_roundtrip.passed
+                    && block_size.passed
+                    && smoothing.iter().all(|r| r.passed),
+                torture,
+                denormal,
+                state_roundtrip,
+                block_size,
+                smoothing,
+                distortion,
+            };
+            print_result(json, &summary, |s| {
+                let status = |ok: bool| if ok { "pass" } else { "FAIL" };
+                let zipper: Vec<&str> = s.smoothing.iter().filter(|r| !r.passed).map(|r| r.param_name.as_str()).collect();
+                format!(
+                    "{} v{}\n  torture:         {} ({} failure(s), {} warning(s))\n  denormals:       {}\n  \
+                     state roundtrip: {} ({} failure(s))\n  block sizes:     {}{}\n  smoothing:       {} ({} parameter(s){})\n  \
+                     distortion:      THD+N {:.1} / {:.1} dB at {:.0} Hz",
+                    name,
+                    version,
+                    status(s.torture.passed),
+                    s.torture.failures,
+                    s.torture.warnings,
+                    status(!s.denormal.crashed && !s.denormal.denormals_detected),
+                    status(s.state_roundtrip.passed),
+                    s.state_roundtrip.failures,
+                    status(s.block_size.passed),
+                    if s.block_size.deterministic { "" } else { " (inconclusive: output isn't deterministic)" },
+                    status(zipper.is_empty()),
+                    s.smoothing.len(),
+                    if zipper.is_empty() { String::new() } else { format!(", zipper noise: {}", zipper.join(", ")) },
+                    s.distortion.left.thd_n_db,
+                    s.distortion.right.thd_n_db,
+                    s.distortion.frequency,
+                )
+            });
+            Ok(summary.passed)
+        }
+        CliCommand::Package { name, version, out } => {
+            let version = resolve_project_version(&name, version).await?;
+            let destination = match out {
+                Some(dir) => dir,
+                None => std::env::current_dir()
+                    .map_err(|e| format!("Failed to get current directory: {}", e))?
+                    .to_string_lossy()
+                    .to_string(),
+            };
+            let result = package_plugins(name, version, destination, None).await?;
+            print_result(json, &result, |r| format!("Packaged {}", r.zip_path));
+            Ok(result.success)
+        }
+        CliCommand::HostPlugin { bundle, shm } => {
+            crate::audio::plugin::isolated::run_helper(std::path::Path::new(&bundle), std::path::Path::new(&shm))?;
+            Ok(true)
+        }
+    }
+}
+
+/// The CLI binary: next to the app's executable when built or bundled together,
+/// otherwise whatever `freqlab-cli` is on PATH
+pub(crate) fn cli_binary() -> std::path::PathBuf {
+    let name = if cfg!(windows) { "freqlab-cli.exe" } else { "freqlab-cli" };
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(name)))
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| std::path::PathBuf::from(name))
+}
+
+/// Run the CLI with the process arguments, returning the exit code
+pub fn run(args: Vec<String>) -> i32 {
+    let args = match parse_args(args.get(1..).unwrap_or_default()) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: failed to start runtime: {}", e);
+            return 1;
+        }
+    };
+    match runtime.block_on(execute(args.command, args.json)) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&args("build warm-drive --version 3 --json")),
+            Ok(CliArgs {
+                command: CliCommand::Build {
+                    name: "warm-drive".to_string(),
+                    version: Some(3),
+                    reload: false,
+                },
+                json: true,
+            })
+        );
+        assert_eq!(parse_args(&[]).map(|a| a.command), Ok(CliCommand::Help));
+        assert!(parse_args(&args("build")).is_err());
+        assert!(parse_args(&args("build x --version three")).is_err());
+        assert!(parse_args(&args("deploy x")).is_err());
+        assert_eq!(
+            parse_args(&args("build warm-drive --reload")).map(|a| a.command),
+            Ok(CliCommand::Build {
+                name: "warm-drive".to_string(),
+                version: None,
+                reload: true,
+            })
+        );
+        assert_eq!(
+            parse_args(&args("validate warm-drive --sanitized")).map(|a| a.command),
+            Ok(CliCommand::Validate {
+                name: "warm-drive".to_string(),
+                version: None,
+                sanitized: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_create_defaults() {
+        let parsed = parse_args(&args("create synth --template instrument")).unwrap();
+        assert_eq!(
+            parsed.command,
+            CliCommand::Create {
+                name: "synth".to_string(),
+                template: "instrument".to_string(),
+                ui_framework: "webview".to_string(),
+                description: String::new(),
+            }
+        );
+    }
+}
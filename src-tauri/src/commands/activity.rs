@@ -0,0 +1,150 @@
+//! Per-project activity timeline
+//!
+//! Builds, publishes, packages, chat sessions, and preview sessions append an
+//! entry to `.vstworkshop/activity.jsonl` so users can see what happened to a
+//! plugin and when. Recording is best-effort: a failed write is logged and never
+//! fails the command that triggered it.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::projects::get_workspace_path;
+
+/// Entries kept per project - older ones are dropped when the file is compacted
+const MAX_ACTIVITY_ENTRIES: usize = 1000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    Build,
+    Publish,
+    Package,
+    Chat,
+    Preview,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ActivityEntry {
+    pub timestamp: String,
+    pub kind: ActivityKind,
+    /// One-line description for the timeline (e.g. "Built v3")
+    pub summary: String,
+    #[serde(default)]
+    pub success: bool,
+    /// Kind-specific extras (version, targets, commit hash, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+fn get_activity_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("activity.jsonl")
+}
+
+/// Project folder for a project name
+pub(crate) fn project_dir(project_name: &str) -> PathBuf {
+    get_workspace_path().join("projects").join(project_name)
+}
+
+fn read_entries(project_path: &Path) -> Vec<ActivityEntry> {
+    fs::read_to_string(get_activity_path(project_path))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Most recent entry of a kind (e.g. the last build)
+pub(crate) fn latest_activity(project_path: &Path, kind: ActivityKind) -> Option<ActivityEntry> {
+    read_entries(project_path).into_iter().rev().find(|e| e.kind == kind)
+}
+
+/// Most recent entry of any kind
+pub(crate) fn last_activity(project_path: &Path) -> Option<ActivityEntry> {
+    read_entries(project_path).pop()
+}
+
+/// Append an entry to a project's activity log
+pub(crate) fn record_activity(
+    project_path: &Path,
+    kind: ActivityKind,
+    summary: impl Into<String>,
+    success: bool,
+    details: Option<serde_json::Value>,
+) {
+    if !project_path.join(".vstworkshop").exists() {
+        return;
+    }
+
+    let entry = ActivityEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        kind,
+        summary: summary.into(),
+        success,
+        details,
+    };
+    let path = get_activity_path(project_path);
+    let result = serde_json::to_string(&entry)
+        .map_err(|e| e.to_string())
+        .and_then(|line| {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| e.to_string())?;
+            writeln!(file, "{}", line).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        log::warn!("Failed to record activity in {:?}: {}", path, e);
+        return;
+    }
+
+    // Compact once the log is well past the limit so appends stay cheap
+    let entries = read_entries(project_path);
+    if entries.len() > MAX_ACTIVITY_ENTRIES + MAX_ACTIVITY_ENTRIES / 10 {
+        let kept = &entries[entries.len() - MAX_ACTIVITY_ENTRIES..];
+        let content: String = kept
+            .iter()
+            .filter_map(|e| serde_json::to_string(e).ok())
+            .map(|line| line + "\n")
+            .collect();
+        if let Err(e) = fs::write(&path, content) {
+            log::warn!("Failed to compact activity log {:?}: {}", path, e);
+        }
+    }
+}
+
+/// Get a project's activity, newest first
+/// `kinds` limits the timeline to certain activity types.
+#[tauri::command]
+pub async fn get_project_activity(
+    project_name: String,
+    limit: Option<usize>,
+    kinds: Option<Vec<ActivityKind>>,
+) -> Result<Vec<ActivityEntry>, String> {
+    let project_path = project_dir(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+
+    Ok(read_entries(&project_path)
+        .into_iter()
+        .rev()
+        .filter(|e| kinds.as_ref().map_or(true, |k| k.contains(&e.kind)))
+        .take(limit.unwrap_or(MAX_ACTIVITY_ENTRIES))
+        .collect())
+}
+
+/// Clear a project's activity log
+#[tauri::command]
+pub async fn clear_project_activity(project_name: String) -> Result<(), String> {
+    let path = get_activity_path(&project_dir(&project_name));
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to clear activity: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! Offline analysis jobs
+//!
+//! Offline renders, version comparisons, spectrogram exports, and the offline
+//! validators run on a small rayon pool rather than the command thread, so
+//! their analysis can use several cores. The pool stays one core short of the
+//! machine so the realtime audio thread always has one to itself. Each job
+//! emits "analysis-job-started" (with its id, for cancelling) and
+//! "analysis-job-progress" events and can be cancelled between steps.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::Emitter;
+use tokio::sync::oneshot;
+
+/// Returned by jobs that stopped because they were cancelled (same as a cancelled render)
+pub(crate) const CANCELLED: &str = crate::audio::offline::RENDER_CANCELLED;
+
+/// Upper bound on pool threads - analysis is memory-bound beyond this
+const MAX_POOL_THREADS: usize = 8;
+
+static POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(pool_threads(cores))
+        .thread_name(|i| format!("analysis-{}", i))
+        // The job's result channel is dropped, so the command reports the failure
+        .panic_handler(|_| log::error!("Analysis job panicked"))
+        .build()
+        .expect("Failed to create analysis thread pool")
+});
+
+static JOBS: Lazy<Mutex<HashMap<u64, RunningJob>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AnalysisJobInfo {
+    pub id: u64,
+    /// e.g. "compare_versions", "export_spectrogram"
+    pub kind: String,
+    pub started_at: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AnalysisJobProgress {
+    pub job_id: u64,
+    pub kind: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+struct RunningJob {
+    info: AnalysisJobInfo,
+    cancelled: Arc<AtomicBool>,
+}
+
+/// Leave one core for the audio thread (and the UI)
+fn pool_threads(cores: usize) -> usize {
+    cores.saturating_sub(1).clamp(1, MAX_POOL_THREADS)
+}
+
+/// Handle a running job uses to report progress and notice cancellation
+pub struct AnalysisJob {
+    id: u64,
+    kind: &'static str,
+    cancelled: Arc<AtomicBool>,
+    /// Last percentage emitted, so per-block progress doesn't flood the UI
+    last_percent: AtomicU32,
+    app_handle: Option<tauri::AppHandle>,
+}
+
+impl AnalysisJob {
+    fn new(kind: &'static str, app_handle: Option<tauri::AppHandle>) -> Self {
+        Self {
+            id: NEXT_JOB_ID.fetch_add(1, Ordering::SeqCst),
+            kind,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            last_percent: AtomicU32::new(u32::MAX),
+            app_handle,
+        }
+    }
+
+    /// Report progress; only whole-percent changes are emitted
+    pub fn progress(&self, done: usize, total: usize) {
+        let percent = (done.min(total) * 100 / total.max(1)) as u32;
+        if self.last_percent.swap(percent, Ordering::Relaxed) == percent {
+            return;
+        }
+        if let Some(app_handle) = &self.app_handle {
+            let _ = app_handle.emit(
+                "analysis-job-progress",
+                AnalysisJobProgress {
+                    job_id: self.id,
+                    kind: self.kind.to_string(),
+                    done,
+                    total,
+                },
+            );
+        }
+    }
+
+    /// `on_block` callback for an offline render that is step `step` of `steps`:
+    /// reports its progress and cancels it along with the job
+    pub fn render_progress(&self, step: usize, steps: usize) -> impl FnMut(usize, usize) -> bool + '_ {
+        move |done, total| {
+            self.progress(step * total + done, steps * total);
+            !self.is_cancelled()
+        }
+    }
+
+    /// The analysis pool, for parallel work that must stay off rayon's global pool
+    pub fn pool(&self) -> &'static rayon::ThreadPool {
+        &POOL
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Err(CANCELLED) once the job has been cancelled, for use with `?` between steps
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Run analysis work on the job pool and wait for its result
+/// Rayon calls inside `work` (join, par_iter) use the same pool.
+pub(crate) async fn run_analysis_job<T, F>(
+    kind: &'static str,
+    app_handle: Option<tauri::AppHandle>,
+    work: F,
+) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&AnalysisJob) -> Result<T, String> + Send + 'static,
+{
+    let job = AnalysisJob::new(kind, app_handle);
+    let id = job.id;
+    let info = AnalysisJobInfo {
+        id,
+        kind: kind.to_string(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+    };
+    if let Some(app_handle) = &job.app_handle {
+        // The command only returns when the job is done; this is how callers learn the id to cancel it
+        let _ = app_handle.emit("analysis-job-started", &info);
+    }
+    JOBS.lock().insert(
+        id,
+        RunningJob {
+            info,
+            cancelled: job.cancelled.clone(),
+        },
+    );
+
+    let (sender, receiver) = oneshot::channel();
+    POOL.spawn(move || {
+        let result = work(&job);
+        if matches!(&result, Err(e) if e == CANCELLED) {
+            log::info!("Analysis job {} ({}) cancelled", job.id, job.kind);
+        }
+        let _ = sender.send(result);
+    });
+
+    let result = receiver.await.map_err(|_| format!("Analysis job {} failed", kind));
+    JOBS.lock().remove(&id);
+    result?
+}
+
+/// Analysis jobs currently running, oldest first
+#[tauri::command]
+pub async fn list_analysis_jobs() -> Vec<AnalysisJobInfo> {
+    let mut jobs: Vec<AnalysisJobInfo> = JOBS.lock().values().map(|j| j.info.clone()).collect();
+    jobs.sort_by_key(|j| j.id);
+    jobs
+}
+
+/// Cancel a running analysis job; it stops at its next checkpoint
+#[tauri::command]
+pub async fn cancel_analysis_job(job_id: u64) -> Result<(), String> {
+    let jobs = JOBS.lock();
+    let job = jobs
+        .get(&job_id)
+        .ok_or_else(|| format!("Analysis job {} is not running", job_id))?;
+    job.cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_threads() {
+        assert_eq!(pool_threads(1), 1);
+        assert_eq!(pool_threads(4), 3);
+        assert_eq!(pool_threads(64), MAX_POOL_THREADS);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let job = AnalysisJob::new("test", None);
+        assert!(job.check_cancelled().is_ok());
+        job.progress(5, 10);
+        job.cancelled.store(true, Ordering::SeqCst);
+        assert_eq!(job.check_cancelled(), Err(CANCELLED.to_string()));
+    }
+}
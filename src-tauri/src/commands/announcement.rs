@@ -0,0 +1,268 @@
+//! Version announcements
+//!
+//! Formats a short release post for a built version - what's new, where to get
+//! it, and what it runs on - as Markdown (Discord, GitHub, most forums) and as
+//! an HTML fragment (for forums and sites that take HTML). "What's new" comes
+//! from the chat requests that produced each version since the previous release
+//! build, plus the parameter changes found by `param_docs`.
+
+use serde::Serialize;
+
+use super::activity::project_dir;
+use super::chat::{load_chat_history, resolve_project_version, ChatMessage};
+use super::param_docs::{get_parameter_changes, ParamDiff};
+use super::projects::{get_output_path, ProjectMeta};
+use super::publish::platform_folder;
+use super::share::html_escape;
+
+/// Longest a chat request can be before it's cut down for the list
+const MAX_HIGHLIGHT_CHARS: usize = 100;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Announcement {
+    pub markdown: String,
+    pub html: String,
+}
+
+/// Everything an announcement says, gathered from the project
+struct AnnouncementContent {
+    name: String,
+    version: u32,
+    description: String,
+    vendor: Option<String>,
+    highlights: Vec<String>,
+    /// Parameter lines, e.g. "New parameters: Drive, Mix"
+    parameter_notes: Vec<String>,
+    /// "VST3", "CLAP"
+    formats: Vec<&'static str>,
+    platform: String,
+    download_url: Option<String>,
+}
+
+/// First line of a chat request, shortened for a bullet list
+fn summarize_request(content: &str) -> String {
+    let line = content.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("");
+    if line.chars().count() <= MAX_HIGHLIGHT_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_HIGHLIGHT_CHARS).collect();
+    format!("{}...", cut.trim_end())
+}
+
+/// The requests behind the versions after `since` up to and including `version`
+/// Versions are recorded on the assistant reply; the request is the user message before it.
+fn version_highlights(messages: &[ChatMessage], since: u32, version: u32) -> Vec<String> {
+    let mut highlights = Vec::new();
+    let mut last_request: Option<&str> = None;
+    for message in messages {
+        if message.role == "user" {
+            last_request = Some(&message.content);
+            continue;
+        }
+        let Some(v) = message.version else { continue };
+        if v <= since || v > version {
+            continue;
+        }
+        if let Some(request) = last_request.take() {
+            let summary = summarize_request(request);
+            if !summary.is_empty() && !highlights.contains(&summary) {
+                highlights.push(summary);
+            }
+        }
+    }
+    highlights
+}
+
+fn parameter_notes(diff: &ParamDiff) -> Vec<String> {
+    let names = |names: Vec<&str>| names.join(", ");
+    let mut notes = Vec::new();
+    if !diff.added.is_empty() {
+        notes.push(format!("New parameters: {}", names(diff.added.iter().map(|p| p.name.as_str()).collect())));
+    }
+    if !diff.changed.is_empty() {
+        notes.push(format!("Updated parameters: {}", names(diff.changed.iter().map(|p| p.name.as_str()).collect())));
+    }
+    if !diff.removed.is_empty() {
+        notes.push(format!("Removed parameters: {}", names(diff.removed.iter().map(|p| p.name.as_str()).collect())));
+    }
+    notes
+}
+
+/// Human-readable CPU architecture the plugin was built for
+fn architecture() -> &'static str {
+    match std::env::consts::ARCH {
+        "aarch64" if cfg!(target_os = "macos") => "Apple Silicon",
+        "aarch64" => "ARM64",
+        "x86_64" => "64-bit Intel/AMD",
+        other => other,
+    }
+}
+
+fn requirements(content: &AnnouncementContent) -> Vec<String> {
+    vec![
+        format!("{} ({})", content.platform, architecture()),
+        format!("A DAW or host that loads {} plugins", content.formats.join(" or ")),
+    ]
+}
+
+fn render_markdown(content: &AnnouncementContent) -> String {
+    let mut md = format!("## {} v{}\n\n", content.name, content.version);
+    if !content.description.trim().is_empty() {
+        md.push_str(content.description.trim());
+        md.push_str("\n\n");
+    }
+
+    let whats_new: Vec<&String> = content.highlights.iter().chain(&content.parameter_notes).collect();
+    if !whats_new.is_empty() {
+        md.push_str("**What's new**\n");
+        for item in whats_new {
+            md.push_str(&format!("- {}\n", item));
+        }
+        md.push('\n');
+    }
+
+    if let Some(url) = &content.download_url {
+        md.push_str(&format!("**Download:** {}\n\n", url));
+    }
+
+    md.push_str(&format!("**Formats:** {}\n\n", content.formats.join(", ")));
+    md.push_str("**Requirements**\n");
+    for requirement in requirements(content) {
+        md.push_str(&format!("- {}\n", requirement));
+    }
+
+    if let Some(vendor) = &content.vendor {
+        md.push_str(&format!("\nMade by {}\n", vendor));
+    }
+    md
+}
+
+fn render_html(content: &AnnouncementContent) -> String {
+    let list = |items: Vec<&String>| -> String {
+        items.iter().map(|i| format!("<li>{}</li>", html_escape(i))).collect()
+    };
+
+    let mut html = format!("<h2>{} v{}</h2>\n", html_escape(&content.name), content.version);
+    if !content.description.trim().is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", html_escape(content.description.trim())));
+    }
+
+    let whats_new: Vec<&String> = content.highlights.iter().chain(&content.parameter_notes).collect();
+    if !whats_new.is_empty() {
+        html.push_str(&format!("<h3>What's new</h3>\n<ul>{}</ul>\n", list(whats_new)));
+    }
+
+    if let Some(url) = &content.download_url {
+        let url = html_escape(url);
+        html.push_str(&format!("<p><strong>Download:</strong> <a href=\"{}\">{}</a></p>\n", url, url));
+    }
+
+    html.push_str(&format!("<p><strong>Formats:</strong> {}</p>\n", content.formats.join(", ")));
+    let requirements = requirements(content);
+    html.push_str(&format!("<h3>Requirements</h3>\n<ul>{}</ul>\n", list(requirements.iter().collect())));
+
+    if let Some(vendor) = &content.vendor {
+        html.push_str(&format!("<p>Made by {}</p>\n", html_escape(vendor)));
+    }
+    html
+}
+
+/// Write up a built version for sharing, as Markdown and HTML
+/// `download_url`: where the package can be downloaded (left out when None).
+#[tauri::command]
+pub async fn generate_announcement(
+    project_name: String,
+    version: Option<u32>,
+    download_url: Option<String>,
+) -> Result<Announcement, String> {
+    let version = resolve_project_version(&project_name, version).await?;
+    let project_path = project_dir(&project_name);
+    let meta: ProjectMeta = std::fs::read_to_string(project_path.join(".vstworkshop/metadata.json"))
+        .map_err(|e| format!("Failed to read metadata: {}", e))
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| format!("Failed to parse metadata: {}", e)))?;
+
+    let output_path = get_output_path().join(&project_name).join(format!("v{}", version));
+    let snake_name = project_name.replace('-', "_");
+    let formats: Vec<&'static str> = [("vst3", "VST3"), ("clap", "CLAP")]
+        .into_iter()
+        .filter(|(ext, _)| output_path.join(format!("{}.{}", snake_name, ext)).exists())
+        .map(|(_, format)| format)
+        .collect();
+    if formats.is_empty() {
+        return Err(format!("v{} hasn't been built yet. Build it before announcing it.", version));
+    }
+
+    // Parameter snapshots are taken on every release build, so the diff's base
+    // version is also where the list of changes starts
+    let diff = get_parameter_changes(project_name.clone(), Some(version)).await.ok();
+    let since = diff.as_ref().and_then(|d| d.from_version).unwrap_or(0);
+    let chat = load_chat_history(project_path.to_string_lossy().to_string()).await?;
+
+    let content = AnnouncementContent {
+        name: meta.name,
+        version,
+        description: meta.description,
+        vendor: meta.identity.map(|i| i.vendor_name).filter(|v| !v.trim().is_empty()),
+        highlights: version_highlights(&chat.messages, since, version),
+        parameter_notes: diff.as_ref().map(parameter_notes).unwrap_or_default(),
+        formats,
+        platform: platform_folder().to_string(),
+        download_url: download_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()),
+    };
+
+    Ok(Announcement {
+        markdown: render_markdown(&content),
+        html: render_html(&content),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str, version: Option<u32>) -> ChatMessage {
+        ChatMessage {
+            id: String::new(),
+            role: role.to_string(),
+            content: content.to_string(),
+            timestamp: String::new(),
+            commit_hash: None,
+            version,
+            reverted: false,
+            attachments: None,
+        }
+    }
+
+    #[test]
+    fn test_version_highlights() {
+        let messages = vec![
+            message("user", "Make a gain plugin", None),
+            message("assistant", "Done", Some(1)),
+            message("user", "Add a drive knob\n\nwith soft clipping", None),
+            message("assistant", "Added", Some(2)),
+            message("user", "What does drive do?", None),
+            message("assistant", "It saturates", None),
+            message("user", "Add a <mix> control", None),
+            message("assistant", "Added", Some(3)),
+        ];
+        assert_eq!(version_highlights(&messages, 1, 3), vec!["Add a drive knob", "Add a <mix> control"]);
+        assert_eq!(summarize_request(&"x".repeat(120)), format!("{}...", "x".repeat(100)));
+
+        let content = AnnouncementContent {
+            name: "Gain".to_string(),
+            version: 3,
+            description: "A gain plugin".to_string(),
+            vendor: None,
+            highlights: version_highlights(&messages, 1, 3),
+            parameter_notes: vec!["New parameters: Drive, Mix".to_string()],
+            formats: vec!["VST3", "CLAP"],
+            platform: "macOS".to_string(),
+            download_url: Some("https://example.com/gain.zip".to_string()),
+        };
+        let md = render_markdown(&content);
+        assert!(md.starts_with("## Gain v3\n\nA gain plugin\n\n**What's new**\n- Add a drive knob\n"));
+        assert!(md.contains("- New parameters: Drive, Mix\n"));
+        assert!(md.contains("**Download:** https://example.com/gain.zip"));
+        assert!(render_html(&content).contains("<li>Add a &lt;mix&gt; control</li>"));
+    }
+}
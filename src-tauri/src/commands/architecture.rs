@@ -0,0 +1,140 @@
+//! CPU architecture checks
+//!
+//! On Apple Silicon the app can end up running under Rosetta, and rustup can
+//! end up with an x86_64 default toolchain (e.g. installed from a Rosetta
+//! terminal). Either way `cargo` quietly produces x86_64-only plugins. This
+//! reads the machine's real CPU, flags mismatches for `check_prerequisites`,
+//! and gives builds the `--target` that matches the CPU.
+
+use std::process::Command;
+
+use super::i18n::{tr, tr_with};
+
+/// Output of a `sysctl -n` query (macOS)
+fn sysctl(name: &str) -> Option<String> {
+    let output = Command::new("sysctl").args(["-n", name]).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The CPU's architecture ("aarch64", "x86_64"), even when this process is translated
+pub(crate) fn native_arch() -> &'static str {
+    if cfg!(target_os = "macos") && sysctl("hw.optional.arm64").as_deref() == Some("1") {
+        "aarch64"
+    } else {
+        std::env::consts::ARCH
+    }
+}
+
+/// Whether the app itself is running under Rosetta
+pub(crate) fn running_under_rosetta() -> bool {
+    cfg!(target_os = "macos") && sysctl("sysctl.proc_translated").as_deref() == Some("1")
+}
+
+/// Host triple of the default Rust toolchain (`rustc -vV`)
+pub(crate) fn rust_host_triple() -> Option<String> {
+    let output = Command::new("rustc")
+        .arg("-vV")
+        .env("PATH", super::get_extended_path())
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|l| l.strip_prefix("host: "))
+        .map(|t| t.trim().to_string())
+}
+
+fn triple_arch(triple: &str) -> &str {
+    triple.split('-').next().unwrap_or(triple)
+}
+
+/// Triple to build for when the toolchain's default doesn't match the CPU
+fn mismatched_target(native: &str, rust_host: &str) -> Option<String> {
+    let host_arch = triple_arch(rust_host);
+    if host_arch == native {
+        return None;
+    }
+    // Same OS and vendor, the CPU's architecture
+    rust_host
+        .split_once('-')
+        .map(|(_, rest)| format!("{}-{}", native, rest))
+}
+
+/// `--target` for plugin builds, or None when the default toolchain already matches the CPU
+pub(crate) fn build_target() -> Option<String> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    mismatched_target(native_arch(), &rust_host_triple()?)
+}
+
+/// Make sure rustup has the standard library for a target, adding it if not
+pub(crate) fn ensure_target_installed(target: &str) -> Result<(), String> {
+    let installed = Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .env("PATH", super::get_extended_path())
+        .output()
+        .map_err(|e| format!("Failed to run rustup: {}", e))?;
+    if String::from_utf8_lossy(&installed.stdout).lines().any(|l| l.trim() == target) {
+        return Ok(());
+    }
+    let output = Command::new("rustup")
+        .args(["target", "add", target])
+        .env("PATH", super::get_extended_path())
+        .output()
+        .map_err(|e| format!("Failed to run rustup: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to add Rust target {}: {}",
+            target,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+fn warnings_for(native: &str, rosetta: bool, rust_host: Option<&str>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if rosetta {
+        warnings.push(tr("arch.rosetta"));
+    }
+    if let Some(host) = rust_host {
+        if let Some(target) = mismatched_target(native, host) {
+            warnings.push(tr_with(
+                "arch.toolchain_mismatch",
+                &[("host", host), ("native", native), ("target", &target)],
+            ));
+        }
+    }
+    warnings
+}
+
+/// Problems with how the app and toolchain match the CPU (empty when all is well)
+pub(crate) fn architecture_warnings() -> Vec<String> {
+    if !cfg!(target_os = "macos") {
+        return Vec::new();
+    }
+    warnings_for(native_arch(), running_under_rosetta(), rust_host_triple().as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mismatched_toolchain() {
+        assert_eq!(
+            mismatched_target("aarch64", "x86_64-apple-darwin").as_deref(),
+            Some("aarch64-apple-darwin")
+        );
+        assert_eq!(mismatched_target("aarch64", "aarch64-apple-darwin"), None);
+
+        let warnings = warnings_for("aarch64", true, Some("x86_64-apple-darwin"));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1].contains("rustup default stable-aarch64-apple-darwin"));
+        assert!(warnings_for("aarch64", false, Some("aarch64-apple-darwin")).is_empty());
+    }
+}
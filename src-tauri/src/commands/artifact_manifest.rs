@@ -0,0 +1,166 @@
+//! Artifact manifests
+//!
+//! Release builds write `manifest.json` into `output/{project}/v{version}/`
+//! listing every file in the plugin bundles with its size and SHA-256, plus the
+//! git commit it was built from. Publishing and packaging check the bundles
+//! against it first, so a bundle that was modified, partly overwritten, or left
+//! over from another build is caught instead of shipped.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::Path;
+use walkdir::WalkDir;
+
+pub(crate) const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ManifestFile {
+    /// Relative to the output folder, with `/` separators
+    pub path: String,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ArtifactManifest {
+    pub project: String,
+    pub version: u32,
+    pub built_at: String,
+    pub git_commit: Option<String>,
+    pub files: Vec<ManifestFile>,
+}
+
+fn is_bundle(path: &Path) -> bool {
+    matches!(path.extension().and_then(|e| e.to_str()), Some("vst3" | "clap"))
+}
+
+pub(crate) fn sha256_of(path: &Path) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Every file in a bundle (or the bundle itself, if it's a single file), with
+/// paths relative to `root`
+pub(crate) fn bundle_files(root: &Path, bundle: &Path) -> Result<Vec<ManifestFile>, String> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(bundle).into_iter().flatten() {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        files.push(ManifestFile {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            bytes: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            sha256: sha256_of(path)?,
+        });
+    }
+    Ok(files)
+}
+
+/// Every file inside the plugin bundles of an output folder, sorted by path
+fn scan_bundles(output_path: &Path) -> Result<Vec<ManifestFile>, String> {
+    let mut files = Vec::new();
+    let bundles = fs::read_dir(output_path)
+        .map_err(|e| format!("Failed to read {}: {}", output_path.display(), e))?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| is_bundle(p));
+    for bundle in bundles {
+        files.extend(bundle_files(output_path, &bundle)?);
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Describe the differences between the manifest and the files on disk
+pub(crate) fn compare_files(expected: &[ManifestFile], actual: &[ManifestFile]) -> Vec<String> {
+    let actual: BTreeMap<&str, &ManifestFile> = actual.iter().map(|f| (f.path.as_str(), f)).collect();
+    let mut problems: Vec<String> = expected
+        .iter()
+        .filter_map(|f| match actual.get(f.path.as_str()) {
+            None => Some(format!("{} is missing", f.path)),
+            Some(a) if a.sha256 != f.sha256 => Some(format!("{} has changed", f.path)),
+            Some(_) => None,
+        })
+        .collect();
+    problems.extend(
+        actual
+            .keys()
+            .filter(|path| !expected.iter().any(|f| f.path == **path))
+            .map(|path| format!("{} isn't part of the build", path)),
+    );
+    problems
+}
+
+/// Write the manifest for a freshly built output folder
+pub(crate) fn write_manifest(
+    output_path: &Path,
+    project_name: &str,
+    version: u32,
+    git_commit: Option<String>,
+) -> Result<(), String> {
+    let manifest = ArtifactManifest {
+        project: project_name.to_string(),
+        version,
+        built_at: chrono::Utc::now().to_rfc3339(),
+        git_commit,
+        files: scan_bundles(output_path)?,
+    };
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    fs::write(output_path.join(MANIFEST_FILE), json).map_err(|e| format!("Failed to write {}: {}", MANIFEST_FILE, e))
+}
+
+/// Check an output folder's bundles against its manifest
+/// Builds from before manifests existed have none and pass unchecked.
+pub(crate) fn verify_manifest(output_path: &Path) -> Result<(), String> {
+    let Ok(content) = fs::read_to_string(output_path.join(MANIFEST_FILE)) else {
+        log::warn!("No {} in {:?} - skipping artifact verification", MANIFEST_FILE, output_path);
+        return Ok(());
+    };
+    let manifest: ArtifactManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", MANIFEST_FILE, e))?;
+    let problems = compare_files(&manifest.files, &scan_bundles(output_path)?);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The v{} build doesn't match its manifest ({}). Rebuild it before shipping.",
+            manifest.version,
+            problems.join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, sha256: &str) -> ManifestFile {
+        ManifestFile {
+            path: path.to_string(),
+            bytes: 1,
+            sha256: sha256.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_compare_files() {
+        let expected = vec![file("a.clap/Contents/MacOS/a", "11"), file("a.vst3/Contents/MacOS/a", "22")];
+        assert!(compare_files(&expected, &expected).is_empty());
+
+        let actual = vec![file("a.clap/Contents/MacOS/a", "99"), file("a.clap/extra.txt", "33")];
+        assert_eq!(
+            compare_files(&expected, &actual),
+            vec![
+                "a.clap/Contents/MacOS/a has changed",
+                "a.vst3/Contents/MacOS/a is missing",
+                "a.clap/extra.txt isn't part of the build",
+            ]
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! Classroom assignments
+//!
+//! An assignment is a project template plus a task checklist (the same checks
+//! as the tutorial) and, optionally, settings students shouldn't change. They're
+//! saved in `~/VSTWorkshop/assignments/{id}.json`. `create_assignment_projects`
+//! creates one project per student, and `export_assignment_status` writes a CSV
+//! of each student's progress (tasks complete, whether the latest build passed)
+//! for the teacher's gradebook.
+//!
+//! Locked settings are merged into the app settings when projects are created
+//! and kept in `assignments/locked-settings.json`; `apply_settings` refuses
+//! changes to them until the teacher calls `unlock_assignment_settings`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{Emitter, Manager};
+
+use super::activity::{latest_activity, ActivityKind};
+use super::prefetch::UI_FRAMEWORKS;
+use super::projects::{get_workspace_path, list_projects, run_create_project, set_project_tags, CreateProjectInput, ProjectMeta};
+use super::settings::{merge_json, update_settings, AppSettings};
+use super::tutorial::{complete_tasks, count_parameters, project_facts, TutorialTask};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Assignment {
+    /// Lowercase, also the prefix of the students' project names
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    /// "effect" or "instrument"
+    pub template: String,
+    pub ui_framework: String,
+    pub tasks: Vec<TutorialTask>,
+    /// Partial settings object (e.g. `{ "buildFormats": ["vst3"] }`) students can't change
+    #[serde(default)]
+    pub locked_settings: Map<String, Value>,
+}
+
+/// Per-project copy of the checklist, in `.vstworkshop/assignment.json`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentProgress {
+    pub assignment_id: String,
+    pub student: String,
+    pub initial_parameters: usize,
+    pub tasks: Vec<TutorialTask>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentError {
+    pub student: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentCreation {
+    pub created: Vec<ProjectMeta>,
+    /// Students whose project couldn't be created; the others still were
+    pub failed: Vec<StudentError>,
+    /// Why the locked settings couldn't be applied, if they couldn't
+    pub locks_error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentStatus {
+    pub student: String,
+    pub project_name: String,
+    pub tasks_completed: usize,
+    pub tasks_total: usize,
+    /// Whether the latest build succeeded
+    pub build_passing: bool,
+    pub last_build_at: Option<String>,
+}
+
+fn get_assignments_dir() -> PathBuf {
+    get_workspace_path().join("assignments")
+}
+
+fn get_assignment_path(id: &str) -> PathBuf {
+    get_assignments_dir().join(format!("{}.json", id))
+}
+
+fn get_locks_path() -> PathBuf {
+    get_assignments_dir().join("locked-settings.json")
+}
+
+fn get_progress_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("assignment.json")
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    fs::read_to_string(path).ok().and_then(|c| serde_json::from_str(&c).ok())
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T, what: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize {}: {}", what, e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to save {}: {}", what, e))
+}
+
+fn load_assignment(id: &str) -> Result<Assignment, String> {
+    read_json(&get_assignment_path(id)).ok_or_else(|| format!("Assignment '{}' not found", id))
+}
+
+/// Locked settings of every assignment, by assignment id
+fn load_locks() -> Map<String, Value> {
+    read_json(&get_locks_path()).unwrap_or_default()
+}
+
+/// Project name for a student's copy of an assignment, within the 50-character limit
+fn student_project_name(assignment_id: &str, student: &str) -> Result<String, String> {
+    let slug = student_slug(student);
+    let slug = slug[..slug.len().min(49 - assignment_id.len())].trim_end_matches('-');
+    if slug.is_empty() {
+        return Err(format!("Can't make a project name from '{}'", student));
+    }
+    Ok(format!("{}-{}", assignment_id, slug))
+}
+
+/// `name`, or `name-2`, `name-3`, ... (kept within 50 characters) when it's taken
+fn unique_project_name(name: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|t| t == name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!("-{}", n);
+            let base = name[..name.len().min(50 - suffix.len())].trim_end_matches('-');
+            format!("{}{}", base, suffix)
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded suffixes")
+}
+
+/// Project-name-safe version of a student's name ("Ana María" -> "ana-mar-a")
+fn student_slug(student: &str) -> String {
+    let mut slug = String::new();
+    for c in student.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Dotted paths of locked values that `settings` doesn't match
+fn locked_differences(settings: &Value, locked: &Map<String, Value>, prefix: &str) -> Vec<String> {
+    let mut differences = Vec::new();
+    for (key, value) in locked {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match (value, settings.get(key)) {
+            (Value::Object(nested), Some(actual)) => differences.extend(locked_differences(actual, nested, &path)),
+            (expected, actual) if actual != Some(expected) => differences.push(path),
+            _ => {}
+        }
+    }
+    differences
+}
+
+/// Reject settings that change anything an assignment has locked
+pub(crate) fn check_locked_settings(settings: &AppSettings) -> Result<(), String> {
+    let locks = load_locks();
+    if locks.is_empty() {
+        return Ok(());
+    }
+    let value = serde_json::to_value(settings).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    for (assignment, locked) in &locks {
+        let Value::Object(locked) = locked else { continue };
+        let differences = locked_differences(&value, locked, "");
+        if !differences.is_empty() {
+            return Err(format!(
+                "{} locked by the '{}' assignment",
+                differences.join(", "),
+                assignment
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn validate_assignment(assignment: &Assignment) -> Result<(), String> {
+    let id_ok = assignment.id.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+        && assignment.id.len() <= 30
+        && assignment.id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !id_ok {
+        return Err("Assignment id must start with a letter and use only lowercase letters, numbers, and hyphens (max 30)".to_string());
+    }
+    if !["effect", "instrument"].contains(&assignment.template.as_str()) {
+        return Err(format!("Unknown template: {}", assignment.template));
+    }
+    if !UI_FRAMEWORKS.contains(&assignment.ui_framework.as_str()) {
+        return Err(format!("Unknown UI framework: {}", assignment.ui_framework));
+    }
+    if assignment.tasks.is_empty() {
+        return Err("An assignment needs at least one task".to_string());
+    }
+    // Locked settings have to make valid settings
+    let mut value = serde_json::to_value(AppSettings::default()).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let Some(key) = assignment.locked_settings.keys().find(|key| value.get(key.as_str()).is_none()) {
+        return Err(format!("Unknown setting: {}", key));
+    }
+    merge_json(&mut value, Value::Object(assignment.locked_settings.clone()));
+    let settings: AppSettings = serde_json::from_value(value).map_err(|e| format!("Invalid locked settings: {}", e))?;
+    settings.validate()
+}
+
+/// Create or replace an assignment
+#[tauri::command]
+pub async fn save_assignment(assignment: Assignment) -> Result<Assignment, String> {
+    validate_assignment(&assignment)?;
+    let mut assignment = assignment;
+    for task in &mut assignment.tasks {
+        task.completed_at = None;
+    }
+    write_json(&get_assignment_path(&assignment.id), &assignment, "assignment")?;
+    Ok(assignment)
+}
+
+#[tauri::command]
+pub async fn list_assignments() -> Result<Vec<Assignment>, String> {
+    let mut assignments: Vec<Assignment> = fs::read_dir(get_assignments_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path() != get_locks_path())
+        .filter_map(|e| read_json(&e.path()))
+        .collect();
+    assignments.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(assignments)
+}
+
+/// Create a student's project and its progress file
+async fn create_student_project(
+    assignment: &Assignment,
+    student: &str,
+    name: String,
+    window: &tauri::Window,
+) -> Result<ProjectMeta, String> {
+    let input = CreateProjectInput {
+        name,
+        display_name: Some(format!("{} ({})", assignment.title, student)),
+        description: assignment.description.clone(),
+        template: assignment.template.clone(),
+        ui_framework: assignment.ui_framework.clone(),
+        vendor_name: None,
+        vendor_url: None,
+        vendor_email: None,
+        components: None,
+    };
+    let meta = run_create_project(input, |event| {
+        let _ = window.emit("project-create-stream", event);
+    })
+    .await?;
+    let meta = set_project_tags(meta.path.clone(), vec!["assignment".to_string(), assignment.id.clone()]).await?;
+
+    let project_path = PathBuf::from(&meta.path);
+    let progress = AssignmentProgress {
+        assignment_id: assignment.id.clone(),
+        student: student.to_string(),
+        initial_parameters: count_parameters(&project_path),
+        tasks: assignment.tasks.clone(),
+    };
+    write_json(&get_progress_path(&project_path), &progress, "assignment progress")?;
+    Ok(meta)
+}
+
+/// Merge the assignment's locked settings into the app settings and record the locks
+async fn apply_locks(assignment: &Assignment, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let mut locks = load_locks();
+    let previous = locks.insert(assignment.id.clone(), Value::Object(assignment.locked_settings.clone()));
+    write_json(&get_locks_path(), &locks, "locked settings")?;
+    let patch = Value::Object(assignment.locked_settings.clone());
+    if let Err(e) = update_settings(patch, app_handle).await {
+        // Another assignment's locks conflict; put the old locks back
+        match previous {
+            Some(old) => locks.insert(assignment.id.clone(), old),
+            None => locks.remove(&assignment.id),
+        };
+        write_json(&get_locks_path(), &locks, "locked settings")?;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Create a project per student ("{assignment}-{student}", or "-2", "-3", ...
+/// when two names give the same project name), streaming
+/// `project-create-stream` events, then apply the assignment's locked settings.
+/// Students who already have a project for the assignment are skipped; one
+/// student's failure is reported without stopping the rest.
+#[tauri::command]
+pub async fn create_assignment_projects(
+    assignment_id: String,
+    students: Vec<String>,
+    window: tauri::Window,
+) -> Result<AssignmentCreation, String> {
+    let assignment = load_assignment(&assignment_id)?;
+    let projects = list_projects().await?;
+    let enrolled: Vec<String> = projects
+        .iter()
+        .filter_map(|p| read_json::<AssignmentProgress>(&get_progress_path(Path::new(&p.path))))
+        .filter(|progress| progress.assignment_id == assignment.id)
+        .map(|progress| progress.student)
+        .collect();
+    let mut taken: Vec<String> = projects.into_iter().map(|p| p.name).collect();
+
+    let mut created = Vec::new();
+    let mut failed = Vec::new();
+    for student in students.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        if enrolled.iter().any(|e| e == student) {
+            continue;
+        }
+        let result = match student_project_name(&assignment.id, student) {
+            Ok(name) => {
+                let name = unique_project_name(&name, &taken);
+                // Claimed even if creation fails, so a half-created folder isn't reused
+                taken.push(name.clone());
+                create_student_project(&assignment, student, name, &window).await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(meta) => created.push(meta),
+            Err(error) => {
+                log::warn!("Failed to create {}'s project for {}: {}", student, assignment.id, error);
+                failed.push(StudentError {
+                    student: student.to_string(),
+                    error,
+                });
+            }
+        }
+    }
+
+    let locks_error = if assignment.locked_settings.is_empty() {
+        None
+    } else {
+        apply_locks(&assignment, window.app_handle().clone()).await.err()
+    };
+    Ok(AssignmentCreation {
+        created,
+        failed,
+        locks_error,
+    })
+}
+
+/// Let students change the settings an assignment locked
+#[tauri::command]
+pub async fn unlock_assignment_settings(assignment_id: String) -> Result<(), String> {
+    let mut locks = load_locks();
+    if locks.remove(&assignment_id).is_some() {
+        write_json(&get_locks_path(), &locks, "locked settings")?;
+    }
+    Ok(())
+}
+
+/// Check every student's project and write a CSV of their progress to `destination`
+#[tauri::command]
+pub async fn export_assignment_status(assignment_id: String, destination: String) -> Result<Vec<StudentStatus>, String> {
+    let mut rows = Vec::new();
+    let now = chrono::Utc::now().to_rfc3339();
+    for project in list_projects().await? {
+        let project_path = PathBuf::from(&project.path);
+        let Some(mut progress) = read_json::<AssignmentProgress>(&get_progress_path(&project_path)) else {
+            continue;
+        };
+        if progress.assignment_id != assignment_id {
+            continue;
+        }
+        let facts = project_facts(&project_path).await?;
+        if complete_tasks(&mut progress.tasks, &facts, progress.initial_parameters, &now) {
+            write_json(&get_progress_path(&project_path), &progress, "assignment progress")?;
+        }
+        rows.push(StudentStatus {
+            student: progress.student.clone(),
+            project_name: project.name.clone(),
+            tasks_completed: progress.tasks.iter().filter(|t| t.completed_at.is_some()).count(),
+            tasks_total: progress.tasks.len(),
+            build_passing: facts.built_at.is_some(),
+            last_build_at: latest_activity(&project_path, ActivityKind::Build).map(|e| e.timestamp),
+        });
+    }
+    rows.sort_by(|a, b| a.student.to_lowercase().cmp(&b.student.to_lowercase()));
+
+    fs::write(&destination, status_csv(&rows)).map_err(|e| format!("Failed to write {}: {}", destination, e))?;
+    Ok(rows)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn status_csv(rows: &[StudentStatus]) -> String {
+    let mut csv = String::from("student,project,tasks_completed,tasks_total,build_passing,last_build_at\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&row.student),
+            csv_field(&row.project_name),
+            row.tasks_completed,
+            row.tasks_total,
+            row.build_passing,
+            row.last_build_at.as_deref().unwrap_or("")
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_locked_differences() {
+        let settings = json!({ "buildFormats": ["vst3", "clap"], "audio": { "sampleRate": 48000, "bufferSize": 512 } });
+        let locked = json!({ "buildFormats": ["vst3", "clap"], "audio": { "sampleRate": 44100 } });
+        let Value::Object(locked) = locked else { unreachable!() };
+        assert_eq!(locked_differences(&settings, &locked, ""), vec!["audio.sampleRate"]);
+
+        assert_eq!(student_project_name("delay", "  Ana María O'Neil ").unwrap(), "delay-ana-mar-a-o-neil");
+        assert!(student_project_name("delay", "李").is_err());
+
+        let taken = vec!["delay-ana".to_string(), "delay-ana-2".to_string()];
+        assert_eq!(unique_project_name("delay-bo", &taken), "delay-bo");
+        assert_eq!(unique_project_name("delay-ana", &taken), "delay-ana-3");
+        let long = format!("delay-{}", "a".repeat(44));
+        assert_eq!(unique_project_name(&long, &[long.clone()]).len(), 50);
+        let row = StudentStatus {
+            student: "O'Neil, Ana".to_string(),
+            project_name: "delay-ana".to_string(),
+            tasks_completed: 2,
+            tasks_total: 4,
+            build_passing: true,
+            last_build_at: None,
+        };
+        assert!(status_csv(&[row]).ends_with("\"O'Neil, Ana\",delay-ana,2,4,true,\n"));
+    }
+}
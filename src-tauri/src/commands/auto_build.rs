@@ -0,0 +1,197 @@
+//! Watch-and-rebuild mode
+//!
+//! Opt-in live-coding loop: watches a project's `src/` folder and Cargo.toml, runs an
+//! incremental build when files change, and hot reloads the preview plugin
+//! when the build succeeds. Builds are spaced by a cooldown so a burst of
+//! saves (or Claude editing several files) produces one build.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+use tokio::sync::mpsc;
+
+use super::projects::get_workspace_path;
+use crate::audio::engine::get_engine_handle;
+use crate::audio::plugin::file_watcher::{PluginWatcher, WatcherConfig, WatcherStatus};
+use crate::audio::plugin::PluginState;
+
+/// Default minimum time between the end of one build and the start of the next
+const DEFAULT_COOLDOWN_MS: u64 = 2000;
+
+/// Quiet period after the last save before building
+const SOURCE_DEBOUNCE_MS: u64 = 750;
+
+/// Source files the build writes itself - changes to these must not retrigger it
+const GENERATED_SOURCES: &[&str] = &["factory_presets.rs"];
+
+static AUTO_BUILD: Lazy<Mutex<Option<AutoBuildSession>>> = Lazy::new(|| Mutex::new(None));
+
+struct AutoBuildSession {
+    project_name: String,
+    version: u32,
+    watcher: PluginWatcher,
+    /// Task running the builds; aborting it stops a build in progress
+    builds: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AutoBuildInfo {
+    pub project_name: String,
+    pub version: u32,
+    pub watcher: WatcherStatus,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum AutoBuildEvent {
+    /// Source changed, a build is starting
+    #[serde(rename = "building")]
+    Building,
+    #[serde(rename = "built")]
+    Built { success: bool },
+    /// The preview plugin was reloaded with the new build
+    #[serde(rename = "reloaded")]
+    Reloaded,
+    #[serde(rename = "error")]
+    Error { message: String },
+}
+
+/// Build the project, then hot reload the preview plugin if it's loaded
+async fn rebuild_and_reload(project_name: &str, version: u32, window: &tauri::Window) {
+    let _ = window.emit("auto-build", AutoBuildEvent::Building);
+    log::info!("Auto build: rebuilding {} v{}", project_name, version);
+
+    let result = super::build::build_project(project_name.to_string(), version, window.clone()).await;
+    let success = match result {
+        Ok(result) => result.success,
+        Err(e) => {
+            let _ = window.emit("auto-build", AutoBuildEvent::Error { message: e });
+            return;
+        }
+    };
+    let _ = window.emit("auto-build", AutoBuildEvent::Built { success });
+    if !success {
+        return;
+    }
+
+    // The artifact watcher reloads on its own when it's running
+    let plugin_active = get_engine_handle()
+        .map(|h| matches!(h.get_plugin_state(), PluginState::Active { .. }))
+        .unwrap_or(false);
+    if !plugin_active || super::preview::is_artifact_watch_active() {
+        return;
+    }
+
+    let reload_project = project_name.to_string();
+    let app_handle = window.app_handle().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        super::preview::plugin_reload(Some(reload_project), Some(version), app_handle)
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("Task join error: {}", e)));
+    match result {
+        Ok(()) => {
+            let _ = window.emit("auto-build", AutoBuildEvent::Reloaded);
+        }
+        Err(e) => {
+            let _ = window.emit("auto-build", AutoBuildEvent::Error { message: e });
+        }
+    }
+}
+
+/// Build on each batch of changes, one build at a time. Saves made during a
+/// build queue up and trigger one more; builds are spaced by `cooldown`.
+async fn run_builds(
+    mut changes: mpsc::UnboundedReceiver<()>,
+    project_name: String,
+    version: u32,
+    cooldown: Duration,
+    window: tauri::Window,
+) {
+    let mut last_build: Option<Instant> = None;
+    while changes.recv().await.is_some() {
+        if let Some(wait) = last_build.and_then(|finished| cooldown.checked_sub(finished.elapsed())) {
+            tokio::time::sleep(wait).await;
+        }
+        // Everything saved up to now is part of this build
+        while changes.try_recv().is_ok() {}
+        rebuild_and_reload(&project_name, version, &window).await;
+        last_build = Some(Instant::now());
+    }
+}
+
+/// Turn watch-and-rebuild mode on or off for a project
+/// Emits "auto-build" events for each build and "auto-build-watcher-status" on watcher changes
+#[tauri::command]
+pub fn set_auto_build(
+    project_name: String,
+    version: u32,
+    enabled: bool,
+    cooldown_ms: Option<u64>,
+    window: tauri::Window,
+) -> Result<(), String> {
+    let mut session = AUTO_BUILD.lock();
+
+    // Any previous session stops first (also when switching projects), along
+    // with a build it has running
+    if let Some(mut previous) = session.take() {
+        previous.watcher.unwatch();
+        previous.builds.abort();
+        log::info!("Auto build disabled for {}", previous.project_name);
+    }
+    if !enabled {
+        return Ok(());
+    }
+
+    let project_path = get_workspace_path().join("projects").join(&project_name);
+    let src_path = project_path.join("src");
+    if !src_path.exists() {
+        return Err(format!("Project '{}' has no src folder", project_name));
+    }
+
+    let mut config = WatcherConfig {
+        debounce_ms: SOURCE_DEBOUNCE_MS,
+        ..WatcherConfig::default()
+    };
+    config
+        .ignore_patterns
+        .extend(GENERATED_SOURCES.iter().map(|p| p.to_string()));
+    let cooldown = Duration::from_millis(cooldown_ms.unwrap_or(DEFAULT_COOLDOWN_MS));
+
+    let (changed, changes) = mpsc::unbounded_channel();
+    let mut watcher = PluginWatcher::new();
+    let status_window = window.clone();
+    watcher.watch(
+        // Dependency and feature changes need a rebuild as much as source edits
+        vec![src_path, project_path.join("Cargo.toml")],
+        config,
+        Box::new(move |_changed| {
+            let _ = changed.send(());
+        }),
+        Box::new(move |status| {
+            let _ = status_window.emit("auto-build-watcher-status", &status);
+        }),
+    )?;
+    let builds = tauri::async_runtime::spawn(run_builds(changes, project_name.clone(), version, cooldown, window));
+
+    log::info!("Auto build enabled for {} v{}", project_name, version);
+    *session = Some(AutoBuildSession {
+        project_name,
+        version,
+        watcher,
+        builds,
+    });
+    Ok(())
+}
+
+/// Current watch-and-rebuild session, if any
+#[tauri::command]
+pub fn get_auto_build_status() -> Option<AutoBuildInfo> {
+    AUTO_BUILD.lock().as_ref().map(|s| AutoBuildInfo {
+        project_name: s.project_name.clone(),
+        version: s.version,
+        watcher: s.watcher.status(),
+    })
+}
@@ -0,0 +1,158 @@
+//! Parameter automation test vectors
+//!
+//! Records the parameter gestures made in the plugin GUI during preview and
+//! exports them as JSON test vectors in the project's `tests/automation/`
+//! folder, where the generated DSP test harness replays them.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::projects::get_workspace_path;
+use crate::audio::automation::RecordedParamEvent;
+use crate::audio::engine::{get_engine_handle, get_engine_sample_rate};
+
+/// Test vector format version
+const TEST_VECTOR_VERSION: u32 = 1;
+
+/// Test vector folder (relative to the project root)
+const AUTOMATION_DIR: &str = "tests/automation";
+
+/// Most recent finished recording, kept until exported or replaced
+static LAST_RECORDING: Lazy<Mutex<Option<AutomationTestVector>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AutomationParam {
+    pub id: u32,
+    pub name: String,
+    pub min_value: f64,
+    pub max_value: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AutomationTestVector {
+    pub version: u32,
+    pub plugin_name: String,
+    pub sample_rate: u32,
+    pub length_samples: u64,
+    /// Parameters referenced by the events (plain-value ranges for normalizing)
+    pub params: Vec<AutomationParam>,
+    pub events: Vec<RecordedParamEvent>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AutomationRecordingSummary {
+    pub length_secs: f64,
+    pub event_count: usize,
+    pub gesture_count: usize,
+    pub param_count: usize,
+}
+
+fn event_param_id(event: &RecordedParamEvent) -> u32 {
+    match *event {
+        RecordedParamEvent::Value { param_id, .. }
+        | RecordedParamEvent::GestureBegin { param_id, .. }
+        | RecordedParamEvent::GestureEnd { param_id, .. } => param_id,
+    }
+}
+
+/// Keep vector file names to safe characters
+fn sanitize_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if cleaned.is_empty() {
+        "gesture".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Start recording parameter gestures from the plugin GUI
+#[tauri::command]
+pub fn start_automation_recording() -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.start_param_recording()
+}
+
+/// Stop recording and keep the result for export
+#[tauri::command]
+pub fn stop_automation_recording() -> Result<AutomationRecordingSummary, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    if !handle.is_param_recording() {
+        return Err("Not recording".to_string());
+    }
+    let (events, length_samples) = handle.stop_param_recording();
+
+    let mut ids: Vec<u32> = events.iter().map(event_param_id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let params: Vec<AutomationParam> = handle
+        .list_plugin_params()
+        .into_iter()
+        .filter(|p| ids.contains(&p.id))
+        .map(|p| AutomationParam {
+            id: p.id,
+            name: p.name,
+            min_value: p.min_value,
+            max_value: p.max_value,
+        })
+        .collect();
+
+    let sample_rate = get_engine_sample_rate().unwrap_or(48000);
+    let summary = AutomationRecordingSummary {
+        length_secs: length_samples as f64 / sample_rate as f64,
+        event_count: events.len(),
+        gesture_count: events
+            .iter()
+            .filter(|e| matches!(e, RecordedParamEvent::GestureBegin { .. }))
+            .count(),
+        param_count: params.len(),
+    };
+    log::info!(
+        "Automation recording stopped: {} events over {:.1}s",
+        summary.event_count,
+        summary.length_secs
+    );
+
+    *LAST_RECORDING.lock() = Some(AutomationTestVector {
+        version: TEST_VECTOR_VERSION,
+        plugin_name: handle.plugin_name().unwrap_or_default(),
+        sample_rate,
+        length_samples,
+        params,
+        events,
+    });
+    Ok(summary)
+}
+
+/// Export the last recording as a test vector in the project's tests/automation folder
+/// Returns the path of the written file
+#[tauri::command]
+pub fn export_automation_recording(project_name: String, name: String) -> Result<String, String> {
+    let vector = LAST_RECORDING
+        .lock()
+        .clone()
+        .ok_or_else(|| "No recording to export".to_string())?;
+    if vector.events.is_empty() {
+        return Err("Recording has no parameter changes".to_string());
+    }
+
+    let project_path = get_workspace_path().join("projects").join(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+
+    let dir = project_path.join(AUTOMATION_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create automation folder: {}", e))?;
+    let path = dir.join(format!("{}.json", sanitize_name(&name)));
+
+    let json = serde_json::to_string_pretty(&vector)
+        .map_err(|e| format!("Failed to serialize test vector: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write test vector: {}", e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
@@ -0,0 +1,581 @@
+//! Automation API for editors and scripts
+//!
+//! Lets external tools trigger "build and reload project X" (e.g. from a VS Code
+//! keybinding) in two ways, both off unless the `automationApi` setting is on:
+//!
+//! - `freqlab://build-and-reload/<project>?version=N&token=<token>` links (also
+//!   `build` and `reload`), registered as a URL scheme in Info.plist. Any web
+//!   page can open a link, so links without the token are ignored.
+//! - JSON-RPC 2.0 over HTTP on 127.0.0.1, e.g.
+//!   `{"jsonrpc":"2.0","id":1,"method":"build_and_reload","params":{"project":"x"}}`,
+//!   sent with `Authorization: Bearer <token>`
+//!
+//! The server's port and token are written to ~/VSTWorkshop/automation.json
+//! (readable only by the user) so tools can find them. `freqlab-cli build
+//! --reload` reads them from there, which keeps the token out of editor tasks.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tauri::Emitter;
+
+use super::build::{run_build, BUILD_LOCK};
+use super::chat::resolve_project_version;
+use super::preview::plugin_load_for_project;
+use super::projects::{get_workspace_path, validate_name};
+use super::settings::{current_settings, update_settings};
+use crate::audio::engine::get_engine_handle;
+
+/// Port tried first; another free port is used if it's taken
+const DEFAULT_PORT: u16 = 47319;
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+static SERVER_RUNNING: AtomicBool = AtomicBool::new(false);
+static SERVER_PORT: AtomicU16 = AtomicU16::new(0);
+
+/// The running server's accept thread and the flag that ends it
+/// Each server gets its own flag, so stopping one can't be undone by a restart
+/// that flips a shared flag back before the old thread has seen it.
+struct ServerThread {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+static SERVER_THREAD: Lazy<Mutex<Option<ServerThread>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationAction {
+    Build,
+    Reload,
+    BuildAndReload,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct AutomationRequest {
+    #[serde(skip, default = "default_action")]
+    pub action: AutomationAction,
+    pub project: String,
+    pub version: Option<u32>,
+}
+
+fn default_action() -> AutomationAction {
+    AutomationAction::BuildAndReload
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationInfo {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: Option<u16>,
+    pub token: Option<String>,
+}
+
+impl AutomationAction {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "build" => Some(AutomationAction::Build),
+            "reload" => Some(AutomationAction::Reload),
+            "build_and_reload" | "build-and-reload" => Some(AutomationAction::BuildAndReload),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed `freqlab://` link and the token it carried
+#[derive(Debug, PartialEq)]
+struct DeepLink {
+    request: AutomationRequest,
+    token: Option<String>,
+}
+
+/// Parse a `freqlab://<action>/<project>[?version=N][&token=T]` link
+fn parse_deep_link(url: &str) -> Result<DeepLink, String> {
+    let rest = url
+        .strip_prefix("freqlab://")
+        .ok_or_else(|| format!("Not a freqlab link: {}", url))?;
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let action = segments
+        .next()
+        .and_then(AutomationAction::parse)
+        .ok_or_else(|| format!("Unknown freqlab link action in {}", url))?;
+    let project = segments
+        .next()
+        .ok_or_else(|| format!("No project in {}", url))?
+        .to_string();
+    validate_name(&project)?;
+
+    let mut version = None;
+    let mut token = None;
+    for pair in query.split('&') {
+        if let Some(value) = pair.strip_prefix("version=") {
+            version = Some(value.parse().map_err(|_| format!("Invalid version in {}", url))?);
+        } else if let Some(value) = pair.strip_prefix("token=") {
+            token = Some(value.to_string());
+        }
+    }
+    Ok(DeepLink {
+        request: AutomationRequest { action, project, version },
+        token,
+    })
+}
+
+/// Build and/or reload a project, returning a JSON summary
+/// Builds wait for any other build (from the app or another request) to finish.
+async fn perform(request: AutomationRequest, app_handle: &tauri::AppHandle) -> Result<Value, String> {
+    let version = resolve_project_version(&request.project, request.version).await?;
+    log::info!("Automation: {:?} {} v{}", request.action, request.project, version);
+    // Lets the UI switch to the project and show the build output
+    let _ = app_handle.emit(
+        "automation-request",
+        json!({ "action": request.action, "project": request.project, "version": version }),
+    );
+
+    let mut output_path = None;
+    if request.action != AutomationAction::Reload {
+        let handle = app_handle.clone();
+        let result = run_build(request.project.clone(), version, move |event| {
+            let _ = handle.emit("build-stream", event);
+        })
+        .await?;
+        if !result.success {
+            return Err(format!(
+                "Build failed: {}",
+                result.error.unwrap_or_default().lines().last().unwrap_or("see the build output")
+            ));
+        }
+        output_path = result.output_path;
+    }
+
+    let mut reloaded = false;
+    if request.action != AutomationAction::Build {
+        if get_engine_handle().is_none() {
+            return Err("The preview's audio engine isn't running".to_string());
+        }
+        // Don't load a bundle that a build is replacing
+        let _build = BUILD_LOCK.lock().await;
+        plugin_load_for_project(request.project.clone(), version, app_handle.clone())?;
+        reloaded = true;
+    }
+
+    Ok(json!({
+        "project": request.project,
+        "version": version,
+        "outputPath": output_path,
+        "reloaded": reloaded,
+    }))
+}
+
+/// Handle a `freqlab://` link opened by the system
+pub fn handle_deep_link(url: &str, app_handle: &tauri::AppHandle) {
+    if !current_settings().automation_api {
+        log::warn!("Ignoring {} - the automation API is off", url);
+        return;
+    }
+    let link = match parse_deep_link(url) {
+        Ok(link) => link,
+        Err(e) => {
+            log::warn!("Ignoring {}: {}", url, e);
+            return;
+        }
+    };
+    let token = read_automation_file().token.unwrap_or_default();
+    let authorized = !token.is_empty() && link.token.as_deref().is_some_and(|t| tokens_match(t, &token));
+    if !authorized {
+        log::warn!("Ignoring freqlab link for {} - missing or wrong token", link.request.project);
+        let _ = app_handle.emit("automation-error", "A freqlab:// link without a valid token was ignored");
+        return;
+    }
+    let request = link.request;
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = perform(request, &app_handle).await {
+            log::warn!("Automation link failed: {}", e);
+            let _ = app_handle.emit("automation-error", &e);
+        }
+    });
+}
+
+// =============================================================================
+// JSON-RPC server
+// =============================================================================
+
+fn get_automation_file_path() -> PathBuf {
+    get_workspace_path().join("automation.json")
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct AutomationFile {
+    port: Option<u16>,
+    token: Option<String>,
+}
+
+fn read_automation_file() -> AutomationFile {
+    std::fs::read_to_string(get_automation_file_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn write_automation_file(file: &AutomationFile) -> Result<(), String> {
+    std::fs::create_dir_all(get_workspace_path())
+        .map_err(|e| format!("Failed to create workspace dir: {}", e))?;
+    let path = get_automation_file_path();
+    let json = serde_json::to_string_pretty(file).map_err(|e| format!("Failed to serialize automation info: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write automation info: {}", e))?;
+    // The token authorizes builds, so keep it private to the user
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+/// The saved token, creating one on first use
+fn get_or_create_token() -> Result<String, String> {
+    let mut file = read_automation_file();
+    if let Some(token) = file.token.clone() {
+        return Ok(token);
+    }
+    let token = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+    file.token = Some(token.clone());
+    write_automation_file(&file)?;
+    Ok(token)
+}
+
+/// Compare without bailing at the first differing byte
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Answer one JSON-RPC request body
+async fn handle_rpc(body: &str, app_handle: tauri::AppHandle) -> Value {
+    let request: Value = match serde_json::from_str(body) {
+        Ok(v) => v,
+        Err(_) => return rpc_error(Value::Null, -32700, "Parse error"),
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str).unwrap_or_default();
+    if method == "ping" {
+        return json!({ "jsonrpc": "2.0", "id": id, "result": "pong" });
+    }
+    let action = match AutomationAction::parse(method) {
+        Some(a) => a,
+        None => return rpc_error(id, -32601, &format!("Unknown method: {}", method)),
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    let mut automation_request: AutomationRequest = match serde_json::from_value(params) {
+        Ok(r) => r,
+        Err(e) => return rpc_error(id, -32602, &format!("Invalid params: {}", e)),
+    };
+    if let Err(e) = validate_name(&automation_request.project) {
+        return rpc_error(id, -32602, &format!("Invalid project: {}", e));
+    }
+    automation_request.action = action;
+
+    match perform(automation_request, &app_handle).await {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => rpc_error(id, -32000, &e),
+    }
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn handle_connection(mut stream: TcpStream, app_handle: tauri::AppHandle) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    // Read per request so a regenerated token takes effect immediately
+    let token = read_automation_file().token.unwrap_or_default();
+    let mut reader = match stream.try_clone() {
+        Ok(s) => BufReader::new(s),
+        Err(_) => return,
+    };
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut content_length = 0usize;
+    let mut authorized = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.parse().unwrap_or(0),
+                "authorization" => {
+                    authorized = !token.is_empty()
+                        && value.strip_prefix("Bearer ").is_some_and(|t| tokens_match(t.trim(), &token))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !request_line.starts_with("POST ") {
+        return write_response(&mut stream, "405 Method Not Allowed", r#"{"error":"POST JSON-RPC requests only"}"#);
+    }
+    if !authorized {
+        return write_response(&mut stream, "401 Unauthorized", r#"{"error":"Missing or wrong bearer token"}"#);
+    }
+    if content_length > MAX_BODY_BYTES {
+        return write_response(&mut stream, "413 Payload Too Large", r#"{"error":"Request too large"}"#);
+    }
+    let mut body = vec![0u8; content_length];
+    if reader.read_exact(&mut body).is_err() {
+        return write_response(&mut stream, "400 Bad Request", r#"{"error":"Incomplete body"}"#);
+    }
+
+    let response = tauri::async_runtime::block_on(handle_rpc(&String::from_utf8_lossy(&body), app_handle));
+    write_response(&mut stream, "200 OK", &response.to_string());
+}
+
+// =============================================================================
+// Client (freqlab-cli)
+// =============================================================================
+
+/// Send a request to the running app's server, returning the JSON-RPC result
+pub(crate) fn send_automation_request(request: &AutomationRequest) -> Result<Value, String> {
+    let file = read_automation_file();
+    let (Some(port), Some(token)) = (file.port, file.token) else {
+        return Err("The automation API isn't set up - turn it on in freqlab's Settings".to_string());
+    };
+    let method = serde_json::to_value(request.action).map_err(|e| e.to_string())?;
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": { "project": request.project, "version": request.version },
+    })
+    .to_string();
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).map_err(|e| {
+        format!("Failed to reach freqlab on port {} (is it running with the automation API on?): {}", port, e)
+    })?;
+    let http = format!(
+        "POST / HTTP/1.1\r\nHost: 127.0.0.1\r\nAuthorization: Bearer {}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        token,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(http.as_bytes())
+        .map_err(|e| format!("Failed to send automation request: {}", e))?;
+    // No read timeout - the response only comes once the build has finished
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|e| format!("Failed to read automation response: {}", e))?;
+    parse_rpc_response(&response)
+}
+
+/// The result of an HTTP response carrying a JSON-RPC reply (or the server's
+/// plain `{"error": "..."}` for rejected requests)
+fn parse_rpc_response(response: &str) -> Result<Value, String> {
+    let (_, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| "Malformed response from freqlab".to_string())?;
+    let reply: Value =
+        serde_json::from_str(body).map_err(|e| format!("Malformed response from freqlab: {}", e))?;
+    match reply.get("error") {
+        Some(Value::String(message)) => Err(message.clone()),
+        Some(error) => Err(error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("Request failed")
+            .to_string()),
+        None => Ok(reply.get("result").cloned().unwrap_or(Value::Null)),
+    }
+}
+
+/// Start the JSON-RPC server if it isn't running
+pub fn start_automation_server(app_handle: tauri::AppHandle) -> Result<u16, String> {
+    if SERVER_RUNNING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return Ok(SERVER_PORT.load(Ordering::SeqCst));
+    }
+    let started = spawn_server(app_handle);
+    if started.is_err() {
+        SERVER_RUNNING.store(false, Ordering::SeqCst);
+    }
+    started
+}
+
+fn spawn_server(app_handle: tauri::AppHandle) -> Result<u16, String> {
+    // Let a previous server's thread release its listener before rebinding,
+    // or a quick off/on would land on a random port
+    let previous = SERVER_THREAD.lock().take();
+    if let Some(previous) = previous {
+        previous.stop.store(true, Ordering::SeqCst);
+        let _ = previous.thread.join();
+    }
+
+    get_or_create_token()?;
+    let listener = TcpListener::bind(("127.0.0.1", DEFAULT_PORT))
+        .or_else(|_| TcpListener::bind(("127.0.0.1", 0)))
+        .map_err(|e| format!("Failed to start automation server: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    // Polled so stop_automation_server can end the loop
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    let mut file = read_automation_file();
+    file.port = Some(port);
+    write_automation_file(&file)?;
+
+    SERVER_PORT.store(port, Ordering::SeqCst);
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    let thread = std::thread::spawn(move || {
+        while !thread_stop.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let _ = stream.set_nonblocking(false);
+                    let app_handle = app_handle.clone();
+                    std::thread::spawn(move || handle_connection(stream, app_handle));
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => log::warn!("Automation server accept failed: {}", e),
+            }
+        }
+        log::info!("Automation server stopped");
+    });
+    *SERVER_THREAD.lock() = Some(ServerThread { stop, thread });
+    log::info!("Automation server listening on 127.0.0.1:{}", port);
+    Ok(port)
+}
+
+/// Stop the server, waiting for its accept thread to let go of the port
+fn stop_automation_server() {
+    let server = SERVER_THREAD.lock().take();
+    if let Some(server) = server {
+        server.stop.store(true, Ordering::SeqCst);
+        let _ = server.thread.join();
+    }
+    SERVER_PORT.store(0, Ordering::SeqCst);
+    SERVER_RUNNING.store(false, Ordering::SeqCst);
+}
+
+/// Start the server at launch when the setting is on
+pub fn init_automation(app_handle: tauri::AppHandle) {
+    if current_settings().automation_api {
+        if let Err(e) = start_automation_server(app_handle) {
+            log::warn!("{}", e);
+        }
+    }
+}
+
+fn automation_info() -> AutomationInfo {
+    let running = SERVER_RUNNING.load(Ordering::SeqCst);
+    AutomationInfo {
+        enabled: current_settings().automation_api,
+        running,
+        port: running.then(|| SERVER_PORT.load(Ordering::SeqCst)),
+        token: read_automation_file().token,
+    }
+}
+
+/// Get the automation server's state, port, and token (for showing in settings)
+#[tauri::command]
+pub async fn get_automation_info() -> AutomationInfo {
+    automation_info()
+}
+
+/// Turn the automation API on or off (saves the setting and starts/stops the server)
+#[tauri::command]
+pub async fn set_automation_enabled(enabled: bool, app_handle: tauri::AppHandle) -> Result<AutomationInfo, String> {
+    update_settings(json!({ "automationApi": enabled }), app_handle.clone()).await?;
+    if enabled {
+        start_automation_server(app_handle)?;
+    } else {
+        stop_automation_server();
+    }
+    Ok(automation_info())
+}
+
+/// Replace the token, invalidating the old one
+#[tauri::command]
+pub async fn regenerate_automation_token() -> Result<AutomationInfo, String> {
+    let mut file = read_automation_file();
+    file.token = None;
+    write_automation_file(&file)?;
+    get_or_create_token()?;
+    Ok(automation_info())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deep_link() {
+        assert_eq!(
+            parse_deep_link("freqlab://build-and-reload/warm-drive?version=4&token=abc"),
+            Ok(DeepLink {
+                request: AutomationRequest {
+                    action: AutomationAction::BuildAndReload,
+                    project: "warm-drive".to_string(),
+                    version: Some(4),
+                },
+                token: Some("abc".to_string()),
+            })
+        );
+        let link = parse_deep_link("freqlab://reload/x/").unwrap();
+        assert_eq!((link.request.action, link.token), (AutomationAction::Reload, None));
+        // Project names are checked like everywhere else
+        assert!(parse_deep_link("freqlab://build/..%2F..%2Fetc").is_err());
+        assert!(parse_deep_link("freqlab://build/Warm").is_err());
+        assert!(parse_deep_link("freqlab://delete/x").is_err());
+        assert!(parse_deep_link("freqlab://build").is_err());
+        assert!(parse_deep_link("https://build/x").is_err());
+    }
+
+    #[test]
+    fn test_parse_rpc_response() {
+        let ok = "HTTP/1.1 200 OK\r\n\r\n{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"reloaded\":true}}";
+        assert_eq!(parse_rpc_response(ok), Ok(json!({ "reloaded": true })));
+        let failed = "HTTP/1.1 200 OK\r\n\r\n{\"jsonrpc\":\"2.0\",\"id\":1,\"error\":{\"code\":-32000,\"message\":\"Build failed\"}}";
+        assert_eq!(parse_rpc_response(failed), Err("Build failed".to_string()));
+        let rejected = "HTTP/1.1 401 Unauthorized\r\n\r\n{\"error\":\"Missing or wrong bearer token\"}";
+        assert_eq!(parse_rpc_response(rejected), Err("Missing or wrong bearer token".to_string()));
+        assert!(parse_rpc_response("").is_err());
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc123", "abc124"));
+        assert!(!tokens_match("abc", "abc123"));
+    }
+}
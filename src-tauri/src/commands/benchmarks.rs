@@ -0,0 +1,264 @@
+//! DSP benchmarks
+//!
+//! Times the built plugin's process() at representative block sizes - a
+//! warm-up, then a few seconds of audio per size, summarized as median and p99
+//! block times and as a share of the real-time budget. Results are kept per
+//! version in `.vstworkshop/benchmarks.json`, and each run is compared against
+//! the latest earlier version so a slowdown from an edit shows up as a
+//! regression.
+//!
+//! The plugin is driven through its CLAP entry point, the same path a DAW
+//! takes, so the numbers include the wrapper's per-block overhead.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::activity::project_dir;
+use super::chat::resolve_project_version;
+use super::compare::find_clap_bundle;
+use crate::audio::plugin::PluginInstance;
+use crate::audio::signals::{SignalConfig, SignalGenerator, SignalType};
+
+const SAMPLE_RATE: u32 = 48000;
+
+/// Block sizes from low-latency tracking up to mixdown
+const BLOCK_SIZES: [usize; 5] = [32, 64, 128, 512, 1024];
+
+/// Audio processed per block size before timing starts (seconds)
+const WARMUP_SECS: f64 = 0.25;
+/// Audio timed per block size (seconds)
+const MEASURE_SECS: f64 = 2.0;
+
+/// Median block time this much above the baseline counts as a regression
+const REGRESSION_THRESHOLD: f64 = 1.10;
+/// Changes on blocks faster than this are timer noise, not regressions (µs)
+const MIN_SIGNIFICANT_US: f64 = 1.0;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BlockBenchmark {
+    pub block_size: usize,
+    pub iterations: usize,
+    pub median_us: f64,
+    pub p99_us: f64,
+    pub mean_us: f64,
+    pub ns_per_sample: f64,
+    /// Median block time as a percentage of the block's real-time budget
+    pub budget_percent: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BenchmarkRun {
+    pub version: u32,
+    pub measured_at: String,
+    pub plugin_path: String,
+    pub sample_rate: u32,
+    pub blocks: Vec<BlockBenchmark>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct BenchmarkComparison {
+    pub block_size: usize,
+    pub baseline_median_us: f64,
+    pub median_us: f64,
+    /// Positive means slower than the baseline
+    pub change_percent: f64,
+    pub regression: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BenchmarkReport {
+    pub run: BenchmarkRun,
+    /// Version compared against (the latest earlier version with results)
+    pub baseline_version: Option<u32>,
+    pub comparisons: Vec<BenchmarkComparison>,
+    pub regression: bool,
+}
+
+fn get_history_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("benchmarks.json")
+}
+
+fn load_history(project_path: &Path) -> Vec<BenchmarkRun> {
+    fs::read_to_string(get_history_path(project_path))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+    sorted[index.min(sorted.len() - 1)]
+}
+
+/// Summarize the timings (µs) of one block size
+fn summarize(block_size: usize, timings_us: &[f64]) -> BlockBenchmark {
+    let mut sorted = timings_us.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median_us = percentile(&sorted, 0.5);
+    let budget_us = block_size as f64 / SAMPLE_RATE as f64 * 1_000_000.0;
+    BlockBenchmark {
+        block_size,
+        iterations: sorted.len(),
+        median_us,
+        p99_us: percentile(&sorted, 0.99),
+        mean_us: if sorted.is_empty() { 0.0 } else { sorted.iter().sum::<f64>() / sorted.len() as f64 },
+        ns_per_sample: median_us * 1000.0 / block_size as f64,
+        budget_percent: median_us / budget_us * 100.0,
+    }
+}
+
+/// Compare each block size's median against the baseline run
+fn compare_runs(run: &BenchmarkRun, baseline: &BenchmarkRun) -> Vec<BenchmarkComparison> {
+    run.blocks
+        .iter()
+        .filter_map(|block| {
+            let base = baseline.blocks.iter().find(|b| b.block_size == block.block_size)?;
+            let ratio = if base.median_us > 0.0 { block.median_us / base.median_us } else { 1.0 };
+            Some(BenchmarkComparison {
+                block_size: block.block_size,
+                baseline_median_us: base.median_us,
+                median_us: block.median_us,
+                change_percent: (ratio - 1.0) * 100.0,
+                regression: ratio > REGRESSION_THRESHOLD && block.median_us - base.median_us > MIN_SIGNIFICANT_US,
+            })
+        })
+        .collect()
+}
+
+/// Time process() at every block size
+fn measure(bundle: &Path) -> Result<Vec<BlockBenchmark>, String> {
+    let max_block = BLOCK_SIZES[BLOCK_SIZES.len() - 1];
+    let mut plugin = PluginInstance::load(bundle, SAMPLE_RATE as f64, max_block as u32)?;
+
+    // Pink noise exercises filters and dynamics across the spectrum
+    let mut generator = SignalGenerator::new(SAMPLE_RATE);
+    generator.set_config(SignalConfig {
+        signal_type: SignalType::PinkNoise,
+        amplitude: 0.5,
+        ..SignalConfig::default()
+    });
+    let mut input = vec![0.0f32; max_block * 2];
+    let mut output = vec![0.0f32; max_block * 2];
+
+    let mut blocks = Vec::with_capacity(BLOCK_SIZES.len());
+    for block_size in BLOCK_SIZES {
+        plugin.reactivate(SAMPLE_RATE as f64, block_size as u32)?;
+        let warmup = (WARMUP_SECS * SAMPLE_RATE as f64) as usize / block_size;
+        let iterations = (MEASURE_SECS * SAMPLE_RATE as f64) as usize / block_size;
+        let mut timings = Vec::with_capacity(iterations);
+
+        for i in 0..warmup + iterations {
+            for frame in input[..block_size * 2].chunks_exact_mut(2) {
+                let s = generator.next_sample();
+                frame[0] = s.left;
+                frame[1] = s.right;
+            }
+            let start = Instant::now();
+            plugin.process(&input[..block_size * 2], &mut output[..block_size * 2])?;
+            let elapsed = start.elapsed().as_secs_f64() * 1_000_000.0;
+            if plugin.has_crashed() {
+                return Err(format!("Plugin crashed while benchmarking {}-sample blocks", block_size));
+            }
+            if i >= warmup {
+                timings.push(elapsed);
+            }
+        }
+        blocks.push(summarize(block_size, &timings));
+    }
+    plugin.stop_processing();
+    Ok(blocks)
+}
+
+/// Benchmark a version's process() and store the result with the project
+/// Defaults to the current version; compares against the latest earlier version.
+#[tauri::command]
+pub async fn run_benchmarks(project_name: String, version: Option<u32>) -> Result<BenchmarkReport, String> {
+    let version = resolve_project_version(&project_name, version).await?;
+    let bundle = find_clap_bundle(&project_name, version)?;
+    log::info!("Benchmarking {} v{} ({:?})", project_name, version, bundle);
+
+    let blocks = {
+        let bundle = bundle.clone();
+        tokio::task::spawn_blocking(move || measure(&bundle))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??
+    };
+    let run = BenchmarkRun {
+        version,
+        measured_at: chrono::Utc::now().to_rfc3339(),
+        plugin_path: bundle.to_string_lossy().to_string(),
+        sample_rate: SAMPLE_RATE,
+        blocks,
+    };
+
+    let project_path = project_dir(&project_name);
+    let mut history = load_history(&project_path);
+    let baseline = history
+        .iter()
+        .filter(|r| r.version < version)
+        .max_by_key(|r| r.version)
+        .cloned();
+    let comparisons = baseline.as_ref().map(|b| compare_runs(&run, b)).unwrap_or_default();
+
+    // One run per version - re-running replaces it
+    history.retain(|r| r.version != version);
+    history.push(run.clone());
+    history.sort_by_key(|r| r.version);
+    let json = serde_json::to_string_pretty(&history).map_err(|e| format!("Failed to serialize benchmarks: {}", e))?;
+    fs::write(get_history_path(&project_path), json).map_err(|e| format!("Failed to save benchmarks: {}", e))?;
+
+    Ok(BenchmarkReport {
+        run,
+        baseline_version: baseline.map(|b| b.version),
+        regression: comparisons.iter().any(|c| c.regression),
+        comparisons,
+    })
+}
+
+/// Stored benchmark runs for a project, oldest version first
+#[tauri::command]
+pub async fn get_benchmark_history(project_name: String) -> Result<Vec<BenchmarkRun>, String> {
+    Ok(load_history(&project_dir(&project_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(version: u32, medians: &[(usize, f64)]) -> BenchmarkRun {
+        BenchmarkRun {
+            version,
+            measured_at: String::new(),
+            plugin_path: String::new(),
+            sample_rate: SAMPLE_RATE,
+            blocks: medians.iter().map(|&(size, median)| summarize(size, &[median])).collect(),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let timings: Vec<f64> = (1..=100).map(|t| t as f64).collect();
+        let block = summarize(480, &timings);
+        assert_eq!(block.iterations, 100);
+        assert_eq!(block.median_us, 51.0);
+        assert_eq!(block.p99_us, 99.0);
+        // 480 samples at 48 kHz is a 10 ms budget
+        assert!((block.budget_percent - 0.51).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compare_runs_flags_regressions() {
+        let baseline = run(2, &[(64, 10.0), (512, 80.0), (1024, 0.2)]);
+        let current = run(3, &[(64, 10.5), (512, 100.0), (1024, 0.4)]);
+        let comparisons = compare_runs(&current, &baseline);
+        assert!(!comparisons[0].regression, "5% is within noise");
+        assert!(comparisons[1].regression);
+        assert!((comparisons[1].change_percent - 25.0).abs() < 1e-9);
+        assert!(!comparisons[2].regression, "sub-microsecond blocks are timer noise");
+    }
+}
@@ -0,0 +1,280 @@
+//! Block-size independence audit
+//!
+//! Part of the offline validation suite. Renders the test program with fixed
+//! 512-sample blocks as the reference, then again with single-sample,
+//! prime-sized, and irregular blocks, the way real DAWs split buffers around
+//! loop points and automation. A plugin whose output changes with the block
+//! size mishandles state between process() calls: smoothers or envelopes reset
+//! per block, block-rate processing advanced per call, or assumptions about
+//! power-of-two buffers.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::analysis_jobs::run_analysis_job;
+use super::distortion::resolve_plugin_bundle;
+use super::issues::{sync_validator_issues, ValidatorIssue};
+use crate::audio::buffer::StereoSample;
+use crate::audio::offline::{default_test_program, deinterleave, BlockProcessor};
+use crate::audio::plugin::PluginInstance;
+
+const SAMPLE_RATE: u32 = 48000;
+const REFERENCE_BLOCK_SIZE: usize = 512;
+
+/// Largest block in any pattern (the plugin is activated with this)
+const MAX_BLOCK_SIZE: usize = 4096;
+
+/// Sample differences below this are float round-off, not a bug (dBFS)
+const DIVERGENCE_THRESHOLD_DB: f32 = -100.0;
+
+const SUGGESTION: &str = "The output depends on how the host splits the audio into blocks, so some state isn't \
+carried correctly between process() calls. Keep filter, phase, envelope and smoother state in the plugin struct \
+instead of resetting it per block, advance block-rate work (FFT frames, LFO and control-rate updates) by samples \
+rather than per call, and don't assume a fixed or power-of-two buffer size.";
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockPattern {
+    /// One sample per process() call
+    SingleSample,
+    /// Cycling prime sizes, so no boundary lines up with the reference's
+    Prime,
+    /// Sizes that change every call, including tiny and maximum-size blocks
+    Irregular,
+}
+
+impl BlockPattern {
+    const ALL: [BlockPattern; 3] = [Self::SingleSample, Self::Prime, Self::Irregular];
+
+    fn sizes(self) -> &'static [usize] {
+        match self {
+            Self::SingleSample => &[1],
+            Self::Prime => &[7, 131, 509, 1021, 2053],
+            Self::Irregular => &[3, 512, 64, 1, 4096, 37, 1000, 255, 2],
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::SingleSample => "single-sample blocks",
+            Self::Prime => "prime-sized blocks",
+            Self::Irregular => "irregular blocks",
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BlockSizeCase {
+    pub pattern: BlockPattern,
+    pub blocks: usize,
+    /// Largest sample difference from the reference render (dBFS)
+    pub peak_difference_db: f32,
+    /// First sample that differs from the reference beyond round-off
+    pub first_divergence_sample: Option<usize>,
+    pub first_divergence_secs: Option<f32>,
+    pub passed: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct BlockSizeAuditReport {
+    pub plugin_path: String,
+    pub reference_block_size: usize,
+    /// False when two identical reference renders already differ (random
+    /// seeds, time-based modulation); the cases are then inconclusive
+    pub deterministic: bool,
+    pub cases: Vec<BlockSizeCase>,
+    pub crashed: bool,
+    /// Inconclusive runs aren't failures
+    pub passed: bool,
+    pub suggestion: Option<String>,
+}
+
+/// Block sizes for rendering `total` samples with a pattern (the last block is cut short)
+fn block_schedule(sizes: &[usize], total: usize) -> Vec<usize> {
+    let mut schedule = Vec::new();
+    let mut pos = 0;
+    for &size in sizes.iter().cycle() {
+        if pos >= total {
+            break;
+        }
+        let frames = size.min(total - pos);
+        schedule.push(frames);
+        pos += frames;
+    }
+    schedule
+}
+
+/// Peak difference between two renders and the first sample past the threshold
+fn divergence(reference: &[StereoSample], candidate: &[StereoSample]) -> (f32, Option<usize>) {
+    let threshold = 10f32.powf(DIVERGENCE_THRESHOLD_DB / 20.0);
+    let mut peak = 0.0f32;
+    let mut first = None;
+    for (i, (a, b)) in reference.iter().zip(candidate).enumerate() {
+        let (left, right) = ((a.left - b.left).abs(), (a.right - b.right).abs());
+        // NaN output counts as diverged
+        let d = if left.is_nan() || right.is_nan() { f32::INFINITY } else { left.max(right) };
+        if d > threshold && first.is_none() {
+            first = Some(i);
+        }
+        peak = peak.max(d);
+    }
+    if reference.len() != candidate.len() && first.is_none() {
+        first = Some(reference.len().min(candidate.len()));
+    }
+    let peak_db = if peak > 0.0 { (20.0 * peak.log10()).clamp(-200.0, 200.0) } else { -200.0 };
+    (peak_db, first)
+}
+
+/// Render the input from a freshly activated plugin with the given block sizes
+fn render(plugin: &mut PluginInstance, input: &[StereoSample], schedule: &[usize]) -> Result<Vec<StereoSample>, String> {
+    plugin.reactivate(SAMPLE_RATE as f64, MAX_BLOCK_SIZE as u32)?;
+
+    let mut output = Vec::with_capacity(input.len());
+    let mut processor = BlockProcessor::new(MAX_BLOCK_SIZE);
+    let mut pos = 0;
+
+    for &frames in schedule {
+        let processed = processor.process(plugin, &input[pos..pos + frames])?;
+        if plugin.has_crashed() {
+            return Err("Plugin crashed".to_string());
+        }
+        output.extend(deinterleave(processed));
+        pos += frames;
+    }
+    Ok(output)
+}
+
+/// Run the block-size audit on a specific .clap bundle
+pub(crate) async fn block_size_audit_bundle(bundle: PathBuf) -> Result<BlockSizeAuditReport, String> {
+    run_analysis_job("block_size_audit", None, move |_job| {
+        let mut plugin = PluginInstance::load(Path::new(&bundle), SAMPLE_RATE as f64, MAX_BLOCK_SIZE as u32)?;
+        log::info!("Block-size auditing {:?}", bundle);
+
+        let input = default_test_program(SAMPLE_RATE);
+        let reference_schedule = block_schedule(&[REFERENCE_BLOCK_SIZE], input.len());
+        let mut cases = Vec::with_capacity(BlockPattern::ALL.len());
+        let mut crashed = false;
+        let mut deterministic = true;
+
+        let result = render(&mut plugin, &input, &reference_schedule).and_then(|reference| {
+            let repeat = render(&mut plugin, &input, &reference_schedule)?;
+            deterministic = divergence(&reference, &repeat).1.is_none();
+            if !deterministic {
+                log::warn!("Plugin output isn't deterministic; block-size cases are inconclusive");
+                return Ok(());
+            }
+
+            for pattern in BlockPattern::ALL {
+                let schedule = block_schedule(pattern.sizes(), input.len());
+                let output = render(&mut plugin, &input, &schedule)?;
+                let (peak_difference_db, first_divergence_sample) = divergence(&reference, &output);
+                cases.push(BlockSizeCase {
+                    pattern,
+                    blocks: schedule.len(),
+                    peak_difference_db,
+                    first_divergence_sample,
+                    first_divergence_secs: first_divergence_sample.map(|s| s as f32 / SAMPLE_RATE as f32),
+                    passed: first_divergence_sample.is_none(),
+                });
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => {}
+            Err(e) if plugin.has_crashed() => {
+                log::error!("Plugin crashed during block-size audit: {}", e);
+                crashed = true;
+            }
+            Err(e) => return Err(e),
+        }
+
+        plugin.stop_processing();
+
+        let passed = !crashed && cases.iter().all(|c| c.passed);
+        log::info!(
+            "Block-size audit finished: {} cases, {}",
+            cases.len(),
+            if passed { "passed" } else { "failed" }
+        );
+
+        Ok(BlockSizeAuditReport {
+            plugin_path: bundle.to_string_lossy().to_string(),
+            reference_block_size: REFERENCE_BLOCK_SIZE,
+            deterministic,
+            suggestion: cases.iter().any(|c| !c.passed).then(|| SUGGESTION.to_string()),
+            cases,
+            crashed,
+            passed,
+        })
+    })
+    .await
+}
+
+/// Check that a plugin's output doesn't depend on the host's block size
+///
+/// Compares each `BlockPattern` against the 512-sample reference render. A
+/// plugin whose reference renders differ from run to run is reported as
+/// inconclusive instead. Audits the named project's build, else the previewed plugin.
+#[tauri::command]
+pub async fn run_block_size_audit(project_name: Option<String>, version: Option<u32>) -> Result<BlockSizeAuditReport, String> {
+    let bundle = resolve_plugin_bundle(project_name.clone(), version)?;
+
+    let report = block_size_audit_bundle(bundle).await?;
+
+    if let Some(project_name) = project_name {
+        let found = report
+            .cases
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| ValidatorIssue {
+                key: c.pattern.label().to_string(),
+                title: format!("Output changes with {}", c.pattern.label()),
+                details: Some(format!(
+                    "Differs from the {}-sample reference by up to {:.1} dB, first at {:.3} s. {}",
+                    REFERENCE_BLOCK_SIZE,
+                    c.peak_difference_db,
+                    c.first_divergence_secs.unwrap_or(0.0),
+                    SUGGESTION
+                )),
+            })
+            .collect();
+        sync_validator_issues(&project_name, "block_size_audit", found);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_schedule() {
+        let schedule = block_schedule(BlockPattern::Prime.sizes(), 10_000);
+        assert_eq!(schedule.iter().sum::<usize>(), 10_000);
+        assert_eq!(&schedule[..6], &[7, 131, 509, 1021, 2053, 7]);
+        assert!(schedule.iter().all(|&s| s > 0 && s <= MAX_BLOCK_SIZE));
+
+        assert_eq!(block_schedule(BlockPattern::SingleSample.sizes(), 5), vec![1; 5]);
+        assert!(block_schedule(&[512], 0).is_empty());
+    }
+
+    #[test]
+    fn test_divergence() {
+        let reference: Vec<StereoSample> = (0..100).map(|i| StereoSample::new(i as f32 * 0.01, 0.0)).collect();
+
+        // Round-off is tolerated
+        let nudged: Vec<StereoSample> = reference.iter().map(|s| StereoSample::new(s.left + 1e-7, s.right)).collect();
+        assert_eq!(divergence(&reference, &nudged).1, None);
+
+        // A state reset at sample 40 is found
+        let mut reset = reference.clone();
+        reset[40].right = 0.5;
+        let (peak_db, first) = divergence(&reference, &reset);
+        assert_eq!(first, Some(40));
+        assert!((peak_db - 20.0 * 0.5f32.log10()).abs() < 0.01);
+
+        let mut nan = reference.clone();
+        nan[7].left = f32::NAN;
+        assert_eq!(divergence(&reference, &nan).1, Some(7));
+    }
+}
@@ -1,10 +1,17 @@
-use serde::Serialize;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::Emitter;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
+use super::activity::ActivityKind;
+use super::artifact_manifest::write_manifest;
+use super::build_report::{measure_artifacts, observe_cargo_line, write_build_report, BuildCacheStats, BuildTimer};
 use super::projects::{ensure_workspace, get_output_path, get_workspace_path};
+use super::release_optimization::optimize_bundles;
+use super::size_report::{crate_breakdown, write_size_report};
 
 #[derive(Serialize, Clone)]
 pub struct BuildResult {
@@ -29,8 +36,17 @@ pub enum BuildStreamEvent {
     Error { message: String },
 }
 
+/// Where the most recent build's full output is kept (for diagnostics)
+pub(crate) fn get_last_build_log_path(project_path: &std::path::Path) -> std::path::PathBuf {
+    project_path.join(".vstworkshop").join("last-build.log")
+}
+
+/// Held for the whole of every bundle build: all projects share the workspace's
+/// target dir and xtask, so two cargo runs would block on (or clobber) each other
+pub(crate) static BUILD_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
 /// Convert project name to Cargo package name (snake_case)
-fn to_package_name(name: &str) -> String {
+pub(crate) fn to_package_name(name: &str) -> String {
     name.replace('-', "_")
 }
 
@@ -41,22 +57,162 @@ pub async fn build_project(
     version: u32,
     window: tauri::Window,
 ) -> Result<BuildResult, String> {
+    run_build(project_name, version, |event| {
+        let _ = window.emit("build-stream", event);
+    })
+    .await
+}
+
+/// Cargo profile for a bundle build
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum BuildProfile {
+    Release,
+    /// Unoptimized with full debug info, for attaching a debugger in the preview
+    Debug,
+    /// Debug build instrumented with AddressSanitizer (nightly toolchain)
+    Sanitized,
+}
+
+/// Build and bundle a project into output/{project}/v{version}/, reporting
+/// progress through `emit` (the app streams it to the window, the CLI prints it)
+pub(crate) async fn run_build(
+    project_name: String,
+    version: u32,
+    emit: impl Fn(BuildStreamEvent),
+) -> Result<BuildResult, String> {
+    run_bundle(project_name, version, BuildProfile::Release, emit).await
+}
+
+/// Debug builds go to output/{project}/debug/ so they never replace a release build
+pub(crate) fn get_debug_output_path(project_name: &str) -> std::path::PathBuf {
+    get_output_path().join(project_name).join("debug")
+}
+
+/// Sanitized builds go to output/{project}/sanitize/
+pub(crate) fn get_sanitized_output_path(project_name: &str) -> std::path::PathBuf {
+    get_output_path().join(project_name).join("sanitize")
+}
+
+/// First .clap bundle in a build output folder
+pub(crate) fn find_clap_in(dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    std::fs::read_dir(dir).ok().and_then(|entries| {
+        entries
+            .flatten()
+            .map(|e| e.path())
+            .find(|p| p.extension().map(|e| e == "clap").unwrap_or(false))
+    })
+}
+
+/// Line tables in a separate dSYM/PDB for release builds
+const RELEASE_DEBUG_INFO: [(&str, &str); 2] = [
+    ("CARGO_PROFILE_RELEASE_DEBUG", "line-tables-only"),
+    ("CARGO_PROFILE_RELEASE_SPLIT_DEBUGINFO", "packed"),
+];
+
+/// Build the workspace's xtask (without any profile overrides), returning its binary
+/// Uses the same debug info settings as `cargo xtask` so neither rebuilds the other's xtask.
+async fn build_xtask(workspace_path: &std::path::Path) -> Result<std::path::PathBuf, String> {
+    let output = Command::new("cargo")
+        .current_dir(workspace_path)
+        .args(["build", "--package", "xtask", "--release"])
+        .env("PATH", super::get_extended_path())
+        .envs(RELEASE_DEBUG_INFO)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to spawn cargo: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to build xtask: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(workspace_path
+        .join("target/release")
+        .join(format!("xtask{}", std::env::consts::EXE_SUFFIX)))
+}
+
+pub(crate) async fn run_bundle(
+    project_name: String,
+    version: u32,
+    profile: BuildProfile,
+    emit: impl Fn(BuildStreamEvent),
+) -> Result<BuildResult, String> {
+    let _build = BUILD_LOCK.lock().await;
+
     // Ensure workspace structure exists (creates shared xtask if needed)
     ensure_workspace()?;
 
     let workspace_path = get_workspace_path();
     let base_output_path = get_output_path();
 
-    // Create versioned output folder: output/{project_name}/v{version}/
-    let output_path = base_output_path
-        .join(&project_name)
-        .join(format!("v{}", version));
+    // Create the output folder: output/{project_name}/v{version}/ (or debug/, sanitize/)
+    let output_path = match profile {
+        BuildProfile::Release => base_output_path
+            .join(&project_name)
+            .join(format!("v{}", version)),
+        BuildProfile::Debug => get_debug_output_path(&project_name),
+        BuildProfile::Sanitized => get_sanitized_output_path(&project_name),
+    };
+    // Sanitized builds need an explicit target (so build scripts aren't
+    // instrumented) and their own target dir (so flags don't churn the cache)
+    let sanitizer_target = match profile {
+        BuildProfile::Sanitized => Some(super::sanitizer::nightly_host_triple()?),
+        _ => None,
+    };
+    let target_dir = match profile {
+        BuildProfile::Sanitized => workspace_path.join("target/sanitize"),
+        _ => workspace_path.join("target"),
+    };
+    // Build for the CPU even when the default toolchain is x86_64 on Apple Silicon
+    let arch_target = match profile {
+        BuildProfile::Sanitized => None,
+        _ => super::architecture::build_target(),
+    };
+    // Cargo puts a --target build's output under {target dir}/{triple}/
+    let profile_dir = match sanitizer_target.as_ref().or(arch_target.as_ref()) {
+        Some(target) => target_dir.join(target),
+        None => target_dir.clone(),
+    }
+    .join(if profile == BuildProfile::Release { "release" } else { "debug" });
 
     std::fs::create_dir_all(&output_path)
         .map_err(|e| format!("Failed to create versioned output directory: {}", e))?;
 
     // Emit start event
-    let _ = window.emit("build-stream", BuildStreamEvent::Start);
+    emit(BuildStreamEvent::Start);
+    let mut timer = BuildTimer::start();
+    let mut cache = BuildCacheStats {
+        clean_build: !profile_dir.exists(),
+        ..Default::default()
+    };
+
+    if let Some(target) = &arch_target {
+        emit(BuildStreamEvent::Output {
+            line: format!("Default Rust toolchain doesn't match this Mac's CPU; building for {}", target),
+        });
+        super::architecture::ensure_target_installed(target)?;
+    }
+
+    // Compile the project's presets/ folder into src/factory_presets.rs
+    let project_path = workspace_path.join("projects").join(&project_name);
+    match super::presets::generate_factory_presets(&project_path) {
+        Ok(0) => {}
+        Ok(count) => {
+            emit(BuildStreamEvent::Output {
+                line: format!("Embedded {} factory preset(s)", count),
+            });
+        }
+        Err(e) => {
+            emit(BuildStreamEvent::Output {
+                line: format!("Warning: failed to embed factory presets: {}", e),
+            });
+        }
+    }
+
+    timer.lap("presets");
+
+    let optimization = super::release_optimization::load_optimization(&project_path);
 
     // Convert project name to Cargo package name (hyphens -> underscores)
     let package_name = to_package_name(&project_name);
@@ -68,11 +224,57 @@ pub async fn build_project(
         .unwrap_or_else(|_| "0".to_string());
 
     // Run cargo xtask bundle from workspace root
-    let mut child = Command::new("cargo")
+    let mut args = vec!["xtask", "bundle", package_name.as_str()];
+    match (profile, &sanitizer_target) {
+        (BuildProfile::Release, _) => args.push("--release"),
+        (BuildProfile::Sanitized, Some(target)) => {
+            args.insert(0, "+nightly");
+            args.extend(["--target", target.as_str()]);
+        }
+        _ => {}
+    }
+    if let Some(target) = &arch_target {
+        args.extend(["--target", target.as_str()]);
+    }
+    // The xtask alias is `cargo run --release`, so CARGO_PROFILE_RELEASE_* overrides
+    // would also apply to (and rebuild) the xtask itself. With overrides, build the
+    // xtask without them and run its binary, so they only reach the plugin build.
+    let overrides = match profile {
+        BuildProfile::Release => optimization.cargo_env(),
+        _ => Vec::new(),
+    };
+    let mut command = if overrides.is_empty() {
+        let mut command = Command::new("cargo");
+        command.args(&args);
+        command
+    } else {
+        let mut command = Command::new(build_xtask(&workspace_path).await?);
+        command
+            .args(&args[1..])
+            // nih_plug_xtask finds the workspace from the variable `cargo run` sets
+            .env("CARGO_MANIFEST_DIR", workspace_path.join("xtask"));
+        command
+    };
+    command
         .current_dir(&workspace_path)
-        .args(["xtask", "bundle", &package_name, "--release"])
         .env("PATH", super::get_extended_path())
         .env("WRY_BUILD_SUFFIX", &build_suffix)
+        // Dropping a cancelled build's future stops cargo too
+        .kill_on_drop(true);
+    if profile == BuildProfile::Release {
+        // Line tables in a separate dSYM/PDB, so plugin crashes can be symbolicated
+        // without shipping debug info inside the bundle
+        command.envs(RELEASE_DEBUG_INFO).envs(overrides);
+    }
+    if let Some(target) = &sanitizer_target {
+        command
+            .env(
+                super::sanitizer::target_rustflags_var(target),
+                super::sanitizer::SANITIZER_RUSTFLAGS,
+            )
+            .env("CARGO_TARGET_DIR", &target_dir);
+    }
+    let mut child = command
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -92,6 +294,10 @@ pub async fn build_project(
     let mut stderr_reader = BufReader::new(stderr).lines();
 
     let mut error_output = String::new();
+    // Everything the build printed, saved for diagnostics once it finishes
+    let mut build_log = String::new();
+    // When cargo last reported "Finished" - the rest is nih-plug's bundling
+    let mut compiled_at = None;
 
     // Read stdout and stderr concurrently
     loop {
@@ -99,13 +305,15 @@ pub async fn build_project(
             line = stdout_reader.next_line() => {
                 match line {
                     Ok(Some(text)) => {
-                        let _ = window.emit("build-stream", BuildStreamEvent::Output {
+                        build_log.push_str(&text);
+                        build_log.push('\n');
+                        emit(BuildStreamEvent::Output {
                             line: text,
                         });
                     }
                     Ok(None) => break,
                     Err(e) => {
-                        let _ = window.emit("build-stream", BuildStreamEvent::Error {
+                        emit(BuildStreamEvent::Error {
                             message: e.to_string(),
                         });
                         break;
@@ -115,10 +323,15 @@ pub async fn build_project(
             line = stderr_reader.next_line() => {
                 match line {
                     Ok(Some(text)) => {
+                        if observe_cargo_line(&mut cache, &text) {
+                            compiled_at = Some(std::time::Instant::now());
+                        }
                         error_output.push_str(&text);
                         error_output.push('\n');
+                        build_log.push_str(&text);
+                        build_log.push('\n');
                         // Emit stderr as output too (cargo outputs to stderr)
-                        let _ = window.emit("build-stream", BuildStreamEvent::Output {
+                        emit(BuildStreamEvent::Output {
                             line: text,
                         });
                     }
@@ -135,9 +348,29 @@ pub async fn build_project(
         .await
         .map_err(|e| format!("Failed to wait for cargo: {}", e))?;
 
+    let _ = std::fs::write(get_last_build_log_path(&project_path), &build_log);
+
+    super::activity::record_activity(
+        &project_path,
+        ActivityKind::Build,
+        match (status.success(), profile) {
+            (true, BuildProfile::Release) => format!("Built v{}", version),
+            (true, BuildProfile::Debug) => format!("Built v{} (debug)", version),
+            (true, BuildProfile::Sanitized) => format!("Built v{} (sanitized)", version),
+            (false, _) => format!("Build of v{} failed", version),
+        },
+        status.success(),
+        Some(serde_json::json!({ "version": version })),
+    );
+
     if status.success() {
+        if let Some(at) = compiled_at {
+            timer.lap_at("compile", at);
+        }
+        timer.lap("bundle");
+
         // Copy artifacts to output folder
-        let bundled_path = workspace_path.join("target/bundled");
+        let bundled_path = target_dir.join("bundled");
 
         // Look for .vst3 and .clap bundles
         let mut copied_files = Vec::new();
@@ -171,6 +404,56 @@ pub async fn build_project(
             }
         }
 
+        // Before the size step (which may re-sign the bundles) and the manifest
+        if profile == BuildProfile::Release {
+            match super::update_manifest::embed_update_info(&output_path, &project_name, version) {
+                Ok(0) => {}
+                Ok(count) => log::info!("Embedded update info in {} bundle(s)", count),
+                Err(e) => emit(BuildStreamEvent::Output {
+                    line: format!("Warning: failed to embed update info: {}", e),
+                }),
+            }
+        }
+
+        if profile == BuildProfile::Release {
+            if let Some(symbols) = copy_debug_symbols(&profile_dir, &package_name, &output_path) {
+                log::info!("Kept debug symbols at {}", symbols.display());
+            }
+        }
+
+        // Size breakdown, then stripping/compression (before the manifest records the files)
+        if profile == BuildProfile::Release {
+            let sizes_path = output_path.clone();
+            let options = optimization.clone();
+            let sizes = tokio::task::spawn_blocking(move || {
+                // The breakdown needs the symbols stripping removes
+                let breakdown = crate_breakdown(&sizes_path);
+                let optimized = options.post_build().then(|| optimize_bundles(&sizes_path, &options));
+                write_size_report(&sizes_path, version, breakdown, optimized)
+            });
+            match sizes.await {
+                Ok(Ok(report)) => {
+                    if let Some(e) = &report.breakdown_error {
+                        log::info!("No size breakdown for {} v{}: {}", project_name, version, e);
+                    }
+                    if let Some(summary) = report.optimization {
+                        for step in summary.steps {
+                            emit(BuildStreamEvent::Output { line: step });
+                        }
+                        emit(BuildStreamEvent::Output {
+                            line: format!(
+                                "Plugin binaries: {:.1} MB -> {:.1} MB",
+                                summary.bytes_before as f64 / 1_048_576.0,
+                                summary.bytes_after as f64 / 1_048_576.0
+                            ),
+                        });
+                    }
+                }
+                Ok(Err(e)) => log::warn!("{}", e),
+                Err(e) => log::warn!("Size report task failed: {}", e),
+            }
+        }
+
         // Clear macOS quarantine attributes to avoid Gatekeeper issues
         #[cfg(target_os = "macos")]
         for artifact_path in &copied_files {
@@ -179,9 +462,34 @@ pub async fn build_project(
                 .output();
         }
 
+        if profile == BuildProfile::Release {
+            let git_commit = super::git::get_head_commit(&project_path.to_string_lossy()).await.ok();
+            if let Err(e) = write_manifest(&output_path, &project_name, version, git_commit) {
+                emit(BuildStreamEvent::Output {
+                    line: format!("Warning: failed to write artifact manifest: {}", e),
+                });
+            }
+        }
+        timer.lap("copy");
+
+        if profile == BuildProfile::Release {
+            document_parameters(&project_path, version, &output_path, &emit).await;
+            timer.lap("docs");
+        }
+
+        let profile_name = match profile {
+            BuildProfile::Release => "release",
+            BuildProfile::Debug => "debug",
+            BuildProfile::Sanitized => "sanitized",
+        };
+        let report = timer.finish(version, profile_name, cache, measure_artifacts(&output_path));
+        if let Err(e) = write_build_report(&output_path, &report) {
+            log::warn!("{}", e);
+        }
+
         let output_str = output_path.to_string_lossy().to_string();
 
-        let _ = window.emit("build-stream", BuildStreamEvent::Done {
+        emit(BuildStreamEvent::Done {
             success: true,
             output_path: Some(output_str.clone()),
         });
@@ -192,7 +500,7 @@ pub async fn build_project(
             error: None,
         })
     } else {
-        let _ = window.emit("build-stream", BuildStreamEvent::Done {
+        emit(BuildStreamEvent::Done {
             success: false,
             output_path: None,
         });
@@ -205,6 +513,250 @@ pub async fn build_project(
     }
 }
 
+/// Regenerate docs/PARAMETERS.md from the new build; failures only cost the docs
+async fn document_parameters(
+    project_path: &std::path::Path,
+    version: u32,
+    output_path: &std::path::Path,
+    emit: &impl Fn(BuildStreamEvent),
+) {
+    let Some(clap) = find_clap_in(output_path) else { return };
+    let project_path = project_path.to_path_buf();
+    let result = match tokio::task::spawn_blocking(move || {
+        super::param_docs::write_parameter_docs(&project_path, version, &clap)
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => Err(format!("Task join error: {}", e)),
+    };
+    match result {
+        Ok(diff) => {
+            let mut line = "Updated docs/PARAMETERS.md".to_string();
+            if !diff.added.is_empty() {
+                let names: Vec<&str> = diff.added.iter().map(|p| p.name.as_str()).collect();
+                line.push_str(&format!(" - new parameters: {}", names.join(", ")));
+            }
+            emit(BuildStreamEvent::Output { line });
+        }
+        Err(e) => log::warn!("Failed to document parameters: {}", e),
+    }
+}
+
+/// Folder in a release output folder where the version's debug symbols are archived
+pub(crate) const SYMBOLS_DIR: &str = "symbols";
+
+/// Symbol file archived for a package: `{package}.dSYM` (macOS), `{package}.pdb`
+/// (Windows), or the unstripped `lib{package}.so` (Linux)
+pub(crate) fn symbols_file_name(package_name: &str) -> String {
+    if cfg!(target_os = "macos") {
+        format!("{}.dSYM", package_name)
+    } else if cfg!(windows) {
+        format!("{}.pdb", package_name)
+    } else {
+        format!("lib{}.so", package_name)
+    }
+}
+
+/// Archive the build's debug symbols (dSYM on macOS, PDB on Windows, the
+/// unstripped library on Linux) in `{output}/symbols/`, for crash symbolication
+fn copy_debug_symbols(
+    target: &std::path::Path,
+    package_name: &str,
+    output_path: &std::path::Path,
+) -> Option<std::path::PathBuf> {
+    let source = if cfg!(target_os = "macos") {
+        target.join(format!("lib{}.dylib.dSYM", package_name))
+    } else if cfg!(windows) {
+        target.join(format!("{}.pdb", package_name))
+    } else {
+        target.join(format!("lib{}.so", package_name))
+    };
+    if !source.exists() {
+        return None;
+    }
+
+    let symbols_dir = output_path.join(SYMBOLS_DIR);
+    let dest = symbols_dir.join(symbols_file_name(package_name));
+    if dest.is_dir() {
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+    let copied = std::fs::create_dir_all(&symbols_dir).is_ok()
+        && if source.is_dir() {
+            copy_dir_all(&source, &dest).is_ok()
+        } else {
+            std::fs::copy(&source, &dest).is_ok()
+        };
+    copied.then_some(dest)
+}
+
+/// Set by cancel_build_all; checked between projects
+static BUILD_ALL_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// Compiler error lines kept per project in the batch report
+const MAX_ERROR_LINES: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectBuildSummary {
+    pub project: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    /// First compiler errors when the build failed
+    pub errors: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BuildAllReport {
+    pub started_at: String,
+    pub finished_at: String,
+    pub cancelled: bool,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub projects: Vec<ProjectBuildSummary>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct BuildAllProgress {
+    pub index: usize,
+    pub total: usize,
+    pub project: String,
+    /// "building", "succeeded", or "failed"
+    pub status: String,
+}
+
+fn get_build_all_report_path() -> std::path::PathBuf {
+    get_workspace_path().join("last-build-all.json")
+}
+
+/// Pull the `error...` lines (and their location line) out of cargo's stderr
+pub(crate) fn extract_compiler_errors(stderr: &str) -> Vec<String> {
+    let lines: Vec<&str> = stderr.lines().collect();
+    let mut errors = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("error") {
+            match lines.get(i + 1).map(|l| l.trim_start()) {
+                Some(location) if location.starts_with("-->") => {
+                    errors.push(format!("{} ({})", line, location.trim_start_matches("-->").trim()))
+                }
+                _ => errors.push(line.to_string()),
+            }
+            if errors.len() >= MAX_ERROR_LINES {
+                break;
+            }
+        }
+    }
+    errors
+}
+
+/// Compile every project in the workspace, one at a time
+///
+/// All projects share the workspace target directory, so dependencies are only
+/// compiled once. This checks that each project still builds (e.g. after a Rust
+/// or nih-plug upgrade) - it doesn't bundle or copy anything to the output folder.
+/// Emits `build-all-progress` per project; the report is also saved for later.
+#[tauri::command]
+pub async fn build_all_projects(app_handle: tauri::AppHandle) -> Result<BuildAllReport, String> {
+    ensure_workspace()?;
+    BUILD_ALL_CANCELLED.store(false, Ordering::SeqCst);
+
+    let workspace_path = get_workspace_path();
+    let mut project_names: Vec<String> = std::fs::read_dir(workspace_path.join("projects"))
+        .map_err(|e| format!("Failed to read projects dir: {}", e))?
+        .flatten()
+        .filter(|e| e.path().join("Cargo.toml").exists())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    project_names.sort();
+
+    let started_at = chrono::Local::now().to_rfc3339();
+    let total = project_names.len();
+    let mut projects = Vec::with_capacity(total);
+    let mut cancelled = false;
+
+    for (index, project) in project_names.into_iter().enumerate() {
+        if BUILD_ALL_CANCELLED.load(Ordering::SeqCst) {
+            cancelled = true;
+            break;
+        }
+
+        let progress = |status: &str| BuildAllProgress {
+            index,
+            total,
+            project: project.clone(),
+            status: status.to_string(),
+        };
+        let _ = app_handle.emit("build-all-progress", progress("building"));
+
+        let started = std::time::Instant::now();
+        let output = Command::new("cargo")
+            .current_dir(&workspace_path)
+            .args(["build", "--release", "-p", &to_package_name(&project)])
+            .env("PATH", super::get_extended_path())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .await;
+
+        let (success, errors) = match output {
+            Ok(output) if output.status.success() => (true, Vec::new()),
+            Ok(output) => (false, extract_compiler_errors(&String::from_utf8_lossy(&output.stderr))),
+            Err(e) => (false, vec![format!("Failed to run cargo: {}", e)]),
+        };
+        let _ = app_handle.emit(
+            "build-all-progress",
+            progress(if success { "succeeded" } else { "failed" }),
+        );
+
+        projects.push(ProjectBuildSummary {
+            project,
+            success,
+            duration_ms: started.elapsed().as_millis() as u64,
+            errors,
+        });
+    }
+
+    let succeeded = projects.iter().filter(|p| p.success).count();
+    let report = BuildAllReport {
+        started_at,
+        finished_at: chrono::Local::now().to_rfc3339(),
+        cancelled,
+        succeeded,
+        failed: projects.len() - succeeded,
+        projects,
+    };
+
+    if let Ok(json) = serde_json::to_string_pretty(&report) {
+        let _ = std::fs::write(get_build_all_report_path(), json);
+    }
+    log::info!(
+        "Build all: {} succeeded, {} failed{}",
+        report.succeeded,
+        report.failed,
+        if cancelled { " (cancelled)" } else { "" }
+    );
+    Ok(report)
+}
+
+/// Stop build_all_projects after the project currently building
+#[tauri::command]
+pub async fn cancel_build_all() {
+    BUILD_ALL_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// Get the report from the last build_all_projects run
+#[tauri::command]
+pub async fn get_last_build_all_report() -> Result<Option<BuildAllReport>, String> {
+    let path = get_build_all_report_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read build report: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse build report: {}", e))
+}
+
 /// Recursively copy a directory
 fn copy_dir_all(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
     std::fs::create_dir_all(dst)?;
@@ -234,3 +786,24 @@ pub async fn open_output_folder() -> Result<(), String> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_compiler_errors() {
+        let stderr = "   Compiling demo v0.1.0\n\
+error[E0425]: cannot find value `gain` in this scope\n\
+  --> projects/demo/src/lib.rs:42:9\n\
+   |\n\
+error: could not compile `demo` (lib) due to 1 previous error\n";
+        assert_eq!(
+            extract_compiler_errors(stderr),
+            vec![
+                "error[E0425]: cannot find value `gain` in this scope (projects/demo/src/lib.rs:42:9)",
+                "error: could not compile `demo` (lib) due to 1 previous error",
+            ]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Build reports
+//!
+//! Each successful build writes `build-report.json` next to its bundles in
+//! `output/{project}/v{version}/`: how long each step took, how much cargo had
+//! to rebuild, and how big the artifacts came out. The reports of all versions
+//! make up the project's build history, for spotting builds getting slower.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::projects::get_output_path;
+
+const REPORT_FILE: &str = "build-report.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BuildStep {
+    /// "presets", "compile", "bundle", "copy", "docs"
+    pub name: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BuildCacheStats {
+    /// Crates cargo compiled (the rest came from the target cache)
+    pub compiled_crates: u32,
+    /// Crates that had to be downloaded first
+    pub downloaded_crates: u32,
+    /// No build output for this profile existed beforehand
+    pub clean_build: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BuildArtifact {
+    pub name: String,
+    pub bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BuildReport {
+    pub version: u32,
+    pub profile: String,
+    pub built_at: String,
+    pub total_ms: u64,
+    pub steps: Vec<BuildStep>,
+    pub cache: BuildCacheStats,
+    pub artifacts: Vec<BuildArtifact>,
+}
+
+/// Times the steps of a build as it goes
+pub(crate) struct BuildTimer {
+    started: Instant,
+    step_started: Instant,
+    steps: Vec<BuildStep>,
+}
+
+impl BuildTimer {
+    pub(crate) fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            step_started: Instant::now(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Finish the current step under `name` and start the next one
+    pub(crate) fn lap(&mut self, name: &str) {
+        self.lap_at(name, Instant::now());
+    }
+
+    /// Like `lap`, for a step that ended at `at`
+    pub(crate) fn lap_at(&mut self, name: &str, at: Instant) {
+        self.steps.push(BuildStep {
+            name: name.to_string(),
+            duration_ms: at.saturating_duration_since(self.step_started).as_millis() as u64,
+        });
+        self.step_started = at;
+    }
+
+    pub(crate) fn finish(self, version: u32, profile: &str, cache: BuildCacheStats, artifacts: Vec<BuildArtifact>) -> BuildReport {
+        BuildReport {
+            version,
+            profile: profile.to_string(),
+            built_at: chrono::Utc::now().to_rfc3339(),
+            total_ms: self.started.elapsed().as_millis() as u64,
+            steps: self.steps,
+            cache,
+            artifacts,
+        }
+    }
+}
+
+/// Update the cache stats from a line of cargo output
+/// Returns true for "Finished" lines (xtask's own build prints one before the plugin's).
+pub(crate) fn observe_cargo_line(cache: &mut BuildCacheStats, line: &str) -> bool {
+    match line.split_whitespace().next() {
+        Some("Compiling") => cache.compiled_crates += 1,
+        Some("Downloaded") => cache.downloaded_crates += 1,
+        Some("Finished") => return true,
+        _ => {}
+    }
+    false
+}
+
+fn path_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Sizes of the bundles and symbol files in an output folder
+pub(crate) fn measure_artifacts(output_path: &Path) -> Vec<BuildArtifact> {
+    let mut artifacts: Vec<BuildArtifact> = fs::read_dir(output_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        // Skip this report and the artifact manifest
+        .filter(|e| !e.file_name().to_string_lossy().ends_with(".json"))
+        .map(|e| BuildArtifact {
+            name: e.file_name().to_string_lossy().to_string(),
+            bytes: path_size(&e.path()),
+        })
+        .collect();
+    artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+    artifacts
+}
+
+pub(crate) fn write_build_report(output_path: &Path, report: &BuildReport) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize build report: {}", e))?;
+    fs::write(output_path.join(REPORT_FILE), json).map_err(|e| format!("Failed to save build report: {}", e))
+}
+
+pub(crate) fn load_history(project_output: &Path) -> Vec<BuildReport> {
+    let versions: Vec<PathBuf> = fs::read_dir(project_output)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with('v'))
+        .map(|e| e.path().join(REPORT_FILE))
+        .collect();
+    let mut reports: Vec<BuildReport> = versions
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|c| serde_json::from_str(&c).ok())
+        .collect();
+    reports.sort_by_key(|r| r.version);
+    reports
+}
+
+/// Build reports of every version of a project, oldest first
+#[tauri::command]
+pub async fn get_build_history(project_name: String) -> Result<Vec<BuildReport>, String> {
+    Ok(load_history(&get_output_path().join(&project_name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe_cargo_line() {
+        let mut cache = BuildCacheStats::default();
+        let lines = [
+            "  Downloaded realfft v3.3.0",
+            "   Compiling realfft v3.3.0",
+            "   Compiling my_plugin v0.1.0 (/ws/projects/my_plugin)",
+            "warning: unused variable: `x`",
+            "    Finished `release` profile [optimized] target(s) in 41.20s",
+        ];
+        let finished: Vec<bool> = lines.iter().map(|l| observe_cargo_line(&mut cache, l)).collect();
+        assert_eq!(finished, vec![false, false, false, false, true]);
+        assert_eq!(cache.compiled_crates, 2);
+        assert_eq!(cache.downloaded_crates, 1);
+    }
+}
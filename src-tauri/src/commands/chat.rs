@@ -194,6 +194,19 @@ pub async fn get_current_version(project_path: String) -> Result<u32, String> {
     Ok(max_version)
 }
 
+/// Version to build or load for a project: the one given, or its current version
+/// Fresh projects are version 0 but build to v1.
+pub(crate) async fn resolve_project_version(project_name: &str, version: Option<u32>) -> Result<u32, String> {
+    let project_path = super::projects::get_workspace_path().join("projects").join(project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+    match version {
+        Some(v) => Ok(v),
+        None => Ok(get_current_version(project_path.to_string_lossy().to_string()).await?.max(1)),
+    }
+}
+
 /// Set the active version and checkout that commit
 #[tauri::command]
 pub async fn set_active_version(
@@ -1,38 +1,61 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::process::Stdio;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::Emitter;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use tokio::time::timeout;
 
-// Track active Claude processes by project path so we can interrupt them
-static ACTIVE_PROCESSES: Mutex<Option<HashMap<String, u32>>> = Mutex::new(None);
+/// A running Claude process: the project's interactive chat (no task) or a queued task
+type ProcessKey = (String, Option<u64>);
 
-fn register_process(project_path: &str, pid: u32) {
+// Track active Claude processes so we can interrupt them
+static ACTIVE_PROCESSES: Mutex<Option<HashMap<ProcessKey, u32>>> = Mutex::new(None);
+
+/// One Claude session at a time per project: chat messages and queued tasks
+/// share the session ID and commit history, so they must not interleave
+static PROJECT_SESSIONS: Lazy<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn process_key(project_path: &str, task_id: Option<u64>) -> ProcessKey {
+    (project_path.to_string(), task_id)
+}
+
+fn register_process(key: &ProcessKey, pid: u32) {
     let mut guard = ACTIVE_PROCESSES.lock().unwrap();
     if guard.is_none() {
         *guard = Some(HashMap::new());
     }
     if let Some(ref mut map) = *guard {
-        map.insert(project_path.to_string(), pid);
+        map.insert(key.clone(), pid);
     }
 }
 
-fn unregister_process(project_path: &str) {
+fn unregister_process(key: &ProcessKey) {
     let mut guard = ACTIVE_PROCESSES.lock().unwrap();
     if let Some(ref mut map) = *guard {
-        map.remove(project_path);
+        map.remove(key);
     }
 }
 
-fn get_process_pid(project_path: &str) -> Option<u32> {
+fn get_process_pid(key: &ProcessKey) -> Option<u32> {
     let guard = ACTIVE_PROCESSES.lock().unwrap();
-    guard.as_ref().and_then(|map| map.get(project_path).copied())
+    guard.as_ref().and_then(|map| map.get(key).copied())
+}
+
+/// Lock held while a Claude session runs for a project
+pub(crate) fn project_session_lock(project_path: &str) -> Arc<tokio::sync::Mutex<()>> {
+    PROJECT_SESSIONS
+        .lock()
+        .unwrap()
+        .entry(project_path.to_string())
+        .or_default()
+        .clone()
 }
 
 #[derive(Serialize, Clone)]
@@ -472,6 +495,8 @@ fn load_project_metadata(project_path: &str) -> Option<super::projects::ProjectM
     serde_json::from_str(&content).ok()
 }
 
+/// Send a message to the project's Claude session
+/// Waits for a queued task that's running on the same project to finish first.
 #[tauri::command]
 pub async fn send_to_claude(
     project_path: String,
@@ -481,8 +506,43 @@ pub async fn send_to_claude(
     model: Option<String>,
     custom_instructions: Option<String>,
     agent_verbosity: Option<String>,
+    include_context: Option<bool>,
     window: tauri::Window,
 ) -> Result<ClaudeResponse, String> {
+    let session = project_session_lock(&project_path);
+    let _session = session.lock().await;
+    run_claude_session(
+        project_path,
+        project_name,
+        description,
+        message,
+        model,
+        custom_instructions,
+        agent_verbosity,
+        include_context,
+        None,
+        window,
+    )
+    .await
+}
+
+/// Run one Claude turn; the caller holds the project's `project_session_lock`.
+/// `task_id` is set for queued tasks so they can be cancelled on their own.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run_claude_session(
+    project_path: String,
+    project_name: String,
+    description: String,
+    message: String,
+    model: Option<String>,
+    custom_instructions: Option<String>,
+    agent_verbosity: Option<String>,
+    include_context: Option<bool>,
+    task_id: Option<u64>,
+    window: tauri::Window,
+) -> Result<ClaudeResponse, String> {
+    let process = process_key(&project_path, task_id);
+
     // Ensure git is initialized for this project (handles existing projects)
     if !super::git::is_git_repo(&project_path) {
         super::git::init_repo(&project_path).await?;
@@ -496,6 +556,10 @@ pub async fn send_to_claude(
         eprintln!("[WARN] Failed to update gitignore: {}", e);
     }
 
+    if let Some(command) = super::library_usage::command_in_message(&message) {
+        super::library_usage::record_command_used(&project_name, &command);
+    }
+
     // Record HEAD commit before Claude runs (to detect if Claude commits changes itself)
     let head_before = super::git::get_head_commit(&project_path).await.ok();
     eprintln!("[DEBUG] HEAD before Claude: {:?}", head_before);
@@ -515,11 +579,21 @@ pub async fn send_to_claude(
     // Get verbosity style (default to balanced)
     let verbosity = agent_verbosity.as_deref().unwrap_or("balanced");
 
+    // Attach live parameter values, preview levels, and build diagnostics (on by default)
+    let prompt = match include_context
+        .unwrap_or(true)
+        .then(|| super::context_snapshot::snapshot_for_project(&project_name))
+        .flatten()
+    {
+        Some(snapshot) => format!("{}\n---\n\n{}", snapshot, message),
+        None => message.clone(),
+    };
+
     // Prepend style hint to message (reinforces on every turn, even resumed sessions)
     let styled_message = match verbosity {
-        "direct" => format!("[Response Style: Direct - minimal questions, implement immediately, 1-3 sentences max]\n\n{}", message),
-        "thorough" => format!("[Response Style: Thorough - ask clarifying questions, explore options before implementing]\n\n{}", message),
-        _ => format!("[Response Style: Balanced - ask 1-2 key questions if needed, then implement]\n\n{}", message),
+        "direct" => format!("[Response Style: Direct - minimal questions, implement immediately, 1-3 sentences max]\n\n{}", prompt),
+        "thorough" => format!("[Response Style: Thorough - ask clarifying questions, explore options before implementing]\n\n{}", prompt),
+        _ => format!("[Response Style: Balanced - ask 1-2 key questions if needed, then implement]\n\n{}", prompt),
     };
 
     // Build args - include --resume if we have an existing session
@@ -542,6 +616,12 @@ pub async fn send_to_claude(
         args.push(m.clone());
     }
 
+    // Route shell commands and edits outside src/ through the app for approval
+    if let Some(settings) = super::permissions::hook_settings() {
+        args.push("--settings".to_string());
+        args.push(settings);
+    }
+
     // Only add system prompt on first message (new session)
     // For resumed sessions, Claude already has the context
     if existing_session.is_none() {
@@ -613,8 +693,17 @@ pub async fn send_to_claude(
 
     // Register process for potential interruption
     if let Some(pid) = child.id() {
-        register_process(&project_path, pid);
+        register_process(&process, pid);
     }
+    // A queued task cancelled before the process was registered had nothing to stop
+    if task_id.is_some_and(super::task_queue::is_task_cancelled) {
+        let _ = child.kill().await;
+        unregister_process(&process);
+        return Err("Cancelled".to_string());
+    }
+
+    // Surface permission requests from the hook until the session ends
+    let _permission_watcher = super::permissions::PermissionWatcher::start(&project_path, window.clone());
 
     // Emit start event
     let _ = window.emit("claude-stream", ClaudeStreamEvent::Start {
@@ -659,7 +748,7 @@ pub async fn send_to_claude(
     // Read stdout and stderr concurrently with timeout protection
     loop {
         // Check if we were interrupted before each read
-        if get_process_pid(&project_path).is_none() {
+        if get_process_pid(&process).is_none() {
             eprintln!("[DEBUG] Process was interrupted - breaking loop");
             was_interrupted_during_loop = true;
             break;
@@ -682,6 +771,10 @@ pub async fn send_to_claude(
                             captured_session_id = Some(sid);
                         }
 
+                        if let Some(skill) = super::library_usage::skill_in_stream_event(&json_line) {
+                            super::library_usage::record_command_used(&project_name, &skill);
+                        }
+
                         // Try to parse as JSON event for display
                         let parsed = parse_claude_event(&json_line);
 
@@ -763,7 +856,7 @@ pub async fn send_to_claude(
                 total_idle_seconds += read_timeout.as_secs();
 
                 // Check if we were interrupted during the timeout
-                if get_process_pid(&project_path).is_none() {
+                if get_process_pid(&process).is_none() {
                     eprintln!("[DEBUG] Process was interrupted during read timeout - breaking loop");
                     was_interrupted_during_loop = true;
                     break;
@@ -827,10 +920,10 @@ pub async fn send_to_claude(
     };
 
     // Check if process was interrupted (either detected in loop, or PID was unregistered)
-    let was_interrupted = was_interrupted_during_loop || get_process_pid(&project_path).is_none();
+    let was_interrupted = was_interrupted_during_loop || get_process_pid(&process).is_none();
 
     // Unregister process now that it's complete (no-op if already unregistered by interrupt)
-    unregister_process(&project_path);
+    unregister_process(&process);
 
     // Handle non-success exit
     if !status.success() {
@@ -978,6 +1071,20 @@ pub async fn send_to_claude(
         }
     };
 
+    super::activity::record_activity(
+        &PathBuf::from(&project_path),
+        super::activity::ActivityKind::Chat,
+        match commit_hash {
+            Some(_) => "Chat session made changes",
+            None => "Chat session (no code changes)",
+        },
+        true,
+        Some(serde_json::json!({
+            "sessionId": captured_session_id,
+            "commitHash": commit_hash,
+        })),
+    );
+
     Ok(ClaudeResponse {
         content: final_content,
         session_id: captured_session_id,
@@ -1003,32 +1110,41 @@ pub async fn test_claude_cli() -> Result<String, String> {
     }
 }
 
-/// Interrupt a running Claude session for a specific project
-#[tauri::command]
-pub async fn interrupt_claude(project_path: String, window: tauri::Window) -> Result<(), String> {
-    if let Some(pid) = get_process_pid(&project_path) {
-        eprintln!("[DEBUG] Interrupting Claude process {} for {}", pid, project_path);
-
-        // Send SIGTERM to the process (graceful termination)
-        #[cfg(unix)]
-        {
-            use std::process::Command as StdCommand;
-            let _ = StdCommand::new("kill")
-                .args(["-TERM", &pid.to_string()])
-                .output();
-        }
+/// Stop the Claude process running for a project's chat (`task_id` None) or
+/// one of its queued tasks. Returns false if no such session was running.
+pub(crate) fn terminate_claude_process(project_path: &str, task_id: Option<u64>) -> bool {
+    let process = process_key(project_path, task_id);
+    let Some(pid) = get_process_pid(&process) else {
+        return false;
+    };
+    eprintln!("[DEBUG] Interrupting Claude process {} for {}", pid, project_path);
+
+    // Send SIGTERM to the process (graceful termination)
+    #[cfg(unix)]
+    {
+        use std::process::Command as StdCommand;
+        let _ = StdCommand::new("kill")
+            .args(["-TERM", &pid.to_string()])
+            .output();
+    }
 
-        #[cfg(windows)]
-        {
-            use std::process::Command as StdCommand;
-            let _ = StdCommand::new("taskkill")
-                .args(["/PID", &pid.to_string(), "/F"])
-                .output();
-        }
+    #[cfg(windows)]
+    {
+        use std::process::Command as StdCommand;
+        let _ = StdCommand::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .output();
+    }
 
-        // Unregister the process
-        unregister_process(&project_path);
+    // Unregister the process
+    unregister_process(&process);
+    true
+}
 
+/// Interrupt a running Claude session for a specific project
+#[tauri::command]
+pub async fn interrupt_claude(project_path: String, window: tauri::Window) -> Result<(), String> {
+    if terminate_claude_process(&project_path, None) {
         // Emit a text event (not error) so the frontend shows a friendly message
         let _ = window.emit("claude-stream", ClaudeStreamEvent::Text {
             project_path: project_path.clone(),
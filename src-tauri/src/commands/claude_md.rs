@@ -20,6 +20,9 @@ pub fn generate_claude_md(
     // Skill manifest - tells Claude what skills are available
     content.push_str(&generate_skill_manifest(template, ui_framework, components));
 
+    // Outstanding work items tracked by the app and validators
+    content.push_str(&generate_issue_tracker());
+
     // Critical safety reminders (brief)
     content.push_str(&generate_critical_safety());
 
@@ -45,6 +48,16 @@ fn generate_header(
         })
         .unwrap_or_else(|| "None".to_string());
 
+    // Headings stay English (the app edits sections by heading); the user's
+    // language is noted so Claude answers them in it
+    let language = super::i18n::current_language();
+    let language_line = match super::i18n::language_name(&language) {
+        Some(name) if language != super::i18n::DEFAULT_LANGUAGE => {
+            format!("- **User Language**: {} - reply to the user in this language\n", name)
+        }
+        _ => String::new(),
+    };
+
     format!(
         r#"# {project_name} - Plugin Development Context
 
@@ -56,7 +69,7 @@ fn generate_header(
 - **Type**: {template}
 - **UI Framework**: {ui_framework}
 - **Components**: {components_str}
-
+{language_line}
 ## Current Implementation
 
 <!-- Update this section as you implement features -->
@@ -87,7 +100,7 @@ Invoke these skills when you need detailed implementation patterns:
 ### Core (Always Available)
 | Skill | Purpose |
 |-------|---------|
-| `/dsp-safety` | Critical DSP safety rules, anti-hallucination guardrails, NaN/Inf protection |
+| `/dsp-safety` | Critical DSP safety rules, anti-hallucination guardrails, NaN/Inf and denormal protection |
 | `/nih-plug-basics` | Framework essentials, parameter setup, process loop, plugin lifecycle |
 
 "#,
@@ -97,17 +110,8 @@ Invoke these skills when you need detailed implementation patterns:
     content.push_str("### UI Framework\n");
     content.push_str("| Skill | Purpose |\n");
     content.push_str("|-------|---------|\n");
-    match ui_framework {
-        "webview" => {
-            content.push_str("| `/webview-ui` | WebView IPC patterns, AtomicBool sync, HTML/JS integration |\n");
-        }
-        "egui" => {
-            content.push_str("| `/egui-ui` | egui widget patterns, ParamSlider, EguiState, layout |\n");
-        }
-        "native" => {
-            content.push_str("| `/native-ui` | Native plugin patterns (DAW controls only), parameter naming |\n");
-        }
-        _ => {}
+    if let Some(row) = ui_framework_manifest_row(ui_framework) {
+        content.push_str(row);
     }
     content.push('\n');
 
@@ -133,18 +137,9 @@ Invoke these skills when you need detailed implementation patterns:
             content.push_str("| Skill | Purpose |\n");
             content.push_str("|-------|---------|\n");
             for component in comps {
-                let (skill_name, description) = match component.as_str() {
-                    "preset_system" => ("preset-system", "Preset save/load, factory presets, user presets"),
-                    "param_smoothing" => ("param-smoothing", "Advanced parameter smoothing techniques"),
-                    "sidechain_input" => ("sidechain-input", "Aux input configuration, sidechain processing"),
-                    "oversampling" => ("oversampling", "Oversampling for nonlinear processing"),
-                    "polyphony" => ("polyphony", "Voice management, allocation, stealing"),
-                    "velocity_layers" => ("velocity-layers", "Velocity layer selection, crossfading"),
-                    "adsr_envelope" => ("adsr-envelope", "ADSR envelope implementation"),
-                    "lfo" => ("lfo", "LFO implementation, tempo sync, modulation"),
-                    _ => continue,
-                };
-                content.push_str(&format!("| `/{}` | {} |\n", skill_name, description));
+                if let Some(row) = component_manifest_row(component) {
+                    content.push_str(&row);
+                }
             }
             content.push('\n');
         }
@@ -153,6 +148,219 @@ Invoke these skills when you need detailed implementation patterns:
     content
 }
 
+fn ui_framework_manifest_row(ui_framework: &str) -> Option<&'static str> {
+    match ui_framework {
+        "webview" => Some("| `/webview-ui` | WebView IPC patterns, AtomicBool sync, HTML/JS integration |\n"),
+        "egui" => Some("| `/egui-ui` | egui widget patterns, ParamSlider, EguiState, layout |\n"),
+        "native" => Some("| `/native-ui` | Native plugin patterns (DAW controls only), parameter naming |\n"),
+        _ => None,
+    }
+}
+
+/// Switch an existing CLAUDE.md to a different UI framework
+/// Rewrites the header line and the UI Framework skill row, leaving the rest untouched.
+pub fn set_ui_framework_in_claude_md(content: &str, ui_framework: &str) -> String {
+    let Some(new_row) = ui_framework_manifest_row(ui_framework) else {
+        return content.to_string();
+    };
+    let old_rows: Vec<&str> = ["webview", "egui", "native"]
+        .iter()
+        .filter_map(|f| ui_framework_manifest_row(f))
+        .map(str::trim_end)
+        .collect();
+
+    let lines: Vec<String> = content
+        .lines()
+        .map(|line| {
+            if line.starts_with("- **UI Framework**:") {
+                format!("- **UI Framework**: {}", ui_framework)
+            } else if old_rows.contains(&line) {
+                new_row.trim_end().to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+/// Skill name and manifest description for a starter component
+fn component_skill_info(component: &str) -> Option<(&'static str, &'static str)> {
+    match component {
+        "preset_system" => Some(("preset-system", "Preset save/load, factory presets, user presets")),
+        "param_smoothing" => Some(("param-smoothing", "Advanced parameter smoothing techniques")),
+        "sidechain_input" => Some(("sidechain-input", "Aux input configuration, sidechain processing")),
+        "oversampling" => Some(("oversampling", "Oversampling for nonlinear processing")),
+        "polyphony" => Some(("polyphony", "Voice management, allocation, stealing")),
+        "velocity_layers" => Some(("velocity-layers", "Velocity layer selection, crossfading")),
+        "adsr_envelope" => Some(("adsr-envelope", "ADSR envelope implementation")),
+        "lfo" => Some(("lfo", "LFO implementation, tempo sync, modulation")),
+        _ => None,
+    }
+}
+
+fn component_manifest_row(component: &str) -> Option<String> {
+    component_skill_info(component)
+        .map(|(skill_name, description)| format!("| `/{}` | {} |\n", skill_name, description))
+}
+
+/// Whether a starter component applies to a plugin type (mirrors the new project picker)
+pub fn component_supports_template(component: &str, template: &str) -> bool {
+    match component {
+        "preset_system" => true,
+        "param_smoothing" | "sidechain_input" | "oversampling" => template == "effect",
+        "polyphony" | "velocity_layers" | "adsr_envelope" | "lfo" => template == "instrument",
+        _ => false,
+    }
+}
+
+/// Add a component to an existing CLAUDE.md without regenerating it
+/// Updates the Components line in the header and the Components skill table, leaving
+/// anything Claude or the user wrote (e.g. Current Implementation) untouched.
+pub fn add_component_to_claude_md(content: &str, component: &str) -> String {
+    let Some(row) = component_manifest_row(component) else {
+        return content.to_string();
+    };
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+
+    // Header: "- **Components**: None" or "- **Components**: a, b"
+    if let Some(line) = lines.iter_mut().find(|l| l.starts_with("- **Components**:")) {
+        let current = line.trim_start_matches("- **Components**:").trim();
+        let already_listed = current.split(',').any(|c| c.trim() == component);
+        if current.is_empty() || current == "None" {
+            *line = format!("- **Components**: {}", component);
+        } else if !already_listed {
+            *line = format!("- **Components**: {}, {}", current, component);
+        }
+    }
+
+    let row = row.trim_end().to_string();
+    if !lines.iter().any(|l| *l == row) {
+        match lines.iter().position(|l| l == "### Components") {
+            Some(section) => {
+                // Append after the last row of the existing table
+                let mut insert_at = section + 1;
+                while insert_at < lines.len() && lines[insert_at].starts_with('|') {
+                    insert_at += 1;
+                }
+                lines.insert(insert_at, row);
+            }
+            None => {
+                // No components yet - add the table at the end of the skill manifest
+                let insert_at = lines
+                    .iter()
+                    .position(|l| l == "## Critical Safety Rules")
+                    .unwrap_or(lines.len());
+                let table = [
+                    "### Components".to_string(),
+                    "| Skill | Purpose |".to_string(),
+                    "|-------|---------|".to_string(),
+                    row,
+                    String::new(),
+                ];
+                lines.splice(insert_at..insert_at, table);
+            }
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+const ISSUE_TRACKER_HEADING: &str = "## Outstanding Issues";
+
+fn generate_issue_tracker() -> String {
+    format!(
+        r#"{ISSUE_TRACKER_HEADING}
+
+Known problems and TODOs live in `.vstworkshop/issues.json` (a JSON array). Validators add
+issues automatically (e.g. "NaN/Inf output at 192 kHz") and resolve them when a later run passes.
+
+- Check the open issues before starting work and fix related ones along the way
+- When you fix an issue, set its `"status"` to `"resolved"` and `"resolved_at"` to the current time
+- Add work you leave unfinished as a new issue: next unused `"id"`, `"title"`, optional `"details"`,
+  `"source": "claude"`, `"status": "open"`, `"created_at"` (RFC 3339)
+
+"#
+    )
+}
+
+/// Add the Outstanding Issues section to a CLAUDE.md written before the issue tracker existed
+pub fn add_issue_tracker_to_claude_md(content: &str) -> String {
+    if content.contains(ISSUE_TRACKER_HEADING) {
+        return content.to_string();
+    }
+    match content.find("## Critical Safety Rules") {
+        Some(pos) => format!("{}{}{}", &content[..pos], generate_issue_tracker(), &content[pos..]),
+        None => {
+            let separator = if content.ends_with("\n\n") || content.is_empty() {
+                ""
+            } else if content.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            format!("{}{}{}", content, separator, generate_issue_tracker())
+        }
+    }
+}
+
+const SCAFFOLD_HEADING: &str = "## Scaffolded DSP Modules";
+
+/// List a module generated by `scaffold_recipe` so Claude knows it exists and still needs finishing
+pub fn add_scaffolded_module_to_claude_md(content: &str, recipe_id: &str, file: &str, struct_name: &str) -> String {
+    let entry = format!(
+        "- `{}` - `{}` scaffolded from the `/{}` recipe (finish the TODO, then wire it into the params and `process()`)",
+        file, struct_name, recipe_id
+    );
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    if lines.iter().any(|l| l.starts_with(&format!("- `{}`", file))) {
+        return content.to_string();
+    }
+
+    match lines.iter().position(|l| l == SCAFFOLD_HEADING) {
+        Some(section) => {
+            // Append after the last entry of the existing list
+            let end = lines[section + 1..]
+                .iter()
+                .position(|l| l.starts_with("## "))
+                .map_or(lines.len(), |i| section + 1 + i);
+            let insert_at = (section + 1..end)
+                .rev()
+                .find(|&i| lines[i].starts_with("- "))
+                .map_or(section + 1, |i| i + 1);
+            lines.insert(insert_at, entry);
+        }
+        None => {
+            let insert_at = lines
+                .iter()
+                .position(|l| l == "## Critical Safety Rules")
+                .unwrap_or(lines.len());
+            let section = [
+                SCAFFOLD_HEADING.to_string(),
+                String::new(),
+                entry,
+                String::new(),
+            ];
+            lines.splice(insert_at..insert_at, section);
+        }
+    }
+
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
 fn generate_critical_safety() -> String {
     r#"## Critical Safety Rules
 
@@ -285,6 +493,68 @@ mod tests {
         assert!(!content.contains("/sidechain-input"));
     }
 
+    #[test]
+    fn test_add_component_to_existing_claude_md() {
+        let content = generate_claude_md("test-synth", "instrument", "egui", None);
+        let updated = add_component_to_claude_md(&content, "lfo");
+
+        assert!(updated.contains("- **Components**: lfo"));
+        assert!(updated.contains("| `/lfo` |"));
+        // Table goes before the safety rules, existing sections are kept
+        assert!(updated.find("/lfo").unwrap() < updated.find("## Critical Safety Rules").unwrap());
+        assert!(updated.contains("## Current Implementation"));
+
+        let updated = add_component_to_claude_md(&updated, "polyphony");
+        assert!(updated.contains("- **Components**: lfo, polyphony"));
+        assert_eq!(updated.matches("### Components").count(), 1);
+
+        // Adding twice is a no-op
+        assert_eq!(add_component_to_claude_md(&updated, "polyphony"), updated);
+    }
+
+    #[test]
+    fn test_set_ui_framework_in_claude_md() {
+        let content = generate_claude_md("test-effect", "effect", "native", None);
+        let updated = set_ui_framework_in_claude_md(&content, "webview");
+
+        assert!(updated.contains("- **UI Framework**: webview"));
+        assert!(updated.contains("/webview-ui"));
+        assert!(!updated.contains("/native-ui"));
+        assert_eq!(set_ui_framework_in_claude_md(&updated, "native"), content);
+    }
+
+    #[test]
+    fn test_add_issue_tracker_to_claude_md() {
+        let content = generate_claude_md("test", "effect", "native", None);
+        assert!(content.contains(".vstworkshop/issues.json"));
+        assert_eq!(add_issue_tracker_to_claude_md(&content), content);
+
+        // Older CLAUDE.md without the section gets it before the safety rules
+        let old = content.replace(&generate_issue_tracker(), "");
+        let updated = add_issue_tracker_to_claude_md(&old);
+        assert_eq!(updated, content);
+
+        let custom = add_issue_tracker_to_claude_md("# My notes\n");
+        assert!(custom.starts_with("# My notes\n\n## Outstanding Issues"));
+    }
+
+    #[test]
+    fn test_add_scaffolded_module_to_claude_md() {
+        let content = generate_claude_md("test", "effect", "native", None);
+        let updated = add_scaffolded_module_to_claude_md(&content, "moog-ladder-filter", "src/dsp/moog_ladder.rs", "MoogLadder");
+        let updated = add_scaffolded_module_to_claude_md(&updated, "delay-line", "src/dsp/delay_line.rs", "DelayLine");
+
+        let section = updated.find(SCAFFOLD_HEADING).unwrap();
+        assert!(section < updated.find("## Critical Safety Rules").unwrap());
+        let ladder = updated.find("- `src/dsp/moog_ladder.rs` - `MoogLadder` scaffolded from the `/moog-ladder-filter` recipe").unwrap();
+        let delay = updated.find("- `src/dsp/delay_line.rs`").unwrap();
+        assert!(section < ladder && ladder < delay);
+
+        // Scaffolding the same file again doesn't duplicate the entry
+        let again = add_scaffolded_module_to_claude_md(&updated, "moog-ladder-filter", "src/dsp/moog_ladder.rs", "MoogLadder");
+        assert_eq!(again, updated);
+    }
+
     #[test]
     fn test_critical_safety_included() {
         let content = generate_claude_md("test", "effect", "native", None);
@@ -190,6 +190,24 @@ if !sample.is_finite() {
 
 **Note:** Do NOT use `sample.clamp(-1.0, 1.0)` as a safety limiter - this masks problems and breaks gain staging. The preview engine has its own output limiter for speaker protection. Let plugins output their true levels so users can see accurate metering.
 
+## Denormals (FTZ/DAZ)
+
+When a feedback path (IIR filter, reverb, delay, envelope) decays after the input goes silent, its state passes through the denormal range (below ~1e-38 for f32). Many CPUs are 10-100x slower on denormal math, so a plugin that idles fine at full level spikes the CPU a few seconds into silence.
+
+- nih-plug turns on flush-to-zero (FTZ/DAZ) for the duration of `process()` - don't disable it, and don't rely on it for DSP run anywhere else (background threads, `initialize()`)
+- Flush recursive state explicitly so it also works without FTZ:
+
+```rust
+// After updating each feedback/filter state variable:
+if self.state.abs() < 1e-20 {
+    self.state = 0.0;
+}
+```
+
+- Skip expensive processing once the input is silent and the tail has decayed below -120 dB
+
+The validation suite's denormal test feeds decaying signals followed by long silence and flags blocks that get slower in the tail.
+
 ## Anti-Hallucination Checklist
 
 Before generating DSP code, verify:
@@ -221,6 +239,7 @@ If you're unsure about a DSP algorithm:
 | Hand-rolled filter math | Wrong coefficients | Use `biquad` crate |
 | Division by zero | NaN/Inf propagation | Guard all divisions |
 | Unbounded feedback | Runaway levels | Limit feedback to < 1.0 or use tanh() |
+| Decaying feedback state | Denormal CPU spikes in silence | Flush state below 1e-20 to 0.0 |
 
 ## Implementing reset() (Important!)
 
@@ -39,6 +39,7 @@ enum UIMessage {
     Init,                        // UI requests initial state
     SetGain { value: f32 },      // User adjusted gain slider
     SetFilterCutoff { value: f32 },
+    Heartbeat,                   // UI is still rendering (see below)
     // Add more as needed...
 }
 ```
@@ -102,6 +103,7 @@ fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Edi
                             setter.set_parameter_normalized(&params.gain, value);
                             setter.end_set_parameter(&params.gain);
                         }
+                        UIMessage::Heartbeat => ui_heartbeat(), // generated with the project
                         // Handle other messages...
                     }
                 }
@@ -162,6 +164,17 @@ fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Edi
         document.getElementById('gain').addEventListener('input', (e) => {
             sendToPlugin({ type: 'SetGain', value: parseFloat(e.target.value) });
         });
+
+        // Heartbeat from the render loop (keep this - see below)
+        let lastHeartbeat = 0;
+        function heartbeat(now) {
+            if (now - lastHeartbeat > 1000) {
+                lastHeartbeat = now;
+                sendToPlugin({ type: 'Heartbeat' });
+            }
+            requestAnimationFrame(heartbeat);
+        }
+        requestAnimationFrame(heartbeat);
     </script>
 </body>
 </html>
@@ -176,6 +189,7 @@ fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Edi
 | Feedback loop | UI -> Plugin -> UI infinite loop | JavaScript should ignore updates while dragging |
 | Missing begin/end_set_parameter | Undo/redo doesn't work properly | Always wrap set_parameter_normalized |
 | Empty persist keys | Compile errors | Use unique descriptive keys like `"gain-dirty"` |
+| Removing the Heartbeat | freqlab can't detect and recreate a blank editor after hot reload | Keep the `requestAnimationFrame` heartbeat and `ui_heartbeat()` |
 
 ## Feature Completion Checklist
 
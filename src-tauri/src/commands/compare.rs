@@ -0,0 +1,114 @@
+//! Regression rendering between plugin versions
+//!
+//! Renders the same input through two built versions of a project's plugin
+//! (taken from the versioned output folder) and reports how far apart they
+//! are, so a refactor can be verified as audio-transparent.
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+use super::analysis_jobs::run_analysis_job;
+use super::projects::get_output_path;
+use crate::audio::engine::get_engine_sample_rate;
+use crate::audio::offline::{compare_renders, default_test_program, render_through_plugin, RenderComparison};
+use crate::audio::samples::AudioSample;
+
+/// Peak difference at or below this counts as transparent (dBFS, ~float rounding)
+const TRANSPARENT_THRESHOLD_DB: f32 = -90.0;
+
+/// Sample rate for the built-in test program when the engine isn't running
+const DEFAULT_RENDER_RATE: u32 = 48000;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VersionComparison {
+    pub project_name: String,
+    pub version: u32,
+    pub base_version: u32,
+    /// Name of the input material ("test program" for the built-in sweep + noise)
+    pub input_name: String,
+    pub sample_rate: u32,
+    pub duration_secs: f32,
+    /// True if the peak difference is below the transparency threshold
+    pub transparent: bool,
+    #[serde(flatten)]
+    pub comparison: RenderComparison,
+}
+
+/// Find the .clap bundle built for a project version
+pub(crate) fn find_clap_bundle(project_name: &str, version: u32) -> Result<PathBuf, String> {
+    // Version 0 (no Claude commits) builds into v1
+    let dir = get_output_path()
+        .join(project_name)
+        .join(format!("v{}", version.max(1)));
+
+    std::fs::read_dir(&dir)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .find(|p| p.extension().map(|e| e == "clap").unwrap_or(false))
+        })
+        .ok_or_else(|| format!("No .clap build found for {} v{} - build that version first", project_name, version))
+}
+
+/// Render the same input through two versions of a plugin and compare the output
+///
+/// `base_version` defaults to the previous version. Without `input_path`, a
+/// built-in sine sweep + pink noise program is used. Runs as an analysis job.
+#[tauri::command]
+pub async fn compare_versions(
+    project_name: String,
+    version: u32,
+    base_version: Option<u32>,
+    input_path: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<VersionComparison, String> {
+    let base_version = match base_version {
+        Some(v) => v,
+        None if version > 1 => version - 1,
+        None => return Err("No previous version to compare against".to_string()),
+    };
+
+    let candidate_bundle = find_clap_bundle(&project_name, version)?;
+    let reference_bundle = find_clap_bundle(&project_name, base_version)?;
+
+    run_analysis_job("compare_versions", Some(app_handle), move |job| {
+        let (input, sample_rate, input_name) = match input_path {
+            Some(path) => {
+                let sample = AudioSample::load(&path)?;
+                (sample.data, sample.info.sample_rate, sample.info.name)
+            }
+            None => {
+                let rate = get_engine_sample_rate().unwrap_or(DEFAULT_RENDER_RATE);
+                (default_test_program(rate), rate, "test program".to_string())
+            }
+        };
+
+        log::info!(
+            "Comparing {} v{} against v{} ({} frames @ {} Hz)",
+            project_name,
+            version,
+            base_version,
+            input.len(),
+            sample_rate
+        );
+
+        // Render one at a time so both builds are never loaded together
+        let reference = render_through_plugin(&reference_bundle, &input, sample_rate, job.render_progress(0, 2))?;
+        let candidate = render_through_plugin(&candidate_bundle, &input, sample_rate, job.render_progress(1, 2))?;
+        let comparison = compare_renders(&reference, &candidate, sample_rate);
+
+        Ok(VersionComparison {
+            project_name,
+            version,
+            base_version,
+            input_name,
+            sample_rate,
+            duration_secs: reference.len() as f32 / sample_rate as f32,
+            transparent: comparison.peak_difference_db <= TRANSPARENT_THRESHOLD_DB,
+            comparison,
+        })
+    })
+    .await
+}
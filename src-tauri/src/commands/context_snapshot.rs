@@ -0,0 +1,295 @@
+//! Live app state for Claude prompts
+//!
+//! Claude only sees the source code, so it has to guess what the plugin is
+//! actually doing. This assembles what the app knows right now - parameter
+//! values of the plugin in the preview, output levels, DSP health warnings,
+//! and the last build's diagnostics - into a compact markdown block that is
+//! prepended to each chat turn.
+
+use std::fs;
+use std::path::Path;
+
+use super::activity::{latest_activity, project_dir, ActivityKind};
+use super::build::{extract_compiler_errors, get_last_build_log_path};
+use super::projects::get_output_path;
+use crate::audio::engine::get_engine_handle;
+use crate::audio::plugin::PluginState;
+use crate::audio::watchdog::DspHealthReport;
+
+/// Parameters listed before the table is cut short
+const MAX_PARAMS: usize = 40;
+
+/// Compiler errors quoted from the last build
+const MAX_BUILD_ERRORS: usize = 5;
+
+struct ParamRow {
+    name: String,
+    value: f64,
+    /// Value as the plugin displays it
+    text: Option<String>,
+    min: f64,
+    max: f64,
+}
+
+struct PreviewSnapshot {
+    /// Output folder of the loaded build (e.g. "v3")
+    version: Option<String>,
+    playing: bool,
+    crashed: bool,
+    params: Vec<ParamRow>,
+    /// Output peak levels (dBFS) while playing
+    levels_db: Option<(f32, f32)>,
+    correlation: f32,
+    cpu_percent: Option<f32>,
+    health: DspHealthReport,
+}
+
+struct BuildSnapshot {
+    summary: String,
+    success: bool,
+    minutes_ago: Option<i64>,
+    errors: Vec<String>,
+    warnings: usize,
+}
+
+fn to_db(level: f32) -> f32 {
+    if level > 0.0 {
+        (20.0 * level.log10()).max(-120.0)
+    } else {
+        -120.0
+    }
+}
+
+/// State of the preview, if it is hosting a build of this project
+fn preview_snapshot(project_name: &str) -> Option<PreviewSnapshot> {
+    let handle = get_engine_handle()?;
+    let PluginState::Active { path, .. } = handle.get_plugin_state() else {
+        return None;
+    };
+    let project_output = get_output_path().join(project_name);
+    let version = Path::new(&path)
+        .strip_prefix(&project_output)
+        .ok()?
+        .components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().to_string());
+
+    let params = handle.list_plugin_params();
+    let ids: Vec<u32> = params.iter().map(|p| p.id).collect();
+    let values = handle.get_plugin_param_values(&ids);
+    let params = params
+        .into_iter()
+        .filter(|p| !p.is_read_only)
+        .filter_map(|p| {
+            let value = values.iter().find(|(id, _)| *id == p.id)?.1;
+            Some(ParamRow {
+                text: handle.format_plugin_param_value(p.id, value),
+                name: p.name,
+                value,
+                min: p.min_value,
+                max: p.max_value,
+            })
+        })
+        .collect();
+
+    let playing = handle.is_playing();
+    let (left, right) = handle.get_output_levels();
+    Some(PreviewSnapshot {
+        version,
+        playing,
+        crashed: handle.plugin_has_crashed(),
+        params,
+        levels_db: playing.then(|| (to_db(left), to_db(right))),
+        correlation: handle.get_stereo_correlation(),
+        cpu_percent: handle.get_plugin_performance().map(|p| p.cpu_percent),
+        health: handle.dsp_health(),
+    })
+}
+
+/// Outcome and diagnostics of the project's last build
+fn build_snapshot(project_path: &Path) -> Option<BuildSnapshot> {
+    let entry = latest_activity(project_path, ActivityKind::Build)?;
+    let log = fs::read_to_string(get_last_build_log_path(project_path)).unwrap_or_default();
+    let minutes_ago = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+        .ok()
+        .map(|t| (chrono::Utc::now() - t.with_timezone(&chrono::Utc)).num_minutes());
+
+    Some(BuildSnapshot {
+        summary: entry.summary,
+        success: entry.success,
+        minutes_ago,
+        errors: extract_compiler_errors(&log).into_iter().take(MAX_BUILD_ERRORS).collect(),
+        // Skip cargo's "generated N warnings" summary lines
+        warnings: log
+            .lines()
+            .filter(|l| l.starts_with("warning") && !l.contains("generated"))
+            .count(),
+    })
+}
+
+fn render_preview(preview: &PreviewSnapshot, out: &mut String) {
+    out.push_str(&format!(
+        "**Preview**: {} loaded, {}{}\n",
+        preview.version.as_deref().unwrap_or("this project's plugin"),
+        if preview.playing { "playing" } else { "stopped" },
+        if preview.crashed { " - **the plugin has crashed**" } else { "" }
+    ));
+
+    if let Some((left, right)) = preview.levels_db {
+        out.push_str(&format!(
+            "**Output**: peak L {:.1} dBFS / R {:.1} dBFS, stereo correlation {:.2}\n",
+            left, right, preview.correlation
+        ));
+    }
+    if let Some(cpu) = preview.cpu_percent {
+        out.push_str(&format!("**CPU**: {:.1}% of the real-time budget\n", cpu));
+    }
+
+    let health = &preview.health;
+    let mut warnings = Vec::new();
+    if health.non_finite > 0 {
+        warnings.push(format!("{} buffer(s) with NaN/Inf output", health.non_finite));
+    }
+    if health.clicks > 0 {
+        warnings.push(format!("{} click(s)", health.clicks));
+    }
+    if health.overloads > 0 {
+        warnings.push(format!("{} runaway level(s)", health.overloads));
+    }
+    if health.dangerous_levels > 0 {
+        warnings.push(format!("{} dangerous level(s) caught by the safety limiter", health.dangerous_levels));
+    }
+    if !warnings.is_empty() {
+        out.push_str(&format!("**DSP health warnings** (since load): {}\n", warnings.join(", ")));
+    }
+
+    if !preview.params.is_empty() {
+        out.push_str("\n| Parameter | Value | Range |\n|---|---|---|\n");
+        for p in preview.params.iter().take(MAX_PARAMS) {
+            let value = p.text.clone().unwrap_or_else(|| format!("{:.3}", p.value));
+            out.push_str(&format!("| {} | {} | {} – {} |\n", p.name, value, p.min, p.max));
+        }
+        if preview.params.len() > MAX_PARAMS {
+            out.push_str(&format!("| … {} more | | |\n", preview.params.len() - MAX_PARAMS));
+        }
+    }
+}
+
+fn render_build(build: &BuildSnapshot, out: &mut String) {
+    let age = match build.minutes_ago {
+        Some(m) if m < 1 => ", just now".to_string(),
+        Some(m) if m < 120 => format!(", {} min ago", m),
+        Some(m) => format!(", {} h ago", m / 60),
+        None => String::new(),
+    };
+    out.push_str(&format!(
+        "**Last build**: {} ({}{}{})\n",
+        build.summary,
+        if build.success { "succeeded" } else { "failed" },
+        age,
+        if build.warnings > 0 {
+            format!(", {} warning(s)", build.warnings)
+        } else {
+            String::new()
+        }
+    ));
+    for error in &build.errors {
+        out.push_str(&format!("- `{}`\n", error));
+    }
+}
+
+/// Markdown block for the prompt, or None if there is nothing to report
+fn render_snapshot(preview: Option<&PreviewSnapshot>, build: Option<&BuildSnapshot>) -> Option<String> {
+    if preview.is_none() && build.is_none() {
+        return None;
+    }
+
+    let mut out = String::from("## Current App State (attached automatically - don't mention it unless relevant)\n\n");
+    if let Some(build) = build {
+        render_build(build, &mut out);
+    }
+    match preview {
+        Some(preview) => render_preview(preview, &mut out),
+        None => out.push_str("**Preview**: not loaded\n"),
+    }
+    Some(out)
+}
+
+/// Snapshot of a project's live state for a chat turn
+pub(crate) fn snapshot_for_project(project_name: &str) -> Option<String> {
+    render_snapshot(
+        preview_snapshot(project_name).as_ref(),
+        build_snapshot(&project_dir(project_name)).as_ref(),
+    )
+}
+
+/// Assemble the context block that is prepended to chat turns
+/// Returns an empty string when there is nothing to report.
+#[tauri::command]
+pub async fn build_context_snapshot(project_name: String) -> Result<String, String> {
+    if !project_dir(&project_name).exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+    Ok(snapshot_for_project(&project_name).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preview() -> PreviewSnapshot {
+        PreviewSnapshot {
+            version: Some("v3".to_string()),
+            playing: true,
+            crashed: false,
+            params: vec![ParamRow {
+                name: "Gain".to_string(),
+                value: 0.5,
+                text: Some("-6.0 dB".to_string()),
+                min: 0.0,
+                max: 1.0,
+            }],
+            levels_db: Some((-6.5, -7.0)),
+            correlation: 0.98,
+            cpu_percent: None,
+            health: DspHealthReport::default(),
+        }
+    }
+
+    #[test]
+    fn test_render_snapshot() {
+        assert!(render_snapshot(None, None).is_none());
+
+        let build = BuildSnapshot {
+            summary: "Build of v4 failed".to_string(),
+            success: false,
+            minutes_ago: Some(3),
+            errors: vec!["error[E0308]: mismatched types (src/lib.rs:42:5)".to_string()],
+            warnings: 2,
+        };
+        let mut preview = preview();
+        preview.health.non_finite = 4;
+
+        let text = render_snapshot(Some(&preview), Some(&build)).unwrap();
+        assert!(text.contains("**Last build**: Build of v4 failed (failed, 3 min ago, 2 warning(s))"));
+        assert!(text.contains("`error[E0308]: mismatched types (src/lib.rs:42:5)`"));
+        assert!(text.contains("v3 loaded, playing"));
+        assert!(text.contains("| Gain | -6.0 dB | 0 – 1 |"));
+        assert!(text.contains("4 buffer(s) with NaN/Inf output"));
+        assert!(!text.contains("CPU"));
+    }
+
+    #[test]
+    fn test_render_without_preview() {
+        let build = BuildSnapshot {
+            summary: "Built v2".to_string(),
+            success: true,
+            minutes_ago: None,
+            errors: Vec::new(),
+            warnings: 0,
+        };
+        let text = render_snapshot(None, Some(&build)).unwrap();
+        assert!(text.contains("**Last build**: Built v2 (succeeded)"));
+        assert!(text.contains("**Preview**: not loaded"));
+    }
+}
@@ -0,0 +1,442 @@
+//! Host crash reporting
+//!
+//! Captures crashes of the app itself (not hosted plugins - those are caught
+//! by `audio::plugin::crash_guard`) into ~/VSTWorkshop/crash-reports/:
+//! - Rust panics on any thread, with a full backtrace
+//! - native crashes (SIGSEGV, SIGBUS, SIGABRT, ...) with a raw symbolized backtrace
+//!
+//! Reports stay on disk. Sharing one requires opting in first, and every
+//! share is confirmed in a native dialog showing exactly what will be sent.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::projects::get_workspace_path;
+
+/// Where shared reports are filed
+const ISSUES_URL: &str = "https://github.com/jamesontucker/freqlab/issues/new";
+
+/// Report text included in a shared issue (URLs have practical length limits)
+const MAX_SHARED_REPORT_CHARS: usize = 6000;
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashKind {
+    Panic,
+    Native,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CrashReportInfo {
+    /// File name, used as the report id
+    pub id: String,
+    pub kind: CrashKind,
+    pub created_at: String,
+    /// First meaningful line of the report
+    pub summary: String,
+    pub shared: bool,
+}
+
+/// Crash reporting preferences (crash-reports/settings.json)
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct CrashReportSettings {
+    /// Whether the user has answered the opt-in prompt
+    #[serde(default)]
+    pub asked: bool,
+    #[serde(rename = "optIn", default)]
+    pub opt_in: bool,
+    /// Ids of reports already shared
+    #[serde(default)]
+    pub shared: Vec<String>,
+}
+
+pub(crate) fn get_crash_reports_dir() -> PathBuf {
+    get_workspace_path().join("crash-reports")
+}
+
+fn get_settings_path() -> PathBuf {
+    get_crash_reports_dir().join("settings.json")
+}
+
+fn load_settings() -> CrashReportSettings {
+    fs::read_to_string(get_settings_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(settings: &CrashReportSettings) -> Result<(), String> {
+    fs::create_dir_all(get_crash_reports_dir())
+        .map_err(|e| format!("Failed to create crash reports dir: {}", e))?;
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize crash report settings: {}", e))?;
+    fs::write(get_settings_path(), json)
+        .map_err(|e| format!("Failed to write crash report settings: {}", e))
+}
+
+/// Resolve a report id to its file, rejecting anything that isn't a plain report file name
+fn report_path(id: &str) -> Result<PathBuf, String> {
+    let valid = (id.starts_with("panic-") || id.starts_with("native-"))
+        && id.ends_with(".txt")
+        && !id.contains('/')
+        && !id.contains('\\');
+    if !valid {
+        return Err(format!("Invalid crash report id: {}", id));
+    }
+    let path = get_crash_reports_dir().join(id);
+    if !path.exists() {
+        return Err(format!("Crash report '{}' not found", id));
+    }
+    Ok(path)
+}
+
+// =============================================================================
+// Capture
+// =============================================================================
+
+/// Install the panic hook and native crash handlers
+/// Call once at startup, after logging is initialized
+pub fn install_crash_handlers() {
+    let app_version = env!("CARGO_PKG_VERSION");
+    let dir = get_crash_reports_dir();
+    if let Err(e) = fs::create_dir_all(&dir) {
+        log::warn!("Crash reporting disabled - failed to create {:?}: {}", dir, e);
+        return;
+    }
+
+    install_panic_hook(app_version.to_string());
+    #[cfg(unix)]
+    native::install(&dir, app_version);
+
+    log::info!("Host crash reporting installed ({:?})", dir);
+}
+
+fn install_panic_hook(app_version: String) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "<unknown>".to_string());
+        let thread = std::thread::current();
+        let backtrace = std::backtrace::Backtrace::force_capture();
+
+        let now = chrono::Local::now();
+        let report = format!(
+            "Panic: {}\nLocation: {}\nThread: {}\nApp version: {}\nOS: {} {}\nTime: {}\n\nBacktrace:\n{}\n",
+            message,
+            location,
+            thread.name().unwrap_or("<unnamed>"),
+            app_version,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            now.to_rfc3339(),
+            backtrace,
+        );
+        let path = get_crash_reports_dir().join(format!("panic-{}.txt", now.format("%Y%m%d-%H%M%S%.3f")));
+        if let Err(e) = fs::write(&path, report) {
+            eprintln!("Failed to write panic report {:?}: {}", path, e);
+        }
+
+        previous(info);
+    }));
+}
+
+#[cfg(unix)]
+mod native {
+    //! Native crash capture. Everything here runs inside a signal handler, so it
+    //! only uses async-signal-safe calls on buffers prepared at install time.
+
+    use once_cell::sync::OnceCell;
+    use std::ffi::{c_void, CString};
+    use std::path::Path;
+
+    extern "C" {
+        fn backtrace(buffer: *mut *mut c_void, size: libc::c_int) -> libc::c_int;
+        fn backtrace_symbols_fd(buffer: *const *mut c_void, size: libc::c_int, fd: libc::c_int);
+    }
+
+    const SIGNALS: [libc::c_int; 5] = [
+        libc::SIGSEGV,
+        libc::SIGBUS,
+        libc::SIGABRT,
+        libc::SIGILL,
+        libc::SIGFPE,
+    ];
+
+    const MAX_FRAMES: usize = 128;
+
+    /// Report file for this launch
+    static REPORT_PATH: OnceCell<CString> = OnceCell::new();
+
+    /// Report header (version, launch time) written before the signal details
+    static REPORT_HEADER: OnceCell<Vec<u8>> = OnceCell::new();
+
+    pub fn install(dir: &Path, app_version: &str) {
+        let launched = chrono::Local::now();
+        let path = dir.join(format!("native-{}.txt", launched.format("%Y%m%d-%H%M%S")));
+        let path = match CString::new(path.to_string_lossy().as_bytes()) {
+            Ok(p) => p,
+            Err(_) => return,
+        };
+        let header = format!(
+            "Native crash\nApp version: {}\nOS: {} {}\nLaunched: {}\n",
+            app_version,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            launched.to_rfc3339(),
+        );
+        let _ = REPORT_PATH.set(path);
+        let _ = REPORT_HEADER.set(header.into_bytes());
+
+        // The plugin crash guard installs its handlers later and chains to these
+        // whenever a crash happens outside guarded plugin processing
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = native_crash_handler as usize;
+            // Simple 1-arg handler like crash_guard; SA_ONSTACK uses the alternate
+            // stack Rust sets up per thread, so stack overflows still get reported
+            action.sa_flags = libc::SA_ONSTACK;
+            libc::sigemptyset(&mut action.sa_mask);
+            for sig in SIGNALS {
+                libc::sigaction(sig, &action, std::ptr::null_mut());
+            }
+        }
+    }
+
+    unsafe fn write_bytes(fd: libc::c_int, bytes: &[u8]) {
+        let _ = libc::write(fd, bytes.as_ptr() as *const c_void, bytes.len());
+    }
+
+    /// Format a number without allocating
+    unsafe fn write_number(fd: libc::c_int, mut value: i64) {
+        let mut buf = [0u8; 24];
+        let mut pos = buf.len();
+        let negative = value < 0;
+        if value == 0 {
+            pos -= 1;
+            buf[pos] = b'0';
+        }
+        while value != 0 {
+            pos -= 1;
+            buf[pos] = b'0' + (value % 10).unsigned_abs() as u8;
+            value /= 10;
+        }
+        if negative {
+            pos -= 1;
+            buf[pos] = b'-';
+        }
+        write_bytes(fd, &buf[pos..]);
+    }
+
+    extern "C" fn native_crash_handler(sig: libc::c_int) {
+        unsafe {
+            if let (Some(path), Some(header)) = (REPORT_PATH.get(), REPORT_HEADER.get()) {
+                let fd = libc::open(
+                    path.as_ptr(),
+                    libc::O_WRONLY | libc::O_CREAT | libc::O_APPEND,
+                    0o644 as libc::c_uint,
+                );
+                if fd >= 0 {
+                    write_bytes(fd, header);
+                    write_bytes(fd, b"Signal: ");
+                    write_number(fd, sig as i64);
+                    write_bytes(fd, b"\nTime (unix): ");
+                    write_number(fd, libc::time(std::ptr::null_mut()) as i64);
+                    write_bytes(fd, b"\n\nBacktrace:\n");
+
+                    let mut frames = [std::ptr::null_mut::<c_void>(); MAX_FRAMES];
+                    let count = backtrace(frames.as_mut_ptr(), MAX_FRAMES as libc::c_int);
+                    backtrace_symbols_fd(frames.as_ptr(), count, fd);
+                    libc::close(fd);
+                }
+            }
+
+            // Let the process die the normal way (so the OS crash reporter still runs)
+            libc::signal(sig, libc::SIG_DFL);
+            libc::raise(sig);
+        }
+    }
+}
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+/// List host crash reports, newest first
+#[tauri::command]
+pub async fn list_crash_reports() -> Result<Vec<CrashReportInfo>, String> {
+    let settings = load_settings();
+    let entries = match fs::read_dir(get_crash_reports_dir()) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut reports: Vec<CrashReportInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let kind = if id.starts_with("panic-") {
+                CrashKind::Panic
+            } else if id.starts_with("native-") {
+                CrashKind::Native
+            } else {
+                return None;
+            };
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            let content = fs::read_to_string(entry.path()).unwrap_or_default();
+            let summary = match kind {
+                CrashKind::Panic => content.lines().next().unwrap_or_default().to_string(),
+                CrashKind::Native => content
+                    .lines()
+                    .find(|l| l.starts_with("Signal:"))
+                    .unwrap_or("Native crash")
+                    .to_string(),
+            };
+            Some(CrashReportInfo {
+                shared: settings.shared.contains(&id),
+                id,
+                kind,
+                created_at: chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339(),
+                summary,
+            })
+        })
+        .collect();
+
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(reports)
+}
+
+/// Get the full text of a crash report
+#[tauri::command]
+pub async fn get_crash_report(id: String) -> Result<String, String> {
+    fs::read_to_string(report_path(&id)?).map_err(|e| format!("Failed to read crash report: {}", e))
+}
+
+/// Delete a crash report
+#[tauri::command]
+pub async fn delete_crash_report(id: String) -> Result<(), String> {
+    fs::remove_file(report_path(&id)?).map_err(|e| format!("Failed to delete crash report: {}", e))?;
+    let mut settings = load_settings();
+    settings.shared.retain(|s| s != &id);
+    save_settings(&settings)
+}
+
+/// Get crash reporting preferences (`asked` false means the opt-in prompt hasn't been shown)
+#[tauri::command]
+pub async fn get_crash_report_settings() -> Result<CrashReportSettings, String> {
+    Ok(load_settings())
+}
+
+/// Record the user's answer to the crash reporting opt-in prompt
+#[tauri::command]
+pub async fn set_crash_reporting_opt_in(opt_in: bool) -> Result<(), String> {
+    let mut settings = load_settings();
+    settings.asked = true;
+    settings.opt_in = opt_in;
+    save_settings(&settings)
+}
+
+/// Percent-encode a string for use in a URL query value
+fn encode_query_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() * 3);
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Share a crash report as a prefilled GitHub issue
+/// Requires the opt-in, and the user confirms the exact contents in a native dialog first.
+/// Returns false if the user cancelled.
+#[tauri::command]
+pub async fn share_crash_report(id: String, app_handle: tauri::AppHandle) -> Result<bool, String> {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+    let mut settings = load_settings();
+    if !settings.opt_in {
+        return Err("Crash reporting is turned off - enable it before sharing reports".to_string());
+    }
+
+    let content = fs::read_to_string(report_path(&id)?)
+        .map_err(|e| format!("Failed to read crash report: {}", e))?;
+    // Usernames in paths stay on this machine
+    let content = match dirs::home_dir() {
+        Some(home) => content.replace(&*home.to_string_lossy(), "~"),
+        None => content,
+    };
+    let body: String = content.chars().take(MAX_SHARED_REPORT_CHARS).collect();
+
+    let confirmed = app_handle
+        .dialog()
+        .message(format!(
+            "This report will be opened as a new GitHub issue in your browser:\n\n{}",
+            body.chars().take(1500).collect::<String>()
+        ))
+        .title("Share crash report?")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom("Share".to_string(), "Don't Share".to_string()))
+        .blocking_show();
+    if !confirmed {
+        return Ok(false);
+    }
+
+    let title = content.lines().next().unwrap_or("Crash report");
+    let url = format!(
+        "{}?title={}&body={}",
+        ISSUES_URL,
+        encode_query_value(title),
+        encode_query_value(&format!("```\n{}\n```", body)),
+    );
+
+    open_url(&url)?;
+
+    if !settings.shared.contains(&id) {
+        settings.shared.push(id);
+    }
+    save_settings(&settings)?;
+    Ok(true)
+}
+
+/// Open a URL in the default browser
+fn open_url(url: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg(url)
+            .spawn()
+            .map_err(|e| format!("Failed to open browser: {}", e))?;
+        Ok(())
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Err(format!("Opening the browser is not supported on this platform: {}", url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(encode_query_value("a b&c"), "a%20b%26c");
+        assert_eq!(encode_query_value("safe-_.~"), "safe-_.~");
+    }
+
+    #[test]
+    fn test_report_path_rejects_traversal() {
+        assert!(report_path("../settings.json").is_err());
+        assert!(report_path("panic-../../x.txt").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! Debug preview
+//!
+//! Builds a project with the dev profile (unoptimized, full debug info, not
+//! stripped), loads that CLAP into the preview, and reports what's needed to
+//! attach a native debugger: the host's PID, the plugin binary and its load
+//! address, and a ready-to-paste `lldb` or WinDbg command. The plugin runs
+//! inside the app, so attaching to the app debugs the plugin.
+
+use serde::Serialize;
+use std::path::Path;
+use tauri::Emitter;
+
+use super::build::{find_clap_in, get_debug_output_path, run_bundle, BuildProfile, BuildStreamEvent};
+use super::chat::resolve_project_version;
+use super::preview::plugin_load;
+use crate::audio::engine::get_engine_handle;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DebugPreviewInfo {
+    pub pid: u32,
+    pub plugin_path: String,
+    /// Binary the preview actually loaded (a temp copy of the bundle's)
+    pub module_path: String,
+    /// Load address of the plugin binary, e.g. "0x10a4c0000"
+    pub module_base: Option<String>,
+    pub attach_command: String,
+}
+
+/// lldb attach that also stops on Rust panics inside the plugin
+fn lldb_command(pid: u32, module_name: &str) -> String {
+    format!(
+        "lldb -p {} -o \"image list {}\" -o \"breakpoint set -n rust_panic\" -o continue",
+        pid, module_name
+    )
+}
+
+/// WinDbg attach; module names drop the extension and use underscores
+fn windbg_command(pid: u32, module_name: &str) -> String {
+    let module = Path::new(module_name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().replace(['-', '.', ' '], "_"))
+        .unwrap_or_default();
+    format!("windbg -p {} -c \"bu {}!rust_panic; g\"", pid, module)
+}
+
+fn attach_command(pid: u32, module_path: &Path) -> String {
+    let module_name = module_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if cfg!(windows) {
+        windbg_command(pid, &module_name)
+    } else {
+        lldb_command(pid, &module_name)
+    }
+}
+
+/// Build the project without optimizations or stripping, load the debug CLAP
+/// in the preview, and return the attach details (also printed to the build output)
+#[tauri::command]
+pub async fn start_debug_preview(
+    project_name: String,
+    version: Option<u32>,
+    window: tauri::Window,
+    app_handle: tauri::AppHandle,
+) -> Result<DebugPreviewInfo, String> {
+    let version = resolve_project_version(&project_name, version).await?;
+    let emit = |event: BuildStreamEvent| {
+        let _ = window.emit("build-stream", event);
+    };
+
+    let result = run_bundle(project_name.clone(), version, BuildProfile::Debug, &emit).await?;
+    if !result.success {
+        return Err("Debug build failed - see the build output".to_string());
+    }
+
+    let clap = find_clap_in(&get_debug_output_path(&project_name))
+        .ok_or_else(|| format!("No debug .clap build found for {}", project_name))?;
+    let plugin_path = clap.to_string_lossy().to_string();
+    plugin_load(plugin_path.clone(), app_handle)?;
+
+    let (module_path, module_base) = get_engine_handle()
+        .and_then(|h| h.plugin_module_info())
+        .ok_or_else(|| "Debug plugin didn't stay loaded".to_string())?;
+    let pid = std::process::id();
+    let info = DebugPreviewInfo {
+        pid,
+        plugin_path,
+        module_path: module_path.to_string_lossy().to_string(),
+        module_base: module_base.map(|base| format!("{:#x}", base)),
+        attach_command: attach_command(pid, &module_path),
+    };
+
+    log::info!(
+        "Debug preview: pid {}, {} at {}",
+        info.pid,
+        info.module_path,
+        info.module_base.as_deref().unwrap_or("unknown address")
+    );
+    for line in [
+        format!("Debug build of {} loaded in the preview (pid {})", project_name, info.pid),
+        format!("Plugin binary: {}", info.module_path),
+        format!("Load address: {}", info.module_base.as_deref().unwrap_or("unknown")),
+        format!("Attach with: {}", info.attach_command),
+    ] {
+        emit(BuildStreamEvent::Output { line });
+    }
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attach_commands() {
+        assert_eq!(
+            lldb_command(4242, "warm_drive"),
+            "lldb -p 4242 -o \"image list warm_drive\" -o \"breakpoint set -n rust_panic\" -o continue"
+        );
+        assert_eq!(
+            windbg_command(4242, "warm-drive.clap"),
+            "windbg -p 4242 -c \"bu warm_drive!rust_panic; g\""
+        );
+    }
+}
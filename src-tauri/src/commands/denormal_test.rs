@@ -0,0 +1,327 @@
+//! Denormal / CPU-spike detection under silence
+//!
+//! Part of the offline validation suite. Feeds the plugin decaying signals
+//! followed by long digital silence while timing every process() call. Filter,
+//! reverb and envelope state that decays into the denormal range makes many
+//! CPUs drastically slower, so a plugin whose blocks get slower once the input
+//! goes quiet - or that keeps emitting subnormal samples - is flagged with a
+//! pointer to the FTZ/DAZ section of the dsp-safety guide.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use super::analysis_jobs::run_analysis_job;
+use super::distortion::resolve_plugin_bundle;
+use super::issues::{sync_validator_issues, ValidatorIssue};
+use crate::audio::buffer::StereoSample;
+use crate::audio::offline::BlockProcessor;
+use crate::audio::plugin::PluginInstance;
+use crate::audio::signals::{SignalConfig, SignalGenerator, SignalType};
+
+const SAMPLE_RATE: u32 = 48000;
+const BLOCK_SIZE: usize = 512;
+
+/// Full-level signal that sets the baseline block time (seconds)
+const ACTIVE_SECS: f32 = 0.5;
+/// Exponential fade from full level down through the denormal range (seconds)
+const DECAY_SECS: f32 = 2.0;
+/// Digital silence while the plugin's own tails decay (seconds)
+const SILENCE_SECS: f32 = 10.0;
+
+/// Blocks ignored at the start of each case (cache warm-up, lazy allocation)
+const WARMUP_BLOCKS: usize = 8;
+
+/// Tail blocks this much slower than the baseline (median) mean sustained denormal math
+const SLOWDOWN_THRESHOLD: f64 = 1.5;
+/// A single tail block this much slower than the baseline is a spike
+const SPIKE_FACTOR: f64 = 4.0;
+/// Share of tail blocks that may spike before it's flagged (scheduler noise)
+const SPIKE_FRACTION: f64 = 0.05;
+/// Timings below this share of the real-time budget are too small to matter
+const SIGNIFICANT_BUDGET_FRACTION: f64 = 0.01;
+
+const SUGGESTION: &str = "Processing slows down as signals decay into silence, which points to denormal numbers. \
+See \"Denormals (FTZ/DAZ)\" in /dsp-safety: keep flush-to-zero enabled in process() and flush \
+filter/feedback state below 1e-20 to 0.0.";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DenormalCase {
+    pub name: String,
+    /// Median block time while the signal is at full level (µs)
+    pub active_block_us: f64,
+    /// Median block time during the decay and silence (µs)
+    pub tail_block_us: f64,
+    pub max_tail_block_us: f64,
+    /// tail_block_us / active_block_us
+    pub slowdown: f64,
+    pub spike_blocks: usize,
+    pub tail_blocks: usize,
+    /// Subnormal output samples while the input was digital silence
+    pub subnormal_samples: usize,
+    pub denormals_suspected: bool,
+    pub issues: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DenormalReport {
+    pub plugin_path: String,
+    /// Real-time budget for one block (µs)
+    pub block_budget_us: f64,
+    pub cases: Vec<DenormalCase>,
+    pub crashed: bool,
+    pub denormals_detected: bool,
+    /// FTZ/DAZ guidance when denormals were detected
+    pub suggestion: Option<String>,
+}
+
+/// Test signals: (name, signal type, frequency)
+const CASES: [(&str, SignalType, f32); 3] = [
+    ("Decaying sine", SignalType::Sine, 220.0),
+    ("Decaying noise", SignalType::WhiteNoise, 0.0),
+    ("Decaying impulses", SignalType::Impulse, 4.0),
+];
+
+/// Generate a case's input: full level, an exponential fade that passes through
+/// the f32 denormal range, then exact zeros
+fn case_input(signal_type: SignalType, frequency: f32, sample_rate: u32) -> Vec<StereoSample> {
+    let active = (ACTIVE_SECS * sample_rate as f32) as usize;
+    let decay = (DECAY_SECS * sample_rate as f32) as usize;
+    let silence = (SILENCE_SECS * sample_rate as f32) as usize;
+
+    // Per-sample factor that takes the envelope from 1.0 to 1e-40 (subnormal) over the decay
+    let factor = (1e-40f64.ln() / decay as f64).exp();
+
+    let mut generator = SignalGenerator::new(sample_rate);
+    generator.set_config(SignalConfig {
+        signal_type,
+        frequency,
+        amplitude: 0.5,
+        ..SignalConfig::default()
+    });
+
+    let mut envelope = 1.0f64;
+    let mut input = Vec::with_capacity(active + decay + silence);
+    for i in 0..active + decay {
+        if i >= active {
+            envelope *= factor;
+        }
+        let s = generator.next_sample();
+        input.push(StereoSample::new(
+            (s.left as f64 * envelope) as f32,
+            (s.right as f64 * envelope) as f32,
+        ));
+    }
+    input.resize(active + decay + silence, StereoSample::new(0.0, 0.0));
+    input
+}
+
+fn median(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    sorted[sorted.len() / 2]
+}
+
+/// Judge a case from its per-block timings (µs)
+/// The first `active_blocks` timings are the full-level baseline, the rest are the tail.
+fn analyze_case(
+    name: &str,
+    timings: &[f64],
+    active_blocks: usize,
+    budget_us: f64,
+    subnormal_samples: usize,
+) -> DenormalCase {
+    let split = active_blocks.min(timings.len());
+    let active = &timings[WARMUP_BLOCKS.min(split)..split];
+    let tail = &timings[split..];
+
+    let active_block_us = median(active);
+    let tail_block_us = median(tail);
+    let max_tail_block_us = tail.iter().cloned().fold(0.0, f64::max);
+    let slowdown = if active_block_us > 0.0 {
+        tail_block_us / active_block_us
+    } else {
+        1.0
+    };
+
+    let significant = budget_us * SIGNIFICANT_BUDGET_FRACTION;
+    let spike_limit = (active_block_us * SPIKE_FACTOR).max(significant);
+    let spike_blocks = tail.iter().filter(|&&t| t > spike_limit).count();
+
+    let mut issues = Vec::new();
+    if slowdown > SLOWDOWN_THRESHOLD && tail_block_us > significant {
+        issues.push(format!(
+            "Blocks are {:.1}x slower in the tail than at full level ({:.0} µs vs {:.0} µs)",
+            slowdown, tail_block_us, active_block_us
+        ));
+    }
+    if !tail.is_empty() && spike_blocks as f64 > tail.len() as f64 * SPIKE_FRACTION {
+        issues.push(format!(
+            "{} of {} tail blocks spiked above {:.0} µs (max {:.0} µs, budget {:.0} µs)",
+            spike_blocks,
+            tail.len(),
+            spike_limit,
+            max_tail_block_us,
+            budget_us
+        ));
+    }
+    if subnormal_samples > 0 {
+        issues.push(format!("{} subnormal output samples during silence", subnormal_samples));
+    }
+
+    DenormalCase {
+        name: name.to_string(),
+        active_block_us,
+        tail_block_us,
+        max_tail_block_us,
+        slowdown,
+        spike_blocks,
+        tail_blocks: tail.len(),
+        subnormal_samples,
+        denormals_suspected: !issues.is_empty(),
+        issues,
+    }
+}
+
+/// Push one case through the plugin, timing each block
+/// Returns the block timings (µs) and the subnormal samples output during silence.
+fn run_case(plugin: &mut PluginInstance, input: &[StereoSample], silence_start: usize) -> Result<(Vec<f64>, usize), String> {
+    plugin.reactivate(SAMPLE_RATE as f64, BLOCK_SIZE as u32)?;
+
+    let mut timings = Vec::with_capacity(input.len() / BLOCK_SIZE);
+    let mut subnormal_samples = 0;
+    let mut processor = BlockProcessor::new(BLOCK_SIZE);
+
+    for (block, chunk) in input.chunks_exact(BLOCK_SIZE).enumerate() {
+        // Includes interleaving, which costs the same for every block
+        let start = Instant::now();
+        let processed = processor.process(plugin, chunk)?;
+        timings.push(start.elapsed().as_secs_f64() * 1_000_000.0);
+
+        if plugin.has_crashed() {
+            return Err("Plugin crashed".to_string());
+        }
+        if block * BLOCK_SIZE >= silence_start {
+            subnormal_samples += processed.iter().filter(|s| s.is_subnormal()).count();
+        }
+    }
+    Ok((timings, subnormal_samples))
+}
+
+/// Run the denormal test on a specific .clap bundle
+pub(crate) async fn denormal_test_bundle(bundle: PathBuf) -> Result<DenormalReport, String> {
+    run_analysis_job("denormal_test", None, move |_job| {
+        let mut plugin = PluginInstance::load(Path::new(&bundle), SAMPLE_RATE as f64, BLOCK_SIZE as u32)?;
+        log::info!("Denormal testing {:?}", bundle);
+
+        let budget_us = BLOCK_SIZE as f64 / SAMPLE_RATE as f64 * 1_000_000.0;
+        let active_blocks = (ACTIVE_SECS * SAMPLE_RATE as f32) as usize / BLOCK_SIZE;
+        let silence_start = ((ACTIVE_SECS + DECAY_SECS) * SAMPLE_RATE as f32) as usize;
+        let mut cases = Vec::with_capacity(CASES.len());
+        let mut crashed = false;
+
+        for (name, signal_type, frequency) in CASES {
+            let input = case_input(signal_type, frequency, SAMPLE_RATE);
+            match run_case(&mut plugin, &input, silence_start) {
+                Ok((timings, subnormal_samples)) => {
+                    cases.push(analyze_case(name, &timings, active_blocks, budget_us, subnormal_samples));
+                }
+                Err(e) if plugin.has_crashed() => {
+                    log::error!("Plugin crashed during denormal test ({}): {}", name, e);
+                    crashed = true;
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        plugin.stop_processing();
+
+        let denormals_detected = cases.iter().any(|c| c.denormals_suspected);
+        log::info!(
+            "Denormal test finished: {} cases, denormals {}",
+            cases.len(),
+            if denormals_detected { "detected" } else { "not detected" }
+        );
+
+        Ok(DenormalReport {
+            plugin_path: bundle.to_string_lossy().to_string(),
+            block_budget_us: budget_us,
+            cases,
+            crashed,
+            denormals_detected,
+            suggestion: denormals_detected.then(|| SUGGESTION.to_string()),
+        })
+    })
+    .await
+}
+
+/// Check a plugin for denormal-induced CPU spikes
+///
+/// Times every block of each decaying case and flags cases whose silent tail
+/// runs slower than the active part or still outputs subnormals. Runs on the
+/// project's build of `version`, or the previewed plugin without a project.
+#[tauri::command]
+pub async fn run_denormal_test(project_name: Option<String>, version: Option<u32>) -> Result<DenormalReport, String> {
+    let bundle = resolve_plugin_bundle(project_name.clone(), version)?;
+
+    let report = denormal_test_bundle(bundle).await?;
+
+    if let Some(project_name) = project_name {
+        let found = report
+            .cases
+            .iter()
+            .filter(|c| c.denormals_suspected)
+            .map(|c| ValidatorIssue {
+                key: c.name.clone(),
+                title: format!("Denormal CPU spikes in silence ({})", c.name.to_lowercase()),
+                details: Some(format!("{} {}", c.issues.join("; "), SUGGESTION)),
+            })
+            .collect();
+        sync_validator_issues(&project_name, "denormal_test", found);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET_US: f64 = 10_666.0;
+
+    #[test]
+    fn test_case_input_passes_through_denormals() {
+        let input = case_input(SignalType::Sine, 220.0, SAMPLE_RATE);
+        let silence_start = ((ACTIVE_SECS + DECAY_SECS) * SAMPLE_RATE as f32) as usize;
+        assert!(input[..silence_start].iter().any(|s| s.left.is_subnormal()));
+        assert!(input[silence_start..].iter().all(|s| s.left == 0.0 && s.right == 0.0));
+    }
+
+    #[test]
+    fn test_analyze_case() {
+        // Steady timings: clean
+        let steady = vec![200.0; 100];
+        let case = analyze_case("steady", &steady, 30, BUDGET_US, 0);
+        assert!(!case.denormals_suspected);
+        assert_eq!(case.spike_blocks, 0);
+
+        // Tail blocks 5x slower: sustained slowdown and spikes
+        let mut slow = vec![200.0; 30];
+        slow.extend(vec![1000.0; 70]);
+        let case = analyze_case("slow", &slow, 30, BUDGET_US, 0);
+        assert!(case.denormals_suspected);
+        assert!((case.slowdown - 5.0).abs() < 1e-9);
+        assert_eq!(case.spike_blocks, 70);
+
+        // A trivial plugin's microsecond jitter is not significant
+        let mut tiny = vec![1.0; 30];
+        tiny.extend(vec![5.0; 70]);
+        assert!(!analyze_case("tiny", &tiny, 30, BUDGET_US, 0).denormals_suspected);
+
+        // Subnormal output is flagged on its own
+        assert!(analyze_case("subnormal", &steady, 30, BUDGET_US, 12).denormals_suspected);
+    }
+}
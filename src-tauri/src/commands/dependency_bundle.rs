@@ -0,0 +1,231 @@
+//! Offline dependency bundles
+//!
+//! `export_dependency_bundle` runs `cargo vendor` over the workspace and a
+//! new project of each UI framework, and zips the sources with the cargo
+//! source-replacement config it prints. `import_dependency_bundle` unpacks one
+//! into `~/VSTWorkshop/vendor/` and writes `~/VSTWorkshop/.cargo/config.toml`
+//! pointing cargo at it with `net.offline` set, so every cargo run in the
+//! workspace (including xtask's own builds) works without a network - for
+//! classrooms and air-gapped studios.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use super::prefetch::{write_template_crate, PREFETCH_DIR, UI_FRAMEWORKS};
+use super::projects::{ensure_workspace, get_workspace_path, NIH_PLUG_REV};
+use super::publish::add_directory_to_zip;
+
+/// Manifest file inside a bundle
+const BUNDLE_MANIFEST: &str = "bundle.json";
+/// First line of the cargo config we write, so we never overwrite someone else's
+const CONFIG_HEADER: &str = "# Written by freqlab for an offline dependency bundle";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyBundleInfo {
+    pub nih_plug_rev: String,
+    pub created_at: String,
+    /// Vendored crates
+    pub crates: usize,
+    /// Source replacement printed by `cargo vendor`
+    pub cargo_config: String,
+}
+
+fn vendor_dir() -> PathBuf {
+    get_workspace_path().join("vendor")
+}
+
+fn cargo_config_path() -> PathBuf {
+    get_workspace_path().join(".cargo").join("config.toml")
+}
+
+/// Where the imported bundle's manifest is kept
+fn installed_info_path() -> PathBuf {
+    get_workspace_path().join(".cargo").join("dependency-bundle.json")
+}
+
+/// Cargo config for an imported bundle: `cargo vendor`'s source replacement
+/// pointed at `vendor_dir`, with the network turned off
+fn offline_config(cargo_config: &str, vendor_dir: &Path) -> String {
+    // Forward slashes are valid in TOML strings and in paths on every platform
+    let directory = vendor_dir.to_string_lossy().replace('\\', "/");
+    let replaced: Vec<String> = cargo_config
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("directory =") {
+                format!("directory = \"{}\"", directory)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    format!("{}\n{}\n\n[net]\noffline = true\n", CONFIG_HEADER, replaced.join("\n").trim_end())
+}
+
+fn is_our_config(path: &Path) -> bool {
+    fs::read_to_string(path).map_or(true, |c| c.starts_with(CONFIG_HEADER))
+}
+
+fn export(destination: &Path) -> Result<DependencyBundleInfo, String> {
+    ensure_workspace()?;
+    let workspace = get_workspace_path();
+    if cargo_config_path().exists() {
+        return Err("An offline bundle is already in use; remove it before exporting a new one".to_string());
+    }
+
+    let staging = workspace.join(".vendor-export");
+    let _ = fs::remove_dir_all(&staging);
+    let prefetch_dir = workspace.join(PREFETCH_DIR);
+    let mut args = vec!["vendor".to_string(), "--versioned-dirs".to_string()];
+    for framework in UI_FRAMEWORKS {
+        let dir = write_template_crate(&prefetch_dir, framework)?;
+        args.extend(["--sync".to_string(), dir.join("Cargo.toml").to_string_lossy().to_string()]);
+    }
+    args.push(staging.join("vendor").to_string_lossy().to_string());
+
+    let output = Command::new("cargo")
+        .current_dir(&workspace)
+        .args(&args)
+        .env("PATH", super::get_extended_path())
+        .output();
+    let _ = fs::remove_dir_all(&prefetch_dir);
+    let output = output.map_err(|e| format!("Failed to run cargo vendor: {}", e))?;
+    if !output.status.success() {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(format!("cargo vendor failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let info = DependencyBundleInfo {
+        nih_plug_rev: NIH_PLUG_REV.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        crates: fs::read_dir(staging.join("vendor")).map(|d| d.flatten().count()).unwrap_or(0),
+        cargo_config: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    };
+    let result = write_bundle(destination, &staging, &info);
+    let _ = fs::remove_dir_all(&staging);
+    result.map(|_| info)
+}
+
+fn write_bundle(destination: &Path, staging: &Path, info: &DependencyBundleInfo) -> Result<(), String> {
+    let file = File::create(destination).map_err(|e| format!("Failed to create bundle: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let manifest = serde_json::to_vec_pretty(info).map_err(|e| format!("Failed to serialize bundle manifest: {}", e))?;
+    zip.start_file(BUNDLE_MANIFEST, options)
+        .map_err(|e| format!("Failed to add {} to bundle: {}", BUNDLE_MANIFEST, e))?;
+    zip.write_all(&manifest).map_err(|e| format!("Failed to write bundle manifest: {}", e))?;
+    add_directory_to_zip(&mut zip, &staging.join("vendor"), "vendor", options)?;
+    zip.finish().map_err(|e| format!("Failed to finalize bundle: {}", e))?;
+    Ok(())
+}
+
+fn import(bundle_path: &Path) -> Result<DependencyBundleInfo, String> {
+    ensure_workspace()?;
+    let config_path = cargo_config_path();
+    if !is_our_config(&config_path) {
+        return Err(format!("{} already exists and wasn't written by freqlab", config_path.display()));
+    }
+
+    let file = File::open(bundle_path).map_err(|e| format!("Failed to open bundle: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read bundle: {}", e))?;
+    let info: DependencyBundleInfo = {
+        let mut entry = archive
+            .by_name(BUNDLE_MANIFEST)
+            .map_err(|_| "Not a dependency bundle (no bundle.json)".to_string())?;
+        let mut json = String::new();
+        entry
+            .read_to_string(&mut json)
+            .map_err(|e| format!("Failed to read bundle manifest: {}", e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse bundle manifest: {}", e))?
+    };
+    if info.nih_plug_rev != NIH_PLUG_REV {
+        log::warn!(
+            "Dependency bundle was made for nih-plug {}, this version uses {}",
+            info.nih_plug_rev,
+            NIH_PLUG_REV
+        );
+    }
+
+    // Unpack next to the workspace, then swap it in
+    let staging = get_workspace_path().join(".vendor-import");
+    let _ = fs::remove_dir_all(&staging);
+    archive
+        .extract(&staging)
+        .map_err(|e| format!("Failed to unpack bundle: {}", e))?;
+    let vendor = vendor_dir();
+    let _ = fs::remove_dir_all(&vendor);
+    let moved = fs::rename(staging.join("vendor"), &vendor).map_err(|e| format!("Failed to install vendored sources: {}", e));
+    let _ = fs::remove_dir_all(&staging);
+    moved?;
+
+    let cargo_dir = config_path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(cargo_dir).map_err(|e| format!("Failed to create {}: {}", cargo_dir.display(), e))?;
+    fs::write(&config_path, offline_config(&info.cargo_config, &vendor))
+        .map_err(|e| format!("Failed to write cargo config: {}", e))?;
+    let json = serde_json::to_string_pretty(&info).map_err(|e| format!("Failed to serialize bundle manifest: {}", e))?;
+    fs::write(installed_info_path(), json).map_err(|e| format!("Failed to save bundle manifest: {}", e))?;
+    Ok(info)
+}
+
+/// Vendor every dependency the workspace and new projects need into a zip at `destination`
+#[tauri::command]
+pub async fn export_dependency_bundle(destination: String) -> Result<DependencyBundleInfo, String> {
+    tokio::task::spawn_blocking(move || export(Path::new(&destination)))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Install a bundle from `export_dependency_bundle` and switch cargo to offline mode
+#[tauri::command]
+pub async fn import_dependency_bundle(bundle_path: String) -> Result<DependencyBundleInfo, String> {
+    tokio::task::spawn_blocking(move || import(Path::new(&bundle_path)))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// The imported bundle, if builds are currently offline
+#[tauri::command]
+pub async fn get_dependency_bundle() -> Result<Option<DependencyBundleInfo>, String> {
+    Ok(fs::read_to_string(installed_info_path())
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok()))
+}
+
+/// Remove the imported bundle and let cargo use the network again
+#[tauri::command]
+pub async fn remove_dependency_bundle() -> Result<(), String> {
+    let config_path = cargo_config_path();
+    if config_path.exists() {
+        if !is_our_config(&config_path) {
+            return Err(format!("{} wasn't written by freqlab; leaving it alone", config_path.display()));
+        }
+        fs::remove_file(&config_path).map_err(|e| format!("Failed to remove cargo config: {}", e))?;
+    }
+    let _ = fs::remove_file(installed_info_path());
+    if vendor_dir().exists() {
+        fs::remove_dir_all(vendor_dir()).map_err(|e| format!("Failed to remove vendored sources: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_config() {
+        let vendored = "[source.crates-io]\nreplace-with = \"vendored-sources\"\n\n[source.vendored-sources]\ndirectory = \"/Users/a/VSTWorkshop/.vendor-export/vendor\"\n";
+        let config = offline_config(vendored, Path::new("/Users/b/VSTWorkshop/vendor"));
+        assert!(config.starts_with(CONFIG_HEADER));
+        assert!(config.contains("directory = \"/Users/b/VSTWorkshop/vendor\"\n"));
+        assert!(!config.contains(".vendor-export"));
+        assert!(config.ends_with("[net]\noffline = true\n"));
+    }
+}
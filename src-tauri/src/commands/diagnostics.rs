@@ -0,0 +1,225 @@
+//! Diagnostic bundle export
+//!
+//! Collects everything support usually asks for into one zip the user can
+//! attach to an issue: recent logs, prerequisite status, sanitized project
+//! metadata, the last build output, and crash reports.
+//!
+//! Paths under the user's home directory are rewritten to `~` and project
+//! descriptions are left out, so the bundle doesn't leak personal details.
+
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use super::build::get_last_build_log_path;
+use super::crash_reports::get_crash_reports_dir;
+use super::logging::get_log_dir;
+use super::projects::{get_workspace_path, ProjectMeta};
+
+/// Most recent rotated log files included in the bundle
+const MAX_LOG_FILES: usize = 3;
+
+/// Most recent crash reports included in the bundle
+const MAX_CRASH_REPORTS: usize = 10;
+
+#[derive(Serialize)]
+struct SystemInfo {
+    app_version: String,
+    os: &'static str,
+    arch: &'static str,
+    generated_at: String,
+}
+
+/// Project metadata with user-written text and paths removed
+#[derive(Serialize)]
+struct SanitizedProject {
+    name: String,
+    template: Option<String>,
+    #[serde(rename = "uiFramework")]
+    ui_framework: Option<String>,
+    components: Option<Vec<String>>,
+    created_at: String,
+    updated_at: String,
+    has_build_log: bool,
+}
+
+/// Replace the home directory with ~ so usernames don't end up in the bundle
+fn sanitize(text: &str) -> String {
+    match dirs::home_dir() {
+        Some(home) => text.replace(&*home.to_string_lossy(), "~"),
+        None => text.to_string(),
+    }
+}
+
+fn add_text_to_zip(
+    zip: &mut ZipWriter<File>,
+    name: &str,
+    text: &str,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {} to diagnostics: {}", name, e))?;
+    zip.write_all(sanitize(text).as_bytes())
+        .map_err(|e| format!("Failed to write {} to diagnostics: {}", name, e))
+}
+
+/// Files in a directory matching a filter, newest first
+fn newest_files(dir: &Path, limit: usize, filter: impl Fn(&str) -> bool) -> Vec<PathBuf> {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| filter(&e.file_name().to_string_lossy()))
+                .filter_map(|e| {
+                    let modified = e.metadata().ok()?.modified().ok()?;
+                    Some((modified, e.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files.into_iter().take(limit).map(|(_, path)| path).collect()
+}
+
+/// Crash report locations: the app's own reports and macOS's DiagnosticReports
+fn crash_report_files() -> Vec<PathBuf> {
+    #[cfg_attr(not(target_os = "macos"), allow(unused_mut))]
+    let mut reports = newest_files(
+        &get_crash_reports_dir(),
+        MAX_CRASH_REPORTS,
+        |name| name.ends_with(".txt"),
+    );
+
+    #[cfg(target_os = "macos")]
+    if let Some(home) = dirs::home_dir() {
+        reports.extend(newest_files(
+            &home.join("Library/Logs/DiagnosticReports"),
+            MAX_CRASH_REPORTS,
+            |name| name.to_lowercase().starts_with("freqlab"),
+        ));
+    }
+
+    reports
+}
+
+fn read_project_meta(project_path: &Path) -> Option<ProjectMeta> {
+    let content = fs::read_to_string(project_path.join(".vstworkshop/metadata.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Export a diagnostics zip for support requests
+/// Includes every project unless `project_name` narrows it to one. Returns the zip path.
+#[tauri::command]
+pub async fn export_diagnostics(
+    destination: String,
+    project_name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let projects_dir = get_workspace_path().join("projects");
+    if let Some(ref name) = project_name {
+        if !projects_dir.join(name).exists() {
+            return Err(format!("Project '{}' not found", name));
+        }
+    }
+
+    let prerequisites = super::prerequisites::check_prerequisites().await;
+    let app_version = app_handle.package_info().version.to_string();
+
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let zip_path = if destination.ends_with(".zip") {
+        PathBuf::from(&destination)
+    } else {
+        Path::new(&destination).join(format!("freqlab-diagnostics-{}.zip", timestamp))
+    };
+
+    let file = File::create(&zip_path)
+        .map_err(|e| format!("Failed to create diagnostics file: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let system = SystemInfo {
+        app_version,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        generated_at: chrono::Local::now().to_rfc3339(),
+    };
+    let system_json = serde_json::to_string_pretty(&system)
+        .map_err(|e| format!("Failed to serialize system info: {}", e))?;
+    add_text_to_zip(&mut zip, "system.json", &system_json, options)?;
+
+    let prerequisites_json = serde_json::to_string_pretty(&prerequisites)
+        .map_err(|e| format!("Failed to serialize prerequisites: {}", e))?;
+    add_text_to_zip(&mut zip, "prerequisites.json", &prerequisites_json, options)?;
+
+    // Logs
+    for log_file in newest_files(&get_log_dir(), MAX_LOG_FILES, |name| name.ends_with(".log")) {
+        if let (Some(name), Ok(content)) = (log_file.file_name(), fs::read_to_string(&log_file)) {
+            let name = format!("logs/{}", name.to_string_lossy());
+            add_text_to_zip(&mut zip, &name, &content, options)?;
+        }
+    }
+
+    // Projects and their last build output
+    let project_paths: Vec<PathBuf> = match project_name {
+        Some(ref name) => vec![projects_dir.join(name)],
+        None => fs::read_dir(&projects_dir)
+            .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+            .unwrap_or_default(),
+    };
+
+    let mut projects = Vec::new();
+    for project_path in project_paths {
+        let meta = match read_project_meta(&project_path) {
+            Some(meta) => meta,
+            None => continue,
+        };
+        let build_log = fs::read_to_string(get_last_build_log_path(&project_path)).ok();
+        if let Some(ref log) = build_log {
+            let name = format!("builds/{}.log", meta.name);
+            add_text_to_zip(&mut zip, &name, log, options)?;
+        }
+        projects.push(SanitizedProject {
+            name: meta.name,
+            template: meta.template,
+            ui_framework: meta.ui_framework,
+            components: meta.components,
+            created_at: meta.created_at,
+            updated_at: meta.updated_at,
+            has_build_log: build_log.is_some(),
+        });
+    }
+    let projects_json = serde_json::to_string_pretty(&projects)
+        .map_err(|e| format!("Failed to serialize projects: {}", e))?;
+    add_text_to_zip(&mut zip, "projects.json", &projects_json, options)?;
+
+    // Crash reports
+    for report in crash_report_files() {
+        if let (Some(name), Ok(content)) = (report.file_name(), fs::read_to_string(&report)) {
+            let name = format!("crash-reports/{}", name.to_string_lossy());
+            add_text_to_zip(&mut zip, &name, &content, options)?;
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize diagnostics: {}", e))?;
+
+    log::info!("Exported diagnostics to {:?}", zip_path);
+    Ok(zip_path.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_replaces_home() {
+        if let Some(home) = dirs::home_dir() {
+            let text = format!("error at {}/VSTWorkshop/projects/foo", home.to_string_lossy());
+            assert_eq!(sanitize(&text), "error at ~/VSTWorkshop/projects/foo");
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! THD+N / SNR measurement of a plugin
+//!
+//! Renders a pure sine through a plugin build offline and reports distortion
+//! and noise figures for each output channel, so saturation and distortion
+//! plugins can be judged by numbers as well as by ear.
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+use super::analysis_jobs::run_analysis_job;
+use super::compare::find_clap_bundle;
+use crate::audio::distortion::{
+    analysis_start, coherent_frequency, measure_channel, test_tone, ChannelDistortion, ANALYSIS_SIZE,
+};
+use crate::audio::engine::{get_engine_handle, get_engine_sample_rate};
+use crate::audio::offline::render_through_plugin;
+use crate::audio::plugin::PluginState;
+
+/// Sample rate for the measurement when the engine isn't running
+const DEFAULT_MEASURE_RATE: u32 = 48000;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DistortionReport {
+    /// Plugin bundle that was measured
+    pub plugin_path: String,
+    pub sample_rate: u32,
+    /// Requested test frequency
+    pub requested_frequency: f32,
+    /// Frequency actually used (snapped to an FFT bin)
+    pub frequency: f32,
+    /// Input level (dBFS)
+    pub level_db: f32,
+    pub left: ChannelDistortion,
+    pub right: ChannelDistortion,
+}
+
+/// Bundle of the plugin currently loaded in the preview engine
+pub(crate) fn hosted_plugin_path() -> Result<PathBuf, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    match handle.get_plugin_state() {
+        PluginState::Active { path, .. } => Ok(PathBuf::from(path)),
+        _ => Err("No plugin loaded - load one or pass a project and version".to_string()),
+    }
+}
+
+/// Bundle to analyse: a project version's build, or the hosted plugin when no project is given
+pub(crate) fn resolve_plugin_bundle(project_name: Option<String>, version: Option<u32>) -> Result<PathBuf, String> {
+    match project_name {
+        Some(name) => {
+            let version = version.ok_or_else(|| "A version is required when measuring a project".to_string())?;
+            find_clap_bundle(&name, version)
+        }
+        None => hosted_plugin_path(),
+    }
+}
+
+/// Measure a specific .clap bundle (1 kHz at -6 dBFS unless given)
+pub(crate) async fn measure_distortion_bundle(
+    bundle: PathBuf,
+    frequency: Option<f32>,
+    level_db: Option<f32>,
+    app_handle: Option<tauri::AppHandle>,
+) -> Result<DistortionReport, String> {
+    let sample_rate = get_engine_sample_rate().unwrap_or(DEFAULT_MEASURE_RATE);
+    let requested_frequency = frequency.unwrap_or(1000.0);
+    let level_db = level_db.unwrap_or(-6.0);
+
+    // Leave room for a few harmonics below Nyquist
+    let max_frequency = sample_rate as f32 / 4.0;
+    if !(20.0..=max_frequency).contains(&requested_frequency) {
+        return Err(format!("Frequency must be between 20 and {:.0} Hz", max_frequency));
+    }
+    if !(-60.0..=0.0).contains(&level_db) {
+        return Err("Level must be between -60 and 0 dBFS".to_string());
+    }
+
+    let frequency = coherent_frequency(requested_frequency, sample_rate);
+
+    run_analysis_job("measure_distortion", app_handle, move |job| {
+        log::info!(
+            "Measuring distortion of {:?}: {:.1} Hz @ {:.1} dBFS, {} Hz",
+            bundle,
+            frequency,
+            level_db,
+            sample_rate
+        );
+
+        let input = test_tone(frequency, level_db, sample_rate);
+        let output = render_through_plugin(&bundle, &input, sample_rate, job.render_progress(0, 1))?;
+
+        let start = analysis_start(sample_rate);
+        let window = output
+            .get(start..start + ANALYSIS_SIZE)
+            .ok_or_else(|| "Plugin render was too short to analyse".to_string())?;
+        let left: Vec<f32> = window.iter().map(|s| s.left).collect();
+        let right: Vec<f32> = window.iter().map(|s| s.right).collect();
+        let (left, right) = rayon::join(
+            || measure_channel(&left, frequency, sample_rate),
+            || measure_channel(&right, frequency, sample_rate),
+        );
+
+        Ok(DistortionReport {
+            plugin_path: bundle.to_string_lossy().to_string(),
+            sample_rate,
+            requested_frequency,
+            frequency,
+            level_db,
+            left: left?,
+            right: right?,
+        })
+    })
+    .await
+}
+
+/// Drive a plugin with a sine and measure THD+N, THD, and noise floor
+///
+/// Measures the given project version's build, or the plugin currently loaded
+/// in the preview when no project is given. Defaults to 1 kHz at -6 dBFS.
+#[tauri::command]
+pub async fn measure_distortion(
+    project_name: Option<String>,
+    version: Option<u32>,
+    frequency: Option<f32>,
+    level_db: Option<f32>,
+    app_handle: tauri::AppHandle,
+) -> Result<DistortionReport, String> {
+    let bundle = resolve_plugin_bundle(project_name, version)?;
+    measure_distortion_bundle(bundle, frequency, level_db, Some(app_handle)).await
+}
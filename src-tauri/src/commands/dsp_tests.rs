@@ -0,0 +1,808 @@
+//! Snapshot-based DSP unit tests for plugin projects
+//!
+//! Generates `src/dsp_snapshots.rs` in a project: unit tests that render known
+//! inputs (impulse, sine sweep, noise burst) through the plugin's process() and
+//! compare against golden outputs stored in `tests/golden/`. Missing goldens are
+//! recorded on the first run, so the first passing run locks in current
+//! behavior and later runs catch unintended changes.
+//!
+//! Automation test vectors exported to `tests/automation/` (recorded GUI
+//! gestures) are replayed over a test tone and checked the same way, plus a
+//! click check so "it clicks when I turn the knob fast" stays reproducible.
+//!
+//! The tests drive the plugin through the `clap_entry` that `nih_export_clap!`
+//! puts in the crate, with a minimal CLAP host in the generated module, so they
+//! exercise the same process() a DAW calls - parameters, smoothing and all.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tauri::Emitter;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+use super::projects::get_workspace_path;
+
+/// Generated test module (relative to the project root)
+const TEST_MODULE: &str = "src/dsp_snapshots.rs";
+
+/// Golden output folder (relative to the project root)
+const GOLDEN_DIR: &str = "tests/golden";
+
+/// Env var that makes the generated tests re-record every golden
+const UPDATE_GOLDEN_ENV: &str = "FREQLAB_UPDATE_GOLDEN";
+
+const MOD_DECLARATION: &str = "#[cfg(test)]\nmod dsp_snapshots;\n";
+
+const TEST_MODULE_SOURCE: &str = r#"// @generated by freqlab - regenerate from the app instead of editing by hand.
+//! Snapshot tests for the plugin's DSP.
+//!
+//! Each test renders a known input through the plugin's real process() - via
+//! the crate's exported clap_entry, the way a host runs it - and compares the
+//! result against a golden file in tests/golden/. Missing goldens are recorded
+//! on first run; set FREQLAB_UPDATE_GOLDEN=1 to re-record after an intended change.
+//! Automation vectors in tests/automation/ are replayed as CLAP parameter
+//! events over a 440 Hz tone.
+
+use std::path::PathBuf;
+
+const SAMPLE_RATE: f32 = 48000.0;
+
+/// Host block size for the renders
+const BLOCK_SIZE: usize = 512;
+
+/// Maximum allowed per-sample difference from the golden output
+const TOLERANCE: f32 = 1.0e-5;
+
+/// Sample-to-sample jump treated as a click (same threshold as the preview watchdog)
+const CLICK_THRESHOLD: f32 = 1.0;
+
+/// A recorded parameter change, replayed at `time` samples into the render
+#[derive(Debug, Clone)]
+struct AutomationPoint {
+    time: usize,
+    /// CLAP parameter ID, as recorded by freqlab
+    param_id: u32,
+    /// Plain value as reported by the plugin
+    value: f64,
+}
+
+/// The parts of the CLAP ABI the renders use (see clap/include/clap/*.h)
+#[allow(dead_code)]
+mod clap {
+    use std::ffi::{c_char, c_void};
+
+    pub const EVENT_PARAM_VALUE: u16 = 5;
+
+    #[repr(C)]
+    pub struct Version {
+        pub major: u32,
+        pub minor: u32,
+        pub revision: u32,
+    }
+
+    #[repr(C)]
+    pub struct Entry {
+        pub clap_version: Version,
+        pub init: Option<unsafe extern "C" fn(plugin_path: *const c_char) -> bool>,
+        pub deinit: Option<unsafe extern "C" fn()>,
+        pub get_factory: Option<unsafe extern "C" fn(factory_id: *const c_char) -> *const c_void>,
+    }
+
+    #[repr(C)]
+    pub struct Factory {
+        pub get_plugin_count: Option<unsafe extern "C" fn(factory: *const Factory) -> u32>,
+        pub get_plugin_descriptor: Option<unsafe extern "C" fn(factory: *const Factory, index: u32) -> *const Descriptor>,
+        pub create_plugin:
+            Option<unsafe extern "C" fn(factory: *const Factory, host: *const Host, plugin_id: *const c_char) -> *const Plugin>,
+    }
+
+    /// Only the leading fields; descriptors are read through pointers
+    #[repr(C)]
+    pub struct Descriptor {
+        pub clap_version: Version,
+        pub id: *const c_char,
+    }
+
+    #[repr(C)]
+    pub struct Host {
+        pub clap_version: Version,
+        pub host_data: *mut c_void,
+        pub name: *const c_char,
+        pub vendor: *const c_char,
+        pub url: *const c_char,
+        pub version: *const c_char,
+        pub get_extension: Option<unsafe extern "C" fn(host: *const Host, extension_id: *const c_char) -> *const c_void>,
+        pub request_restart: Option<unsafe extern "C" fn(host: *const Host)>,
+        pub request_process: Option<unsafe extern "C" fn(host: *const Host)>,
+        pub request_callback: Option<unsafe extern "C" fn(host: *const Host)>,
+    }
+
+    /// Only the leading fields; plugins are read through pointers
+    #[repr(C)]
+    pub struct Plugin {
+        pub desc: *const Descriptor,
+        pub plugin_data: *mut c_void,
+        pub init: Option<unsafe extern "C" fn(plugin: *const Plugin) -> bool>,
+        pub destroy: Option<unsafe extern "C" fn(plugin: *const Plugin)>,
+        pub activate: Option<unsafe extern "C" fn(plugin: *const Plugin, sample_rate: f64, min_frames: u32, max_frames: u32) -> bool>,
+        pub deactivate: Option<unsafe extern "C" fn(plugin: *const Plugin)>,
+        pub start_processing: Option<unsafe extern "C" fn(plugin: *const Plugin) -> bool>,
+        pub stop_processing: Option<unsafe extern "C" fn(plugin: *const Plugin)>,
+        pub reset: Option<unsafe extern "C" fn(plugin: *const Plugin)>,
+        pub process: Option<unsafe extern "C" fn(plugin: *const Plugin, process: *const Process) -> i32>,
+    }
+
+    #[repr(C)]
+    pub struct AudioBuffer {
+        pub data32: *mut *mut f32,
+        pub data64: *mut *mut f64,
+        pub channel_count: u32,
+        pub latency: u32,
+        pub constant_mask: u64,
+    }
+
+    #[repr(C)]
+    pub struct Process {
+        pub steady_time: i64,
+        pub frames_count: u32,
+        pub transport: *const c_void,
+        pub audio_inputs: *const AudioBuffer,
+        pub audio_outputs: *mut AudioBuffer,
+        pub audio_inputs_count: u32,
+        pub audio_outputs_count: u32,
+        pub in_events: *const InputEvents,
+        pub out_events: *const OutputEvents,
+    }
+
+    #[repr(C)]
+    pub struct InputEvents {
+        pub ctx: *mut c_void,
+        pub size: Option<unsafe extern "C" fn(list: *const InputEvents) -> u32>,
+        pub get: Option<unsafe extern "C" fn(list: *const InputEvents, index: u32) -> *const EventHeader>,
+    }
+
+    #[repr(C)]
+    pub struct OutputEvents {
+        pub ctx: *mut c_void,
+        pub try_push: Option<unsafe extern "C" fn(list: *const OutputEvents, event: *const EventHeader) -> bool>,
+    }
+
+    #[repr(C)]
+    pub struct EventHeader {
+        pub size: u32,
+        pub time: u32,
+        pub space_id: u16,
+        pub type_: u16,
+        pub flags: u32,
+    }
+
+    #[repr(C)]
+    pub struct ParamValue {
+        pub header: EventHeader,
+        pub param_id: u32,
+        pub cookie: *mut c_void,
+        pub note_id: i32,
+        pub port_index: i16,
+        pub channel: i16,
+        pub key: i16,
+        pub value: f64,
+    }
+
+    extern "C" {
+        /// Defined by nih_export_clap! in this crate
+        pub static clap_entry: Entry;
+    }
+
+    pub unsafe extern "C" fn no_extension(_host: *const Host, _id: *const c_char) -> *const c_void {
+        std::ptr::null()
+    }
+
+    pub unsafe extern "C" fn ignore_request(_host: *const Host) {}
+
+    pub unsafe extern "C" fn events_size(list: *const InputEvents) -> u32 {
+        (*((*list).ctx as *const Vec<ParamValue>)).len() as u32
+    }
+
+    pub unsafe extern "C" fn events_get(list: *const InputEvents, index: u32) -> *const EventHeader {
+        let events = &*((*list).ctx as *const Vec<ParamValue>);
+        events.get(index as usize).map_or(std::ptr::null(), |e| &e.header as *const EventHeader)
+    }
+
+    pub unsafe extern "C" fn discard_event(_list: *const OutputEvents, _event: *const EventHeader) -> bool {
+        true
+    }
+}
+
+/// Render through the plugin's process() like a host would: a fresh instance
+/// at default settings, BLOCK_SIZE blocks, automation as sample-accurate
+/// CLAP parameter events (`automation` must be sorted by time)
+fn render(input: &[[f32; 2]], sample_rate: f32, automation: &[AutomationPoint]) -> Vec<[f32; 2]> {
+    use clap::*;
+    use std::ffi::c_char;
+    use std::ptr::{null, null_mut};
+
+    let text = |s: &'static [u8]| s.as_ptr() as *const c_char;
+    unsafe {
+        let entry = &clap_entry;
+        assert!((entry.init.unwrap())(text(b"\0")), "clap_entry.init() failed");
+        let factory = (entry.get_factory.unwrap())(text(b"clap.plugin-factory\0")) as *const Factory;
+        assert!(!factory.is_null(), "the plugin has no CLAP plugin factory");
+        let descriptor = ((*factory).get_plugin_descriptor.unwrap())(factory, 0);
+        assert!(!descriptor.is_null(), "the CLAP factory has no plugins");
+
+        let host = Host {
+            clap_version: Version { major: 1, minor: 2, revision: 0 },
+            host_data: null_mut(),
+            name: text(b"freqlab snapshot tests\0"),
+            vendor: text(b"freqlab\0"),
+            url: text(b"\0"),
+            version: text(b"1.0\0"),
+            get_extension: Some(no_extension),
+            request_restart: Some(ignore_request),
+            request_process: Some(ignore_request),
+            request_callback: Some(ignore_request),
+        };
+        let plugin = ((*factory).create_plugin.unwrap())(factory, &host, (*descriptor).id);
+        assert!(!plugin.is_null(), "create_plugin() failed");
+        let p = &*plugin;
+        assert!((p.init.unwrap())(plugin), "plugin init() failed");
+        assert!(
+            (p.activate.unwrap())(plugin, sample_rate as f64, 1, BLOCK_SIZE as u32),
+            "plugin activate() failed"
+        );
+        assert!((p.start_processing.unwrap())(plugin), "plugin start_processing() failed");
+
+        let mut inputs = [vec![0.0f32; BLOCK_SIZE], vec![0.0f32; BLOCK_SIZE]];
+        let mut outputs = [vec![0.0f32; BLOCK_SIZE], vec![0.0f32; BLOCK_SIZE]];
+        let mut events: Vec<ParamValue> = Vec::new();
+        let out_events = OutputEvents {
+            ctx: null_mut(),
+            try_push: Some(discard_event),
+        };
+        let mut output = Vec::with_capacity(input.len());
+        let mut next_point = 0;
+
+        for (block, chunk) in input.chunks(BLOCK_SIZE).enumerate() {
+            let start = block * BLOCK_SIZE;
+            for (i, frame) in chunk.iter().enumerate() {
+                inputs[0][i] = frame[0];
+                inputs[1][i] = frame[1];
+            }
+
+            events.clear();
+            while let Some(point) = automation.get(next_point).filter(|p| p.time < start + chunk.len()) {
+                events.push(ParamValue {
+                    header: EventHeader {
+                        size: std::mem::size_of::<ParamValue>() as u32,
+                        time: point.time.saturating_sub(start) as u32,
+                        space_id: 0,
+                        type_: EVENT_PARAM_VALUE,
+                        flags: 0,
+                    },
+                    param_id: point.param_id,
+                    cookie: null_mut(),
+                    note_id: -1,
+                    port_index: -1,
+                    channel: -1,
+                    key: -1,
+                    value: point.value,
+                });
+                next_point += 1;
+            }
+            let in_events = InputEvents {
+                ctx: &events as *const Vec<ParamValue> as *mut std::ffi::c_void,
+                size: Some(events_size),
+                get: Some(events_get),
+            };
+
+            let mut in_channels = [inputs[0].as_mut_ptr(), inputs[1].as_mut_ptr()];
+            let mut out_channels = [outputs[0].as_mut_ptr(), outputs[1].as_mut_ptr()];
+            let audio_in = AudioBuffer {
+                data32: in_channels.as_mut_ptr(),
+                data64: null_mut(),
+                channel_count: 2,
+                latency: 0,
+                constant_mask: 0,
+            };
+            let mut audio_out = AudioBuffer {
+                data32: out_channels.as_mut_ptr(),
+                data64: null_mut(),
+                channel_count: 2,
+                latency: 0,
+                constant_mask: 0,
+            };
+            let process = Process {
+                steady_time: start as i64,
+                frames_count: chunk.len() as u32,
+                transport: null(),
+                audio_inputs: &audio_in,
+                audio_outputs: &mut audio_out,
+                audio_inputs_count: 1,
+                audio_outputs_count: 1,
+                in_events: &in_events,
+                out_events: &out_events,
+            };
+            assert_ne!((p.process.unwrap())(plugin, &process), 0, "plugin process() returned an error");
+            output.extend((0..chunk.len()).map(|i| [outputs[0][i], outputs[1][i]]));
+        }
+
+        (p.stop_processing.unwrap())(plugin);
+        (p.deactivate.unwrap())(plugin);
+        (p.destroy.unwrap())(plugin);
+        (entry.deinit.unwrap())();
+        output
+    }
+}
+
+fn impulse() -> Vec<[f32; 2]> {
+    let mut input = vec![[0.0; 2]; (SAMPLE_RATE * 0.1) as usize];
+    input[0] = [1.0, 1.0];
+    input
+}
+
+/// Exponential sine sweep, 20 Hz to 20 kHz over one second
+fn sine_sweep() -> Vec<[f32; 2]> {
+    let len = SAMPLE_RATE as usize;
+    let (start, end) = (20.0f64, 20000.0f64);
+    let k = (end / start).ln();
+    (0..len)
+        .map(|i| {
+            let t = i as f64 / SAMPLE_RATE as f64;
+            let phase = 2.0 * std::f64::consts::PI * start * (k * t).exp_m1() / k;
+            let s = (phase.sin() * 0.5) as f32;
+            [s, s]
+        })
+        .collect()
+}
+
+/// 100 ms of seeded white noise followed by 100 ms of silence
+fn noise_burst() -> Vec<[f32; 2]> {
+    let burst = (SAMPLE_RATE * 0.1) as usize;
+    let mut seed: u32 = 0x1234_5678;
+    let mut next = || {
+        seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (seed >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0
+    };
+    (0..burst * 2)
+        .map(|i| if i < burst { [next() * 0.5, next() * 0.5] } else { [0.0, 0.0] })
+        .collect()
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{}.bin", name))
+}
+
+fn write_golden(path: &PathBuf, output: &[[f32; 2]]) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let bytes: Vec<u8> = output
+        .iter()
+        .flat_map(|f| f.iter().flat_map(|s| s.to_le_bytes()))
+        .collect();
+    std::fs::write(path, bytes).unwrap();
+}
+
+fn read_golden(path: &PathBuf) -> Vec<[f32; 2]> {
+    let bytes = std::fs::read(path).unwrap();
+    bytes
+        .chunks_exact(8)
+        .map(|c| {
+            [
+                f32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                f32::from_le_bytes([c[4], c[5], c[6], c[7]]),
+            ]
+        })
+        .collect()
+}
+
+fn check_snapshot(name: &str, input: Vec<[f32; 2]>) {
+    let output = render(&input, SAMPLE_RATE, &[]);
+    check_output(name, &output);
+}
+
+fn check_output(name: &str, output: &[[f32; 2]]) {
+    assert!(
+        output.iter().all(|f| f[0].is_finite() && f[1].is_finite()),
+        "{}: output contains NaN/Inf",
+        name
+    );
+
+    let path = golden_path(name);
+    if std::env::var_os("FREQLAB_UPDATE_GOLDEN").is_some() || !path.exists() {
+        write_golden(&path, output);
+        eprintln!("recorded golden output for {}", name);
+        return;
+    }
+
+    let golden = read_golden(&path);
+    assert_eq!(golden.len(), output.len(), "{}: output length changed", name);
+
+    let (frame, diff) = output
+        .iter()
+        .zip(golden.iter())
+        .enumerate()
+        .map(|(i, (a, b))| (i, (a[0] - b[0]).abs().max((a[1] - b[1]).abs())))
+        .fold((0, 0.0f32), |best, cur| if cur.1 > best.1 { cur } else { best });
+    assert!(
+        diff <= TOLERANCE,
+        "{}: output differs from golden by {:e} at frame {}",
+        name,
+        diff,
+        frame
+    );
+}
+
+#[test]
+fn snapshot_impulse() {
+    check_snapshot("impulse", impulse());
+}
+
+#[test]
+fn snapshot_sine_sweep() {
+    check_snapshot("sine_sweep", sine_sweep());
+}
+
+#[test]
+fn snapshot_noise_burst() {
+    check_snapshot("noise_burst", noise_burst());
+}
+
+/// Load a test vector exported by freqlab, returning (sample rate, length, value events by time)
+fn load_automation(path: &std::path::Path) -> (f32, usize, Vec<AutomationPoint>) {
+    let text = std::fs::read_to_string(path).unwrap();
+    let vector: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+    let mut points: Vec<AutomationPoint> = vector["events"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+        .iter()
+        .filter(|e| e["type"] == "value")
+        .map(|e| AutomationPoint {
+            time: e["time"].as_u64().unwrap() as usize,
+            param_id: e["param_id"].as_u64().unwrap() as u32,
+            value: e["value"].as_f64().unwrap(),
+        })
+        .collect();
+    points.sort_by_key(|p| p.time);
+
+    (
+        vector["sample_rate"].as_f64().unwrap() as f32,
+        vector["length_samples"].as_u64().unwrap() as usize,
+        points,
+    )
+}
+
+#[test]
+fn snapshot_automation_vectors() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("automation");
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().map_or(true, |e| e != "json") {
+            continue;
+        }
+        let stem = path.file_stem().unwrap().to_string_lossy().to_string();
+        let (sample_rate, length, automation) = load_automation(&path);
+
+        let input: Vec<[f32; 2]> = (0..length)
+            .map(|i| {
+                let s = (2.0 * std::f32::consts::PI * 440.0 * i as f32 / sample_rate).sin() * 0.5;
+                [s, s]
+            })
+            .collect();
+        let output = render(&input, sample_rate, &automation);
+
+        let name = format!("automation_{}", stem);
+        check_output(&name, &output);
+
+        let mut prev = [0.0f32; 2];
+        for (i, frame) in output.iter().enumerate() {
+            for ch in 0..2 {
+                assert!(
+                    (frame[ch] - prev[ch]).abs() <= CLICK_THRESHOLD,
+                    "{}: click at frame {} (jump {:.3})",
+                    name,
+                    i,
+                    (frame[ch] - prev[ch]).abs()
+                );
+            }
+            prev = *frame;
+        }
+    }
+}
+"#;
+
+/// Pass-through `render_dsp` hook that earlier versions added to lib.rs
+const LEGACY_RENDER_HOOK: &str = r#"
+/// DSP core entry point for the snapshot tests in src/dsp_snapshots.rs
+/// Keep this running the same processing as Plugin::process (minus smoothing/host state)
+#[cfg(test)]
+pub(crate) fn render_dsp(input: &[[f32; 2]], _sample_rate: f32) -> Vec<[f32; 2]> {
+    // TODO: route the input through the plugin's DSP. Pass-through until then.
+    input.to_vec()
+}
+"#;
+
+/// Automation-ignoring `render_dsp_automated` hook that earlier versions added to lib.rs
+const LEGACY_AUTOMATED_HOOK: &str = r#"
+/// Like render_dsp, but applies each automation point at its sample time
+/// (use `point.normalized` with the matching param's preview_plain())
+#[cfg(test)]
+pub(crate) fn render_dsp_automated(
+    input: &[[f32; 2]],
+    sample_rate: f32,
+    _automation: &[dsp_snapshots::AutomationPoint],
+) -> Vec<[f32; 2]> {
+    // TODO: apply the automation. Ignores it until then.
+    render_dsp(input, sample_rate)
+}
+"#;
+
+/// serde_json is needed by the generated tests to read automation vectors
+const SERDE_JSON_DEV_DEP: &str = "serde_json = \"1\"";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DspTestHarnessInfo {
+    pub test_file: String,
+    pub golden_dir: String,
+    /// Goldens deleted because they were recorded through the old pass-through hook
+    pub discarded_goldens: usize,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum TestStreamEvent {
+    #[serde(rename = "start")]
+    Start,
+    #[serde(rename = "output")]
+    Output { line: String },
+    #[serde(rename = "done")]
+    Done { success: bool, passed: u32, failed: u32 },
+    #[serde(rename = "error")]
+    Error { message: String },
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TestRunResult {
+    pub success: bool,
+    pub passed: u32,
+    pub failed: u32,
+}
+
+fn get_project_path(project_name: &str) -> PathBuf {
+    get_workspace_path().join("projects").join(project_name)
+}
+
+/// Write the snapshot test module and make sure lib.rs declares it
+///
+/// Fails when the plugin doesn't export CLAP, since the tests render through
+/// its CLAP entry point. Stub hooks left by earlier versions are removed along
+/// with the goldens recorded through them (they snapshot the test signal).
+fn generate_harness(project_path: &Path) -> Result<DspTestHarnessInfo, String> {
+    let lib_path = project_path.join("src/lib.rs");
+    let mut lib_source =
+        fs::read_to_string(&lib_path).map_err(|e| format!("Failed to read lib.rs: {}", e))?;
+    if !lib_source.contains("nih_export_clap!") {
+        return Err(
+            "lib.rs has no nih_export_clap! - the snapshot tests render through the plugin's CLAP entry point".to_string(),
+        );
+    }
+
+    let mut changed = false;
+    let mut had_stub = false;
+    for stub in [LEGACY_RENDER_HOOK, LEGACY_AUTOMATED_HOOK] {
+        if lib_source.contains(stub) {
+            lib_source = lib_source.replace(stub, "");
+            had_stub = true;
+            changed = true;
+        }
+    }
+    if !lib_source.contains("mod dsp_snapshots;") {
+        lib_source.push('\n');
+        lib_source.push_str(MOD_DECLARATION);
+        changed = true;
+    }
+
+    fs::write(project_path.join(TEST_MODULE), TEST_MODULE_SOURCE)
+        .map_err(|e| format!("Failed to write dsp_snapshots.rs: {}", e))?;
+    if changed {
+        fs::write(&lib_path, lib_source).map_err(|e| format!("Failed to write lib.rs: {}", e))?;
+    }
+
+    let golden_dir = project_path.join(GOLDEN_DIR);
+    let mut discarded_goldens = 0;
+    if had_stub {
+        for entry in fs::read_dir(&golden_dir).into_iter().flatten().flatten() {
+            if entry.path().extension().is_some_and(|e| e == "bin") && fs::remove_file(entry.path()).is_ok() {
+                discarded_goldens += 1;
+            }
+        }
+    }
+    fs::create_dir_all(&golden_dir).map_err(|e| format!("Failed to create golden folder: {}", e))?;
+    ensure_dev_dependency(&project_path.join("Cargo.toml"))?;
+
+    Ok(DspTestHarnessInfo {
+        test_file: project_path.join(TEST_MODULE).to_string_lossy().to_string(),
+        golden_dir: golden_dir.to_string_lossy().to_string(),
+        discarded_goldens,
+    })
+}
+
+/// Add serde_json to the project's [dev-dependencies] if it isn't there yet
+fn ensure_dev_dependency(cargo_toml: &Path) -> Result<(), String> {
+    let manifest =
+        fs::read_to_string(cargo_toml).map_err(|e| format!("Failed to read Cargo.toml: {}", e))?;
+    if manifest.contains("serde_json") {
+        return Ok(());
+    }
+
+    let updated = match manifest.find("[dev-dependencies]") {
+        Some(pos) => {
+            let line_end = pos + "[dev-dependencies]".len();
+            format!("{}\n{}{}", &manifest[..line_end], SERDE_JSON_DEV_DEP, &manifest[line_end..])
+        }
+        None => format!("{}\n[dev-dependencies]\n{}\n", manifest.trim_end(), SERDE_JSON_DEV_DEP),
+    };
+    fs::write(cargo_toml, updated).map_err(|e| format!("Failed to write Cargo.toml: {}", e))
+}
+
+/// Parse cargo/libtest "test result:" summary lines into (passed, failed)
+fn parse_test_summary(line: &str) -> Option<(u32, u32)> {
+    let rest = line.trim().strip_prefix("test result:")?;
+    let count = |label: &str| {
+        rest.split(';')
+            .find_map(|part| part.trim().strip_suffix(label)?.trim().rsplit(' ').next()?.parse().ok())
+            .unwrap_or(0)
+    };
+    Some((count("passed"), count("failed")))
+}
+
+/// Parse a ctest "N% tests passed, M tests failed out of T" line into (passed, failed)
+fn parse_ctest_summary(line: &str) -> Option<(u32, u32)> {
+    let (_, rest) = line.split_once("tests passed, ")?;
+    let failed: u32 = rest.split_whitespace().next()?.parse().ok()?;
+    let total: u32 = rest.rsplit(' ').next()?.trim().parse().ok()?;
+    Some((total.saturating_sub(failed), failed))
+}
+
+/// Generate (or regenerate) the DSP snapshot test harness for a project
+#[tauri::command]
+pub async fn generate_dsp_tests(project_name: String) -> Result<DspTestHarnessInfo, String> {
+    let project_path = get_project_path(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+    generate_harness(&project_path)
+}
+
+/// Run a project's tests, streaming output as "test-stream" events
+/// Uses `cargo test` for Rust projects and `ctest` for CMake projects
+#[tauri::command]
+pub async fn run_project_tests(
+    project_name: String,
+    update_golden: Option<bool>,
+    window: tauri::Window,
+) -> Result<TestRunResult, String> {
+    let project_path = get_project_path(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+
+    let is_cmake = project_path.join("CMakeLists.txt").exists();
+    let mut command = if is_cmake {
+        let mut c = Command::new("ctest");
+        c.current_dir(project_path.join("build"))
+            .args(["--output-on-failure"]);
+        c
+    } else {
+        // Run from the workspace root (projects are workspace members)
+        let mut c = Command::new("cargo");
+        c.current_dir(get_workspace_path())
+            .args(["test", "-p", &project_name.replace('-', "_"), "--lib"]);
+        c
+    };
+    if update_golden.unwrap_or(false) {
+        command.env(UPDATE_GOLDEN_ENV, "1");
+    }
+
+    let _ = window.emit("test-stream", TestStreamEvent::Start);
+
+    let mut child = command
+        .env("PATH", super::get_extended_path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn test runner: {}", e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    let mut stdout_reader = BufReader::new(stdout).lines();
+    let mut stderr_reader = BufReader::new(stderr).lines();
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut stderr_open = true;
+
+    loop {
+        tokio::select! {
+            line = stdout_reader.next_line() => {
+                match line {
+                    Ok(Some(text)) => {
+                        let summary = if is_cmake {
+                            parse_ctest_summary(&text)
+                        } else {
+                            parse_test_summary(&text)
+                        };
+                        if let Some((p, f)) = summary {
+                            passed += p;
+                            failed += f;
+                        }
+                        let _ = window.emit("test-stream", TestStreamEvent::Output { line: text });
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        let _ = window.emit("test-stream", TestStreamEvent::Error {
+                            message: e.to_string(),
+                        });
+                        break;
+                    }
+                }
+            }
+            line = stderr_reader.next_line(), if stderr_open => {
+                match line {
+                    // cargo reports compile progress on stderr
+                    Ok(Some(text)) => {
+                        let _ = window.emit("test-stream", TestStreamEvent::Output { line: text });
+                    }
+                    _ => stderr_open = false,
+                }
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for test runner: {}", e))?;
+    let success = status.success() && failed == 0;
+
+    let _ = window.emit(
+        "test-stream",
+        TestStreamEvent::Done {
+            success,
+            passed,
+            failed,
+        },
+    );
+
+    Ok(TestRunResult {
+        success,
+        passed,
+        failed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_summaries() {
+        assert_eq!(
+            parse_test_summary("test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured"),
+            Some((2, 1))
+        );
+        assert_eq!(parse_test_summary("running 3 tests"), None);
+        assert_eq!(
+            parse_ctest_summary("67% tests passed, 1 tests failed out of 3"),
+            Some((2, 1))
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! External editor integration
+//!
+//! Generates `.vscode/tasks.json` and `launch.json` for projects (build and
+//! validate through `freqlab-cli`, build-and-reload through `freqlab-cli build
+//! --reload` so the automation token stays out of the project, and an attach
+//! configuration for debugging the plugin inside the preview) and opens build diagnostics at their file and line.
+
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+use super::activity::project_dir;
+use super::projects::get_workspace_path;
+use super::settings::current_settings;
+
+fn tasks_json(project_name: &str) -> serde_json::Value {
+    json!({
+        "version": "2.0.0",
+        "tasks": [
+            {
+                "label": "freqlab: build",
+                "detail": "Build and bundle with freqlab-cli (cargo install --path src-tauri --bin freqlab-cli)",
+                "type": "process",
+                "command": "freqlab-cli",
+                "args": ["build", project_name],
+                "group": { "kind": "build", "isDefault": true },
+                "problemMatcher": {
+                    "base": "$rustc",
+                    // cargo runs from ~/VSTWorkshop, two levels above the project
+                    "fileLocation": ["relative", "${workspaceFolder}/../.."]
+                }
+            },
+            {
+                "label": "freqlab: build and reload preview",
+                "detail": "Asks the running app to build and reload (needs the automation API on in Settings)",
+                "type": "process",
+                "command": "freqlab-cli",
+                "args": ["build", project_name, "--reload"],
+                "problemMatcher": []
+            },
+            {
+                "label": "freqlab: validate",
+                "detail": "Run the offline validation suite on the current build",
+                "type": "process",
+                "command": "freqlab-cli",
+                "args": ["validate", project_name],
+                "group": "test",
+                "problemMatcher": []
+            }
+        ]
+    })
+}
+
+/// Attach configuration - written as JSONC so the setup notes stay in the file
+const LAUNCH_JSON: &str = r#"{
+    "version": "0.2.0",
+    "configurations": [
+        {
+            // The preview hosts the plugin in-process, so attaching to the running
+            // app stops at breakpoints in this project's code. Requires CodeLLDB.
+            //
+            // Release bundles have no debug info - start a debug preview in
+            // freqlab first, which builds with symbols and loads that build.
+            "name": "Attach to freqlab preview",
+            "type": "lldb",
+            "request": "attach",
+            "program": "freqlab"
+        }
+    ]
+}
+"#;
+
+/// Write `.vscode/tasks.json` and `launch.json`, leaving existing files alone
+/// Returns the files written
+pub(crate) fn write_vscode_config(project_path: &Path, project_name: &str) -> Result<Vec<String>, String> {
+    let dir = project_path.join(".vscode");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create .vscode folder: {}", e))?;
+
+    let mut written = Vec::new();
+    let tasks = serde_json::to_string_pretty(&tasks_json(project_name))
+        .map_err(|e| format!("Failed to serialize tasks.json: {}", e))?;
+    for (file, content) in [("tasks.json", tasks.as_str()), ("launch.json", LAUNCH_JSON)] {
+        let path = dir.join(file);
+        if path.exists() {
+            continue;
+        }
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+/// Arguments that open a file at a line (and column) in an editor
+fn goto_args(editor: &str, path: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    let location = match (line, column) {
+        (Some(line), Some(column)) => format!("{}:{}:{}", path, line, column),
+        (Some(line), None) => format!("{}:{}", path, line),
+        _ => path.to_string(),
+    };
+    let program = Path::new(editor)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match (program.as_str(), line) {
+        (_, None) => vec![path.to_string()],
+        ("code" | "code-insiders" | "codium" | "cursor" | "windsurf", _) => vec!["-g".to_string(), location],
+        ("zed" | "subl" | "hx", _) => vec![location],
+        ("idea" | "rustrover" | "clion", Some(line)) => vec!["--line".to_string(), line.to_string(), path.to_string()],
+        ("vim" | "nvim" | "emacs" | "nano", Some(line)) => vec![format!("+{}", line), path.to_string()],
+        // Unknown editor: the path alone always works
+        _ => vec![path.to_string()],
+    }
+}
+
+/// Resolve a diagnostic path - cargo reports paths relative to the workspace root
+fn resolve_diagnostic_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        get_workspace_path().join(path)
+    }
+}
+
+/// Create the VS Code tasks and launch configuration for an existing project
+#[tauri::command]
+pub async fn generate_editor_config(project_name: String) -> Result<Vec<String>, String> {
+    let project_path = project_dir(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+    write_vscode_config(&project_path, &project_name)
+}
+
+/// Open a file at a line and column, e.g. from a build diagnostic
+/// (`projects/demo/src/lib.rs:42:9` arrives as path, 42, 9)
+#[tauri::command]
+pub async fn open_in_editor_at(
+    path: String,
+    line: Option<u32>,
+    column: Option<u32>,
+    editor: Option<String>,
+) -> Result<(), String> {
+    let editor_cmd = editor.unwrap_or_else(|| current_settings().editor);
+    let file = resolve_diagnostic_path(&path);
+    if !file.exists() {
+        return Err(format!("File not found: {}", file.display()));
+    }
+
+    std::process::Command::new(&editor_cmd)
+        .args(goto_args(&editor_cmd, &file.to_string_lossy(), line, column))
+        .env("PATH", super::get_extended_path())
+        .spawn()
+        .map_err(|e| format!("Failed to open in {}: {}. Make sure it's installed and in your PATH.", editor_cmd, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goto_args() {
+        assert_eq!(goto_args("code", "/p/src/lib.rs", Some(42), Some(9)), ["-g", "/p/src/lib.rs:42:9"]);
+        assert_eq!(goto_args("/usr/local/bin/zed", "/p/src/lib.rs", Some(42), None), ["/p/src/lib.rs:42"]);
+        assert_eq!(goto_args("nvim", "/p/src/lib.rs", Some(42), Some(9)), ["+42", "/p/src/lib.rs"]);
+        assert_eq!(goto_args("code", "/p/src/lib.rs", None, None), ["/p/src/lib.rs"]);
+        assert_eq!(goto_args("mystery-editor", "/p/src/lib.rs", Some(1), Some(1)), ["/p/src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_tasks_json_targets_project() {
+        let tasks = tasks_json("warm-drive");
+        let tasks = tasks["tasks"].as_array().unwrap();
+        assert_eq!(tasks[0]["args"], json!(["build", "warm-drive"]));
+        assert_eq!(tasks[1]["args"], json!(["build", "warm-drive", "--reload"]));
+    }
+}
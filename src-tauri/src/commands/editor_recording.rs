@@ -0,0 +1,206 @@
+//! Demo clips of the plugin editor
+//!
+//! Records the region of the screen under the preview's plugin editor window
+//! with macOS's `screencapture`, then converts it with ffmpeg to an mp4 or GIF
+//! short enough to attach to release notes. Clips are saved in the project's
+//! `demos/` folder so exported packages carry them along.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::activity::project_dir;
+use crate::audio::plugin::editor::{self, PreviewWindowKind};
+
+const MIN_CLIP_SECS: u32 = 1;
+const MAX_CLIP_SECS: u32 = 30;
+
+/// Frame rate and width cap for GIFs - higher makes files balloon quickly
+const GIF_FPS: u32 = 15;
+const GIF_MAX_WIDTH: u32 = 800;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipFormat {
+    Mp4,
+    Gif,
+}
+
+impl ClipFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ClipFormat::Mp4 => "mp4",
+            ClipFormat::Gif => "gif",
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct EditorClip {
+    pub path: String,
+    pub format: ClipFormat,
+    pub duration_secs: u32,
+    pub size_bytes: u64,
+}
+
+/// Arguments for `screencapture` recording a screen region as video
+fn capture_args(rect: (f64, f64, f64, f64), duration_secs: u32, output: &Path) -> Vec<String> {
+    let (x, y, width, height) = rect;
+    vec![
+        "-v".to_string(),
+        "-x".to_string(),
+        format!("-V{}", duration_secs),
+        format!("-R{},{},{},{}", x.round(), y.round(), width.round(), height.round()),
+        output.to_string_lossy().to_string(),
+    ]
+}
+
+/// Arguments for ffmpeg converting the raw capture to the requested format
+fn convert_args(input: &Path, output: &Path, format: ClipFormat) -> Vec<String> {
+    let mut args = vec!["-y".to_string(), "-i".to_string(), input.to_string_lossy().to_string()];
+    match format {
+        // Even dimensions and yuv420p so the mp4 plays in browsers and QuickTime
+        ClipFormat::Mp4 => args.extend(
+            [
+                "-vf",
+                "scale=trunc(iw/2)*2:trunc(ih/2)*2",
+                "-c:v",
+                "libx264",
+                "-pix_fmt",
+                "yuv420p",
+                "-movflags",
+                "+faststart",
+                "-an",
+            ]
+            .map(String::from),
+        ),
+        // Single-pass palette generation keeps GIF colors close to the editor's
+        ClipFormat::Gif => args.extend([
+            "-vf".to_string(),
+            format!(
+                "fps={},scale='min({},iw)':-1:flags=lanczos,split[a][b];[a]palettegen[p];[b][p]paletteuse",
+                GIF_FPS, GIF_MAX_WIDTH
+            ),
+            "-loop".to_string(),
+            "0".to_string(),
+        ]),
+    }
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+fn run_tool(program: &str, args: &[String], install_hint: &str) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .env("PATH", super::get_extended_path())
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => format!("{} is not installed. {}", program, install_hint),
+            _ => format!("Failed to run {}: {}", program, e),
+        })?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+fn clip_path(project_name: &str, format: ClipFormat) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    project_dir(project_name)
+        .join("demos")
+        .join(format!("{}-{}.{}", project_name, stamp, format.extension()))
+}
+
+/// Record the open plugin editor window for `duration_secs` (clamped to 1-30s)
+/// and save it to the project's `demos/` folder as an mp4 or GIF
+#[tauri::command]
+pub async fn record_editor_clip(
+    project_name: String,
+    duration_secs: u32,
+    format: Option<ClipFormat>,
+) -> Result<EditorClip, String> {
+    if !cfg!(target_os = "macos") {
+        return Err("Editor recording is only available on macOS".to_string());
+    }
+    if !project_dir(&project_name).exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+    let format = format.unwrap_or(ClipFormat::Mp4);
+    let duration_secs = duration_secs.clamp(MIN_CLIP_SECS, MAX_CLIP_SECS);
+
+    let window = editor::registered_window(PreviewWindowKind::PluginEditor)
+        .filter(|&w| editor::is_window_visible(w))
+        .ok_or_else(|| "Open the plugin editor before recording".to_string())?;
+    // Bring it forward so nothing covers it during the capture
+    editor::restore_window(window);
+    let rect = editor::get_window_capture_rect(window)
+        .ok_or_else(|| "Failed to get the editor window's frame".to_string())?;
+
+    let output = clip_path(&project_name, format);
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create demos folder: {}", e))?;
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let raw = std::env::temp_dir().join(format!("freqlab-editor-{}.mov", uuid::Uuid::new_v4()));
+        log::info!("Recording editor window at {:?} for {}s", rect, duration_secs);
+        let result = run_tool(
+            "screencapture",
+            &capture_args(rect, duration_secs, &raw),
+            "It ships with macOS.",
+        )
+        .and_then(|_| {
+            if !raw.exists() {
+                // screencapture exits cleanly when Screen Recording permission is missing
+                return Err("No recording was made. Allow freqlab under System Settings > \
+                            Privacy & Security > Screen Recording."
+                    .to_string());
+            }
+            run_tool(
+                "ffmpeg",
+                &convert_args(&raw, &output, format),
+                "Install it with `brew install ffmpeg`.",
+            )
+        });
+        let _ = std::fs::remove_file(&raw);
+        result?;
+
+        let size_bytes = std::fs::metadata(&output)
+            .map(|m| m.len())
+            .map_err(|e| format!("Failed to read clip: {}", e))?;
+        Ok(EditorClip {
+            path: output.to_string_lossy().to_string(),
+            format,
+            duration_secs,
+            size_bytes,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_args() {
+        let args = capture_args((120.4, 80.0, 640.0, 479.6), 5, Path::new("/tmp/clip.mov"));
+        assert_eq!(args, ["-v", "-x", "-V5", "-R120,80,640,480", "/tmp/clip.mov"]);
+    }
+
+    #[test]
+    fn test_convert_args() {
+        let gif = convert_args(Path::new("in.mov"), Path::new("out.gif"), ClipFormat::Gif);
+        assert_eq!(gif.first().map(String::as_str), Some("-y"));
+        assert!(gif.iter().any(|a| a.contains("palettegen") && a.starts_with("fps=15")));
+        assert_eq!(gif.last().map(String::as_str), Some("out.gif"));
+
+        let mp4 = convert_args(Path::new("in.mov"), Path::new("out.mp4"), ClipFormat::Mp4);
+        assert!(mp4.windows(2).any(|w| w == ["-pix_fmt", "yuv420p"]));
+    }
+}
@@ -0,0 +1,202 @@
+//! Framework upgrades
+//!
+//! Projects pin nih-plug to a git revision in their Cargo.toml. New projects get
+//! `NIH_PLUG_REV`; older projects stay on whatever was current when they were
+//! created. `upgrade_framework` moves a project to a revision the caller picks,
+//! verifies it still builds, and restores the previous manifest and lockfile if
+//! it doesn't.
+//!
+//! Only nih-plug projects exist today. The shared xtask keeps its own pin since
+//! every project builds through it.
+
+use serde::Serialize;
+use std::fs;
+use std::process::Stdio;
+use tokio::process::Command;
+
+use super::build::to_package_name;
+use super::projects::get_workspace_path;
+
+/// Lines of build output kept in the report when verification fails
+const MAX_REPORT_LINES: usize = 60;
+
+const NIH_PLUG_GIT: &str = "robbert-vdh/nih-plug";
+
+#[derive(Serialize)]
+pub struct FrameworkUpgradeReport {
+    pub framework: String,
+    /// Revision(s) the project was pinned to before the upgrade
+    pub from_revisions: Vec<String>,
+    pub to_revision: String,
+    /// Dependency names whose pin was changed
+    pub updated_dependencies: Vec<String>,
+    /// The project was already on the target revision - nothing was changed
+    pub up_to_date: bool,
+    pub build_succeeded: bool,
+    pub rolled_back: bool,
+    /// Tail of the verification build output (only when it failed)
+    pub build_output: Option<String>,
+}
+
+/// Rewrite `rev = "..."` on every nih-plug git dependency
+/// Returns the updated manifest, the old revisions found, and the dependencies changed.
+fn bump_nih_plug_rev(cargo_toml: &str, to_rev: &str) -> (String, Vec<String>, Vec<String>) {
+    let mut from_revisions = Vec::new();
+    let mut updated_dependencies = Vec::new();
+
+    let lines: Vec<String> = cargo_toml
+        .lines()
+        .map(|line| {
+            if !line.contains(NIH_PLUG_GIT) {
+                return line.to_string();
+            }
+            let Some(rev_start) = line.find("rev = \"").map(|i| i + "rev = \"".len()) else {
+                return line.to_string();
+            };
+            let Some(rev_len) = line[rev_start..].find('"') else {
+                return line.to_string();
+            };
+            let current = &line[rev_start..rev_start + rev_len];
+            if !from_revisions.iter().any(|r| r == current) {
+                from_revisions.push(current.to_string());
+            }
+            if current == to_rev {
+                return line.to_string();
+            }
+            let dependency = line.split('=').next().unwrap_or_default().trim().to_string();
+            updated_dependencies.push(dependency);
+            format!("{}{}{}", &line[..rev_start], to_rev, &line[rev_start + rev_len..])
+        })
+        .collect();
+
+    let mut updated = lines.join("\n");
+    if cargo_toml.ends_with('\n') {
+        updated.push('\n');
+    }
+    (updated, from_revisions, updated_dependencies)
+}
+
+/// Last `max` lines of build output
+fn tail_lines(output: &str, max: usize) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+    lines[lines.len().saturating_sub(max)..].join("\n")
+}
+
+/// Move a project's pinned nih-plug dependencies to `revision` and verify it still builds
+/// On a failed build the project's Cargo.toml and the workspace Cargo.lock are restored.
+#[tauri::command]
+pub async fn upgrade_framework(
+    project_name: String,
+    revision: String,
+) -> Result<FrameworkUpgradeReport, String> {
+    let to_rev = revision.trim().to_string();
+    if to_rev.is_empty() || !to_rev.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid git revision '{}'", to_rev));
+    }
+
+    let workspace_path = get_workspace_path();
+    let project_path = workspace_path.join("projects").join(&project_name);
+    let cargo_path = project_path.join("Cargo.toml");
+    let original_manifest = fs::read_to_string(&cargo_path)
+        .map_err(|_| format!("Project '{}' not found", project_name))?;
+
+    let (updated_manifest, from_revisions, updated_dependencies) =
+        bump_nih_plug_rev(&original_manifest, &to_rev);
+    if from_revisions.is_empty() {
+        return Err("No pinned nih-plug dependency found in Cargo.toml".to_string());
+    }
+
+    let mut report = FrameworkUpgradeReport {
+        framework: "nih-plug".to_string(),
+        from_revisions,
+        to_revision: to_rev.clone(),
+        updated_dependencies,
+        up_to_date: false,
+        build_succeeded: false,
+        rolled_back: false,
+        build_output: None,
+    };
+    if report.updated_dependencies.is_empty() {
+        report.up_to_date = true;
+        report.build_succeeded = true;
+        return Ok(report);
+    }
+
+    // The lockfile is shared by every project in the workspace
+    let lock_path = workspace_path.join("Cargo.lock");
+    let original_lock = fs::read_to_string(&lock_path).ok();
+
+    fs::write(&cargo_path, &updated_manifest)
+        .map_err(|e| format!("Failed to write Cargo.toml: {}", e))?;
+    log::info!(
+        "Upgrading {} nih-plug {:?} -> {}",
+        project_name,
+        report.from_revisions,
+        to_rev
+    );
+
+    let output = Command::new("cargo")
+        .current_dir(&workspace_path)
+        .args(["build", "--release", "-p", &to_package_name(&project_name)])
+        .env("PATH", super::get_extended_path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await;
+
+    let build_error = match output {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => Some(tail_lines(&String::from_utf8_lossy(&output.stderr), MAX_REPORT_LINES)),
+        Err(e) => Some(format!("Failed to run cargo: {}", e)),
+    };
+
+    match build_error {
+        None => {
+            report.build_succeeded = true;
+            if super::git::is_git_repo(&project_path.to_string_lossy()) {
+                let message = format!("Upgrade nih-plug to {}", to_rev);
+                if let Err(e) = super::git::commit_changes(&project_path.to_string_lossy(), &message).await {
+                    log::warn!("Failed to commit framework upgrade: {}", e);
+                }
+            }
+        }
+        Some(build_output) => {
+            fs::write(&cargo_path, &original_manifest)
+                .map_err(|e| format!("Build failed and Cargo.toml could not be restored: {}", e))?;
+            if let Some(lock) = original_lock {
+                let _ = fs::write(&lock_path, lock);
+            }
+            log::warn!("nih-plug upgrade for {} failed to build, rolled back", project_name);
+            report.rolled_back = true;
+            report.build_output = Some(build_output);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_nih_plug_rev() {
+        let cargo = r#"[dependencies]
+nih_plug = { git = "https://github.com/robbert-vdh/nih-plug.git", rev = "aaaa1111" }
+nih_plug_egui = { git = "https://github.com/robbert-vdh/nih-plug.git", rev = "aaaa1111" }
+nih_plug_webview = { git = "https://github.com/jamesontucker/nih-plug-webview" }
+egui = "0.24"
+"#;
+        let (updated, from, deps) = bump_nih_plug_rev(cargo, "bbbb2222");
+        assert_eq!(from, vec!["aaaa1111"]);
+        assert_eq!(deps, vec!["nih_plug", "nih_plug_egui"]);
+        assert_eq!(updated.matches("rev = \"bbbb2222\"").count(), 2);
+        assert!(updated.contains("nih_plug_webview = { git = \"https://github.com/jamesontucker/nih-plug-webview\" }"));
+
+        // Already on the target revision
+        let (same, from, deps) = bump_nih_plug_rev(&updated, "bbbb2222");
+        assert_eq!(same, updated);
+        assert_eq!(from, vec!["bbbb2222"]);
+        assert!(deps.is_empty());
+    }
+}
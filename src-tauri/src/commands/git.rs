@@ -87,6 +87,8 @@ fn commit_changes_sync(path: &str, message: &str) -> Result<String, String> {
         "Cargo.toml",     // Project manifest
         "Cargo.lock",     // Dependencies lock
         ".gitignore",     // Git config (created at project setup)
+        "presets/",       // Factory presets compiled into the plugin
+        "tests/",         // DSP snapshot goldens
     ];
 
     for pattern in &source_patterns {
@@ -97,7 +99,7 @@ fn commit_changes_sync(path: &str, message: &str) -> Result<String, String> {
         // Ignore errors - pattern might not match any files
     }
 
-    eprintln!("[DEBUG] Staged source files only (src/, Cargo.toml, Cargo.lock, .gitignore)");
+    eprintln!("[DEBUG] Staged source files only (src/, Cargo.toml, Cargo.lock, .gitignore, presets/)");
 
     // Check if there are STAGED changes to commit (not just any changes)
     // git diff --cached --quiet exits with 1 if there are staged changes, 0 if none
@@ -167,6 +169,43 @@ pub async fn get_head_commit(path: &str) -> Result<String, String> {
         .map_err(|e| format!("Task join error: {}", e))?
 }
 
+/// List all tracked file paths at a given commit (blocking)
+pub fn list_files_at_commit(path: &str, commit: &str) -> Result<Vec<String>, String> {
+    let output = git_command()
+        .current_dir(path)
+        .args(["ls-tree", "-r", "-z", "--name-only", commit])
+        .output()
+        .map_err(|e| format!("Failed to run git ls-tree: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git ls-tree failed: {}", stderr));
+    }
+
+    Ok(output
+        .stdout
+        .split(|b| *b == 0)
+        .filter(|p| !p.is_empty())
+        .map(|p| String::from_utf8_lossy(p).to_string())
+        .collect())
+}
+
+/// Read the raw contents of a file as it existed at a given commit (blocking)
+pub fn read_file_at_commit(path: &str, commit: &str, file: &str) -> Result<Vec<u8>, String> {
+    let output = git_command()
+        .current_dir(path)
+        .args(["show", &format!("{}:{}", commit, file)])
+        .output()
+        .map_err(|e| format!("Failed to run git show: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git show failed for {}: {}", file, stderr));
+    }
+
+    Ok(output.stdout)
+}
+
 /// Revert files to a specific commit - blocking implementation
 fn revert_to_commit_sync(
     project_path: &str,
@@ -321,3 +360,336 @@ fn truncate_string(s: &str, max_len: usize) -> String {
         format!("{}...", &s[..max_len.saturating_sub(3)])
     }
 }
+
+// ============================================================================
+// Remote sync (GitHub/GitLab push/pull)
+// ============================================================================
+
+/// Name used for the project's sync remote
+const SYNC_REMOTE: &str = "origin";
+
+/// Ahead/behind status of a project repo relative to its remote
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct RemoteStatus {
+    #[serde(rename = "remoteUrl")]
+    pub remote_url: Option<String>,
+    pub branch: String,
+    pub ahead: u32,
+    pub behind: u32,
+    #[serde(rename = "hasUpstream")]
+    pub has_upstream: bool,
+}
+
+/// Create a git command for network operations (push/pull/fetch)
+/// Disables interactive prompts so a missing credential fails fast instead of hanging,
+/// and forwards the system SSH agent socket so keys loaded in the agent are used
+fn git_network_command() -> Command {
+    let mut cmd = git_command();
+    cmd.env("GIT_TERMINAL_PROMPT", "0");
+    // BatchMode stops ssh from prompting for passphrases/host keys on a TTY we don't have
+    cmd.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes -o StrictHostKeyChecking=accept-new");
+    if let Some(sock) = ssh_auth_sock() {
+        cmd.env("SSH_AUTH_SOCK", sock);
+    }
+    cmd
+}
+
+/// Locate the SSH agent socket
+/// Bundled macOS apps launched from Finder don't inherit SSH_AUTH_SOCK from the shell,
+/// so fall back to asking launchd for the agent socket it manages
+fn ssh_auth_sock() -> Option<String> {
+    if let Ok(sock) = std::env::var("SSH_AUTH_SOCK") {
+        if !sock.is_empty() {
+            return Some(sock);
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let output = Command::new("launchctl")
+            .args(["getenv", "SSH_AUTH_SOCK"])
+            .output()
+            .ok()?;
+        let sock = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !sock.is_empty() {
+            return Some(sock);
+        }
+    }
+
+    None
+}
+
+/// Validate a remote URL - only allow transports we expect for GitHub/GitLab style hosting
+/// Rejects things like `ext::` or `file://` which could run arbitrary commands or read local paths
+fn validate_remote_url(url: &str) -> Result<(), String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err("Remote URL cannot be empty".to_string());
+    }
+    if url.starts_with('-') || url.contains(char::is_whitespace) {
+        return Err("Remote URL is invalid".to_string());
+    }
+
+    let is_https = url.starts_with("https://") || url.starts_with("http://");
+    let is_ssh_url = url.starts_with("ssh://");
+    // scp-style: git@github.com:user/repo.git
+    let is_scp_style = !url.contains("://")
+        && url
+            .split_once(':')
+            .map(|(host, path)| host.contains('@') && !host.contains('/') && !path.is_empty())
+            .unwrap_or(false);
+
+    if is_https || is_ssh_url || is_scp_style {
+        Ok(())
+    } else {
+        Err("Remote URL must be an HTTPS or SSH URL (e.g. https://github.com/user/repo.git or git@github.com:user/repo.git)".to_string())
+    }
+}
+
+/// Map common network git failures to a friendlier message
+fn describe_network_error(action: &str, stderr: &str) -> String {
+    let lower = stderr.to_lowercase();
+    if lower.contains("permission denied (publickey)") {
+        format!(
+            "git {} failed: SSH key was rejected. Make sure your key is added to the SSH agent (ssh-add) and registered with the host.",
+            action
+        )
+    } else if lower.contains("terminal prompts disabled") || lower.contains("could not read username") {
+        format!(
+            "git {} failed: no stored HTTPS credentials. Configure a git credential helper (e.g. osxkeychain) or use an SSH remote.",
+            action
+        )
+    } else if lower.contains("not possible to fast-forward") || lower.contains("diverging branches") {
+        format!(
+            "git {} failed: local and remote history have diverged. Resolve this outside freqlab before syncing again.",
+            action
+        )
+    } else {
+        format!("git {} failed: {}", action, stderr.trim())
+    }
+}
+
+/// Get the current branch name (blocking)
+fn current_branch_sync(path: &str) -> Result<String, String> {
+    let output = git_command()
+        .current_dir(path)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .map_err(|e| format!("Failed to run git rev-parse: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to determine current branch: {}", stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Get the URL of the sync remote, if configured (blocking)
+fn remote_url_sync(path: &str) -> Option<String> {
+    let output = git_command()
+        .current_dir(path)
+        .args(["remote", "get-url", SYNC_REMOTE])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if url.is_empty() {
+        None
+    } else {
+        Some(url)
+    }
+}
+
+/// Attach (or replace) the sync remote (blocking)
+fn set_remote_sync(path: &str, url: &str) -> Result<(), String> {
+    validate_remote_url(url)?;
+
+    let args: Vec<&str> = if remote_url_sync(path).is_some() {
+        vec!["remote", "set-url", SYNC_REMOTE, url.trim()]
+    } else {
+        vec!["remote", "add", SYNC_REMOTE, url.trim()]
+    };
+
+    let output = git_command()
+        .current_dir(path)
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed to run git remote: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git remote failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Remove the sync remote (blocking)
+fn remove_remote_sync(path: &str) -> Result<(), String> {
+    if remote_url_sync(path).is_none() {
+        return Ok(());
+    }
+
+    let output = git_command()
+        .current_dir(path)
+        .args(["remote", "remove", SYNC_REMOTE])
+        .output()
+        .map_err(|e| format!("Failed to run git remote: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git remote remove failed: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Push the current branch to the sync remote, setting upstream (blocking)
+fn push_sync(path: &str) -> Result<(), String> {
+    if remote_url_sync(path).is_none() {
+        return Err("No remote configured for this project".to_string());
+    }
+
+    let branch = current_branch_sync(path)?;
+    let output = git_network_command()
+        .current_dir(path)
+        .args(["push", "-u", SYNC_REMOTE, &branch])
+        .output()
+        .map_err(|e| format!("Failed to run git push: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(describe_network_error("push", &stderr));
+    }
+
+    Ok(())
+}
+
+/// Pull from the sync remote (fast-forward only, never creates merge commits) (blocking)
+fn pull_sync(path: &str) -> Result<String, String> {
+    if remote_url_sync(path).is_none() {
+        return Err("No remote configured for this project".to_string());
+    }
+
+    let branch = current_branch_sync(path)?;
+    let output = git_network_command()
+        .current_dir(path)
+        .args(["pull", "--ff-only", SYNC_REMOTE, &branch])
+        .output()
+        .map_err(|e| format!("Failed to run git pull: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(describe_network_error("pull", &stderr));
+    }
+
+    get_current_commit_sync(path)
+}
+
+/// Fetch and compute ahead/behind counts against the upstream branch (blocking)
+fn remote_status_sync(path: &str) -> Result<RemoteStatus, String> {
+    let branch = current_branch_sync(path)?;
+    let remote_url = remote_url_sync(path);
+
+    let mut status = RemoteStatus {
+        remote_url: remote_url.clone(),
+        branch: branch.clone(),
+        ahead: 0,
+        behind: 0,
+        has_upstream: false,
+    };
+
+    if remote_url.is_none() {
+        return Ok(status);
+    }
+
+    let fetch = git_network_command()
+        .current_dir(path)
+        .args(["fetch", SYNC_REMOTE])
+        .output()
+        .map_err(|e| format!("Failed to run git fetch: {}", e))?;
+
+    if !fetch.status.success() {
+        let stderr = String::from_utf8_lossy(&fetch.stderr);
+        return Err(describe_network_error("fetch", &stderr));
+    }
+
+    // Compare against the remote branch of the same name (exists once pushed at least once)
+    let remote_ref = format!("{}/{}", SYNC_REMOTE, branch);
+    let counts = git_command()
+        .current_dir(path)
+        .args([
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("HEAD...{}", remote_ref),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run git rev-list: {}", e))?;
+
+    if !counts.status.success() {
+        // Remote branch doesn't exist yet - everything local is "ahead"
+        let local = git_command()
+            .current_dir(path)
+            .args(["rev-list", "--count", "HEAD"])
+            .output()
+            .map_err(|e| format!("Failed to run git rev-list: {}", e))?;
+        status.ahead = String::from_utf8_lossy(&local.stdout).trim().parse().unwrap_or(0);
+        return Ok(status);
+    }
+
+    let text = String::from_utf8_lossy(&counts.stdout);
+    let mut parts = text.split_whitespace();
+    status.ahead = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+    status.behind = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+    status.has_upstream = true;
+
+    Ok(status)
+}
+
+/// Attach a remote (GitHub/GitLab/etc) to a project repo, replacing any existing one
+#[tauri::command]
+pub async fn git_set_remote(project_path: String, url: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || set_remote_sync(&project_path, &url))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Detach the remote from a project repo
+#[tauri::command]
+pub async fn git_remove_remote(project_path: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || remove_remote_sync(&project_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Push the project's current branch to its remote
+/// Credentials come from the system SSH agent or git credential helper - never prompts
+#[tauri::command]
+pub async fn git_push(project_path: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || push_sync(&project_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Pull remote changes into the project (fast-forward only)
+/// Returns the new HEAD commit hash
+#[tauri::command]
+pub async fn git_pull(project_path: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || pull_sync(&project_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Fetch from the remote and report how far ahead/behind the project is
+#[tauri::command]
+pub async fn git_remote_status(project_path: String) -> Result<RemoteStatus, String> {
+    tokio::task::spawn_blocking(move || remote_status_sync(&project_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
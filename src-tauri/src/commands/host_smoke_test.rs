@@ -0,0 +1,450 @@
+//! Host compatibility smoke tests
+//!
+//! `run_host_smoke_tests` writes a smoke-test procedure per DAW for a release
+//! build into `output/{project}/v{version}/smoke-tests/`, runs what can be run
+//! without a person in front of the DAW, and saves the results as
+//! `compatibility-report.json`:
+//!
+//! - REAPER: a ReaScript that loads each format on a track and records whether
+//!   it instantiated; run by starting REAPER with the script when `run` is set
+//!   and the copies in REAPER's plugin folders match the build
+//! - Ableton Live: its `Log.txt` is read for what the last plugin scan said
+//!   about the bundle
+//! - Logic Pro: reported as not applicable - Logic only loads Audio Units,
+//!   which nih-plug doesn't build
+//! - FL Studio: manual steps only
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::activity::project_dir;
+use super::build::to_package_name;
+use super::identity::read_const_str;
+use super::install_verify::check_bundle;
+use super::projects::get_output_path;
+use super::publish::expand_tilde;
+use super::settings::current_settings;
+
+const SMOKE_TEST_DIR: &str = "smoke-tests";
+const REAPER_SCRIPT: &str = "reaper_smoke_test.lua";
+const REAPER_RESULTS: &str = "reaper-results.txt";
+/// Written by the script when it starts, so a crash can be told from REAPER never running it
+const REAPER_STARTED: &str = "reaper-started.txt";
+
+/// How long REAPER gets to start, scan, and load the plugin
+const REAPER_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Words in a DAW's log that mean the plugin had a problem
+const FAILURE_WORDS: [&str; 6] = ["error", "fail", "crash", "timed out", "could not", "couldn't"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HostStatus {
+    Passed,
+    Failed,
+    /// Scriptable, but wasn't run (or had nothing to look at yet)
+    NotRun,
+    /// Only a person can do this one; see the procedure
+    Manual,
+    NotApplicable,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HostResult {
+    pub daw: String,
+    pub status: HostStatus,
+    /// Steps to test by hand (or what the script does)
+    pub procedure: Vec<String>,
+    /// What the run or log check found
+    pub details: Vec<String>,
+    /// Generated script, if the DAW has one
+    pub script: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityReport {
+    pub project: String,
+    pub version: u32,
+    pub plugin_name: String,
+    /// "VST3", "CLAP"
+    pub formats: Vec<String>,
+    pub checked_at: String,
+    pub hosts: Vec<HostResult>,
+}
+
+fn lua_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// ReaScript that marks `started_path`, adds each format of the plugin to a
+/// track, checks it instantiated, and writes one PASS/FAIL line per format to
+/// `results_path`
+fn reaper_script(plugin_name: &str, formats: &[String], started_path: &Path, results_path: &Path) -> String {
+    let fx_names: Vec<String> = formats
+        .iter()
+        .map(|format| lua_string(&format!("{}: {}", format, plugin_name)))
+        .collect();
+    format!(
+        r#"-- Smoke test for {name}: load each format on a new track and record the result
+local started = io.open({started}, "w")
+started:write("started")
+started:close()
+local results = {{}}
+for _, fx in ipairs({{ {fx_names} }}) do
+  reaper.InsertTrackAtIndex(0, false)
+  local track = reaper.GetTrack(0, 0)
+  local index = reaper.TrackFX_AddByName(track, fx, false, -1)
+  if index < 0 then
+    table.insert(results, "FAIL " .. fx .. ": not found (publish it and let REAPER rescan)")
+  else
+    local params = reaper.TrackFX_GetNumParams(track, index)
+    reaper.TrackFX_Show(track, index, 3) -- open the editor in a floating window
+    reaper.TrackFX_Show(track, index, 2) -- and close it again
+    table.insert(results, "PASS " .. fx .. ": loaded with " .. params .. " parameters")
+  end
+  reaper.DeleteTrack(track)
+end
+local file = io.open({results}, "w")
+file:write(table.concat(results, "\n"))
+file:close()
+"#,
+        name = plugin_name,
+        fx_names = fx_names.join(", "),
+        started = lua_string(&started_path.to_string_lossy()),
+        results = lua_string(&results_path.to_string_lossy()),
+    )
+}
+
+/// Status and details from the script's PASS/FAIL lines
+fn parse_reaper_results(results: &str) -> (HostStatus, Vec<String>) {
+    let lines: Vec<String> = results.lines().filter(|l| !l.trim().is_empty()).map(String::from).collect();
+    let status = if lines.is_empty() {
+        HostStatus::NotRun
+    } else if lines.iter().any(|l| l.starts_with("FAIL")) {
+        HostStatus::Failed
+    } else {
+        HostStatus::Passed
+    };
+    (status, lines)
+}
+
+/// What Live's log says about the plugin: lines naming the bundle or plugin,
+/// failed if any of them reports a problem (None when the log never mentions it)
+fn parse_ableton_log(log: &str, names: &[String]) -> Option<(HostStatus, Vec<String>)> {
+    let lines: Vec<String> = log
+        .lines()
+        .filter(|line| names.iter().any(|name| line.contains(name.as_str())))
+        .map(|line| line.trim().to_string())
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    let failed = lines.iter().any(|line| {
+        let lower = line.to_lowercase();
+        FAILURE_WORDS.iter().any(|word| lower.contains(word))
+    });
+    Some((if failed { HostStatus::Failed } else { HostStatus::Passed }, lines))
+}
+
+/// Live's most recently written `Log.txt`
+fn ableton_log_path() -> Option<PathBuf> {
+    let live_dirs = if cfg!(target_os = "macos") {
+        dirs::home_dir()?.join("Library/Preferences/Ableton")
+    } else if cfg!(windows) {
+        dirs::config_dir()?.join("Ableton")
+    } else {
+        return None;
+    };
+    fs::read_dir(live_dirs)
+        .ok()?
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("Live "))
+        .map(|e| {
+            if cfg!(windows) {
+                e.path().join("Preferences").join("Log.txt")
+            } else {
+                e.path().join("Log.txt")
+            }
+        })
+        .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+fn reaper_executable() -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = if cfg!(target_os = "macos") {
+        vec![
+            PathBuf::from("/Applications/REAPER.app/Contents/MacOS/REAPER"),
+            PathBuf::from("/Applications/REAPER64.app/Contents/MacOS/REAPER"),
+        ]
+    } else if cfg!(windows) {
+        vec![PathBuf::from(r"C:\Program Files\REAPER (x64)\reaper.exe")]
+    } else {
+        let mut paths = vec![PathBuf::from("/usr/bin/reaper"), PathBuf::from("/usr/local/bin/reaper")];
+        paths.extend(dirs::home_dir().map(|home| home.join("opt/REAPER/reaper")));
+        paths
+    };
+    candidates.into_iter().find(|path| path.exists())
+}
+
+/// How a REAPER run ended
+#[derive(Debug)]
+enum ReaperRun {
+    /// The script wrote its results
+    Finished(String),
+    /// The script started but REAPER exited or hung before it finished -
+    /// most likely the plugin crashed or froze it
+    Aborted(String),
+    /// REAPER couldn't be started or never ran the script
+    NotStarted(String),
+}
+
+impl ReaperRun {
+    fn into_result(self) -> (HostStatus, Vec<String>) {
+        match self {
+            ReaperRun::Finished(results) => parse_reaper_results(&results),
+            ReaperRun::Aborted(reason) => (HostStatus::Failed, vec![reason]),
+            ReaperRun::NotStarted(reason) => (HostStatus::NotRun, vec![reason]),
+        }
+    }
+}
+
+/// Start REAPER with the script and wait for its results file, then close REAPER
+async fn run_reaper(script: &Path, started_path: &Path, results_path: &Path) -> ReaperRun {
+    let Some(executable) = reaper_executable() else {
+        return ReaperRun::NotStarted("REAPER isn't installed in its default location".to_string());
+    };
+    let _ = fs::remove_file(started_path);
+    let _ = fs::remove_file(results_path);
+    let mut child = match tokio::process::Command::new(executable)
+        .args(["-newinst", "-nosplash", "-new"])
+        .arg(script)
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return ReaperRun::NotStarted(format!("Failed to start REAPER: {}", e)),
+    };
+
+    let started = std::time::Instant::now();
+    let results = loop {
+        if let Ok(results) = fs::read_to_string(results_path) {
+            break Ok(results);
+        }
+        if started.elapsed() > REAPER_TIMEOUT {
+            break Err("REAPER didn't finish the smoke test in time".to_string());
+        }
+        if let Ok(Some(status)) = child.try_wait() {
+            break Err(format!("REAPER exited ({}) before finishing the smoke test", status));
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+    // The test project has unsaved changes; don't leave REAPER asking about them
+    let _ = child.kill().await;
+    match results {
+        Ok(results) => ReaperRun::Finished(results),
+        Err(reason) if started_path.exists() => {
+            ReaperRun::Aborted(format!("{} - the plugin may have crashed or hung it", reason))
+        }
+        Err(reason) => ReaperRun::NotStarted(reason),
+    }
+}
+
+/// Problems with the copies REAPER would load: missing, or not this build
+fn reaper_install_problems(output_path: &Path, package: &str, formats: &[String]) -> Vec<String> {
+    let paths = current_settings().daw_paths.reaper;
+    formats
+        .iter()
+        .filter_map(|format| {
+            let (dir, extension) = if format == "VST3" { (&paths.vst3, "vst3") } else { (&paths.clap, "clap") };
+            let name = format!("{}.{}", package, extension);
+            let check = check_bundle(&output_path.join(&name), &expand_tilde(dir).join(&name), format);
+            if check.installed && check.matches_build == Some(true) {
+                return None;
+            }
+            Some(format!("{} in {}: {}", format, dir, check.problems.join("; ")))
+        })
+        .collect()
+}
+
+fn reaper_procedure(plugin_name: &str) -> Vec<String> {
+    vec![
+        "Publish the build to REAPER's plugin folders and let REAPER rescan".to_string(),
+        format!("Run {} from Actions > Show action list > Load ReaScript, or let freqlab start it", REAPER_SCRIPT),
+        format!("The script adds \"{}\" in each format to a track, opens and closes its editor, and records the result", plugin_name),
+    ]
+}
+
+fn ableton_procedure() -> Vec<String> {
+    vec![
+        "Publish the VST3 build to Ableton's plugin folder (Live doesn't load CLAP)".to_string(),
+        "In Live, open Settings > Plug-ins and click Rescan".to_string(),
+        "Run the smoke test again to read the scan results from Live's Log.txt".to_string(),
+        "Drag the plugin onto a track and open its editor".to_string(),
+    ]
+}
+
+fn fl_studio_procedure(plugin_name: &str) -> Vec<String> {
+    vec![
+        "Publish the build to FL Studio's plugin folders".to_string(),
+        "Open Options > Manage plugins and click Find installed plugins".to_string(),
+        format!("Check that \"{}\" is listed without errors and mark it as a favorite", plugin_name),
+        "Load it on a mixer insert, open its editor, play audio through it, and save and reopen the project".to_string(),
+    ]
+}
+
+fn render_procedures(report: &CompatibilityReport) -> String {
+    let mut md = format!(
+        "# Host smoke tests: {} v{}\n\nFormats: {}\n",
+        report.plugin_name,
+        report.version,
+        report.formats.join(", ")
+    );
+    for host in &report.hosts {
+        md.push_str(&format!("\n## {}\n\n", host.daw));
+        for (i, step) in host.procedure.iter().enumerate() {
+            md.push_str(&format!("{}. {}\n", i + 1, step));
+        }
+    }
+    md
+}
+
+/// Write smoke-test procedures and scripts for each DAW, run the scriptable
+/// ones when `run` is set, and save a per-DAW compatibility report
+#[tauri::command]
+pub async fn run_host_smoke_tests(project_name: String, version: u32, run: bool) -> Result<CompatibilityReport, String> {
+    let output_path = get_output_path().join(&project_name).join(format!("v{}", version));
+    let package = to_package_name(&project_name);
+    let formats: Vec<String> = [("VST3", "vst3"), ("CLAP", "clap")]
+        .into_iter()
+        .filter(|(_, extension)| output_path.join(format!("{}.{}", package, extension)).exists())
+        .map(|(format, _)| format.to_string())
+        .collect();
+    if formats.is_empty() {
+        return Err(format!("No v{} build found. Build the project first.", version));
+    }
+    let plugin_name = fs::read_to_string(project_dir(&project_name).join("src/lib.rs"))
+        .ok()
+        .and_then(|source| read_const_str(&source, "NAME"))
+        .unwrap_or_else(|| package.clone());
+
+    let dir = output_path.join(SMOKE_TEST_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    // REAPER
+    let script_path = dir.join(REAPER_SCRIPT);
+    let started_path = dir.join(REAPER_STARTED);
+    let results_path = dir.join(REAPER_RESULTS);
+    fs::write(&script_path, reaper_script(&plugin_name, &formats, &started_path, &results_path))
+        .map_err(|e| format!("Failed to write REAPER script: {}", e))?;
+    let (status, details) = if run {
+        // REAPER loads whatever is installed, which may be another version
+        let stale = reaper_install_problems(&output_path, &package, &formats);
+        if stale.is_empty() {
+            run_reaper(&script_path, &started_path, &results_path).await.into_result()
+        } else {
+            let mut details = vec![format!("Publish v{} to REAPER's plugin folders first", version)];
+            details.extend(stale);
+            (HostStatus::NotRun, details)
+        }
+    } else {
+        (HostStatus::NotRun, Vec::new())
+    };
+    let reaper = HostResult {
+        daw: "REAPER".to_string(),
+        status,
+        procedure: reaper_procedure(&plugin_name),
+        details,
+        script: Some(script_path.to_string_lossy().to_string()),
+    };
+
+    // Ableton Live
+    let ableton = if formats.iter().any(|f| f == "VST3") {
+        let names = vec![format!("{}.vst3", package), plugin_name.clone()];
+        let log = ableton_log_path().and_then(|path| fs::read_to_string(path).ok());
+        let (status, details) = match log.as_deref().map(|log| parse_ableton_log(log, &names)) {
+            Some(Some(found)) => found,
+            Some(None) => (HostStatus::NotRun, vec!["Live's log doesn't mention the plugin yet".to_string()]),
+            None => (HostStatus::NotRun, vec!["No Ableton Live log found".to_string()]),
+        };
+        HostResult {
+            daw: "Ableton Live".to_string(),
+            status,
+            procedure: ableton_procedure(),
+            details,
+            script: None,
+        }
+    } else {
+        HostResult {
+            daw: "Ableton Live".to_string(),
+            status: HostStatus::NotApplicable,
+            procedure: Vec::new(),
+            details: vec!["Live loads VST3, and this build has no VST3".to_string()],
+            script: None,
+        }
+    };
+
+    let logic = HostResult {
+        daw: "Logic Pro".to_string(),
+        status: HostStatus::NotApplicable,
+        procedure: Vec::new(),
+        details: vec!["Logic only loads Audio Units; this build has VST3 and CLAP only".to_string()],
+        script: None,
+    };
+
+    let fl_studio = HostResult {
+        daw: "FL Studio".to_string(),
+        status: HostStatus::Manual,
+        procedure: fl_studio_procedure(&plugin_name),
+        details: Vec::new(),
+        script: None,
+    };
+
+    let report = CompatibilityReport {
+        project: project_name,
+        version,
+        plugin_name,
+        formats,
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        hosts: vec![reaper, ableton, logic, fl_studio],
+    };
+    fs::write(dir.join("procedures.md"), render_procedures(&report))
+        .map_err(|e| format!("Failed to write procedures: {}", e))?;
+    let json = serde_json::to_string_pretty(&report).map_err(|e| format!("Failed to serialize report: {}", e))?;
+    fs::write(dir.join("compatibility-report.json"), json).map_err(|e| format!("Failed to save report: {}", e))?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_host_results() {
+        let formats = ["VST3".to_string(), "CLAP".to_string()];
+        let script = reaper_script("Warm \"Drive\"", &formats, Path::new("/tmp/s.txt"), Path::new("/tmp/r.txt"));
+        assert!(script.contains(r#"for _, fx in ipairs({ "VST3: Warm \"Drive\"", "CLAP: Warm \"Drive\"" }) do"#));
+        assert!(script.contains(r#"io.open("/tmp/s.txt", "w")"#));
+        assert!(script.contains(r#"io.open("/tmp/r.txt", "w")"#));
+
+        // A run that started but never finished is a failure, one that never started isn't
+        assert_eq!(ReaperRun::Aborted("exited".to_string()).into_result().0, HostStatus::Failed);
+        assert_eq!(ReaperRun::NotStarted("missing".to_string()).into_result().0, HostStatus::NotRun);
+
+        let (status, details) = parse_reaper_results("PASS VST3: Warm Drive: loaded with 4 parameters\nFAIL CLAP: Warm Drive: not found\n");
+        assert_eq!(status, HostStatus::Failed);
+        assert_eq!(details.len(), 2);
+
+        let names = vec!["warm_drive.vst3".to_string(), "Warm Drive".to_string()];
+        let log = "2026-03-01T10:00:00: info: Scanning /Library/Audio/Plug-Ins/VST3/warm_drive.vst3\n\
+                   2026-03-01T10:00:01: info: Scan of other.vst3 failed\n";
+        assert_eq!(parse_ableton_log(log, &names).map(|(s, _)| s), Some(HostStatus::Passed));
+        let log = "2026-03-01T10:00:01: error: Plugin warm_drive.vst3 crashed during scan\n";
+        assert_eq!(parse_ableton_log(log, &names).map(|(s, _)| s), Some(HostStatus::Failed));
+        assert_eq!(parse_ableton_log("nothing here", &names), None);
+    }
+}
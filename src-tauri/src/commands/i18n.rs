@@ -0,0 +1,112 @@
+//! Translations for backend-generated text
+//!
+//! Messages the backend writes for the user (install progress, project creation
+//! phases, tutorial tasks) are looked up by key in the catalog for the language
+//! in settings. Catalogs are flat JSON objects in `resources/locales/`, with
+//! `{name}` placeholders; keys missing from a catalog fall back to English.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+use super::settings::current_settings;
+
+pub(crate) const DEFAULT_LANGUAGE: &str = "en";
+
+/// (code, native name, catalog)
+const CATALOGS: &[(&str, &str, &str)] = &[
+    ("en", "English", include_str!("../../resources/locales/en.json")),
+    ("de", "Deutsch", include_str!("../../resources/locales/de.json")),
+    ("es", "Español", include_str!("../../resources/locales/es.json")),
+];
+
+static PARSED: Lazy<HashMap<&'static str, HashMap<String, String>>> = Lazy::new(|| {
+    CATALOGS
+        .iter()
+        .map(|(code, _, json)| {
+            let catalog = serde_json::from_str(json).unwrap_or_else(|e| {
+                log::error!("Invalid {} catalog: {}", code, e);
+                HashMap::new()
+            });
+            (*code, catalog)
+        })
+        .collect()
+});
+
+pub(crate) fn is_supported(language: &str) -> bool {
+    CATALOGS.iter().any(|(code, _, _)| *code == language)
+}
+
+/// Native name of a language ("Deutsch" for "de")
+pub(crate) fn language_name(language: &str) -> Option<&'static str> {
+    CATALOGS.iter().find(|(code, _, _)| *code == language).map(|(_, name, _)| *name)
+}
+
+/// Language from settings
+pub(crate) fn current_language() -> String {
+    current_settings().language
+}
+
+fn lookup(language: &str, key: &str) -> String {
+    [language, DEFAULT_LANGUAGE]
+        .iter()
+        .find_map(|code| PARSED.get(code)?.get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// A message in the user's language
+pub(crate) fn tr(key: &str) -> String {
+    lookup(&current_language(), key)
+}
+
+/// A message in the user's language with its `{name}` placeholders filled in
+pub(crate) fn tr_with(key: &str, args: &[(&str, &str)]) -> String {
+    fill(&lookup(&current_language(), key), args)
+}
+
+/// Languages the backend has catalogs for, as (code, native name)
+#[tauri::command]
+pub async fn list_languages() -> Result<Vec<(String, String)>, String> {
+    Ok(CATALOGS.iter().map(|(code, name, _)| (code.to_string(), name.to_string())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_catalogs_match_english() {
+        let english = &PARSED[DEFAULT_LANGUAGE];
+        assert!(!english.is_empty());
+        for (code, _, _) in CATALOGS {
+            let catalog = &PARSED[code];
+            for (key, text) in english {
+                let translated = catalog.get(key).unwrap_or_else(|| panic!("{} is missing {}", code, key));
+                assert_eq!(placeholders(translated), placeholders(text), "{} {}", code, key);
+            }
+            assert!(catalog.keys().all(|k| english.contains_key(k)), "{} has keys English doesn't", code);
+        }
+    }
+
+    #[test]
+    fn test_lookup_falls_back_to_english() {
+        assert_eq!(lookup("xx", "create.prepare"), PARSED["en"]["create.prepare"]);
+        assert_eq!(lookup("de", "no.such.key"), "no.such.key");
+        assert_eq!(fill("Found: {package}", &[("package", "CLT 16")]), "Found: CLT 16");
+    }
+}
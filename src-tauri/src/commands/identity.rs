@@ -0,0 +1,299 @@
+//! Plugin identity editing
+//!
+//! Vendor name, URL, email, and the plugin's bundle ID are written into the
+//! generated source at project creation. These commands read them back and
+//! rewrite them in place so they don't drift from reality.
+//!
+//! Only nih-plug projects exist today: the values live in `src/lib.rs`
+//! (`Plugin::VENDOR/URL/EMAIL`, `ClapPlugin::CLAP_ID`) and Cargo.toml
+//! (`authors`, `homepage`). The macOS bundle's Info.plist is generated by
+//! `cargo xtask bundle` from those, so it picks up changes on the next build.
+
+use std::fs;
+use std::path::Path;
+
+use super::projects::{get_workspace_path, PluginIdentity, ProjectMeta};
+
+/// Escape a value for a double-quoted string (same rules for Rust and TOML basic strings)
+fn escape_quoted(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Read the string value of `const NAME: &'static str = "...";`
+pub(crate) fn read_const_str(source: &str, name: &str) -> Option<String> {
+    let prefix = format!("const {}:", name);
+    source.lines().find_map(|line| {
+        let trimmed = line.trim();
+        if !trimmed.starts_with(&prefix) {
+            return None;
+        }
+        let start = trimmed.find('"')? + 1;
+        let end = trimmed.rfind('"')?;
+        (end >= start).then(|| trimmed[start..end].replace("\\\"", "\"").replace("\\\\", "\\"))
+    })
+}
+
+/// Replace the string value of `const NAME: &'static str = "...";`
+/// Returns None if the constant isn't declared as a plain string literal.
+pub(crate) fn replace_const_str(source: &str, name: &str, value: &str) -> Option<String> {
+    let prefix = format!("const {}:", name);
+    let mut found = false;
+    let lines: Vec<String> = source
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if found || !trimmed.starts_with(&prefix) {
+                return line.to_string();
+            }
+            match (line.find('"'), line.rfind('"')) {
+                (Some(start), Some(end)) if end > start => {
+                    found = true;
+                    format!("{}{}{}", &line[..=start], escape_quoted(value), &line[end..])
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+
+    found.then(|| {
+        let mut updated = lines.join("\n");
+        if source.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated
+    })
+}
+
+/// Set (or remove, when `value` is None) a key in Cargo.toml's [package] table
+fn set_package_field(cargo_toml: &str, key: &str, value: Option<&str>) -> String {
+    let mut lines: Vec<String> = cargo_toml.lines().map(String::from).collect();
+    let package_start = lines.iter().position(|l| l.trim() == "[package]");
+    let Some(package_start) = package_start else {
+        return cargo_toml.to_string();
+    };
+    let package_end = lines
+        .iter()
+        .skip(package_start + 1)
+        .position(|l| l.trim_start().starts_with('['))
+        .map(|i| i + package_start + 1)
+        .unwrap_or(lines.len());
+
+    let existing = (package_start + 1..package_end).find(|&i| {
+        let line = lines[i].trim_start();
+        line.strip_prefix(key)
+            .map(|rest| rest.trim_start().starts_with('='))
+            .unwrap_or(false)
+    });
+
+    match (existing, value) {
+        (Some(i), Some(value)) => lines[i] = format!("{} = {}", key, value),
+        (Some(i), None) => {
+            lines.remove(i);
+        }
+        (None, Some(value)) => {
+            // Insert after the last non-blank line of the [package] table
+            let mut insert_at = package_end;
+            while insert_at > package_start + 1 && lines[insert_at - 1].trim().is_empty() {
+                insert_at -= 1;
+            }
+            lines.insert(insert_at, format!("{} = {}", key, value));
+        }
+        (None, None) => {}
+    }
+
+    let mut updated = lines.join("\n");
+    if cargo_toml.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+fn validate_identity(identity: &PluginIdentity) -> Result<(), String> {
+    if identity.vendor_name.trim().is_empty() {
+        return Err("Vendor name cannot be empty".to_string());
+    }
+    if !identity.vendor_url.is_empty()
+        && !identity.vendor_url.starts_with("https://")
+        && !identity.vendor_url.starts_with("http://")
+    {
+        return Err("Vendor URL must start with http:// or https://".to_string());
+    }
+    if !identity.vendor_email.is_empty() && !identity.vendor_email.contains('@') {
+        return Err("Vendor email is not a valid address".to_string());
+    }
+
+    let bundle_id = &identity.bundle_id;
+    let segments: Vec<&str> = bundle_id.split('.').collect();
+    if segments.len() < 2 || segments.iter().any(|s| s.is_empty()) {
+        return Err("Bundle ID must be reverse-DNS style, e.g. com.vendor.my_plugin".to_string());
+    }
+    if !bundle_id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
+    {
+        return Err("Bundle ID can only contain letters, numbers, dots, hyphens, and underscores".to_string());
+    }
+    Ok(())
+}
+
+/// Read the identity currently compiled into a project's source
+fn read_identity_from_source(project_path: &Path) -> Result<PluginIdentity, String> {
+    let lib_rs = fs::read_to_string(project_path.join("src/lib.rs"))
+        .map_err(|e| format!("Failed to read lib.rs: {}", e))?;
+    Ok(PluginIdentity {
+        vendor_name: read_const_str(&lib_rs, "VENDOR").unwrap_or_default(),
+        vendor_url: read_const_str(&lib_rs, "URL").unwrap_or_default(),
+        vendor_email: read_const_str(&lib_rs, "EMAIL").unwrap_or_default(),
+        bundle_id: read_const_str(&lib_rs, "CLAP_ID").unwrap_or_default(),
+    })
+}
+
+fn read_meta(project_path: &Path) -> Result<ProjectMeta, String> {
+    let content = fs::read_to_string(project_path.join(".vstworkshop/metadata.json"))
+        .map_err(|e| format!("Failed to read metadata: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse metadata: {}", e))
+}
+
+/// Get a project's plugin identity as it currently appears in the source
+#[tauri::command]
+pub async fn get_plugin_identity(project_name: String) -> Result<PluginIdentity, String> {
+    let project_path = get_workspace_path().join("projects").join(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+    read_identity_from_source(&project_path)
+}
+
+/// Rewrite a project's vendor details and bundle ID in its source and manifest
+/// Commits the change to the project's git history and records it in metadata.
+#[tauri::command]
+pub async fn update_plugin_identity(
+    project_name: String,
+    identity: PluginIdentity,
+) -> Result<ProjectMeta, String> {
+    validate_identity(&identity)?;
+
+    let project_path = get_workspace_path().join("projects").join(&project_name);
+    let mut meta = read_meta(&project_path)
+        .map_err(|_| format!("Project '{}' not found", project_name))?;
+
+    super::plugin_ids::check_clap_id_available(&project_name, &identity.bundle_id)?;
+
+    let lib_path = project_path.join("src/lib.rs");
+    let mut lib_rs = fs::read_to_string(&lib_path)
+        .map_err(|e| format!("Failed to read lib.rs: {}", e))?;
+    for (name, value) in [
+        ("VENDOR", &identity.vendor_name),
+        ("URL", &identity.vendor_url),
+        ("EMAIL", &identity.vendor_email),
+        ("CLAP_ID", &identity.bundle_id),
+    ] {
+        lib_rs = replace_const_str(&lib_rs, name, value)
+            .ok_or_else(|| format!("Could not find `const {}` in src/lib.rs", name))?;
+    }
+
+    let cargo_path = project_path.join("Cargo.toml");
+    let mut cargo_toml = fs::read_to_string(&cargo_path)
+        .map_err(|e| format!("Failed to read Cargo.toml: {}", e))?;
+    let author = if identity.vendor_email.is_empty() {
+        identity.vendor_name.clone()
+    } else {
+        format!("{} <{}>", identity.vendor_name, identity.vendor_email)
+    };
+    cargo_toml = set_package_field(
+        &cargo_toml,
+        "authors",
+        Some(&format!("[\"{}\"]", escape_quoted(&author))),
+    );
+    let homepage = (!identity.vendor_url.is_empty())
+        .then(|| format!("\"{}\"", escape_quoted(&identity.vendor_url)));
+    cargo_toml = set_package_field(&cargo_toml, "homepage", homepage.as_deref());
+
+    // Write only after both files were rewritten successfully
+    fs::write(&lib_path, lib_rs).map_err(|e| format!("Failed to write lib.rs: {}", e))?;
+    fs::write(&cargo_path, cargo_toml)
+        .map_err(|e| format!("Failed to write Cargo.toml: {}", e))?;
+
+    super::plugin_ids::record_clap_id_change(&project_name, &identity.bundle_id);
+
+    meta.identity = Some(identity);
+    meta.updated_at = chrono::Utc::now().to_rfc3339();
+    let metadata_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    fs::write(project_path.join(".vstworkshop/metadata.json"), metadata_json)
+        .map_err(|e| format!("Failed to write metadata: {}", e))?;
+
+    let project_path_str = project_path.to_string_lossy().to_string();
+    if let Err(e) = super::git::commit_changes(&project_path_str, "Update plugin identity").await {
+        log::warn!("Failed to commit identity change for {}: {}", project_name, e);
+    }
+
+    log::info!("Updated plugin identity for {}", project_name);
+    Ok(meta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB_RS: &str = r#"impl Plugin for Demo {
+    const NAME: &'static str = "Demo";
+    const VENDOR: &'static str = "freqlab";
+    const URL: &'static str = "";
+    const EMAIL: &'static str = "";
+}
+
+impl ClapPlugin for Demo {
+    const CLAP_ID: &'static str = "com.freqlab.demo";
+}
+"#;
+
+    #[test]
+    fn test_replace_and_read_const_str() {
+        let updated = replace_const_str(LIB_RS, "VENDOR", "Acme \"Audio\"").unwrap();
+        assert!(updated.contains(r#"const VENDOR: &'static str = "Acme \"Audio\"";"#));
+        assert_eq!(read_const_str(&updated, "VENDOR").as_deref(), Some("Acme \"Audio\""));
+
+        let updated = replace_const_str(&updated, "URL", "https://acme.audio").unwrap();
+        assert_eq!(read_const_str(&updated, "URL").as_deref(), Some("https://acme.audio"));
+        assert_eq!(read_const_str(&updated, "CLAP_ID").as_deref(), Some("com.freqlab.demo"));
+        assert!(updated.ends_with("}\n"));
+
+        assert!(replace_const_str(LIB_RS, "MISSING", "x").is_none());
+    }
+
+    #[test]
+    fn test_set_package_field() {
+        let cargo = "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n";
+
+        let added = set_package_field(cargo, "homepage", Some("\"https://acme.audio\""));
+        assert_eq!(
+            added,
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nhomepage = \"https://acme.audio\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n"
+        );
+
+        let replaced = set_package_field(&added, "homepage", Some("\"https://example.com\""));
+        assert!(replaced.contains("homepage = \"https://example.com\""));
+        assert!(!replaced.contains("acme"));
+
+        assert_eq!(set_package_field(&added, "homepage", None), cargo);
+    }
+
+    #[test]
+    fn test_validate_identity() {
+        let mut identity = PluginIdentity {
+            vendor_name: "Acme".to_string(),
+            vendor_url: "https://acme.audio".to_string(),
+            vendor_email: "hi@acme.audio".to_string(),
+            bundle_id: "com.acme.demo".to_string(),
+        };
+        assert!(validate_identity(&identity).is_ok());
+
+        identity.bundle_id = "demo".to_string();
+        assert!(validate_identity(&identity).is_err());
+
+        identity.bundle_id = "com.acme.demo".to_string();
+        identity.vendor_url = "acme.audio".to_string();
+        assert!(validate_identity(&identity).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+//! Checking published plugins
+//!
+//! `verify_install` looks at what `publish_to_daw` left in each DAW's plugin
+//! folders: the bundle is there, its files match the build, its code signature
+//! is valid (macOS), and its binary runs on this machine's architecture. Each
+//! DAW gets a pass/fail with the reasons, rather than trusting the copy.
+
+use serde::Serialize;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use super::artifact_manifest::{bundle_files, compare_files};
+use super::projects::get_output_path;
+use super::publish::{expand_tilde, DawPublishTarget};
+
+#[derive(Serialize, Clone, Debug)]
+pub struct InstallCheck {
+    /// "VST3", "CLAP"
+    pub format: String,
+    pub path: String,
+    pub installed: bool,
+    /// Files identical to the build (None when not installed)
+    pub matches_build: Option<bool>,
+    /// None where there's nothing to check (not macOS, not installed, or unsigned)
+    pub signature_valid: Option<bool>,
+    /// Architectures in the plugin binary, e.g. ["arm64", "x86_64"]
+    pub architectures: Vec<String>,
+    pub problems: Vec<String>,
+    /// Findings that don't fail the check, e.g. an unsigned development build
+    pub notes: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DawInstallReport {
+    pub daw: String,
+    pub passed: bool,
+    pub checks: Vec<InstallCheck>,
+}
+
+fn read_u16(bytes: &[u8], at: usize, big_endian: bool) -> Option<u16> {
+    let b: [u8; 2] = bytes.get(at..at + 2)?.try_into().ok()?;
+    Some(if big_endian { u16::from_be_bytes(b) } else { u16::from_le_bytes(b) })
+}
+
+fn read_u32(bytes: &[u8], at: usize, big_endian: bool) -> Option<u32> {
+    let b: [u8; 4] = bytes.get(at..at + 4)?.try_into().ok()?;
+    Some(if big_endian { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+}
+
+fn mach_cpu_name(cpu_type: u32) -> &'static str {
+    match cpu_type {
+        0x0100_000c => "arm64",
+        0x0100_0007 => "x86_64",
+        _ => "unknown",
+    }
+}
+
+/// Architectures of a Mach-O (thin or universal), ELF, or PE binary from its
+/// header; empty if the bytes aren't an executable
+fn binary_architectures(header: &[u8]) -> Vec<&'static str> {
+    match read_u32(header, 0, true) {
+        // Universal binary: big-endian list of 20-byte fat_arch entries
+        Some(0xcafe_babe) => {
+            let count = read_u32(header, 4, true).unwrap_or(0).min(16) as usize;
+            (0..count)
+                .filter_map(|i| read_u32(header, 8 + i * 20, true))
+                .map(mach_cpu_name)
+                .collect()
+        }
+        // Thin 64-bit Mach-O, stored little-endian
+        Some(0xcffa_edfe) => read_u32(header, 4, false).map(mach_cpu_name).into_iter().collect(),
+        Some(0x7f45_4c46) => {
+            let big_endian = header.get(5) == Some(&2);
+            match read_u16(header, 18, big_endian) {
+                Some(0x3e) => vec!["x86_64"],
+                Some(0xb7) => vec!["arm64"],
+                Some(_) => vec!["unknown"],
+                None => Vec::new(),
+            }
+        }
+        _ if header.starts_with(b"MZ") => {
+            let Some(pe) = read_u32(header, 0x3c, false).map(|o| o as usize) else {
+                return Vec::new();
+            };
+            if header.get(pe..pe + 4) != Some(b"PE\0\0".as_slice()) {
+                return Vec::new();
+            }
+            match read_u16(header, pe + 4, false) {
+                Some(0x8664) => vec!["x86_64"],
+                Some(0xaa64) => vec!["arm64"],
+                _ => vec!["unknown"],
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// This machine's architecture, named as in `binary_architectures`
+/// Uses the CPU's, so an x86_64 app running under Rosetta still expects arm64.
+fn host_architecture() -> &'static str {
+    match super::architecture::native_arch() {
+        "aarch64" => "arm64",
+        other => other,
+    }
+}
+
+/// Architectures of the first executable found in a bundle
+fn bundle_architectures(bundle: &Path) -> Vec<String> {
+    for entry in WalkDir::new(bundle).into_iter().flatten() {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let mut header = vec![0u8; 4096];
+        let Ok(read) = File::open(entry.path()).and_then(|mut f| f.read(&mut header)) else {
+            continue;
+        };
+        header.truncate(read);
+        let architectures = binary_architectures(&header);
+        if !architectures.is_empty() {
+            return architectures.into_iter().map(String::from).collect();
+        }
+    }
+    Vec::new()
+}
+
+// Only checked on macOS
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+enum Signature {
+    Valid,
+    /// Not signed at all - normal for local builds, which DAWs load anyway
+    Unsigned,
+    Invalid(String),
+}
+
+#[cfg(target_os = "macos")]
+fn check_signature(bundle: &Path) -> Option<Signature> {
+    let output = std::process::Command::new("codesign")
+        .args(["--verify", "--deep", "--strict"])
+        .arg(bundle)
+        .output()
+        .ok()?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Some(if output.status.success() {
+        Signature::Valid
+    } else if stderr.contains("not signed at all") {
+        Signature::Unsigned
+    } else {
+        Signature::Invalid(stderr)
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn check_signature(_bundle: &Path) -> Option<Signature> {
+    // DAWs don't require signed plugins outside macOS
+    None
+}
+
+pub(crate) fn check_bundle(built: &Path, installed: &Path, format: &str) -> InstallCheck {
+    let mut check = InstallCheck {
+        format: format.to_string(),
+        path: installed.to_string_lossy().to_string(),
+        installed: installed.exists(),
+        matches_build: None,
+        signature_valid: None,
+        architectures: Vec::new(),
+        problems: Vec::new(),
+        notes: Vec::new(),
+    };
+    if !check.installed {
+        check.problems.push("Not installed".to_string());
+        return check;
+    }
+
+    // Both sides are relative to their parent folder, so paths start with the bundle name
+    let built_files = built.parent().map(|root| bundle_files(root, built));
+    let installed_files = installed.parent().map(|root| bundle_files(root, installed));
+    match (built_files, installed_files) {
+        (Some(Ok(mut expected)), Some(Ok(mut actual))) => {
+            expected.sort_by(|a, b| a.path.cmp(&b.path));
+            actual.sort_by(|a, b| a.path.cmp(&b.path));
+            let differences = compare_files(&expected, &actual);
+            check.matches_build = Some(differences.is_empty());
+            if !differences.is_empty() {
+                check.problems.push(format!("Differs from the build: {}", differences.join(", ")));
+            }
+        }
+        (_, Some(Err(e))) | (Some(Err(e)), _) => check.problems.push(e),
+        _ => {}
+    }
+
+    match check_signature(installed) {
+        Some(Signature::Valid) => check.signature_valid = Some(true),
+        Some(Signature::Unsigned) => check
+            .notes
+            .push("Not code signed - fine on this Mac, but sign it before distributing".to_string()),
+        Some(Signature::Invalid(output)) => {
+            check.signature_valid = Some(false);
+            check.problems.push(format!("Code signature is invalid: {}", output));
+        }
+        None => {}
+    }
+
+    check.architectures = bundle_architectures(installed);
+    if check.architectures.is_empty() {
+        check.problems.push("No plugin binary found in the bundle".to_string());
+    } else if !check.architectures.iter().any(|a| a == host_architecture()) {
+        check.problems.push(format!(
+            "Built for {} but this machine is {}",
+            check.architectures.join("/"),
+            host_architecture()
+        ));
+    }
+    check
+}
+
+/// Check that a published version is correctly installed for each DAW
+#[tauri::command]
+pub async fn verify_install(
+    project_name: String,
+    version: u32,
+    targets: Vec<DawPublishTarget>,
+) -> Result<Vec<DawInstallReport>, String> {
+    let output_path = get_output_path()
+        .join(&project_name)
+        .join(format!("v{}", version.max(1)));
+    let snake_name = project_name.replace('-', "_");
+
+    // Only formats that were built get checked
+    let formats: Vec<(&str, String)> = [("VST3", "vst3"), ("CLAP", "clap")]
+        .into_iter()
+        .map(|(format, extension)| (format, format!("{}.{}", snake_name, extension)))
+        .filter(|(_, name)| output_path.join(name).exists())
+        .collect();
+    if formats.is_empty() {
+        return Err("No built plugins found. Build the project first.".to_string());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        targets
+            .into_iter()
+            .map(|target| {
+                let checks: Vec<InstallCheck> = formats
+                    .iter()
+                    .filter_map(|(format, name)| {
+                        let dir = if *format == "VST3" { &target.vst3_path } else { &target.clap_path };
+                        (!dir.is_empty())
+                            .then(|| check_bundle(&output_path.join(name), &expand_tilde(dir).join(name), format))
+                    })
+                    .collect();
+                DawInstallReport {
+                    passed: !checks.is_empty() && checks.iter().all(|c| c.problems.is_empty()),
+                    daw: target.daw,
+                    checks,
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_architectures() {
+        let mut fat = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 2];
+        fat.extend([0x01, 0, 0, 0x07]);
+        fat.extend([0; 16]);
+        fat.extend([0x01, 0, 0, 0x0c]);
+        fat.extend([0; 16]);
+        assert_eq!(binary_architectures(&fat), vec!["x86_64", "arm64"]);
+
+        let thin = [0xcf, 0xfa, 0xed, 0xfe, 0x0c, 0, 0, 0x01];
+        assert_eq!(binary_architectures(&thin), vec!["arm64"]);
+
+        let mut elf = vec![0x7f, b'E', b'L', b'F', 2, 1];
+        elf.resize(18, 0);
+        elf.extend([0x3e, 0]);
+        assert_eq!(binary_architectures(&elf), vec!["x86_64"]);
+
+        let mut pe = b"MZ".to_vec();
+        pe.resize(0x3c, 0);
+        pe.extend(0x40u32.to_le_bytes());
+        pe.extend(b"PE\0\0");
+        pe.extend(0xaa64u16.to_le_bytes());
+        assert_eq!(binary_architectures(&pe), vec!["arm64"]);
+
+        assert!(binary_architectures(b"<?xml version").is_empty());
+    }
+}
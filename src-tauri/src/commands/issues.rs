@@ -0,0 +1,269 @@
+//! Project-level issue tracker
+//!
+//! A lightweight list of outstanding work items stored in
+//! `.vstworkshop/issues.json`. Issues come from the user, from Claude (which
+//! reads and updates the file as instructed by CLAUDE.md), and from the
+//! validation suite - validators open an issue for each problem they find and
+//! resolve their own issues once a later run no longer reproduces them.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::activity::project_dir;
+use super::claude_md::add_issue_tracker_to_claude_md;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSource {
+    User,
+    Claude,
+    Validator,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStatus {
+    Open,
+    Resolved,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProjectIssue {
+    pub id: u32,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    pub source: IssueSource,
+    pub status: IssueStatus,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<String>,
+    /// Validator that opened the issue and its key for the problem (e.g. "torture_test", "NaN/Inf output@192000")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// A problem found by a validator run
+pub(crate) struct ValidatorIssue {
+    pub key: String,
+    pub title: String,
+    pub details: Option<String>,
+}
+
+/// Held across every read-modify-write of an issues.json, so a validator
+/// finishing while the user adds an issue can't drop either change
+static ISSUES_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+fn get_issues_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("issues.json")
+}
+
+/// Read the issue list (empty if there isn't one yet)
+/// A file that doesn't parse is an error rather than an empty list so it never gets overwritten.
+fn read_issues(project_path: &Path) -> Result<Vec<ProjectIssue>, String> {
+    let path = get_issues_path(project_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read issues: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse issues: {}", e))
+}
+
+fn write_issues(project_path: &Path, issues: &[ProjectIssue]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(issues)
+        .map_err(|e| format!("Failed to serialize issues: {}", e))?;
+    fs::write(get_issues_path(project_path), json).map_err(|e| format!("Failed to write issues: {}", e))?;
+
+    // Projects created before the tracker need CLAUDE.md to point Claude at the list
+    let claude_md_path = project_path.join("CLAUDE.md");
+    if let Ok(content) = fs::read_to_string(&claude_md_path) {
+        let updated = add_issue_tracker_to_claude_md(&content);
+        if updated != content {
+            if let Err(e) = fs::write(&claude_md_path, updated) {
+                log::warn!("Failed to add issue tracker to {:?}: {}", claude_md_path, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn next_id(issues: &[ProjectIssue]) -> u32 {
+    issues.iter().map(|i| i.id).max().unwrap_or(0) + 1
+}
+
+fn existing_project(project_name: &str) -> Result<PathBuf, String> {
+    let project_path = project_dir(project_name);
+    if !project_path.join(".vstworkshop").exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+    Ok(project_path)
+}
+
+/// Open issues for new problems and resolve the validator's issues that are gone
+/// Returns true if the list changed.
+fn apply_validator_results(issues: &mut Vec<ProjectIssue>, validator: &str, found: Vec<ValidatorIssue>, now: &str) -> bool {
+    let mut changed = false;
+
+    for issue in issues.iter_mut() {
+        let still_found = found.iter().any(|f| issue.key.as_deref() == Some(f.key.as_str()));
+        if issue.status == IssueStatus::Open && issue.validator.as_deref() == Some(validator) && !still_found {
+            issue.status = IssueStatus::Resolved;
+            issue.resolved_at = Some(now.to_string());
+            changed = true;
+        }
+    }
+
+    for f in found {
+        let already_open = issues.iter().any(|i| {
+            i.status == IssueStatus::Open
+                && i.validator.as_deref() == Some(validator)
+                && i.key.as_deref() == Some(f.key.as_str())
+        });
+        if already_open {
+            continue;
+        }
+        issues.push(ProjectIssue {
+            id: next_id(issues),
+            title: f.title,
+            details: f.details,
+            source: IssueSource::Validator,
+            status: IssueStatus::Open,
+            created_at: now.to_string(),
+            resolved_at: None,
+            validator: Some(validator.to_string()),
+            key: Some(f.key),
+        });
+        changed = true;
+    }
+
+    changed
+}
+
+/// Record a validator run's findings in the project's issue list
+/// Best-effort like activity recording: failures are logged, never returned.
+pub(crate) fn sync_validator_issues(project_name: &str, validator: &str, found: Vec<ValidatorIssue>) {
+    let project_path = project_dir(project_name);
+    if !project_path.join(".vstworkshop").exists() {
+        return;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let _guard = ISSUES_LOCK.lock();
+    let result = read_issues(&project_path).and_then(|mut issues| {
+        if apply_validator_results(&mut issues, validator, found, &now) {
+            write_issues(&project_path, &issues)?;
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to record {} issues for {}: {}", validator, project_name, e);
+    }
+}
+
+/// List a project's issues, open ones first
+#[tauri::command]
+pub async fn list_project_issues(project_name: String, include_resolved: Option<bool>) -> Result<Vec<ProjectIssue>, String> {
+    let project_path = existing_project(&project_name)?;
+    let mut issues: Vec<ProjectIssue> = read_issues(&project_path)?
+        .into_iter()
+        .filter(|i| include_resolved.unwrap_or(false) || i.status == IssueStatus::Open)
+        .collect();
+    issues.sort_by_key(|i| (i.status == IssueStatus::Resolved, i.id));
+    Ok(issues)
+}
+
+/// Add an issue to a project
+#[tauri::command]
+pub async fn create_project_issue(
+    project_name: String,
+    title: String,
+    details: Option<String>,
+    source: Option<IssueSource>,
+) -> Result<ProjectIssue, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Issue title cannot be empty".to_string());
+    }
+
+    let project_path = existing_project(&project_name)?;
+    let _guard = ISSUES_LOCK.lock();
+    let mut issues = read_issues(&project_path)?;
+    let issue = ProjectIssue {
+        id: next_id(&issues),
+        title: title.to_string(),
+        details: details.filter(|d| !d.trim().is_empty()),
+        source: source.unwrap_or(IssueSource::User),
+        status: IssueStatus::Open,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        resolved_at: None,
+        validator: None,
+        key: None,
+    };
+    issues.push(issue.clone());
+    write_issues(&project_path, &issues)?;
+    Ok(issue)
+}
+
+/// Mark an issue as resolved
+#[tauri::command]
+pub async fn resolve_project_issue(project_name: String, issue_id: u32) -> Result<ProjectIssue, String> {
+    let project_path = existing_project(&project_name)?;
+    let _guard = ISSUES_LOCK.lock();
+    let mut issues = read_issues(&project_path)?;
+    let issue = issues
+        .iter_mut()
+        .find(|i| i.id == issue_id)
+        .ok_or_else(|| format!("Issue #{} not found", issue_id))?;
+
+    if issue.status == IssueStatus::Open {
+        issue.status = IssueStatus::Resolved;
+        issue.resolved_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+    let resolved = issue.clone();
+    write_issues(&project_path, &issues)?;
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(key: &str) -> ValidatorIssue {
+        ValidatorIssue {
+            key: key.to_string(),
+            title: format!("Problem {}", key),
+            details: None,
+        }
+    }
+
+    #[test]
+    fn test_validator_issues_open_and_resolve() {
+        let mut issues = Vec::new();
+        assert!(apply_validator_results(&mut issues, "torture_test", vec![found("a"), found("b")], "t1"));
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[1].id, 2);
+
+        // Same findings again: nothing changes, no duplicates
+        assert!(!apply_validator_results(&mut issues, "torture_test", vec![found("a"), found("b")], "t2"));
+        assert_eq!(issues.len(), 2);
+
+        // "a" is fixed; another validator's findings are left alone
+        apply_validator_results(&mut issues, "denormal_test", vec![found("a")], "t3");
+        apply_validator_results(&mut issues, "torture_test", vec![found("b")], "t4");
+        assert_eq!(issues[0].status, IssueStatus::Resolved);
+        assert_eq!(issues[0].resolved_at.as_deref(), Some("t4"));
+        assert_eq!(issues[1].status, IssueStatus::Open);
+        assert_eq!(issues[2].validator.as_deref(), Some("denormal_test"));
+        assert_eq!(issues[2].status, IssueStatus::Open);
+
+        // A problem that comes back after being resolved opens a new issue
+        apply_validator_results(&mut issues, "torture_test", vec![found("a"), found("b")], "t5");
+        assert_eq!(issues.len(), 4);
+        assert_eq!(issues[3].key.as_deref(), Some("a"));
+    }
+}
@@ -0,0 +1,234 @@
+//! Skill library usage stats
+//!
+//! Opt-in and local only: counts how often each skill is copied into a project's
+//! `.claude/commands/` and which slash commands get used per project - typed by
+//! the user or invoked by Claude through its Skill tool - so the library can be
+//! trimmed and improved where it matters. Nothing is recorded until the user
+//! opts in, and nothing leaves the machine.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::projects::get_workspace_path;
+
+/// Serializes read-modify-write of the stats file
+static USAGE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+struct UsageFile {
+    #[serde(rename = "optIn", default)]
+    opt_in: bool,
+    /// Skill name -> times copied into a project
+    #[serde(default)]
+    copies: BTreeMap<String, u64>,
+    /// Project -> command -> invocations
+    #[serde(default)]
+    invocations: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct CommandUsage {
+    pub name: String,
+    pub invocations: u64,
+    /// Projects the command was used in
+    pub projects: usize,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct GuideCopies {
+    pub name: String,
+    pub copies: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct LibraryUsageStats {
+    pub opt_in: bool,
+    /// Most used first
+    pub commands: Vec<CommandUsage>,
+    /// Most copied first
+    pub copies: Vec<GuideCopies>,
+    pub per_project: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+fn get_usage_path() -> PathBuf {
+    get_workspace_path().join("library-usage.json")
+}
+
+fn load_usage() -> UsageFile {
+    fs::read_to_string(get_usage_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_usage(usage: &UsageFile) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(usage).map_err(|e| format!("Failed to serialize library usage: {}", e))?;
+    fs::write(get_usage_path(), json).map_err(|e| format!("Failed to save library usage: {}", e))
+}
+
+/// Apply a change to the stats if the user opted in; failures are only logged
+fn update_usage(change: impl FnOnce(&mut UsageFile)) {
+    let _guard = USAGE_LOCK.lock();
+    let mut usage = load_usage();
+    if !usage.opt_in {
+        return;
+    }
+    change(&mut usage);
+    if let Err(e) = save_usage(&usage) {
+        log::warn!("{}", e);
+    }
+}
+
+/// Count skills copied into a project (names without `.md`)
+pub(crate) fn record_guides_copied(names: &[&str]) {
+    update_usage(|usage| {
+        for name in names {
+            *usage.copies.entry(name.to_string()).or_default() += 1;
+        }
+    });
+}
+
+/// Count a slash command used in a project
+pub(crate) fn record_command_used(project_name: &str, command: &str) {
+    update_usage(|usage| {
+        *usage
+            .invocations
+            .entry(project_name.to_string())
+            .or_default()
+            .entry(command.to_string())
+            .or_default() += 1;
+    });
+}
+
+/// Slash command at the start of a chat message: "/dsp-safety check this" -> "dsp-safety"
+pub(crate) fn command_in_message(message: &str) -> Option<String> {
+    let name = message.trim_start().strip_prefix('/')?.split_whitespace().next()?;
+    let valid = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ':');
+    valid.then(|| name.to_string())
+}
+
+fn skill_in_tool_use(name: &str, input: &serde_json::Value) -> Option<String> {
+    if name != "Skill" && name != "SlashCommand" {
+        return None;
+    }
+    let value = input.get("skill").or_else(|| input.get("command"))?.as_str()?;
+    let skill = value.trim().trim_start_matches('/').split_whitespace().next()?;
+    (!skill.is_empty()).then(|| skill.to_string())
+}
+
+/// Skill Claude invoked in a stream-json line, if any
+pub(crate) fn skill_in_stream_event(json_line: &str) -> Option<String> {
+    let event: serde_json::Value = serde_json::from_str(json_line).ok()?;
+    if let (Some(tool), Some(input)) = (event.get("tool").and_then(|t| t.as_str()), event.get("tool_input")) {
+        return skill_in_tool_use(tool, input);
+    }
+    // Assistant messages carry tool_use blocks in their content
+    event
+        .get("message")?
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+        .find_map(|block| skill_in_tool_use(block.get("name")?.as_str()?, block.get("input")?))
+}
+
+fn summarize(usage: UsageFile) -> LibraryUsageStats {
+    let mut totals: BTreeMap<String, (u64, usize)> = BTreeMap::new();
+    for commands in usage.invocations.values() {
+        for (name, count) in commands {
+            let entry = totals.entry(name.clone()).or_default();
+            entry.0 += count;
+            entry.1 += 1;
+        }
+    }
+    let mut commands: Vec<CommandUsage> = totals
+        .into_iter()
+        .map(|(name, (invocations, projects))| CommandUsage {
+            name,
+            invocations,
+            projects,
+        })
+        .collect();
+    commands.sort_by(|a, b| b.invocations.cmp(&a.invocations));
+
+    let mut copies: Vec<GuideCopies> = usage
+        .copies
+        .into_iter()
+        .map(|(name, copies)| GuideCopies { name, copies })
+        .collect();
+    copies.sort_by(|a, b| b.copies.cmp(&a.copies));
+
+    LibraryUsageStats {
+        opt_in: usage.opt_in,
+        commands,
+        copies,
+        per_project: usage.invocations,
+    }
+}
+
+/// Skill library usage collected so far (empty until the user opts in)
+#[tauri::command]
+pub async fn get_library_usage_stats() -> Result<LibraryUsageStats, String> {
+    Ok(summarize(load_usage()))
+}
+
+/// Turn local library usage stats on or off; turning them off clears what was collected
+#[tauri::command]
+pub async fn set_library_usage_opt_in(opt_in: bool) -> Result<(), String> {
+    let _guard = USAGE_LOCK.lock();
+    let usage = if opt_in {
+        UsageFile {
+            opt_in: true,
+            ..load_usage()
+        }
+    } else {
+        UsageFile::default()
+    };
+    save_usage(&usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_from_messages_and_stream() {
+        assert_eq!(command_in_message("  /dsp-safety check the filter").as_deref(), Some("dsp-safety"));
+        assert_eq!(command_in_message("use /dsp-safety"), None);
+        assert_eq!(command_in_message("/path/to/file.wav"), None);
+
+        let top_level = r#"{"type":"tool_use","tool":"Skill","tool_input":{"skill":"effect-patterns"}}"#;
+        assert_eq!(skill_in_stream_event(top_level).as_deref(), Some("effect-patterns"));
+        let in_message = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"hi"},{"type":"tool_use","name":"SlashCommand","input":{"command":"/webview-ui"}}]}}"#;
+        assert_eq!(skill_in_stream_event(in_message).as_deref(), Some("webview-ui"));
+        let other_tool = r#"{"type":"tool_use","tool":"Read","tool_input":{"file_path":"src/lib.rs"}}"#;
+        assert_eq!(skill_in_stream_event(other_tool), None);
+    }
+
+    #[test]
+    fn test_summarize_totals_across_projects() {
+        let mut usage = UsageFile {
+            opt_in: true,
+            ..Default::default()
+        };
+        usage.copies.insert("dsp-safety".into(), 4);
+        usage.copies.insert("reverb".into(), 1);
+        usage.invocations.insert("a".into(), BTreeMap::from([("dsp-safety".into(), 2), ("reverb".into(), 1)]));
+        usage.invocations.insert("b".into(), BTreeMap::from([("dsp-safety".into(), 3)]));
+
+        let stats = summarize(usage);
+        assert_eq!(
+            stats.commands[0],
+            CommandUsage {
+                name: "dsp-safety".into(),
+                invocations: 5,
+                projects: 2
+            }
+        );
+        assert_eq!(stats.copies[0].name, "dsp-safety");
+    }
+}
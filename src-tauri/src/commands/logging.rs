@@ -1,53 +1,202 @@
-use std::fs::{self, OpenOptions};
-use std::io::Write;
+//! Structured logging
+//!
+//! Everything logged through `log` or `tracing` goes to a tracing subscriber with:
+//! - daily rotating JSON-lines files under ~/VSTWorkshop/logs/ (old files pruned)
+//! - human-readable stderr output for dev mode
+//! - an in-memory ring of recent entries for the in-app log viewer
+//!
+//! Per-module levels use `EnvFilter` directives (e.g. `info,freqlab_lib::audio=debug`)
+//! and can be changed at runtime with `set_log_levels`.
+
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
 use std::path::PathBuf;
-use std::sync::Mutex;
-use once_cell::sync::Lazy;
+use std::str::FromStr;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::{fmt, reload, EnvFilter, Layer, Registry};
 
-static LOG_FILE: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+/// Level directives used when RUST_LOG isn't set
+const DEFAULT_LOG_LEVELS: &str = "info";
 
-/// Get the log file path (in user's home directory)
-fn get_log_path() -> PathBuf {
+/// Log file name prefix - files are named freqlab.YYYY-MM-DD.log
+const LOG_FILE_PREFIX: &str = "freqlab";
+
+/// Rotated log files kept on disk (one per day)
+const MAX_LOG_FILES: usize = 7;
+
+/// Entries kept in memory for get_recent_logs
+const RECENT_LOG_CAPACITY: usize = 2000;
+
+static FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+
+/// Flushes the background file writer on drop - must live for the whole process
+static FILE_GUARD: OnceCell<WorkerGuard> = OnceCell::new();
+
+static CURRENT_LEVELS: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new(DEFAULT_LOG_LEVELS.to_string()));
+
+static RECENT_LOGS: Lazy<Mutex<VecDeque<LogEntry>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_LOG_CAPACITY)));
+
+/// A captured log event for the in-app log viewer
+#[derive(Serialize, Clone, Debug)]
+pub struct LogEntry {
+    pub timestamp: String,
+    /// "ERROR", "WARN", "INFO", "DEBUG", "TRACE"
+    pub level: String,
+    /// Module path the event came from (e.g. freqlab_lib::audio::engine)
+    pub target: String,
+    pub message: String,
+    /// Structured fields attached to the event
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+    #[serde(skip)]
+    level_value: Level,
+}
+
+/// Collects an event's message and fields
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.record(field, format!("{:?}", value));
+    }
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "message" => self.message = value,
+            // Metadata carried over from the log crate bridge, already normalized
+            name if name.starts_with("log.") => {}
+            name => {
+                self.fields.insert(name.to_string(), value);
+            }
+        }
+    }
+}
+
+/// Layer that keeps the most recent events in memory
+struct RecentLogsLayer;
+
+impl<S: Subscriber> Layer<S> for RecentLogsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        // Events from the log crate carry their real target in normalized metadata
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+            level_value: *metadata.level(),
+        };
+
+        let mut logs = RECENT_LOGS.lock();
+        if logs.len() >= RECENT_LOG_CAPACITY {
+            logs.pop_front();
+        }
+        logs.push_back(entry);
+    }
+}
+
+/// Get the log directory (in user's home directory)
+pub(crate) fn get_log_dir() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-    PathBuf::from(home)
-        .join("VSTWorkshop")
-        .join("logs")
-        .join("freqlab.log")
+    PathBuf::from(home).join("VSTWorkshop").join("logs")
+}
+
+/// Get the log file currently being written (newest rotated file)
+fn get_log_path() -> PathBuf {
+    let dir = get_log_dir();
+    fs::read_dir(&dir)
+        .ok()
+        .and_then(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .map(|n| n.to_string_lossy().starts_with(LOG_FILE_PREFIX))
+                        .unwrap_or(false)
+                })
+                // Date-stamped names sort chronologically
+                .max()
+        })
+        .unwrap_or_else(|| {
+            let date = chrono::Local::now().format("%Y-%m-%d");
+            dir.join(format!("{}.{}.log", LOG_FILE_PREFIX, date))
+        })
 }
 
-/// Initialize logging - creates log directory if needed
+/// Initialize logging - creates log directory and installs the global subscriber
 pub fn init_logging() {
-    let log_path = get_log_path();
+    let log_dir = get_log_dir();
+    let _ = fs::create_dir_all(&log_dir);
 
-    // Create logs directory if it doesn't exist
-    if let Some(parent) = log_path.parent() {
-        let _ = fs::create_dir_all(parent);
-    }
+    let levels = std::env::var("RUST_LOG").unwrap_or_else(|_| DEFAULT_LOG_LEVELS.to_string());
+    let filter = EnvFilter::try_new(&levels).unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_LEVELS));
+    *CURRENT_LEVELS.lock() = levels;
+    let (filter, filter_handle) = reload::Layer::new(filter);
+
+    let file_layer = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)
+        .map_err(|e| eprintln!("Failed to open log file in {:?}: {}", log_dir, e))
+        .ok()
+        .map(|appender| {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let _ = FILE_GUARD.set(guard);
+            fmt::layer().json().with_writer(writer)
+        });
 
-    // Store the path
-    if let Ok(mut path) = LOG_FILE.lock() {
-        *path = Some(log_path.clone());
+    let subscriber = Registry::default()
+        .with(filter)
+        .with(file_layer)
+        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(RecentLogsLayer);
+
+    // Bridge the log crate at full verbosity - the reloadable filter decides what's kept,
+    // so raising a module's level at runtime also takes effect for log:: macros
+    let _ = tracing_log::LogTracer::init();
+    if tracing::subscriber::set_global_default(subscriber).is_ok() {
+        let _ = FILTER_HANDLE.set(filter_handle);
     }
 
     // Write startup marker
     log_message("INFO", "freqlab", "Application started");
 }
 
-/// Write a log message to the log file
+/// Log a message from a named module
 pub fn log_message(level: &str, module: &str, message: &str) {
-    let log_path = get_log_path();
-
-    // Also print to stderr for dev mode
-    eprintln!("[{}] [{}] {}", level, module, message);
-
-    // Append to log file
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_path)
-    {
-        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-        let _ = writeln!(file, "[{}] [{}] [{}] {}", timestamp, level, module, message);
+    match level {
+        "ERROR" => tracing::error!(module, "{}", message),
+        "WARN" => tracing::warn!(module, "{}", message),
+        "DEBUG" => tracing::debug!(module, "{}", message),
+        _ => tracing::info!(module, "{}", message),
     }
 }
 
@@ -70,7 +219,7 @@ pub async fn read_log_file() -> Result<String, String> {
         .map_err(|e| format!("Failed to read log file: {}", e))
 }
 
-/// Clear the log file
+/// Clear the current log file and the in-memory entries
 #[tauri::command]
 pub async fn clear_log_file() -> Result<(), String> {
     let log_path = get_log_path();
@@ -79,6 +228,7 @@ pub async fn clear_log_file() -> Result<(), String> {
         fs::write(&log_path, "")
             .map_err(|e| format!("Failed to clear log file: {}", e))?;
     }
+    RECENT_LOGS.lock().clear();
 
     log_message("INFO", "freqlab", "Log file cleared");
     Ok(())
@@ -97,3 +247,53 @@ pub async fn get_log_file_size() -> Result<u64, String> {
         .map(|m| m.len())
         .map_err(|e| format!("Failed to get log file size: {}", e))
 }
+
+/// Get recent log entries, newest last
+/// `min_level` keeps entries at that severity or worse (e.g. "warn" = warnings and errors);
+/// `target` keeps entries whose module path starts with it
+#[tauri::command]
+pub async fn get_recent_logs(
+    limit: Option<usize>,
+    min_level: Option<String>,
+    target: Option<String>,
+) -> Result<Vec<LogEntry>, String> {
+    let min_level = min_level
+        .map(|l| Level::from_str(&l).map_err(|_| format!("Invalid log level: {}", l)))
+        .transpose()?;
+
+    let logs = RECENT_LOGS.lock();
+    let mut entries: Vec<LogEntry> = logs
+        .iter()
+        .rev()
+        // Level ordering is by verbosity: ERROR < WARN < ... < TRACE
+        .filter(|e| min_level.map_or(true, |min| e.level_value <= min))
+        .filter(|e| target.as_ref().map_or(true, |t| e.target.starts_with(t.as_str())))
+        .take(limit.unwrap_or(RECENT_LOG_CAPACITY))
+        .cloned()
+        .collect();
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Get the active level directives
+#[tauri::command]
+pub async fn get_log_levels() -> Result<String, String> {
+    Ok(CURRENT_LEVELS.lock().clone())
+}
+
+/// Change log levels at runtime
+/// Accepts EnvFilter directives, e.g. "info" or "warn,freqlab_lib::audio::engine=debug"
+#[tauri::command]
+pub async fn set_log_levels(levels: String) -> Result<(), String> {
+    let filter = EnvFilter::try_new(&levels).map_err(|e| format!("Invalid log levels: {}", e))?;
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or_else(|| "Logging not initialized".to_string())?;
+    handle
+        .reload(filter)
+        .map_err(|e| format!("Failed to update log levels: {}", e))?;
+
+    log::info!("Log levels set to '{}'", levels);
+    *CURRENT_LEVELS.lock() = levels;
+    Ok(())
+}
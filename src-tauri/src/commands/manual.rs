@@ -0,0 +1,355 @@
+//! Plugin manual generation
+//!
+//! Builds a user-facing README/manual for a built plugin version: the project
+//! description, supported formats, install instructions, and a parameter table.
+//! Parameter names, ranges, and defaults come from the built CLAP's params
+//! extension (so they match what a DAW shows); descriptions come from the doc
+//! comments on the params struct in the project source.
+//!
+//! Output goes to output/{project}/v{version}/manual/ as README.md and
+//! MANUAL.html, which `package_plugins` includes in the package zip.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::projects::{get_output_path, get_workspace_path, ProjectMeta};
+use super::share::html_escape;
+use crate::audio::plugin::PluginInstance;
+
+/// Folder name inside the versioned output directory
+pub(crate) const MANUAL_DIR: &str = "manual";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ManualParameter {
+    pub name: String,
+    pub module: String,
+    /// Minimum as displayed by the plugin (e.g. "-30.0 dB")
+    pub min: String,
+    pub max: String,
+    pub default: String,
+    pub description: Option<String>,
+    pub is_stepped: bool,
+    pub is_bypass: bool,
+}
+
+#[derive(Serialize)]
+pub struct ManualResult {
+    pub markdown_path: String,
+    pub html_path: String,
+    pub parameters: Vec<ManualParameter>,
+}
+
+/// Get the manual folder for a built version
+pub(crate) fn get_manual_dir(project_name: &str, version: u32) -> PathBuf {
+    get_output_path()
+        .join(project_name)
+        .join(format!("v{}", version.max(1)))
+        .join(MANUAL_DIR)
+}
+
+/// Map parameter display names to the doc comments on their params struct fields
+///
+/// Looks for nih-plug style declarations:
+/// ```text
+/// /// Output level after processing
+/// #[id = "gain"]
+/// pub gain: FloatParam,
+/// ...
+/// gain: FloatParam::new(
+///     "Gain",
+/// ```
+pub(crate) fn extract_param_docs(source: &str) -> HashMap<String, String> {
+    // Field name -> doc comment
+    let mut field_docs: HashMap<String, String> = HashMap::new();
+    let mut pending_doc: Vec<String> = Vec::new();
+    let mut has_id = false;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if let Some(doc) = trimmed.strip_prefix("///") {
+            pending_doc.push(doc.trim().to_string());
+        } else if trimmed.starts_with("#[id") {
+            has_id = true;
+        } else if trimmed.starts_with("#[") {
+            // Other attributes between the doc comment and the field
+        } else {
+            if has_id && !pending_doc.is_empty() {
+                let field = trimmed
+                    .trim_start_matches("pub ")
+                    .split(':')
+                    .next()
+                    .unwrap_or_default()
+                    .trim();
+                if !field.is_empty() && trimmed.contains("Param") {
+                    field_docs.insert(field.to_string(), pending_doc.join(" "));
+                }
+            }
+            pending_doc.clear();
+            has_id = false;
+        }
+    }
+
+    // Resolve field names to the display names passed to *Param::new("Name", ...)
+    let mut docs = HashMap::new();
+    for (field, doc) in field_docs {
+        let needle = format!("{}:", field);
+        let mut search_from = 0;
+        while let Some(offset) = source[search_from..].find(&needle) {
+            let start = search_from + offset + needle.len();
+            search_from = start;
+            let rest = &source[start..];
+            let constructor = match rest.find("Param::new(") {
+                // Only accept a constructor right after the field (allowing whitespace/newlines)
+                Some(pos) if rest[..pos].trim().chars().all(|c| c.is_alphanumeric() || c == '_') => pos,
+                _ => continue,
+            };
+            let args = &rest[constructor + "Param::new(".len()..];
+            let name = args
+                .trim_start()
+                .strip_prefix('"')
+                .and_then(|s| s.split('"').next());
+            if let Some(name) = name {
+                docs.insert(name.to_string(), doc.clone());
+                break;
+            }
+        }
+    }
+    docs
+}
+
+/// Read all Rust sources in the project's src/ folder
+fn read_project_sources(project_path: &Path) -> String {
+    walkdir::WalkDir::new(project_path.join("src"))
+        .into_iter()
+        .flatten()
+        .filter(|e| e.path().extension().map(|ext| ext == "rs").unwrap_or(false))
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Introspect a built CLAP's parameters
+fn read_plugin_params(clap_path: &Path, docs: &HashMap<String, String>) -> Result<Vec<ManualParameter>, String> {
+    let plugin = PluginInstance::load(clap_path, 48000.0, 512)?;
+    let format = |id: u32, value: f64| {
+        plugin
+            .param_value_to_text(id, value)
+            .unwrap_or_else(|| format!("{:.2}", value))
+    };
+
+    Ok(plugin
+        .list_params()
+        .into_iter()
+        .filter(|p| !p.is_read_only)
+        .map(|p| ManualParameter {
+            min: format(p.id, p.min_value),
+            max: format(p.id, p.max_value),
+            default: format(p.id, p.default_value),
+            description: docs.get(&p.name).cloned(),
+            name: p.name,
+            module: p.module,
+            is_stepped: p.is_stepped,
+            is_bypass: p.is_bypass,
+        })
+        .collect())
+}
+
+fn render_markdown(meta: &ProjectMeta, version: u32, formats: &[&str], params: &[ManualParameter]) -> String {
+    let mut md = format!("# {}\n\nVersion {}\n\n", meta.name, version);
+    if !meta.description.trim().is_empty() {
+        md.push_str(meta.description.trim());
+        md.push_str("\n\n");
+    }
+
+    md.push_str("## Formats\n\n");
+    for format in formats {
+        md.push_str(&format!("- {}\n", format.to_uppercase()));
+    }
+
+    md.push_str("\n## Installation (macOS)\n\n");
+    for format in formats {
+        let folder = match *format {
+            "vst3" => "VST3",
+            _ => "CLAP",
+        };
+        md.push_str(&format!(
+            "- **{}**: copy `{}.{}` to `~/Library/Audio/Plug-Ins/{}/`\n",
+            format.to_uppercase(),
+            meta.name.replace('-', "_"),
+            format,
+            folder
+        ));
+    }
+    md.push_str("\nThen rescan plugins in your DAW.\n");
+
+    md.push_str("\n## Parameters\n\n");
+    if params.is_empty() {
+        md.push_str("This plugin has no automatable parameters.\n");
+    } else {
+        md.push_str("| Parameter | Range | Default | Description |\n");
+        md.push_str("|---|---|---|---|\n");
+        for p in params {
+            let range = if p.is_bypass {
+                "On / Off".to_string()
+            } else {
+                format!("{} to {}", p.min, p.max)
+            };
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                p.name.replace('|', "\\|"),
+                range.replace('|', "\\|"),
+                p.default.replace('|', "\\|"),
+                p.description.as_deref().unwrap_or("").replace('|', "\\|"),
+            ));
+        }
+    }
+    md
+}
+
+fn render_html(meta: &ProjectMeta, version: u32, formats: &[&str], params: &[ManualParameter]) -> String {
+    let name = html_escape(&meta.name);
+    let format_items: String = formats
+        .iter()
+        .map(|f| format!("<li>{}</li>", f.to_uppercase()))
+        .collect();
+    let rows: String = params
+        .iter()
+        .map(|p| {
+            let range = if p.is_bypass {
+                "On / Off".to_string()
+            } else {
+                format!("{} to {}", p.min, p.max)
+            };
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                html_escape(&p.name),
+                html_escape(&range),
+                html_escape(&p.default),
+                html_escape(p.description.as_deref().unwrap_or("")),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{name} Manual</title>
+<style>
+body {{ font-family: -apple-system, BlinkMacSystemFont, sans-serif; max-width: 760px; margin: 40px auto; padding: 0 20px; color: #222; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ text-align: left; padding: 6px 10px; border-bottom: 1px solid #ddd; }}
+th {{ background: #f4f4f4; }}
+</style>
+</head>
+<body>
+<h1>{name}</h1>
+<p>Version {version}</p>
+<p>{description}</p>
+<h2>Formats</h2>
+<ul>{format_items}</ul>
+<h2>Parameters</h2>
+<table>
+<tr><th>Parameter</th><th>Range</th><th>Default</th><th>Description</th></tr>
+{rows}
+</table>
+</body>
+</html>
+"#,
+        name = name,
+        version = version,
+        description = html_escape(meta.description.trim()),
+        format_items = format_items,
+        rows = rows,
+    )
+}
+
+/// Generate the README/manual for a built plugin version
+#[tauri::command]
+pub async fn generate_manual(project_name: String, version: u32) -> Result<ManualResult, String> {
+    let project_path = get_workspace_path().join("projects").join(&project_name);
+    let meta: ProjectMeta = fs::read_to_string(project_path.join(".vstworkshop/metadata.json"))
+        .map_err(|_| format!("Project '{}' not found", project_name))
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| format!("Failed to parse metadata: {}", e)))?;
+
+    let folder_version = version.max(1);
+    let output_path = get_output_path()
+        .join(&project_name)
+        .join(format!("v{}", folder_version));
+    let snake_name = project_name.replace('-', "_");
+    let formats: Vec<&str> = ["clap", "vst3"]
+        .into_iter()
+        .filter(|f| output_path.join(format!("{}.{}", snake_name, f)).exists())
+        .collect();
+    if !formats.contains(&"clap") {
+        return Err("No built CLAP plugin found. Build the project first.".to_string());
+    }
+
+    let clap_path = output_path.join(format!("{}.clap", snake_name));
+    let params = tokio::task::spawn_blocking(move || {
+        let docs = extract_param_docs(&read_project_sources(&project_path));
+        read_plugin_params(&clap_path, &docs)
+    })
+    .await
+    .map_err(|e| format!("Parameter introspection failed: {}", e))??;
+
+    let manual_dir = get_manual_dir(&project_name, folder_version);
+    fs::create_dir_all(&manual_dir)
+        .map_err(|e| format!("Failed to create manual folder: {}", e))?;
+    let markdown_path = manual_dir.join("README.md");
+    let html_path = manual_dir.join("MANUAL.html");
+    fs::write(&markdown_path, render_markdown(&meta, folder_version, &formats, &params))
+        .map_err(|e| format!("Failed to write README.md: {}", e))?;
+    fs::write(&html_path, render_html(&meta, folder_version, &formats, &params))
+        .map_err(|e| format!("Failed to write MANUAL.html: {}", e))?;
+
+    Ok(ManualResult {
+        markdown_path: markdown_path.to_string_lossy().to_string(),
+        html_path: html_path.to_string_lossy().to_string(),
+        parameters: params,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_param_docs() {
+        let source = r#"
+struct MyParams {
+    /// Output level after processing
+    #[id = "gain"]
+    pub gain: FloatParam,
+
+    #[id = "mix"]
+    pub mix: FloatParam,
+
+    /// Cutoff of the tone filter
+    /// in Hz
+    #[id = "tone"]
+    pub tone_freq: FloatParam,
+}
+
+impl Default for MyParams {
+    fn default() -> Self {
+        Self {
+            gain: FloatParam::new(
+                "Gain",
+                util::db_to_gain(0.0),
+            ),
+            mix: FloatParam::new("Mix", 1.0, FloatRange::Linear { min: 0.0, max: 1.0 }),
+            tone_freq: FloatParam::new("Tone", 1000.0, range),
+        }
+    }
+}
+"#;
+        let docs = extract_param_docs(source);
+        assert_eq!(docs.get("Gain").map(String::as_str), Some("Output level after processing"));
+        assert_eq!(docs.get("Tone").map(String::as_str), Some("Cutoff of the tone filter in Hz"));
+        assert!(!docs.contains_key("Mix"));
+    }
+}
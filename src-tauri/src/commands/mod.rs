@@ -12,6 +12,65 @@ pub mod logging;
 pub mod files;
 pub mod share;
 pub mod preview;
+pub mod presets;
+pub mod sample_library;
+pub mod param_stress;
+pub mod compare;
+pub mod dsp_tests;
+pub mod automation;
+pub mod auto_build;
+pub mod diagnostics;
+pub mod crash_reports;
+pub mod settings;
+pub mod manual;
+pub mod identity;
+pub mod framework;
+pub mod plugin_ids;
+pub mod activity;
+pub mod distortion;
+pub mod torture_test;
+pub mod state_roundtrip;
+pub mod denormal_test;
+pub mod issues;
+pub mod task_queue;
+pub mod permissions;
+pub mod context_snapshot;
+pub mod patches;
+pub mod voice;
+pub mod reference;
+pub mod recipes;
+pub mod smoothing_audit;
+pub mod editor_recording;
+pub mod session;
+pub mod automation_api;
+pub mod editor_integration;
+pub mod debug_preview;
+pub mod plugin_crashes;
+pub mod sanitizer;
+pub mod benchmarks;
+pub mod param_docs;
+pub mod library_usage;
+pub mod build_report;
+pub mod artifact_manifest;
+pub mod package_signing;
+pub mod upload;
+pub mod announcement;
+pub mod update_manifest;
+pub mod install_verify;
+pub mod plugin_folders;
+pub mod tutorial;
+pub mod i18n;
+pub mod stream_mirror;
+pub mod architecture;
+pub mod prefetch;
+pub mod dependency_bundle;
+pub mod assignments;
+pub mod workspace_report;
+pub mod size_report;
+pub mod release_optimization;
+pub mod host_smoke_test;
+pub mod block_size_audit;
+pub mod analysis_jobs;
 
 /// Get an extended PATH that includes common tool installation directories.
 /// Bundled macOS apps don't inherit the user's shell PATH, so we need to
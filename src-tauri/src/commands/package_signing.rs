@@ -0,0 +1,202 @@
+//! Package checksums and signatures
+//!
+//! Every package gets a `{zip}.sha256` file in `sha256sum` format, so testers can
+//! check a download and the sums can be published with a release. Packages can
+//! also be signed with minisign (`{zip}.minisig`) or GPG (`{zip}.asc`) using the
+//! user's own tools and keys; `verify_package` checks both.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::artifact_manifest::sha256_of;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningMethod {
+    Minisign,
+    Gpg,
+}
+
+impl SigningMethod {
+    fn extension(self) -> &'static str {
+        match self {
+            SigningMethod::Minisign => "minisig",
+            SigningMethod::Gpg => "asc",
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SignatureCheck {
+    pub method: SigningMethod,
+    pub valid: bool,
+    /// What the signing tool reported
+    pub output: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PackageVerification {
+    pub sha256: String,
+    /// None when there's no .sha256 file next to the package
+    pub checksum_matches: Option<bool>,
+    /// None when the package isn't signed
+    pub signature: Option<SignatureCheck>,
+}
+
+fn sidecar(zip_path: &Path, extension: &str) -> PathBuf {
+    let mut name = zip_path.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+/// `sha256sum` line for a file: "<hex>  <file name>"
+fn checksum_line(sha256: &str, zip_path: &Path) -> String {
+    let name = zip_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    format!("{}  {}\n", sha256, name)
+}
+
+/// Hash from a `sha256sum` file (the first field of its first line)
+fn parse_checksum_file(content: &str) -> Option<String> {
+    let hash = content.lines().next()?.split_whitespace().next()?;
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())).then(|| hash.to_lowercase())
+}
+
+fn run_tool(mut command: Command, tool: &str) -> Result<(bool, String), String> {
+    let output = command
+        .env("PATH", super::get_extended_path())
+        .output()
+        .map_err(|e| format!("Failed to run {} (is it installed?): {}", tool, e))?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok((output.status.success(), text.trim().to_string()))
+}
+
+/// Write `{zip}.sha256` for a package; returns the hash
+pub(crate) fn write_checksum(zip_path: &Path) -> Result<String, String> {
+    let sha256 = sha256_of(zip_path)?;
+    let path = sidecar(zip_path, "sha256");
+    fs::write(&path, checksum_line(&sha256, zip_path)).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(sha256)
+}
+
+/// Delete signatures left next to a package by an earlier build, so a rebuilt
+/// archive can't sit beside a signature for the old one (and a switch from
+/// minisign to GPG doesn't leave a `.minisig` that `verify_package` checks first)
+pub(crate) fn remove_signatures(zip_path: &Path) -> Result<(), String> {
+    for method in [SigningMethod::Minisign, SigningMethod::Gpg] {
+        let path = sidecar(zip_path, method.extension());
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to remove stale signature {}: {}", path.display(), e)),
+        }
+    }
+    Ok(())
+}
+
+/// Sign a package, writing the signature next to it
+/// `key`: minisign secret key file or GPG key ID (the tool's default when None).
+/// minisign keys must be usable without a password prompt.
+pub(crate) fn sign_package(zip_path: &Path, method: SigningMethod, key: Option<&str>) -> Result<PathBuf, String> {
+    let signature = sidecar(zip_path, method.extension());
+    let mut command = match method {
+        SigningMethod::Minisign => {
+            let mut command = Command::new("minisign");
+            command.arg("-S").arg("-m").arg(zip_path).arg("-x").arg(&signature);
+            if let Some(key) = key {
+                command.args(["-s", key]);
+            }
+            command
+        }
+        SigningMethod::Gpg => {
+            let mut command = Command::new("gpg");
+            command.args(["--batch", "--yes", "--armor", "--detach-sign", "--output"]).arg(&signature);
+            if let Some(key) = key {
+                command.args(["--local-user", key]);
+            }
+            command.arg(zip_path);
+            command
+        }
+    };
+    command.stdin(std::process::Stdio::null());
+    let tool = match method {
+        SigningMethod::Minisign => "minisign",
+        SigningMethod::Gpg => "gpg",
+    };
+    match run_tool(command, tool)? {
+        (true, _) => Ok(signature),
+        (false, output) => Err(format!("Failed to sign package with {}: {}", tool, output)),
+    }
+}
+
+/// Check a package against its .sha256 file and, if present, its signature
+/// `public_key`: minisign public key file (GPG uses the keyring).
+#[tauri::command]
+pub async fn verify_package(zip_path: String, public_key: Option<String>) -> Result<PackageVerification, String> {
+    let zip_path = PathBuf::from(zip_path);
+    if !zip_path.is_file() {
+        return Err(format!("Package not found: {}", zip_path.display()));
+    }
+    tokio::task::spawn_blocking(move || {
+        let sha256 = sha256_of(&zip_path)?;
+        let checksum_matches = fs::read_to_string(sidecar(&zip_path, "sha256"))
+            .ok()
+            .map(|content| parse_checksum_file(&content).as_deref() == Some(sha256.as_str()));
+
+        let minisig = sidecar(&zip_path, SigningMethod::Minisign.extension());
+        let asc = sidecar(&zip_path, SigningMethod::Gpg.extension());
+        let signature = if minisig.exists() {
+            let mut command = Command::new("minisign");
+            command.arg("-V").arg("-m").arg(&zip_path).arg("-x").arg(&minisig);
+            if let Some(key) = &public_key {
+                command.args(["-p", key]);
+            }
+            let (valid, output) = run_tool(command, "minisign")?;
+            Some(SignatureCheck {
+                method: SigningMethod::Minisign,
+                valid,
+                output,
+            })
+        } else if asc.exists() {
+            let mut command = Command::new("gpg");
+            command.args(["--batch", "--verify"]).arg(&asc).arg(&zip_path);
+            let (valid, output) = run_tool(command, "gpg")?;
+            Some(SignatureCheck {
+                method: SigningMethod::Gpg,
+                valid,
+                output,
+            })
+        } else {
+            None
+        };
+
+        Ok(PackageVerification {
+            sha256,
+            checksum_matches,
+            signature,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_file_round_trip() {
+        let sha = "a".repeat(64);
+        let line = checksum_line(&sha, Path::new("/out/gain_v3.zip"));
+        assert_eq!(line, format!("{}  gain_v3.zip\n", sha));
+        assert_eq!(parse_checksum_file(&line), Some(sha));
+        assert_eq!(parse_checksum_file("not a hash  gain_v3.zip"), None);
+        assert_eq!(sidecar(Path::new("/out/gain_v3.zip"), "asc"), PathBuf::from("/out/gain_v3.zip.asc"));
+    }
+}
@@ -0,0 +1,285 @@
+//! Parameter documentation
+//!
+//! After each release build the plugin is loaded headlessly and its CLAP
+//! parameters are written to `docs/PARAMETERS.md` in the project. A snapshot per
+//! version (`.vstworkshop/parameters/v{n}.json`) lets versions be diffed, so a
+//! changelog can list added, removed, and changed parameters.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::activity::project_dir;
+use super::chat::resolve_project_version;
+use crate::audio::plugin::PluginInstance;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ParamDoc {
+    pub id: u32,
+    pub name: String,
+    pub module: String,
+    pub min: f64,
+    pub max: f64,
+    pub default: f64,
+    /// Values as the plugin displays them, e.g. "-6.0 dB"
+    pub min_text: Option<String>,
+    pub max_text: Option<String>,
+    pub default_text: Option<String>,
+    pub unit: Option<String>,
+    /// "stepped", "read-only", "bypass"
+    pub flags: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ParamChange {
+    pub id: u32,
+    pub name: String,
+    /// Which of name/range/default/unit/flags changed
+    pub fields: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ParamDiff {
+    /// Version compared against (the latest earlier version with a snapshot)
+    pub from_version: Option<u32>,
+    pub to_version: u32,
+    pub added: Vec<ParamDoc>,
+    pub removed: Vec<ParamDoc>,
+    pub changed: Vec<ParamChange>,
+}
+
+fn get_docs_path(project_path: &Path) -> PathBuf {
+    project_path.join("docs").join("PARAMETERS.md")
+}
+
+fn get_snapshot_dir(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("parameters")
+}
+
+fn load_snapshot(project_path: &Path, version: u32) -> Option<Vec<ParamDoc>> {
+    fs::read_to_string(get_snapshot_dir(project_path).join(format!("v{}.json", version)))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+/// Latest version before `version` that has a snapshot
+fn previous_snapshot_version(project_path: &Path, version: u32) -> Option<u32> {
+    fs::read_dir(get_snapshot_dir(project_path))
+        .ok()?
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.strip_prefix('v')?.strip_suffix(".json")?.parse::<u32>().ok()
+        })
+        .filter(|v| *v < version)
+        .max()
+}
+
+/// Unit from a displayed value: "-6.0 dB" -> "dB", "50%" -> "%" (none for "Sine")
+fn unit_of(text: &str) -> Option<String> {
+    let number = text.trim_start_matches(['-', '+']);
+    if !number.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let unit = number.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.' || c == ',').trim();
+    (!unit.is_empty() && unit.len() <= 8).then(|| unit.to_string())
+}
+
+/// Load the bundle headlessly and describe its parameters
+fn introspect(bundle: &Path) -> Result<(String, Vec<ParamDoc>), String> {
+    let plugin = PluginInstance::load(bundle, 48000.0, 512)?;
+    let params = plugin
+        .list_params()
+        .into_iter()
+        .map(|p| {
+            let text = |value: f64| plugin.param_value_to_text(p.id, value);
+            let (min_text, max_text, default_text) = (text(p.min_value), text(p.max_value), text(p.default_value));
+            let unit = [&default_text, &max_text, &min_text]
+                .into_iter()
+                .flatten()
+                .find_map(|t| unit_of(t));
+            let flags = [(p.is_stepped, "stepped"), (p.is_read_only, "read-only"), (p.is_bypass, "bypass")]
+                .into_iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, flag)| flag.to_string())
+                .collect();
+            ParamDoc {
+                id: p.id,
+                name: p.name,
+                module: p.module,
+                min: p.min_value,
+                max: p.max_value,
+                default: p.default_value,
+                min_text,
+                max_text,
+                default_text,
+                unit,
+                flags,
+            }
+        })
+        .collect();
+    Ok((plugin.name.clone(), params))
+}
+
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn render_markdown(plugin_name: &str, version: u32, params: &[ParamDoc]) -> String {
+    let show_module = params.iter().any(|p| !p.module.is_empty());
+    let mut md = format!(
+        "# {} Parameters\n\nGenerated from the v{} build by freqlab - it's rewritten on every build, so edits here are lost.\n\n",
+        plugin_name, version
+    );
+    if params.is_empty() {
+        md.push_str("This plugin has no parameters.\n");
+        return md;
+    }
+
+    if show_module {
+        md.push_str("| Module | Name | ID | Range | Default | Unit | Flags |\n|---|---|---|---|---|---|---|\n");
+    } else {
+        md.push_str("| Name | ID | Range | Default | Unit | Flags |\n|---|---|---|---|---|---|\n");
+    }
+    for p in params {
+        let range = format!(
+            "{} – {}",
+            p.min_text.clone().unwrap_or_else(|| p.min.to_string()),
+            p.max_text.clone().unwrap_or_else(|| p.max.to_string())
+        );
+        let default = p.default_text.clone().unwrap_or_else(|| p.default.to_string());
+        if show_module {
+            md.push_str(&format!("| {} ", cell(&p.module)));
+        }
+        md.push_str(&format!(
+            "| {} | `{:#010x}` | {} | {} | {} | {} |\n",
+            cell(&p.name),
+            p.id,
+            cell(&range),
+            cell(&default),
+            p.unit.as_deref().unwrap_or(""),
+            p.flags.join(", ")
+        ));
+    }
+    md
+}
+
+/// Compare two parameter lists by CLAP id
+fn diff_params(old: &[ParamDoc], new: &[ParamDoc]) -> (Vec<ParamDoc>, Vec<ParamDoc>, Vec<ParamChange>) {
+    let added = new.iter().filter(|p| !old.iter().any(|o| o.id == p.id)).cloned().collect();
+    let removed = old.iter().filter(|o| !new.iter().any(|p| p.id == o.id)).cloned().collect();
+    let changed = new
+        .iter()
+        .filter_map(|p| {
+            let o = old.iter().find(|o| o.id == p.id)?;
+            let fields: Vec<String> = [
+                ("name", o.name != p.name),
+                ("range", o.min != p.min || o.max != p.max),
+                ("default", o.default != p.default),
+                ("unit", o.unit != p.unit),
+                ("flags", o.flags != p.flags),
+            ]
+            .into_iter()
+            .filter(|(_, differs)| *differs)
+            .map(|(field, _)| field.to_string())
+            .collect();
+            (!fields.is_empty()).then(|| ParamChange {
+                id: p.id,
+                name: p.name.clone(),
+                fields,
+            })
+        })
+        .collect();
+    (added, removed, changed)
+}
+
+fn diff_against_previous(project_path: &Path, version: u32, params: &[ParamDoc]) -> ParamDiff {
+    let from_version = previous_snapshot_version(project_path, version);
+    let old = from_version
+        .and_then(|v| load_snapshot(project_path, v))
+        .unwrap_or_default();
+    let (added, removed, changed) = if from_version.is_some() {
+        diff_params(&old, params)
+    } else {
+        (Vec::new(), Vec::new(), Vec::new())
+    };
+    ParamDiff {
+        from_version,
+        to_version: version,
+        added,
+        removed,
+        changed,
+    }
+}
+
+/// Document a freshly built bundle's parameters in the project (blocking - loads the plugin)
+pub(crate) fn write_parameter_docs(project_path: &Path, version: u32, bundle: &Path) -> Result<ParamDiff, String> {
+    let (plugin_name, params) = introspect(bundle)?;
+
+    let docs_path = get_docs_path(project_path);
+    if let Some(parent) = docs_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create docs folder: {}", e))?;
+    }
+    fs::write(&docs_path, render_markdown(&plugin_name, version, &params))
+        .map_err(|e| format!("Failed to write {}: {}", docs_path.display(), e))?;
+
+    let snapshot_dir = get_snapshot_dir(project_path);
+    fs::create_dir_all(&snapshot_dir).map_err(|e| format!("Failed to create parameter snapshot folder: {}", e))?;
+    let json = serde_json::to_string_pretty(&params).map_err(|e| format!("Failed to serialize parameters: {}", e))?;
+    fs::write(snapshot_dir.join(format!("v{}.json", version)), json)
+        .map_err(|e| format!("Failed to save parameter snapshot: {}", e))?;
+
+    Ok(diff_against_previous(project_path, version, &params))
+}
+
+/// Parameters added, removed, and changed in a version (defaults to the current
+/// one) since the previous documented version
+#[tauri::command]
+pub async fn get_parameter_changes(project_name: String, version: Option<u32>) -> Result<ParamDiff, String> {
+    let version = resolve_project_version(&project_name, version).await?;
+    let project_path = project_dir(&project_name);
+    let params = load_snapshot(&project_path, version)
+        .ok_or_else(|| format!("No parameter docs for v{} yet - build it first", version))?;
+    Ok(diff_against_previous(&project_path, version, &params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(id: u32, name: &str, default: f64) -> ParamDoc {
+        ParamDoc {
+            id,
+            name: name.to_string(),
+            module: String::new(),
+            min: 0.0,
+            max: 1.0,
+            default,
+            min_text: None,
+            max_text: None,
+            default_text: None,
+            unit: None,
+            flags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_unit_of() {
+        assert_eq!(unit_of("-6.0 dB").as_deref(), Some("dB"));
+        assert_eq!(unit_of("50%").as_deref(), Some("%"));
+        assert_eq!(unit_of("1.2 kHz").as_deref(), Some("kHz"));
+        assert_eq!(unit_of("-inf dB"), None);
+        assert_eq!(unit_of("Sine"), None);
+        assert_eq!(unit_of("0.50"), None);
+    }
+
+    #[test]
+    fn test_diff_params() {
+        let old = vec![param(1, "Gain", 0.5), param(2, "Mix", 1.0)];
+        let new = vec![param(1, "Drive", 0.5), param(3, "Tone", 0.5)];
+        let (added, removed, changed) = diff_params(&old, &new);
+        assert_eq!(added.iter().map(|p| p.id).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(removed.iter().map(|p| p.id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(changed, vec![ParamChange { id: 1, name: "Drive".to_string(), fields: vec!["name".to_string()] }]);
+    }
+}
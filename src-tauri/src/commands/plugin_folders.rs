@@ -0,0 +1,352 @@
+//! Finding stale copies of a plugin in the system plugin folders
+//!
+//! After a rename or a few rounds of manual copying, a DAW can end up with
+//! several bundles of the same plugin and load whichever it finds first.
+//! `scan_installed_copies` looks through the standard VST3/CLAP folders and the
+//! DAW paths from settings for bundles with the project's name or any VST3/CLAP
+//! ID it has used, and flags the ones that differ from its latest build.
+//! `remove_installed_copies` deletes the ones the user picks.
+
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::artifact_manifest::{bundle_files, compare_files};
+use super::plugin_ids::project_id_history;
+use super::projects::get_output_path;
+use super::publish::expand_tilde;
+use super::settings::current_settings;
+
+/// Bytes read at a time when searching a plugin binary for IDs
+const SEARCH_CHUNK_BYTES: usize = 1024 * 1024;
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledCopy {
+    pub path: String,
+    /// "VST3", "CLAP"
+    pub format: String,
+    /// What ties it to the project: "name", "VST3 ID", "CLAP ID"
+    pub matched_by: Vec<String>,
+    /// Identical to the bundle in the project's latest build
+    pub matches_latest_build: bool,
+    pub modified_at: Option<String>,
+}
+
+fn format_of(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "vst3" => Some("VST3"),
+        "clap" => Some("CLAP"),
+        _ => None,
+    }
+}
+
+/// Standard plugin folders for this platform plus the DAW paths in settings
+fn plugin_dirs() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let mut candidates: Vec<PathBuf> = if cfg!(target_os = "macos") {
+        vec![
+            home.join("Library/Audio/Plug-Ins/VST3"),
+            home.join("Library/Audio/Plug-Ins/CLAP"),
+            PathBuf::from("/Library/Audio/Plug-Ins/VST3"),
+            PathBuf::from("/Library/Audio/Plug-Ins/CLAP"),
+        ]
+    } else if cfg!(windows) {
+        let local = dirs::data_local_dir().unwrap_or_default();
+        vec![
+            PathBuf::from(r"C:\Program Files\Common Files\VST3"),
+            PathBuf::from(r"C:\Program Files\Common Files\CLAP"),
+            local.join(r"Programs\Common\VST3"),
+            local.join(r"Programs\Common\CLAP"),
+        ]
+    } else {
+        vec![
+            home.join(".vst3"),
+            home.join(".clap"),
+            PathBuf::from("/usr/lib/vst3"),
+            PathBuf::from("/usr/lib/clap"),
+            PathBuf::from("/usr/local/lib/vst3"),
+            PathBuf::from("/usr/local/lib/clap"),
+        ]
+    };
+    let paths = current_settings().daw_paths;
+    for config in [&paths.reaper, &paths.ableton, &paths.fl_studio, &paths.logic, &paths.other] {
+        for dir in [&config.vst3, &config.clap] {
+            if !dir.is_empty() {
+                candidates.push(expand_tilde(dir));
+            }
+        }
+    }
+
+    let mut existing = Vec::new();
+    for dir in candidates {
+        if dir.is_dir() && !existing.contains(&dir) {
+            existing.push(dir);
+        }
+    }
+    existing
+}
+
+/// Plugin bundles in a folder, including ones in vendor subfolders
+fn find_bundles(dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .min_depth(1)
+        .max_depth(2)
+        .into_iter()
+        // Don't look inside the bundles themselves
+        .filter_entry(|e| e.depth() == 1 || e.path().parent().and_then(format_of).is_none())
+        .flatten()
+        .filter(|e| format_of(e.path()).is_some())
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Bytes that can continue a CLAP ID (reverse-DNS plus `_` and `-`)
+fn is_id_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-')
+}
+
+/// An ID to look for; CLAP IDs must stand alone, so "com.x.tape" doesn't match
+/// inside "com.x.tape_deck"
+struct IdNeedle<'a> {
+    bytes: &'a [u8],
+    whole_word: bool,
+}
+
+/// Which needles occur in `reader`, read in chunks so large binaries aren't loaded whole
+/// Empty needles never match (projects may be missing an ID).
+fn find_ids(mut reader: impl Read, needles: &[IdNeedle]) -> std::io::Result<Vec<bool>> {
+    let mut found = vec![false; needles.len()];
+    // Enough of the previous chunk to finish any match that crossed into this one,
+    // plus the byte before it
+    let carry = needles.iter().map(|n| n.bytes.len()).max().unwrap_or(0) + 1;
+    let mut buffer: Vec<u8> = Vec::with_capacity(SEARCH_CHUNK_BYTES + carry);
+    let mut chunk = vec![0u8; SEARCH_CHUNK_BYTES];
+    let mut at_file_start = true;
+
+    loop {
+        let read = reader.read(&mut chunk)?;
+        let eof = read == 0;
+        buffer.extend_from_slice(&chunk[..read]);
+
+        for (needle, found) in needles.iter().zip(found.iter_mut()) {
+            let len = needle.bytes.len();
+            if *found || len == 0 || buffer.len() < len {
+                continue;
+            }
+            *found = (0..=buffer.len() - len).any(|i| {
+                if &buffer[i..i + len] != needle.bytes {
+                    return false;
+                }
+                if !needle.whole_word {
+                    return true;
+                }
+                // i == 0 after a carry was already judged with its preceding byte
+                let starts_id = if i == 0 { at_file_start } else { !is_id_byte(buffer[i - 1]) };
+                // A match at the very end waits for the next chunk's first byte
+                let ends_id = match buffer.get(i + len) {
+                    Some(&next) => !is_id_byte(next),
+                    None => eof,
+                };
+                starts_id && ends_id
+            });
+        }
+
+        if eof || found.iter().all(|f| *f) {
+            return Ok(found);
+        }
+        if buffer.len() > carry {
+            buffer.drain(..buffer.len() - carry);
+            at_file_start = false;
+        }
+    }
+}
+
+/// Mach-O, ELF or PE executable header
+fn is_binary(header: &[u8]) -> bool {
+    const MACHO_MAGICS: [[u8; 4]; 6] = [
+        [0xfe, 0xed, 0xfa, 0xce],
+        [0xfe, 0xed, 0xfa, 0xcf],
+        [0xce, 0xfa, 0xed, 0xfe],
+        [0xcf, 0xfa, 0xed, 0xfe],
+        [0xca, 0xfe, 0xba, 0xbe],
+        [0xbe, 0xba, 0xfe, 0xca],
+    ];
+    header.starts_with(b"\x7fELF")
+        || header.starts_with(b"MZ")
+        || MACHO_MAGICS.iter().any(|magic| header.starts_with(magic))
+}
+
+/// The plugin binaries in a bundle (or the bundle itself, for single-file .clap/.vst3)
+fn bundle_binaries(bundle: &Path) -> Vec<PathBuf> {
+    WalkDir::new(bundle)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            let mut header = [0u8; 4];
+            File::open(e.path())
+                .and_then(|mut f| f.read_exact(&mut header))
+                .is_ok_and(|_| is_binary(&header))
+        })
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Why a bundle belongs to the project, given its file name and which of its
+/// (VST3, CLAP) IDs were found in the binary
+fn match_reasons(file_stem: &str, snake_name: &str, vst3_found: bool, clap_found: bool) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if file_stem == snake_name {
+        reasons.push("name".to_string());
+    }
+    if vst3_found {
+        reasons.push("VST3 ID".to_string());
+    }
+    if clap_found {
+        reasons.push("CLAP ID".to_string());
+    }
+    reasons
+}
+
+/// Whether any of the project's (VST3, CLAP) IDs appear in the bundle's binaries
+fn search_bundle(bundle: &Path, ids: &[(String, String)]) -> (bool, bool) {
+    let needles: Vec<IdNeedle> = ids
+        .iter()
+        .flat_map(|(vst3, clap)| {
+            [
+                IdNeedle { bytes: vst3.as_bytes(), whole_word: false },
+                IdNeedle { bytes: clap.as_bytes(), whole_word: true },
+            ]
+        })
+        .collect();
+
+    let (mut vst3_found, mut clap_found) = (false, false);
+    for binary in bundle_binaries(bundle) {
+        let Ok(found) = File::open(&binary).and_then(|f| find_ids(f, &needles)) else {
+            continue;
+        };
+        // Needles alternate VST3, CLAP
+        vst3_found |= found.iter().step_by(2).any(|f| *f);
+        clap_found |= found.iter().skip(1).step_by(2).any(|f| *f);
+    }
+    (vst3_found, clap_found)
+}
+
+/// The newest versioned output folder of a project
+fn latest_build_dir(project_name: &str) -> Option<PathBuf> {
+    let project_output = get_output_path().join(project_name);
+    fs::read_dir(&project_output)
+        .ok()?
+        .flatten()
+        .filter_map(|e| {
+            let version = e.file_name().to_string_lossy().strip_prefix('v')?.parse::<u32>().ok()?;
+            Some((version, e.path()))
+        })
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, path)| path)
+}
+
+fn matches_build(bundle: &Path, built: &Path) -> bool {
+    let (Some(root), Some(built_root)) = (bundle.parent(), built.parent()) else {
+        return false;
+    };
+    match (bundle_files(root, bundle), bundle_files(built_root, built)) {
+        (Ok(mut actual), Ok(mut expected)) => {
+            actual.sort_by(|a, b| a.path.cmp(&b.path));
+            expected.sort_by(|a, b| a.path.cmp(&b.path));
+            compare_files(&expected, &actual).is_empty()
+        }
+        _ => false,
+    }
+}
+
+fn scan(project_name: &str) -> Vec<InstalledCopy> {
+    let snake_name = project_name.replace('-', "_");
+    let ids = project_id_history(project_name);
+    let latest = latest_build_dir(project_name);
+
+    let mut copies = Vec::new();
+    for dir in plugin_dirs() {
+        for bundle in find_bundles(&dir) {
+            let Some(format) = format_of(&bundle) else { continue };
+            let stem = bundle.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let (vst3_found, clap_found) = search_bundle(&bundle, &ids);
+            let matched_by = match_reasons(&stem, &snake_name, vst3_found, clap_found);
+            if matched_by.is_empty() {
+                continue;
+            }
+            let built = latest.as_ref().map(|dir| dir.join(format!("{}.{}", snake_name, format.to_lowercase())));
+            copies.push(InstalledCopy {
+                path: bundle.to_string_lossy().to_string(),
+                format: format.to_string(),
+                matched_by,
+                matches_latest_build: built.is_some_and(|b| b.exists() && matches_build(&bundle, &b)),
+                modified_at: fs::metadata(&bundle)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+            });
+        }
+    }
+    copies
+}
+
+/// Installed bundles of a project (by name or any ID it has used) in the plugin folders
+#[tauri::command]
+pub async fn scan_installed_copies(project_name: String) -> Result<Vec<InstalledCopy>, String> {
+    tokio::task::spawn_blocking(move || scan(&project_name))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))
+}
+
+/// Delete installed copies found by `scan_installed_copies`; returns the paths removed
+/// Paths the scan doesn't turn up are refused, so this can't delete arbitrary folders.
+#[tauri::command]
+pub async fn remove_installed_copies(project_name: String, paths: Vec<String>) -> Result<Vec<String>, String> {
+    tokio::task::spawn_blocking(move || {
+        let found = scan(&project_name);
+        let mut removed = Vec::new();
+        for path in paths {
+            if !found.iter().any(|c| c.path == path) {
+                return Err(format!("{} isn't an installed copy of {}", path, project_name));
+            }
+            let bundle = Path::new(&path);
+            let result = if bundle.is_dir() { fs::remove_dir_all(bundle) } else { fs::remove_file(bundle) };
+            result.map_err(|e| format!("Failed to remove {}: {}", path, e))?;
+            removed.push(path);
+        }
+        Ok(removed)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_ids() {
+        let needles = [
+            IdNeedle { bytes: b"VSTWorkshop67890", whole_word: false },
+            IdNeedle { bytes: b"com.freqlab.old_tape", whole_word: true },
+            IdNeedle { bytes: b"com.freqlab.tape", whole_word: true },
+            IdNeedle { bytes: b"", whole_word: true },
+        ];
+        let binary = b"\x00\x01com.freqlab.old_tape\x00VSTWorkshop67890\x00com.freqlab.tape_deck\x00".to_vec();
+        assert_eq!(find_ids(binary.as_slice(), &needles).unwrap(), vec![true, true, false, false]);
+
+        // A match split across chunks, right at the end of the file
+        let mut large = vec![0u8; SEARCH_CHUNK_BYTES - 5];
+        large.extend_from_slice(b"com.freqlab.tape");
+        assert_eq!(find_ids(large.as_slice(), &needles[2..3]).unwrap(), vec![true]);
+        large.push(b'_');
+        assert_eq!(find_ids(large.as_slice(), &needles[2..3]).unwrap(), vec![false]);
+
+        assert_eq!(match_reasons("tape_deck", "tape_deck", false, true), vec!["name", "CLAP ID"]);
+        assert!(match_reasons("reverb", "tape_deck", false, false).is_empty());
+    }
+}
@@ -0,0 +1,409 @@
+//! Workspace-wide plugin ID registry
+//!
+//! VST3 class IDs are short hashes of the project name and CLAP IDs are derived
+//! from the vendor, so two projects (or a project and a deleted/renamed one) can
+//! end up with the same ID - and DAWs will then load the wrong plugin. The
+//! registry at ~/VSTWorkshop/plugin-ids.json records every project's IDs plus
+//! the IDs it used before, so new IDs avoid anything ever handed out.
+//!
+//! Once a project has been packaged for distribution its IDs are marked released,
+//! and regenerating them requires an explicit override.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use super::identity::{read_const_str, replace_const_str};
+use super::projects::{generate_vst3_id, get_workspace_path};
+
+/// Serializes read-modify-write cycles on the registry file
+static REGISTRY_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Salts tried before giving up on finding a free VST3 ID
+const MAX_VST3_ATTEMPTS: u32 = 1000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IdHistoryEntry {
+    pub vst3_class_id: String,
+    pub clap_id: String,
+    pub changed_at: String,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisteredIds {
+    pub vst3_class_id: String,
+    pub clap_id: String,
+    /// Set once the plugin has been packaged - IDs shouldn't change after that
+    #[serde(default)]
+    pub released: bool,
+    /// The project folder was deleted; its IDs stay reserved
+    #[serde(default)]
+    pub deleted: bool,
+    /// Previous IDs, oldest first
+    #[serde(default)]
+    pub history: Vec<IdHistoryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct IdRegistry {
+    /// Keyed by project folder name
+    pub projects: BTreeMap<String, RegisteredIds>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct IdCollision {
+    /// "vst3" or "clap"
+    pub kind: String,
+    pub id: String,
+    pub projects: Vec<String>,
+}
+
+fn get_registry_path() -> PathBuf {
+    get_workspace_path().join("plugin-ids.json")
+}
+
+fn load_registry() -> IdRegistry {
+    fs::read_to_string(get_registry_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_registry(registry: &IdRegistry) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(registry)
+        .map_err(|e| format!("Failed to serialize ID registry: {}", e))?;
+    fs::write(get_registry_path(), json).map_err(|e| format!("Failed to write ID registry: {}", e))
+}
+
+impl RegisteredIds {
+    fn set_ids(&mut self, vst3_class_id: &str, clap_id: &str, reason: &str) {
+        if self.vst3_class_id == vst3_class_id && self.clap_id == clap_id {
+            return;
+        }
+        if !self.vst3_class_id.is_empty() || !self.clap_id.is_empty() {
+            self.history.push(IdHistoryEntry {
+                vst3_class_id: self.vst3_class_id.clone(),
+                clap_id: self.clap_id.clone(),
+                changed_at: chrono::Utc::now().to_rfc3339(),
+                reason: reason.to_string(),
+            });
+        }
+        self.vst3_class_id = vst3_class_id.to_string();
+        self.clap_id = clap_id.to_string();
+    }
+
+    /// Every VST3 and CLAP ID this project has used
+    fn all_ids(&self) -> impl Iterator<Item = (&str, &str)> {
+        std::iter::once((self.vst3_class_id.as_str(), self.clap_id.as_str())).chain(
+            self.history
+                .iter()
+                .map(|h| (h.vst3_class_id.as_str(), h.clap_id.as_str())),
+        )
+    }
+}
+
+impl IdRegistry {
+    /// Whether an ID is or was used by anything other than the project's own live registration
+    /// A deleted project's IDs and a project's previous IDs stay taken, including for a
+    /// project recreated under the same name.
+    fn is_taken(&self, project_name: &str, kind: &str, id: &str) -> bool {
+        self.taken_by(project_name, kind, id).is_some()
+    }
+
+    /// The registry entry that has (or had) an ID, as for `is_taken`
+    fn taken_by(&self, project_name: &str, kind: &str, id: &str) -> Option<&str> {
+        self.projects.iter().find_map(|(name, ids)| {
+            let own_live = name.as_str() == project_name && !ids.deleted;
+            ids.all_ids()
+                .skip(usize::from(own_live))
+                .any(|(vst3, clap)| if kind == "vst3" { vst3 == id } else { clap == id })
+                .then_some(name.as_str())
+        })
+    }
+
+    fn first_free_vst3_id(&self, project_name: &str, skip: &[String]) -> String {
+        (0..MAX_VST3_ATTEMPTS)
+            .map(|salt| generate_vst3_id(project_name, salt))
+            .find(|id| !self.is_taken(project_name, "vst3", id) && !skip.contains(id))
+            // 100k possible IDs - only reachable with a pathological registry
+            .unwrap_or_else(|| generate_vst3_id(project_name, MAX_VST3_ATTEMPTS))
+    }
+
+    /// IDs shared by more than one live project
+    fn collisions(&self) -> Vec<IdCollision> {
+        let mut by_id: HashMap<(&str, &str), Vec<String>> = HashMap::new();
+        for (name, ids) in self.projects.iter().filter(|(_, ids)| !ids.deleted) {
+            by_id.entry(("vst3", ids.vst3_class_id.as_str())).or_default().push(name.clone());
+            by_id.entry(("clap", ids.clap_id.as_str())).or_default().push(name.clone());
+        }
+
+        let mut collisions: Vec<IdCollision> = by_id
+            .into_iter()
+            .filter(|((_, id), projects)| !id.is_empty() && projects.len() > 1)
+            .map(|((kind, id), projects)| IdCollision {
+                kind: kind.to_string(),
+                id: id.to_string(),
+                projects,
+            })
+            .collect();
+        collisions.sort_by(|a, b| (&a.kind, &a.id).cmp(&(&b.kind, &b.id)));
+        collisions
+    }
+}
+
+/// Pick a VST3 class ID for a new project that no other project has used
+pub(crate) fn allocate_vst3_id(project_name: &str) -> String {
+    let _lock = REGISTRY_LOCK.lock();
+    load_registry().first_free_vst3_id(project_name, &[])
+}
+
+/// Pick a CLAP ID for a new or imported project: `preferred` if no other project
+/// has used it, otherwise `preferred` with the first free numeric suffix
+pub(crate) fn allocate_clap_id(project_name: &str, preferred: &str) -> String {
+    let _lock = REGISTRY_LOCK.lock();
+    let registry = load_registry();
+    std::iter::once(preferred.to_string())
+        .chain((2..).map(|n| format!("{}_{}", preferred, n)))
+        .find(|id| !registry.is_taken(project_name, "clap", id))
+        .unwrap_or_default()
+}
+
+/// Record a newly created project's IDs
+pub(crate) fn register_project(project_name: &str, vst3_class_id: &str, clap_id: &str) {
+    let _lock = REGISTRY_LOCK.lock();
+    let mut registry = load_registry();
+    let entry = registry.projects.entry(project_name.to_string()).or_default();
+    // A project recreated under a deleted project's name starts fresh but keeps its history
+    entry.set_ids(vst3_class_id, clap_id, "project recreated");
+    entry.deleted = false;
+    entry.released = false;
+    if let Err(e) = save_registry(&registry) {
+        log::warn!("{}", e);
+    }
+}
+
+/// Fail if another project uses (or used) a CLAP ID
+pub(crate) fn check_clap_id_available(project_name: &str, clap_id: &str) -> Result<(), String> {
+    let _lock = REGISTRY_LOCK.lock();
+    let registry = load_registry();
+    match registry.taken_by(project_name, "clap", clap_id) {
+        Some(other) => Err(format!(
+            "CLAP ID '{}' is already used by project '{}'",
+            clap_id, other
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Record a CLAP ID change made through the identity editor
+pub(crate) fn record_clap_id_change(project_name: &str, clap_id: &str) {
+    let _lock = REGISTRY_LOCK.lock();
+    let mut registry = load_registry();
+    let entry = registry.projects.entry(project_name.to_string()).or_default();
+    let vst3 = entry.vst3_class_id.clone();
+    entry.set_ids(&vst3, clap_id, "identity updated");
+    if let Err(e) = save_registry(&registry) {
+        log::warn!("{}", e);
+    }
+}
+
+/// Mark a project's IDs as released (called when it's packaged for distribution)
+pub(crate) fn mark_released(project_name: &str) {
+    let _lock = REGISTRY_LOCK.lock();
+    let mut registry = load_registry();
+    if let Some(entry) = registry.projects.get_mut(project_name) {
+        if !entry.released {
+            entry.released = true;
+            if let Err(e) = save_registry(&registry) {
+                log::warn!("{}", e);
+            }
+        }
+    }
+}
+
+/// Keep a deleted project's IDs reserved
+pub(crate) fn mark_deleted(project_name: &str) {
+    let _lock = REGISTRY_LOCK.lock();
+    let mut registry = load_registry();
+    if let Some(entry) = registry.projects.get_mut(project_name) {
+        entry.deleted = true;
+        if let Err(e) = save_registry(&registry) {
+            log::warn!("{}", e);
+        }
+    }
+}
+
+/// Every (VST3, CLAP) ID pair a project has used, current source first
+pub(crate) fn project_id_history(project_name: &str) -> Vec<(String, String)> {
+    let registry = load_registry();
+    let mut ids: Vec<(String, String)> = read_source_ids(project_name).into_iter().collect();
+    if let Some(entry) = registry.projects.get(project_name) {
+        for (vst3, clap) in entry.all_ids() {
+            if !ids.iter().any(|(v, c)| v == vst3 && c == clap) {
+                ids.push((vst3.to_string(), clap.to_string()));
+            }
+        }
+    }
+    ids
+}
+
+/// Read a project's IDs from its source
+fn read_source_ids(project_name: &str) -> Option<(String, String)> {
+    let lib_path = get_workspace_path()
+        .join("projects")
+        .join(project_name)
+        .join("src/lib.rs");
+    let source = fs::read_to_string(lib_path).ok()?;
+    Some((
+        read_const_str(&source, "VST3_CLASS_ID").unwrap_or_default(),
+        read_const_str(&source, "CLAP_ID").unwrap_or_default(),
+    ))
+}
+
+/// Bring the registry in line with the projects on disk
+/// Projects created before the registry existed are added, and IDs edited by hand
+/// (or by Claude) are recorded as history.
+fn sync_registry(registry: &mut IdRegistry) {
+    let projects_dir = get_workspace_path().join("projects");
+    let Ok(entries) = fs::read_dir(&projects_dir) else {
+        return;
+    };
+    for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if let Some((vst3, clap)) = read_source_ids(&name) {
+            let ids = registry.projects.entry(name).or_default();
+            ids.set_ids(&vst3, &clap, "changed in source");
+            ids.deleted = false;
+        }
+    }
+}
+
+/// Get the ID registry, synced with the projects on disk
+#[tauri::command]
+pub async fn get_id_registry() -> Result<IdRegistry, String> {
+    let _lock = REGISTRY_LOCK.lock();
+    let mut registry = load_registry();
+    sync_registry(&mut registry);
+    save_registry(&registry)?;
+    Ok(registry)
+}
+
+/// Find VST3 or CLAP IDs shared by more than one project
+#[tauri::command]
+pub async fn check_id_collisions() -> Result<Vec<IdCollision>, String> {
+    let _lock = REGISTRY_LOCK.lock();
+    let mut registry = load_registry();
+    sync_registry(&mut registry);
+    save_registry(&registry)?;
+    Ok(registry.collisions())
+}
+
+/// Give a project a fresh VST3 class ID
+/// Released plugins are refused unless `force` is set, since changing the ID makes
+/// DAWs treat it as a different plugin and breaks existing sessions.
+#[tauri::command]
+pub async fn regenerate_plugin_ids(project_name: String, force: bool) -> Result<RegisteredIds, String> {
+    let project_path = get_workspace_path().join("projects").join(&project_name);
+    let lib_path = project_path.join("src/lib.rs");
+    let source = fs::read_to_string(&lib_path)
+        .map_err(|_| format!("Project '{}' not found", project_name))?;
+
+    let updated = {
+        let _lock = REGISTRY_LOCK.lock();
+        let mut registry = load_registry();
+        sync_registry(&mut registry);
+
+        let current = registry.projects.get(&project_name).cloned().unwrap_or_default();
+        if current.released && !force {
+            return Err(format!(
+                "'{}' has been released - changing its VST3 ID will break existing DAW sessions",
+                project_name
+            ));
+        }
+
+        // Never hand back an ID this project already used
+        let used: Vec<String> = current.all_ids().map(|(vst3, _)| vst3.to_string()).collect();
+        let new_id = registry.first_free_vst3_id(&project_name, &used);
+
+        let new_source = replace_const_str(&source, "VST3_CLASS_ID", &new_id)
+            .ok_or_else(|| "Could not find `const VST3_CLASS_ID` in src/lib.rs".to_string())?;
+        fs::write(&lib_path, new_source).map_err(|e| format!("Failed to write lib.rs: {}", e))?;
+
+        let entry = registry.projects.entry(project_name.clone()).or_default();
+        entry.set_ids(&new_id, &current.clap_id, "regenerated");
+        let updated = entry.clone();
+        save_registry(&registry)?;
+        updated
+    };
+
+    let project_path_str = project_path.to_string_lossy().to_string();
+    if let Err(e) = super::git::commit_changes(&project_path_str, "Regenerate VST3 class ID").await {
+        log::warn!("Failed to commit regenerated ID for {}: {}", project_name, e);
+    }
+
+    log::info!("Regenerated VST3 class ID for {}: {}", project_name, updated.vst3_class_id);
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(vst3: &str, clap: &str) -> RegisteredIds {
+        RegisteredIds {
+            vst3_class_id: vst3.to_string(),
+            clap_id: clap.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_collisions_ignore_deleted_projects() {
+        let mut registry = IdRegistry::default();
+        registry.projects.insert("a".into(), ids("VSTWorkshop00001", "com.x.a"));
+        registry.projects.insert("b".into(), ids("VSTWorkshop00001", "com.x.b"));
+        let mut deleted = ids("VSTWorkshop00001", "com.x.a");
+        deleted.deleted = true;
+        registry.projects.insert("c".into(), deleted);
+
+        assert_eq!(
+            registry.collisions(),
+            vec![IdCollision {
+                kind: "vst3".into(),
+                id: "VSTWorkshop00001".into(),
+                projects: vec!["a".into(), "b".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_history_keeps_ids_reserved() {
+        let mut old = ids("VSTWorkshop00001", "com.x.old");
+        old.set_ids("VSTWorkshop00002", "com.x.old", "regenerated");
+        assert_eq!(old.history.len(), 1);
+
+        let mut registry = IdRegistry::default();
+        registry.projects.insert("old".into(), old);
+        assert!(registry.is_taken("new", "vst3", "VSTWorkshop00001"));
+        assert!(registry.is_taken("new", "vst3", "VSTWorkshop00002"));
+        assert!(!registry.is_taken("old", "vst3", "VSTWorkshop00002"));
+        // Its own previous IDs aren't handed back
+        assert!(registry.is_taken("old", "vst3", "VSTWorkshop00001"));
+
+        // A project recreated under a deleted project's name gets neither its IDs
+        registry.projects.get_mut("old").unwrap().deleted = true;
+        assert!(registry.is_taken("old", "vst3", "VSTWorkshop00002"));
+        assert!(registry.is_taken("old", "clap", "com.x.old"));
+
+        let mut gone = ids(&generate_vst3_id("gone", 0), "com.x.gone");
+        gone.deleted = true;
+        registry.projects.insert("gone".into(), gone);
+        assert_ne!(registry.first_free_vst3_id("gone", &[]), generate_vst3_id("gone", 0));
+    }
+}
@@ -0,0 +1,196 @@
+//! Downloading build dependencies ahead of time
+//!
+//! A first build downloads nih-plug, its git dependencies, and a few hundred
+//! crates, which on a slow connection can take most of half an hour.
+//! `prefetch_dependencies` warms cargo's registry and git caches up front: it
+//! fetches the workspace (xtask and existing projects), then a throwaway crate
+//! with the same Cargo.toml a new project of each chosen UI framework gets.
+//! Progress streams as `prefetch-stream` events.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tauri::Emitter;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+
+use super::projects::{ensure_workspace, get_workspace_path, render_project_sources, CreateProjectInput};
+
+pub(crate) const UI_FRAMEWORKS: [&str; 3] = ["webview", "egui", "native"];
+
+/// Workspace folder for the throwaway template crates
+pub(crate) const PREFETCH_DIR: &str = ".prefetch";
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum PrefetchStreamEvent {
+    #[serde(rename = "start")]
+    Start { step: String },
+    #[serde(rename = "output")]
+    Output { line: String },
+    #[serde(rename = "done")]
+    Done { success: bool },
+    #[serde(rename = "error")]
+    Error { message: String },
+}
+
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchResult {
+    pub downloaded_crates: usize,
+    /// Git dependencies cargo cloned or updated
+    pub git_repositories: usize,
+    pub duration_ms: u64,
+}
+
+/// Count downloads in a line of `cargo fetch` output
+fn observe_fetch_line(result: &mut PrefetchResult, line: &str) {
+    let line = line.trim_start();
+    if let Some(rest) = line.strip_prefix("Downloaded ") {
+        // Skip the "Downloaded 12 crates (3.4 MB) in 2.1s" summary
+        if rest.split_whitespace().next().is_some_and(|w| w.parse::<usize>().is_err()) {
+            result.downloaded_crates += 1;
+        }
+    } else if line.starts_with("Updating git repository") {
+        result.git_repositories += 1;
+    }
+}
+
+/// Manifest for a throwaway crate with a new project's dependencies
+/// (its own `[workspace]` so cargo doesn't treat it as part of the workspace around it)
+fn prefetch_manifest(ui_framework: &str) -> Option<String> {
+    let input = CreateProjectInput {
+        name: "prefetch".to_string(),
+        display_name: None,
+        description: String::new(),
+        template: "effect".to_string(),
+        ui_framework: ui_framework.to_string(),
+        vendor_name: None,
+        vendor_url: None,
+        vendor_email: None,
+        components: None,
+    };
+    render_project_sources(&input, "0000000000000000")
+        .into_iter()
+        .find(|file| file.path == "Cargo.toml")
+        .map(|file| format!("{}\n[workspace]\n", file.content))
+}
+
+/// Write a throwaway crate for a UI framework under `parent`; returns its folder
+pub(crate) fn write_template_crate(parent: &Path, framework: &str) -> Result<PathBuf, String> {
+    let manifest = prefetch_manifest(framework).ok_or("Failed to render the project template")?;
+    let dir = parent.join(framework);
+    fs::create_dir_all(dir.join("src")).map_err(|e| format!("Failed to create prefetch project: {}", e))?;
+    fs::write(dir.join("Cargo.toml"), manifest).map_err(|e| format!("Failed to write prefetch project: {}", e))?;
+    fs::write(dir.join("src/lib.rs"), "").map_err(|e| format!("Failed to write prefetch project: {}", e))?;
+    // Start from the workspace's lockfile so the same versions get used
+    let _ = fs::copy(get_workspace_path().join("Cargo.lock"), dir.join("Cargo.lock"));
+    Ok(dir)
+}
+
+/// Run `cargo fetch` in a folder, streaming its output
+async fn cargo_fetch(dir: &Path, result: &mut PrefetchResult, emit: &impl Fn(PrefetchStreamEvent)) -> Result<(), String> {
+    let mut child = Command::new("cargo")
+        .arg("fetch")
+        .current_dir(dir)
+        .env("PATH", super::get_extended_path())
+        // Ride out dropped connections instead of failing the whole fetch
+        .env("CARGO_NET_RETRY", "10")
+        .env("CARGO_HTTP_TIMEOUT", "120")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to spawn cargo: {}", e))?;
+
+    let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        observe_fetch_line(result, &line);
+        emit(PrefetchStreamEvent::Output { line });
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for cargo: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err("cargo fetch failed; check your connection and try again".to_string())
+    }
+}
+
+async fn run_prefetch(frameworks: &[&str], emit: &impl Fn(PrefetchStreamEvent)) -> Result<PrefetchResult, String> {
+    let started = std::time::Instant::now();
+    let mut result = PrefetchResult::default();
+
+    ensure_workspace()?;
+    let workspace = get_workspace_path();
+    emit(PrefetchStreamEvent::Start {
+        step: "workspace".to_string(),
+    });
+    cargo_fetch(&workspace, &mut result, emit).await?;
+
+    let prefetch_dir = workspace.join(PREFETCH_DIR);
+    for framework in frameworks {
+        let dir = write_template_crate(&prefetch_dir, framework)?;
+        emit(PrefetchStreamEvent::Start {
+            step: framework.to_string(),
+        });
+        let fetched = cargo_fetch(&dir, &mut result, emit).await;
+        let _ = fs::remove_dir_all(&dir);
+        fetched?;
+    }
+    let _ = fs::remove_dir(&prefetch_dir);
+
+    result.duration_ms = started.elapsed().as_millis() as u64;
+    Ok(result)
+}
+
+/// Download the dependencies builds need, for one UI framework ("webview",
+/// "egui", "native") or all of them when `ui_framework` is None
+#[tauri::command]
+pub async fn prefetch_dependencies(ui_framework: Option<String>, window: tauri::Window) -> Result<PrefetchResult, String> {
+    let frameworks: Vec<&str> = match ui_framework.as_deref() {
+        Some(framework) if UI_FRAMEWORKS.contains(&framework) => vec![framework],
+        Some(framework) => return Err(format!("Unknown UI framework: {}", framework)),
+        None => UI_FRAMEWORKS.to_vec(),
+    };
+    let emit = |event: PrefetchStreamEvent| {
+        let _ = window.emit("prefetch-stream", event);
+    };
+
+    let result = run_prefetch(&frameworks, &emit).await;
+    match &result {
+        Ok(_) => emit(PrefetchStreamEvent::Done { success: true }),
+        Err(message) => {
+            emit(PrefetchStreamEvent::Error {
+                message: message.clone(),
+            });
+            emit(PrefetchStreamEvent::Done { success: false });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe_fetch_line() {
+        let mut result = PrefetchResult::default();
+        for line in [
+            "    Updating crates.io index",
+            "    Updating git repository `https://github.com/robbert-vdh/nih-plug.git`",
+            "  Downloaded serde v1.0.200",
+            "  Downloaded 2 crates (1.2 MB) in 0.85s",
+        ] {
+            observe_fetch_line(&mut result, line);
+        }
+        assert_eq!(result.git_repositories, 1);
+        assert_eq!(result.downloaded_crates, 1);
+    }
+}
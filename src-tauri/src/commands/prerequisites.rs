@@ -5,6 +5,9 @@ use std::time::Duration;
 use tauri::Emitter;
 use tokio::io::{AsyncBufReadExt, BufReader};
 
+use super::i18n::{tr, tr_with};
+use super::upload::{delete_secret, load_secret, store_secret};
+
 // Track active child process PIDs for cleanup on exit
 static ACTIVE_CHILD_PIDS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
 
@@ -47,7 +50,13 @@ pub enum InstallEvent {
     #[serde(rename = "start")]
     Start { step: String },
     #[serde(rename = "output")]
-    Output { line: String },
+    Output {
+        line: String,
+        /// Catalog key of a translated line, so the UI can follow progress in any
+        /// language; None for raw tool output
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code: Option<String>,
+    },
     #[serde(rename = "done")]
     Done { success: bool },
     #[serde(rename = "error")]
@@ -56,12 +65,49 @@ pub enum InstallEvent {
     ActionRequired { action: String, message: String },
 }
 
+impl InstallEvent {
+    /// A translated progress line tagged with its catalog key
+    fn message(key: &str) -> Self {
+        Self::Output {
+            line: tr(key),
+            code: Some(key.to_string()),
+        }
+    }
+
+    fn message_with(key: &str, args: &[(&str, &str)]) -> Self {
+        Self::Output {
+            line: tr_with(key, args),
+            code: Some(key.to_string()),
+        }
+    }
+
+    /// An untranslated line (tool output, error text)
+    fn raw(line: impl Into<String>) -> Self {
+        Self::Output {
+            line: line.into(),
+            code: None,
+        }
+    }
+}
+
 #[derive(Serialize, Clone)]
 pub struct PrerequisiteStatus {
     pub xcode_cli: CheckResult,
     pub rust: CheckResult,
     pub claude_cli: CheckResult,
     pub claude_auth: CheckResult,
+    /// Who Claude is signed in as, when the CLI reports it
+    pub claude_account: Option<ClaudeAccount>,
+    /// Rosetta or a Rust toolchain that doesn't match the CPU (see architecture.rs)
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct ClaudeAccount {
+    pub email: Option<String>,
+    pub organization: Option<String>,
+    /// Subscription ("pro", "max") or auth method ("api_key") as reported by the CLI
+    pub plan: Option<String>,
 }
 
 #[derive(Serialize, Clone)]
@@ -142,7 +188,7 @@ fn check_xcode() -> CheckResult {
         _ => CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Run: xcode-select --install".to_string()),
+            message: Some(tr("prereq.hint.xcode")),
         },
     }
 }
@@ -162,7 +208,7 @@ fn check_rust() -> CheckResult {
         _ => CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Install from https://rustup.rs".to_string()),
+            message: Some(tr("prereq.hint.rust")),
         },
     }
 }
@@ -180,75 +226,64 @@ fn check_claude_cli() -> CheckResult {
         _ => CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Run: curl -fsSL https://claude.ai/install.sh | bash".to_string()),
+            message: Some(tr("prereq.hint.claude")),
         },
     }
 }
 
-fn check_claude_auth() -> CheckResult {
+fn check_claude_auth() -> (CheckResult, Option<ClaudeAccount>) {
     // First check if claude is installed using 'which'
     let cli_check = run_command_with_timeout("which", &["claude"], 3);
     if cli_check.is_none() || !cli_check.as_ref().unwrap().status.success() {
-        return CheckResult {
+        let result = CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Install Claude CLI first".to_string()),
+            message: Some(tr("prereq.hint.claude_first")),
         };
+        return (result, None);
     }
 
     let home = std::env::var("HOME").unwrap_or_default();
-
-    // Primary check: Look for Claude credentials in macOS keychain
-    // This is the most reliable indicator since Claude stores OAuth tokens here
-    if let Some(output) = run_command_with_timeout(
-        "security",
-        &["find-generic-password", "-s", "Claude Code-credentials"],
-        3,
-    ) {
-        if output.status.success() {
-            return CheckResult {
-                status: CheckStatus::Installed,
-                version: None,
-                message: Some("Authenticated".to_string()),
-            };
-        }
-    }
-
-    // Fallback to file-based checks for edge cases
-    if check_auth_files(&home) {
-        return CheckResult {
+    let (authenticated, account) = match claude_auth_status() {
+        Some(status) => (status.logged_in, status.account),
+        None => (is_authenticated_by_heuristics(&home), None),
+    };
+    if authenticated {
+        let result = CheckResult {
             status: CheckStatus::Installed,
             version: None,
-            message: Some("Authenticated".to_string()),
+            message: Some(tr("prereq.status.authenticated")),
         };
+        return (result, account);
     }
 
-    // No auth indicators found
+    // Not signed in
     let claude_dir = std::path::Path::new(&home).join(".claude");
-    if claude_dir.exists() {
-        CheckResult {
-            status: CheckStatus::NeedsConfig,
-            version: None,
-            message: Some("Sign in to continue".to_string()),
-        }
+    let message = if claude_dir.exists() {
+        tr("prereq.status.sign_in_to_continue")
     } else {
-        CheckResult {
-            status: CheckStatus::NeedsConfig,
-            version: None,
-            message: Some("Sign in required".to_string()),
-        }
-    }
+        tr("prereq.status.sign_in_required")
+    };
+    let result = CheckResult {
+        status: CheckStatus::NeedsConfig,
+        version: None,
+        message: Some(message),
+    };
+    (result, None)
 }
 
 #[tauri::command]
 pub async fn check_prerequisites() -> PrerequisiteStatus {
     // Run checks in a blocking thread pool to not freeze the UI
     tokio::task::spawn_blocking(|| {
+        let (claude_auth, claude_account) = check_claude_auth();
         PrerequisiteStatus {
             xcode_cli: check_xcode(),
             rust: check_rust(),
             claude_cli: check_claude_cli(),
-            claude_auth: check_claude_auth(),
+            claude_auth,
+            claude_account,
+            warnings: super::architecture::architecture_warnings(),
         }
     })
     .await
@@ -256,23 +291,25 @@ pub async fn check_prerequisites() -> PrerequisiteStatus {
         xcode_cli: CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Check failed".to_string()),
+            message: Some(tr("prereq.status.check_failed")),
         },
         rust: CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Check failed".to_string()),
+            message: Some(tr("prereq.status.check_failed")),
         },
         claude_cli: CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Check failed".to_string()),
+            message: Some(tr("prereq.status.check_failed")),
         },
         claude_auth: CheckResult {
             status: CheckStatus::NotInstalled,
             version: None,
-            message: Some("Check failed".to_string()),
+            message: Some(tr("prereq.status.check_failed")),
         },
+        claude_account: None,
+        warnings: Vec::new(),
     })
 }
 
@@ -346,9 +383,7 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
         if output.status.success() {
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Apple Developer Tools already installed.".to_string(),
-                },
+                InstallEvent::message("install.xcode.already_installed"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: true });
             return Ok(true);
@@ -357,9 +392,7 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Preparing to install Apple Developer Tools...".to_string(),
-        },
+        InstallEvent::message("install.xcode.preparing"),
     );
 
     // Create the trigger file that makes softwareupdate list CLT
@@ -367,17 +400,13 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
     if let Err(e) = tokio::fs::write(trigger_file, "").await {
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: format!("Warning: Could not create trigger file: {}", e),
-            },
+            InstallEvent::message_with("install.xcode.trigger_failed", &[("error", &e.to_string())]),
         );
     }
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Finding available version...".to_string(),
-        },
+        InstallEvent::message("install.xcode.finding_version"),
     );
 
     // Run softwareupdate -l to find the CLT package name
@@ -431,9 +460,7 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
             // Fallback to GUI installer if softwareupdate doesn't list CLT
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Could not find package via softwareupdate, using GUI installer...".to_string(),
-                },
+                InstallEvent::message("install.xcode.gui_fallback"),
             );
             return install_xcode_gui_fallback(window).await;
         }
@@ -441,24 +468,20 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: format!("Found: {}", package),
-        },
+        InstallEvent::message_with("install.xcode.found", &[("package", &package)]),
     );
 
     let _ = window.emit(
         "install-stream",
         InstallEvent::ActionRequired {
             action: "password".to_string(),
-            message: "Enter your Mac password when prompted".to_string(),
+            message: tr("install.xcode.password_prompt"),
         },
     );
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Installing (this may take 5-10 minutes)...".to_string(),
-        },
+        InstallEvent::message("install.xcode.installing"),
     );
 
     // Install with admin privileges using osascript
@@ -485,9 +508,7 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
             if output.status.success() {
                 let _ = window.emit(
                     "install-stream",
-                    InstallEvent::Output {
-                        line: "Apple Developer Tools installed successfully!".to_string(),
-                    },
+                    InstallEvent::message("install.xcode.success"),
                 );
                 let _ = window.emit("install-stream", InstallEvent::Done { success: true });
                 return Ok(true);
@@ -498,9 +519,7 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
     // If softwareupdate failed, fall back to GUI
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Silent install failed, trying GUI installer...".to_string(),
-        },
+        InstallEvent::message("install.xcode.silent_failed"),
     );
     install_xcode_gui_fallback(window).await
 }
@@ -509,9 +528,7 @@ pub async fn install_xcode(window: tauri::Window) -> Result<bool, String> {
 async fn install_xcode_gui_fallback(window: tauri::Window) -> Result<bool, String> {
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Opening system installer dialog...".to_string(),
-        },
+        InstallEvent::message("install.xcode.opening_dialog"),
     );
 
     // Trigger the install dialog
@@ -538,7 +555,7 @@ async fn install_xcode_gui_fallback(window: tauri::Window) -> Result<bool, Strin
         "install-stream",
         InstallEvent::ActionRequired {
             action: "xcode_dialog".to_string(),
-            message: "Click 'Install' in the popup (may be behind this window)".to_string(),
+            message: tr("install.xcode.click_install"),
         },
     );
 
@@ -555,9 +572,7 @@ async fn install_xcode_gui_fallback(window: tauri::Window) -> Result<bool, Strin
             if output.status.success() {
                 let _ = window.emit(
                     "install-stream",
-                    InstallEvent::Output {
-                        line: "Installation complete!".to_string(),
-                    },
+                    InstallEvent::message("install.xcode.complete"),
                 );
                 let _ = window.emit("install-stream", InstallEvent::Done { success: true });
                 return Ok(true);
@@ -568,18 +583,14 @@ async fn install_xcode_gui_fallback(window: tauri::Window) -> Result<bool, Strin
             let minutes = (attempt * 3) / 60;
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: format!("Still waiting... ({} min)", minutes),
-                },
+                InstallEvent::message_with("install.xcode.still_waiting", &[("minutes", &minutes.to_string())]),
             );
         }
     }
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Installation timed out. Try installing from Software Update in your Mac settings.".to_string(),
-        },
+        InstallEvent::message("install.xcode.timed_out"),
     );
     let _ = window.emit("install-stream", InstallEvent::Done { success: false });
     Err("Installation timed out".to_string())
@@ -601,9 +612,7 @@ pub async fn install_rust(window: tauri::Window) -> Result<bool, String> {
             let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: format!("{} is already installed.", version),
-                },
+                InstallEvent::message_with("install.already_installed", &[("version", &version)]),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: true });
             return Ok(true);
@@ -612,9 +621,7 @@ pub async fn install_rust(window: tauri::Window) -> Result<bool, String> {
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Downloading Rust installer...".to_string(),
-        },
+        InstallEvent::message("install.rust.downloading"),
     );
 
     // Use -y for non-interactive
@@ -642,9 +649,7 @@ pub async fn install_rust(window: tauri::Window) -> Result<bool, String> {
         {
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Rust installed successfully!".to_string(),
-                },
+                InstallEvent::message("install.rust.success"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: true });
             Ok(true)
@@ -653,9 +658,7 @@ pub async fn install_rust(window: tauri::Window) -> Result<bool, String> {
             // This shouldn't happen since we use extended PATH, but just in case
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Rust installed! Click Recheck to verify.".to_string(),
-                },
+                InstallEvent::message("install.rust.recheck"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: true });
             Ok(true)
@@ -664,7 +667,7 @@ pub async fn install_rust(window: tauri::Window) -> Result<bool, String> {
         let msg = "Failed to install Rust. Check your internet connection and try again.";
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output { line: msg.to_string() },
+            InstallEvent::raw(msg),
         );
         let _ = window.emit("install-stream", InstallEvent::Done { success: false });
         Err(msg.to_string())
@@ -686,9 +689,7 @@ pub async fn install_claude_cli(window: tauri::Window) -> Result<bool, String> {
         if output.status.success() {
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Claude Code is already installed.".to_string(),
-                },
+                InstallEvent::message("install.claude.already_installed"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: true });
             return Ok(true);
@@ -697,9 +698,7 @@ pub async fn install_claude_cli(window: tauri::Window) -> Result<bool, String> {
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Downloading Claude Code...".to_string(),
-        },
+        InstallEvent::message("install.claude.downloading"),
     );
 
     // Use the native installer - no Node.js required!
@@ -789,9 +788,7 @@ pub async fn install_claude_cli(window: tauri::Window) -> Result<bool, String> {
 
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Claude Code installed successfully!".to_string(),
-                },
+                InstallEvent::message("install.claude.success"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: true });
             Ok(true)
@@ -799,30 +796,22 @@ pub async fn install_claude_cli(window: tauri::Window) -> Result<bool, String> {
             // Binary exists but didn't run - might be permissions issue
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Claude Code was installed but couldn't be verified.".to_string(),
-                },
+                InstallEvent::message("install.claude.unverified"),
             );
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "This may be a permissions issue. Try closing and reopening the app.".to_string(),
-                },
+                InstallEvent::message("install.claude.permissions"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: false });
             Err("Claude installed but verification failed".to_string())
         } else {
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Installation completed but Claude not detected.".to_string(),
-                },
+                InstallEvent::message("install.claude.not_detected"),
             );
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Please close and reopen this app, then click Recheck.".to_string(),
-                },
+                InstallEvent::message("install.claude.reopen"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: false });
             Err("Claude installed but requires app restart to detect".to_string())
@@ -830,39 +819,27 @@ pub async fn install_claude_cli(window: tauri::Window) -> Result<bool, String> {
     } else {
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "Installation failed. Please check your internet connection.".to_string(),
-            },
+            InstallEvent::message("install.claude.failed"),
         );
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "".to_string(),
-            },
+            InstallEvent::raw(""),
         );
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "You can also try installing manually:".to_string(),
-            },
+            InstallEvent::message("install.claude.manual_intro"),
         );
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "1. Open Terminal".to_string(),
-            },
+            InstallEvent::message("install.claude.manual_step1"),
         );
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "2. Run: curl -fsSL https://claude.ai/install.sh | bash".to_string(),
-            },
+            InstallEvent::message("install.claude.manual_step2"),
         );
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "3. Come back here and click Recheck".to_string(),
-            },
+            InstallEvent::message("install.claude.manual_step3"),
         );
         let _ = window.emit("install-stream", InstallEvent::Done { success: false });
         Err("Failed to install Claude Code".to_string())
@@ -889,7 +866,7 @@ pub async fn start_claude_auth(window: tauri::Window) -> Result<bool, String> {
             let msg = "Claude Code not found. Please install it first.";
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output { line: msg.to_string() },
+                InstallEvent::raw(msg),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: false });
             return Err(msg.to_string());
@@ -921,9 +898,7 @@ pub async fn start_claude_auth(window: tauri::Window) -> Result<bool, String> {
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Opening Terminal for sign-in...".to_string(),
-        },
+        InstallEvent::message("signin.opening_terminal"),
     );
 
     // Claude requires a real TTY for /login command, so we must use Terminal
@@ -998,7 +973,7 @@ pub async fn start_claude_auth(window: tauri::Window) -> Result<bool, String> {
                 "install-stream",
                 InstallEvent::ActionRequired {
                     action: "browser_auth".to_string(),
-                    message: "Sign in with your Claude account in the browser".to_string(),
+                    message: tr("signin.browser"),
                 },
             );
             true
@@ -1042,7 +1017,7 @@ pub async fn start_claude_auth(window: tauri::Window) -> Result<bool, String> {
                 "install-stream",
                 InstallEvent::ActionRequired {
                     action: "manual_login".to_string(),
-                    message: "In Terminal: type /login then press Enter".to_string(),
+                    message: tr("signin.terminal_login"),
                 },
             );
             false
@@ -1051,9 +1026,7 @@ pub async fn start_claude_auth(window: tauri::Window) -> Result<bool, String> {
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Waiting for sign-in to complete...".to_string(),
-        },
+        InstallEvent::message("signin.waiting"),
     );
 
     // Poll for authentication completion (auto-detect when done)
@@ -1144,9 +1117,7 @@ pub async fn start_claude_auth(window: tauri::Window) -> Result<bool, String> {
 
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Sign-in successful!".to_string(),
-                },
+                InstallEvent::message("signin.success"),
             );
             let _ = window.emit("install-stream", InstallEvent::Done { success: true });
             return Ok(true);
@@ -1156,18 +1127,14 @@ pub async fn start_claude_auth(window: tauri::Window) -> Result<bool, String> {
         if attempt > 0 && attempt % 15 == 0 {
             let _ = window.emit(
                 "install-stream",
-                InstallEvent::Output {
-                    line: "Still waiting for sign-in...".to_string(),
-                },
+                InstallEvent::message("signin.still_waiting"),
             );
         }
     }
 
     let _ = window.emit(
         "install-stream",
-        InstallEvent::Output {
-            line: "Sign-in timed out. Click Recheck after signing in.".to_string(),
-        },
+        InstallEvent::message("signin.timed_out"),
     );
     let _ = window.emit("install-stream", InstallEvent::Done { success: false });
     Err("Sign-in timed out".to_string())
@@ -1213,11 +1180,89 @@ fn find_claude_binary() -> Option<String> {
     None
 }
 
-/// Check if Claude is authenticated by looking for keychain credentials
-/// Claude stores auth tokens in macOS keychain under "Claude Code-credentials"
+/// Sign-in state as reported by `claude auth status`
+#[derive(Debug, PartialEq)]
+struct ClaudeAuthStatus {
+    logged_in: bool,
+    account: Option<ClaudeAccount>,
+}
+
+/// First string found at any of the dotted paths ("account.email")
+fn json_str(value: &serde_json::Value, paths: &[&str]) -> Option<String> {
+    paths.iter().find_map(|path| {
+        path.split('.')
+            .try_fold(value, |v, key| v.get(key))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    })
+}
+
+/// Parse `claude auth status` output: JSON from CLIs that support `--json`,
+/// otherwise the human-readable text. None if the output isn't recognizable.
+fn parse_auth_status(output: &str) -> Option<ClaudeAuthStatus> {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(output.trim()) {
+        let logged_in = ["loggedIn", "logged_in", "authenticated"]
+            .iter()
+            .find_map(|key| value.get(key)?.as_bool())?;
+        let account = ClaudeAccount {
+            email: json_str(&value, &["email", "emailAddress", "account.email", "account.emailAddress"]),
+            organization: json_str(&value, &["orgName", "organizationName", "organization.name", "account.organizationName"]),
+            plan: json_str(&value, &["subscriptionType", "plan", "account.subscriptionType", "authMethod"]),
+        };
+        let account = (logged_in && account != ClaudeAccount::default()).then_some(account);
+        return Some(ClaudeAuthStatus { logged_in, account });
+    }
+
+    let lower = output.to_lowercase();
+    if lower.contains("not logged in") || lower.contains("not authenticated") || lower.contains("not signed in") {
+        return Some(ClaudeAuthStatus { logged_in: false, account: None });
+    }
+    if !lower.contains("logged in") && !lower.contains("authenticated") && !lower.contains("signed in") {
+        return None;
+    }
+    let field = |names: &[&str]| {
+        output.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            let name = name.trim().to_lowercase();
+            names.contains(&name.as_str()).then(|| value.trim().to_string()).filter(|v| !v.is_empty())
+        })
+    };
+    let account = ClaudeAccount {
+        email: output
+            .split_whitespace()
+            .find(|word| word.contains('@') && word.contains('.'))
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_string()),
+        organization: field(&["organization", "org"]),
+        plan: field(&["plan", "subscription", "auth method"]),
+    };
+    let account = (account != ClaudeAccount::default()).then_some(account);
+    Some(ClaudeAuthStatus { logged_in: true, account })
+}
+
+/// Ask the CLI whether it's signed in; None when this CLI version has no status command
+fn claude_auth_status() -> Option<ClaudeAuthStatus> {
+    let output = run_command_with_timeout("claude", &["auth", "status", "--json"], 10)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // An error about an unknown command or option can mention logging in, so
+    // text output only counts on a clean exit or as an explicit "not logged in"
+    parse_auth_status(&stdout)
+        .or_else(|| parse_auth_status(&format!("{}\n{}", stdout, stderr)))
+        .filter(|status| output.status.success() || !status.logged_in)
+}
+
+/// Check if Claude is authenticated, asking the CLI first
 fn is_claude_authenticated(home: &str) -> bool {
-    // Primary check: Look for Claude credentials in macOS keychain
-    // This is the most reliable indicator since Claude stores OAuth tokens here
+    match claude_auth_status() {
+        Some(status) => status.logged_in,
+        None => is_authenticated_by_heuristics(home),
+    }
+}
+
+/// Guess from the keychain and config files, for CLIs without `claude auth status`
+/// Claude stores auth tokens in macOS keychain under "Claude Code-credentials"
+fn is_authenticated_by_heuristics(home: &str) -> bool {
     if let Some(output) = run_command_with_timeout(
         "security",
         &["find-generic-password", "-s", "Claude Code-credentials"],
@@ -1286,7 +1331,7 @@ async fn stream_and_wait(child: &mut tokio::process::Child, window: &tauri::Wind
         let window_clone = window.clone();
         Some(tokio::spawn(async move {
             while let Ok(Some(line)) = reader.next_line().await {
-                let _ = window_clone.emit("install-stream", InstallEvent::Output { line });
+                let _ = window_clone.emit("install-stream", InstallEvent::Output { line, code: None });
             }
         }))
     } else {
@@ -1298,7 +1343,7 @@ async fn stream_and_wait(child: &mut tokio::process::Child, window: &tauri::Wind
         let window_clone = window.clone();
         Some(tokio::spawn(async move {
             while let Ok(Some(line)) = reader.next_line().await {
-                let _ = window_clone.emit("install-stream", InstallEvent::Output { line });
+                let _ = window_clone.emit("install-stream", InstallEvent::Output { line, code: None });
             }
         }))
     } else {
@@ -1318,9 +1363,7 @@ async fn stream_and_wait(child: &mut tokio::process::Child, window: &tauri::Wind
         Err(_) => {
             // Timeout - kill the process
             let _ = child.kill().await;
-            let _ = window.emit("install-stream", InstallEvent::Output {
-                line: "Process timed out after 10 minutes".to_string(),
-            });
+            let _ = window.emit("install-stream", InstallEvent::message("install.timed_out"));
             false
         }
     };
@@ -1512,7 +1555,7 @@ pub async fn prime_admin_privileges(window: tauri::Window) -> Result<bool, Strin
         "install-stream",
         InstallEvent::ActionRequired {
             action: "password".to_string(),
-            message: "Enter your Mac password to authorize installations".to_string(),
+            message: tr("admin.password_prompt"),
         },
     );
 
@@ -1528,20 +1571,330 @@ pub async fn prime_admin_privileges(window: tauri::Window) -> Result<bool, Strin
     if result.status.success() {
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "Admin access granted! Password cached for this session.".to_string(),
-            },
+            InstallEvent::message("admin.granted"),
         );
         let _ = window.emit("install-stream", InstallEvent::Done { success: true });
         Ok(true)
     } else {
         let _ = window.emit(
             "install-stream",
-            InstallEvent::Output {
-                line: "Admin access not granted. You may be prompted again during installation.".to_string(),
-            },
+            InstallEvent::message("admin.denied"),
         );
         let _ = window.emit("install-stream", InstallEvent::Done { success: false });
         Ok(false)
     }
 }
+
+// ============================================================================
+// API Key Sign-In
+// ============================================================================
+
+/// Keychain service and account for the Anthropic API key
+const API_KEY_SERVICE: &str = "freqlab-claude";
+const API_KEY_ACCOUNT: &str = "anthropic-api-key";
+const API_KEY_LABEL: &str = "Anthropic API key for Claude Code";
+
+/// Rough shape check so typos fail before a network round trip
+fn check_api_key_format(api_key: &str) -> Result<(), String> {
+    if !api_key.starts_with("sk-ant-") || api_key.len() < 20 || api_key.chars().any(char::is_whitespace) {
+        return Err(tr("apikey.invalid_format"));
+    }
+    Ok(())
+}
+
+/// Shell command Claude runs (`apiKeyHelper`) to read the key back from the keychain
+fn api_key_helper_command() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        Some(format!("security find-generic-password -s {} -a {} -w", API_KEY_SERVICE, API_KEY_ACCOUNT))
+    } else if cfg!(target_os = "linux") {
+        Some(format!("secret-tool lookup service {} account {}", API_KEY_SERVICE, API_KEY_ACCOUNT))
+    } else {
+        None
+    }
+}
+
+/// Claude settings JSON with `apiKeyHelper` set, keeping everything else
+fn with_api_key_helper(settings: &str, helper: &str) -> Result<String, String> {
+    let mut value: serde_json::Value = if settings.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str(settings).map_err(|e| format!("Failed to parse Claude settings: {}", e))?
+    };
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| "Claude settings aren't a JSON object".to_string())?;
+    object.insert("apiKeyHelper".to_string(), serde_json::Value::String(helper.to_string()));
+    serde_json::to_string_pretty(&value).map_err(|e| format!("Failed to serialize Claude settings: {}", e))
+}
+
+/// Run a one-line prompt to confirm Claude can authenticate; returns the error output on failure
+async fn test_claude_invocation(claude_path: &str) -> Result<(), String> {
+    let output = tokio::process::Command::new(claude_path)
+        .args(["-p", "Reply with OK", "--max-turns", "1"])
+        .env("PATH", super::get_extended_path())
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(Duration::from_secs(90), output).await {
+        Ok(Ok(output)) if output.status.success() => Ok(()),
+        Ok(Ok(output)) => {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Err(if stderr.is_empty() { stdout } else { stderr })
+        }
+        Ok(Err(e)) => Err(format!("Failed to run Claude: {}", e)),
+        Err(_) => Err("Claude didn't respond within 90 seconds".to_string()),
+    }
+}
+
+async fn configure_api_key(api_key: &str, emit: &impl Fn(InstallEvent)) -> Result<(), String> {
+    check_api_key_format(api_key)?;
+    let claude_path = find_claude_binary().ok_or_else(|| tr("prereq.hint.claude_first"))?;
+    let helper = api_key_helper_command().ok_or_else(|| tr("apikey.unsupported_platform"))?;
+
+    emit(InstallEvent::message("apikey.storing"));
+    let previous_key = load_secret(API_KEY_SERVICE, API_KEY_ACCOUNT);
+    store_secret(API_KEY_SERVICE, API_KEY_LABEL, API_KEY_ACCOUNT, api_key)?;
+
+    let result = apply_api_key(&claude_path, &helper, emit).await;
+    if result.is_err() {
+        // Don't leave a rejected key in the keychain; put back the one it replaced
+        match previous_key {
+            Some(key) => {
+                if let Err(e) = store_secret(API_KEY_SERVICE, API_KEY_LABEL, API_KEY_ACCOUNT, &key) {
+                    log::warn!("Failed to restore the previous API key: {}", e);
+                }
+            }
+            None => delete_secret(API_KEY_SERVICE, API_KEY_ACCOUNT),
+        }
+    }
+    result
+}
+
+/// Point Claude's settings at the stored key and check that it works
+async fn apply_api_key(claude_path: &str, helper: &str, emit: &impl Fn(InstallEvent)) -> Result<(), String> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let claude_dir = std::path::Path::new(&home).join(".claude");
+    let settings_file = claude_dir.join("settings.json");
+    let previous = std::fs::read_to_string(&settings_file).ok();
+    let updated = with_api_key_helper(previous.as_deref().unwrap_or(r#"{"model": "opus"}"#), helper)?;
+    std::fs::create_dir_all(&claude_dir).map_err(|e| format!("Failed to create {}: {}", claude_dir.display(), e))?;
+    std::fs::write(&settings_file, updated).map_err(|e| format!("Failed to save Claude settings: {}", e))?;
+
+    emit(InstallEvent::message("apikey.testing"));
+    if let Err(output) = test_claude_invocation(claude_path).await {
+        // Put the settings back so a bad key doesn't replace a working sign-in
+        let _ = match &previous {
+            Some(contents) => std::fs::write(&settings_file, contents),
+            None => std::fs::remove_file(&settings_file),
+        };
+        return Err(tr_with("apikey.rejected", &[("output", &output)]));
+    }
+    Ok(())
+}
+
+/// Sign Claude in with an Anthropic API key instead of the interactive /login
+/// The key goes in the OS keychain and Claude reads it through `apiKeyHelper`,
+/// then a test prompt confirms it works. Progress streams as `install-stream` events.
+#[tauri::command]
+pub async fn set_claude_api_key(window: tauri::Window, api_key: String) -> Result<bool, String> {
+    let emit = |event: InstallEvent| {
+        let _ = window.emit("install-stream", event);
+    };
+    emit(InstallEvent::Start {
+        step: "claude_api_key".to_string(),
+    });
+    match configure_api_key(api_key.trim(), &emit).await {
+        Ok(()) => {
+            emit(InstallEvent::message("signin.success"));
+            emit(InstallEvent::Done { success: true });
+            Ok(true)
+        }
+        Err(message) => {
+            emit(InstallEvent::raw(message.clone()));
+            emit(InstallEvent::Done { success: false });
+            Err(message)
+        }
+    }
+}
+
+// ============================================================================
+// Unattended Setup
+// ============================================================================
+
+/// One installer in an unattended setup run
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct SetupStep {
+    /// Same names as the `install-stream` steps: "xcode", "rust", "claude_cli", "claude_auth"
+    pub step: String,
+    pub needed: bool,
+    /// Disk space the step takes
+    pub disk_gb: f64,
+    /// What the step will ask of whoever is at the machine: "password" or "sign_in"
+    pub requires: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct SetupReport {
+    pub dry_run: bool,
+    pub steps: Vec<SetupStep>,
+    pub disk: DiskSpaceInfo,
+    /// Steps that ran and succeeded (empty for a dry run)
+    pub completed: Vec<String>,
+    /// The step that failed, which stops the run
+    pub failed: Option<String>,
+    /// Needed steps that didn't run: after a failure, or ones that need the user
+    pub remaining: Vec<String>,
+}
+
+/// Installers in the order they have to run, given what's missing
+fn plan_setup(
+    xcode_needed: bool,
+    rust_needed: bool,
+    claude_cli_needed: bool,
+    claude_auth_needed: bool,
+    has_api_key: bool,
+    breakdown: &DiskSpaceBreakdown,
+) -> Vec<SetupStep> {
+    let step = |name: &str, needed: bool, disk_gb: f64, requires: Option<&str>| SetupStep {
+        step: name.to_string(),
+        needed,
+        disk_gb: if needed { disk_gb } else { 0.0 },
+        requires: requires.filter(|_| needed).map(String::from),
+    };
+    vec![
+        // softwareupdate runs with administrator privileges
+        step("xcode", xcode_needed, breakdown.xcode_gb, Some("password")),
+        step("rust", rust_needed, breakdown.rust_gb, None),
+        step("claude_cli", claude_cli_needed, breakdown.claude_cli_gb, None),
+        // With an API key, sign-in doesn't need anyone at the machine
+        step("claude_auth", claude_auth_needed, 0.0, (!has_api_key).then_some("sign_in")),
+    ]
+}
+
+/// Install everything that's missing, one installer after another
+///
+/// With `dry_run`, only reports the steps that would run, the disk space they
+/// need, and which ones will stop for a password or sign-in. Passing `api_key`
+/// signs Claude in with it (see `set_claude_api_key`) instead of the interactive
+/// sign-in, which otherwise is left for the user and listed in `remaining`.
+/// Each installer streams its usual `install-stream` events.
+#[tauri::command]
+pub async fn setup_all_prerequisites(
+    window: tauri::Window,
+    dry_run: bool,
+    api_key: Option<String>,
+) -> Result<SetupReport, String> {
+    let disk = check_disk_space().await?;
+    let status = check_prerequisites().await;
+    let api_key = api_key.map(|key| key.trim().to_string()).filter(|key| !key.is_empty());
+    let steps = plan_setup(
+        cfg!(target_os = "macos") && status.xcode_cli.status != CheckStatus::Installed,
+        status.rust.status != CheckStatus::Installed,
+        status.claude_cli.status != CheckStatus::Installed,
+        status.claude_auth.status != CheckStatus::Installed,
+        api_key.is_some(),
+        &disk.breakdown,
+    );
+
+    let mut report = SetupReport {
+        dry_run,
+        steps: steps.clone(),
+        disk,
+        completed: Vec::new(),
+        failed: None,
+        remaining: Vec::new(),
+    };
+    if dry_run {
+        report.remaining = steps.iter().filter(|s| s.needed).map(|s| s.step.clone()).collect();
+        return Ok(report);
+    }
+    if !report.disk.sufficient {
+        return Err(tr_with(
+            "setup.insufficient_disk",
+            &[
+                ("available", &format!("{:.1}", report.disk.available_gb)),
+                ("required", &format!("{:.1}", report.disk.required_gb)),
+            ],
+        ));
+    }
+
+    for step in steps.into_iter().filter(|s| s.needed) {
+        if report.failed.is_some() {
+            report.remaining.push(step.step);
+            continue;
+        }
+        let result = match (step.step.as_str(), &api_key) {
+            ("xcode", _) => install_xcode(window.clone()).await,
+            ("rust", _) => install_rust(window.clone()).await,
+            ("claude_cli", _) => install_claude_cli(window.clone()).await,
+            ("claude_auth", Some(key)) => set_claude_api_key(window.clone(), key.clone()).await,
+            _ => {
+                report.remaining.push(step.step);
+                continue;
+            }
+        };
+        match result {
+            Ok(true) => report.completed.push(step.step),
+            Ok(false) | Err(_) => report.failed = Some(step.step),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_settings() {
+        assert!(check_api_key_format("sk-ant-REDACTED").is_ok());
+        assert!(check_api_key_format("sk-ant-api03 abcdefghijklmnop").is_err());
+        assert!(check_api_key_format("sk-proj-abcdefghijklmnopqrst").is_err());
+
+        let updated = with_api_key_helper(r#"{"model": "opus", "apiKeyHelper": "old"}"#, "helper").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&updated).unwrap();
+        assert_eq!(value["model"], "opus");
+        assert_eq!(value["apiKeyHelper"], "helper");
+        assert!(with_api_key_helper("[]", "helper").is_err());
+    }
+
+    #[test]
+    fn test_parse_auth_status() {
+        let json = r#"{"loggedIn": true, "authMethod": "claude.ai", "email": "dev@example.com", "subscriptionType": "max"}"#;
+        let status = parse_auth_status(json).unwrap();
+        assert!(status.logged_in);
+        let account = status.account.unwrap();
+        assert_eq!(account.email.as_deref(), Some("dev@example.com"));
+        assert_eq!(account.plan.as_deref(), Some("max"));
+
+        assert_eq!(
+            parse_auth_status(r#"{"loggedIn": false}"#),
+            Some(ClaudeAuthStatus { logged_in: false, account: None })
+        );
+        let text = parse_auth_status("Logged in as dev@example.com.\nPlan: Pro\n").unwrap();
+        assert_eq!(text.account.unwrap().plan.as_deref(), Some("Pro"));
+        assert!(!parse_auth_status("Not logged in. Run claude /login").unwrap().logged_in);
+        assert_eq!(parse_auth_status("error: unknown option '--json'"), None);
+    }
+
+    #[test]
+    fn test_plan_setup() {
+        let breakdown = DiskSpaceBreakdown {
+            xcode_gb: 4.0,
+            rust_gb: 1.5,
+            claude_cli_gb: 0.1,
+            total_required_gb: 5.6,
+        };
+        let steps = plan_setup(false, true, true, true, false, &breakdown);
+        let names: Vec<&str> = steps.iter().map(|s| s.step.as_str()).collect();
+        assert_eq!(names, vec!["xcode", "rust", "claude_cli", "claude_auth"]);
+        assert_eq!((steps[0].needed, steps[0].disk_gb, steps[0].requires.as_deref()), (false, 0.0, None));
+        assert_eq!(steps[1].disk_gb, 1.5);
+        assert_eq!(steps[3].requires.as_deref(), Some("sign_in"));
+
+        let steps = plan_setup(true, false, false, true, true, &breakdown);
+        assert_eq!(steps[0].requires.as_deref(), Some("password"));
+        assert_eq!(steps[3].requires, None);
+    }
+}
@@ -0,0 +1,257 @@
+//! Factory presets
+//!
+//! Each project can have a `presets/` folder. Presets saved from the preview host
+//! land there as .vstpreset files, and the build step compiles every preset in the
+//! folder into `src/factory_presets.rs` so they ship inside the built plugin.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::projects::get_workspace_path;
+use crate::audio::engine::get_engine_handle;
+use crate::audio::plugin::preset;
+
+/// Folder (relative to the project root) holding factory presets
+pub const PRESETS_DIR: &str = "presets";
+
+/// Generated module (relative to the project root) embedding the presets
+const GENERATED_MODULE: &str = "src/factory_presets.rs";
+
+/// Folder (relative to the project root) holding the decoded state blobs the module includes
+const COMPILED_DIR: &str = "presets/.compiled";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct FactoryPresetInfo {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+}
+
+fn get_project_path(project_name: &str) -> PathBuf {
+    get_workspace_path().join("projects").join(project_name)
+}
+
+/// Turn a preset display name into a safe file stem
+fn preset_file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    if stem.trim().is_empty() {
+        "preset".to_string()
+    } else {
+        stem.trim().to_string()
+    }
+}
+
+/// Turn a file stem into a snake_case identifier for the compiled blob
+fn blob_name(stem: &str, index: usize) -> String {
+    let ident: String = stem
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{:03}_{}.bin", index, ident)
+}
+
+/// List preset files in a project's presets folder, sorted by name
+fn list_preset_files(project_path: &Path) -> Vec<PathBuf> {
+    let dir = project_path.join(PRESETS_DIR);
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && preset::PresetFormat::from_path(p).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Escape a string for use in a Rust string literal
+fn rust_string_literal(s: &str) -> String {
+    format!("{:?}", s)
+}
+
+/// Compile the project's presets folder into `src/factory_presets.rs` and make sure
+/// lib.rs declares the module
+///
+/// The module is rebuilt from the presets currently on disk, so deleted presets drop
+/// out (an emptied or removed folder leaves an empty list). Does nothing for projects
+/// that never had presets. Returns the number of presets embedded. Presets that fail
+/// to decode are skipped with a warning rather than failing the build.
+pub fn generate_factory_presets(project_path: &Path) -> Result<usize, String> {
+    let module_path = project_path.join(GENERATED_MODULE);
+    if !project_path.join(PRESETS_DIR).is_dir() && !module_path.exists() {
+        return Ok(0);
+    }
+
+    let compiled_dir = project_path.join(COMPILED_DIR);
+    if compiled_dir.exists() {
+        fs::remove_dir_all(&compiled_dir)
+            .map_err(|e| format!("Failed to clear compiled presets: {}", e))?;
+    }
+    fs::create_dir_all(&compiled_dir)
+        .map_err(|e| format!("Failed to create compiled presets dir: {}", e))?;
+
+    // VST3 presets saved for another plugin (e.g. before the ID was regenerated) are skipped
+    let class_id = fs::read_to_string(project_path.join("src/lib.rs"))
+        .ok()
+        .and_then(|source| preset::find_vst3_class_id(&source))
+        .map(|id| preset::vst3_class_id_hex(&id));
+
+    let mut entries = Vec::new();
+    for (index, path) in list_preset_files(project_path).iter().enumerate() {
+        let checked = preset::decode_preset_file(path).and_then(|decoded| {
+            if let (Some(id), Some(preset::PresetFormat::Vst3)) = (&class_id, preset::PresetFormat::from_path(path)) {
+                decoded.check_plugin_id(id)?;
+            }
+            Ok(decoded)
+        });
+        let decoded = match checked {
+            Ok(d) => d,
+            Err(e) => {
+                log::warn!("Skipping factory preset {:?}: {}", path, e);
+                continue;
+            }
+        };
+
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "preset".to_string());
+        let name = decoded.name.clone().unwrap_or_else(|| stem.clone());
+        let blob = blob_name(&stem, index);
+
+        fs::write(compiled_dir.join(&blob), &decoded.state)
+            .map_err(|e| format!("Failed to write compiled preset: {}", e))?;
+        entries.push((name, blob));
+    }
+
+    let mut module = String::from(
+        "// @generated by freqlab from the presets/ folder - do not edit by hand.\n\
+         // Load a preset's state with your plugin's state deserialization to\n\
+         // expose these in the host.\n\n\
+         /// A preset compiled into the plugin\n\
+         pub struct FactoryPreset {\n    pub name: &'static str,\n    pub state: &'static [u8],\n}\n\n\
+         /// All factory presets, in file name order\n\
+         #[allow(dead_code)]\n\
+         pub const FACTORY_PRESETS: &[FactoryPreset] = &[\n",
+    );
+    for (name, blob) in &entries {
+        module.push_str(&format!(
+            "    FactoryPreset {{ name: {}, state: include_bytes!(\"../{}/{}\") }},\n",
+            rust_string_literal(name),
+            COMPILED_DIR,
+            blob
+        ));
+    }
+    module.push_str("];\n");
+
+    // Only rewrite when changed so cargo doesn't rebuild needlessly
+    if fs::read_to_string(&module_path).ok().as_deref() != Some(module.as_str()) {
+        fs::write(&module_path, module)
+            .map_err(|e| format!("Failed to write factory_presets.rs: {}", e))?;
+    }
+    ensure_module_declared(&project_path.join("src/lib.rs"))?;
+
+    Ok(entries.len())
+}
+
+/// Add `mod factory_presets;` to lib.rs unless it's already declared
+fn ensure_module_declared(lib_path: &Path) -> Result<(), String> {
+    let source = fs::read_to_string(lib_path).map_err(|e| format!("Failed to read lib.rs: {}", e))?;
+    if declares_module(&source) {
+        return Ok(());
+    }
+    let mut updated = source.trim_end().to_string();
+    updated.push_str("\n\nmod factory_presets;\n");
+    fs::write(lib_path, updated).map_err(|e| format!("Failed to write lib.rs: {}", e))
+}
+
+/// Whether lib.rs has a `mod factory_presets;` line (with any visibility)
+fn declares_module(source: &str) -> bool {
+    source.lines().any(|line| {
+        let line = line.trim();
+        line.strip_prefix("pub ")
+            .or_else(|| line.strip_prefix("pub(crate) "))
+            .unwrap_or(line)
+            .starts_with("mod factory_presets;")
+    })
+}
+
+/// Save the preview host's current plugin state as a factory preset for a project
+#[tauri::command]
+pub async fn save_factory_preset(
+    project_name: String,
+    name: String,
+) -> Result<FactoryPresetInfo, String> {
+    let project_path = get_project_path(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    let state = handle.save_plugin_state()?;
+
+    let source = fs::read_to_string(project_path.join("src/lib.rs"))
+        .map_err(|e| format!("Failed to read project source: {}", e))?;
+    let class_id = preset::find_vst3_class_id(&source)
+        .ok_or_else(|| "Could not find VST3_CLASS_ID in the project source".to_string())?;
+
+    let dir = project_path.join(PRESETS_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create presets folder: {}", e))?;
+
+    let path = dir.join(format!(
+        "{}.{}",
+        preset_file_stem(&name),
+        preset::PresetFormat::Vst3.extension()
+    ));
+    let bytes = preset::encode_vstpreset(&class_id, &state);
+    fs::write(&path, &bytes).map_err(|e| format!("Failed to write preset: {}", e))?;
+
+    Ok(FactoryPresetInfo {
+        name: preset_file_stem(&name),
+        path: path.to_string_lossy().to_string(),
+        size: bytes.len() as u64,
+    })
+}
+
+/// List a project's factory presets
+#[tauri::command]
+pub async fn list_factory_presets(project_name: String) -> Result<Vec<FactoryPresetInfo>, String> {
+    let project_path = get_project_path(&project_name);
+
+    Ok(list_preset_files(&project_path)
+        .into_iter()
+        .map(|path| FactoryPresetInfo {
+            name: path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            size: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect())
+}
+
+/// Delete a factory preset file from a project's presets folder
+#[tauri::command]
+pub async fn delete_factory_preset(project_name: String, file_name: String) -> Result<(), String> {
+    let dir = get_project_path(&project_name).join(PRESETS_DIR);
+    // Only accept a bare file name - never a path
+    if file_name.contains('/') || file_name.contains('\\') || file_name.starts_with('.') {
+        return Err("Invalid preset file name".to_string());
+    }
+
+    let path = dir.join(&file_name);
+    if !path.is_file() {
+        return Err(format!("Preset '{}' not found", file_name));
+    }
+
+    fs::remove_file(&path).map_err(|e| format!("Failed to delete preset: {}", e))
+}
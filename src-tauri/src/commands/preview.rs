@@ -1,10 +1,13 @@
 //! Tauri commands for the audio preview system
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{Emitter, Manager};
 
+use crate::audio::midi::{CcMapping, MIDI_LEARN};
+
 /// Global flag to control the level meter thread
 static LEVEL_METER_RUNNING: AtomicBool = AtomicBool::new(false);
 
@@ -14,6 +17,57 @@ static CRASH_MONITOR_RUNNING: AtomicBool = AtomicBool::new(false);
 /// Tracks whether we've already emitted a plugin-crashed event (to avoid spam)
 static CRASH_EVENT_EMITTED: AtomicBool = AtomicBool::new(false);
 
+/// Global flag to control the output device monitor thread
+static DEVICE_MONITOR_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Start the device monitor thread - detects a lost output device (headphones
+/// unplugged, interface asleep) and moves the engine to the default device
+fn start_device_monitor(app_handle: tauri::AppHandle) {
+    if DEVICE_MONITOR_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        log::info!("Device monitor thread started");
+
+        while DEVICE_MONITOR_RUNNING.load(Ordering::SeqCst) {
+            // Poll once a second - enumerating devices isn't free on every host
+            std::thread::sleep(std::time::Duration::from_secs(1));
+
+            let Some(handle) = get_engine_handle() else {
+                continue;
+            };
+            if !handle.device_lost() && output_device_available(handle.device_name()) {
+                continue;
+            }
+
+            // Nothing to fall back to yet - try again on the next poll
+            if get_default_output_device_name().is_none() {
+                log::warn!("Output device '{}' lost and no default device available", handle.device_name());
+                continue;
+            }
+
+            log::warn!("Output device '{}' lost - switching to default device", handle.device_name());
+            match recover_from_device_loss() {
+                Ok(change) => {
+                    if change.plugin_reloaded {
+                        CRASH_EVENT_EMITTED.store(false, Ordering::SeqCst);
+                        update_midi_player_queue();
+                        update_midi_input_queue();
+                        if let Some(handle) = get_engine_handle() {
+                            let _ = app_handle.emit("plugin-loaded", handle.get_plugin_state());
+                        }
+                    }
+                    let _ = app_handle.emit("audio-device-changed", &change);
+                }
+                Err(e) => log::error!("Failed to recover from lost output device: {}", e),
+            }
+        }
+
+        log::info!("Device monitor thread stopped");
+    });
+}
+
 /// Start the crash monitor thread - runs independently of metering to detect crashes
 /// even when PreviewPanel is closed
 fn start_crash_monitor(app_handle: tauri::AppHandle) {
@@ -25,18 +79,40 @@ fn start_crash_monitor(app_handle: tauri::AppHandle) {
     std::thread::spawn(move || {
         log::info!("Crash monitor thread started");
 
+        // Dangerous-level events are throttled so runaway feedback doesn't flood the UI
+        let mut last_dangerous_levels = get_engine_handle()
+            .map(|h| h.dsp_health().dangerous_levels)
+            .unwrap_or(0);
+        let mut last_dangerous_event: Option<std::time::Instant> = None;
+
         while CRASH_MONITOR_RUNNING.load(Ordering::SeqCst) {
             // Check for plugin crash
             if let Some(handle) = get_engine_handle() {
+                let dangerous_levels = handle.dsp_health().dangerous_levels;
+                // Counters start over when the engine is rebuilt
+                if dangerous_levels < last_dangerous_levels {
+                    last_dangerous_levels = dangerous_levels;
+                }
+                if dangerous_levels > last_dangerous_levels
+                    && last_dangerous_event.map_or(true, |t| t.elapsed() >= std::time::Duration::from_secs(1))
+                {
+                    log::warn!("Safety limiter caught a dangerous output level");
+                    let _ = app_handle.emit(
+                        "dangerous-level-caught",
+                        DangerousLevelEvent {
+                            count: dangerous_levels - last_dangerous_levels,
+                            ceiling_db: handle.get_output_ceiling_db(),
+                        },
+                    );
+                    last_dangerous_levels = dangerous_levels;
+                    last_dangerous_event = Some(std::time::Instant::now());
+                }
+
                 let plugin_crashed = handle.plugin_has_crashed();
 
                 // Emit crash event once (not on every poll)
-                if plugin_crashed && !CRASH_EVENT_EMITTED.swap(true, Ordering::SeqCst) {
-                    log::error!("Crash monitor detected plugin crash - emitting event");
-                    let _ = app_handle.emit(
-                        "plugin-crashed",
-                        "Plugin crashed during audio processing. Reload to try again.",
-                    );
+                if plugin_crashed {
+                    report_plugin_crash(&app_handle);
                 }
             }
 
@@ -48,16 +124,53 @@ fn start_crash_monitor(app_handle: tauri::AppHandle) {
     });
 }
 
+/// Emit plugin-crashed once per crash, naming the crashing function when the
+/// crash can be symbolicated (on its own thread - symbolicating takes a moment)
+fn report_plugin_crash(app_handle: &tauri::AppHandle) {
+    if CRASH_EVENT_EMITTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    log::error!("Plugin crash detected - emitting plugin-crashed event");
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        let message = match super::plugin_crashes::write_plugin_crash_report() {
+            Some(summary) => format!("Plugin crashed: {}. Reload to try again.", summary),
+            None => "Plugin crashed during audio processing. Reload to try again.".to_string(),
+        };
+        let _ = app_handle.emit("plugin-crashed", message);
+    });
+}
+
+/// Payload of the dangerous-level-caught event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DangerousLevelEvent {
+    /// Buffers the safety limiter had to pull down hard since the last event
+    count: u64,
+    /// Ceiling the output was held at (dBFS)
+    ceiling_db: f32,
+}
+
 /// Stop the crash monitor thread
 fn stop_crash_monitor() {
     CRASH_MONITOR_RUNNING.store(false, Ordering::SeqCst);
 }
 
 use crate::audio::{
-    device::{get_default_sample_rate, list_input_devices, list_output_devices, AudioConfig, AudioDeviceInfo},
-    engine::{get_engine_handle, get_engine_sample_rate, init_engine, reinit_engine, shutdown_engine, EngineState, InputSource, PluginPerformance},
+    device::{
+        check_stream_mode, get_buffer_size_range, get_default_output_device_name, get_default_sample_rate,
+        get_output_device, list_input_devices, list_output_devices, output_device_available, AudioConfig,
+        AudioDeviceInfo, BufferSizeRange,
+    },
+    engine::{
+        get_engine_handle, get_engine_sample_rate, init_engine, recover_from_device_loss, reinit_engine,
+        shutdown_engine, EngineState, InputSource, LatencyReport, PluginPerformance,
+    },
+    mixer::{ChannelStrip, MixerSettings, MixerSource},
     plugin::PluginState,
+    samples::{PlayheadInfo, TempoMode},
     signals::{GatePattern, SignalConfig, SignalType},
+    stereo::mid_side_balance,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -121,6 +234,16 @@ pub struct MeteringData {
     pub stereo_positions_input: Vec<[f32; 2]>,
     /// INPUT stereo correlation coefficient (-1.0 to +1.0) - pre-FX
     pub stereo_correlation_input: f32,
+    /// Mid (L+R) RMS level in dB (-60 to 0) - post-FX output
+    pub mid_db: f32,
+    /// Side (L-R) RMS level in dB (-60 to 0) - post-FX output
+    pub side_db: f32,
+    /// Side-to-mid balance (0.0 = mono, 0.5 = equal, 1.0 = side only) - post-FX output
+    pub mid_side_balance: f32,
+    /// INPUT side-to-mid balance - pre-FX
+    pub mid_side_balance_input: f32,
+    /// Raw [left, right] sample pairs for a Lissajous/goniometer display - post-FX output
+    pub lissajous: Vec<[f32; 2]>,
     /// Plugin performance metrics (only present when monitoring is enabled)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugin_performance: Option<PluginPerformance>,
@@ -128,6 +251,9 @@ pub struct MeteringData {
     /// When true, the plugin outputs silence until reloaded
     #[serde(default)]
     pub plugin_crashed: bool,
+    /// Sample playback position (only present when a sample is loaded)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playhead: Option<PlayheadInfo>,
 }
 
 /// Convert linear level to dB
@@ -152,17 +278,41 @@ pub fn init_audio_engine(
     device_name: Option<String>,
     sample_rate: Option<u32>,
     buffer_size: Option<u32>,
+    low_latency: Option<bool>,
+    exclusive: Option<bool>,
+    app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let config = AudioConfig {
         sample_rate: sample_rate.unwrap_or(48000),
         channels: 2,
         buffer_size: buffer_size.unwrap_or(512),
+        low_latency: low_latency.unwrap_or(false),
+        exclusive: exclusive.unwrap_or(false),
     };
+    check_stream_mode(&config)?;
     let result = init_engine(device_name.as_deref(), config);
 
-    // Pre-initialize MIDI player to avoid warm-up lag on first use
     if result.is_ok() {
+        restore_output_ceiling();
+        // Pre-initialize MIDI player to avoid warm-up lag on first use
         init_midi_player();
+        // Fall back to the default device if the output device disappears
+        start_device_monitor(app_handle.clone());
+        // Space in the editor window toggles playback like it does in the app
+        editor::set_host_shortcut_handler(move |action| {
+            if action != editor::HostShortcut::TogglePlayback {
+                return;
+            }
+            if let Some(handle) = get_engine_handle() {
+                let playing = !handle.is_playing();
+                if playing {
+                    handle.play();
+                } else {
+                    handle.stop();
+                }
+                let _ = app_handle.emit("preview-playback-changed", playing);
+            }
+        });
     }
 
     result
@@ -171,6 +321,7 @@ pub fn init_audio_engine(
 /// Shutdown the audio engine
 #[tauri::command]
 pub fn shutdown_audio_engine() {
+    DEVICE_MONITOR_RUNNING.store(false, Ordering::SeqCst);
     shutdown_engine();
 }
 
@@ -195,20 +346,42 @@ pub fn set_audio_config(
     device_name: Option<String>,
     sample_rate: u32,
     buffer_size: Option<u32>,
+    low_latency: Option<bool>,
+    exclusive: Option<bool>,
     _app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
+    let config = AudioConfig {
+        sample_rate,
+        channels: 2,
+        buffer_size: buffer_size.unwrap_or(512),
+        low_latency: low_latency.unwrap_or(false),
+        exclusive: exclusive.unwrap_or(false),
+    };
+    check_stream_mode(&config)?;
+
     // Stop any current playback
     if let Some(handle) = get_engine_handle() {
         handle.stop();
     }
 
     // Reinitialize the engine with new settings
-    let config = AudioConfig {
-        sample_rate,
-        channels: 2,
-        buffer_size: buffer_size.unwrap_or(512),
-    };
-    reinit_engine(device_name.as_deref(), config)
+    reinit_engine(device_name.as_deref(), config)?;
+    restore_output_ceiling();
+    Ok(())
+}
+
+/// Get the buffer and latency figures of the running audio engine
+#[tauri::command]
+pub fn get_audio_latency() -> Result<LatencyReport, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    Ok(handle.latency_report())
+}
+
+/// Get the buffer sizes an output device accepts (None if the host doesn't report them)
+#[tauri::command]
+pub fn get_output_buffer_size_range(device_name: Option<String>) -> Result<Option<BufferSizeRange>, String> {
+    let device = get_output_device(device_name.as_deref())?;
+    Ok(get_buffer_size_range(&device))
 }
 
 /// Get the system's default audio sample rate
@@ -346,6 +519,43 @@ pub fn preview_set_looping(looping: bool) -> Result<(), String> {
     Ok(())
 }
 
+/// Seek sample playback to a position (seconds)
+#[tauri::command]
+pub fn preview_seek(position_secs: f32) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.seek_sample(position_secs);
+    Ok(())
+}
+
+/// Set sample playback tempo (0.25 - 2.0)
+/// "stretch" mode keeps the original pitch, "resample" lets pitch follow the tempo
+#[tauri::command]
+pub fn preview_set_tempo(tempo: f32, mode: Option<TempoMode>) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.set_sample_tempo(tempo, mode.unwrap_or(TempoMode::Stretch));
+    Ok(())
+}
+
+/// Set the sample loop region (seconds) - pass None for both to clear it
+#[tauri::command]
+pub fn preview_set_loop_region(start_secs: Option<f32>, end_secs: Option<f32>) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    match (start_secs, end_secs) {
+        (Some(start), Some(end)) => handle.set_sample_loop_region(start, end),
+        _ => {
+            handle.clear_sample_loop_region();
+            Ok(())
+        }
+    }
+}
+
+/// Get the current sample playhead (None if no sample is loaded)
+#[tauri::command]
+pub fn preview_get_playhead() -> Result<Option<PlayheadInfo>, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    Ok(handle.get_sample_playhead())
+}
+
 /// Get current preview state
 #[tauri::command]
 pub fn preview_get_state() -> Result<PreviewState, String> {
@@ -512,6 +722,9 @@ pub fn start_level_meter(app_handle: tauri::AppHandle) -> Result<(), String> {
                 // Input stereo (pre-FX)
                 let stereo_positions_input_tuples = handle.get_stereo_positions_input();
                 let stereo_correlation_input = handle.get_stereo_correlation_input();
+                // Mid/side levels and goniometer pairs
+                let (mid, side) = handle.get_mid_side_levels();
+                let (mid_input, side_input) = handle.get_mid_side_levels_input();
 
                 // Convert stereo positions from tuples to arrays for JSON serialization
                 let stereo_positions: Vec<[f32; 2]> = stereo_positions_tuples
@@ -530,9 +743,8 @@ pub fn start_level_meter(app_handle: tauri::AppHandle) -> Result<(), String> {
                 let plugin_crashed = handle.plugin_has_crashed();
 
                 // Emit crash event once (not on every metering update)
-                if plugin_crashed && !CRASH_EVENT_EMITTED.swap(true, Ordering::SeqCst) {
-                    log::error!("Plugin crash detected - emitting plugin-crashed event");
-                    let _ = app_handle.emit("plugin-crashed", "Plugin crashed during audio processing. Reload to try again.");
+                if plugin_crashed {
+                    report_plugin_crash(&app_handle);
                 }
 
                 // Send combined metering data with dB values, waveform, and clipping indicators
@@ -561,8 +773,14 @@ pub fn start_level_meter(app_handle: tauri::AppHandle) -> Result<(), String> {
                     stereo_correlation,
                     stereo_positions_input,
                     stereo_correlation_input,
+                    mid_db: level_to_db(mid),
+                    side_db: level_to_db(side),
+                    mid_side_balance: mid_side_balance(mid, side),
+                    mid_side_balance_input: mid_side_balance(mid_input, side_input),
+                    lissajous: handle.get_lissajous_points(),
                     plugin_performance,
                     plugin_crashed,
+                    playhead: handle.get_sample_playhead(),
                 };
                 let _ = app_handle.emit("preview-metering", &metering);
             } else {
@@ -615,7 +833,7 @@ pub fn plugin_load(path: String, app_handle: tauri::AppHandle) -> Result<(), Str
     // Emit loading event
     let _ = app_handle.emit("plugin-loading", &path);
 
-    match handle.load_plugin(std::path::Path::new(&path)) {
+    match load_into_engine(&handle, std::path::Path::new(&path)) {
         Ok(()) => {
             // Reset crash event flag AFTER successful load so we don't get a
             // spurious toast from the old crashed plugin during reload
@@ -629,6 +847,9 @@ pub fn plugin_load(path: String, app_handle: tauri::AppHandle) -> Result<(), Str
             // Update MIDI queues for pattern playback and live input
             update_midi_player_queue();
             update_midi_input_queue();
+            // Not a project build, so no saved MIDI mappings
+            MIDI_LEARN.set_mappings(Vec::new());
+            apply_midi_mappings(&handle, None);
             // Pre-warm MIDI code paths to reduce initial lag
             prewarm_midi_paths(&handle);
             Ok(())
@@ -759,7 +980,7 @@ pub fn plugin_load_for_project(
     // Emit loading event
     let _ = app_handle.emit("plugin-loading", &plugin_path);
 
-    match handle.load_plugin(std::path::Path::new(&plugin_path)) {
+    match load_into_engine(&handle, std::path::Path::new(&plugin_path)) {
         Ok(()) => {
             // Reset crash event flag AFTER successful load so we don't get a
             // spurious toast from the old crashed plugin during reload
@@ -773,8 +994,18 @@ pub fn plugin_load_for_project(
             // Update MIDI queues for pattern playback and live input
             update_midi_player_queue();
             update_midi_input_queue();
+            let project_path = super::activity::project_dir(&project_name);
+            apply_midi_mappings(&handle, Some(&project_path.to_string_lossy()));
             // Pre-warm MIDI code paths to reduce initial lag
             prewarm_midi_paths(&handle);
+
+            super::activity::record_activity(
+                &project_path,
+                super::activity::ActivityKind::Preview,
+                format!("Previewed v{}", version.max(1)),
+                true,
+                Some(serde_json::json!({ "version": version.max(1) })),
+            );
             Ok(())
         }
         Err(e) => {
@@ -786,24 +1017,86 @@ pub fn plugin_load_for_project(
 
 /// Open the plugin's editor window
 ///
-/// Uses stored position if available, otherwise centers the window.
+/// Restores the window state saved for the loaded plugin (in the given project,
+/// or the project whose build is loaded), otherwise centers the window.
 #[tauri::command]
-pub fn plugin_open_editor() -> Result<(), String> {
+pub fn plugin_open_editor(
+    project_path: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
     log::info!("plugin_open_editor command called");
     let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    restore_editor_state(&handle, project_path.as_deref());
     let result = handle.open_plugin_editor();
     log::info!("plugin_open_editor: result = {:?}", result.is_ok());
+    if result.is_ok() {
+        // Opening may have picked up geometry from a window the user closed with X
+        save_editor_state(&handle, project_path.as_deref());
+    }
+    if result.is_ok() {
+        start_window_monitor(app_handle);
+    }
     result
 }
 
-/// Close the plugin's editor window
+/// Close the plugin's editor window, saving its state for the loaded plugin
 #[tauri::command]
-pub fn plugin_close_editor() -> Result<(), String> {
+pub fn plugin_close_editor(project_path: Option<String>) -> Result<(), String> {
     let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
     handle.close_plugin_editor();
+    save_editor_state(&handle, project_path.as_deref());
     Ok(())
 }
 
+/// Set whether the editor window stays above other windows
+/// Applies to the open window immediately and is saved with the plugin's window state.
+#[tauri::command]
+pub fn plugin_set_editor_always_on_top(
+    always_on_top: bool,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    restore_editor_state(&handle, project_path.as_deref());
+    handle.set_editor_always_on_top(always_on_top);
+    save_editor_state(&handle, project_path.as_deref());
+    Ok(())
+}
+
+/// Set whether the editor window sends every key to the plugin
+/// Off by default, so host shortcuts (space = play/stop, Cmd+W = close) work
+/// while the editor has focus. Saved with the plugin's window state.
+#[tauri::command]
+pub fn plugin_set_editor_key_routing(
+    plugin_gets_all_keys: bool,
+    project_path: Option<String>,
+) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    restore_editor_state(&handle, project_path.as_deref());
+    handle.set_editor_plugin_gets_all_keys(plugin_gets_all_keys);
+    save_editor_state(&handle, project_path.as_deref());
+    Ok(())
+}
+
+/// Show the open plugin editor at 75%, 100%, or 150% to check the GUI at other
+/// pixel densities without changing display settings
+/// Returns the GUI's new size.
+#[tauri::command]
+pub fn plugin_set_editor_scale(scale: f64) -> Result<(u32, u32), String> {
+    if !editor::EDITOR_SCALES.iter().any(|&s| (s - scale).abs() < 1e-6) {
+        return Err(format!("Unsupported editor scale {} (use one of {:?})", scale, editor::EDITOR_SCALES));
+    }
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.set_plugin_editor_scale(scale)
+}
+
+/// Get the editor window state (the loaded plugin's saved state)
+#[tauri::command]
+pub fn plugin_get_editor_window_state(project_path: Option<String>) -> Result<EditorWindowState, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    restore_editor_state(&handle, project_path.as_deref());
+    Ok(handle.get_editor_window_state())
+}
+
 /// Check if the plugin editor is open
 #[tauri::command]
 pub fn plugin_is_editor_open() -> Result<bool, String> {
@@ -811,6 +1104,364 @@ pub fn plugin_is_editor_open() -> Result<bool, String> {
     Ok(handle.is_plugin_editor_open())
 }
 
+// =============================================================================
+// Editor Window State Persistence
+// =============================================================================
+
+use crate::audio::plugin::editor::EditorWindowState;
+
+/// Project and plugin ID whose saved editor state is currently loaded into the engine
+/// The engine's copy stays authoritative while the same plugin is open, so
+/// hot reloads keep the live geometry instead of re-reading the file
+static EDITOR_STATE_KEY: Lazy<Mutex<Option<EditorStateKey>>> = Lazy::new(|| Mutex::new(None));
+
+/// (project path, plugin ID)
+type EditorStateKey = (String, String);
+
+/// Per-project UI state (.vstworkshop/ui-state.json)
+#[derive(Serialize, Deserialize, Default)]
+struct ProjectUiState {
+    /// Editor window state by plugin ID, so a plugin whose ID changed (or a
+    /// different plugin loaded for the project) doesn't inherit another's window
+    #[serde(rename = "editorWindows", skip_serializing_if = "HashMap::is_empty", default)]
+    editor_windows: HashMap<String, EditorWindowState>,
+    /// Preview the plugin in a helper process (for plugins that crash the app)
+    #[serde(rename = "isolatePlugin", default)]
+    isolate_plugin: bool,
+}
+
+fn get_ui_state_path(project_path: &str) -> PathBuf {
+    std::path::Path::new(project_path).join(".vstworkshop").join("ui-state.json")
+}
+
+fn load_ui_state(project_path: &str) -> ProjectUiState {
+    std::fs::read_to_string(get_ui_state_path(project_path))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_ui_state(project_path: &str, ui_state: &ProjectUiState) -> Result<(), String> {
+    let path = get_ui_state_path(project_path);
+    path.parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(ui_state).map_err(|e| e.to_string()))
+        .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()))
+}
+
+/// Where the loaded plugin's editor state is saved: the given project, or the
+/// project whose build is loaded (None for plugins from elsewhere)
+fn editor_state_key(
+    handle: &crate::audio::engine::AudioEngineHandle,
+    project_path: Option<&str>,
+) -> Option<EditorStateKey> {
+    let plugin_id = handle.plugin_id()?;
+    let project_path = match project_path {
+        Some(project_path) => project_path.to_string(),
+        None => {
+            let PluginState::Active { path, .. } = handle.get_plugin_state() else {
+                return None;
+            };
+            let project = super::plugin_crashes::project_for_bundle(
+                std::path::Path::new(&path),
+                &super::projects::get_output_path(),
+            )?;
+            super::activity::project_dir(&project).to_string_lossy().to_string()
+        }
+    };
+    Some((project_path, plugin_id))
+}
+
+/// Load the plugin's saved editor state into the engine when a different plugin is loaded
+fn restore_editor_state(
+    handle: &crate::audio::engine::AudioEngineHandle,
+    project_path: Option<&str>,
+) {
+    let Some(key) = editor_state_key(handle, project_path) else {
+        return;
+    };
+    let mut current = EDITOR_STATE_KEY.lock();
+    if current.as_ref() == Some(&key) {
+        return;
+    }
+    let (project_path, plugin_id) = &key;
+    let state = load_ui_state(project_path)
+        .editor_windows
+        .remove(plugin_id)
+        .unwrap_or_default();
+    log::info!("Restoring editor window state for {} in {}: {:?}", plugin_id, project_path, state);
+    handle.set_editor_window_state(state);
+    *current = Some(key);
+}
+
+/// Write the engine's editor state to the project's ui-state.json under the plugin's ID
+/// Failures are logged - losing window placement isn't worth failing the command
+fn save_editor_state(
+    handle: &crate::audio::engine::AudioEngineHandle,
+    project_path: Option<&str>,
+) {
+    let Some(key) = editor_state_key(handle, project_path) else {
+        return;
+    };
+    let (project_path, plugin_id) = &key;
+    let mut ui_state = load_ui_state(project_path);
+    ui_state.editor_windows.insert(plugin_id.clone(), handle.get_editor_window_state());
+
+    match save_ui_state(project_path, &ui_state) {
+        Ok(()) => *EDITOR_STATE_KEY.lock() = Some(key),
+        Err(e) => log::warn!("Failed to save editor window state: {}", e),
+    }
+}
+
+// =============================================================================
+// Process Isolation
+// =============================================================================
+
+/// Load a plugin into the engine, out of process if its project asks for it
+fn load_into_engine(handle: &crate::audio::engine::AudioEngineHandle, path: &std::path::Path) -> Result<(), String> {
+    let isolated = super::plugin_crashes::project_for_bundle(path, &super::projects::get_output_path())
+        .map(|project| load_ui_state(&super::activity::project_dir(&project).to_string_lossy()).isolate_plugin)
+        .unwrap_or(false);
+    if isolated {
+        handle.load_plugin_isolated(path)
+    } else {
+        handle.load_plugin(path)
+    }
+}
+
+/// Whether a project's plugin is previewed in a separate process
+#[tauri::command]
+pub fn plugin_get_isolation(project_path: String) -> Result<bool, String> {
+    Ok(load_ui_state(&project_path).isolate_plugin)
+}
+
+/// Preview a project's plugin in a separate process (takes effect on the next load)
+/// Isolated plugins can't crash the app, but have no editor, parameters, or MIDI
+#[tauri::command]
+pub fn plugin_set_isolation(project_path: String, enabled: bool) -> Result<(), String> {
+    let mut ui_state = load_ui_state(&project_path);
+    ui_state.isolate_plugin = enabled;
+    save_ui_state(&project_path, &ui_state).map_err(|e| format!("Failed to save isolation setting: {}", e))
+}
+
+// =============================================================================
+// Preview Windows (plugin editor + analyzer side by side)
+// =============================================================================
+
+use crate::audio::plugin::editor::{self, AnalyzerFrame, PreviewWindowKind};
+use crate::audio::plugin::ui_heartbeat::{self, HeartbeatMonitor, HeartbeatStatus};
+
+/// Whether the window monitor thread has been started (it lives for the rest of the app)
+static WINDOW_MONITOR_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Set when a preview window opens; the idle monitor waits on the condvar for it.
+/// A flag rather than a bare notify, so an open that lands while the monitor is
+/// winding down still wakes it.
+static WINDOW_MONITOR_WAKE: Lazy<(std::sync::Mutex<bool>, std::sync::Condvar)> =
+    Lazy::new(|| (std::sync::Mutex::new(false), std::sync::Condvar::new()));
+
+/// Last analyzer window position (reused when it's reopened)
+static ANALYZER_POSITION: Lazy<Mutex<Option<(f64, f64)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Poll interval for analyzer updates (~30fps)
+const WINDOW_MONITOR_INTERVAL_MS: u64 = 33;
+
+/// Check for manually closed windows every N polls (~250ms)
+const WINDOW_CLOSE_CHECK_TICKS: u32 = 8;
+
+/// Times a stalled webview editor is recreated before giving up, per open
+const MAX_EDITOR_RECYCLES: u32 = 3;
+
+/// Emitted when a plugin editor that stopped rendering was recreated
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct EditorRecycledEvent {
+    attempt: u32,
+    /// Recreating didn't help - the editor is left as is
+    gave_up: bool,
+    error: Option<String>,
+}
+
+/// Wake the window monitor - feeds the analyzer window and emits
+/// `preview-window-closed` when the user closes a window with X.
+/// Call after opening a window; the monitor idles once no preview windows are open.
+fn start_window_monitor(app_handle: tauri::AppHandle) {
+    let (wake, condvar) = &*WINDOW_MONITOR_WAKE;
+    *wake.lock().unwrap_or_else(|e| e.into_inner()) = true;
+    condvar.notify_one();
+
+    if WINDOW_MONITOR_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(move || {
+        log::info!("Window monitor thread started");
+        loop {
+            {
+                let guard = wake.lock().unwrap_or_else(|e| e.into_inner());
+                let mut woken = condvar
+                    .wait_while(guard, |woken| !*woken)
+                    .unwrap_or_else(|e| e.into_inner());
+                *woken = false;
+            }
+            monitor_windows(&app_handle);
+            log::info!("Window monitor idle");
+        }
+    });
+}
+
+/// Poll the open preview windows until none are left
+fn monitor_windows(app_handle: &tauri::AppHandle) {
+    let mut tick: u32 = 0;
+    // Heartbeat of the open plugin editor, keyed by its window
+    let mut heartbeat: Option<(usize, HeartbeatMonitor)> = None;
+    let mut recycles: u32 = 0;
+
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(WINDOW_MONITOR_INTERVAL_MS));
+        tick = tick.wrapping_add(1);
+
+        if editor::registered_window(PreviewWindowKind::Analyzer).is_some() {
+            if let Some(handle) = get_engine_handle() {
+                let (left, right) = handle.get_output_levels();
+                let spectrum = handle.get_spectrum_data();
+                editor::update_analyzer_window(&AnalyzerFrame {
+                    left_db: level_to_db(left),
+                    right_db: level_to_db(right),
+                    spectrum: &spectrum[..],
+                });
+            }
+        }
+
+        if tick % WINDOW_CLOSE_CHECK_TICKS == 0 {
+            for kind in editor::manually_closed_windows() {
+                log::info!("Window monitor: {:?} window closed by user", kind);
+                teardown_window(kind);
+                let _ = app_handle.emit("preview-window-closed", kind);
+            }
+            check_editor_heartbeat(app_handle, &mut heartbeat, &mut recycles);
+            if editor::registered_windows().is_empty() {
+                return;
+            }
+        }
+    }
+}
+
+/// Recreate the plugin editor window if its webview stopped rendering
+fn check_editor_heartbeat(
+    app_handle: &tauri::AppHandle,
+    heartbeat: &mut Option<(usize, HeartbeatMonitor)>,
+    recycles: &mut u32,
+) {
+    let Some(window) = editor::registered_window(PreviewWindowKind::PluginEditor) else {
+        if heartbeat.take().is_some() {
+            ui_heartbeat::stop();
+            *recycles = 0;
+        }
+        return;
+    };
+    match heartbeat {
+        Some((watched, _)) if *watched == window as usize => {}
+        // A new editor window (opened, reopened, or hot reloaded)
+        _ => *heartbeat = Some((window as usize, HeartbeatMonitor::start())),
+    }
+    let Some((_, monitor)) = heartbeat.as_mut() else { return };
+    if *recycles > MAX_EDITOR_RECYCLES
+        || monitor.check(editor::is_window_on_screen(window)) != HeartbeatStatus::Stalled
+    {
+        return;
+    }
+
+    *recycles += 1;
+    if *recycles > MAX_EDITOR_RECYCLES {
+        log::warn!("Plugin editor still isn't rendering after {} recreates", MAX_EDITOR_RECYCLES);
+        let _ = app_handle.emit(
+            "plugin-editor-recycled",
+            EditorRecycledEvent {
+                attempt: *recycles,
+                gave_up: true,
+                error: None,
+            },
+        );
+        return;
+    }
+
+    log::warn!("Plugin editor stopped rendering - recreating its window (attempt {})", recycles);
+    teardown_window(PreviewWindowKind::PluginEditor);
+    let result = get_engine_handle()
+        .ok_or_else(|| "Audio engine not initialized".to_string())
+        .and_then(|handle| handle.open_plugin_editor());
+    if let Err(ref e) = result {
+        log::error!("Failed to recreate the plugin editor: {}", e);
+    }
+    let _ = app_handle.emit(
+        "plugin-editor-recycled",
+        EditorRecycledEvent {
+            attempt: *recycles,
+            gave_up: false,
+            error: result.err(),
+        },
+    );
+}
+
+/// Close a preview window, saving its placement for the next open
+fn teardown_window(kind: PreviewWindowKind) {
+    match kind {
+        PreviewWindowKind::PluginEditor => {
+            if let Some(handle) = get_engine_handle() {
+                handle.close_plugin_editor();
+                let project_path = EDITOR_STATE_KEY.lock().clone().map(|(path, _)| path);
+                save_editor_state(&handle, project_path.as_deref());
+            }
+        }
+        PreviewWindowKind::Analyzer => {
+            if let Some(position) = editor::get_analyzer_position() {
+                *ANALYZER_POSITION.lock() = Some(position);
+            }
+            editor::close_analyzer_window();
+        }
+    }
+}
+
+/// Open a preview window (the plugin editor or the analyzer)
+/// Windows are independent - opening one leaves the others as they are
+#[tauri::command]
+pub fn preview_open_window(
+    kind: PreviewWindowKind,
+    project_path: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    match kind {
+        PreviewWindowKind::PluginEditor => plugin_open_editor(project_path, app_handle),
+        PreviewWindowKind::Analyzer => {
+            let position = *ANALYZER_POSITION.lock();
+            editor::open_analyzer_window(position)?;
+            start_window_monitor(app_handle);
+            Ok(())
+        }
+    }
+}
+
+/// Close a preview window
+#[tauri::command]
+pub fn preview_close_window(kind: PreviewWindowKind) -> Result<(), String> {
+    teardown_window(kind);
+    Ok(())
+}
+
+/// Bring a preview window to the front
+#[tauri::command]
+pub fn preview_focus_window(kind: PreviewWindowKind) -> Result<(), String> {
+    editor::focus_window(kind)
+}
+
+/// List the preview windows that are currently open
+#[tauri::command]
+pub fn preview_list_windows() -> Vec<PreviewWindowKind> {
+    editor::registered_windows()
+}
+
 /// Enable or disable plugin performance monitoring
 /// When enabled, the engine measures plugin.process() call duration
 /// When disabled, no timing overhead is incurred (zero overhead design)
@@ -882,7 +1533,7 @@ pub fn plugin_reload(
     std::thread::sleep(std::time::Duration::from_millis(100));
 
     // Reload
-    match handle.load_plugin(std::path::Path::new(&plugin_path)) {
+    match load_into_engine(&handle, std::path::Path::new(&plugin_path)) {
         Ok(()) => {
             // Reset crash event flag AFTER successful load so we can detect crashes in the reloaded plugin
             CRASH_EVENT_EMITTED.store(false, Ordering::SeqCst);
@@ -895,6 +1546,9 @@ pub fn plugin_reload(
             // Update MIDI queues for pattern playback and live input
             update_midi_player_queue();
             update_midi_input_queue();
+            // Parameters may have changed; the mappings carry over (or come from the project)
+            let project_path = project_name.as_ref().map(|name| super::activity::project_dir(name));
+            apply_midi_mappings(&handle, project_path.as_ref().map(|p| p.to_string_lossy()).as_deref());
             // Pre-warm MIDI code paths to reduce initial lag
             prewarm_midi_paths(&handle);
             log::info!("Plugin hot reload successful");
@@ -907,6 +1561,203 @@ pub fn plugin_reload(
     }
 }
 
+// =============================================================================
+// Artifact Watcher Commands
+// =============================================================================
+
+use crate::audio::plugin::file_watcher::{PluginWatcher, WatcherConfig, WatcherStatus};
+
+/// Watcher that hot reloads the preview plugin when its built bundles change
+static ARTIFACT_WATCHER: Lazy<Mutex<PluginWatcher>> = Lazy::new(|| Mutex::new(PluginWatcher::new()));
+
+/// Watch a project version's built bundles (.clap and .vst3) and hot reload on change
+/// Emits "plugin-watcher-status" when the watcher state changes and
+/// "plugin-artifacts-changed" with the changed bundles before reloading
+#[tauri::command]
+pub fn plugin_watch_artifacts(
+    project_name: String,
+    version: u32,
+    ignore_patterns: Option<Vec<String>>,
+    debounce_ms: Option<u64>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let dir = crate::commands::projects::get_output_path()
+        .join(&project_name)
+        .join(format!("v{}", version.max(1)));
+    let paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().map(|e| e == "clap" || e == "vst3").unwrap_or(false))
+                .collect()
+        })
+        .unwrap_or_default();
+    if paths.is_empty() {
+        return Err(format!("No built plugin found for {} v{}", project_name, version));
+    }
+
+    let mut config = WatcherConfig::default();
+    if let Some(patterns) = ignore_patterns {
+        config.ignore_patterns.extend(patterns);
+    }
+    if let Some(ms) = debounce_ms {
+        config.debounce_ms = ms.clamp(50, 10_000);
+    }
+
+    let reload_app = app_handle.clone();
+    ARTIFACT_WATCHER.lock().watch(
+        paths,
+        config,
+        Box::new(move |changed| {
+            let _ = reload_app.emit("plugin-artifacts-changed", &changed);
+
+            // Only the CLAP bundle is hosted, and only reload a plugin that's still loaded
+            let clap_changed = changed
+                .iter()
+                .any(|p| p.extension().map(|e| e == "clap").unwrap_or(false));
+            let plugin_active = get_engine_handle()
+                .map(|h| matches!(h.get_plugin_state(), PluginState::Active { .. }))
+                .unwrap_or(false);
+            if clap_changed && plugin_active {
+                if let Err(e) = plugin_reload(Some(project_name.clone()), Some(version), reload_app.clone()) {
+                    log::warn!("Automatic plugin reload failed: {}", e);
+                }
+            }
+        }),
+        Box::new(move |status| {
+            let _ = app_handle.emit("plugin-watcher-status", &status);
+        }),
+    )
+}
+
+/// Stop watching plugin artifacts
+#[tauri::command]
+pub fn plugin_unwatch_artifacts() {
+    ARTIFACT_WATCHER.lock().unwatch();
+}
+
+/// Get the artifact watcher's current status
+#[tauri::command]
+pub fn plugin_watcher_status() -> WatcherStatus {
+    ARTIFACT_WATCHER.lock().status()
+}
+
+/// Whether the artifact watcher is running (it handles reloads after builds itself)
+pub(crate) fn is_artifact_watch_active() -> bool {
+    ARTIFACT_WATCHER.lock().is_watching()
+}
+
+// =============================================================================
+// Preset Commands
+// =============================================================================
+
+/// Result of importing a preset file into the loaded plugin
+#[derive(Debug, Serialize)]
+pub struct PresetImportResult {
+    pub name: Option<String>,
+    pub plugin_id: String,
+    pub bytes: usize,
+}
+
+/// Export the loaded plugin's current state as a .vstpreset or .aupreset file
+/// The format is chosen from the destination extension. For VST3 presets the class ID
+/// is read from the project's src/lib.rs so the preset matches the built plugin.
+#[tauri::command]
+pub fn plugin_export_preset(
+    path: String,
+    name: String,
+    project_name: Option<String>,
+) -> Result<String, String> {
+    use crate::audio::plugin::preset::{self, AuComponent, PresetFormat};
+
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    let dest = PathBuf::from(&path);
+    let format = PresetFormat::from_path(&dest)
+        .ok_or_else(|| "Unsupported preset format (expected .vstpreset or .aupreset)".to_string())?;
+
+    let state = handle.save_plugin_state()?;
+
+    let bytes = match format {
+        PresetFormat::Vst3 => {
+            let project = project_name
+                .ok_or_else(|| "A project is required to export VST3 presets".to_string())?;
+            let lib_rs = crate::commands::projects::get_workspace_path()
+                .join("projects")
+                .join(&project)
+                .join("src/lib.rs");
+            let source = std::fs::read_to_string(&lib_rs)
+                .map_err(|e| format!("Failed to read project source: {}", e))?;
+            let class_id = preset::find_vst3_class_id(&source)
+                .ok_or_else(|| "Could not find VST3_CLASS_ID in the project source".to_string())?;
+            preset::encode_vstpreset(&class_id, &state)
+        }
+        PresetFormat::Au => {
+            let (plugin_name, vendor) = handle
+                .plugin_descriptor()
+                .ok_or_else(|| "No plugin loaded".to_string())?;
+            let component = AuComponent::for_plugin(handle.is_instrument(), &plugin_name, &vendor);
+            preset::encode_aupreset(&component, &name, &state).into_bytes()
+        }
+    };
+
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create preset folder: {}", e))?;
+    }
+    std::fs::write(&dest, bytes).map_err(|e| format!("Failed to write preset: {}", e))?;
+
+    log::info!("Exported {} preset to {}", format.extension(), path);
+    Ok(path)
+}
+
+/// Load a .vstpreset or .aupreset file into the loaded plugin
+/// Presets saved by a different plugin are rejected. VST3 presets are matched against
+/// the class ID in the project's src/lib.rs, AU presets against the loaded plugin.
+#[tauri::command]
+pub fn plugin_import_preset(
+    path: String,
+    project_name: Option<String>,
+) -> Result<PresetImportResult, String> {
+    use crate::audio::plugin::preset::{self, AuComponent, PresetFormat};
+
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    let source_path = std::path::Path::new(&path);
+    let decoded = preset::decode_preset_file(source_path)?;
+
+    let expected_id = match PresetFormat::from_path(source_path) {
+        Some(PresetFormat::Vst3) => {
+            let project = project_name
+                .ok_or_else(|| "A project is required to import VST3 presets".to_string())?;
+            let lib_rs = crate::commands::projects::get_workspace_path()
+                .join("projects")
+                .join(&project)
+                .join("src/lib.rs");
+            let source = std::fs::read_to_string(&lib_rs)
+                .map_err(|e| format!("Failed to read project source: {}", e))?;
+            let class_id = preset::find_vst3_class_id(&source)
+                .ok_or_else(|| "Could not find VST3_CLASS_ID in the project source".to_string())?;
+            preset::vst3_class_id_hex(&class_id)
+        }
+        _ => {
+            let (plugin_name, vendor) = handle
+                .plugin_descriptor()
+                .ok_or_else(|| "No plugin loaded".to_string())?;
+            AuComponent::for_plugin(handle.is_instrument(), &plugin_name, &vendor).identifier()
+        }
+    };
+    decoded.check_plugin_id(&expected_id)?;
+
+    handle.load_plugin_state(&decoded.state)?;
+
+    log::info!("Imported preset {} ({} bytes)", path, decoded.state.len());
+    Ok(PresetImportResult {
+        name: decoded.name,
+        plugin_id: decoded.plugin_id,
+        bytes: decoded.state.len(),
+    })
+}
+
 // =============================================================================
 // Live Input Commands
 // =============================================================================
@@ -963,6 +1814,61 @@ pub fn preview_get_master_volume() -> Result<f32, String> {
     Ok(handle.get_master_volume())
 }
 
+/// Set the safety limiter output ceiling (dBFS, -24 to 0) and keep it for later sessions
+#[tauri::command]
+pub fn preview_set_output_ceiling(ceiling_db: f32, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.set_output_ceiling_db(ceiling_db);
+    // Save the value the engine clamped it to
+    super::settings::save_output_ceiling(&app_handle, handle.get_output_ceiling_db())?;
+    Ok(())
+}
+
+/// Put the saved safety limiter ceiling on a newly built engine
+fn restore_output_ceiling() {
+    if let Some(handle) = get_engine_handle() {
+        handle.set_output_ceiling_db(super::settings::current_settings().audio.output_ceiling_db);
+    }
+}
+
+/// Get the safety limiter output ceiling (dBFS)
+#[tauri::command]
+pub fn preview_get_output_ceiling() -> Result<f32, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    Ok(handle.get_output_ceiling_db())
+}
+
+/// Get the input mixer configuration
+#[tauri::command]
+pub fn preview_get_mixer() -> Result<MixerSettings, String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    Ok(handle.get_mixer())
+}
+
+/// Enable or disable mixing multiple input sources together
+#[tauri::command]
+pub fn preview_set_mixer_enabled(enabled: bool) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.set_mixer_enabled(enabled);
+    Ok(())
+}
+
+/// Set gain (dB), pan, mute, and enabled state for one mixer source
+#[tauri::command]
+pub fn preview_set_mixer_channel(source: MixerSource, strip: ChannelStrip) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.set_mixer_channel(source, strip);
+    Ok(())
+}
+
+/// Set the mixer master gain (dB, applied before the plugin)
+#[tauri::command]
+pub fn preview_set_mixer_master_gain(gain_db: f32) -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.set_mixer_master_gain(gain_db);
+    Ok(())
+}
+
 // =============================================================================
 // MIDI Commands (for instrument plugins)
 // =============================================================================
@@ -1411,3 +2317,158 @@ pub fn midi_device_get_connected() -> Option<String> {
 pub fn midi_device_get_last_note() -> Option<u8> {
     MIDI_INPUT_MANAGER.get_last_note()
 }
+
+// =============================================================================
+// MIDI Learn
+// =============================================================================
+
+/// How long start_midi_learn waits for a controller to move
+const MIDI_LEARN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+fn get_midi_mappings_path(project_path: &str) -> PathBuf {
+    std::path::Path::new(project_path).join(".vstworkshop").join("midi-mappings.json")
+}
+
+fn load_midi_mappings(project_path: &str) -> Vec<CcMapping> {
+    std::fs::read_to_string(get_midi_mappings_path(project_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_midi_mappings(project_path: &str, mappings: &[CcMapping]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(mappings).map_err(|e| format!("Failed to serialize MIDI mappings: {}", e))?;
+    std::fs::write(get_midi_mappings_path(project_path), json).map_err(|e| format!("Failed to save MIDI mappings: {}", e))
+}
+
+/// Point MIDI learn at a freshly loaded plugin: its parameter ranges, and the
+/// project's saved mappings (None keeps the current mappings, for reloads)
+fn apply_midi_mappings(handle: &crate::audio::engine::AudioEngineHandle, project_path: Option<&str>) {
+    MIDI_LEARN.set_params(&handle.list_plugin_params());
+    if let Some(project_path) = project_path {
+        MIDI_LEARN.set_mappings(load_midi_mappings(project_path));
+    }
+}
+
+/// Bind the next CC from the connected MIDI device to a parameter and save it
+/// with the project. Returns None if no controller moved in time (or on cancel).
+#[tauri::command]
+pub async fn start_midi_learn(project_path: String, param_id: u32) -> Result<Option<CcMapping>, String> {
+    if !MIDI_INPUT_MANAGER.is_connected() {
+        return Err("Connect a MIDI device first".to_string());
+    }
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    let param = handle
+        .list_plugin_params()
+        .into_iter()
+        .find(|p| p.id == param_id)
+        .ok_or_else(|| format!("Parameter {} not found", param_id))?;
+
+    let receiver = MIDI_LEARN.start(param.id, param.name);
+    match tokio::time::timeout(MIDI_LEARN_TIMEOUT, receiver).await {
+        Ok(Ok(mapping)) => {
+            save_midi_mappings(&project_path, &MIDI_LEARN.mappings())?;
+            Ok(Some(mapping))
+        }
+        // Cancelled (or replaced by another learn)
+        Ok(Err(_)) => Ok(None),
+        Err(_) => {
+            MIDI_LEARN.cancel();
+            Ok(None)
+        }
+    }
+}
+
+/// Stop waiting for a controller
+#[tauri::command]
+pub fn cancel_midi_learn() {
+    MIDI_LEARN.cancel();
+}
+
+/// A project's saved MIDI mappings
+#[tauri::command]
+pub fn get_midi_mappings(project_path: String) -> Vec<CcMapping> {
+    load_midi_mappings(&project_path)
+}
+
+/// Remove a parameter's MIDI mapping
+#[tauri::command]
+pub fn remove_midi_mapping(project_path: String, param_id: u32) -> Result<(), String> {
+    let mut mappings = load_midi_mappings(&project_path);
+    mappings.retain(|m| m.param_id != param_id);
+    save_midi_mappings(&project_path, &mappings)?;
+    MIDI_LEARN.set_mappings(mappings);
+    Ok(())
+}
+
+// =============================================================================
+// Spectrogram Export
+// =============================================================================
+
+use crate::audio::spectrum::{render_spectrogram, HISTORY_SECONDS};
+
+/// Render the last `seconds` of spectrum history into a PNG spectrogram
+/// With `include_input`, the pre-FX input is drawn above the plugin output for a
+/// before/after comparison. Rendering runs as an analysis job. Returns the path
+/// of the written file.
+#[tauri::command]
+pub async fn export_spectrogram(
+    output_path: String,
+    seconds: Option<f32>,
+    include_input: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let seconds = seconds.unwrap_or(10.0);
+    if !(seconds > 0.0 && seconds <= HISTORY_SECONDS as f32) {
+        return Err(format!("Seconds must be between 0 and {}", HISTORY_SECONDS));
+    }
+
+    let (input, output) = get_engine_handle()
+        .ok_or_else(|| "Audio engine not initialized".to_string())?
+        .get_spectrum_history(seconds);
+    if output.is_empty() {
+        return Err("No spectrum history recorded yet - play some audio first".to_string());
+    }
+
+    super::analysis_jobs::run_analysis_job("export_spectrogram", Some(app_handle), move |job| {
+        let (width, height, pixels) = if include_input.unwrap_or(false) {
+            render_spectrogram(job.pool(), &[&input, &output])
+        } else {
+            render_spectrogram(job.pool(), &[&output])
+        };
+        job.progress(1, 2);
+        job.check_cancelled()?;
+
+        let mut path = PathBuf::from(output_path);
+        if path.extension().map_or(true, |ext| ext != "png") {
+            path.set_extension("png");
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create output folder: {}", e))?;
+        }
+
+        let file = std::fs::File::create(&path)
+            .map_err(|e| format!("Failed to create spectrogram file: {}", e))?;
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(|e| format!("Failed to write spectrogram: {}", e))?;
+        job.progress(2, 2);
+
+        log::info!("Exported {:.1}s spectrogram to {:?}", seconds, path);
+        Ok(path.to_string_lossy().to_string())
+    })
+    .await
+}
+
+/// Forget recorded spectrum history so the next export only covers new audio
+#[tauri::command]
+pub fn clear_spectrum_history() -> Result<(), String> {
+    let handle = get_engine_handle().ok_or_else(|| "Audio engine not initialized".to_string())?;
+    handle.clear_spectrum_history();
+    Ok(())
+}
@@ -1,6 +1,10 @@
+use fs2::FileExt;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use tauri::Emitter;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ProjectMeta {
@@ -14,6 +18,50 @@ pub struct ProjectMeta {
     pub created_at: String,
     pub updated_at: String,
     pub path: String,
+    /// Vendor details and plugin IDs baked into the source (see identity.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<PluginIdentity>,
+    /// User-defined labels for organizing the project list (lowercase, deduplicated)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+}
+
+/// Filters for search_projects - all set filters must match
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSearchQuery {
+    /// Matched case-insensitively against name, description, and tags
+    pub text: Option<String>,
+    /// Projects must have every one of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// UI framework ("webview", "egui", "native")
+    pub ui_framework: Option<String>,
+    /// Plugin type ("effect", "instrument")
+    pub template: Option<String>,
+    #[serde(default)]
+    pub favorites_only: bool,
+}
+
+/// Most tags a project can have
+const MAX_TAGS: usize = 20;
+
+/// Longest allowed tag
+const MAX_TAG_LEN: usize = 32;
+
+/// Vendor details and IDs compiled into the plugin
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginIdentity {
+    pub vendor_name: String,
+    #[serde(default)]
+    pub vendor_url: String,
+    #[serde(default)]
+    pub vendor_email: String,
+    /// Reverse-DNS ID used for CLAP_ID (e.g. com.vendor.my_plugin)
+    pub bundle_id: String,
 }
 
 #[derive(Deserialize)]
@@ -34,6 +82,9 @@ pub struct CreateProjectInput {
     pub components: Option<Vec<String>>, // Starter components to include
 }
 
+/// nih-plug revision new projects are pinned to (upgrade_framework moves older projects here)
+pub(crate) const NIH_PLUG_REV: &str = "28b149ec";
+
 pub fn get_workspace_path() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_default();
     PathBuf::from(home).join("VSTWorkshop")
@@ -86,6 +137,120 @@ fn ensure_nih_plug_docs() -> Result<(), String> {
     Ok(())
 }
 
+/// Members every workspace needs; users may add more
+const WORKSPACE_MEMBERS: [&str; 2] = ["projects/*", "xtask"];
+
+/// Serializes workspace Cargo.toml updates within this process (the file lock covers other processes)
+static WORKSPACE_CARGO_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Quoted strings in a TOML array: `["a", 'b']` -> a, b
+fn toml_array_strings(value: &str) -> Vec<String> {
+    value
+        .split(['"', '\''])
+        .skip(1)
+        .step_by(2)
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Workspace Cargo.toml with the required members and resolver added, keeping
+/// everything else as it is (None when nothing needs to change)
+fn merge_workspace_cargo(existing: &str) -> Option<String> {
+    let mut lines: Vec<String> = existing.lines().map(String::from).collect();
+    let Some(start) = lines.iter().position(|l| l.trim() == "[workspace]") else {
+        let members = WORKSPACE_MEMBERS.map(|m| format!("\"{}\"", m)).join(", ");
+        let section = format!("[workspace]\nmembers = [{}]\nresolver = \"2\"\n", members);
+        return Some(if existing.trim().is_empty() {
+            section
+        } else {
+            format!("{}\n{}", section, existing)
+        });
+    };
+    let end = lines
+        .iter()
+        .skip(start + 1)
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| i + start + 1);
+    let key = |line: &str| line.split('=').next().unwrap_or_default().trim().to_string();
+
+    let mut changed = false;
+    match (start + 1..end).find(|&i| key(&lines[i]) == "members") {
+        Some(first) => {
+            // The array may span several lines
+            let last = (first..end).find(|&i| lines[i].contains(']')).unwrap_or(first);
+            let value = lines[first..=last].join("\n");
+            let mut members = toml_array_strings(value.split_once('=').map_or("", |(_, v)| v));
+            let missing: Vec<String> = WORKSPACE_MEMBERS
+                .iter()
+                .filter(|m| !members.iter().any(|have| have == *m))
+                .map(|m| m.to_string())
+                .collect();
+            if !missing.is_empty() {
+                members.extend(missing);
+                let quoted: Vec<String> = members.iter().map(|m| format!("\"{}\"", m)).collect();
+                lines.splice(first..=last, [format!("members = [{}]", quoted.join(", "))]);
+                changed = true;
+            }
+        }
+        None => {
+            let members = WORKSPACE_MEMBERS.map(|m| format!("\"{}\"", m)).join(", ");
+            lines.insert(start + 1, format!("members = [{}]", members));
+            changed = true;
+        }
+    }
+    let end = lines
+        .iter()
+        .skip(start + 1)
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| i + start + 1);
+    if !(start + 1..end).any(|i| key(&lines[i]) == "resolver") {
+        let after_members = (start + 1..end)
+            .filter(|&i| !lines[i].trim().is_empty())
+            .last()
+            .map_or(start + 1, |i| i + 1);
+        lines.insert(after_members, "resolver = \"2\"".to_string());
+        changed = true;
+    }
+
+    changed.then(|| lines.join("\n") + "\n")
+}
+
+/// Exclusive lock on a file for as long as it's held
+struct FileLock {
+    _file: fs::File,
+}
+
+impl FileLock {
+    fn acquire(path: &std::path::Path) -> Result<Self, String> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        // flock on unix, LockFileEx on Windows; released when the file is closed
+        file.lock_exclusive().map_err(|e| format!("Failed to lock {}: {}", path.display(), e))?;
+        Ok(Self { _file: file })
+    }
+}
+
+/// Make sure the workspace Cargo.toml lists the required members
+/// Only writes when something is missing, and writes atomically, so concurrent
+/// builds never see a half-written file and user settings are kept.
+fn update_workspace_cargo(workspace: &std::path::Path) -> Result<(), String> {
+    let _guard = WORKSPACE_CARGO_LOCK.lock();
+    let _lock = FileLock::acquire(&workspace.join(".workspace-cargo.lock"))?;
+
+    let workspace_cargo = workspace.join("Cargo.toml");
+    let existing = fs::read_to_string(&workspace_cargo).unwrap_or_default();
+    let Some(updated) = merge_workspace_cargo(&existing) else {
+        return Ok(());
+    };
+    let tmp = workspace.join("Cargo.toml.tmp");
+    fs::write(&tmp, updated).map_err(|e| format!("Failed to write workspace Cargo.toml: {}", e))?;
+    fs::rename(&tmp, &workspace_cargo).map_err(|e| format!("Failed to update workspace Cargo.toml: {}", e))
+}
+
 /// Ensure the workspace directories exist and workspace Cargo.toml is set up
 pub fn ensure_workspace() -> Result<(), String> {
     let workspace = get_workspace_path();
@@ -98,25 +263,22 @@ pub fn ensure_workspace() -> Result<(), String> {
     fs::create_dir_all(&xtask_dir).map_err(|e| format!("Failed to create xtask dir: {}", e))?;
 
     // Create or update workspace root Cargo.toml
-    let workspace_cargo = workspace.join("Cargo.toml");
-    let cargo_content = r#"[workspace]
-members = ["projects/*", "xtask"]
-resolver = "2"
-"#;
-    fs::write(&workspace_cargo, cargo_content)
-        .map_err(|e| format!("Failed to create workspace Cargo.toml: {}", e))?;
+    update_workspace_cargo(&workspace)?;
 
     // Create shared xtask Cargo.toml if it doesn't exist
     let xtask_cargo = workspace.join("xtask/Cargo.toml");
     if !xtask_cargo.exists() {
-        let xtask_content = r#"[package]
+        let xtask_content = format!(
+            r#"[package]
 name = "xtask"
 version = "0.1.0"
 edition = "2021"
 
 [dependencies]
-nih_plug_xtask = { git = "https://github.com/robbert-vdh/nih-plug.git", rev = "28b149ec" }
-"#;
+nih_plug_xtask = {{ git = "https://github.com/robbert-vdh/nih-plug.git", rev = "{}" }}
+"#,
+            NIH_PLUG_REV
+        );
         fs::write(&xtask_cargo, xtask_content)
             .map_err(|e| format!("Failed to create xtask Cargo.toml: {}", e))?;
     }
@@ -163,7 +325,7 @@ xtask = "run --package xtask --release --"
 }
 
 /// Validate plugin name (lowercase, no spaces, valid Rust identifier)
-fn validate_name(name: &str) -> Result<(), String> {
+pub(crate) fn validate_name(name: &str) -> Result<(), String> {
     if name.is_empty() {
         return Err("Name cannot be empty".to_string());
     }
@@ -197,13 +359,17 @@ fn to_pascal_case(name: &str) -> String {
         .collect()
 }
 
-/// Generate a unique VST3 class ID from the plugin name
-fn generate_vst3_id(name: &str) -> String {
+/// Generate a VST3 class ID from the plugin name
+/// A non-zero `salt` gives an alternative ID when the first one is already taken.
+pub(crate) fn generate_vst3_id(name: &str, salt: u32) -> String {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
     let mut hasher = DefaultHasher::new();
     name.hash(&mut hasher);
+    if salt > 0 {
+        salt.hash(&mut hasher);
+    }
     let hash = hasher.finish();
 
     // Create a 16-byte ID string
@@ -213,103 +379,118 @@ fn generate_vst3_id(name: &str) -> String {
         .collect()
 }
 
-/// Generate .claude/commands/ with project-specific skills
-/// Skills are generated based on the project's template, UI framework, and components
-fn generate_project_skills(
-    project_path: &std::path::Path,
-    template: &str,
-    ui_framework: &str,
-    components: Option<&Vec<String>>,
-) -> Result<(), String> {
+/// Cargo dependencies a UI framework needs on top of nih_plug
+fn ui_dependencies(ui_framework: &str) -> String {
+    match ui_framework {
+        "webview" => r#"# Forked nih-plug-webview with Tauri compatibility and hot reload support
+nih_plug_webview = { git = "https://github.com/jamesontucker/nih-plug-webview" }
+serde = { version = "1.0", features = ["derive"] }
+serde_json = "1.0""#
+            .to_string(),
+        "egui" => format!(
+            r#"nih_plug_egui = {{ git = "https://github.com/robbert-vdh/nih-plug.git", rev = "{}" }}
+egui = "0.24""#,
+            NIH_PLUG_REV
+        ),
+        _ => String::new(), // native - no additional deps
+    }
+}
+
+/// Skill file name and content for a UI framework
+fn ui_skill(ui_framework: &str) -> Option<(&'static str, &'static str)> {
     use super::claude_skills;
 
-    let commands_dir = project_path.join(".claude/commands");
-    fs::create_dir_all(&commands_dir)
-        .map_err(|e| format!("Failed to create .claude/commands: {}", e))?;
+    match ui_framework {
+        "webview" => Some(("webview-ui.md", claude_skills::WEBVIEW_UI)),
+        "egui" => Some(("egui-ui.md", claude_skills::EGUI_UI)),
+        "native" => Some(("native-ui.md", claude_skills::NATIVE_UI)),
+        _ => None,
+    }
+}
 
-    // Always generate core skills (DSP safety, nih-plug basics)
-    fs::write(commands_dir.join("dsp-safety.md"), claude_skills::DSP_SAFETY)
-        .map_err(|e| format!("Failed to write dsp-safety.md: {}", e))?;
-    fs::write(commands_dir.join("nih-plug-basics.md"), claude_skills::NIH_PLUG_BASICS)
-        .map_err(|e| format!("Failed to write nih-plug-basics.md: {}", e))?;
+/// Skills a new project gets as (skill name, content)
+/// Skills are picked based on the project's template, UI framework, and components
+fn project_skills(template: &str, ui_framework: &str, components: Option<&Vec<String>>) -> Vec<(String, &'static str)> {
+    use super::claude_skills;
 
-    // Generate UI framework skill based on selection (only one)
-    match ui_framework {
-        "webview" => {
-            fs::write(commands_dir.join("webview-ui.md"), claude_skills::WEBVIEW_UI)
-                .map_err(|e| format!("Failed to write webview-ui.md: {}", e))?;
-        }
-        "egui" => {
-            fs::write(commands_dir.join("egui-ui.md"), claude_skills::EGUI_UI)
-                .map_err(|e| format!("Failed to write egui-ui.md: {}", e))?;
-        }
-        "native" => {
-            fs::write(commands_dir.join("native-ui.md"), claude_skills::NATIVE_UI)
-                .map_err(|e| format!("Failed to write native-ui.md: {}", e))?;
-        }
-        _ => {}
+    // Always include core skills (DSP safety, nih-plug basics)
+    let mut skills = vec![
+        ("dsp-safety".to_string(), claude_skills::DSP_SAFETY),
+        ("nih-plug-basics".to_string(), claude_skills::NIH_PLUG_BASICS),
+    ];
+
+    // UI framework skill based on selection (only one)
+    if let Some((filename, skill_content)) = ui_skill(ui_framework) {
+        skills.push((filename.trim_end_matches(".md").to_string(), skill_content));
     }
 
-    // Generate plugin type skill based on template (only one)
+    // Plugin type skill based on template (only one)
     match template {
-        "effect" => {
-            fs::write(commands_dir.join("effect-patterns.md"), claude_skills::EFFECT_PATTERNS)
-                .map_err(|e| format!("Failed to write effect-patterns.md: {}", e))?;
-        }
-        "instrument" => {
-            fs::write(commands_dir.join("instrument-patterns.md"), claude_skills::INSTRUMENT_PATTERNS)
-                .map_err(|e| format!("Failed to write instrument-patterns.md: {}", e))?;
-        }
+        "effect" => skills.push(("effect-patterns".to_string(), claude_skills::EFFECT_PATTERNS)),
+        "instrument" => skills.push(("instrument-patterns".to_string(), claude_skills::INSTRUMENT_PATTERNS)),
         _ => {}
     }
 
-    // Generate component skills if any were selected
+    // Component skills if any were selected
     if let Some(comps) = components {
         for component in comps {
             if let Some(skill_content) = claude_skills::get_component_skill(component) {
-                let filename = format!("{}.md", component.replace('_', "-"));
-                fs::write(commands_dir.join(&filename), skill_content)
-                    .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
+                skills.push((component.replace('_', "-"), skill_content));
             }
         }
     }
 
-    Ok(())
+    skills
 }
 
-#[tauri::command]
-pub async fn create_project(input: CreateProjectInput) -> Result<ProjectMeta, String> {
-    validate_name(&input.name)?;
-    ensure_workspace()?;
-
-    let project_path = get_projects_path().join(&input.name);
+/// Generate .claude/commands/ with project-specific skills
+fn generate_project_skills(
+    project_path: &std::path::Path,
+    template: &str,
+    ui_framework: &str,
+    components: Option<&Vec<String>>,
+) -> Result<(), String> {
+    let commands_dir = project_path.join(".claude/commands");
+    fs::create_dir_all(&commands_dir)
+        .map_err(|e| format!("Failed to create .claude/commands: {}", e))?;
 
-    if project_path.exists() {
-        return Err(format!("Project '{}' already exists", input.name));
+    let skills = project_skills(template, ui_framework, components);
+    for (skill_name, skill_content) in &skills {
+        let filename = format!("{}.md", skill_name);
+        fs::write(commands_dir.join(&filename), skill_content)
+            .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
     }
 
-    // Create directory structure
-    fs::create_dir_all(project_path.join("src"))
-        .map_err(|e| format!("Failed to create src dir: {}", e))?;
-    fs::create_dir_all(project_path.join(".vstworkshop"))
-        .map_err(|e| format!("Failed to create .vstworkshop dir: {}", e))?;
+    super::library_usage::record_guides_copied(&skills.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>());
+    Ok(())
+}
+
+/// A file a new project starts with
+#[derive(Serialize, Clone, Debug)]
+pub struct TemplateFile {
+    /// Relative to the project folder
+    pub path: String,
+    pub content: String,
+}
 
+/// Vendor name as used in bundle IDs: "My Company" -> "mycompany"
+fn vendor_id(vendor_name: &str) -> String {
+    vendor_name
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Source files for a new project: Cargo.toml, src/lib.rs, and src/ui.html for webview UIs
+pub(crate) fn render_project_sources(input: &CreateProjectInput, vst3_id: &str) -> Vec<TemplateFile> {
     let snake_name = to_snake_case(&input.name);
     let pascal_name = to_pascal_case(&input.name);
-    let vst3_id = generate_vst3_id(&input.name);
 
     // Generate dependencies based on UI framework
-    let ui_deps = match input.ui_framework.as_str() {
-        "webview" => r#"# Forked nih-plug-webview with Tauri compatibility and hot reload support
-nih_plug_webview = { git = "https://github.com/jamesontucker/nih-plug-webview" }
-serde = { version = "1.0", features = ["derive"] }
-serde_json = "1.0""#,
-        "egui" => r#"nih_plug_egui = { git = "https://github.com/robbert-vdh/nih-plug.git", rev = "28b149ec" }
-egui = "0.24""#,
-        _ => "", // native - no additional deps
-    };
+    let ui_deps = ui_dependencies(&input.ui_framework);
 
-    // Write Cargo.toml (project is a workspace member, no [workspace] section needed)
+    // Cargo.toml (project is a workspace member, no [workspace] section needed)
     let cargo_toml = if ui_deps.is_empty() {
         format!(
             r#"[package]
@@ -323,14 +504,15 @@ description = "{description}"
 crate-type = ["cdylib"]
 
 [dependencies]
-nih_plug = {{ git = "https://github.com/robbert-vdh/nih-plug.git", rev = "28b149ec" }}
+nih_plug = {{ git = "https://github.com/robbert-vdh/nih-plug.git", rev = "{nih_plug_rev}" }}
 
 [profile.release]
 lto = "thin"
 strip = "symbols"
 "#,
             snake_name = snake_name,
-            description = input.description.replace('"', "\\\"")
+            description = input.description.replace('"', "\\\""),
+            nih_plug_rev = NIH_PLUG_REV
         )
     } else {
         format!(
@@ -345,7 +527,7 @@ description = "{description}"
 crate-type = ["cdylib"]
 
 [dependencies]
-nih_plug = {{ git = "https://github.com/robbert-vdh/nih-plug.git", rev = "28b149ec" }}
+nih_plug = {{ git = "https://github.com/robbert-vdh/nih-plug.git", rev = "{nih_plug_rev}" }}
 {ui_deps}
 
 [profile.release]
@@ -354,19 +536,18 @@ strip = "symbols"
 "#,
             snake_name = snake_name,
             description = input.description.replace('"', "\\\""),
-            ui_deps = ui_deps
+            ui_deps = ui_deps,
+            nih_plug_rev = NIH_PLUG_REV
         )
     };
-    fs::write(project_path.join("Cargo.toml"), cargo_toml)
-        .map_err(|e| format!("Failed to write Cargo.toml: {}", e))?;
+    let mut files = vec![TemplateFile {
+        path: "Cargo.toml".to_string(),
+        content: cargo_toml,
+    }];
 
     // Generate template based on type and UI framework
     let vendor_name = input.vendor_name.as_deref().unwrap_or("freqlab");
-    let vendor_id: String = vendor_name
-        .to_lowercase()
-        .chars()
-        .filter(|c| c.is_ascii_alphanumeric())
-        .collect();
+    let vendor_id = vendor_id(vendor_name);
     let vendor_url = input.vendor_url.as_deref().unwrap_or("");
     let vendor_email = input.vendor_email.as_deref().unwrap_or("");
     let description_escaped = input.description.replace('"', "\\\"");
@@ -374,42 +555,238 @@ strip = "symbols"
     // Select template based on plugin type and UI framework
     let lib_rs = match (input.template.as_str(), input.ui_framework.as_str()) {
         ("instrument", "webview") => generate_instrument_webview_template(
-            &pascal_name, &snake_name, &description_escaped, &vst3_id,
+            &pascal_name, &snake_name, &description_escaped, vst3_id,
             vendor_name, &vendor_id, vendor_url, vendor_email,
         ),
         ("instrument", "egui") => generate_instrument_egui_template(
-            &pascal_name, &snake_name, &description_escaped, &vst3_id,
+            &pascal_name, &snake_name, &description_escaped, vst3_id,
             vendor_name, &vendor_id, vendor_url, vendor_email,
         ),
         ("instrument", _) => generate_instrument_native_template(
-            &pascal_name, &snake_name, &description_escaped, &vst3_id,
+            &pascal_name, &snake_name, &description_escaped, vst3_id,
             vendor_name, &vendor_id, vendor_url, vendor_email,
         ),
         ("effect", "webview") => generate_effect_webview_template(
-            &pascal_name, &snake_name, &description_escaped, &vst3_id,
+            &pascal_name, &snake_name, &description_escaped, vst3_id,
             vendor_name, &vendor_id, vendor_url, vendor_email,
         ),
         ("effect", "egui") => generate_effect_egui_template(
-            &pascal_name, &snake_name, &description_escaped, &vst3_id,
+            &pascal_name, &snake_name, &description_escaped, vst3_id,
             vendor_name, &vendor_id, vendor_url, vendor_email,
         ),
         _ => generate_effect_native_template(
-            &pascal_name, &snake_name, &description_escaped, &vst3_id,
+            &pascal_name, &snake_name, &description_escaped, vst3_id,
             vendor_name, &vendor_id, vendor_url, vendor_email,
         ),
     };
 
-    fs::write(project_path.join("src/lib.rs"), lib_rs)
-        .map_err(|e| format!("Failed to write lib.rs: {}", e))?;
+    files.push(TemplateFile {
+        path: "src/lib.rs".to_string(),
+        content: lib_rs,
+    });
 
     // Create ui.html for webview projects
     if input.ui_framework == "webview" {
-        let ui_html = generate_webview_ui_html(&pascal_name);
-        fs::write(project_path.join("src/ui.html"), ui_html)
-            .map_err(|e| format!("Failed to write ui.html: {}", e))?;
+        files.push(TemplateFile {
+            path: "src/ui.html".to_string(),
+            content: generate_webview_ui_html(&pascal_name),
+        });
+    }
+
+    files
+}
+
+/// Steps of project creation, in order
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CreatePhase {
+    Prepare,
+    Sources,
+    Metadata,
+    Skills,
+    EditorConfig,
+    GitInit,
+    GitCommit,
+    Finalize,
+}
+
+impl CreatePhase {
+    fn message(self) -> String {
+        super::i18n::tr(match self {
+            CreatePhase::Prepare => "create.prepare",
+            CreatePhase::Sources => "create.sources",
+            CreatePhase::Metadata => "create.metadata",
+            CreatePhase::Skills => "create.skills",
+            CreatePhase::EditorConfig => "create.editor_config",
+            CreatePhase::GitInit => "create.git_init",
+            CreatePhase::GitCommit => "create.git_commit",
+            CreatePhase::Finalize => "create.finalize",
+        })
+    }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum ProjectCreateEvent {
+    #[serde(rename = "phase")]
+    Phase { phase: CreatePhase, message: String },
+    #[serde(rename = "done")]
+    Done { path: String },
+    #[serde(rename = "error")]
+    Error { phase: CreatePhase, message: String },
+}
+
+/// Create a new project, streaming each phase as `project-create-stream` events
+#[tauri::command]
+pub async fn create_project(input: CreateProjectInput, window: tauri::Window) -> Result<ProjectMeta, String> {
+    run_create_project(input, |event| {
+        let _ = window.emit("project-create-stream", event);
+    })
+    .await
+}
+
+/// Create a new project, reporting progress through `emit`
+pub(crate) async fn run_create_project(
+    input: CreateProjectInput,
+    emit: impl Fn(ProjectCreateEvent),
+) -> Result<ProjectMeta, String> {
+    let current = Mutex::new(CreatePhase::Prepare);
+    let enter = |phase: CreatePhase| {
+        *current.lock() = phase;
+        emit(ProjectCreateEvent::Phase {
+            phase,
+            message: phase.message(),
+        });
+    };
+
+    let result = create_project_phases(&input, &enter).await;
+    match &result {
+        Ok(metadata) => emit(ProjectCreateEvent::Done {
+            path: metadata.path.clone(),
+        }),
+        Err(message) => emit(ProjectCreateEvent::Error {
+            phase: *current.lock(),
+            message: message.clone(),
+        }),
+    }
+    result
+}
+
+async fn create_project_phases(
+    input: &CreateProjectInput,
+    enter: &impl Fn(CreatePhase),
+) -> Result<ProjectMeta, String> {
+    enter(CreatePhase::Prepare);
+    validate_name(&input.name)?;
+    ensure_workspace()?;
+
+    let project_path = get_projects_path().join(&input.name);
+
+    if project_path.exists() {
+        return Err(format!("Project '{}' already exists", input.name));
+    }
+
+    // Build the project in a staging folder (outside projects/, so Cargo and the
+    // project list never see it) and move it into place only once every step has
+    // succeeded - a failure midway no longer leaves a half-created project that
+    // blocks a retry
+    let staging_path = get_staging_path().join(format!("{}-{}", input.name, uuid::Uuid::new_v4()));
+    let staged = stage_project(input, &staging_path, &project_path, enter).await;
+    let result = staged.and_then(|(metadata, vst3_id)| {
+        enter(CreatePhase::Finalize);
+        if project_path.exists() {
+            return Err(format!("Project '{}' already exists", input.name));
+        }
+        fs::rename(&staging_path, &project_path)
+            .map_err(|e| format!("Failed to move project into place: {}", e))?;
+        Ok((metadata, vst3_id))
+    });
+    let (metadata, vst3_id) = match result {
+        Ok(created) => created,
+        Err(e) => {
+            if let Err(cleanup) = fs::remove_dir_all(&staging_path).or_else(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => Ok(()),
+                _ => Err(e),
+            }) {
+                log::warn!("Failed to remove staged project {:?}: {}", staging_path, cleanup);
+            }
+            return Err(e);
+        }
+    };
+
+    if let Some(identity) = &metadata.identity {
+        super::plugin_ids::register_project(&input.name, &vst3_id, &identity.bundle_id);
+    }
+
+    Ok(metadata)
+}
+
+/// Staging area for projects being created
+pub(crate) fn get_staging_path() -> PathBuf {
+    get_workspace_path().join(".staging")
+}
+
+/// Delete projects and share imports left staged by a run that quit or crashed
+/// midway. Call once at startup, before anything can start staging.
+pub(crate) fn clear_staging() {
+    let staging_path = get_staging_path();
+    let Ok(entries) = fs::read_dir(&staging_path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let result = if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+        match result {
+            Ok(()) => log::info!("Removed stale staged project {:?}", path),
+            Err(e) => log::warn!("Failed to remove stale staged project {:?}: {}", path, e),
+        }
+    }
+}
+
+/// Write a new project into `staging_path`; `project_path` is where it will end up
+/// Returns the metadata and the allocated VST3 class ID.
+async fn stage_project(
+    input: &CreateProjectInput,
+    staging_path: &std::path::Path,
+    project_path: &std::path::Path,
+    enter: &impl Fn(CreatePhase),
+) -> Result<(ProjectMeta, String), String> {
+    enter(CreatePhase::Sources);
+
+    // Create directory structure
+    fs::create_dir_all(staging_path.join("src"))
+        .map_err(|e| format!("Failed to create src dir: {}", e))?;
+    fs::create_dir_all(staging_path.join(".vstworkshop"))
+        .map_err(|e| format!("Failed to create .vstworkshop dir: {}", e))?;
+
+    // Avoid IDs already used (or previously released) by other projects
+    let vst3_id = super::plugin_ids::allocate_vst3_id(&input.name);
+    let snake_name = to_snake_case(&input.name);
+    let vendor_name = input.vendor_name.as_deref().unwrap_or("freqlab");
+    let vendor_id = vendor_id(vendor_name);
+    let vendor_url = input.vendor_url.as_deref().unwrap_or("");
+    let vendor_email = input.vendor_email.as_deref().unwrap_or("");
+
+    for file in render_project_sources(input, &vst3_id) {
+        fs::write(staging_path.join(&file.path), file.content)
+            .map_err(|e| format!("Failed to write {}: {}", file.path, e))?;
+    }
+
+    // The CLAP ID follows vendor and name, so a deleted project's may come back
+    let default_clap_id = format!("com.{}.{}", vendor_id, snake_name);
+    let clap_id = super::plugin_ids::allocate_clap_id(&input.name, &default_clap_id);
+    if clap_id != default_clap_id {
+        let lib_path = staging_path.join("src/lib.rs");
+        if let Some(updated) = fs::read_to_string(&lib_path)
+            .ok()
+            .and_then(|source| super::identity::replace_const_str(&source, "CLAP_ID", &clap_id))
+        {
+            fs::write(&lib_path, updated).map_err(|e| format!("Failed to write lib.rs: {}", e))?;
+        }
     }
 
     // Create metadata
+    enter(CreatePhase::Metadata);
     let now = chrono::Utc::now().to_rfc3339();
     let id = uuid::Uuid::new_v4().to_string();
 
@@ -431,38 +808,97 @@ strip = "symbols"
         created_at: now.clone(),
         updated_at: now,
         path: project_path.to_string_lossy().to_string(),
+        identity: Some(PluginIdentity {
+            vendor_name: vendor_name.to_string(),
+            vendor_url: vendor_url.to_string(),
+            vendor_email: vendor_email.to_string(),
+            bundle_id: clap_id,
+        }),
+        tags: Vec::new(),
+        favorite: false,
     };
 
     let metadata_json = serde_json::to_string_pretty(&metadata)
         .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
-    fs::write(project_path.join(".vstworkshop/metadata.json"), metadata_json)
+    fs::write(staging_path.join(".vstworkshop/metadata.json"), metadata_json)
         .map_err(|e| format!("Failed to write metadata.json: {}", e))?;
 
     // Generate CLAUDE.md for project-specific Claude guidance (uses display name for header)
+    enter(CreatePhase::Skills);
     let claude_md_content = super::claude_md::generate_claude_md(
         &display_name,
         &input.template,
         &input.ui_framework,
         input.components.as_ref(),
     );
-    fs::write(project_path.join("CLAUDE.md"), claude_md_content)
+    fs::write(staging_path.join("CLAUDE.md"), claude_md_content)
         .map_err(|e| format!("Failed to write CLAUDE.md: {}", e))?;
 
     // Generate .claude/commands/ with project-specific skills
-    generate_project_skills(&project_path, &input.template, &input.ui_framework, input.components.as_ref())?;
+    generate_project_skills(staging_path, &input.template, &input.ui_framework, input.components.as_ref())?;
+
+    // VS Code tasks (build/validate via freqlab-cli) and preview attach config
+    enter(CreatePhase::EditorConfig);
+    super::editor_integration::write_vscode_config(staging_path, &input.name)?;
 
     // Initialize git repository for version control
     // These operations now run on a blocking thread pool to avoid UI freezes
-    let project_path_str = project_path.to_string_lossy().to_string();
-    super::git::init_repo(&project_path_str).await?;
-    super::git::create_gitignore(&project_path_str)?;
-    super::git::commit_changes(&project_path_str, "Initial plugin template").await?;
+    enter(CreatePhase::GitInit);
+    let staging_path_str = staging_path.to_string_lossy().to_string();
+    super::git::init_repo(&staging_path_str).await?;
+    super::git::create_gitignore(&staging_path_str)?;
+    enter(CreatePhase::GitCommit);
+    super::git::commit_changes(&staging_path_str, "Initial plugin template").await?;
+
+    Ok((metadata, vst3_id))
+}
 
-    Ok(metadata)
+/// Render the files `create_project` would generate for `input` without writing
+/// anything - sources, CLAUDE.md, and skills, sorted by path. Blank names and
+/// descriptions get sample values so the preview works while the form is filled in.
+#[tauri::command]
+pub async fn preview_template(mut input: CreateProjectInput) -> Result<Vec<TemplateFile>, String> {
+    if input.name.trim().is_empty() {
+        input.name = "my_plugin".to_string();
+    }
+    validate_name(&input.name)?;
+    if input.description.trim().is_empty() {
+        input.description = "A freqlab plugin".to_string();
+    }
+    let display_name = input
+        .display_name
+        .clone()
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| input.name.clone());
+
+    let vst3_id = super::plugin_ids::allocate_vst3_id(&input.name);
+    let mut files = render_project_sources(&input, &vst3_id);
+    files.push(TemplateFile {
+        path: "CLAUDE.md".to_string(),
+        content: super::claude_md::generate_claude_md(
+            &display_name,
+            &input.template,
+            &input.ui_framework,
+            input.components.as_ref(),
+        ),
+    });
+    for (skill_name, skill_content) in project_skills(&input.template, &input.ui_framework, input.components.as_ref()) {
+        files.push(TemplateFile {
+            path: format!(".claude/commands/{}.md", skill_name),
+            content: skill_content.to_string(),
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
 }
 
 #[tauri::command]
 pub async fn list_projects() -> Result<Vec<ProjectMeta>, String> {
+    read_all_projects()
+}
+
+/// Read every project's metadata, most recently updated first
+fn read_all_projects() -> Result<Vec<ProjectMeta>, String> {
     ensure_workspace()?;
 
     let projects_dir = get_projects_path();
@@ -495,6 +931,124 @@ pub async fn list_projects() -> Result<Vec<ProjectMeta>, String> {
     Ok(projects)
 }
 
+/// Trim, lowercase, and deduplicate tags, enforcing the count and length limits
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || normalized.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(format!("Tag '{}' is too long (max {} chars)", tag, MAX_TAG_LEN));
+        }
+        normalized.push(tag);
+    }
+    if normalized.len() > MAX_TAGS {
+        return Err(format!("A project can have at most {} tags", MAX_TAGS));
+    }
+    Ok(normalized)
+}
+
+fn matches_search(meta: &ProjectMeta, query: &ProjectSearchQuery) -> bool {
+    if query.favorites_only && !meta.favorite {
+        return false;
+    }
+    if let Some(ref framework) = query.ui_framework {
+        if meta.ui_framework.as_deref() != Some(framework.as_str()) {
+            return false;
+        }
+    }
+    if let Some(ref template) = query.template {
+        if meta.template.as_deref() != Some(template.as_str()) {
+            return false;
+        }
+    }
+    if !query
+        .tags
+        .iter()
+        .all(|tag| meta.tags.contains(&tag.trim().to_lowercase()))
+    {
+        return false;
+    }
+
+    match query.text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        Some(text) => {
+            let text = text.to_lowercase();
+            meta.name.to_lowercase().contains(&text)
+                || meta.description.to_lowercase().contains(&text)
+                || meta.tags.iter().any(|tag| tag.contains(&text))
+        }
+        None => true,
+    }
+}
+
+/// Apply an update to a project's metadata file
+/// Leaves updated_at alone: organizing (tags, favorites) isn't editing the
+/// project, and bumping it would reorder the list on every star.
+fn update_project_meta(
+    project_path: &str,
+    update: impl FnOnce(&mut ProjectMeta) -> Result<(), String>,
+) -> Result<ProjectMeta, String> {
+    let metadata_path = PathBuf::from(project_path).join(".vstworkshop/metadata.json");
+    let content = fs::read_to_string(&metadata_path)
+        .map_err(|_| "Project metadata not found".to_string())?;
+    let mut meta: ProjectMeta = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+
+    update(&mut meta)?;
+
+    let metadata_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    fs::write(&metadata_path, metadata_json)
+        .map_err(|e| format!("Failed to write metadata: {}", e))?;
+    Ok(meta)
+}
+
+/// Replace a project's tags
+#[tauri::command]
+pub async fn set_project_tags(project_path: String, tags: Vec<String>) -> Result<ProjectMeta, String> {
+    let tags = normalize_tags(tags)?;
+    update_project_meta(&project_path, |meta| {
+        meta.tags = tags;
+        Ok(())
+    })
+}
+
+/// Star or unstar a project
+#[tauri::command]
+pub async fn set_project_favorite(project_path: String, favorite: bool) -> Result<ProjectMeta, String> {
+    update_project_meta(&project_path, |meta| {
+        meta.favorite = favorite;
+        Ok(())
+    })
+}
+
+/// All tags in use across projects, with how many projects have each
+#[tauri::command]
+pub async fn list_project_tags() -> Result<Vec<(String, usize)>, String> {
+    let mut counts: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    for meta in read_all_projects()? {
+        for tag in meta.tags {
+            *counts.entry(tag).or_default() += 1;
+        }
+    }
+    Ok(counts.into_iter().collect())
+}
+
+/// Search projects by text, tags, framework, type, and favorite flag
+/// Favorites come first, then most recently updated.
+#[tauri::command]
+pub async fn search_projects(query: ProjectSearchQuery) -> Result<Vec<ProjectMeta>, String> {
+    let mut projects: Vec<ProjectMeta> = read_all_projects()?
+        .into_iter()
+        .filter(|meta| matches_search(meta, &query))
+        .collect();
+    // Stable sort keeps the updated_at order within each group
+    projects.sort_by_key(|meta| !meta.favorite);
+    Ok(projects)
+}
+
 #[tauri::command]
 pub async fn get_project(name: String) -> Result<ProjectMeta, String> {
     let project_path = get_projects_path().join(&name);
@@ -524,6 +1078,9 @@ pub async fn delete_project(name: String) -> Result<(), String> {
     fs::remove_dir_all(&project_path)
         .map_err(|e| format!("Failed to delete project: {}", e))?;
 
+    // Keep its IDs reserved - a released build may still be installed somewhere
+    super::plugin_ids::mark_deleted(&name);
+
     // Also clean up the output folder for this project (output/{name}/)
     let output_folder = get_output_path().join(&name);
     if output_folder.exists() {
@@ -586,7 +1143,7 @@ pub async fn open_project_folder(path: String) -> Result<(), String> {
 
 #[tauri::command]
 pub async fn open_in_editor(path: String, editor: Option<String>) -> Result<(), String> {
-    let editor_cmd = editor.unwrap_or_else(|| "code".to_string());
+    let editor_cmd = editor.unwrap_or_else(|| super::settings::current_settings().editor);
 
     std::process::Command::new(&editor_cmd)
         .arg(&path)
@@ -596,6 +1153,338 @@ pub async fn open_in_editor(path: String, editor: Option<String>) -> Result<(),
     Ok(())
 }
 
+#[derive(Serialize)]
+pub struct AddComponentResult {
+    pub project: ProjectMeta,
+    /// Claude's response when integration was requested
+    pub integration: Option<super::claude::ClaudeResponse>,
+}
+
+/// Add a starter component to an existing project
+/// Installs the component's skill, updates CLAUDE.md's manifest and metadata, and
+/// optionally asks Claude to integrate the component into the current code.
+#[tauri::command]
+pub async fn add_component(
+    project_path: String,
+    component: String,
+    integrate: bool,
+    model: Option<String>,
+    window: tauri::Window,
+) -> Result<AddComponentResult, String> {
+    use super::claude_skills;
+
+    let path = PathBuf::from(&project_path);
+    let metadata_path = path.join(".vstworkshop/metadata.json");
+    let content = fs::read_to_string(&metadata_path)
+        .map_err(|_| "Project metadata not found".to_string())?;
+    let mut meta: ProjectMeta = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+
+    let skill_content = claude_skills::get_component_skill(&component)
+        .ok_or_else(|| format!("Unknown component '{}'", component))?;
+    let template = meta.template.clone().unwrap_or_else(|| "effect".to_string());
+    if !super::claude_md::component_supports_template(&component, &template) {
+        return Err(format!("Component '{}' isn't available for {} plugins", component, template));
+    }
+    if meta.components.as_ref().is_some_and(|c| c.contains(&component)) {
+        return Err(format!("Component '{}' is already part of this project", component));
+    }
+
+    // Install the skill
+    let commands_dir = path.join(".claude/commands");
+    fs::create_dir_all(&commands_dir)
+        .map_err(|e| format!("Failed to create .claude/commands: {}", e))?;
+    let skill_name = component.replace('_', "-");
+    let filename = format!("{}.md", skill_name);
+    fs::write(commands_dir.join(&filename), skill_content)
+        .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
+    super::library_usage::record_guides_copied(&[&skill_name]);
+
+    // Update the CLAUDE.md manifest in place (it may have been edited since creation)
+    let claude_md_path = path.join("CLAUDE.md");
+    let claude_md = match fs::read_to_string(&claude_md_path) {
+        Ok(existing) => super::claude_md::add_component_to_claude_md(&existing, &component),
+        Err(_) => {
+            let mut components = meta.components.clone().unwrap_or_default();
+            components.push(component.clone());
+            super::claude_md::generate_claude_md(
+                &meta.name,
+                &template,
+                meta.ui_framework.as_deref().unwrap_or("native"),
+                Some(&components),
+            )
+        }
+    };
+    fs::write(&claude_md_path, claude_md)
+        .map_err(|e| format!("Failed to write CLAUDE.md: {}", e))?;
+
+    // Record in metadata
+    meta.components.get_or_insert_with(Vec::new).push(component.clone());
+    meta.updated_at = chrono::Utc::now().to_rfc3339();
+    let metadata_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    fs::write(&metadata_path, metadata_json)
+        .map_err(|e| format!("Failed to write metadata: {}", e))?;
+
+    // No git commit here: the skill, CLAUDE.md, and metadata are all gitignored, so
+    // there's nothing versioned to commit until Claude integrates the component
+    let integration = if integrate {
+        let project_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let message = format!(
+            "Integrate the {component} component into the existing plugin. Invoke /{skill_name} first and \
+             follow its patterns. Keep the current parameters, parameter IDs, and DSP behavior intact - \
+             only add what the component needs, then update the Current Implementation section of CLAUDE.md.",
+            component = component.replace('_', " "),
+            skill_name = skill_name,
+        );
+        let response = super::claude::send_to_claude(
+            project_path.clone(),
+            project_name,
+            meta.description.clone(),
+            message,
+            model,
+            None,
+            None,
+            None,
+            window,
+        )
+        .await?;
+        Some(response)
+    } else {
+        None
+    };
+
+    Ok(AddComponentResult { project: meta, integration })
+}
+
+/// Add dependency lines to Cargo.toml's [dependencies] table, skipping crates already present
+/// Comment lines are kept with the dependency that follows them.
+fn add_cargo_dependencies(cargo_toml: &str, deps: &str) -> String {
+    let mut lines: Vec<String> = cargo_toml.lines().map(String::from).collect();
+    let Some(start) = lines.iter().position(|l| l.trim() == "[dependencies]") else {
+        return cargo_toml.to_string();
+    };
+    let mut end = lines
+        .iter()
+        .skip(start + 1)
+        .position(|l| l.trim_start().starts_with('['))
+        .map(|i| i + start + 1)
+        .unwrap_or(lines.len());
+    while end > start + 1 && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+
+    let crate_name = |line: &str| line.split('=').next().unwrap_or_default().trim().to_string();
+    let existing: Vec<String> = lines[start + 1..end]
+        .iter()
+        .filter(|l| l.contains('=') && !l.trim_start().starts_with('#'))
+        .map(|l| crate_name(l))
+        .collect();
+
+    let mut to_add = Vec::new();
+    let mut comments = Vec::new();
+    for line in deps.lines() {
+        if line.trim_start().starts_with('#') {
+            comments.push(line.to_string());
+        } else if !line.trim().is_empty() {
+            if !existing.contains(&crate_name(line)) {
+                to_add.append(&mut comments);
+                to_add.push(line.to_string());
+            }
+            comments.clear();
+        }
+    }
+    lines.splice(end..end, to_add);
+
+    let mut updated = lines.join("\n");
+    if cargo_toml.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+/// Parameter IDs declared with #[id = "..."] in the plugin source
+fn declared_param_ids(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("#[id")?;
+            let start = rest.find('"')? + 1;
+            let end = start + rest[start..].find('"')?;
+            Some(rest[start..end].to_string())
+        })
+        .collect()
+}
+
+/// Manual wiring left after scaffolding a UI framework switch
+fn ui_migration_tasks(from: &str, to: &str, param_ids: &[String]) -> Vec<String> {
+    let params = if param_ids.is_empty() {
+        "each parameter".to_string()
+    } else {
+        param_ids.iter().map(|id| format!("`{}`", id)).collect::<Vec<_>>().join(", ")
+    };
+    let mut tasks = Vec::new();
+
+    match from {
+        "webview" => tasks.push(
+            "Remove the WebViewEditor-based `editor()`, the UIMessage enum, and the `*_changed` AtomicBool flags and callbacks".to_string(),
+        ),
+        "egui" => tasks.push(
+            "Remove the egui `editor()` and the `editor_state: Arc<EguiState>` persisted field".to_string(),
+        ),
+        _ => {}
+    }
+
+    match to {
+        "webview" => {
+            tasks.push("Add `use nih_plug_webview::{WebViewEditor, HTMLSource};` and the serde/serde_json imports to src/lib.rs".to_string());
+            tasks.push("Implement `editor()` returning a WebViewEditor with `HTMLSource::String(include_str!(\"ui.html\"))` (see /webview-ui)".to_string());
+            tasks.push(format!("Add a UIMessage variant and handler for {} using begin/set/end_set_parameter", params));
+            tasks.push(format!("Add AtomicBool change flags with `.with_callback()` so host automation of {} updates the UI", params));
+            tasks.push(format!("Add controls for {} to src/ui.html (the scaffold only has a Gain slider)", params));
+        }
+        "egui" => {
+            tasks.push("Add `#[persist = \"editor-state\"] editor_state: Arc<EguiState>` to the params struct, initialized with `EguiState::from_size(400, 300)`".to_string());
+            tasks.push("Implement `editor()` with `create_egui_editor` (see /egui-ui)".to_string());
+            tasks.push(format!("Add a `widgets::ParamSlider` for {}", params));
+        }
+        _ => {
+            tasks.push("Make sure every parameter has a clear name and unit, since the DAW's generic controls are the only UI".to_string());
+        }
+    }
+
+    if from == "webview" {
+        tasks.push("Delete src/ui.html and remove nih_plug_webview, serde, and serde_json from Cargo.toml if nothing else uses them".to_string());
+    } else if from == "egui" {
+        tasks.push("Remove nih_plug_egui and egui from Cargo.toml".to_string());
+    }
+    tasks.push("Leave `process()` and all DSP code unchanged, then build and open the editor to verify".to_string());
+    tasks
+}
+
+#[derive(Serialize)]
+pub struct UiMigrationResult {
+    pub project: ProjectMeta,
+    /// Files created or modified by the scaffold
+    pub changed_files: Vec<String>,
+    /// Manual wiring left for Claude (also written to .claude/commands/ui-migration.md)
+    pub tasks: Vec<String>,
+}
+
+/// Switch an existing project to a different UI framework
+/// Scaffolds the target framework's files and dependencies without touching the plugin
+/// code, updates the skills/CLAUDE.md/metadata, and produces a task list for the wiring.
+#[tauri::command]
+pub async fn migrate_ui_framework(
+    project_path: String,
+    ui_framework: String,
+) -> Result<UiMigrationResult, String> {
+    if ui_skill(&ui_framework).is_none() {
+        return Err(format!("Unknown UI framework '{}'", ui_framework));
+    }
+
+    let path = PathBuf::from(&project_path);
+    let metadata_path = path.join(".vstworkshop/metadata.json");
+    let content = fs::read_to_string(&metadata_path)
+        .map_err(|_| "Project metadata not found".to_string())?;
+    let mut meta: ProjectMeta = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+
+    let from = meta.ui_framework.clone().unwrap_or_else(|| "native".to_string());
+    if from == ui_framework {
+        return Err(format!("Project already uses the {} UI framework", ui_framework));
+    }
+
+    let folder_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut changed_files = Vec::new();
+
+    // Dependencies (old framework's crates are left for the task list - code still uses them)
+    let cargo_path = path.join("Cargo.toml");
+    let cargo_toml = fs::read_to_string(&cargo_path)
+        .map_err(|e| format!("Failed to read Cargo.toml: {}", e))?;
+    let updated_cargo = add_cargo_dependencies(&cargo_toml, &ui_dependencies(&ui_framework));
+    if updated_cargo != cargo_toml {
+        fs::write(&cargo_path, updated_cargo)
+            .map_err(|e| format!("Failed to write Cargo.toml: {}", e))?;
+        changed_files.push("Cargo.toml".to_string());
+    }
+
+    // Framework files
+    let ui_html_path = path.join("src/ui.html");
+    if ui_framework == "webview" && !ui_html_path.exists() {
+        fs::write(&ui_html_path, generate_webview_ui_html(&to_pascal_case(&folder_name)))
+            .map_err(|e| format!("Failed to write ui.html: {}", e))?;
+        changed_files.push("src/ui.html".to_string());
+    }
+
+    // Skills: swap the UI skill and leave a task list Claude can follow
+    let commands_dir = path.join(".claude/commands");
+    fs::create_dir_all(&commands_dir)
+        .map_err(|e| format!("Failed to create .claude/commands: {}", e))?;
+    if let Some((old_skill, _)) = ui_skill(&from) {
+        let _ = fs::remove_file(commands_dir.join(old_skill));
+    }
+    if let Some((filename, skill_content)) = ui_skill(&ui_framework) {
+        fs::write(commands_dir.join(filename), skill_content)
+            .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
+        changed_files.push(format!(".claude/commands/{}", filename));
+        super::library_usage::record_guides_copied(&[filename.trim_end_matches(".md")]);
+    }
+
+    let lib_rs = fs::read_to_string(path.join("src/lib.rs")).unwrap_or_default();
+    let tasks = ui_migration_tasks(&from, &ui_framework, &declared_param_ids(&lib_rs));
+    let task_skill = format!(
+        "---\nname: ui-migration\ndescription: Remaining steps to finish moving this plugin from the {from} UI to {to}. Invoke when asked to finish the UI migration.\n---\n\n# UI Migration: {from} -> {to}\n\nThe dependencies and skill files are already in place. Finish the wiring in src/lib.rs:\n\n{tasks}\n",
+        from = from,
+        to = ui_framework,
+        tasks = tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| format!("{}. {}", i + 1, t))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    fs::write(commands_dir.join("ui-migration.md"), task_skill)
+        .map_err(|e| format!("Failed to write ui-migration.md: {}", e))?;
+    changed_files.push(".claude/commands/ui-migration.md".to_string());
+
+    let claude_md_path = path.join("CLAUDE.md");
+    if let Ok(claude_md) = fs::read_to_string(&claude_md_path) {
+        fs::write(
+            &claude_md_path,
+            super::claude_md::set_ui_framework_in_claude_md(&claude_md, &ui_framework),
+        )
+        .map_err(|e| format!("Failed to write CLAUDE.md: {}", e))?;
+        changed_files.push("CLAUDE.md".to_string());
+    }
+
+    meta.ui_framework = Some(ui_framework.clone());
+    meta.updated_at = chrono::Utc::now().to_rfc3339();
+    let metadata_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    fs::write(&metadata_path, metadata_json)
+        .map_err(|e| format!("Failed to write metadata: {}", e))?;
+
+    if super::git::is_git_repo(&project_path) {
+        let message = format!("Scaffold {} UI (migrating from {})", ui_framework, from);
+        if let Err(e) = super::git::commit_changes(&project_path, &message).await {
+            log::warn!("Failed to commit UI migration scaffold: {}", e);
+        }
+    }
+
+    Ok(UiMigrationResult {
+        project: meta,
+        changed_files,
+        tasks,
+    })
+}
+
 #[tauri::command]
 pub async fn get_workspace_path_string() -> String {
     get_workspace_path().to_string_lossy().to_string()
@@ -931,6 +1820,15 @@ use std::sync::atomic::{{AtomicBool, Ordering}};
 enum UIMessage {{
     Init,
     SetGain {{ value: f32 }},
+    Heartbeat,
+}}
+
+/// Tells the freqlab preview the UI is still rendering (no-op in other hosts)
+fn ui_heartbeat() {{
+    let path = std::env::temp_dir().join(format!("freqlab-ui-heartbeat-{{}}", std::process::id()));
+    if path.exists() {{
+        let _ = std::fs::write(&path, b"");
+    }}
 }}
 
 /// {description}
@@ -1031,6 +1929,7 @@ impl Plugin for {pascal_name} {{
                                 setter.set_parameter_normalized(&params.gain, value);
                                 setter.end_set_parameter(&params.gain);
                             }}
+                            UIMessage::Heartbeat => ui_heartbeat(),
                         }}
                     }}
                 }}
@@ -1268,6 +2167,15 @@ use std::sync::atomic::{{AtomicBool, Ordering}};
 enum UIMessage {{
     Init,
     SetGain {{ value: f32 }},
+    Heartbeat,
+}}
+
+/// Tells the freqlab preview the UI is still rendering (no-op in other hosts)
+fn ui_heartbeat() {{
+    let path = std::env::temp_dir().join(format!("freqlab-ui-heartbeat-{{}}", std::process::id()));
+    if path.exists() {{
+        let _ = std::fs::write(&path, b"");
+    }}
 }}
 
 /// {description}
@@ -1382,6 +2290,7 @@ impl Plugin for {pascal_name} {{
                                 setter.set_parameter_normalized(&params.gain, value);
                                 setter.end_set_parameter(&params.gain);
                             }}
+                            UIMessage::Heartbeat => ui_heartbeat(),
                         }}
                     }}
                 }}
@@ -1796,6 +2705,17 @@ fn generate_webview_ui_html(pascal_name: &str) -> String {
         window.addEventListener('DOMContentLoaded', () => {{
             sendToPlugin({{ type: 'Init' }});
         }});
+
+        // Heartbeat from the render loop - lets the freqlab preview spot a blank UI
+        let lastHeartbeat = 0;
+        function heartbeat(now) {{
+            if (now - lastHeartbeat > 1000) {{
+                lastHeartbeat = now;
+                sendToPlugin({{ type: 'Heartbeat' }});
+            }}
+            requestAnimationFrame(heartbeat);
+        }}
+        requestAnimationFrame(heartbeat);
     </script>
 </body>
 </html>
@@ -1803,3 +2723,118 @@ fn generate_webview_ui_html(pascal_name: &str) -> String {
         pascal_name = pascal_name
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_cargo_dependencies() {
+        let cargo = "[package]\nname = \"demo\"\n\n[dependencies]\nnih_plug = { git = \"x\" }\nserde = \"1.0\"\n\n[profile.release]\nlto = \"thin\"\n";
+        let updated = add_cargo_dependencies(cargo, &ui_dependencies("webview"));
+
+        assert!(updated.contains("# Forked nih-plug-webview"));
+        assert!(updated.contains("nih_plug_webview = {"));
+        assert!(updated.contains("serde_json = \"1.0\""));
+        // serde was already there - not duplicated
+        assert_eq!(updated.matches("serde =").count(), 1);
+        assert!(updated.find("serde_json").unwrap() < updated.find("[profile.release]").unwrap());
+
+        assert_eq!(add_cargo_dependencies(&updated, &ui_dependencies("webview")), updated);
+        assert_eq!(add_cargo_dependencies(cargo, &ui_dependencies("native")), cargo);
+    }
+
+    #[test]
+    fn test_merge_workspace_cargo() {
+        let fresh = merge_workspace_cargo("").unwrap();
+        assert_eq!(fresh, "[workspace]\nmembers = [\"projects/*\", \"xtask\"]\nresolver = \"2\"\n");
+        assert!(merge_workspace_cargo(&fresh).is_none());
+
+        // User members and sections survive; only the missing member is added
+        let user = "[workspace]\nmembers = [\n    \"projects/*\",\n    \"shared\",\n]\nresolver = \"2\"\n\n[profile.dev]\nopt-level = 1\n";
+        let merged = merge_workspace_cargo(user).unwrap();
+        assert!(merged.starts_with("[workspace]\nmembers = [\"projects/*\", \"shared\", \"xtask\"]\nresolver = \"2\"\n"));
+        assert!(merged.ends_with("[profile.dev]\nopt-level = 1\n"));
+    }
+
+    #[test]
+    fn test_render_project_sources() {
+        let input = CreateProjectInput {
+            name: "tape_deck".to_string(),
+            display_name: None,
+            description: "A \"warm\" saturator".to_string(),
+            template: "effect".to_string(),
+            ui_framework: "webview".to_string(),
+            vendor_name: Some("My Co".to_string()),
+            vendor_url: None,
+            vendor_email: None,
+            components: Some(vec!["param_smoothing".to_string()]),
+        };
+        let files = render_project_sources(&input, "0123456789abcdef");
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["Cargo.toml", "src/lib.rs", "src/ui.html"]);
+        assert!(files[0].content.contains("name = \"tape_deck\""));
+        assert!(files[0].content.contains("description = \"A \\\"warm\\\" saturator\""));
+        assert!(files[1].content.contains("TapeDeck"));
+
+        let skills: Vec<String> = project_skills(&input.template, &input.ui_framework, input.components.as_ref())
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(skills, vec!["dsp-safety", "nih-plug-basics", "webview-ui", "effect-patterns", "param-smoothing"]);
+    }
+
+    fn meta(name: &str, tags: &[&str], framework: &str, favorite: bool) -> ProjectMeta {
+        ProjectMeta {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: "A warm tape saturator".to_string(),
+            template: Some("effect".to_string()),
+            ui_framework: Some(framework.to_string()),
+            components: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            path: String::new(),
+            identity: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            favorite,
+        }
+    }
+
+    #[test]
+    fn test_matches_search() {
+        let project = meta("Tape Deck", &["saturation", "mixing"], "egui", true);
+
+        assert!(matches_search(&project, &ProjectSearchQuery::default()));
+        assert!(matches_search(&project, &ProjectSearchQuery { text: Some("TAPE".into()), ..Default::default() }));
+        assert!(matches_search(&project, &ProjectSearchQuery { text: Some("warm".into()), ..Default::default() }));
+        assert!(matches_search(&project, &ProjectSearchQuery { text: Some("mix".into()), ..Default::default() }));
+        assert!(matches_search(&project, &ProjectSearchQuery {
+            tags: vec!["Saturation".into(), "mixing".into()],
+            ui_framework: Some("egui".into()),
+            favorites_only: true,
+            ..Default::default()
+        }));
+
+        assert!(!matches_search(&project, &ProjectSearchQuery { tags: vec!["drums".into()], ..Default::default() }));
+        assert!(!matches_search(&project, &ProjectSearchQuery { ui_framework: Some("webview".into()), ..Default::default() }));
+        assert!(!matches_search(&project, &ProjectSearchQuery { template: Some("instrument".into()), ..Default::default() }));
+        assert!(!matches_search(
+            &meta("Other", &[], "egui", false),
+            &ProjectSearchQuery { favorites_only: true, ..Default::default() }
+        ));
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = normalize_tags(vec![" Drums ".into(), "drums".into(), "".into(), "Lo-Fi".into()]).unwrap();
+        assert_eq!(tags, vec!["drums", "lo-fi"]);
+        assert!(normalize_tags(vec!["x".repeat(MAX_TAG_LEN + 1)]).is_err());
+    }
+
+    #[test]
+    fn test_declared_param_ids() {
+        let source = "    #[id = \"gain\"]\n    pub gain: FloatParam,\n    #[id = \"mix\"]\n    pub mix: FloatParam,\n    #[persist = \"editor-state\"]\n";
+        assert_eq!(declared_param_ids(source), vec!["gain", "mix"]);
+    }
+}
@@ -6,8 +6,15 @@ use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
 
+use super::activity::{project_dir, record_activity, ActivityKind};
+use super::artifact_manifest::verify_manifest;
+use super::package_signing::{remove_signatures, sign_package, write_checksum, SigningMethod};
 use super::logging::log_message;
+use super::manual::get_manual_dir;
 use super::projects::get_output_path;
+use super::update_manifest::{
+    load_settings as load_update_settings, package_update_file, write_update_manifest, PACKAGE_UPDATE_FILE,
+};
 
 #[derive(Deserialize)]
 pub struct DawPublishTarget {
@@ -31,7 +38,7 @@ pub struct CopiedFile {
 }
 
 /// Expand ~ to home directory
-fn expand_tilde(path: &str) -> PathBuf {
+pub(crate) fn expand_tilde(path: &str) -> PathBuf {
     if path.starts_with("~/") {
         let home = std::env::var("HOME").unwrap_or_default();
         PathBuf::from(home).join(&path[2..])
@@ -139,6 +146,7 @@ pub async fn publish_to_daw(
             "No built plugins found in output folder. Build the project first."
         ));
     }
+    verify_manifest(&output_path)?;
 
     log_message("DEBUG", "publish", &format!("Targets: {:?}", targets.iter().map(|t| (&t.daw, &t.vst3_path, &t.clap_path)).collect::<Vec<_>>()));
 
@@ -241,6 +249,15 @@ pub async fn publish_to_daw(
     }
 
     log_message("INFO", "publish", &format!("Done. Copied: {}, Errors: {}", copied.len(), errors.len()));
+
+    let daws: Vec<&str> = copied.iter().map(|c| c.daw.as_str()).collect();
+    record_activity(
+        &project_dir(&project_name),
+        ActivityKind::Publish,
+        format!("Published v{} to {} location(s)", folder_version, copied.len()),
+        errors.is_empty(),
+        Some(serde_json::json!({ "version": folder_version, "daws": daws, "errors": errors })),
+    );
     Ok(PublishResult {
         success: errors.is_empty() && !copied.is_empty(),
         copied,
@@ -283,49 +300,102 @@ pub struct AvailableFormats {
 #[derive(Serialize)]
 pub struct PackageResult {
     pub success: bool,
+    /// First archive written (the only one unless packaged per format)
     pub zip_path: String,
+    pub zip_paths: Vec<String>,
     pub included: Vec<String>,
+    /// SHA-256 of each archive, in the order of `zip_paths`
+    pub sha256: Vec<String>,
+    /// Signature files written (empty unless signing was requested)
+    pub signatures: Vec<String>,
+    /// Refreshed update manifest to upload (when the project has update settings)
+    pub update_manifest: Option<String>,
 }
 
-/// Package plugin files into a zip archive for distribution
-#[tauri::command]
-pub async fn package_plugins(
-    project_name: String,
-    version: u32,
-    destination: String,
-) -> Result<PackageResult, String> {
-    let base_output_path = get_output_path();
-    let snake_name = project_name.replace('-', "_");
-
-    // Map version 0 (no Claude commits) to v1 for filesystem lookups
-    let folder_version = version.max(1);
+/// How bundles are arranged inside a package
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageLayout {
+    /// Bundles at the root of the archive
+    #[default]
+    Flat,
+    /// `{platform}/{format}/bundle`, e.g. `macOS/VST3/gain.vst3`
+    Structured,
+}
 
-    // Use versioned output folder: output/{project_name}/v{version}/
-    let output_path = base_output_path
-        .join(&project_name)
-        .join(format!("v{}", folder_version));
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageOptions {
+    #[serde(default)]
+    pub layout: PackageLayout,
+    /// One archive per plugin format instead of a single archive
+    #[serde(default)]
+    pub per_format: bool,
+    /// Sign each archive (a .sha256 file is always written)
+    pub signing: Option<SigningMethod>,
+    /// minisign secret key file or GPG key ID; the tool's default when None
+    pub signing_key: Option<String>,
+}
 
-    let vst3_bundle = output_path.join(format!("{}.vst3", snake_name));
-    let clap_bundle = output_path.join(format!("{}.clap", snake_name));
+/// A plugin bundle to package
+struct PackageBundle {
+    /// "VST3", "CLAP"
+    format: &'static str,
+    name: String,
+    path: PathBuf,
+}
 
-    let has_vst3 = vst3_bundle.exists();
-    let has_clap = clap_bundle.exists();
+/// Folder name for the platform the bundles were built on
+pub(crate) fn platform_folder() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macOS"
+    } else if cfg!(windows) {
+        "Windows"
+    } else {
+        "Linux"
+    }
+}
 
-    if !has_vst3 && !has_clap {
-        return Err("No built plugins found. Build the project first.".to_string());
+/// Where a bundle goes inside the archive
+fn bundle_zip_path(layout: PackageLayout, format: &str, name: &str) -> String {
+    match layout {
+        PackageLayout::Flat => name.to_string(),
+        PackageLayout::Structured => format!("{}/{}/{}", platform_folder(), format, name),
     }
+}
 
-    // Create zip file path (use folder_version for accurate naming)
-    let zip_filename = format!("{}_v{}.zip", project_name, folder_version);
-    let zip_path = if destination.ends_with(".zip") {
-        destination.clone()
-    } else {
-        format!("{}/{}", destination, zip_filename)
+/// Archive paths to write: one archive, or one per format named `{stem}_{format}.zip`
+fn package_zip_paths(destination: &str, default_stem: &str, formats: &[&str], per_format: bool) -> Vec<String> {
+    let (dir, stem) = match destination.strip_suffix(".zip") {
+        Some(path) => {
+            let path = std::path::Path::new(path);
+            let stem = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            (path.parent().map(|p| p.to_string_lossy().to_string()).unwrap_or_default(), stem)
+        }
+        None => (destination.to_string(), default_stem.to_string()),
     };
+    let join = |file: String| if dir.is_empty() { file } else { format!("{}/{}", dir, file) };
+    if per_format {
+        formats
+            .iter()
+            .map(|format| join(format!("{}_{}.zip", stem, format.to_lowercase())))
+            .collect()
+    } else {
+        vec![join(format!("{}.zip", stem))]
+    }
+}
 
+/// Write one archive with the given bundles and documents; returns what it contains
+fn write_package(
+    zip_path: &str,
+    bundles: &[&PackageBundle],
+    docs: &[(&str, Vec<u8>)],
+    demos: Option<&std::path::Path>,
+    layout: PackageLayout,
+) -> Result<Vec<String>, String> {
     log_message("INFO", "package", &format!("Creating package at: {}", zip_path));
 
-    let file = File::create(&zip_path)
+    let file = File::create(zip_path)
         .map_err(|e| format!("Failed to create zip file: {}", e))?;
 
     let mut zip = ZipWriter::new(file);
@@ -334,34 +404,187 @@ pub async fn package_plugins(
         .unix_permissions(0o755);
 
     let mut included = Vec::new();
-
-    // Add VST3 bundle if exists
-    if has_vst3 {
-        add_directory_to_zip(&mut zip, &vst3_bundle, &format!("{}.vst3", snake_name), options)?;
-        included.push(format!("{}.vst3", snake_name));
-        log_message("INFO", "package", &format!("Added {}.vst3 to package", snake_name));
+    for bundle in bundles {
+        let path = bundle_zip_path(layout, bundle.format, &bundle.name);
+        add_directory_to_zip(&mut zip, &bundle.path, &path, options)?;
+        log_message("INFO", "package", &format!("Added {} to package", path));
+        included.push(path);
     }
 
-    // Add CLAP bundle if exists
-    if has_clap {
-        add_directory_to_zip(&mut zip, &clap_bundle, &format!("{}.clap", snake_name), options)?;
-        included.push(format!("{}.clap", snake_name));
-        log_message("INFO", "package", &format!("Added {}.clap to package", snake_name));
+    // The generated manual goes at the root whatever the layout
+    for (doc, content) in docs {
+        zip.start_file(*doc, options)
+            .map_err(|e| format!("Failed to add {} to zip: {}", doc, e))?;
+        zip.write_all(content)
+            .map_err(|e| format!("Failed to write {} to zip: {}", doc, e))?;
+        included.push(doc.to_string());
+    }
+    // So do the editor demo clips
+    if let Some(demos) = demos {
+        add_directory_to_zip(&mut zip, demos, "demos", options)?;
+        included.push("demos".to_string());
     }
 
     zip.finish().map_err(|e| format!("Failed to finalize zip: {}", e))?;
 
     log_message("INFO", "package", &format!("Package created successfully: {}", zip_path));
+    Ok(included)
+}
+
+/// Package plugin files into zip archives for distribution
+/// By default everything goes flat into one archive; `options` can arrange the
+/// bundles by platform and format, or write a separate archive per format.
+#[tauri::command]
+pub async fn package_plugins(
+    project_name: String,
+    version: u32,
+    destination: String,
+    options: Option<PackageOptions>,
+) -> Result<PackageResult, String> {
+    let options = options.unwrap_or_default();
+    let base_output_path = get_output_path();
+    let snake_name = project_name.replace('-', "_");
+
+    // Map version 0 (no Claude commits) to v1 for filesystem lookups
+    let folder_version = version.max(1);
+
+    // Use versioned output folder: output/{project_name}/v{version}/
+    let output_path = base_output_path
+        .join(&project_name)
+        .join(format!("v{}", folder_version));
+
+    let bundles: Vec<PackageBundle> = [("VST3", "vst3"), ("CLAP", "clap")]
+        .into_iter()
+        .map(|(format, extension)| PackageBundle {
+            format,
+            name: format!("{}.{}", snake_name, extension),
+            path: output_path.join(format!("{}.{}", snake_name, extension)),
+        })
+        .filter(|b| b.path.exists())
+        .collect();
+
+    if bundles.is_empty() {
+        return Err("No built plugins found. Build the project first.".to_string());
+    }
+    verify_manifest(&output_path)?;
+
+    // Add the generated manual if one exists for this version
+    let manual_dir = get_manual_dir(&project_name, folder_version);
+    let mut docs: Vec<(&str, Vec<u8>)> = ["README.md", "MANUAL.html"]
+        .into_iter()
+        .filter_map(|doc| std::fs::read(manual_dir.join(doc)).ok().map(|content| (doc, content)))
+        .collect();
+    // Tell the packaged build where to look for updates
+    let update_settings = load_update_settings(&project_name);
+    if let Some(settings) = &update_settings {
+        docs.push((PACKAGE_UPDATE_FILE, package_update_file(&project_name, folder_version, settings)));
+    }
+
+    // Demo clips recorded from the plugin editor (see editor_recording.rs)
+    let demos_dir = project_dir(&project_name).join("demos");
+    let demos = demos_dir.is_dir().then_some(demos_dir);
+
+    // Use folder_version for accurate naming
+    let formats: Vec<&str> = bundles.iter().map(|b| b.format).collect();
+    let zip_paths = package_zip_paths(
+        &destination,
+        &format!("{}_v{}", project_name, folder_version),
+        &formats,
+        options.per_format,
+    );
+    // Compressing, hashing, and signing are all blocking work
+    let archive_paths = zip_paths.clone();
+    let (included, sha256, signatures) = tokio::task::spawn_blocking(move || {
+        let zip_paths = archive_paths;
+        let groups: Vec<Vec<&PackageBundle>> = if options.per_format {
+            bundles.iter().map(|b| vec![b]).collect()
+        } else {
+            vec![bundles.iter().collect()]
+        };
+
+        // Per-format archives are compressed in parallel
+        let (docs, demos, layout) = (&docs, demos.as_deref(), options.layout);
+        let results: Vec<Result<Vec<String>, String>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = zip_paths
+                .iter()
+                .zip(&groups)
+                .map(|(zip_path, group)| {
+                    scope.spawn(move || write_package(zip_path, group, docs, demos, layout))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|_| Err("Packaging thread panicked".to_string())))
+                .collect()
+        });
+        let mut included = Vec::new();
+        for result in results {
+            for item in result? {
+                if !included.contains(&item) {
+                    included.push(item);
+                }
+            }
+        }
+
+        // Checksums (and signatures) next to each archive, for publishing with a release
+        let mut sha256 = Vec::new();
+        let mut signatures = Vec::new();
+        for zip_path in &zip_paths {
+            let zip_path = std::path::Path::new(zip_path);
+            remove_signatures(zip_path)?;
+            sha256.push(write_checksum(zip_path)?);
+            if let Some(method) = options.signing {
+                let signature = sign_package(zip_path, method, options.signing_key.as_deref())?;
+                signatures.push(signature.to_string_lossy().to_string());
+            }
+        }
+        Ok::<_, String>((included, sha256, signatures))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    let update_manifest = match &update_settings {
+        Some(settings) => {
+            let zips: Vec<(&std::path::Path, Option<String>)> = zip_paths
+                .iter()
+                .zip(&sha256)
+                .map(|(zip_path, sha256)| (std::path::Path::new(zip_path), Some(sha256.clone())))
+                .collect();
+            let path = write_update_manifest(&project_name, folder_version, settings, &zips)?;
+            Some(path.to_string_lossy().to_string())
+        }
+        None => None,
+    };
+
+    // Packaged builds get distributed - lock in their plugin IDs
+    super::plugin_ids::mark_released(&project_name);
+
+    record_activity(
+        &project_dir(&project_name),
+        ActivityKind::Package,
+        format!("Packaged v{}", folder_version),
+        true,
+        Some(serde_json::json!({
+            "version": folder_version,
+            "zipPath": zip_paths[0],
+            "zipPaths": zip_paths,
+            "included": included,
+        })),
+    );
 
     Ok(PackageResult {
         success: true,
-        zip_path,
+        zip_path: zip_paths[0].clone(),
+        zip_paths,
         included,
+        sha256,
+        signatures,
+        update_manifest,
     })
 }
 
 /// Add a directory recursively to a zip archive
-fn add_directory_to_zip(
+pub(crate) fn add_directory_to_zip(
     zip: &mut ZipWriter<File>,
     source: &std::path::Path,
     prefix: &str,
@@ -403,3 +626,22 @@ fn add_directory_to_zip(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_zip_paths() {
+        assert_eq!(package_zip_paths("/out", "gain_v3", &["VST3", "CLAP"], false), vec!["/out/gain_v3.zip"]);
+        assert_eq!(
+            package_zip_paths("/out/release.zip", "gain_v3", &["VST3", "CLAP"], true),
+            vec!["/out/release_vst3.zip", "/out/release_clap.zip"]
+        );
+        assert_eq!(bundle_zip_path(PackageLayout::Flat, "VST3", "gain.vst3"), "gain.vst3");
+        assert_eq!(
+            bundle_zip_path(PackageLayout::Structured, "CLAP", "gain.clap"),
+            format!("{}/CLAP/gain.clap", platform_folder())
+        );
+    }
+}
@@ -0,0 +1,483 @@
+//! DSP recipe scaffolding
+//!
+//! A small library of common building blocks (filters, delays, dynamics). Each
+//! recipe generates a nih-plug-ready skeleton in `src/dsp/` - the struct and
+//! its state, `new`/`reset`, a `process` function that takes the parameters as
+//! arguments, and `FloatParam` stubs to paste into the plugin's Params - and
+//! lists it in CLAUDE.md, so Claude fills in a known shape instead of starting
+//! from a prose description.
+//!
+//! Recipes carry a difficulty, a rough effort estimate, and the starter
+//! components they lean on, so the list can be narrowed to what a beginner (or
+//! a given project) is ready for.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::activity::project_dir;
+use super::claude_md::add_scaffolded_module_to_claude_md;
+use super::projects::ProjectMeta;
+
+struct RecipeParam {
+    id: &'static str,
+    name: &'static str,
+    default: f32,
+    min: f32,
+    max: f32,
+    unit: &'static str,
+    /// Skewed range for frequencies and times
+    skewed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+struct Recipe {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    difficulty: Difficulty,
+    /// Rough time to finish the TODO, e.g. "1-2 hours"
+    effort: &'static str,
+    /// Starter components (as in project metadata) the recipe builds on
+    requires: &'static [&'static str],
+    module: &'static str,
+    struct_name: &'static str,
+    params: &'static [RecipeParam],
+    /// (field, type, reset value)
+    state: &'static [(&'static str, &'static str, &'static str)],
+    /// Implementation notes written into `process()`
+    todo: &'static str,
+}
+
+const fn param(id: &'static str, name: &'static str, default: f32, min: f32, max: f32, unit: &'static str, skewed: bool) -> RecipeParam {
+    RecipeParam {
+        id,
+        name,
+        default,
+        min,
+        max,
+        unit,
+        skewed,
+    }
+}
+
+const RECIPES: &[Recipe] = &[
+    Recipe {
+        id: "moog-ladder-filter",
+        name: "Moog ladder filter",
+        description: "4-pole (24 dB/oct) resonant lowpass with the classic ladder character",
+        difficulty: Difficulty::Advanced,
+        effort: "2-3 hours",
+        requires: &["oversampling", "param_smoothing"],
+        module: "moog_ladder",
+        struct_name: "MoogLadder",
+        params: &[
+            param("cutoff", "Cutoff", 1000.0, 20.0, 20000.0, " Hz", true),
+            param("resonance", "Resonance", 0.2, 0.0, 1.0, "", false),
+        ],
+        state: &[("stage", "[f32; 4]", "[0.0; 4]")],
+        todo: "Four one-pole stages in series (zero-delay feedback or Huovilainen), tanh() on each stage input \
+               for the ladder saturation, feedback of resonance * 4.0 from stage[3] (self-oscillates near 1.0), \
+               and some input gain compensation for the passband drop at high resonance.",
+    },
+    Recipe {
+        id: "state-variable-filter",
+        name: "State variable filter",
+        description: "2-pole TPT/SVF with simultaneous lowpass, bandpass, and highpass outputs",
+        difficulty: Difficulty::Intermediate,
+        effort: "1 hour",
+        requires: &["param_smoothing"],
+        module: "state_variable_filter",
+        struct_name: "StateVariableFilter",
+        params: &[
+            param("cutoff", "Cutoff", 1000.0, 20.0, 20000.0, " Hz", true),
+            param("q", "Q", 0.707, 0.1, 10.0, "", true),
+        ],
+        state: &[("ic1eq", "f32", "0.0"), ("ic2eq", "f32", "0.0")],
+        todo: "Cytomic (Andrew Simper) trapezoidal SVF: g = tan(PI * cutoff / sample_rate), k = 1 / q, \
+               a1 = 1 / (1 + g * (g + k)), then update ic1eq/ic2eq. Clamp cutoff below Nyquist before tan(). \
+               Return the lowpass output, or add a mode argument for band/highpass.",
+    },
+    Recipe {
+        id: "biquad-eq",
+        name: "Peaking EQ band",
+        description: "RBJ cookbook biquad bell filter",
+        difficulty: Difficulty::Beginner,
+        effort: "30 minutes",
+        requires: &[],
+        module: "biquad_eq",
+        struct_name: "BiquadEq",
+        params: &[
+            param("frequency", "Frequency", 1000.0, 20.0, 20000.0, " Hz", true),
+            param("gain", "Gain", 0.0, -24.0, 24.0, " dB", false),
+            param("q", "Q", 1.0, 0.1, 10.0, "", true),
+        ],
+        state: &[
+            ("coefficients", "[f32; 5]", "[1.0, 0.0, 0.0, 0.0, 0.0]"),
+            ("z1", "f32", "0.0"),
+            ("z2", "f32", "0.0"),
+        ],
+        todo: "Recompute the RBJ peaking coefficients (b0 b1 b2 a1 a2, normalized by a0) only when the parameters \
+               change - cache the last values - and run transposed direct form II with z1/z2.",
+    },
+    Recipe {
+        id: "delay-line",
+        name: "Feedback delay",
+        description: "Delay line with feedback and dry/wet mix",
+        difficulty: Difficulty::Beginner,
+        effort: "30-60 minutes",
+        requires: &["param_smoothing"],
+        module: "delay_line",
+        struct_name: "DelayLine",
+        params: &[
+            param("time", "Time", 250.0, 1.0, 2000.0, " ms", true),
+            param("feedback", "Feedback", 0.4, 0.0, 0.95, "", false),
+            param("mix", "Mix", 0.3, 0.0, 1.0, "", false),
+        ],
+        state: &[("buffer", "Vec<f32>", "vec![0.0; (sample_rate * 2.0) as usize + 1]"), ("write_pos", "usize", "0")],
+        todo: "The buffer holds the 2 s maximum and is allocated in new() - never resize it here. Read \
+               time * sample_rate / 1000 samples behind write_pos with linear interpolation, write \
+               input + delayed * feedback, wrap write_pos, and crossfade dry/wet with mix. Smooth `time` to \
+               avoid zipper noise.",
+    },
+    Recipe {
+        id: "compressor",
+        name: "Feed-forward compressor",
+        description: "Peak compressor with attack/release envelope",
+        difficulty: Difficulty::Intermediate,
+        effort: "1-2 hours",
+        requires: &[],
+        module: "compressor",
+        struct_name: "Compressor",
+        params: &[
+            param("threshold", "Threshold", -18.0, -60.0, 0.0, " dB", false),
+            param("ratio", "Ratio", 4.0, 1.0, 20.0, ":1", true),
+            param("attack", "Attack", 10.0, 0.1, 200.0, " ms", true),
+            param("release", "Release", 100.0, 5.0, 2000.0, " ms", true),
+        ],
+        state: &[("envelope_db", "f32", "-120.0")],
+        todo: "Level in dB from the input (use a stereo-linked max for two channels), one-pole envelope with \
+               separate attack/release coefficients exp(-1 / (ms * 0.001 * sample_rate)), gain reduction \
+               (envelope - threshold) * (1 - 1 / ratio) above the threshold (add a soft knee if wanted), \
+               then convert back to linear gain.",
+    },
+    Recipe {
+        id: "tape-saturation",
+        name: "Tape saturation",
+        description: "Soft asymmetric saturation with a DC blocker",
+        difficulty: Difficulty::Intermediate,
+        effort: "1 hour",
+        requires: &["oversampling"],
+        module: "tape_saturation",
+        struct_name: "TapeSaturation",
+        params: &[
+            param("drive", "Drive", 6.0, 0.0, 36.0, " dB", false),
+            param("bias", "Bias", 0.1, 0.0, 0.5, "", false),
+        ],
+        state: &[("dc_x1", "f32", "0.0"), ("dc_y1", "f32", "0.0")],
+        todo: "Apply drive, add bias for even harmonics, shape with tanh() (or a cubic soft clip), compensate \
+               the output level, then remove the DC the bias introduces: y = x - dc_x1 + 0.995 * dc_y1. Run \
+               this under /oversampling to keep aliasing down.",
+    },
+];
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RecipeInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub difficulty: Difficulty,
+    pub effort: String,
+    pub requires: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ScaffoldResult {
+    /// Generated file, relative to the project
+    pub file: String,
+    pub struct_name: String,
+    /// Commit of the scaffold (None if git had nothing to commit)
+    pub commit_hash: Option<String>,
+}
+
+fn find_recipe(id: &str) -> Option<&'static Recipe> {
+    let id = id.trim().trim_start_matches('/');
+    RECIPES.iter().find(|r| r.id == id)
+}
+
+fn float_literal(value: f32) -> String {
+    let text = value.to_string();
+    if text.contains('.') {
+        text
+    } else {
+        format!("{}.0", text)
+    }
+}
+
+fn param_stub(p: &RecipeParam) -> String {
+    let range = if p.skewed {
+        format!(
+            "FloatRange::Skewed {{ min: {}, max: {}, factor: FloatRange::skew_factor(-2.0) }}",
+            float_literal(p.min),
+            float_literal(p.max)
+        )
+    } else {
+        format!(
+            "FloatRange::Linear {{ min: {}, max: {} }}",
+            float_literal(p.min),
+            float_literal(p.max)
+        )
+    };
+    let unit = if p.unit.is_empty() {
+        String::new()
+    } else {
+        format!("\n//     .with_unit(\"{}\")", p.unit)
+    };
+    format!(
+        "// #[id = \"{id}\"]\n// pub {id}: FloatParam,\n//\n// {id}: FloatParam::new(\"{name}\", {default}, {range})\n//     .with_smoother(SmoothingStyle::Linear(20.0)){unit},\n",
+        id = p.id,
+        name = p.name,
+        default = float_literal(p.default),
+        range = range,
+        unit = unit
+    )
+}
+
+/// Rust source for a recipe's skeleton module
+fn generate_module(recipe: &Recipe) -> String {
+    let mut out = format!(
+        "//! {} - scaffolded from /{}\n//!\n//! {}.\n//! Skeleton only: `process()` passes audio through until the TODO is implemented.\n\n",
+        recipe.name, recipe.id, recipe.description
+    );
+
+    out.push_str("// Parameters - add to the plugin's Params struct and Default impl,\n");
+    out.push_str("// then pass the smoothed values into process():\n//\n");
+    for p in recipe.params {
+        out.push_str(&param_stub(p));
+        out.push_str("//\n");
+    }
+    out.push('\n');
+
+    out.push_str(&format!("pub struct {} {{\n    sample_rate: f32,\n", recipe.struct_name));
+    for (field, ty, _) in recipe.state {
+        out.push_str(&format!("    {}: {},\n", field, ty));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str(&format!("impl {} {{\n", recipe.struct_name));
+    out.push_str("    /// Allocate everything here - never in process()\n");
+    out.push_str("    pub fn new(sample_rate: f32) -> Self {\n        Self {\n            sample_rate,\n");
+    for (field, _, reset) in recipe.state {
+        out.push_str(&format!("            {}: {},\n", field, reset));
+    }
+    out.push_str("        }\n    }\n\n");
+
+    out.push_str("    /// Clear the state (call from the plugin's reset())\n    pub fn reset(&mut self) {\n");
+    for (field, ty, reset) in recipe.state {
+        if ty.starts_with("Vec<") {
+            out.push_str(&format!("        self.{}.fill(0.0);\n", field));
+        } else {
+            out.push_str(&format!("        self.{} = {};\n", field, reset));
+        }
+    }
+    out.push_str("    }\n\n");
+
+    let mut args = vec!["input: f32".to_string()];
+    args.extend(recipe.params.iter().map(|p| format!("{}: f32", p.id)));
+    out.push_str(&format!("    #[inline]\n    pub fn process(&mut self, {}) -> f32 {{\n", args.join(", ")));
+    out.push_str("        // TODO: ");
+    let mut line_len = 0;
+    for word in recipe.todo.split_whitespace() {
+        if line_len > 0 && line_len + word.len() > 90 {
+            out.push_str("\n        // ");
+            line_len = 0;
+        } else if line_len > 0 {
+            out.push(' ');
+            line_len += 1;
+        }
+        out.push_str(word);
+        line_len += word.len();
+    }
+    out.push('\n');
+    let unused: Vec<&str> = recipe.params.iter().map(|p| p.id).collect();
+    out.push_str(&format!("        let _ = ({}, self.sample_rate);\n", unused.join(", ")));
+    out.push_str("        input\n    }\n}\n");
+    out
+}
+
+/// Add `mod dsp;` to the crate root after its `use` lines (None if it's already declared)
+fn add_dsp_mod(lib_rs: &str) -> Option<String> {
+    if lib_rs.lines().any(|l| matches!(l.trim(), "mod dsp;" | "pub mod dsp;")) {
+        return None;
+    }
+    let mut lines: Vec<&str> = lib_rs.lines().collect();
+    let insert_at = lines
+        .iter()
+        .rposition(|l| l.starts_with("use ") || l.starts_with("mod ") || l.starts_with("pub mod "))
+        .map_or(0, |i| i + 1);
+    lines.insert(insert_at, "mod dsp;");
+    Some(lines.join("\n") + "\n")
+}
+
+/// Recipes up to `max_difficulty` whose required components are all in
+/// `components` (when given), easiest first
+fn filter_recipes(max_difficulty: Option<Difficulty>, components: Option<&[String]>) -> Vec<&'static Recipe> {
+    let mut recipes: Vec<&Recipe> = RECIPES
+        .iter()
+        .filter(|r| max_difficulty.map_or(true, |max| r.difficulty <= max))
+        .filter(|r| components.map_or(true, |c| r.requires.iter().all(|req| c.iter().any(|have| have == req))))
+        .collect();
+    recipes.sort_by_key(|r| r.difficulty);
+    recipes
+}
+
+/// List the recipes that can be scaffolded, easiest first
+/// `max_difficulty` hides harder recipes; `project_name` hides recipes that need
+/// components the project doesn't have.
+#[tauri::command]
+pub async fn list_recipes(max_difficulty: Option<Difficulty>, project_name: Option<String>) -> Result<Vec<RecipeInfo>, String> {
+    let components = match project_name {
+        Some(name) => {
+            let metadata_path = project_dir(&name).join(".vstworkshop/metadata.json");
+            let meta: ProjectMeta = fs::read_to_string(&metadata_path)
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+                .ok_or_else(|| format!("Project '{}' not found", name))?;
+            Some(meta.components.unwrap_or_default())
+        }
+        None => None,
+    };
+    Ok(filter_recipes(max_difficulty, components.as_deref())
+        .into_iter()
+        .map(|r| RecipeInfo {
+            id: format!("/{}", r.id),
+            name: r.name.to_string(),
+            description: r.description.to_string(),
+            difficulty: r.difficulty,
+            effort: r.effort.to_string(),
+            requires: r.requires.iter().map(|c| c.to_string()).collect(),
+        })
+        .collect())
+}
+
+/// Generate a recipe's skeleton module in a project and register it in CLAUDE.md
+#[tauri::command]
+pub async fn scaffold_recipe(project_name: String, recipe_id: String) -> Result<ScaffoldResult, String> {
+    let recipe = find_recipe(&recipe_id).ok_or_else(|| format!("Unknown recipe '{}'", recipe_id))?;
+    let project_path = project_dir(&project_name);
+    let src = project_path.join("src");
+    if !src.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+
+    let dsp_dir = src.join("dsp");
+    let module_path = dsp_dir.join(format!("{}.rs", recipe.module));
+    let file = format!("src/dsp/{}.rs", recipe.module);
+    if module_path.exists() {
+        return Err(format!("{} already exists", file));
+    }
+
+    fs::create_dir_all(&dsp_dir).map_err(|e| format!("Failed to create src/dsp: {}", e))?;
+    fs::write(&module_path, generate_module(recipe)).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+
+    let mod_rs = dsp_dir.join("mod.rs");
+    let mut modules = fs::read_to_string(&mod_rs).unwrap_or_default();
+    if !modules.is_empty() && !modules.ends_with('\n') {
+        modules.push('\n');
+    }
+    modules.push_str(&format!("pub mod {};\n", recipe.module));
+    fs::write(&mod_rs, modules).map_err(|e| format!("Failed to update src/dsp/mod.rs: {}", e))?;
+
+    let lib_rs = src.join("lib.rs");
+    if let Some(updated) = fs::read_to_string(&lib_rs).ok().and_then(|content| add_dsp_mod(&content)) {
+        fs::write(&lib_rs, updated).map_err(|e| format!("Failed to update src/lib.rs: {}", e))?;
+    }
+
+    register_in_claude_md(&project_path, recipe, &file);
+
+    let commit_hash = match super::git::commit_changes(
+        &project_path.to_string_lossy(),
+        &format!("Scaffold {} from /{}", recipe.struct_name, recipe.id),
+    )
+    .await
+    {
+        Ok(hash) => Some(hash),
+        Err(e) if e == "no_changes" => None,
+        Err(e) => return Err(e),
+    };
+
+    Ok(ScaffoldResult {
+        file,
+        struct_name: recipe.struct_name.to_string(),
+        commit_hash,
+    })
+}
+
+fn register_in_claude_md(project_path: &Path, recipe: &Recipe, file: &str) {
+    let path = project_path.join("CLAUDE.md");
+    let Ok(content) = fs::read_to_string(&path) else {
+        return;
+    };
+    let updated = add_scaffolded_module_to_claude_md(&content, recipe.id, file, recipe.struct_name);
+    if updated != content {
+        if let Err(e) = fs::write(&path, updated) {
+            log::warn!("Failed to list scaffolded module in {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_module() {
+        let recipe = find_recipe("/moog-ladder-filter").unwrap();
+        let code = generate_module(recipe);
+
+        assert!(code.starts_with("//! Moog ladder filter - scaffolded from /moog-ladder-filter\n"));
+        assert!(code.contains("pub struct MoogLadder {\n    sample_rate: f32,\n    stage: [f32; 4],\n}"));
+        assert!(code.contains("pub fn process(&mut self, input: f32, cutoff: f32, resonance: f32) -> f32 {"));
+        assert!(code.contains(
+            "// cutoff: FloatParam::new(\"Cutoff\", 1000.0, FloatRange::Skewed { min: 20.0, max: 20000.0, factor: FloatRange::skew_factor(-2.0) })"
+        ));
+        assert!(code.contains("self.stage = [0.0; 4];"));
+
+        // Heap state is cleared in place rather than reallocated
+        let delay = generate_module(find_recipe("delay-line").unwrap());
+        assert!(delay.contains("self.buffer.fill(0.0);"));
+        assert!(find_recipe("unknown").is_none());
+    }
+
+    #[test]
+    fn test_add_dsp_mod() {
+        let lib = "use nih_plug::prelude::*;\nuse std::sync::Arc;\n\nmod editor;\n\nstruct Plugin;\n";
+        assert_eq!(
+            add_dsp_mod(lib).unwrap(),
+            "use nih_plug::prelude::*;\nuse std::sync::Arc;\n\nmod editor;\nmod dsp;\n\nstruct Plugin;\n"
+        );
+        assert!(add_dsp_mod("mod dsp;\nuse nih_plug::prelude::*;\n").is_none());
+    }
+
+    #[test]
+    fn test_filter_recipes() {
+        let ids = |recipes: Vec<&Recipe>| recipes.iter().map(|r| r.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(filter_recipes(Some(Difficulty::Beginner), None)), vec!["biquad-eq", "delay-line"]);
+        let all = filter_recipes(None, None);
+        assert_eq!(all.len(), RECIPES.len());
+        assert_eq!(all.last().unwrap().difficulty, Difficulty::Advanced);
+
+        // A bare project only gets recipes that need no components
+        assert_eq!(ids(filter_recipes(None, Some(&[]))), vec!["biquad-eq", "compressor"]);
+        let smoothing = vec!["param_smoothing".to_string()];
+        assert!(ids(filter_recipes(None, Some(&smoothing))).contains(&"state-variable-filter"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Reference matching ("make it sound like this file")
+//!
+//! Renders input through a plugin build offline, extracts the same tonal and
+//! dynamic features from that output and from a user's reference file, and
+//! reports the gap as numbers plus a markdown summary the chat can send to
+//! Claude as concrete targets.
+
+use serde::Serialize;
+
+use super::analysis_jobs::run_analysis_job;
+use super::distortion::{hosted_plugin_path, resolve_plugin_bundle};
+use crate::audio::engine::{get_engine_handle, InputSource};
+use crate::audio::features::{diff_features, extract_features, AudioFeatures, FeatureDiff};
+use crate::audio::offline::render_with_state;
+use crate::audio::samples::AudioSample;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ReferenceAnalysis {
+    pub reference_name: String,
+    /// Name of the material rendered through the plugin
+    pub input_name: String,
+    /// True when the render used the preview's current settings, false for the plugin's defaults
+    pub preview_settings: bool,
+    pub reference: AudioFeatures,
+    pub plugin: AudioFeatures,
+    pub diff: FeatureDiff,
+    /// Markdown summary for the chat
+    pub summary: String,
+}
+
+fn summarize(
+    reference_name: &str,
+    input_name: &str,
+    preview_settings: bool,
+    reference: &AudioFeatures,
+    plugin: &AudioFeatures,
+    diff: &FeatureDiff,
+) -> String {
+    let mut out = format!(
+        "## Reference match: {}\n\nPlugin output ({} settings, fed {}) compared with the reference:\n\n\
+         | | Reference | Plugin |\n|---|---|---|\n",
+        reference_name,
+        if preview_settings { "current" } else { "default" },
+        input_name
+    );
+    let rows = [
+        ("Spectral tilt", reference.spectral_tilt_db_per_octave, plugin.spectral_tilt_db_per_octave, "dB/oct"),
+        ("Spectral centroid", reference.spectral_centroid_hz, plugin.spectral_centroid_hz, "Hz"),
+        ("Crest factor", reference.crest_factor_db, plugin.crest_factor_db, "dB"),
+        ("Dynamic range", reference.dynamic_range_db, plugin.dynamic_range_db, "dB"),
+        ("Stereo width (side/mid)", reference.stereo_width_db, plugin.stereo_width_db, "dB"),
+    ];
+    for (name, r, p, unit) in rows {
+        out.push_str(&format!("| {} | {:.1} {} | {:.1} {} |\n", name, r, unit, p, unit));
+    }
+
+    if diff.targets.is_empty() {
+        out.push_str("\nThe plugin's output is already close to the reference.\n");
+    } else {
+        out.push_str("\nTargets:\n");
+        for target in &diff.targets {
+            out.push_str(&format!("- {}\n", target));
+        }
+    }
+    out
+}
+
+/// Dry material for the render: the given file, or the sample playing in the preview
+fn resolve_input_path(input_path: Option<String>) -> Result<String, String> {
+    if let Some(path) = input_path {
+        return Ok(path);
+    }
+    match get_engine_handle().map(|h| h.get_input_source()) {
+        Some(InputSource::Sample { path }) => Ok(path),
+        _ => Err("Choose the dry audio to run through the plugin, or load it as the preview's input sample".to_string()),
+    }
+}
+
+/// Compare a plugin's output with a reference audio file
+///
+/// Uses the given project version's build, or the plugin in the preview when
+/// no project is given. When that's the plugin the preview is hosting, its
+/// current settings are applied first, so the comparison matches what the
+/// user hears. `input_path` is the dry material to run through the plugin and
+/// defaults to the preview's input sample. Runs as an analysis job.
+#[tauri::command]
+pub async fn analyze_reference(
+    reference_path: String,
+    project_name: Option<String>,
+    version: Option<u32>,
+    input_path: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<ReferenceAnalysis, String> {
+    let bundle = resolve_plugin_bundle(project_name, version)?;
+    let input_path = resolve_input_path(input_path)?;
+
+    // Another build's state may not load into this one, so only the hosted plugin's is used
+    let state = match (hosted_plugin_path(), get_engine_handle()) {
+        (Ok(hosted), Some(handle)) if hosted == bundle => Some(handle.save_plugin_state()?),
+        _ => None,
+    };
+
+    run_analysis_job("analyze_reference", Some(app_handle), move |job| {
+        let reference = AudioSample::load(&reference_path)?;
+        let input = AudioSample::load(&input_path)?;
+        let sample_rate = input.info.sample_rate;
+
+        log::info!("Matching {:?} against reference {}", bundle, reference.info.name);
+        let output = render_with_state(&bundle, state.as_deref(), &input.data, sample_rate, job.render_progress(0, 1))?;
+
+        let (reference_features, plugin_features) = rayon::join(
+            || extract_features(&reference.data, reference.info.sample_rate),
+            || extract_features(&output, sample_rate),
+        );
+        let diff = diff_features(&reference_features, &plugin_features);
+        let preview_settings = state.is_some();
+
+        Ok(ReferenceAnalysis {
+            summary: summarize(
+                &reference.info.name,
+                &input.info.name,
+                preview_settings,
+                &reference_features,
+                &plugin_features,
+                &diff,
+            ),
+            reference_name: reference.info.name,
+            input_name: input.info.name,
+            preview_settings,
+            reference: reference_features,
+            plugin: plugin_features,
+            diff,
+        })
+    })
+    .await
+}
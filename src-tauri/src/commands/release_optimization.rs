@@ -0,0 +1,230 @@
+//! Release optimization options
+//!
+//! Per-project settings in `.vstworkshop/release-optimization.json` for making
+//! release bundles smaller: LTO and opt-level overrides passed to cargo, then
+//! after bundling, stripping symbols from the plugin binaries (the debug symbols
+//! are already archived in `symbols/`) and, on Windows, compressing them with UPX. The size
+//! report records the bundle sizes before and after.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::activity::project_dir;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseOptimization {
+    /// Strip symbols from the plugin binaries after bundling
+    #[serde(default)]
+    pub strip_symbols: bool,
+    /// Cargo `lto` override: "off", "thin", or "fat"
+    #[serde(default)]
+    pub lto: Option<String>,
+    /// Cargo `opt-level` override: "0"-"3", "s", or "z"
+    #[serde(default)]
+    pub opt_level: Option<String>,
+    /// Compress Windows binaries with UPX (must be on PATH)
+    #[serde(default)]
+    pub upx: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimizationSummary {
+    /// What was done, e.g. "Stripped gain.clap"
+    pub steps: Vec<String>,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl ReleaseOptimization {
+    /// Whether anything happens after bundling
+    pub(crate) fn post_build(&self) -> bool {
+        self.strip_symbols || (self.upx && cfg!(windows))
+    }
+
+    /// `CARGO_PROFILE_RELEASE_*` overrides for the build
+    pub(crate) fn cargo_env(&self) -> Vec<(&'static str, String)> {
+        let mut env = Vec::new();
+        if let Some(lto) = &self.lto {
+            env.push(("CARGO_PROFILE_RELEASE_LTO", lto.clone()));
+        }
+        if let Some(level) = &self.opt_level {
+            env.push(("CARGO_PROFILE_RELEASE_OPT_LEVEL", level.clone()));
+        }
+        env
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(lto) = &self.lto {
+            if !["off", "thin", "fat"].contains(&lto.as_str()) {
+                return Err(format!("LTO must be off, thin, or fat (got {})", lto));
+            }
+        }
+        if let Some(level) = &self.opt_level {
+            if !["0", "1", "2", "3", "s", "z"].contains(&level.as_str()) {
+                return Err(format!("Opt level must be 0-3, s, or z (got {})", level));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn get_settings_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("release-optimization.json")
+}
+
+/// A project's release optimization settings (all off when it has none)
+pub(crate) fn load_optimization(project_path: &Path) -> ReleaseOptimization {
+    fs::read_to_string(get_settings_path(project_path))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// The plugin binaries in an output folder: the `.clap`/`.vst3` files themselves
+/// on Linux and Windows, `Contents/MacOS/*` (or `Contents/x86_64-win/*.vst3`) inside bundle folders
+fn plugin_binaries(output_path: &Path) -> Vec<PathBuf> {
+    let mut binaries = Vec::new();
+    for entry in fs::read_dir(output_path).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if !matches!(path.extension().and_then(|e| e.to_str()), Some("clap" | "vst3")) {
+            continue;
+        }
+        if path.is_file() {
+            binaries.push(path);
+            continue;
+        }
+        binaries.extend(
+            walkdir::WalkDir::new(path.join("Contents"))
+                .min_depth(2)
+                .max_depth(2)
+                .into_iter()
+                .flatten()
+                .filter(|e| e.file_type().is_file())
+                .filter(|e| {
+                    let dir = e.path().parent().and_then(|p| p.file_name());
+                    dir.is_some_and(|dir| dir != "Resources" && dir != "_CodeSignature")
+                })
+                .map(|e| e.into_path()),
+        );
+    }
+    binaries.sort();
+    binaries
+}
+
+fn run_tool(program: &str, args: &[&str], file: &Path) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .arg(file)
+        .env("PATH", super::get_extended_path())
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} failed: {}", program, String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// The bundle folder (or file) a binary belongs to, for naming it in the summary
+fn bundle_name(output_path: &Path, binary: &Path) -> String {
+    binary
+        .strip_prefix(output_path)
+        .ok()
+        .and_then(|p| p.components().next())
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .unwrap_or_else(|| binary.to_string_lossy().to_string())
+}
+
+fn total_size(files: &[PathBuf]) -> u64 {
+    files.iter().filter_map(|f| fs::metadata(f).ok()).map(|m| m.len()).sum()
+}
+
+/// Strip and compress the plugin binaries of a release output folder
+/// Individual failures are reported in the steps rather than failing the build.
+pub(crate) fn optimize_bundles(output_path: &Path, options: &ReleaseOptimization) -> OptimizationSummary {
+    let binaries = plugin_binaries(output_path);
+    let bytes_before = total_size(&binaries);
+    let mut steps = Vec::new();
+
+    for binary in &binaries {
+        let name = bundle_name(output_path, binary);
+        if options.strip_symbols && !cfg!(windows) {
+            // -x keeps the exported entry points the host looks up
+            let args: &[&str] = if cfg!(target_os = "macos") { &["-x"] } else { &["--strip-unneeded"] };
+            steps.push(match run_tool("strip", args, binary) {
+                Ok(()) => format!("Stripped {}", name),
+                Err(e) => format!("Couldn't strip {}: {}", name, e),
+            });
+        }
+        if options.upx && cfg!(windows) {
+            steps.push(match run_tool("upx", &["--best", "-q"], binary) {
+                Ok(()) => format!("Compressed {} with UPX", name),
+                Err(e) => format!("Couldn't compress {}: {}", name, e),
+            });
+        }
+    }
+
+    // Stripping invalidates the ad-hoc signature Apple Silicon requires
+    if options.strip_symbols && cfg!(target_os = "macos") {
+        let mut bundles: Vec<String> = binaries.iter().map(|b| bundle_name(output_path, b)).collect();
+        bundles.dedup();
+        for bundle in bundles {
+            if let Err(e) = run_tool("codesign", &["--force", "--sign", "-"], &output_path.join(&bundle)) {
+                steps.push(format!("Couldn't re-sign {}: {}", bundle, e));
+            }
+        }
+    }
+
+    OptimizationSummary {
+        steps,
+        bytes_before,
+        bytes_after: total_size(&binaries),
+    }
+}
+
+#[tauri::command]
+pub async fn get_release_optimization(project_name: String) -> Result<ReleaseOptimization, String> {
+    Ok(load_optimization(&project_dir(&project_name)))
+}
+
+#[tauri::command]
+pub async fn save_release_optimization(project_name: String, options: ReleaseOptimization) -> Result<(), String> {
+    options.validate()?;
+    let path = get_settings_path(&project_dir(&project_name));
+    let json = serde_json::to_string_pretty(&options)
+        .map_err(|e| format!("Failed to serialize release optimization: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to save release optimization: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_env() {
+        let options = ReleaseOptimization {
+            lto: Some("fat".to_string()),
+            opt_level: Some("z".to_string()),
+            ..Default::default()
+        };
+        assert!(options.validate().is_ok());
+        assert_eq!(
+            options.cargo_env(),
+            vec![
+                ("CARGO_PROFILE_RELEASE_LTO", "fat".to_string()),
+                ("CARGO_PROFILE_RELEASE_OPT_LEVEL", "z".to_string()),
+            ]
+        );
+        assert!(!options.post_build());
+
+        let bad = ReleaseOptimization {
+            opt_level: Some("4".to_string()),
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! Sample library for preview audio material
+//!
+//! Keeps a tagged collection of test audio (drums, vocals, guitar DI, full mixes)
+//! in the workspace so users don't have to browse for a file every session.
+//! Imported files are decoded, loudness-normalized, and stored as float WAVs.
+//!
+//! Layout:
+//!   ~/VSTWorkshop/samples/library.json   - index of all entries
+//!   ~/VSTWorkshop/samples/files/{id}.wav - normalized audio
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::projects::get_workspace_path;
+use crate::audio::samples::AudioSample;
+
+/// Target RMS level for normalized samples (dBFS)
+const TARGET_RMS_DB: f32 = -18.0;
+
+/// Peaks are never pushed above this level during normalization (dBFS)
+const PEAK_CEILING_DB: f32 = -1.0;
+
+/// Supported source file extensions (matches the enabled symphonia codecs)
+const SUPPORTED_EXTENSIONS: &[&str] = &["wav", "mp3", "aac"];
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LibrarySample {
+    pub id: String,
+    pub name: String,
+    /// "drums", "vocals", "guitar_di", "bass", "full_mix", "other"
+    pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub path: String,
+    #[serde(rename = "originalPath")]
+    pub original_path: String,
+    #[serde(rename = "sampleRate")]
+    pub sample_rate: u32,
+    #[serde(rename = "durationSecs")]
+    pub duration_secs: f32,
+    /// RMS level of the source before normalization (dBFS)
+    #[serde(rename = "sourceRmsDb")]
+    pub source_rms_db: f32,
+    /// Gain applied on import (dB)
+    #[serde(rename = "gainDb")]
+    pub gain_db: f32,
+    #[serde(rename = "addedAt")]
+    pub added_at: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct LibraryIndex {
+    samples: Vec<LibrarySample>,
+}
+
+/// Per-project preview sample selection (.vstworkshop/preview.json)
+#[derive(Serialize, Deserialize, Default)]
+struct ProjectPreviewSettings {
+    #[serde(rename = "sampleId", skip_serializing_if = "Option::is_none", default)]
+    sample_id: Option<String>,
+}
+
+fn get_library_path() -> PathBuf {
+    get_workspace_path().join("samples")
+}
+
+fn get_index_path() -> PathBuf {
+    get_library_path().join("library.json")
+}
+
+fn load_index() -> Result<LibraryIndex, String> {
+    let path = get_index_path();
+    if !path.exists() {
+        return Ok(LibraryIndex::default());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read sample library: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse sample library: {}", e))
+}
+
+fn save_index(index: &LibraryIndex) -> Result<(), String> {
+    fs::create_dir_all(get_library_path())
+        .map_err(|e| format!("Failed to create sample library dir: {}", e))?;
+    let json = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Failed to serialize sample library: {}", e))?;
+    fs::write(get_index_path(), json).map_err(|e| format!("Failed to write sample library: {}", e))
+}
+
+/// Normalize tags: trimmed, lowercase, deduplicated, no empties
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !out.contains(&tag) {
+            out.push(tag);
+        }
+    }
+    out
+}
+
+fn get_preview_settings_path(project_path: &str) -> PathBuf {
+    Path::new(project_path).join(".vstworkshop").join("preview.json")
+}
+
+/// List library samples, optionally filtered by category and/or tag
+#[tauri::command]
+pub async fn sample_library_list(
+    category: Option<String>,
+    tag: Option<String>,
+) -> Result<Vec<LibrarySample>, String> {
+    let index = load_index()?;
+    let tag = tag.map(|t| t.trim().to_lowercase());
+
+    Ok(index
+        .samples
+        .into_iter()
+        .filter(|s| category.as_ref().map_or(true, |c| &s.category == c))
+        .filter(|s| tag.as_ref().map_or(true, |t| s.tags.contains(t)))
+        .collect())
+}
+
+/// Import an audio file into the library
+/// The file is decoded, normalized to a consistent loudness (unless `normalize` is false),
+/// and stored as a float WAV so preview levels are comparable across material
+#[tauri::command]
+pub async fn sample_library_import(
+    source_path: String,
+    category: String,
+    tags: Vec<String>,
+    normalize: Option<bool>,
+) -> Result<LibrarySample, String> {
+    let source = PathBuf::from(&source_path);
+    let ext = source
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !SUPPORTED_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(format!("Unsupported audio format: .{}", ext));
+    }
+
+    let normalize = normalize.unwrap_or(true);
+
+    // Decoding can take a while for long files - keep it off the async runtime
+    let (mut sample, source_rms_db) = tokio::task::spawn_blocking(move || {
+        let sample = AudioSample::load(&source)?;
+        let rms = sample.rms_db();
+        Ok::<_, String>((sample, rms))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    let gain_db = if normalize {
+        sample.normalization_gain_db(TARGET_RMS_DB, PEAK_CEILING_DB)
+    } else {
+        0.0
+    };
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let files_dir = get_library_path().join("files");
+    fs::create_dir_all(&files_dir)
+        .map_err(|e| format!("Failed to create sample library dir: {}", e))?;
+    let dest = files_dir.join(format!("{}.wav", id));
+
+    let info = sample.info.clone();
+    let dest_clone = dest.clone();
+    tokio::task::spawn_blocking(move || {
+        if gain_db != 0.0 {
+            sample.apply_gain_db(gain_db);
+        }
+        sample.write_wav(&dest_clone)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    let entry = LibrarySample {
+        id,
+        name: info.name,
+        category,
+        tags: normalize_tags(tags),
+        path: dest.to_string_lossy().to_string(),
+        original_path: source_path,
+        sample_rate: info.sample_rate,
+        duration_secs: info.duration_secs,
+        source_rms_db: if source_rms_db.is_finite() { source_rms_db } else { -120.0 },
+        gain_db,
+        added_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let mut index = load_index()?;
+    index.samples.push(entry.clone());
+    save_index(&index)?;
+
+    Ok(entry)
+}
+
+/// Update a library sample's name, category, and tags
+#[tauri::command]
+pub async fn sample_library_update(
+    id: String,
+    name: Option<String>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<LibrarySample, String> {
+    let mut index = load_index()?;
+    let entry = index
+        .samples
+        .iter_mut()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Sample '{}' not found", id))?;
+
+    if let Some(name) = name.filter(|n| !n.trim().is_empty()) {
+        entry.name = name.trim().to_string();
+    }
+    if let Some(category) = category {
+        entry.category = category;
+    }
+    if let Some(tags) = tags {
+        entry.tags = normalize_tags(tags);
+    }
+
+    let updated = entry.clone();
+    save_index(&index)?;
+    Ok(updated)
+}
+
+/// Remove a sample from the library (deletes the stored copy, never the original)
+#[tauri::command]
+pub async fn sample_library_remove(id: String) -> Result<(), String> {
+    let mut index = load_index()?;
+    let pos = index
+        .samples
+        .iter()
+        .position(|s| s.id == id)
+        .ok_or_else(|| format!("Sample '{}' not found", id))?;
+
+    let entry = index.samples.remove(pos);
+    let path = PathBuf::from(&entry.path);
+    // Only delete files we own inside the library folder
+    if path.starts_with(get_library_path()) && path.exists() {
+        let _ = fs::remove_file(&path);
+    }
+
+    save_index(&index)
+}
+
+/// Remember which library sample a project uses for preview
+#[tauri::command]
+pub async fn sample_library_set_project_sample(
+    project_path: String,
+    sample_id: Option<String>,
+) -> Result<(), String> {
+    if let Some(ref id) = sample_id {
+        if !load_index()?.samples.iter().any(|s| &s.id == id) {
+            return Err(format!("Sample '{}' not found", id));
+        }
+    }
+
+    let path = get_preview_settings_path(&project_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create .vstworkshop dir: {}", e))?;
+    }
+
+    let mut settings: ProjectPreviewSettings = fs::read_to_string(&path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+    settings.sample_id = sample_id;
+
+    let json = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize preview settings: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write preview settings: {}", e))
+}
+
+/// Get the library sample selected for a project (None if unset or since removed)
+#[tauri::command]
+pub async fn sample_library_get_project_sample(
+    project_path: String,
+) -> Result<Option<LibrarySample>, String> {
+    let settings: ProjectPreviewSettings = fs::read_to_string(get_preview_settings_path(&project_path))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default();
+
+    let id = match settings.sample_id {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+
+    Ok(load_index()?.samples.into_iter().find(|s| s.id == id))
+}
@@ -0,0 +1,342 @@
+//! Sanitizer builds
+//!
+//! Builds a project with AddressSanitizer on the nightly toolchain
+//! (`-Zsanitizer=address`), runs the headless validator against that build in
+//! a separate `freqlab-cli` process with the ASan runtime preloaded, and parses
+//! the sanitizer reports into structured diagnostics.
+//!
+//! Projects are cargo-only, so there's no CMake configuration here. Rust has no
+//! UBSan; debug builds already trap overflow and out-of-bounds access as panics,
+//! which are parsed too, along with UBSan `runtime error:` lines from C deps.
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::Command;
+use tauri::Emitter;
+
+use super::build::{run_bundle, BuildProfile, BuildStreamEvent};
+use super::chat::resolve_project_version;
+
+pub(crate) const SANITIZER_RUSTFLAGS: &str = "-Zsanitizer=address";
+
+/// Entitlement that keeps the hardened runtime from stripping DYLD_INSERT_LIBRARIES
+const DYLD_ENV_ENTITLEMENT: &str = "com.apple.security.cs.allow-dyld-environment-variables";
+
+/// Leaks in the host would drown out the plugin's, and exiting (rather than
+/// aborting) keeps the crash guard from resuming a process ASan has given up on
+const ASAN_OPTIONS: &str = "detect_leaks=0:abort_on_error=0:symbolize=1";
+
+/// Stack frames kept per diagnostic
+const MAX_FRAMES: usize = 12;
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct SanitizerDiagnostic {
+    /// e.g. "heap-buffer-overflow", "use-after-free", "undefined-behavior", "panic"
+    pub kind: String,
+    pub message: String,
+    /// file:line:col of the first frame the report points at
+    pub location: Option<String>,
+    pub frames: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SanitizerReport {
+    pub build_success: bool,
+    /// The validator's JSON result, when it ran to completion
+    pub validation: Option<serde_json::Value>,
+    pub diagnostics: Vec<SanitizerDiagnostic>,
+    pub passed: bool,
+}
+
+fn nightly_output(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("rustc")
+        .arg("+nightly")
+        .args(args)
+        .env("PATH", super::get_extended_path())
+        .output()
+        .map_err(|e| format!("Failed to run rustc: {}", e))?;
+    if !output.status.success() {
+        return Err("Sanitizer builds need the nightly toolchain. Install it with \
+                    `rustup toolchain install nightly`."
+            .to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Host target triple from the nightly toolchain (errors if nightly isn't installed)
+pub(crate) fn nightly_host_triple() -> Result<String, String> {
+    nightly_output(&["-vV"])?
+        .lines()
+        .find_map(|l| l.strip_prefix("host: "))
+        .map(|t| t.trim().to_string())
+        .ok_or_else(|| "Failed to read the nightly host triple".to_string())
+}
+
+/// Environment variable and path that preload the ASan runtime into the validator
+///
+/// The plugin is a dylib loaded into an uninstrumented process, so the runtime
+/// has to be injected. macOS uses the one shipped with nightly; on Linux Rust
+/// only ships it statically, so the system's libasan is used.
+fn asan_preload() -> Result<(&'static str, PathBuf), String> {
+    if cfg!(target_os = "macos") {
+        let sysroot = PathBuf::from(nightly_output(&["--print", "sysroot"])?);
+        let runtime = sysroot
+            .join("lib/rustlib")
+            .join(nightly_host_triple()?)
+            .join("lib/librustc-nightly_rt.asan.dylib");
+        runtime
+            .exists()
+            .then_some(("DYLD_INSERT_LIBRARIES", runtime))
+            .ok_or_else(|| "Nightly toolchain has no ASan runtime".to_string())
+    } else if cfg!(target_os = "linux") {
+        let output = Command::new("gcc")
+            .arg("-print-file-name=libasan.so")
+            .output()
+            .map_err(|e| format!("Failed to find libasan (install gcc): {}", e))?;
+        let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        path.is_absolute()
+            .then_some(("LD_PRELOAD", path))
+            .ok_or_else(|| "libasan not found - install your distribution's libasan package".to_string())
+    } else {
+        Err("Sanitizer builds are supported on macOS and Linux".to_string())
+    }
+}
+
+/// Cargo's per-target RUSTFLAGS variable, e.g. `CARGO_TARGET_X86_64_UNKNOWN_LINUX_GNU_RUSTFLAGS`
+/// With `--target`, these flags reach only that target's artifacts - not build
+/// scripts or proc macros, which a global RUSTFLAGS would instrument too.
+pub(crate) fn target_rustflags_var(triple: &str) -> String {
+    format!(
+        "CARGO_TARGET_{}_RUSTFLAGS",
+        triple.to_uppercase().replace(['-', '.'], "_")
+    )
+}
+
+/// On macOS, fail if the hardened runtime would strip the ASan preload from `binary`
+///
+/// A signed build of freqlab-cli needs the allow-dyld-environment-variables
+/// entitlement (see entitlements.plist); without it DYLD_INSERT_LIBRARIES is
+/// silently dropped and the sanitized plugin aborts on load.
+fn check_dyld_preload_allowed(binary: &std::path::Path) -> Result<(), String> {
+    if !cfg!(target_os = "macos") {
+        return Ok(());
+    }
+    let Ok(info) = Command::new("codesign").arg("-dv").arg(binary).output() else {
+        return Ok(());
+    };
+    // codesign -dv writes to stderr; unsigned or non-hardened binaries keep DYLD_* vars
+    let hardened = String::from_utf8_lossy(&info.stderr)
+        .lines()
+        .any(|l| l.contains("flags=") && l.contains("runtime"));
+    if !hardened {
+        return Ok(());
+    }
+    let entitlements = Command::new("codesign")
+        .args(["-d", "--entitlements", "-", "--xml"])
+        .arg(binary)
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    if entitlements.contains(DYLD_ENV_ENTITLEMENT) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} is signed with the hardened runtime but lacks the {} entitlement, so the \
+             ASan runtime can't be preloaded. Update freqlab or use a development build.",
+            binary.display(),
+            DYLD_ENV_ENTITLEMENT
+        ))
+    }
+}
+
+/// `file:line:col` at the end of a frame or summary, if there is one
+fn trailing_location(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .rev()
+        .find(|word| word.contains(".rs:") || word.contains(".c:") || word.contains(".cpp:"))
+        .map(|word| word.trim_matches(|c| c == '(' || c == ')').to_string())
+}
+
+/// Parse ASan reports, UBSan runtime errors, and Rust panics out of stderr
+fn parse_sanitizer_output(text: &str) -> Vec<SanitizerDiagnostic> {
+    let mut diagnostics: Vec<SanitizerDiagnostic> = Vec::new();
+    // Latest ASan report, and whether its first stack is still being read
+    let mut asan_report: Option<usize> = None;
+    let mut collecting = false;
+    let mut lines = text.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if let Some(rest) = trimmed.split_once("ERROR: AddressSanitizer: ").map(|(_, r)| r) {
+            diagnostics.push(SanitizerDiagnostic {
+                kind: rest.split_whitespace().next().unwrap_or("unknown").to_string(),
+                message: rest.to_string(),
+                location: None,
+                frames: Vec::new(),
+            });
+            asan_report = Some(diagnostics.len() - 1);
+            collecting = true;
+        } else if let (Some(summary), Some(index)) =
+            (trimmed.strip_prefix("SUMMARY: AddressSanitizer: "), asan_report)
+        {
+            // The summary names the exact file:line:col
+            if let Some(location) = trailing_location(summary.split(" in ").next().unwrap_or(summary)) {
+                diagnostics[index].location = Some(location);
+            }
+            collecting = false;
+        } else if let (true, Some(index)) = (collecting, asan_report) {
+            let diagnostic = &mut diagnostics[index];
+            if trimmed.starts_with('#') {
+                let frame = trimmed.split_once(" in ").map(|(_, f)| f).unwrap_or(trimmed);
+                if diagnostic.location.is_none() {
+                    diagnostic.location = trailing_location(frame);
+                }
+                if diagnostic.frames.len() < MAX_FRAMES {
+                    diagnostic.frames.push(frame.to_string());
+                }
+            } else if trimmed.is_empty() && !diagnostic.frames.is_empty() {
+                collecting = false;
+            }
+        } else if let Some((location, message)) = trimmed.split_once(": runtime error: ") {
+            diagnostics.push(SanitizerDiagnostic {
+                kind: "undefined-behavior".to_string(),
+                message: message.to_string(),
+                location: Some(location.to_string()),
+                frames: Vec::new(),
+            });
+        } else if trimmed.starts_with("thread '") && trimmed.contains("panicked at ") {
+            // thread 'x' panicked at src/lib.rs:42:9:\nattempt to add with overflow
+            let location = trimmed
+                .split_once("panicked at ")
+                .map(|(_, l)| l.trim_end_matches(':').to_string());
+            let message = lines.next_if(|l| !l.trim().is_empty()).unwrap_or_default();
+            diagnostics.push(SanitizerDiagnostic {
+                kind: "panic".to_string(),
+                message: message.trim().to_string(),
+                location,
+                frames: Vec::new(),
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Build the project with AddressSanitizer and run the validator against it
+///
+/// Streams build output on "build-stream" like a normal build. Needs the nightly
+/// toolchain and the `freqlab-cli` binary (see `cli::cli_binary`).
+#[tauri::command]
+pub async fn build_with_sanitizers(
+    project_name: String,
+    version: Option<u32>,
+    window: tauri::Window,
+) -> Result<SanitizerReport, String> {
+    let version = resolve_project_version(&project_name, version).await?;
+    let (preload_var, runtime) = asan_preload()?;
+    let cli = crate::cli::cli_binary();
+    check_dyld_preload_allowed(&cli)?;
+    let emit = |event: BuildStreamEvent| {
+        let _ = window.emit("build-stream", event);
+    };
+
+    let build = run_bundle(project_name.clone(), version, BuildProfile::Sanitized, &emit).await?;
+    if !build.success {
+        return Ok(SanitizerReport {
+            build_success: false,
+            validation: None,
+            diagnostics: Vec::new(),
+            passed: false,
+        });
+    }
+
+    emit(BuildStreamEvent::Output {
+        line: "Running the validator against the sanitized build...".to_string(),
+    });
+    let output = tokio::process::Command::new(&cli)
+        .args(["validate", &project_name, "--sanitized", "--json"])
+        .env("PATH", super::get_extended_path())
+        .env(preload_var, &runtime)
+        .env("ASAN_OPTIONS", ASAN_OPTIONS)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", cli.display(), e))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in stderr.lines() {
+        emit(BuildStreamEvent::Output { line: line.to_string() });
+    }
+    let diagnostics = parse_sanitizer_output(&stderr);
+    // ASan exits mid-run on the first error, leaving no validation result
+    let validation: Option<serde_json::Value> = serde_json::from_slice(&output.stdout).ok();
+    let validation_passed = validation
+        .as_ref()
+        .and_then(|v| v.get("passed"))
+        .and_then(|p| p.as_bool())
+        .unwrap_or(false);
+
+    log::info!(
+        "Sanitizer run for {}: {} diagnostic(s), validator {}",
+        project_name,
+        diagnostics.len(),
+        if validation_passed { "passed" } else { "failed" }
+    );
+    Ok(SanitizerReport {
+        build_success: true,
+        passed: validation_passed && diagnostics.is_empty(),
+        validation,
+        diagnostics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_rustflags_var() {
+        assert_eq!(
+            target_rustflags_var("x86_64-unknown-linux-gnu"),
+            "CARGO_TARGET_X86_64_UNKNOWN_LINUX_GNU_RUSTFLAGS"
+        );
+        assert_eq!(
+            target_rustflags_var("thumbv7em-none-eabihf.custom"),
+            "CARGO_TARGET_THUMBV7EM_NONE_EABIHF_CUSTOM_RUSTFLAGS"
+        );
+    }
+
+    #[test]
+    fn test_parse_asan_report() {
+        let stderr = "\
+==4242==ERROR: AddressSanitizer: heap-buffer-overflow on address 0x602000000014 at pc 0x10a4c1234
+READ of size 4 at 0x602000000014 thread T7
+    #0 0x10a4c1234 in warm_drive::Drive::process lib.rs:42
+    #1 0x10a4c2000 in clap_process wrapper.rs:300
+
+0x602000000014 is located 0 bytes after 4-byte region
+SUMMARY: AddressSanitizer: heap-buffer-overflow /ws/projects/warm-drive/src/lib.rs:42:9 in warm_drive::Drive::process
+";
+        let diagnostics = parse_sanitizer_output(stderr);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, "heap-buffer-overflow");
+        assert_eq!(diagnostics[0].location.as_deref(), Some("/ws/projects/warm-drive/src/lib.rs:42:9"));
+        assert_eq!(diagnostics[0].frames[0], "warm_drive::Drive::process lib.rs:42");
+        assert_eq!(diagnostics[0].frames.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_panics_and_runtime_errors() {
+        let stderr = "\
+thread 'audio' panicked at src/lib.rs:88:17:
+attempt to add with overflow
+vendor/fft.c:10:5: runtime error: signed integer overflow
+";
+        let diagnostics = parse_sanitizer_output(stderr);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].kind, "panic");
+        assert_eq!(diagnostics[0].location.as_deref(), Some("src/lib.rs:88:17"));
+        assert_eq!(diagnostics[0].message, "attempt to add with overflow");
+        assert_eq!(diagnostics[1].kind, "undefined-behavior");
+        assert_eq!(diagnostics[1].location.as_deref(), Some("vendor/fft.c:10:5"));
+    }
+}
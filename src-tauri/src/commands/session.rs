@@ -0,0 +1,287 @@
+//! Session restore
+//!
+//! Remembers what was open when the app quit - the project, the plugin in the
+//! preview, its test signal or sample, transport, and window layout - in
+//! ~/VSTWorkshop/session.json, and puts it back on the next launch when the
+//! `restoreSession` setting is on.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use super::activity::project_dir;
+use super::preview::{plugin_load, plugin_load_for_project, preview_open_window};
+use super::projects::{get_output_path, get_workspace_path};
+use super::settings::current_settings;
+use crate::audio::engine::{get_engine_handle, InputSource};
+use crate::audio::plugin::editor::{self, PreviewWindowKind};
+use crate::audio::plugin::PluginState;
+
+/// Main window placement in physical pixels
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Session {
+    pub project_name: Option<String>,
+    /// Plugin bundle loaded in the preview
+    pub plugin_path: Option<String>,
+    /// Project and version whose build was loaded, so restoring goes through
+    /// the same load path as picking the version in the app
+    pub plugin_project: Option<String>,
+    pub plugin_version: Option<u32>,
+    pub input_source: Option<InputSource>,
+    pub playing: bool,
+    /// Native preview windows that were open (plugin editor, analyzer)
+    pub preview_windows: Vec<PreviewWindowKind>,
+    pub main_window: Option<WindowBounds>,
+    pub saved_at: String,
+}
+
+/// A restored session plus whatever couldn't be brought back
+#[derive(Serialize, Clone, Debug)]
+pub struct RestoredSession {
+    pub session: Session,
+    pub warnings: Vec<String>,
+}
+
+fn get_session_path() -> PathBuf {
+    get_workspace_path().join("session.json")
+}
+
+fn load_session() -> Option<Session> {
+    let content = std::fs::read_to_string(get_session_path()).ok()?;
+    serde_json::from_str(&content)
+        .map_err(|e| log::warn!("Ignoring unreadable session.json: {}", e))
+        .ok()
+}
+
+fn write_session(session: &Session) -> Result<(), String> {
+    std::fs::create_dir_all(get_workspace_path())
+        .map_err(|e| format!("Failed to create workspace dir: {}", e))?;
+    let json = serde_json::to_string_pretty(session)
+        .map_err(|e| format!("Failed to serialize session: {}", e))?;
+    std::fs::write(get_session_path(), json).map_err(|e| format!("Failed to write session: {}", e))
+}
+
+fn main_window_bounds(app_handle: &tauri::AppHandle) -> Option<WindowBounds> {
+    let window = app_handle.get_webview_window("main")?;
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    Some(WindowBounds {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Project and version of a release build (output/{project}/v{n}/...)
+fn project_version_for_bundle(bundle: &Path, output_root: &Path) -> Option<(String, u32)> {
+    let mut components = bundle.strip_prefix(output_root).ok()?.components();
+    let project = components.next()?.as_os_str().to_string_lossy().to_string();
+    let version = components.next()?.as_os_str().to_str()?.strip_prefix('v')?.parse().ok()?;
+    components.next()?;
+    Some((project, version))
+}
+
+/// Snapshot the current session; anything that's gone (closed window, no
+/// engine) falls back to the previous snapshot
+fn capture_session(app_handle: &tauri::AppHandle, project_name: Option<String>, previous: Session) -> Session {
+    let mut session = Session {
+        project_name,
+        main_window: main_window_bounds(app_handle).or(previous.main_window),
+        saved_at: chrono::Utc::now().to_rfc3339(),
+        ..Session::default()
+    };
+
+    match get_engine_handle() {
+        Some(handle) => {
+            if let PluginState::Active { path, .. } = handle.get_plugin_state() {
+                let release = project_version_for_bundle(Path::new(&path), &get_output_path());
+                session.plugin_project = release.as_ref().map(|(project, _)| project.clone());
+                session.plugin_version = release.map(|(_, version)| version);
+                session.plugin_path = Some(path);
+            }
+            session.input_source = match handle.get_input_source() {
+                InputSource::None => None,
+                source => Some(source),
+            };
+            session.playing = handle.is_playing();
+            session.preview_windows = editor::registered_windows()
+                .into_iter()
+                .filter(|&kind| editor::registered_window(kind).is_some_and(editor::is_window_visible))
+                .collect();
+        }
+        None => {
+            session.plugin_path = previous.plugin_path;
+            session.plugin_project = previous.plugin_project;
+            session.plugin_version = previous.plugin_version;
+            session.input_source = previous.input_source;
+            session.preview_windows = previous.preview_windows;
+        }
+    }
+    session
+}
+
+/// Record the current session (call when the open project changes)
+#[tauri::command]
+pub fn save_session(project_name: Option<String>, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let previous = load_session().unwrap_or_default();
+    write_session(&capture_session(&app_handle, project_name, previous))
+}
+
+/// Record the session as the app exits, keeping the last project the frontend reported
+pub fn save_session_on_exit(app_handle: &tauri::AppHandle) {
+    let previous = load_session().unwrap_or_default();
+    let project_name = previous.project_name.clone();
+    if let Err(e) = write_session(&capture_session(app_handle, project_name, previous)) {
+        log::warn!("Failed to save session: {}", e);
+    }
+}
+
+/// Source to restore, or why it can't be (a sample that has since moved)
+fn restorable_source(source: InputSource) -> Result<InputSource, String> {
+    match source {
+        InputSource::Sample { ref path } if !Path::new(path).exists() => {
+            Err(format!("Sample {} no longer exists", path))
+        }
+        source => Ok(source),
+    }
+}
+
+/// Restore the last session if the setting allows it
+///
+/// Call after the audio engine is initialized. Returns None when restoring is
+/// off or there's no saved session; the frontend opens `session.projectName`.
+#[tauri::command]
+pub fn restore_session(app_handle: tauri::AppHandle) -> Result<Option<RestoredSession>, String> {
+    if !current_settings().restore_session {
+        return Ok(None);
+    }
+    let session = match load_session() {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+    log::info!("Restoring session saved at {}", session.saved_at);
+    let mut warnings = Vec::new();
+
+    if let Some(project) = &session.project_name {
+        if !project_dir(project).exists() {
+            warnings.push(format!("Project '{}' no longer exists", project));
+        }
+    }
+
+    if let (Some(bounds), Some(window)) = (session.main_window, app_handle.get_webview_window("main")) {
+        let _ = window.set_position(tauri::PhysicalPosition::new(bounds.x, bounds.y));
+        let _ = window.set_size(tauri::PhysicalSize::new(bounds.width, bounds.height));
+    }
+
+    let handle = match get_engine_handle() {
+        Some(h) => h,
+        None => {
+            warnings.push("Audio engine isn't running, so the preview wasn't restored".to_string());
+            return Ok(Some(RestoredSession { session, warnings }));
+        }
+    };
+
+    let mut plugin_loaded = false;
+    if let (Some(project), Some(version)) = (&session.plugin_project, session.plugin_version) {
+        match plugin_load_for_project(project.clone(), version, app_handle.clone()) {
+            Ok(()) => plugin_loaded = true,
+            Err(e) => warnings.push(format!("Failed to reload {} v{}: {}", project, version, e)),
+        }
+    } else if let Some(path) = &session.plugin_path {
+        // Plugins from outside the project builds (debug builds, other bundles)
+        if Path::new(path).exists() {
+            match plugin_load(path.clone(), app_handle.clone()) {
+                Ok(()) => plugin_loaded = true,
+                Err(e) => warnings.push(format!("Failed to reload plugin: {}", e)),
+            }
+        } else {
+            warnings.push(format!("Plugin {} no longer exists", path));
+        }
+    }
+
+    if let Some(source) = session.input_source.clone() {
+        match restorable_source(source) {
+            Ok(source) => handle.set_input_source(source),
+            Err(e) => warnings.push(e),
+        }
+    }
+
+    let project_path = session
+        .project_name
+        .as_deref()
+        .map(|p| project_dir(p).to_string_lossy().to_string());
+    for &kind in &session.preview_windows {
+        // The editor needs the plugin that owned it
+        if kind == PreviewWindowKind::PluginEditor && !plugin_loaded {
+            continue;
+        }
+        if let Err(e) = preview_open_window(kind, project_path.clone(), app_handle.clone()) {
+            warnings.push(format!("Failed to reopen {:?} window: {}", kind, e));
+        }
+    }
+
+    if session.playing {
+        handle.play();
+    }
+
+    Ok(Some(RestoredSession { session, warnings }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_round_trip() {
+        let session = Session {
+            project_name: Some("warm-drive".to_string()),
+            input_source: Some(InputSource::Sample {
+                path: "/samples/drums.wav".to_string(),
+            }),
+            playing: true,
+            preview_windows: vec![PreviewWindowKind::PluginEditor],
+            ..Session::default()
+        };
+        let json = serde_json::to_string(&session).unwrap();
+        let loaded: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.project_name.as_deref(), Some("warm-drive"));
+        assert!(matches!(loaded.input_source, Some(InputSource::Sample { .. })));
+        assert_eq!(loaded.preview_windows, vec![PreviewWindowKind::PluginEditor]);
+
+        // Older or partial files load with defaults
+        let partial: Session = serde_json::from_str(r#"{"projectName":"x"}"#).unwrap();
+        assert!(!partial.playing && partial.plugin_path.is_none());
+    }
+
+    #[test]
+    fn test_project_version_for_bundle() {
+        let output = Path::new("/home/u/VSTWorkshop/output");
+        assert_eq!(
+            project_version_for_bundle(&output.join("warm-drive/v3/warm_drive.clap"), output),
+            Some(("warm-drive".to_string(), 3))
+        );
+        let debug = output.join("warm-drive/debug/warm_drive.clap");
+        assert_eq!(project_version_for_bundle(&debug, output), None);
+        assert_eq!(project_version_for_bundle(&output.join("warm-drive/v3"), output), None);
+        assert_eq!(project_version_for_bundle(Path::new("/Library/Audio/x.clap"), output), None);
+    }
+
+    #[test]
+    fn test_missing_sample_is_not_restored() {
+        let missing = InputSource::Sample {
+            path: "/definitely/not/here.wav".to_string(),
+        };
+        assert!(restorable_source(missing).is_err());
+        assert!(restorable_source(InputSource::None).is_ok());
+    }
+}
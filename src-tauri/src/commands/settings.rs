@@ -0,0 +1,533 @@
+//! Backend settings store
+//!
+//! Typed, validated app settings persisted at ~/VSTWorkshop/settings.json.
+//! The file carries a schema version; older files are migrated step by step
+//! on load (see `MIGRATIONS`) and rewritten in the current format.
+//!
+//! Every successful change emits `settings-changed` with the full settings.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use tauri::Emitter;
+
+use super::projects::get_workspace_path;
+use crate::audio::limiter::{DEFAULT_CEILING_DB, MIN_CEILING_DB};
+
+/// Current settings schema version
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Build formats the bundler can produce
+const BUILD_FORMATS: &[&str] = &["vst3", "clap"];
+
+/// Sample rates offered by the audio settings
+const SAMPLE_RATES: &[u32] = &[44100, 48000, 88200, 96000, 176400, 192000];
+
+static SETTINGS: Lazy<RwLock<Option<AppSettings>>> = Lazy::new(|| RwLock::new(None));
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DawPathConfig {
+    pub vst3: String,
+    pub clap: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DawPaths {
+    pub reaper: DawPathConfig,
+    pub ableton: DawPathConfig,
+    pub fl_studio: DawPathConfig,
+    pub logic: DawPathConfig,
+    pub other: DawPathConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioSettings {
+    /// None = system default
+    pub output_device: Option<String>,
+    pub sample_rate: u32,
+    pub buffer_size: u32,
+    /// Safety limiter ceiling (dBFS)
+    pub output_ceiling_db: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub version: u32,
+    pub output_path: String,
+    pub build_formats: Vec<String>,
+    pub auto_open_output: bool,
+    pub show_notifications: bool,
+    // Branding
+    pub vendor_name: String,
+    pub vendor_url: String,
+    pub vendor_email: String,
+    // DAW plugin paths
+    pub daw_paths: DawPaths,
+    /// AAX SDK location (None until the user points at one)
+    pub aax_sdk_path: Option<String>,
+    /// Command used by open_in_editor (e.g. "code", "cursor", "zed")
+    pub editor: String,
+    pub audio: AudioSettings,
+    /// Reopen the last project and preview setup on launch
+    pub restore_session: bool,
+    /// Let external tools build and reload projects (freqlab:// links and the local JSON-RPC server)
+    pub automation_api: bool,
+    /// Language for backend-generated messages (see i18n.rs)
+    pub language: String,
+    /// Mirror install/build progress to OS notifications and a status file (see stream_mirror.rs)
+    pub stream_mirror: bool,
+}
+
+impl Default for DawPathConfig {
+    fn default() -> Self {
+        Self {
+            vst3: "~/Library/Audio/Plug-Ins/VST3".to_string(),
+            clap: "~/Library/Audio/Plug-Ins/CLAP".to_string(),
+        }
+    }
+}
+
+impl Default for DawPaths {
+    fn default() -> Self {
+        Self {
+            reaper: DawPathConfig::default(),
+            ableton: DawPathConfig::default(),
+            fl_studio: DawPathConfig::default(),
+            logic: DawPathConfig::default(),
+            other: DawPathConfig {
+                vst3: String::new(),
+                clap: String::new(),
+            },
+        }
+    }
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            output_device: None,
+            sample_rate: 48000,
+            buffer_size: 512,
+            output_ceiling_db: DEFAULT_CEILING_DB,
+        }
+    }
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            output_path: "~/VSTWorkshop/output".to_string(),
+            build_formats: BUILD_FORMATS.iter().map(|f| f.to_string()).collect(),
+            auto_open_output: true,
+            show_notifications: true,
+            vendor_name: "freqlab".to_string(),
+            vendor_url: String::new(),
+            vendor_email: String::new(),
+            daw_paths: DawPaths::default(),
+            aax_sdk_path: None,
+            editor: "code".to_string(),
+            audio: AudioSettings::default(),
+            restore_session: true,
+            automation_api: false,
+            language: super::i18n::DEFAULT_LANGUAGE.to_string(),
+            stream_mirror: false,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Check the settings, returning every problem found
+    pub fn validate(&self) -> Result<(), String> {
+        join_problems(self.problems())
+    }
+
+    /// Check a change to the settings: only problems the change introduces are
+    /// errors, so a field that went bad on its own (a deleted AAX SDK folder)
+    /// doesn't block changing something unrelated - those are logged instead
+    pub fn validate_change(&self, previous: &AppSettings) -> Result<(), String> {
+        let existing = previous.problems();
+        let (kept, introduced): (Vec<String>, Vec<String>) =
+            self.problems().into_iter().partition(|p| existing.contains(p));
+        for problem in kept {
+            log::warn!("Settings problem left unchanged: {}", problem);
+        }
+        join_problems(introduced)
+    }
+
+    fn problems(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if self.build_formats.is_empty() {
+            errors.push("At least one build format is required".to_string());
+        }
+        for format in &self.build_formats {
+            if !BUILD_FORMATS.contains(&format.as_str()) {
+                errors.push(format!("Unknown build format: {}", format));
+            }
+        }
+        if self.vendor_name.trim().is_empty() {
+            errors.push("Vendor name can't be empty".to_string());
+        }
+        if !self.vendor_url.is_empty()
+            && !self.vendor_url.starts_with("https://")
+            && !self.vendor_url.starts_with("http://")
+        {
+            errors.push("Vendor URL must start with http:// or https://".to_string());
+        }
+        if !self.vendor_email.is_empty() && !is_plausible_email(&self.vendor_email) {
+            errors.push(format!("Invalid vendor email: {}", self.vendor_email));
+        }
+        if let Some(ref sdk) = self.aax_sdk_path {
+            if !expand_home(sdk).is_dir() {
+                errors.push(format!("AAX SDK folder not found: {}", sdk));
+            }
+        }
+        if self.editor.trim().is_empty() {
+            errors.push("Editor command can't be empty".to_string());
+        }
+        if !super::i18n::is_supported(&self.language) {
+            errors.push(format!("Unsupported language: {}", self.language));
+        }
+        if !SAMPLE_RATES.contains(&self.audio.sample_rate) {
+            errors.push(format!("Unsupported sample rate: {}", self.audio.sample_rate));
+        }
+        if !self.audio.buffer_size.is_power_of_two() || !(32..=4096).contains(&self.audio.buffer_size) {
+            errors.push(format!(
+                "Buffer size must be a power of two between 32 and 4096 (got {})",
+                self.audio.buffer_size
+            ));
+        }
+        if !(MIN_CEILING_DB..=0.0).contains(&self.audio.output_ceiling_db) {
+            errors.push(format!(
+                "Output ceiling must be between {} and 0 dBFS (got {})",
+                MIN_CEILING_DB, self.audio.output_ceiling_db
+            ));
+        }
+        errors
+    }
+}
+
+fn join_problems(errors: Vec<String>) -> Result<(), String> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn is_plausible_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((user, domain)) => !user.is_empty() && domain.contains('.') && !domain.starts_with('.'),
+        None => false,
+    }
+}
+
+/// Expand a leading ~ to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// =============================================================================
+// Migrations
+// =============================================================================
+
+/// Upgrade steps, indexed by the version they migrate from
+/// Each step receives the raw JSON of version N and returns version N+1
+const MIGRATIONS: &[fn(Value) -> Value] = &[migrate_v0_to_v1];
+
+/// v0: settings exported from the frontend's persisted store
+/// (`{ "state": { ... }, "version": 0 }`) with audio settings under `audioSettings`
+fn migrate_v0_to_v1(value: Value) -> Value {
+    let mut state = match value {
+        Value::Object(mut obj) => match obj.remove("state") {
+            Some(Value::Object(state)) => state,
+            _ => obj,
+        },
+        _ => serde_json::Map::new(),
+    };
+
+    if let Some(audio) = state.remove("audioSettings") {
+        state.insert("audio".to_string(), audio);
+    }
+    // UI-only state stays in the frontend
+    for key in [
+        "appliedAudioSettings",
+        "setupComplete",
+        "theme",
+        "customColors",
+        "aiSettings",
+        "acceptedLicenseVersion",
+        "workspacePath",
+    ] {
+        state.remove(key);
+    }
+
+    state.insert("version".to_string(), Value::from(1));
+    Value::Object(state)
+}
+
+/// Run migrations until the JSON reaches the current version
+fn migrate(mut value: Value) -> Result<Value, String> {
+    let mut version = value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version > SETTINGS_VERSION {
+        return Err(format!(
+            "Settings were written by a newer version of freqlab (schema v{}, this build supports v{})",
+            version, SETTINGS_VERSION
+        ));
+    }
+
+    while version < SETTINGS_VERSION {
+        log::info!("Migrating settings from v{} to v{}", version, version + 1);
+        value = MIGRATIONS[version as usize](value);
+        version += 1;
+    }
+    Ok(value)
+}
+
+// =============================================================================
+// Storage
+// =============================================================================
+
+fn get_settings_path() -> PathBuf {
+    get_workspace_path().join("settings.json")
+}
+
+/// Parse settings JSON of any known version
+fn parse_settings(content: &str) -> Result<AppSettings, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse settings: {}", e))?;
+    let value = migrate(value)?;
+    serde_json::from_value(value).map_err(|e| format!("Failed to read settings: {}", e))
+}
+
+fn write_settings(settings: &AppSettings) -> Result<(), String> {
+    fs::create_dir_all(get_workspace_path())
+        .map_err(|e| format!("Failed to create workspace dir: {}", e))?;
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    // Write to a temp file first so a crash mid-write can't corrupt the settings
+    let path = get_settings_path();
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| format!("Failed to write settings: {}", e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+/// Load settings from disk, migrating and rewriting older files
+fn load_settings() -> AppSettings {
+    let path = get_settings_path();
+    let content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(_) => return AppSettings::default(),
+    };
+
+    match parse_settings(&content) {
+        Ok(settings) => {
+            let on_disk_version = serde_json::from_str::<Value>(&content)
+                .ok()
+                .and_then(|v| v.get("version").and_then(Value::as_u64));
+            if on_disk_version != Some(SETTINGS_VERSION as u64) {
+                if let Err(e) = write_settings(&settings) {
+                    log::warn!("Failed to rewrite migrated settings: {}", e);
+                }
+            }
+            settings
+        }
+        Err(e) => {
+            log::error!("{} - using defaults", e);
+            AppSettings::default()
+        }
+    }
+}
+
+/// Current settings (loaded on first use)
+pub fn current_settings() -> AppSettings {
+    if let Some(settings) = SETTINGS.read().as_ref() {
+        return settings.clone();
+    }
+    let settings = load_settings();
+    *SETTINGS.write() = Some(settings.clone());
+    settings
+}
+
+/// Derive new settings from the current ones, then validate, persist, cache, and announce them
+///
+/// The cache's write lock is held from reading the current settings until the
+/// new ones are written, so concurrent changes can't overwrite each other.
+fn apply_settings(
+    app_handle: &tauri::AppHandle,
+    change: impl FnOnce(&AppSettings) -> Result<AppSettings, String>,
+) -> Result<AppSettings, String> {
+    let mut cache = SETTINGS.write();
+    let previous = cache.clone().unwrap_or_else(load_settings);
+    let mut settings = change(&previous)?;
+    settings.version = SETTINGS_VERSION;
+    settings.validate_change(&previous)?;
+    super::assignments::check_locked_settings(&settings)?;
+    write_settings(&settings)?;
+    *cache = Some(settings.clone());
+    drop(cache);
+    let _ = app_handle.emit("settings-changed", &settings);
+    Ok(settings)
+}
+
+/// Remember the safety limiter ceiling set from the preview
+pub(crate) fn save_output_ceiling(app_handle: &tauri::AppHandle, ceiling_db: f32) -> Result<AppSettings, String> {
+    apply_settings(app_handle, |current| {
+        let mut settings = current.clone();
+        settings.audio.output_ceiling_db = ceiling_db;
+        Ok(settings)
+    })
+}
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+#[tauri::command]
+pub async fn get_settings() -> Result<AppSettings, String> {
+    Ok(current_settings())
+}
+
+/// Replace all settings
+#[tauri::command]
+pub async fn set_settings(settings: AppSettings, app_handle: tauri::AppHandle) -> Result<AppSettings, String> {
+    apply_settings(&app_handle, |_| Ok(settings))
+}
+
+/// Change a subset of settings by merging a partial JSON object
+/// (e.g. `{ "audio": { "bufferSize": 256 } }`)
+#[tauri::command]
+pub async fn update_settings(patch: Value, app_handle: tauri::AppHandle) -> Result<AppSettings, String> {
+    apply_settings(&app_handle, |current| {
+        let mut value =
+            serde_json::to_value(current).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        merge_json(&mut value, patch);
+        serde_json::from_value(value).map_err(|e| format!("Invalid settings: {}", e))
+    })
+}
+
+/// Import settings persisted by the frontend before the backend store existed
+/// Only applies when no backend settings file exists yet
+#[tauri::command]
+pub async fn import_legacy_settings(
+    legacy_json: String,
+    app_handle: tauri::AppHandle,
+) -> Result<AppSettings, String> {
+    if get_settings_path().exists() {
+        return Ok(current_settings());
+    }
+    let settings = parse_settings(&legacy_json)?;
+    apply_settings(&app_handle, |_| Ok(settings))
+}
+
+/// Recursively merge `patch` into `target` (objects merge, everything else replaces)
+pub(crate) fn merge_json(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_are_valid() {
+        assert!(AppSettings::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_migrate_frontend_store() {
+        let legacy = r#"{
+            "state": {
+                "vendorName": "Acme",
+                "buildFormats": ["clap"],
+                "theme": "dark",
+                "audioSettings": { "outputDevice": null, "sampleRate": 44100, "bufferSize": 256 }
+            },
+            "version": 0
+        }"#;
+        let settings = parse_settings(legacy).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.vendor_name, "Acme");
+        assert_eq!(settings.build_formats, vec!["clap".to_string()]);
+        assert_eq!(settings.audio.sample_rate, 44100);
+        assert_eq!(settings.audio.buffer_size, 256);
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let future = format!(r#"{{ "version": {} }}"#, SETTINGS_VERSION + 1);
+        assert!(parse_settings(&future).is_err());
+    }
+
+    #[test]
+    fn test_validation_collects_errors() {
+        let settings = AppSettings {
+            build_formats: vec!["aax".to_string()],
+            vendor_email: "nope".to_string(),
+            audio: AudioSettings {
+                buffer_size: 500,
+                ..AudioSettings::default()
+            },
+            ..AppSettings::default()
+        };
+        let err = settings.validate().unwrap_err();
+        assert!(err.contains("aax"));
+        assert!(err.contains("email"));
+        assert!(err.contains("Buffer size"));
+    }
+
+    #[test]
+    fn test_change_only_fails_on_new_problems() {
+        let broken = AppSettings {
+            aax_sdk_path: Some("/definitely/not/an/sdk".to_string()),
+            ..AppSettings::default()
+        };
+        let renamed = AppSettings {
+            vendor_name: "Acme".to_string(),
+            ..broken.clone()
+        };
+        assert!(renamed.validate().is_err());
+        assert!(renamed.validate_change(&broken).is_ok());
+
+        let bad_buffer = AppSettings {
+            audio: AudioSettings {
+                buffer_size: 500,
+                ..AudioSettings::default()
+            },
+            ..broken.clone()
+        };
+        let err = bad_buffer.validate_change(&broken).unwrap_err();
+        assert!(err.contains("Buffer size") && !err.contains("AAX"));
+    }
+
+    #[test]
+    fn test_merge_json_nested() {
+        let mut target = serde_json::json!({ "audio": { "sampleRate": 48000, "bufferSize": 512 } });
+        merge_json(&mut target, serde_json::json!({ "audio": { "bufferSize": 256 } }));
+        assert_eq!(target["audio"]["sampleRate"], 48000);
+        assert_eq!(target["audio"]["bufferSize"], 256);
+    }
+}
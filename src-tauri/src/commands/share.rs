@@ -1,6 +1,11 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use std::fs::{self, File};
 use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use walkdir::WalkDir;
 use zip::write::SimpleFileOptions;
 use zip::{ZipArchive, ZipWriter};
@@ -571,3 +576,785 @@ fn fix_chat_attachment_paths(
 
     Ok(())
 }
+
+// ============================================================================
+// Share bundles (.freqlabshare)
+// ============================================================================
+//
+// A share bundle captures one version of a project so another user can
+// recreate it exactly:
+//
+//   manifest.json       - bundle format, source project, version, artifact list
+//   metadata.json       - project metadata (IDs regenerated on import)
+//   highlights.json     - the chat messages that produced each version
+//   source/...          - source files as committed for that version
+//   artifacts/...       - built plugin bundles from output/{name}/v{version}/
+
+/// File extension for share bundles
+pub const SHARE_EXTENSION: &str = "freqlabshare";
+
+/// Current bundle format version - bump when the layout changes
+const SHARE_FORMAT_VERSION: u32 = 1;
+
+/// Non-versioned project files that are still useful to carry along
+/// (Claude guidance is generated per-project and not tracked by git)
+const SHARE_EXTRA_PATHS: &[&str] = &["CLAUDE.md", ".claude"];
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ShareManifest {
+    #[serde(rename = "formatVersion")]
+    pub format_version: u32,
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    pub version: u32,
+    #[serde(rename = "commitHash", skip_serializing_if = "Option::is_none", default)]
+    pub commit_hash: Option<String>,
+    #[serde(rename = "exportedAt")]
+    pub exported_at: String,
+    #[serde(rename = "appVersion")]
+    pub app_version: String,
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+}
+
+/// Result of exporting a share bundle
+#[derive(serde::Serialize)]
+pub struct ShareExportResult {
+    pub path: String,
+    pub manifest: ShareManifest,
+}
+
+/// Find the commit hash for a version from chat history (None for version 0 / unknown)
+fn find_version_commit(project_path: &Path, version: u32) -> Option<String> {
+    let content = fs::read_to_string(project_path.join(".vstworkshop/chat.json")).ok()?;
+    let history: ChatHistory = serde_json::from_str(&content).ok()?;
+    history
+        .messages
+        .iter()
+        .find(|m| m.version == Some(version))
+        .and_then(|m| m.commit_hash.clone())
+}
+
+/// Collect chat highlights up to and including a version:
+/// every message that produced a version, plus the user prompt that led to it.
+/// Attachments are dropped - they're large and reference local files.
+fn collect_chat_highlights(project_path: &Path, version: u32) -> Vec<super::chat::ChatMessage> {
+    let content = match fs::read_to_string(project_path.join(".vstworkshop/chat.json")) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+    let history: ChatHistory = match serde_json::from_str(&content) {
+        Ok(h) => h,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut highlights = Vec::new();
+    for (i, message) in history.messages.iter().enumerate() {
+        let produced = match message.version {
+            Some(v) => v <= version,
+            None => false,
+        };
+        if !produced {
+            continue;
+        }
+
+        // Include the most recent user prompt before this version
+        if let Some(prompt) = history.messages[..i].iter().rev().find(|m| m.role == "user") {
+            if !highlights.iter().any(|h: &super::chat::ChatMessage| h.id == prompt.id) {
+                let mut prompt = prompt.clone();
+                prompt.attachments = None;
+                highlights.push(prompt);
+            }
+        }
+
+        let mut message = message.clone();
+        message.attachments = None;
+        highlights.push(message);
+    }
+
+    highlights
+}
+
+/// Add a single in-memory file to the zip
+fn add_bytes_to_zip(
+    zip: &mut ZipWriter<File>,
+    name: &str,
+    bytes: &[u8],
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+    zip.write_all(bytes)
+        .map_err(|e| format!("Failed to write {} to bundle: {}", name, e))
+}
+
+/// Add a file or directory tree from disk to the zip under a prefix
+fn add_path_to_zip(
+    zip: &mut ZipWriter<File>,
+    source: &Path,
+    prefix: &str,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|e| format!("Failed to read directory: {}", e))?;
+        let path = entry.path();
+        let relative = path
+            .strip_prefix(source)
+            .map_err(|e| format!("Failed to get relative path: {}", e))?;
+        let relative_str = relative.to_string_lossy().replace('\\', "/");
+        let name = if relative_str.is_empty() {
+            prefix.to_string()
+        } else {
+            format!("{}/{}", prefix, relative_str)
+        };
+
+        if path.is_file() {
+            let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+            add_bytes_to_zip(zip, &name, &bytes, options)?;
+        } else if path.is_dir() {
+            zip.add_directory(&name, options)
+                .map_err(|e| format!("Failed to add directory to bundle: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a relative bundle path under a base directory, rejecting path traversal
+fn resolve_bundle_path(base: &Path, relative: &str) -> Result<std::path::PathBuf, String> {
+    use std::path::Component;
+
+    let mut resolved = base.to_path_buf();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::Normal(name) => resolved.push(name),
+            Component::CurDir => {}
+            _ => {
+                return Err(format!(
+                    "Security error: bundle entry '{}' contains invalid path component",
+                    relative
+                ));
+            }
+        }
+    }
+    Ok(resolved)
+}
+
+/// Export one version of a project as a .freqlabshare bundle
+/// Source comes from the version's commit so the bundle matches the built artifacts
+#[tauri::command]
+pub async fn export_share(
+    project_name: String,
+    version: u32,
+    destination: String,
+) -> Result<ShareExportResult, String> {
+    let project_path = get_projects_path().join(&project_name);
+    if !project_path.exists() {
+        return Err(format!("Project '{}' not found", project_name));
+    }
+
+    let metadata_path = project_path.join(".vstworkshop/metadata.json");
+    let metadata_json = fs::read_to_string(&metadata_path)
+        .map_err(|e| format!("Failed to read metadata: {}", e))?;
+
+    // Map version 0 (no Claude commits) to v1 for filesystem lookups
+    let folder_version = version.max(1);
+    let artifacts_dir = super::projects::get_output_path()
+        .join(&project_name)
+        .join(format!("v{}", folder_version));
+
+    let bundle_path = if destination.ends_with(&format!(".{}", SHARE_EXTENSION)) {
+        destination.clone()
+    } else {
+        format!(
+            "{}/{}_v{}.{}",
+            destination, project_name, folder_version, SHARE_EXTENSION
+        )
+    };
+
+    let project_path_str = project_path.to_string_lossy().to_string();
+    // Fall back to HEAD when the version isn't recorded in chat (e.g. version 0)
+    let commit_hash = match find_version_commit(&project_path, version) {
+        Some(hash) => Some(hash),
+        None if super::git::is_git_repo(&project_path_str) => {
+            super::git::get_head_commit(&project_path_str).await.ok()
+        }
+        None => None,
+    };
+
+    let file = File::create(&bundle_path)
+        .map_err(|e| format!("Failed to create share bundle: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o755);
+
+    // Source files - from the version's commit when available, else the working tree
+    match &commit_hash {
+        Some(commit) => {
+            let files = super::git::list_files_at_commit(&project_path_str, commit)?;
+            for file in files {
+                let bytes = super::git::read_file_at_commit(&project_path_str, commit, &file)?;
+                add_bytes_to_zip(&mut zip, &format!("source/{}", file), &bytes, options)?;
+            }
+        }
+        None => {
+            for dir in ["src", "Cargo.toml", "Cargo.lock", ".gitignore"] {
+                let path = project_path.join(dir);
+                if path.exists() {
+                    add_path_to_zip(&mut zip, &path, &format!("source/{}", dir), options)?;
+                }
+            }
+        }
+    }
+
+    for extra in SHARE_EXTRA_PATHS {
+        let path = project_path.join(extra);
+        if path.exists() {
+            add_path_to_zip(&mut zip, &path, &format!("source/{}", extra), options)?;
+        }
+    }
+
+    // Built artifacts for this version (optional - a share may be source-only)
+    let mut artifacts = Vec::new();
+    if let Ok(entries) = fs::read_dir(&artifacts_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            add_path_to_zip(&mut zip, &entry.path(), &format!("artifacts/{}", name), options)?;
+            artifacts.push(name);
+        }
+    }
+
+    let highlights = collect_chat_highlights(&project_path, version);
+    let highlights_json = serde_json::to_string_pretty(&highlights)
+        .map_err(|e| format!("Failed to serialize chat highlights: {}", e))?;
+
+    let manifest = ShareManifest {
+        format_version: SHARE_FORMAT_VERSION,
+        project_name: project_name.clone(),
+        version,
+        commit_hash,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        artifacts,
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+
+    add_bytes_to_zip(&mut zip, "manifest.json", manifest_json.as_bytes(), options)?;
+    add_bytes_to_zip(&mut zip, "metadata.json", metadata_json.as_bytes(), options)?;
+    add_bytes_to_zip(&mut zip, "highlights.json", highlights_json.as_bytes(), options)?;
+
+    zip.finish().map_err(|e| format!("Failed to finalize share bundle: {}", e))?;
+
+    Ok(ShareExportResult {
+        path: bundle_path,
+        manifest,
+    })
+}
+
+/// Read a share bundle's manifest without importing it
+fn read_share_manifest_sync(archive: &mut ZipArchive<File>) -> Result<ShareManifest, String> {
+    let mut entry = archive
+        .by_name("manifest.json")
+        .map_err(|_| "Not a freqlab share bundle (missing manifest.json)".to_string())?;
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    let manifest: ShareManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse manifest: {}", e))?;
+
+    if manifest.format_version > SHARE_FORMAT_VERSION {
+        return Err(format!(
+            "This share bundle was created by a newer version of freqlab (format v{}). Please update to import it.",
+            manifest.format_version
+        ));
+    }
+
+    Ok(manifest)
+}
+
+/// Read a zip entry as a string, if present
+fn read_bundle_string(archive: &mut ZipArchive<File>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+/// Inspect a share bundle - returns its manifest and whether the project name is taken
+#[tauri::command]
+pub async fn inspect_share(bundle_path: String) -> Result<(ShareManifest, bool), String> {
+    let file = File::open(&bundle_path)
+        .map_err(|e| format!("Failed to open share bundle: {}", e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read share bundle: {}", e))?;
+
+    let manifest = read_share_manifest_sync(&mut archive)?;
+    let conflict = get_projects_path().join(&manifest.project_name).exists();
+
+    Ok((manifest, conflict))
+}
+
+/// Import a .freqlabshare bundle as a new project
+/// Regenerates the project ID, plugin IDs and chat message IDs so a re-import never
+/// collides with the original, starts a fresh git history, and places artifacts in
+/// output/{name}/v1/. Everything is staged first and only moved into place on success.
+#[tauri::command]
+pub async fn import_share(
+    bundle_path: String,
+    rename_to: Option<String>,
+) -> Result<ProjectMeta, String> {
+    ensure_workspace()?;
+
+    if let Some(ref new_name) = rename_to {
+        validate_name(new_name)?;
+    }
+
+    let file = File::open(&bundle_path)
+        .map_err(|e| format!("Failed to open share bundle: {}", e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read share bundle: {}", e))?;
+
+    let manifest = read_share_manifest_sync(&mut archive)?;
+    let target_name = rename_to.unwrap_or_else(|| manifest.project_name.clone());
+    validate_name(&target_name)?;
+
+    let target_path = get_projects_path().join(&target_name);
+    if target_path.exists() {
+        return Err(format!(
+            "Project '{}' already exists. Choose a different name to import this share.",
+            target_name
+        ));
+    }
+    let artifacts_path = super::projects::get_output_path()
+        .join(&target_name)
+        .join("v1");
+
+    let staging_path = super::projects::get_staging_path()
+        .join(format!("import-{}", uuid::Uuid::new_v4()));
+    let staged = stage_share_import(&mut archive, &manifest, &target_name, &target_path, &staging_path).await;
+
+    let result = staged.and_then(|(meta, ids)| {
+        if target_path.exists() {
+            return Err(format!("Project '{}' already exists", target_name));
+        }
+        fs::rename(staging_path.join("source"), &target_path)
+            .map_err(|e| format!("Failed to move project into place: {}", e))?;
+        let staged_artifacts = staging_path.join("artifacts");
+        if staged_artifacts.exists() {
+            // Source is already in place, so a failure here only loses the shared binaries
+            let moved = artifacts_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::rename(&staged_artifacts, &artifacts_path));
+            if let Err(e) = moved {
+                log::warn!("Failed to move shared artifacts into place: {}", e);
+            }
+        }
+        Ok((meta, ids))
+    });
+    if let Err(e) = fs::remove_dir_all(&staging_path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove staged import {:?}: {}", staging_path, e);
+        }
+    }
+    let (meta, (vst3_id, clap_id)) = result?;
+
+    super::plugin_ids::register_project(&target_name, &vst3_id, &clap_id);
+    Ok(meta)
+}
+
+/// Extract a share bundle into `staging_path` (source/ and artifacts/) and turn it
+/// into a project that will live at `target_path`
+/// Returns the metadata and the project's new (VST3, CLAP) IDs.
+async fn stage_share_import(
+    archive: &mut ZipArchive<File>,
+    manifest: &ShareManifest,
+    target_name: &str,
+    target_path: &Path,
+    staging_path: &Path,
+) -> Result<(ProjectMeta, (String, String)), String> {
+    let source_path = staging_path.join("source");
+    let artifacts_path = staging_path.join("artifacts");
+    let original_name = &manifest.project_name;
+
+    let metadata_json = read_bundle_string(archive, "metadata.json")
+        .ok_or("Share bundle is missing metadata.json")?;
+    let highlights_json = read_bundle_string(archive, "highlights.json");
+
+    // Extract source and artifacts
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read bundle entry: {}", e))?;
+        let entry_path = normalize_zip_path(entry.name());
+
+        let out_path = if let Some(rel) = entry_path.strip_prefix("source/") {
+            resolve_bundle_path(&source_path, rel)?
+        } else if let Some(rel) = entry_path.strip_prefix("artifacts/") {
+            resolve_bundle_path(&artifacts_path, rel)?
+        } else {
+            continue;
+        };
+
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+            }
+            let mut outfile = File::create(&out_path)
+                .map_err(|e| format!("Failed to create file: {}", e))?;
+            std::io::copy(&mut entry, &mut outfile)
+                .map_err(|e| format!("Failed to extract file: {}", e))?;
+        }
+    }
+
+    if !source_path.join("Cargo.toml").exists() {
+        return Err("Share bundle does not contain project source".to_string());
+    }
+
+    let workshop_dir = source_path.join(".vstworkshop");
+    fs::create_dir_all(&workshop_dir)
+        .map_err(|e| format!("Failed to create .vstworkshop dir: {}", e))?;
+
+    // Fresh plugin IDs, so the import and the original can be loaded side by side
+    let ids = reassign_plugin_ids(&source_path, target_name)?;
+
+    // Metadata with fresh identity
+    let mut meta: ProjectMeta = serde_json::from_str(&metadata_json)
+        .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+    let now = chrono::Utc::now().to_rfc3339();
+    meta.id = uuid::Uuid::new_v4().to_string();
+    meta.path = target_path.to_string_lossy().to_string();
+    meta.created_at = now.clone();
+    meta.updated_at = now.clone();
+    if let Some(identity) = meta.identity.as_mut() {
+        identity.bundle_id = ids.1.clone();
+    }
+
+    let updated_json = serde_json::to_string_pretty(&meta)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    fs::write(workshop_dir.join("metadata.json"), updated_json)
+        .map_err(|e| format!("Failed to write metadata: {}", e))?;
+
+    if target_name != original_name.as_str() {
+        update_cargo_package_name(&source_path, original_name, target_name)?;
+    }
+
+    // Chat highlights become the new project's history.
+    // Commits from the original repo don't exist here, so version links are cleared.
+    if let Some(json) = highlights_json {
+        if let Ok(mut messages) = serde_json::from_str::<Vec<super::chat::ChatMessage>>(&json) {
+            for message in &mut messages {
+                message.id = uuid::Uuid::new_v4().to_string();
+                message.commit_hash = None;
+                message.version = None;
+                message.reverted = false;
+                message.attachments = None;
+            }
+            let history = ChatHistory {
+                messages,
+                last_updated: now,
+                active_version: None,
+            };
+            let chat_json = serde_json::to_string_pretty(&history)
+                .map_err(|e| format!("Failed to serialize chat history: {}", e))?;
+            fs::write(workshop_dir.join("chat.json"), chat_json)
+                .map_err(|e| format!("Failed to write chat history: {}", e))?;
+        }
+    }
+
+    // Fresh git history rooted at the shared source (the repo moves with the folder)
+    let source_path_str = source_path.to_string_lossy().to_string();
+    super::git::init_repo(&source_path_str).await?;
+    if !source_path.join(".gitignore").exists() {
+        super::git::create_gitignore(&source_path_str)?;
+    }
+    let _ = super::git::ensure_vstworkshop_ignored(&source_path_str);
+    let message = format!(
+        "Imported from share: {} v{}",
+        original_name, manifest.version
+    );
+    match super::git::commit_changes(&source_path_str, &message).await {
+        Ok(_) => {}
+        Err(e) if e == "no_changes" => {}
+        Err(e) => return Err(e),
+    }
+
+    Ok((meta, ids))
+}
+
+/// Give an imported project's source a VST3 class ID and CLAP ID no other project uses
+fn reassign_plugin_ids(project_path: &Path, project_name: &str) -> Result<(String, String), String> {
+    use super::identity::{read_const_str, replace_const_str};
+
+    let lib_path = project_path.join("src/lib.rs");
+    let source = fs::read_to_string(&lib_path)
+        .map_err(|e| format!("Failed to read lib.rs: {}", e))?;
+
+    let vst3_id = super::plugin_ids::allocate_vst3_id(project_name);
+    let mut updated = replace_const_str(&source, "VST3_CLASS_ID", &vst3_id)
+        .ok_or_else(|| "Could not find `const VST3_CLASS_ID` in src/lib.rs".to_string())?;
+
+    let shared_clap_id = read_const_str(&updated, "CLAP_ID")
+        .ok_or_else(|| "Could not find `const CLAP_ID` in src/lib.rs".to_string())?;
+    let clap_id = super::plugin_ids::allocate_clap_id(project_name, &shared_clap_id);
+    if clap_id != shared_clap_id {
+        updated = replace_const_str(&updated, "CLAP_ID", &clap_id)
+            .ok_or_else(|| "Could not find `const CLAP_ID` in src/lib.rs".to_string())?;
+    }
+
+    fs::write(&lib_path, updated).map_err(|e| format!("Failed to write lib.rs: {}", e))?;
+    Ok((vst3_id, clap_id))
+}
+
+// ============================================================================
+// LAN plugin gallery (opt-in)
+// ============================================================================
+//
+// A tiny HTTP server that serves one packaged zip plus an install page to other
+// machines on the local network. It only ever serves the file it was started
+// with - there's no directory listing and no access to anything else on disk.
+
+/// Running LAN server state
+struct LanServer {
+    info: LanServerInfo,
+    running: Arc<AtomicBool>,
+}
+
+/// Global LAN server instance (at most one at a time)
+static LAN_SERVER: Lazy<Mutex<Option<LanServer>>> = Lazy::new(|| Mutex::new(None));
+
+/// Info about the running LAN server, returned to the UI
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct LanServerInfo {
+    pub url: String,
+    pub address: String,
+    pub port: u16,
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    /// Payload to encode in a QR code (the install page URL)
+    #[serde(rename = "qrPayload")]
+    pub qr_payload: String,
+}
+
+/// Determine this machine's LAN IP address
+/// Connecting a UDP socket sends no packets but makes the OS pick the outbound interface
+fn local_lan_address() -> Option<std::net::IpAddr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.168.0.1:80").ok()?;
+    let addr = socket.local_addr().ok()?.ip();
+    if addr.is_unspecified() || addr.is_loopback() {
+        None
+    } else {
+        Some(addr)
+    }
+}
+
+/// Minimal HTML escaping for values interpolated into generated pages
+pub(crate) fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render the install page shown to testers
+fn render_install_page(project_name: &str, file_name: &str, size: u64) -> String {
+    let name = html_escape(project_name);
+    let file = html_escape(file_name);
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{name} - freqlab</title>
+<style>
+  body {{ font-family: -apple-system, sans-serif; background: #111; color: #eee; max-width: 560px; margin: 40px auto; padding: 0 16px; }}
+  a.button {{ display: inline-block; background: #2dd4bf; color: #111; padding: 12px 20px; border-radius: 8px; text-decoration: none; font-weight: 600; }}
+  code {{ background: #222; padding: 2px 6px; border-radius: 4px; }}
+  li {{ margin-bottom: 8px; }}
+</style>
+</head>
+<body>
+<h1>{name}</h1>
+<p><a class="button" href="/download/{file}">Download {file}</a> ({size_kb} KB)</p>
+<h3>Install</h3>
+<ol>
+  <li>Unzip the download.</li>
+  <li>macOS: copy <code>.vst3</code> to <code>~/Library/Audio/Plug-Ins/VST3</code> and <code>.clap</code> to <code>~/Library/Audio/Plug-Ins/CLAP</code>.</li>
+  <li>Windows: copy <code>.vst3</code> to <code>C:\Program Files\Common Files\VST3</code> and <code>.clap</code> to <code>C:\Program Files\Common Files\CLAP</code>.</li>
+  <li>Rescan plugins in your DAW.</li>
+</ol>
+<p>On macOS you may need to run <code>xattr -cr</code> on the plugin if Gatekeeper blocks it.</p>
+</body>
+</html>
+"#,
+        name = name,
+        file = file,
+        size_kb = size / 1024
+    )
+}
+
+/// Write a simple HTTP response
+fn write_http_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    extra_headers: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len(),
+        extra_headers
+    );
+    stream.write_all(header.as_bytes())?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+/// Handle a single HTTP request
+fn handle_lan_request(
+    mut stream: TcpStream,
+    zip_path: &Path,
+    file_name: &str,
+    project_name: &str,
+) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(5)))?;
+
+    // Read just the request line + headers (we never need a body)
+    let mut buf = [0u8; 4096];
+    let n = stream.read(&mut buf)?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut parts = request.lines().next().unwrap_or("").split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let target = parts.next().unwrap_or("");
+
+    if method != "GET" {
+        return write_http_response(&mut stream, "405 Method Not Allowed", "text/plain", "", b"Method not allowed");
+    }
+
+    let download_target = format!("/download/{}", file_name);
+    if target == "/" || target == "/index.html" {
+        let size = fs::metadata(zip_path).map(|m| m.len()).unwrap_or(0);
+        let page = render_install_page(project_name, file_name, size);
+        write_http_response(&mut stream, "200 OK", "text/html; charset=utf-8", "", page.as_bytes())
+    } else if target == download_target {
+        let bytes = fs::read(zip_path)?;
+        let disposition = format!("Content-Disposition: attachment; filename=\"{}\"\r\n", file_name);
+        write_http_response(&mut stream, "200 OK", "application/zip", &disposition, &bytes)
+    } else {
+        write_http_response(&mut stream, "404 Not Found", "text/plain", "", b"Not found")
+    }
+}
+
+/// Start serving a packaged plugin zip on the local network
+/// Opt-in: nothing listens until this is called, and only one server runs at a time
+#[tauri::command]
+pub async fn start_lan_server(
+    zip_path: String,
+    project_name: String,
+    port: Option<u16>,
+) -> Result<LanServerInfo, String> {
+    let zip = std::path::PathBuf::from(&zip_path);
+    if !zip.is_file() {
+        return Err("Package not found. Package the plugin first.".to_string());
+    }
+
+    stop_lan_server_inner();
+
+    let address = local_lan_address()
+        .ok_or("Could not determine a local network address. Are you connected to a network?")?;
+
+    let listener = TcpListener::bind(("0.0.0.0", port.unwrap_or(0)))
+        .map_err(|e| format!("Failed to start LAN server: {}", e))?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to configure LAN server: {}", e))?;
+    let bound_port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read LAN server address: {}", e))?
+        .port();
+
+    let file_name = zip
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "plugin.zip".to_string());
+
+    let url = format!("http://{}:{}/", address, bound_port);
+    let info = LanServerInfo {
+        url: url.clone(),
+        address: address.to_string(),
+        port: bound_port,
+        file_name: file_name.clone(),
+        qr_payload: url,
+    };
+
+    let running = Arc::new(AtomicBool::new(true));
+    let thread_running = running.clone();
+    let zip: Arc<Path> = Arc::from(zip.as_path());
+    let file_name: Arc<str> = Arc::from(file_name);
+    let project_name: Arc<str> = Arc::from(project_name);
+
+    std::thread::spawn(move || {
+        log::info!("LAN server listening on port {}", bound_port);
+        while thread_running.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, peer)) => {
+                    log::info!("LAN server request from {}", peer);
+                    let _ = stream.set_nonblocking(false);
+                    // One thread per connection so a slow download doesn't hold up other visitors
+                    let (zip, file_name, project_name) = (zip.clone(), file_name.clone(), project_name.clone());
+                    std::thread::spawn(move || {
+                        if let Err(e) = handle_lan_request(stream, &zip, &file_name, &project_name) {
+                            log::warn!("LAN server request failed: {}", e);
+                        }
+                    });
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(std::time::Duration::from_millis(50));
+                }
+                Err(e) => {
+                    log::warn!("LAN server accept failed: {}", e);
+                    std::thread::sleep(std::time::Duration::from_millis(200));
+                }
+            }
+        }
+        log::info!("LAN server stopped");
+    });
+
+    *LAN_SERVER.lock() = Some(LanServer {
+        info: info.clone(),
+        running,
+    });
+
+    Ok(info)
+}
+
+/// Stop the LAN server if it's running
+fn stop_lan_server_inner() {
+    if let Some(server) = LAN_SERVER.lock().take() {
+        server.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Stop serving on the local network
+#[tauri::command]
+pub async fn stop_lan_server() -> Result<(), String> {
+    stop_lan_server_inner();
+    Ok(())
+}
+
+/// Get info about the running LAN server (None if stopped)
+#[tauri::command]
+pub async fn get_lan_server_info() -> Result<Option<LanServerInfo>, String> {
+    Ok(LAN_SERVER.lock().as_ref().map(|s| s.info.clone()))
+}
@@ -0,0 +1,282 @@
+//! Binary size reports
+//!
+//! Release builds write `size-report.json` next to their bundles: the size of
+//! each format's bundle and the plugin binary inside it, and a breakdown of the
+//! binary's code by crate (in the spirit of `cargo bloat --crates`), read from
+//! its symbol table with `nm` before any stripping. With release optimization
+//! on, the report also records the sizes before and after it (the build runs
+//! the optimization between the breakdown and the report). Comparing two
+//! versions' reports shows which dependency made a plugin balloon - usually the
+//! GUI framework.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::build::find_clap_in;
+use super::projects::get_output_path;
+use super::release_optimization::OptimizationSummary;
+
+const SIZE_REPORT_FILE: &str = "size-report.json";
+
+/// Crates listed individually; the rest are summed into "[other]"
+const MAX_CRATES: usize = 20;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSize {
+    /// "VST3", "CLAP"
+    pub format: String,
+    pub name: String,
+    pub bundle_bytes: u64,
+    /// The plugin binary inside the bundle
+    pub binary_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrateSize {
+    pub name: String,
+    /// Code attributed to the crate (including generics instantiated for it)
+    pub bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeReport {
+    pub version: u32,
+    pub bundles: Vec<BundleSize>,
+    /// Total code measured in the crate breakdown
+    pub code_bytes: u64,
+    /// Largest first; empty when the binary couldn't be analyzed
+    pub crates: Vec<CrateSize>,
+    /// Why there's no breakdown (e.g. `nm` missing, stripped binary)
+    pub breakdown_error: Option<String>,
+    /// Stripping/compression done after the build (see release_optimization.rs)
+    #[serde(default)]
+    pub optimization: Option<OptimizationSummary>,
+}
+
+fn path_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// The plugin binary of a bundle: the bundle itself on Linux and Windows,
+/// the largest file inside it for macOS bundle folders
+fn bundle_binary(bundle: &Path) -> Option<PathBuf> {
+    if bundle.is_file() {
+        return Some(bundle.to_path_buf());
+    }
+    walkdir::WalkDir::new(bundle)
+        .into_iter()
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .max_by_key(|e| e.metadata().map(|m| m.len()).unwrap_or(0))
+        .map(|e| e.into_path())
+}
+
+/// Crate a symbol belongs to, from its legacy Rust (`_ZN`) mangled name
+/// Symbols of trait impls (`<T as Trait>::f`) count toward the crate of the type.
+fn symbol_crate(symbol: &str) -> String {
+    // macOS prefixes every symbol with an extra underscore
+    let symbol = symbol.strip_prefix('_').filter(|s| s.starts_with("_ZN")).unwrap_or(symbol);
+    let Some(rest) = symbol.strip_prefix("_ZN") else {
+        return "[unknown]".to_string();
+    };
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    let Ok(len) = rest[..digits].parse::<usize>() else {
+        return "[unknown]".to_string();
+    };
+    let Some(first) = rest.get(digits..digits + len) else {
+        return "[unknown]".to_string();
+    };
+    let first = first.strip_prefix("_$LT$").unwrap_or(first);
+    let name = first.split("..").next().unwrap_or(first);
+    let name = name.split('$').next().unwrap_or(name);
+    if name.is_empty() {
+        "[unknown]".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Code size per crate from `nm -n` output (symbols sorted by address); a
+/// function's size is the distance to the next symbol
+fn crate_sizes(nm_output: &str) -> Vec<CrateSize> {
+    let symbols: Vec<(u64, char, &str)> = nm_output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let address = u64::from_str_radix(parts.next()?, 16).ok()?;
+            let kind = parts.next()?.chars().next()?;
+            Some((address, kind, parts.next()?))
+        })
+        .collect();
+
+    let mut sizes: HashMap<String, u64> = HashMap::new();
+    for pair in symbols.windows(2) {
+        let (address, kind, name) = pair[0];
+        if kind.eq_ignore_ascii_case(&'t') {
+            *sizes.entry(symbol_crate(name)).or_default() += pair[1].0.saturating_sub(address);
+        }
+    }
+
+    let mut crates: Vec<CrateSize> = sizes
+        .into_iter()
+        .filter(|(_, bytes)| *bytes > 0)
+        .map(|(name, bytes)| CrateSize { name, bytes })
+        .collect();
+    crates.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    if crates.len() > MAX_CRATES {
+        let other: u64 = crates.drain(MAX_CRATES..).map(|c| c.bytes).sum();
+        crates.push(CrateSize {
+            name: "[other]".to_string(),
+            bytes: other,
+        });
+    }
+    crates
+}
+
+fn analyze_binary(binary: &Path) -> Result<Vec<CrateSize>, String> {
+    let output = Command::new("nm")
+        .arg("-n")
+        .arg(binary)
+        .output()
+        .map_err(|e| format!("Failed to run nm: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("nm failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    let crates = crate_sizes(&String::from_utf8_lossy(&output.stdout));
+    if crates.is_empty() {
+        return Err("The plugin binary has no symbols to analyze".to_string());
+    }
+    Ok(crates)
+}
+
+fn measure_bundles(output_path: &Path) -> Result<Vec<BundleSize>, String> {
+    let mut bundles: Vec<BundleSize> = fs::read_dir(output_path)
+        .map_err(|e| format!("Failed to read {}: {}", output_path.display(), e))?
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let format = match e.path().extension()?.to_str()? {
+                "vst3" => "VST3",
+                "clap" => "CLAP",
+                _ => return None,
+            };
+            Some(BundleSize {
+                format: format.to_string(),
+                bundle_bytes: path_size(&e.path()),
+                binary_bytes: bundle_binary(&e.path()).map_or(0, |b| path_size(&b)),
+                name,
+            })
+        })
+        .collect();
+    bundles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(bundles)
+}
+
+/// Break down a release build's binary by crate (call before stripping it)
+/// Returns the crates, or why there's no breakdown.
+pub(crate) fn crate_breakdown(output_path: &Path) -> Result<Vec<CrateSize>, String> {
+    // Both formats hold the same code; the CLAP binary is the simpler one to find
+    let binary = find_clap_in(output_path)
+        .and_then(|clap| bundle_binary(&clap))
+        .ok_or_else(|| "No CLAP bundle to analyze".to_string())?;
+    analyze_binary(&binary)
+}
+
+/// Measure the bundles and save the report with the breakdown and optimization results
+pub(crate) fn write_size_report(
+    output_path: &Path,
+    version: u32,
+    breakdown: Result<Vec<CrateSize>, String>,
+    optimization: Option<OptimizationSummary>,
+) -> Result<SizeReport, String> {
+    let (crates, breakdown_error) = match breakdown {
+        Ok(crates) => (crates, None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+
+    let report = SizeReport {
+        version,
+        bundles: measure_bundles(output_path)?,
+        code_bytes: crates.iter().map(|c| c.bytes).sum(),
+        crates,
+        breakdown_error,
+        optimization,
+    };
+    let json = serde_json::to_string_pretty(&report).map_err(|e| format!("Failed to serialize size report: {}", e))?;
+    fs::write(output_path.join(SIZE_REPORT_FILE), json).map_err(|e| format!("Failed to save size report: {}", e))?;
+    Ok(report)
+}
+
+/// Size report of a release build (the latest one with a report when `version` is None)
+#[tauri::command]
+pub async fn get_size_report(project_name: String, version: Option<u32>) -> Result<Option<SizeReport>, String> {
+    let project_output = get_output_path().join(&project_name);
+    let version = match version {
+        Some(version) => version,
+        None => {
+            let latest = fs::read_dir(&project_output)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|e| e.path().join(SIZE_REPORT_FILE).exists())
+                .filter_map(|e| e.file_name().to_string_lossy().strip_prefix('v')?.parse::<u32>().ok())
+                .max();
+            match latest {
+                Some(version) => version,
+                None => return Ok(None),
+            }
+        }
+    };
+    let path = project_output.join(format!("v{}", version)).join(SIZE_REPORT_FILE);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse size report: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crate_sizes() {
+        assert_eq!(symbol_crate("__ZN4core3fmt5write17h0123456789abcdefE"), "core");
+        assert_eq!(
+            symbol_crate("_ZN61_$LT$egui..context..Context$u20$as$u20$core..clone..Clone$GT$5clone17h0123456789abcdefE"),
+            "egui"
+        );
+        assert_eq!(symbol_crate("_clap_entry"), "[unknown]");
+
+        let nm = "\
+0000000000001000 T __ZN4core3fmt5write17h0123456789abcdefE
+0000000000001400 t __ZN4egui7context7Context3run17h0123456789abcdefE
+0000000000009400 T _clap_entry
+0000000000009500 T __ZN4core3ptr13drop_in_place17h0123456789abcdefE
+0000000000009600 D _some_data
+0000000000009900 T __ZN4gain4Gain7process17h0123456789abcdefE
+";
+        let crates = crate_sizes(nm);
+        assert_eq!(
+            crates,
+            vec![
+                CrateSize { name: "egui".to_string(), bytes: 0x8000 },
+                CrateSize { name: "core".to_string(), bytes: 0x500 },
+                CrateSize { name: "[unknown]".to_string(), bytes: 0x100 },
+            ]
+        );
+    }
+}
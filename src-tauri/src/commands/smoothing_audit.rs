@@ -0,0 +1,365 @@
+//! Parameter smoothing auditor
+//!
+//! Part of the offline validation suite. Plays a steady two-tone signal through
+//! the plugin while one parameter is automated with step and ramp patterns
+//! (changes land on block boundaries, like host automation), and compares the
+//! spectral flux above the test tones with a render where the parameter holds
+//! still. An unsmoothed parameter turns every change into a
+//! broadband click, which shows up as flux spikes - so this tells users whether
+//! their `SmoothingStyle` choices actually work.
+
+use realfft::RealFftPlanner;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::analysis_jobs::run_analysis_job;
+use super::distortion::resolve_plugin_bundle;
+use super::issues::{sync_validator_issues, ValidatorIssue};
+use crate::audio::buffer::StereoSample;
+use crate::audio::offline::BlockProcessor;
+use crate::audio::plugin::{PluginInstance, PluginParamInfo};
+
+const SAMPLE_RATE: u32 = 48000;
+const BLOCK_SIZE: usize = 512;
+
+/// Length of each render (seconds)
+const PATTERN_SECS: f32 = 2.0;
+
+/// Skipped at the start of each render so activation and note attacks don't count (seconds)
+const SETTLE_SECS: f32 = 0.2;
+
+const FFT_SIZE: usize = 1024;
+const HOP_SIZE: usize = 256;
+
+/// Flux is measured above the test tones, where a smooth change adds next to nothing
+const ANALYSIS_LOW_HZ: f32 = 5000.0;
+
+/// A frame is a spike when its flux is this many times the static render's worst frame
+const SPIKE_RATIO: f32 = 4.0;
+
+/// ...and above this floor, so a near-silent static render doesn't make everything a spike
+const MIN_SPIKE_FLUX: f32 = 0.001;
+
+/// Automated range, as fractions of the parameter's range
+const LOW_POSITION: f64 = 0.25;
+const HIGH_POSITION: f64 = 0.75;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationPattern {
+    /// Jump between the low and high value every 250 ms
+    Step,
+    /// Triangle between low and high, 250 ms each way
+    Ramp,
+}
+
+impl AutomationPattern {
+    const ALL: [AutomationPattern; 2] = [Self::Step, Self::Ramp];
+
+    /// Position between the low (0) and high (1) value at a time
+    fn position(self, secs: f32) -> f64 {
+        match self {
+            Self::Step => ((secs / 0.25) as u32 % 2) as f64,
+            Self::Ramp => {
+                let phase = (secs / 0.5).fract() as f64;
+                1.0 - (2.0 * phase - 1.0).abs()
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct PatternResult {
+    pub pattern: AutomationPattern,
+    /// Analysis frames with a flux spike
+    pub spikes: usize,
+    /// Worst frame relative to the static render's worst frame (dB)
+    pub worst_flux_db: f32,
+    /// When the worst spike happened, from the start of the render (seconds)
+    pub worst_time_secs: Option<f32>,
+    pub passed: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SmoothingAuditReport {
+    pub plugin_path: String,
+    pub param_id: u32,
+    pub param_name: String,
+    pub low_value: f64,
+    pub high_value: f64,
+    pub patterns: Vec<PatternResult>,
+    pub passed: bool,
+}
+
+/// Steady 220 Hz + 3.3 kHz tones - enough for filters and saturators to work on
+fn test_tones(len: usize) -> Vec<StereoSample> {
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let s = 0.5 * (std::f32::consts::TAU * 220.0 * t).sin() + 0.2 * (std::f32::consts::TAU * 3300.0 * t).sin();
+            StereoSample::new(s, s)
+        })
+        .collect()
+}
+
+/// Positive spectral flux per frame above the analysis band edge, relative to the frame's total magnitude
+fn spectral_flux(mono: &[f32]) -> Vec<f32> {
+    let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+    let window: Vec<f32> = (0..FFT_SIZE)
+        .map(|i| 0.5 * (1.0 - (std::f32::consts::TAU * i as f32 / FFT_SIZE as f32).cos()))
+        .collect();
+    let mut input = fft.make_input_vec();
+    let mut output = fft.make_output_vec();
+    let low_bin = (ANALYSIS_LOW_HZ / SAMPLE_RATE as f32 * FFT_SIZE as f32) as usize;
+
+    let mut previous: Option<Vec<f32>> = None;
+    let mut flux = Vec::new();
+    let mut start = 0;
+    while start + FFT_SIZE <= mono.len() {
+        for (i, x) in input.iter_mut().enumerate() {
+            *x = mono[start + i] * window[i];
+        }
+        if fft.process(&mut input, &mut output).is_err() {
+            break;
+        }
+        let magnitudes: Vec<f32> = output.iter().map(|c| c.norm()).collect();
+        if let Some(previous) = &previous {
+            let total: f32 = magnitudes.iter().sum::<f32>().max(1e-9);
+            let rise: f32 = (low_bin..magnitudes.len())
+                .map(|k| (magnitudes[k] - previous[k]).max(0.0))
+                .sum();
+            flux.push(rise / total);
+        }
+        previous = Some(magnitudes);
+        start += HOP_SIZE;
+    }
+    flux
+}
+
+/// Count the spikes in an automated render against the static render
+fn evaluate(pattern: AutomationPattern, baseline: &[f32], flux: &[f32]) -> PatternResult {
+    let reference = baseline.iter().copied().fold(0.0f32, f32::max);
+    let threshold = (reference * SPIKE_RATIO).max(MIN_SPIKE_FLUX);
+    let spikes = flux.iter().filter(|&&f| f > threshold).count();
+    let worst = flux.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1));
+
+    let worst_flux_db = worst.map_or(0.0, |(_, &f)| 20.0 * (f.max(1e-9) / reference.max(1e-9)).log10());
+    PatternResult {
+        pattern,
+        spikes,
+        worst_flux_db,
+        worst_time_secs: worst
+            .filter(|_| spikes > 0)
+            .map(|(frame, _)| SETTLE_SECS + ((frame + 1) * HOP_SIZE) as f32 / SAMPLE_RATE as f32),
+        passed: spikes == 0,
+    }
+}
+
+/// Render the test tones while automating the parameter (held at the midpoint without a pattern)
+/// Returns the mono output after the settle time.
+fn render_pattern(
+    plugin: &mut PluginInstance,
+    param: &PluginParamInfo,
+    pattern: Option<AutomationPattern>,
+    (low, high): (f64, f64),
+) -> Result<Vec<f32>, String> {
+    plugin.reactivate(SAMPLE_RATE as f64, BLOCK_SIZE as u32)?;
+    let value_at = |secs: f32| match pattern {
+        Some(pattern) => low + (high - low) * pattern.position(secs),
+        None => (low + high) / 2.0,
+    };
+    plugin.queue_param_value(param.id, value_at(0.0));
+    // Instruments need a note to make sound; effects ignore it
+    plugin.send_note_on(48, 100);
+
+    let total = (PATTERN_SECS * SAMPLE_RATE as f32) as usize / BLOCK_SIZE;
+    let input = test_tones(total * BLOCK_SIZE);
+    let mut output = Vec::with_capacity(total * BLOCK_SIZE);
+    let mut processor = BlockProcessor::new(BLOCK_SIZE);
+    for (index, chunk) in input.chunks_exact(BLOCK_SIZE).enumerate() {
+        plugin.queue_param_value(param.id, value_at((index * BLOCK_SIZE) as f32 / SAMPLE_RATE as f32));
+        let processed = processor.process(plugin, chunk)?;
+        if plugin.has_crashed() {
+            return Err("Plugin crashed".to_string());
+        }
+        output.extend(processed.chunks_exact(2).map(|s| (s[0] + s[1]) * 0.5));
+    }
+    plugin.send_all_notes_off();
+
+    let settle = (SETTLE_SECS * SAMPLE_RATE as f32) as usize;
+    Ok(output.split_off(settle.min(output.len())))
+}
+
+/// Audit one parameter of a loaded plugin with the given patterns
+fn audit_param(
+    plugin: &mut PluginInstance,
+    bundle: &Path,
+    param: PluginParamInfo,
+    patterns: &[AutomationPattern],
+) -> Result<SmoothingAuditReport, String> {
+    let range = param.max_value - param.min_value;
+    let values = (
+        param.min_value + range * LOW_POSITION,
+        param.min_value + range * HIGH_POSITION,
+    );
+    log::info!("Auditing smoothing of '{}' in {:?}", param.name, bundle);
+
+    let baseline = spectral_flux(&render_pattern(plugin, &param, None, values)?);
+    let mut results = Vec::with_capacity(patterns.len());
+    for pattern in patterns {
+        let flux = spectral_flux(&render_pattern(plugin, &param, Some(*pattern), values)?);
+        results.push(evaluate(*pattern, &baseline, &flux));
+    }
+
+    Ok(SmoothingAuditReport {
+        plugin_path: bundle.to_string_lossy().to_string(),
+        param_id: param.id,
+        param_name: param.name,
+        low_value: values.0,
+        high_value: values.1,
+        passed: results.iter().all(|r| r.passed),
+        patterns: results,
+    })
+}
+
+/// Only continuous, writable parameters are smoothed
+fn is_smoothable(param: &PluginParamInfo) -> bool {
+    !param.is_stepped && !param.is_read_only
+}
+
+/// Audit one parameter of a specific .clap bundle
+pub(crate) async fn smoothing_audit_bundle(
+    bundle: PathBuf,
+    param_id: u32,
+    patterns: Vec<AutomationPattern>,
+) -> Result<SmoothingAuditReport, String> {
+    run_analysis_job("smoothing_audit", None, move |_job| {
+        let mut plugin = PluginInstance::load(Path::new(&bundle), SAMPLE_RATE as f64, BLOCK_SIZE as u32)?;
+        let param = plugin
+            .list_params()
+            .into_iter()
+            .find(|p| p.id == param_id)
+            .ok_or_else(|| format!("Plugin has no parameter {}", param_id))?;
+        if !is_smoothable(&param) {
+            plugin.stop_processing();
+            return Err(format!("'{}' is a stepped or read-only parameter - only continuous parameters are smoothed", param.name));
+        }
+
+        let report = audit_param(&mut plugin, &bundle, param, &patterns);
+        plugin.stop_processing();
+        report
+    })
+    .await
+}
+
+/// Audit every continuous parameter of a specific .clap bundle with all patterns
+pub(crate) async fn smoothing_audit_all_bundle(bundle: PathBuf) -> Result<Vec<SmoothingAuditReport>, String> {
+    run_analysis_job("smoothing_audit", None, move |_job| {
+        let mut plugin = PluginInstance::load(Path::new(&bundle), SAMPLE_RATE as f64, BLOCK_SIZE as u32)?;
+        let params: Vec<PluginParamInfo> = plugin.list_params().into_iter().filter(is_smoothable).collect();
+
+        let mut reports = Vec::with_capacity(params.len());
+        for param in params {
+            match audit_param(&mut plugin, &bundle, param, &AutomationPattern::ALL) {
+                Ok(report) => reports.push(report),
+                Err(e) => {
+                    plugin.stop_processing();
+                    return Err(e);
+                }
+            }
+        }
+        plugin.stop_processing();
+        Ok(reports)
+    })
+    .await
+}
+
+/// Record a parameter's failed patterns as the project's zipper noise issue
+pub(crate) fn sync_smoothing_issues(project_name: &str, report: &SmoothingAuditReport) {
+    let failed: Vec<String> = report
+        .patterns
+        .iter()
+        .filter(|r| !r.passed)
+        .map(|r| format!("{:?}", r.pattern).to_lowercase())
+        .collect();
+    let found = if failed.is_empty() {
+        Vec::new()
+    } else {
+        vec![ValidatorIssue {
+            key: "zipper".to_string(),
+            title: format!("Zipper noise when automating {}", report.param_name),
+            details: Some(format!(
+                "Clicks on {} automation - smooth the parameter (SmoothingStyle) and read it with next() per sample",
+                failed.join(", ")
+            )),
+        }]
+    };
+    // One validator per parameter so auditing another parameter doesn't resolve this one
+    sync_validator_issues(project_name, &format!("smoothing_audit:{}", report.param_id), found);
+}
+
+/// Automate one parameter and check its changes for zipper noise
+///
+/// `param_id` must be continuous and writable. Runs every pattern unless
+/// `patterns` is set; a project build gets one issue per audited parameter,
+/// while the previewed plugin (no project) is only reported on.
+#[tauri::command]
+pub async fn run_smoothing_audit(
+    project_name: Option<String>,
+    version: Option<u32>,
+    param_id: u32,
+    patterns: Option<Vec<AutomationPattern>>,
+) -> Result<SmoothingAuditReport, String> {
+    let bundle = resolve_plugin_bundle(project_name.clone(), version)?;
+    let patterns = patterns
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| AutomationPattern::ALL.to_vec());
+
+    let report = smoothing_audit_bundle(bundle, param_id, patterns).await?;
+
+    if let Some(project_name) = project_name {
+        sync_smoothing_issues(&project_name, &report);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tones scaled by a gain that follows the pattern per block, optionally through a 10 ms one-pole smoother
+    fn gain_render(pattern: Option<AutomationPattern>, smoothed: bool) -> Vec<f32> {
+        let coefficient = if smoothed { (-1.0 / (0.01 * SAMPLE_RATE as f32)).exp() } else { 0.0 };
+        let mut gain = 0.5;
+        test_tones(SAMPLE_RATE as usize)
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let block_start = (i / BLOCK_SIZE * BLOCK_SIZE) as f32 / SAMPLE_RATE as f32;
+                let target = pattern.map_or(0.5, |p| 0.25 + 0.5 * p.position(block_start) as f32);
+                gain = target + (gain - target) * coefficient;
+                s.left * gain
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unsmoothed_gain_has_spikes() {
+        let baseline = spectral_flux(&gain_render(None, false));
+        let pattern = AutomationPattern::Step;
+        let result = evaluate(pattern, &baseline, &spectral_flux(&gain_render(Some(pattern), false)));
+        assert!(!result.passed && result.spikes > 0);
+        assert!(result.worst_flux_db > 20.0);
+
+        let result = evaluate(pattern, &baseline, &spectral_flux(&gain_render(Some(pattern), true)));
+        assert!(result.passed, "smoothed step should pass ({} spikes)", result.spikes);
+    }
+
+    #[test]
+    fn test_pattern_positions() {
+        assert_eq!(AutomationPattern::Step.position(0.1), 0.0);
+        assert_eq!(AutomationPattern::Step.position(0.3), 1.0);
+        assert!((AutomationPattern::Ramp.position(0.125) - 0.5).abs() < 1e-6);
+        assert!((AutomationPattern::Ramp.position(0.25) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,322 @@
+//! Preset/state round-trip fuzzer
+//!
+//! Part of the offline validation suite next to the torture test. Each
+//! iteration randomizes the plugin's parameters, saves its state, renders the
+//! test audio, scrambles the parameters again and restores the saved state.
+//! Every parameter must come back to its saved value and the render must match
+//! the one made before the scramble - otherwise presets and DAW sessions won't
+//! recall the sound the user saved.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::analysis_jobs::run_analysis_job;
+use super::distortion::resolve_plugin_bundle;
+use super::issues::{sync_validator_issues, ValidatorIssue};
+use super::param_stress::{select_params, step_values, ParamValue, StressMode};
+use super::torture_test::{peak_difference_db, test_audio};
+use crate::audio::buffer::StereoSample;
+use crate::audio::offline::BlockProcessor;
+use crate::audio::plugin::{PluginInstance, PluginParamInfo};
+
+const SAMPLE_RATE: u32 = 48000;
+const BLOCK_SIZE: usize = 512;
+
+pub(crate) const DEFAULT_ITERATIONS: u32 = 20;
+const MAX_ITERATIONS: u32 = 500;
+
+/// Allowed parameter drift after a restore, as a fraction of the parameter's range
+/// (covers plugins that store values as f32)
+const PARAM_TOLERANCE: f64 = 1e-4;
+
+/// Peak difference between the saved and restored renders above this fails (dBFS)
+const AUDIO_THRESHOLD_DB: f32 = -80.0;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ParamMismatch {
+    pub id: u32,
+    pub name: String,
+    pub expected: f64,
+    /// None if the plugin stopped reporting a value for the parameter
+    pub actual: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RoundTripIteration {
+    pub iteration: u32,
+    pub state_bytes: usize,
+    pub param_mismatches: Vec<ParamMismatch>,
+    /// Peak difference between the render before and after the restore (dBFS)
+    pub audio_deviation_db: f32,
+    pub passed: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RoundTripReport {
+    pub plugin_path: String,
+    pub param_count: usize,
+    pub iterations: Vec<RoundTripIteration>,
+    pub failures: usize,
+    pub crashed: bool,
+    pub passed: bool,
+}
+
+/// Read the current value of every parameter
+fn capture_values(plugin: &PluginInstance, params: &[PluginParamInfo]) -> Vec<ParamValue> {
+    params
+        .iter()
+        .filter_map(|p| {
+            plugin
+                .get_param_value(p.id)
+                .map(|value| ParamValue { id: p.id, value })
+        })
+        .collect()
+}
+
+/// Parameters whose restored value differs from the saved one
+fn compare_params(params: &[PluginParamInfo], expected: &[ParamValue], actual: &[ParamValue]) -> Vec<ParamMismatch> {
+    expected
+        .iter()
+        .filter_map(|e| {
+            let param = params.iter().find(|p| p.id == e.id)?;
+            let tolerance = PARAM_TOLERANCE * (param.max_value - param.min_value).abs().max(1.0);
+            let actual = actual.iter().find(|a| a.id == e.id).map(|a| a.value);
+            match actual {
+                Some(value) if (value - e.value).abs() <= tolerance => None,
+                _ => Some(ParamMismatch {
+                    id: e.id,
+                    name: param.name.clone(),
+                    expected: e.value,
+                    actual,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Send parameter values to the plugin (they're delivered with a block of silence)
+fn apply_values(plugin: &mut PluginInstance, values: &[ParamValue]) -> Result<(), String> {
+    for v in values {
+        plugin.queue_param_value(v.id, v.value);
+    }
+    let silence = vec![0.0f32; BLOCK_SIZE * 2];
+    let mut out = vec![0.0f32; BLOCK_SIZE * 2];
+    plugin.process(&silence, &mut out)
+}
+
+/// Render the test audio from a freshly activated plugin so DSP history doesn't leak between renders
+fn render(plugin: &mut PluginInstance, input: &[StereoSample]) -> Result<Vec<f32>, String> {
+    plugin.reactivate(SAMPLE_RATE as f64, BLOCK_SIZE as u32)?;
+
+    let mut output = Vec::with_capacity(input.len() * 2);
+    let mut processor = BlockProcessor::new(BLOCK_SIZE);
+    for chunk in input.chunks_exact(BLOCK_SIZE) {
+        let processed = processor.process(plugin, chunk)?;
+        if plugin.has_crashed() {
+            return Err("Plugin crashed".to_string());
+        }
+        output.extend_from_slice(processed);
+    }
+    Ok(output)
+}
+
+/// Save, scramble, restore, and compare once
+fn run_iteration(
+    plugin: &mut PluginInstance,
+    params: &[PluginParamInfo],
+    input: &[StereoSample],
+    iteration: u32,
+    rng: &mut impl rand::Rng,
+) -> Result<RoundTripIteration, String> {
+    let randomize = StressMode::Randomize { ranges: Vec::new() };
+
+    // The first iteration checks the default state as loaded
+    if iteration > 0 {
+        apply_values(plugin, &step_values(&randomize, params, iteration, 0, rng))?;
+    }
+    let state = plugin.save_state()?;
+    let saved = capture_values(plugin, params);
+    let saved_render = render(plugin, input)?;
+
+    apply_values(plugin, &step_values(&randomize, params, iteration, 0, rng))?;
+    plugin.load_state(&state)?;
+
+    let param_mismatches = compare_params(params, &saved, &capture_values(plugin, params));
+    let audio_deviation_db = peak_difference_db(&saved_render, &render(plugin, input)?);
+
+    Ok(RoundTripIteration {
+        iteration,
+        state_bytes: state.len(),
+        passed: param_mismatches.is_empty() && audio_deviation_db <= AUDIO_THRESHOLD_DB,
+        param_mismatches,
+        audio_deviation_db,
+        error: None,
+    })
+}
+
+/// Issues for the project's tracker, one per kind of round-trip problem
+fn roundtrip_issues(report: &RoundTripReport) -> Vec<ValidatorIssue> {
+    let mut found = Vec::new();
+    if report.crashed {
+        found.push(ValidatorIssue {
+            key: "crash".to_string(),
+            title: "Crash while saving or restoring state".to_string(),
+            details: None,
+        });
+    }
+
+    let mut names: Vec<&str> = report
+        .iterations
+        .iter()
+        .flat_map(|i| i.param_mismatches.iter().map(|m| m.name.as_str()))
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    if !names.is_empty() {
+        found.push(ValidatorIssue {
+            key: "params".to_string(),
+            title: format!("Restoring a preset doesn't restore {} parameter(s)", names.len()),
+            details: Some(names.join(", ")),
+        });
+    }
+
+    if let Some(worst) = report
+        .iterations
+        .iter()
+        .filter(|i| i.error.is_none() && i.audio_deviation_db > AUDIO_THRESHOLD_DB)
+        .map(|i| i.audio_deviation_db)
+        .reduce(f32::max)
+    {
+        found.push(ValidatorIssue {
+            key: "audio".to_string(),
+            title: "Sound changes after restoring a preset".to_string(),
+            details: Some(format!("Output differs by up to {:.1} dBFS", worst)),
+        });
+    }
+
+    if let Some(error) = report.iterations.iter().find_map(|i| i.error.as_ref()) {
+        found.push(ValidatorIssue {
+            key: "error".to_string(),
+            title: "State save/restore fails".to_string(),
+            details: Some(error.clone()),
+        });
+    }
+    found
+}
+
+/// Run the state round-trip test on a specific .clap bundle
+pub(crate) async fn state_roundtrip_bundle(bundle: PathBuf, iterations: u32) -> Result<RoundTripReport, String> {
+    run_analysis_job("state_roundtrip", None, move |_job| {
+        let mut plugin = PluginInstance::load(Path::new(&bundle), SAMPLE_RATE as f64, BLOCK_SIZE as u32)?;
+        if !plugin.has_state() {
+            return Err("Plugin does not support saving state".to_string());
+        }
+        let params = select_params(plugin.list_params(), &None);
+        log::info!("State round-trip testing {:?}: {} params", bundle, params.len());
+
+        let input = test_audio(SAMPLE_RATE);
+        let mut rng = rand::thread_rng();
+        let mut results = Vec::with_capacity(iterations as usize);
+        let mut crashed = false;
+
+        for iteration in 0..iterations {
+            let result = run_iteration(&mut plugin, &params, &input, iteration, &mut rng).unwrap_or_else(|e| {
+                RoundTripIteration {
+                    iteration,
+                    state_bytes: 0,
+                    param_mismatches: Vec::new(),
+                    audio_deviation_db: -200.0,
+                    passed: false,
+                    error: Some(e),
+                }
+            });
+            results.push(result);
+
+            if plugin.has_crashed() {
+                log::error!("Plugin crashed during state round-trip test (iteration {})", iteration);
+                crashed = true;
+                break;
+            }
+        }
+
+        plugin.stop_processing();
+
+        let failures = results.iter().filter(|r| !r.passed).count();
+        log::info!("State round-trip test finished: {} iterations, {} failures", results.len(), failures);
+
+        Ok(RoundTripReport {
+            plugin_path: bundle.to_string_lossy().to_string(),
+            param_count: params.len(),
+            iterations: results,
+            failures,
+            crashed,
+            passed: failures == 0 && !crashed,
+        })
+    })
+    .await
+}
+
+/// Fuzz the plugin's state save/restore
+///
+/// Runs `iterations` save/scramble/restore cycles (20 by default, at most 500)
+/// and reports parameters that didn't come back and renders that changed. With
+/// no project the plugin in the preview is fuzzed and no issues are filed.
+#[tauri::command]
+pub async fn run_state_roundtrip_test(
+    project_name: Option<String>,
+    version: Option<u32>,
+    iterations: Option<u32>,
+) -> Result<RoundTripReport, String> {
+    let bundle = resolve_plugin_bundle(project_name.clone(), version)?;
+    let iterations = iterations.unwrap_or(DEFAULT_ITERATIONS).clamp(1, MAX_ITERATIONS);
+
+    let report = state_roundtrip_bundle(bundle, iterations).await?;
+
+    if let Some(project_name) = project_name {
+        sync_validator_issues(&project_name, "state_roundtrip", roundtrip_issues(&report));
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(id: u32, max_value: f64) -> PluginParamInfo {
+        PluginParamInfo {
+            id,
+            name: format!("Param {}", id),
+            module: String::new(),
+            min_value: 0.0,
+            max_value,
+            default_value: 0.0,
+            is_stepped: false,
+            is_read_only: false,
+            is_bypass: false,
+        }
+    }
+
+    #[test]
+    fn test_compare_params() {
+        let params = vec![param(1, 1.0), param(2, 20000.0), param(3, 1.0)];
+        let expected = vec![
+            ParamValue { id: 1, value: 0.5 },
+            ParamValue { id: 2, value: 1000.0 },
+            ParamValue { id: 3, value: 0.25 },
+        ];
+        // f32 round-off is tolerated, a real change and a missing value are not
+        let actual = vec![
+            ParamValue { id: 1, value: 0.5 + 1e-7 },
+            ParamValue { id: 2, value: 1000.5 },
+        ];
+
+        let mismatches = compare_params(&params, &expected, &actual);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].id, 3);
+        assert_eq!(mismatches[0].actual, None);
+
+        let changed = vec![ParamValue { id: 1, value: 0.6 }];
+        assert_eq!(compare_params(&params[..1], &expected[..1], &changed)[0].actual, Some(0.6));
+    }
+}
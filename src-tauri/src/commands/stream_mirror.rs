@@ -0,0 +1,200 @@
+//! Mirroring install and build progress outside the window
+//!
+//! With `stream_mirror` on in settings, `install-stream` and `build-stream`
+//! events are also reported as OS notifications (when an install needs the
+//! user - a password prompt, a sign-in - and once when an install or build
+//! ends, with a short summary or the error that stopped it) and written to `~/VSTWorkshop/stream-status.json`, so screen readers, scripts, and
+//! users who switched to another app still find out.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+use tauri::Listener;
+use tauri_plugin_notification::NotificationExt;
+
+use super::i18n::tr;
+use super::projects::get_workspace_path;
+use super::settings::current_settings;
+
+const STATUS_FILE: &str = "stream-status.json";
+
+/// Output lines only refresh the status file this often
+const OUTPUT_INTERVAL: Duration = Duration::from_secs(1);
+
+static LAST_OUTPUT_WRITE: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+/// Error reported by each stream's current run; it becomes the body of the
+/// notification when the run ends, rather than a notification of its own
+static RUN_ERRORS: Lazy<Mutex<HashMap<StreamSource, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamSource {
+    Install,
+    Build,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamState {
+    Running,
+    ActionRequired,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamStatus {
+    pub source: StreamSource,
+    pub state: StreamState,
+    pub message: String,
+    pub updated_at: String,
+}
+
+/// Status for a stream event, and whether it's worth a notification
+/// (only when the user has to act, or the run is over; errors wait for the end)
+fn interpret(payload: &Value) -> Option<(StreamState, String, bool)> {
+    let text = |field: &str| payload.get(field).and_then(Value::as_str).unwrap_or_default().to_string();
+    match payload.get("type")?.as_str()? {
+        "start" => Some((StreamState::Running, text("step"), false)),
+        "output" => Some((StreamState::Running, text("line"), false)),
+        "action_required" => Some((StreamState::ActionRequired, text("message"), true)),
+        "error" => Some((StreamState::Failed, text("message"), false)),
+        "done" => {
+            let success = payload.get("success").and_then(Value::as_bool).unwrap_or(false);
+            let state = if success { StreamState::Succeeded } else { StreamState::Failed };
+            Some((state, String::new(), true))
+        }
+        _ => None,
+    }
+}
+
+fn notification_title(source: StreamSource, state: StreamState) -> String {
+    tr(match (source, state) {
+        (_, StreamState::ActionRequired) => "mirror.action_required",
+        (StreamSource::Install, StreamState::Failed) => "mirror.install.failed",
+        (StreamSource::Install, _) => "mirror.install.done",
+        (StreamSource::Build, StreamState::Failed) => "mirror.build.failed",
+        (StreamSource::Build, _) => "mirror.build.done",
+    })
+}
+
+/// What a finished run did, in words: the run's error when it failed with one
+fn summary(source: StreamSource, state: StreamState, error: Option<String>) -> String {
+    if state == StreamState::Failed {
+        if let Some(error) = error.filter(|e| !e.trim().is_empty()) {
+            return error;
+        }
+    }
+    tr(match (source, state) {
+        (StreamSource::Install, StreamState::Failed) => "mirror.install.failed_body",
+        (StreamSource::Install, _) => "mirror.install.done_body",
+        (StreamSource::Build, StreamState::Failed) => "mirror.build.failed_body",
+        (StreamSource::Build, _) => "mirror.build.done_body",
+    })
+}
+
+fn write_status(status: &StreamStatus) {
+    let path = get_workspace_path().join(STATUS_FILE);
+    let tmp = path.with_extension("json.tmp");
+    let result = serde_json::to_string_pretty(status)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&tmp, json).map_err(|e| e.to_string()))
+        .and_then(|_| fs::rename(&tmp, &path).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        log::warn!("Failed to write {}: {}", STATUS_FILE, e);
+    }
+}
+
+fn mirror(app_handle: &tauri::AppHandle, source: StreamSource, payload: &str) {
+    if !current_settings().stream_mirror {
+        return;
+    }
+    let Ok(payload) = serde_json::from_str::<Value>(payload) else {
+        return;
+    };
+    let Some((state, mut message, notify)) = interpret(&payload) else {
+        return;
+    };
+
+    match (state, notify) {
+        (StreamState::Running, _) if payload.get("type").and_then(Value::as_str) == Some("start") => {
+            RUN_ERRORS.lock().remove(&source);
+        }
+        (StreamState::Failed, false) => {
+            RUN_ERRORS.lock().insert(source, message.clone());
+        }
+        (StreamState::Succeeded | StreamState::Failed, true) => {
+            message = summary(source, state, RUN_ERRORS.lock().remove(&source));
+        }
+        _ => {}
+    }
+
+    if state == StreamState::Running {
+        let mut last = LAST_OUTPUT_WRITE.lock();
+        if last.is_some_and(|at| at.elapsed() < OUTPUT_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+    }
+    write_status(&StreamStatus {
+        source,
+        state,
+        message: message.clone(),
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    });
+
+    if notify {
+        let result = app_handle
+            .notification()
+            .builder()
+            .title(notification_title(source, state))
+            .body(message)
+            .show();
+        if let Err(e) = result {
+            log::warn!("Failed to show notification: {}", e);
+        }
+    }
+}
+
+/// Start mirroring stream events (checks the setting per event, so it can be toggled live)
+pub fn init_stream_mirror(app_handle: tauri::AppHandle) {
+    for (event, source) in [("install-stream", StreamSource::Install), ("build-stream", StreamSource::Build)] {
+        let handle = app_handle.clone();
+        app_handle.listen_any(event, move |event| mirror(&handle, source, event.payload()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_interpret() {
+        let action = json!({ "type": "action_required", "action": "password", "message": "Enter your Mac password" });
+        assert_eq!(
+            interpret(&action),
+            Some((StreamState::ActionRequired, "Enter your Mac password".to_string(), true))
+        );
+        let output = json!({ "type": "output", "line": "Compiling gain v0.1.0" });
+        assert_eq!(
+            interpret(&output),
+            Some((StreamState::Running, "Compiling gain v0.1.0".to_string(), false))
+        );
+        let error = json!({ "type": "error", "message": "linker failed" });
+        assert_eq!(interpret(&error), Some((StreamState::Failed, "linker failed".to_string(), false)));
+        let done = json!({ "type": "done", "success": false, "output_path": null });
+        assert_eq!(interpret(&done), Some((StreamState::Failed, String::new(), true)));
+        assert_eq!(
+            summary(StreamSource::Build, StreamState::Failed, Some("linker failed".to_string())),
+            "linker failed"
+        );
+        assert_eq!(interpret(&json!({ "type": "progress" })), None);
+    }
+}
@@ -0,0 +1,399 @@
+//! Background queue of Claude tasks
+//!
+//! Lets the user line up several steps (e.g. "add ADSR", build, "write
+//! presets") that run one after another in the background instead of waiting
+//! on each chat turn. Chat tasks go through the same session path as
+//! `send_to_claude`, taking turns with interactive messages on the project, so
+//! they stream, commit, and record activity exactly like them.
+//! Every status change is emitted as a "claude-task-update" event.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::Emitter;
+
+/// Finished tasks kept for the UI - older ones are dropped
+const MAX_FINISHED_TASKS: usize = 50;
+
+static TASKS: Mutex<Vec<ClaudeTask>> = Mutex::new(Vec::new());
+static NEXT_TASK_ID: AtomicU64 = AtomicU64::new(1);
+/// Set while the worker is draining the queue
+static WORKER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClaudeTaskKind {
+    /// Send a message to Claude, like typing it in the chat
+    Chat {
+        message: String,
+        description: String,
+        #[serde(default)]
+        model: Option<String>,
+        #[serde(default)]
+        custom_instructions: Option<String>,
+        #[serde(default)]
+        agent_verbosity: Option<String>,
+    },
+    /// Build the project into the given version's output folder
+    Build { version: u32 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaudeTaskStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ClaudeTask {
+    pub id: u64,
+    pub project_name: String,
+    pub project_path: String,
+    pub kind: ClaudeTaskKind,
+    pub status: ClaudeTaskStatus,
+    /// Claude's reply or the build output path
+    pub result: Option<String>,
+    pub commit_hash: Option<String>,
+    pub error: Option<String>,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    /// Run even when an earlier task for the project failed (by default a
+    /// failure cancels the project's remaining queue)
+    pub continue_on_failure: bool,
+}
+
+impl ClaudeTask {
+    fn is_finished(&self) -> bool {
+        !matches!(self.status, ClaudeTaskStatus::Queued | ClaudeTaskStatus::Running)
+    }
+}
+
+/// Drop the oldest finished tasks beyond the limit (queued/running are always kept)
+fn prune_finished(tasks: &mut Vec<ClaudeTask>) {
+    let finished = tasks.iter().filter(|t| t.is_finished()).count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_TASKS);
+    tasks.retain(|t| {
+        if excess > 0 && t.is_finished() {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Cancel the project's queued tasks that follow a failed one, unless they
+/// opted to run anyway; returns the cancelled tasks
+fn skip_after_failure(tasks: &mut [ClaudeTask], failed: &ClaudeTask) -> Vec<ClaudeTask> {
+    let mut skipped = Vec::new();
+    for task in tasks.iter_mut() {
+        if task.project_name != failed.project_name
+            || task.status != ClaudeTaskStatus::Queued
+            || task.continue_on_failure
+        {
+            continue;
+        }
+        task.status = ClaudeTaskStatus::Cancelled;
+        task.error = Some(format!("Skipped because task {} failed", failed.id));
+        task.finished_at = Some(chrono::Local::now().to_rfc3339());
+        skipped.push(task.clone());
+    }
+    skipped
+}
+
+/// Mark the next queued task as running and return it
+fn take_next(tasks: &mut [ClaudeTask]) -> Option<ClaudeTask> {
+    let task = tasks.iter_mut().find(|t| t.status == ClaudeTaskStatus::Queued)?;
+    task.status = ClaudeTaskStatus::Running;
+    task.started_at = Some(chrono::Local::now().to_rfc3339());
+    Some(task.clone())
+}
+
+fn emit_update(window: &tauri::Window, task: &ClaudeTask) {
+    let _ = window.emit("claude-task-update", task);
+}
+
+/// Whether a task was cancelled (checked again once its Claude process is
+/// registered, since a cancel before that has no process to stop)
+pub(crate) fn is_task_cancelled(task_id: u64) -> bool {
+    TASKS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|t| t.id == task_id && t.status == ClaudeTaskStatus::Cancelled)
+}
+
+/// Run one task to completion: Ok((result, commit hash)) or Err(error)
+async fn run_task(task: &ClaudeTask, window: &tauri::Window) -> Result<(Option<String>, Option<String>), String> {
+    match &task.kind {
+        ClaudeTaskKind::Chat {
+            message,
+            description,
+            model,
+            custom_instructions,
+            agent_verbosity,
+        } => {
+            // Waits for an interactive message on the same project to finish
+            let session = super::claude::project_session_lock(&task.project_path);
+            let _session = session.lock().await;
+            if is_task_cancelled(task.id) {
+                return Err("Cancelled".to_string());
+            }
+
+            let response = super::claude::run_claude_session(
+                task.project_path.clone(),
+                task.project_name.clone(),
+                description.clone(),
+                message.clone(),
+                model.clone(),
+                custom_instructions.clone(),
+                agent_verbosity.clone(),
+                None,
+                Some(task.id),
+                window.clone(),
+            )
+            .await?;
+            Ok((Some(response.content), response.commit_hash))
+        }
+        ClaudeTaskKind::Build { version } => {
+            let result = super::build::build_project(task.project_name.clone(), *version, window.clone()).await?;
+            if result.success {
+                Ok((result.output_path, None))
+            } else {
+                Err(result.error.unwrap_or_else(|| "Build failed".to_string()))
+            }
+        }
+    }
+}
+
+/// Start the worker if it isn't already draining the queue
+fn ensure_worker(window: tauri::Window) {
+    if WORKER_RUNNING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let next = take_next(&mut TASKS.lock().unwrap());
+            let Some(task) = next else {
+                WORKER_RUNNING.store(false, Ordering::SeqCst);
+                // A task queued between the check and the store would otherwise sit idle
+                let pending = TASKS.lock().unwrap().iter().any(|t| t.status == ClaudeTaskStatus::Queued);
+                if pending
+                    && WORKER_RUNNING
+                        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+                        .is_ok()
+                {
+                    continue;
+                }
+                break;
+            };
+
+            log::info!("Running Claude task {} for {}", task.id, task.project_name);
+            emit_update(&window, &task);
+            let outcome = run_task(&task, &window).await;
+
+            let (finished, skipped) = {
+                let mut tasks = TASKS.lock().unwrap();
+                let Some(entry) = tasks.iter_mut().find(|t| t.id == task.id) else {
+                    continue;
+                };
+                // A cancelled task keeps its status even though send_to_claude returned an error
+                if entry.status == ClaudeTaskStatus::Running {
+                    match outcome {
+                        Ok((result, commit_hash)) => {
+                            entry.status = ClaudeTaskStatus::Succeeded;
+                            entry.result = result;
+                            entry.commit_hash = commit_hash;
+                        }
+                        Err(e) => {
+                            entry.status = ClaudeTaskStatus::Failed;
+                            entry.error = Some(e);
+                        }
+                    }
+                }
+                entry.finished_at = Some(chrono::Local::now().to_rfc3339());
+                let finished = entry.clone();
+                let skipped = if finished.status == ClaudeTaskStatus::Failed {
+                    skip_after_failure(&mut tasks, &finished)
+                } else {
+                    Vec::new()
+                };
+                prune_finished(&mut tasks);
+                (finished, skipped)
+            };
+            log::info!("Claude task {} finished: {:?}", finished.id, finished.status);
+            emit_update(&window, &finished);
+            for task in &skipped {
+                emit_update(&window, task);
+            }
+        }
+    });
+}
+
+/// Add a task to the background queue
+/// Tasks run one at a time in the order they were queued. A failed task
+/// cancels the rest of its project's queue unless `continue_on_failure` is set.
+#[tauri::command]
+pub async fn queue_claude_task(
+    project_name: String,
+    project_path: String,
+    kind: ClaudeTaskKind,
+    continue_on_failure: Option<bool>,
+    window: tauri::Window,
+) -> Result<ClaudeTask, String> {
+    if let ClaudeTaskKind::Chat { message, .. } = &kind {
+        if message.trim().is_empty() {
+            return Err("Task message cannot be empty".to_string());
+        }
+    }
+
+    let task = ClaudeTask {
+        id: NEXT_TASK_ID.fetch_add(1, Ordering::SeqCst),
+        project_name,
+        project_path,
+        kind,
+        status: ClaudeTaskStatus::Queued,
+        result: None,
+        commit_hash: None,
+        error: None,
+        queued_at: chrono::Local::now().to_rfc3339(),
+        started_at: None,
+        finished_at: None,
+        continue_on_failure: continue_on_failure.unwrap_or(false),
+    };
+    TASKS.lock().unwrap().push(task.clone());
+    emit_update(&window, &task);
+
+    ensure_worker(window);
+    Ok(task)
+}
+
+/// List queued, running, and recently finished tasks (optionally for one project)
+#[tauri::command]
+pub async fn list_claude_tasks(project_name: Option<String>) -> Result<Vec<ClaudeTask>, String> {
+    Ok(TASKS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|t| project_name.as_ref().map_or(true, |p| &t.project_name == p))
+        .cloned()
+        .collect())
+}
+
+/// Cancel a task
+/// Queued tasks are skipped; a running chat task has its Claude session stopped.
+/// A build that has already started runs to completion.
+#[tauri::command]
+pub async fn cancel_claude_task(task_id: u64, window: tauri::Window) -> Result<ClaudeTask, String> {
+    let task = {
+        let mut tasks = TASKS.lock().unwrap();
+        let task = tasks
+            .iter_mut()
+            .find(|t| t.id == task_id)
+            .ok_or_else(|| format!("Task {} not found", task_id))?;
+
+        match (&task.status, &task.kind) {
+            (ClaudeTaskStatus::Queued, _) => {
+                task.finished_at = Some(chrono::Local::now().to_rfc3339());
+            }
+            (ClaudeTaskStatus::Running, ClaudeTaskKind::Chat { .. }) => {
+                // Still waiting for the project's session: it sees the status and skips
+                super::claude::terminate_claude_process(&task.project_path, Some(task.id));
+            }
+            (ClaudeTaskStatus::Running, ClaudeTaskKind::Build { .. }) => {
+                return Err("A build can't be cancelled once it has started".to_string());
+            }
+            _ => return Err("Task has already finished".to_string()),
+        }
+        task.status = ClaudeTaskStatus::Cancelled;
+        task.clone()
+    };
+
+    emit_update(&window, &task);
+    Ok(task)
+}
+
+/// Remove finished tasks from the list
+#[tauri::command]
+pub async fn clear_finished_claude_tasks() -> Result<(), String> {
+    TASKS.lock().unwrap().retain(|t| !t.is_finished());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: u64, status: ClaudeTaskStatus) -> ClaudeTask {
+        ClaudeTask {
+            id,
+            project_name: "test".to_string(),
+            project_path: "/tmp/test".to_string(),
+            kind: ClaudeTaskKind::Build { version: 1 },
+            status,
+            result: None,
+            commit_hash: None,
+            error: None,
+            queued_at: String::new(),
+            started_at: None,
+            finished_at: None,
+            continue_on_failure: false,
+        }
+    }
+
+    #[test]
+    fn test_take_next_runs_in_order() {
+        let mut tasks = vec![
+            task(1, ClaudeTaskStatus::Succeeded),
+            task(2, ClaudeTaskStatus::Cancelled),
+            task(3, ClaudeTaskStatus::Queued),
+            task(4, ClaudeTaskStatus::Queued),
+        ];
+        assert_eq!(take_next(&mut tasks).unwrap().id, 3);
+        assert_eq!(tasks[2].status, ClaudeTaskStatus::Running);
+        assert_eq!(take_next(&mut tasks).unwrap().id, 4);
+        assert!(take_next(&mut tasks).is_none());
+    }
+
+    #[test]
+    fn test_failure_skips_the_rest_of_the_project() {
+        let mut tasks = vec![
+            task(1, ClaudeTaskStatus::Failed),
+            task(2, ClaudeTaskStatus::Queued),
+            task(3, ClaudeTaskStatus::Queued),
+            task(4, ClaudeTaskStatus::Queued),
+        ];
+        tasks[2].continue_on_failure = true;
+        tasks[3].project_name = "other".to_string();
+        let failed = tasks[0].clone();
+
+        let skipped: Vec<u64> = skip_after_failure(&mut tasks, &failed).iter().map(|t| t.id).collect();
+        assert_eq!(skipped, vec![2]);
+        assert_eq!(tasks[1].status, ClaudeTaskStatus::Cancelled);
+        assert_eq!(tasks[2].status, ClaudeTaskStatus::Queued);
+        assert_eq!(tasks[3].status, ClaudeTaskStatus::Queued);
+    }
+
+    #[test]
+    fn test_prune_keeps_pending_tasks() {
+        let mut tasks: Vec<ClaudeTask> = (0..MAX_FINISHED_TASKS as u64 + 5)
+            .map(|id| task(id, ClaudeTaskStatus::Succeeded))
+            .collect();
+        tasks.insert(0, task(999, ClaudeTaskStatus::Queued));
+        prune_finished(&mut tasks);
+
+        assert_eq!(tasks.len(), MAX_FINISHED_TASKS + 1);
+        assert_eq!(tasks[0].id, 999);
+        // The oldest finished tasks were dropped
+        assert_eq!(tasks[1].id, 5);
+    }
+}
@@ -0,0 +1,459 @@
+//! Block-size and sample-rate torture test
+//!
+//! Re-activates a plugin at every common sample rate and pushes the same test
+//! audio through it at a range of block sizes - including odd ones and an
+//! irregular sequence like hosts produce around loop points and automation.
+//! Crashes, NaN/Inf output, and process errors fail a run. Output that differs
+//! from the 512-sample run at the same rate, or clicks/overloads the reference
+//! run didn't have, are reported as warnings - the classic "works at 512 but
+//! clicks at 64" bug.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+
+use super::analysis_jobs::run_analysis_job;
+use super::distortion::resolve_plugin_bundle;
+use super::issues::{sync_validator_issues, ValidatorIssue};
+use crate::audio::buffer::StereoSample;
+use crate::audio::offline::BlockProcessor;
+use crate::audio::plugin::PluginInstance;
+use crate::audio::signals::{SignalConfig, SignalGenerator, SignalType};
+use crate::audio::watchdog::DspHealth;
+
+const SAMPLE_RATES: [u32; 5] = [44100, 48000, 88200, 96000, 192000];
+
+/// Fixed block sizes, including odd and prime sizes that expose buffer-math bugs
+const BLOCK_SIZES: [u32; 17] = [1, 2, 3, 7, 16, 31, 32, 64, 100, 128, 256, 441, 512, 1000, 1024, 2048, 4096];
+
+/// Largest block any run uses (the plugin is activated with this max)
+const MAX_BLOCK_SIZE: u32 = 4096;
+
+/// Block size every other run is compared against
+const REFERENCE_BLOCK_SIZE: u32 = 512;
+
+/// Length of the test audio per run (seconds)
+const RUN_SECS: f32 = 0.5;
+
+/// Peak difference from the reference run above this is a warning (dBFS)
+const DEVIATION_THRESHOLD_DB: f32 = -60.0;
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Pass,
+    Warning,
+    Fail,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TortureRun {
+    pub sample_rate: u32,
+    /// Fixed block size, or None for the irregular sequence
+    pub block_size: Option<u32>,
+    pub status: RunStatus,
+    pub issues: Vec<String>,
+    /// Short label for the first failure (e.g. "NaN/Inf output")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+    /// Peak difference from the reference block size at this rate (dBFS)
+    pub max_deviation_db: f32,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TortureProgress {
+    pub run: usize,
+    pub total: usize,
+    pub sample_rate: u32,
+    pub block_size: Option<u32>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct TortureReport {
+    pub plugin_path: String,
+    pub runs: Vec<TortureRun>,
+    pub failures: usize,
+    pub warnings: usize,
+    /// Plugin crashed - remaining runs were skipped
+    pub crashed: bool,
+    pub passed: bool,
+}
+
+/// Reproducible pseudo-random block sizes (1..=max) that add up to `total_frames`
+fn irregular_blocks(total_frames: usize, max: u32, seed: u32) -> Vec<usize> {
+    let mut state = seed.max(1);
+    let mut blocks = Vec::new();
+    let mut remaining = total_frames;
+    while remaining > 0 {
+        // xorshift32
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        // Favour small blocks - that's where bugs hide
+        let size = match state % 4 {
+            0 => 1 + (state >> 8) % 16,
+            1 => 1 + (state >> 8) % 256,
+            _ => 1 + (state >> 8) % max,
+        } as usize;
+        let size = size.min(remaining);
+        blocks.push(size);
+        remaining -= size;
+    }
+    blocks
+}
+
+/// Short sweep followed by pink noise
+pub(crate) fn test_audio(sample_rate: u32) -> Vec<StereoSample> {
+    let mut generator = SignalGenerator::new(sample_rate);
+    let half = (RUN_SECS * 0.5 * sample_rate as f32) as usize;
+    let mut out = Vec::with_capacity(half * 2);
+
+    generator.set_config(SignalConfig {
+        signal_type: SignalType::Sweep,
+        amplitude: 0.5,
+        sweep_start: 50.0,
+        sweep_end: 15000.0,
+        sweep_duration: RUN_SECS * 0.5,
+        ..SignalConfig::default()
+    });
+    out.extend((0..half).map(|_| generator.next_sample()));
+
+    generator.set_config(SignalConfig {
+        signal_type: SignalType::PinkNoise,
+        amplitude: 0.3,
+        ..SignalConfig::default()
+    });
+    out.extend((0..half).map(|_| generator.next_sample()));
+    out
+}
+
+/// Peak absolute difference between two interleaved renders (dBFS)
+pub(crate) fn peak_difference_db(a: &[f32], b: &[f32]) -> f32 {
+    let peak = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).abs())
+        .filter(|d| d.is_finite())
+        .fold(0.0f32, f32::max);
+    if peak > 0.0 {
+        (20.0 * peak.log10()).max(-200.0)
+    } else {
+        -200.0
+    }
+}
+
+/// Output and problems from one pass of the test audio
+#[derive(Clone)]
+struct RunOutput {
+    output: Vec<f32>,
+    process_error: Option<String>,
+    crashed: bool,
+    non_finite: u64,
+    clicks: u64,
+    overloads: u64,
+}
+
+/// Re-activate the plugin and push the test audio through in the given blocks
+fn run_blocks(plugin: &mut PluginInstance, input: &[StereoSample], sample_rate: u32, blocks: &[usize]) -> Result<RunOutput, String> {
+    plugin.reactivate(sample_rate as f64, MAX_BLOCK_SIZE)?;
+
+    let health = DspHealth::new();
+    let mut output = Vec::with_capacity(input.len() * 2);
+    let mut processor = BlockProcessor::new(MAX_BLOCK_SIZE as usize);
+    let mut process_error = None;
+
+    let mut pos = 0;
+    for &frames in blocks {
+        let processed = match processor.process(plugin, &input[pos..pos + frames]) {
+            Ok(processed) => processed,
+            Err(e) => {
+                process_error = Some(e);
+                break;
+            }
+        };
+        if plugin.has_crashed() {
+            break;
+        }
+        health.check(processed, 2);
+        output.extend_from_slice(processed);
+        pos += frames;
+    }
+
+    let report = health.snapshot();
+    Ok(RunOutput {
+        output,
+        process_error,
+        crashed: plugin.has_crashed(),
+        non_finite: report.non_finite,
+        clicks: report.clicks,
+        overloads: report.overloads,
+    })
+}
+
+/// Judge a run against the reference run at the same sample rate
+fn evaluate_run(sample_rate: u32, block_size: Option<u32>, run: &RunOutput, reference: Option<&RunOutput>) -> TortureRun {
+    let mut issues = Vec::new();
+    let mut status = RunStatus::Pass;
+    let mut failure = None;
+
+    if run.crashed {
+        issues.push("Plugin crashed".to_string());
+        failure = failure.or(Some("Crash"));
+        status = RunStatus::Fail;
+    }
+    if let Some(e) = &run.process_error {
+        issues.push(format!("Process error: {}", e));
+        failure = failure.or(Some("Process error"));
+        status = RunStatus::Fail;
+    }
+    if run.non_finite > 0 {
+        issues.push(format!("NaN/Inf output in {} buffer(s)", run.non_finite));
+        failure = failure.or(Some("NaN/Inf output"));
+        status = RunStatus::Fail;
+    }
+
+    let mut max_deviation_db = -200.0;
+    if let Some(reference) = reference {
+        max_deviation_db = peak_difference_db(&run.output, &reference.output);
+        if status != RunStatus::Fail {
+            if max_deviation_db > DEVIATION_THRESHOLD_DB {
+                issues.push(format!(
+                    "Output differs from the {}-sample run by up to {:.1} dBFS",
+                    REFERENCE_BLOCK_SIZE, max_deviation_db
+                ));
+                status = RunStatus::Warning;
+            }
+            if run.clicks > reference.clicks {
+                issues.push(format!("{} click(s) not present at {} samples", run.clicks - reference.clicks, REFERENCE_BLOCK_SIZE));
+                status = RunStatus::Warning;
+            }
+            if run.overloads > reference.overloads {
+                issues.push("Runaway output level".to_string());
+                status = RunStatus::Warning;
+            }
+        }
+    }
+
+    TortureRun {
+        sample_rate,
+        block_size,
+        status,
+        issues,
+        failure: failure.map(String::from),
+        max_deviation_db,
+    }
+}
+
+/// One issue per kind of failure and sample rate, listing the block sizes it happened at
+fn failure_issues(runs: &[TortureRun]) -> Vec<ValidatorIssue> {
+    let mut found: Vec<ValidatorIssue> = Vec::new();
+    for run in runs {
+        let Some(failure) = &run.failure else {
+            continue;
+        };
+        let block = run.block_size.map_or("irregular".to_string(), |b| b.to_string());
+        let key = format!("{}@{}", failure, run.sample_rate);
+        match found.iter_mut().find(|f| f.key == key) {
+            Some(issue) => {
+                if let Some(details) = issue.details.as_mut() {
+                    details.push_str(&format!(", {}", block));
+                }
+            }
+            None => found.push(ValidatorIssue {
+                title: format!("{} at {} kHz", failure, run.sample_rate as f32 / 1000.0),
+                details: Some(format!("Torture test block sizes: {}", block)),
+                key,
+            }),
+        }
+    }
+    found
+}
+
+/// Run the torture test on a specific .clap bundle
+/// With an app handle, emits "torture-test-progress" before each run.
+pub(crate) async fn torture_test_bundle(
+    bundle: PathBuf,
+    app_handle: Option<tauri::AppHandle>,
+) -> Result<TortureReport, String> {
+    run_analysis_job("torture_test", app_handle.clone(), move |job| {
+        let mut plugin = PluginInstance::load(Path::new(&bundle), SAMPLE_RATES[0] as f64, MAX_BLOCK_SIZE)?;
+        log::info!("Torture testing {:?}", bundle);
+
+        let total = SAMPLE_RATES.len() * (BLOCK_SIZES.len() + 1);
+        let mut runs = Vec::with_capacity(total);
+        let mut crashed = false;
+
+        'rates: for &sample_rate in &SAMPLE_RATES {
+            let input = test_audio(sample_rate);
+            let reference = run_blocks(
+                &mut plugin,
+                &input,
+                sample_rate,
+                &vec![REFERENCE_BLOCK_SIZE as usize; input.len() / REFERENCE_BLOCK_SIZE as usize],
+            )?;
+
+            let sizes = BLOCK_SIZES.iter().map(|&b| Some(b)).chain(std::iter::once(None));
+            for block_size in sizes {
+                job.progress(runs.len(), total);
+                if let Some(app_handle) = &app_handle {
+                    let _ = app_handle.emit(
+                        "torture-test-progress",
+                        TortureProgress {
+                            run: runs.len() + 1,
+                            total,
+                            sample_rate,
+                            block_size,
+                        },
+                    );
+                }
+
+                let run = if block_size == Some(REFERENCE_BLOCK_SIZE) || reference.crashed {
+                    evaluate_run(sample_rate, Some(REFERENCE_BLOCK_SIZE), &reference, None)
+                } else {
+                    let blocks = match block_size {
+                        Some(size) => {
+                            let size = size as usize;
+                            let mut blocks = vec![size; input.len() / size];
+                            if input.len() % size > 0 {
+                                blocks.push(input.len() % size);
+                            }
+                            blocks
+                        }
+                        None => irregular_blocks(input.len(), MAX_BLOCK_SIZE, sample_rate),
+                    };
+                    let mut output = run_blocks(&mut plugin, &input, sample_rate, &blocks)?;
+                    // The reference drops the last partial block - compare the common length
+                    output.output.truncate(reference.output.len());
+                    evaluate_run(sample_rate, block_size, &output, Some(&reference))
+                };
+
+                // A crashed plugin only outputs silence from here on
+                let failed_with_crash = run.status == RunStatus::Fail && plugin.has_crashed();
+                runs.push(run);
+                if failed_with_crash {
+                    log::error!("Plugin crashed during torture test at {} Hz", sample_rate);
+                    crashed = true;
+                    break 'rates;
+                }
+            }
+        }
+
+        plugin.stop_processing();
+
+        let failures = runs.iter().filter(|r| r.status == RunStatus::Fail).count();
+        let warnings = runs.iter().filter(|r| r.status == RunStatus::Warning).count();
+        log::info!(
+            "Torture test finished: {} runs, {} failures, {} warnings",
+            runs.len(),
+            failures,
+            warnings
+        );
+
+        Ok(TortureReport {
+            plugin_path: bundle.to_string_lossy().to_string(),
+            runs,
+            failures,
+            warnings,
+            crashed,
+            passed: failures == 0 && !crashed,
+        })
+    })
+    .await
+}
+
+/// Record the report's failures as the project's torture test issues
+pub(crate) fn sync_torture_issues(project_name: &str, report: &TortureReport) {
+    sync_validator_issues(project_name, "torture_test", failure_issues(&report.runs));
+}
+
+/// Run the block-size / sample-rate torture test
+///
+/// Every rate in `SAMPLE_RATES` gets a 512-sample reference run and one run per
+/// block size; crashes and NaNs fail, deviations only warn. Failures are filed
+/// as issues when a project build is tested rather than the previewed plugin.
+/// Emits "torture-test-progress" before each run.
+#[tauri::command]
+pub async fn run_torture_test(
+    project_name: Option<String>,
+    version: Option<u32>,
+    app_handle: tauri::AppHandle,
+) -> Result<TortureReport, String> {
+    let bundle = resolve_plugin_bundle(project_name.clone(), version)?;
+
+    let report = torture_test_bundle(bundle, Some(app_handle)).await?;
+
+    if let Some(project_name) = project_name {
+        sync_torture_issues(&project_name, &report);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_irregular_blocks_cover_input() {
+        let blocks = irregular_blocks(48000, MAX_BLOCK_SIZE, 48000);
+        assert_eq!(blocks.iter().sum::<usize>(), 48000);
+        assert!(blocks.iter().all(|&b| b >= 1 && b <= MAX_BLOCK_SIZE as usize));
+        assert!(blocks.iter().any(|&b| b < 16));
+        // Reproducible for the same seed
+        assert_eq!(blocks, irregular_blocks(48000, MAX_BLOCK_SIZE, 48000));
+    }
+
+    #[test]
+    fn test_evaluate_run() {
+        let clean = RunOutput {
+            output: vec![0.25; 64],
+            process_error: None,
+            crashed: false,
+            non_finite: 0,
+            clicks: 0,
+            overloads: 0,
+        };
+        assert_eq!(evaluate_run(48000, Some(64), &clean, Some(&clean)).status, RunStatus::Pass);
+
+        let shifted = RunOutput {
+            output: vec![0.3; 64],
+            ..clean.clone()
+        };
+        let run = evaluate_run(48000, Some(64), &shifted, Some(&clean));
+        assert_eq!(run.status, RunStatus::Warning);
+        assert!(run.max_deviation_db > DEVIATION_THRESHOLD_DB);
+
+        let broken = RunOutput {
+            non_finite: 2,
+            ..clean.clone()
+        };
+        assert_eq!(evaluate_run(48000, Some(64), &broken, Some(&clean)).status, RunStatus::Fail);
+    }
+
+    #[test]
+    fn test_failure_issues_grouped_by_rate() {
+        let clean = RunOutput {
+            output: vec![0.0; 64],
+            process_error: None,
+            crashed: false,
+            non_finite: 0,
+            clicks: 0,
+            overloads: 0,
+        };
+        let nan = RunOutput {
+            non_finite: 1,
+            ..clean.clone()
+        };
+        let runs = vec![
+            evaluate_run(192000, Some(1), &nan, None),
+            evaluate_run(192000, Some(64), &clean, None),
+            evaluate_run(192000, None, &nan, None),
+            evaluate_run(44100, Some(7), &nan, None),
+        ];
+
+        let issues = failure_issues(&runs);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].title, "NaN/Inf output at 192 kHz");
+        assert_eq!(issues[0].details.as_deref(), Some("Torture test block sizes: 1, irregular"));
+        assert_eq!(issues[1].title, "NaN/Inf output at 44.1 kHz");
+    }
+}
@@ -0,0 +1,265 @@
+//! First-run tutorial project
+//!
+//! `create_tutorial_project` creates a small effect project tagged "tutorial"
+//! with a list of staged tasks in `.vstworkshop/tutorial.json`. Tasks are done
+//! in order; `get_tutorial_progress` checks the current one against the project
+//! itself (a new chat version, an added parameter, a successful build, a
+//! publish), so the onboarding flow advances as the user actually does each step.
+//! Builds and publishes only count when they happened after the previous task
+//! was completed.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+
+use super::activity::{latest_activity, project_dir, ActivityKind};
+use super::chat::get_current_version;
+use super::i18n::tr;
+use super::projects::{run_create_project, set_project_tags, CreateProjectInput, ProjectMeta};
+
+const TUTORIAL_NAME: &str = "freqlab-tutorial";
+
+/// How a task's completion is detected
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskCheck {
+    /// Claude made a change (the project has a chat version)
+    ChatChange,
+    /// The source declares more parameters than the template did
+    ParameterAdded,
+    BuildSucceeded,
+    Published,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TutorialTask {
+    pub id: String,
+    pub title: String,
+    pub instructions: String,
+    pub check: TaskCheck,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TutorialState {
+    pub project_name: String,
+    /// Parameters the template started with
+    pub initial_parameters: usize,
+    pub tasks: Vec<TutorialTask>,
+    /// Index of the first unfinished task; equal to tasks.len() when done
+    pub current_task: usize,
+}
+
+/// What the checks look at, gathered once per progress check
+pub(crate) struct ProjectFacts {
+    pub chat_version: u32,
+    pub parameters: usize,
+    /// When the latest build ran, if it succeeded
+    pub built_at: Option<String>,
+    /// When the latest publish ran, if it succeeded
+    pub published_at: Option<String>,
+}
+
+/// Title and instructions come from the `tutorial.{id}.*` catalog entries
+fn task(id: &str, check: TaskCheck) -> TutorialTask {
+    TutorialTask {
+        id: id.to_string(),
+        title: tr(&format!("tutorial.{}.title", id)),
+        instructions: tr(&format!("tutorial.{}.instructions", id)),
+        check,
+        completed_at: None,
+    }
+}
+
+fn tutorial_tasks() -> Vec<TutorialTask> {
+    vec![
+        task("first_change", TaskCheck::ChatChange),
+        task("add_parameter", TaskCheck::ParameterAdded),
+        task("build", TaskCheck::BuildSucceeded),
+        task("publish", TaskCheck::Published),
+    ]
+}
+
+fn get_tutorial_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("tutorial.json")
+}
+
+fn save_state(project_path: &Path, state: &TutorialState) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| format!("Failed to serialize tutorial: {}", e))?;
+    fs::write(get_tutorial_path(project_path), json).map_err(|e| format!("Failed to save tutorial: {}", e))
+}
+
+/// Parameters declared in the project source (`#[id = "..."]` attributes)
+pub(crate) fn count_parameters(project_path: &Path) -> usize {
+    walkdir::WalkDir::new(project_path.join("src"))
+        .into_iter()
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .map(|source| source.lines().filter(|l| l.trim_start().starts_with("#[id")).count())
+        .sum()
+}
+
+/// Whether an activity at `at` happened after `since` (always, when there's no `since`)
+fn happened_after(at: Option<&str>, since: Option<&str>) -> bool {
+    let Some(at) = at else { return false };
+    let Some(since) = since else { return true };
+    match (
+        chrono::DateTime::parse_from_rfc3339(at),
+        chrono::DateTime::parse_from_rfc3339(since),
+    ) {
+        (Ok(at), Ok(since)) => at > since,
+        _ => at > since,
+    }
+}
+
+/// `since`: when the previous task was completed
+fn is_complete(check: TaskCheck, facts: &ProjectFacts, initial_parameters: usize, since: Option<&str>) -> bool {
+    match check {
+        TaskCheck::ChatChange => facts.chat_version > 0,
+        TaskCheck::ParameterAdded => facts.parameters > initial_parameters,
+        TaskCheck::BuildSucceeded => happened_after(facts.built_at.as_deref(), since),
+        TaskCheck::Published => happened_after(facts.published_at.as_deref(), since),
+    }
+}
+
+/// Complete tasks in order up to the first one that isn't done yet
+/// Returns whether anything changed.
+pub(crate) fn complete_tasks(
+    tasks: &mut [TutorialTask],
+    facts: &ProjectFacts,
+    initial_parameters: usize,
+    now: &str,
+) -> bool {
+    let mut changed = false;
+    let mut previous: Option<String> = None;
+    for task in tasks.iter_mut() {
+        if task.completed_at.is_none() {
+            if !is_complete(task.check, facts, initial_parameters, previous.as_deref()) {
+                break;
+            }
+            task.completed_at = Some(now.to_string());
+            changed = true;
+        }
+        previous = task.completed_at.clone();
+    }
+    changed
+}
+
+fn advance(state: &mut TutorialState, facts: &ProjectFacts, now: &str) -> bool {
+    let changed = complete_tasks(&mut state.tasks, facts, state.initial_parameters, now);
+    state.current_task = state.tasks.iter().take_while(|t| t.completed_at.is_some()).count();
+    changed
+}
+
+/// Gather what the task checks look at for a project
+pub(crate) async fn project_facts(project_path: &Path) -> Result<ProjectFacts, String> {
+    let succeeded_at = |kind| {
+        latest_activity(project_path, kind)
+            .filter(|e| e.success)
+            .map(|e| e.timestamp)
+    };
+    Ok(ProjectFacts {
+        chat_version: get_current_version(project_path.to_string_lossy().to_string()).await?,
+        parameters: count_parameters(project_path),
+        built_at: succeeded_at(ActivityKind::Build),
+        published_at: succeeded_at(ActivityKind::Publish),
+    })
+}
+
+/// First free tutorial project name ("freqlab-tutorial", "freqlab-tutorial-2", ...)
+fn tutorial_project_name() -> String {
+    (1..)
+        .map(|n| if n == 1 { TUTORIAL_NAME.to_string() } else { format!("{}-{}", TUTORIAL_NAME, n) })
+        .find(|name| !project_dir(name).exists())
+        .unwrap_or_else(|| TUTORIAL_NAME.to_string())
+}
+
+/// Create the tutorial project, streaming progress as `project-create-stream` events
+#[tauri::command]
+pub async fn create_tutorial_project(window: tauri::Window) -> Result<(ProjectMeta, TutorialState), String> {
+    let project_name = tutorial_project_name();
+    let input = CreateProjectInput {
+        name: project_name.clone(),
+        display_name: Some(tr("tutorial.project_name")),
+        description: tr("tutorial.project_description"),
+        template: "effect".to_string(),
+        ui_framework: "webview".to_string(),
+        vendor_name: None,
+        vendor_url: None,
+        vendor_email: None,
+        components: None,
+    };
+    let meta = run_create_project(input, |event| {
+        let _ = window.emit("project-create-stream", event);
+    })
+    .await?;
+    let meta = set_project_tags(meta.path.clone(), vec!["tutorial".to_string()]).await?;
+
+    let project_path = PathBuf::from(&meta.path);
+    let state = TutorialState {
+        project_name,
+        initial_parameters: count_parameters(&project_path),
+        tasks: tutorial_tasks(),
+        current_task: 0,
+    };
+    save_state(&project_path, &state)?;
+    Ok((meta, state))
+}
+
+/// Check the tutorial's tasks against the project and record newly finished ones
+#[tauri::command]
+pub async fn get_tutorial_progress(project_name: String) -> Result<TutorialState, String> {
+    let project_path = project_dir(&project_name);
+    let mut state: TutorialState = fs::read_to_string(get_tutorial_path(&project_path))
+        .map_err(|_| format!("'{}' isn't a tutorial project", project_name))
+        .and_then(|c| serde_json::from_str(&c).map_err(|e| format!("Failed to parse tutorial: {}", e)))?;
+
+    let facts = project_facts(&project_path).await?;
+    if advance(&mut state, &facts, &chrono::Utc::now().to_rfc3339()) {
+        save_state(&project_path, &state)?;
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tasks_complete_in_order() {
+        let mut state = TutorialState {
+            project_name: "freqlab-tutorial".to_string(),
+            initial_parameters: 1,
+            tasks: tutorial_tasks(),
+            current_task: 0,
+        };
+        // Built before adding a parameter: the build task waits its turn
+        let facts = ProjectFacts {
+            chat_version: 1,
+            parameters: 1,
+            built_at: Some("2026-03-01T10:00:00+00:00".to_string()),
+            published_at: None,
+        };
+        assert!(advance(&mut state, &facts, "2026-03-01T10:01:00+00:00"));
+        assert_eq!(state.current_task, 1);
+        assert!(state.tasks[2].completed_at.is_none());
+
+        // ...and that earlier build doesn't count once the parameter is added
+        let facts = ProjectFacts { parameters: 2, ..facts };
+        assert!(advance(&mut state, &facts, "2026-03-01T10:02:00+00:00"));
+        assert_eq!(state.current_task, 2);
+
+        let facts = ProjectFacts {
+            built_at: Some("2026-03-01T10:03:00+00:00".to_string()),
+            ..facts
+        };
+        assert!(advance(&mut state, &facts, "2026-03-01T10:04:00+00:00"));
+        assert_eq!(state.current_task, 3);
+        assert!(!advance(&mut state, &facts, "2026-03-01T10:05:00+00:00"));
+        assert_eq!(state.tasks[0].completed_at.as_deref(), Some("2026-03-01T10:01:00+00:00"));
+    }
+}
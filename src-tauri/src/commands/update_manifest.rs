@@ -0,0 +1,355 @@
+//! Update manifests for distributed builds
+//!
+//! A project can set the URL where its update manifest will be hosted in
+//! `.vstworkshop/update.json`. Release builds then carry an `update.json` in
+//! each bundle's `Contents/Resources/` (so an installed copy knows where to
+//! look) naming the plugin, its version, and that URL; packages also get one
+//! at their root. Every packaging run refreshes
+//! `output/{project}/update-manifest.json`, listing each archive - the file to
+//! upload to the URL. `check_package_update` reads the `update.json` of a
+//! package or installed bundle and fetches the manifest, so testers can be
+//! told when a newer build is out.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::activity::project_dir;
+use super::projects::get_output_path;
+
+/// Name of the file embedded at the root of packages
+pub(crate) const PACKAGE_UPDATE_FILE: &str = "update.json";
+/// Where the file goes inside a bundle
+const BUNDLE_UPDATE_FILE: &str = "Contents/Resources/update.json";
+const MANIFEST_FILE: &str = "update-manifest.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSettings {
+    /// Where the update manifest is hosted
+    pub manifest_url: String,
+    /// Where packages can be downloaded; `{file}` and `{version}` are filled in
+    #[serde(default)]
+    pub download_url: String,
+}
+
+/// Embedded in packages so the installed build knows where to look for updates
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageUpdateInfo {
+    pub plugin: String,
+    pub version: u32,
+    pub manifest_url: String,
+}
+
+/// One downloadable archive of a release
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePackage {
+    pub file: String,
+    pub download_url: Option<String>,
+    /// SHA-256 of the package at `download_url`
+    pub sha256: Option<String>,
+}
+
+/// The hosted manifest describing the latest build
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateManifest {
+    pub plugin: String,
+    pub version: u32,
+    /// Every archive of the release (one per format when packaged per format)
+    pub packages: Vec<UpdatePackage>,
+    pub published_at: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheck {
+    pub plugin: String,
+    pub current_version: u32,
+    pub latest_version: u32,
+    pub update_available: bool,
+    pub packages: Vec<UpdatePackage>,
+}
+
+fn get_settings_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("update.json")
+}
+
+fn get_manifest_path(project_name: &str) -> PathBuf {
+    get_output_path().join(project_name).join(MANIFEST_FILE)
+}
+
+/// A project's update settings, if it has any
+pub(crate) fn load_settings(project_name: &str) -> Option<UpdateSettings> {
+    fs::read_to_string(get_settings_path(&project_dir(project_name)))
+        .ok()
+        .and_then(|c| serde_json::from_str::<UpdateSettings>(&c).ok())
+        .filter(|s| !s.manifest_url.is_empty())
+}
+
+fn validate_url(url: &str, what: &str) -> Result<(), String> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(format!("{} must start with http:// or https://", what))
+    }
+}
+
+/// Fill `{file}` and `{version}` into a download URL
+fn expand_download_url(template: &str, file_name: &str, version: u32) -> Option<String> {
+    let url = template
+        .trim()
+        .replace("{file}", file_name)
+        .replace("{version}", &version.to_string());
+    (!url.is_empty()).then_some(url)
+}
+
+/// The `update.json` to embed in a package
+pub(crate) fn package_update_file(project_name: &str, version: u32, settings: &UpdateSettings) -> Vec<u8> {
+    let info = PackageUpdateInfo {
+        plugin: project_name.to_string(),
+        version,
+        manifest_url: settings.manifest_url.clone(),
+    };
+    serde_json::to_vec_pretty(&info).unwrap_or_default()
+}
+
+/// Write `update.json` into each bundle of a release build's output folder
+/// Returns how many bundles got one (single-file CLAP plugins can't hold it).
+pub(crate) fn embed_update_info(output_path: &Path, project_name: &str, version: u32) -> Result<usize, String> {
+    let Some(settings) = load_settings(project_name) else {
+        return Ok(0);
+    };
+    let content = package_update_file(project_name, version, &settings);
+    let mut embedded = 0;
+    for entry in fs::read_dir(output_path).map_err(|e| format!("Failed to read output folder: {}", e))?.flatten() {
+        let bundle = entry.path();
+        let is_plugin = bundle.extension().is_some_and(|e| e == "vst3" || e == "clap");
+        if !is_plugin || !bundle.is_dir() {
+            continue;
+        }
+        let path = bundle.join(BUNDLE_UPDATE_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, &content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        embedded += 1;
+    }
+    Ok(embedded)
+}
+
+/// Manifest entries for packaged archives (`(zip path, sha256)`), or the
+/// default archive name when there are none
+fn update_packages(
+    project_name: &str,
+    version: u32,
+    settings: &UpdateSettings,
+    zips: &[(&Path, Option<String>)],
+) -> Vec<UpdatePackage> {
+    let package = |file: String, sha256: Option<String>| UpdatePackage {
+        download_url: expand_download_url(&settings.download_url, &file, version),
+        file,
+        sha256,
+    };
+    if zips.is_empty() {
+        return vec![package(format!("{}_v{}.zip", project_name, version), None)];
+    }
+    zips.iter()
+        .map(|(path, sha256)| {
+            let file = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            package(file, sha256.clone())
+        })
+        .collect()
+}
+
+/// Rewrite `update-manifest.json` for a packaged version; returns its path
+pub(crate) fn write_update_manifest(
+    project_name: &str,
+    version: u32,
+    settings: &UpdateSettings,
+    zips: &[(&Path, Option<String>)],
+) -> Result<PathBuf, String> {
+    let manifest = UpdateManifest {
+        plugin: project_name.to_string(),
+        version,
+        packages: update_packages(project_name, version, settings, zips),
+        published_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let path = get_manifest_path(project_name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create output folder: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| format!("Failed to serialize update manifest: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", MANIFEST_FILE, e))?;
+    Ok(path)
+}
+
+/// `update.json` from a package zip (its root or any bundle in it), an
+/// installed bundle, or the file itself
+fn read_update_info(path: &Path) -> Result<PackageUpdateInfo, String> {
+    let content = if path.extension().is_some_and(|e| e == "zip") {
+        let file = File::open(path).map_err(|e| format!("Failed to open package: {}", e))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Failed to read package: {}", e))?;
+        let name = archive
+            .file_names()
+            .find(|name| *name == PACKAGE_UPDATE_FILE || name.ends_with(&format!("/{}", BUNDLE_UPDATE_FILE)))
+            .map(String::from)
+            .ok_or_else(|| "This package wasn't built with update checks enabled".to_string())?;
+        let mut entry = archive
+            .by_name(&name)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        let mut content = String::new();
+        entry
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read {}: {}", PACKAGE_UPDATE_FILE, e))?;
+        content
+    } else if path.is_dir() {
+        fs::read_to_string(path.join(BUNDLE_UPDATE_FILE))
+            .map_err(|_| "This plugin wasn't built with update checks enabled".to_string())?
+    } else {
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+    };
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", PACKAGE_UPDATE_FILE, e))
+}
+
+fn compare(info: &PackageUpdateInfo, manifest: UpdateManifest) -> Result<UpdateCheck, String> {
+    if manifest.plugin != info.plugin {
+        return Err(format!(
+            "The update manifest is for '{}', not '{}'",
+            manifest.plugin, info.plugin
+        ));
+    }
+    Ok(UpdateCheck {
+        plugin: info.plugin.clone(),
+        current_version: info.version,
+        latest_version: manifest.version,
+        update_available: manifest.version > info.version,
+        packages: manifest.packages,
+    })
+}
+
+#[tauri::command]
+pub async fn get_update_settings(project_name: String) -> Result<Option<UpdateSettings>, String> {
+    Ok(load_settings(&project_name))
+}
+
+/// Save update settings (an empty manifest URL turns update checks off)
+#[tauri::command]
+pub async fn save_update_settings(project_name: String, settings: UpdateSettings) -> Result<(), String> {
+    let path = get_settings_path(&project_dir(&project_name));
+    if settings.manifest_url.trim().is_empty() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove update settings: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+    validate_url(settings.manifest_url.trim(), "Manifest URL")?;
+    if !settings.download_url.trim().is_empty() {
+        validate_url(settings.download_url.trim(), "Download URL")?;
+    }
+    let settings = UpdateSettings {
+        manifest_url: settings.manifest_url.trim().to_string(),
+        download_url: settings.download_url.trim().to_string(),
+    };
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| format!("Failed to serialize update settings: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to save update settings: {}", e))
+}
+
+/// Regenerate the update manifest for a version without repackaging
+/// `zip_paths`: the packages being published, for their file names and checksums.
+#[tauri::command]
+pub async fn generate_update_manifest(
+    project_name: String,
+    version: u32,
+    zip_paths: Option<Vec<String>>,
+) -> Result<String, String> {
+    let settings = load_settings(&project_name)
+        .ok_or_else(|| "Set an update manifest URL for this project first".to_string())?;
+    let zip_paths: Vec<PathBuf> = zip_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect();
+    let mut zips = Vec::new();
+    for path in &zip_paths {
+        zips.push((path.as_path(), Some(super::artifact_manifest::sha256_of(path)?)));
+    }
+    let path = write_update_manifest(&project_name, version.max(1), &settings, &zips)?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Check whether a newer build than a package (or its `update.json`) is published
+#[tauri::command]
+pub async fn check_package_update(package_path: String) -> Result<UpdateCheck, String> {
+    let info = read_update_info(Path::new(&package_path))?;
+    let output = tokio::process::Command::new("curl")
+        .args(["-fsSL", "--max-time", "15"])
+        .arg(&info.manifest_url)
+        .env("PATH", super::get_extended_path())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Couldn't fetch the update manifest from {}: {}",
+            info.manifest_url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let manifest: UpdateManifest = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse update manifest: {}", e))?;
+    compare(&info, manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_check() {
+        assert_eq!(
+            expand_download_url("https://example.com/gain/{version}/{file}", "gain_v3.zip", 3),
+            Some("https://example.com/gain/3/gain_v3.zip".to_string())
+        );
+        assert_eq!(expand_download_url("", "gain_v3.zip", 3), None);
+
+        let settings = UpdateSettings {
+            manifest_url: "https://example.com/gain.json".to_string(),
+            download_url: String::new(),
+        };
+        let info: PackageUpdateInfo = serde_json::from_slice(&package_update_file("gain", 2, &settings)).unwrap();
+
+        // Every per-format archive is listed
+        let settings = UpdateSettings {
+            download_url: "https://example.com/{file}".to_string(),
+            ..settings
+        };
+        let zips = [
+            (Path::new("/out/gain_v3_vst3.zip"), Some("aa".to_string())),
+            (Path::new("/out/gain_v3_clap.zip"), Some("bb".to_string())),
+        ];
+        let packages = update_packages("gain", 3, &settings, &zips);
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[1].file, "gain_v3_clap.zip");
+        assert_eq!(packages[1].download_url.as_deref(), Some("https://example.com/gain_v3_clap.zip"));
+        assert_eq!(packages[1].sha256.as_deref(), Some("bb"));
+        assert_eq!(update_packages("gain", 3, &settings, &[])[0].file, "gain_v3.zip");
+
+        let manifest = UpdateManifest {
+            plugin: "gain".to_string(),
+            version: 3,
+            packages,
+            published_at: String::new(),
+        };
+        let check = compare(&info, manifest.clone()).unwrap();
+        assert!(check.update_available);
+        assert_eq!(check.latest_version, 3);
+
+        let other = UpdateManifest {
+            plugin: "reverb".to_string(),
+            ..manifest
+        };
+        assert!(compare(&info, other).is_err());
+    }
+}
@@ -0,0 +1,441 @@
+//! Uploading packages to distribution targets
+//!
+//! A project can list upload targets in `.vstworkshop/upload.json`: itch.io
+//! (through butler), S3-compatible buckets (through the AWS CLI), and SFTP
+//! servers. The external tools do the transfer and their output is streamed as
+//! `upload-stream` events. API keys and secret access keys are kept in the OS
+//! keychain (macOS Keychain or the Secret Service on Linux), never in the
+//! project; SFTP uses the user's SSH keys.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tauri::Emitter;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+
+use super::activity::project_dir;
+
+/// Keychain service name for upload credentials
+const KEYCHAIN_SERVICE: &str = "freqlab-upload";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum UploadDestination {
+    /// `butler push` to e.g. "studio/tape-deck:mac"
+    Itch { target: String },
+    S3 {
+        bucket: String,
+        #[serde(default)]
+        prefix: String,
+        /// Custom endpoint for S3-compatible storage (R2, B2, MinIO...)
+        endpoint: Option<String>,
+        region: Option<String>,
+        access_key_id: String,
+    },
+    Sftp {
+        host: String,
+        port: Option<u16>,
+        user: String,
+        remote_dir: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UploadTarget {
+    pub name: String,
+    #[serde(flatten)]
+    pub destination: UploadDestination,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct UploadConfig {
+    #[serde(default)]
+    pub targets: Vec<UploadTarget>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "type")]
+pub enum UploadStreamEvent {
+    #[serde(rename = "start")]
+    Start { target: String },
+    #[serde(rename = "output")]
+    Output { line: String },
+    #[serde(rename = "done")]
+    Done { target: String, success: bool },
+    #[serde(rename = "error")]
+    Error { message: String },
+}
+
+/// An external command that performs an upload
+#[derive(Debug, PartialEq)]
+struct UploadInvocation {
+    program: &'static str,
+    args: Vec<String>,
+    env: Vec<(&'static str, String)>,
+    /// Written to the command's stdin (sftp batch commands)
+    stdin: Option<String>,
+}
+
+fn get_config_path(project_path: &Path) -> PathBuf {
+    project_path.join(".vstworkshop").join("upload.json")
+}
+
+fn load_config(project_path: &Path) -> UploadConfig {
+    fs::read_to_string(get_config_path(project_path))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// Keychain account for a target's secret
+fn keychain_account(project_name: &str, target: &str) -> String {
+    format!("{}:{}", project_name, target)
+}
+
+fn needs_secret(destination: &UploadDestination) -> bool {
+    !matches!(destination, UploadDestination::Sftp { .. })
+}
+
+/// Store a secret in the OS keychain under a service and account
+/// The secret never appears on a command line: macOS goes through the Security
+/// framework and Linux passes it to secret-tool on stdin.
+#[cfg(target_os = "macos")]
+pub(crate) fn store_secret(service: &str, _label: &str, account: &str, secret: &str) -> Result<(), String> {
+    security_framework::passwords::set_generic_password(service, account, secret.as_bytes())
+        .map_err(|e| format!("Failed to store credential: {}", e))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn store_secret(service: &str, label: &str, account: &str, secret: &str) -> Result<(), String> {
+    if !cfg!(target_os = "linux") {
+        return Err("Storing credentials isn't supported on this platform yet".to_string());
+    }
+    use std::io::Write;
+    let output = std::process::Command::new("secret-tool")
+        .args(["store", "--label", label, "service", service, "account", account])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(secret.as_bytes())?;
+            }
+            child.wait_with_output()
+        })
+        .map_err(|e| format!("Failed to reach the keychain: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("Failed to store credential: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn load_secret(service: &str, account: &str) -> Option<String> {
+    let secret = security_framework::passwords::get_generic_password(service, account).ok()?;
+    String::from_utf8(secret).ok().filter(|s| !s.is_empty())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn load_secret(service: &str, account: &str) -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let output = std::process::Command::new("secret-tool")
+        .args(["lookup", "service", service, "account", account])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let secret = String::from_utf8_lossy(&output.stdout).trim_end_matches('\n').to_string();
+    (!secret.is_empty()).then_some(secret)
+}
+
+/// Remove a secret from the OS keychain (missing secrets aren't an error)
+#[cfg(target_os = "macos")]
+pub(crate) fn delete_secret(service: &str, account: &str) {
+    let _ = security_framework::passwords::delete_generic_password(service, account);
+}
+
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn delete_secret(service: &str, account: &str) {
+    if cfg!(target_os = "linux") {
+        let _ = std::process::Command::new("secret-tool")
+            .args(["clear", "service", service, "account", account])
+            .output();
+    }
+}
+
+/// Quote a path for an sftp batch command
+/// Inside double quotes sftp unescapes `\"` and `\\`, and a backslash keeps glob
+/// characters literal.
+fn sftp_quote(path: &str) -> String {
+    let mut quoted = String::with_capacity(path.len() + 2);
+    quoted.push('"');
+    for c in path.chars() {
+        if matches!(c, '"' | '\\' | '*' | '?' | '[' | ']') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn file_name(zip_path: &Path) -> String {
+    zip_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// Command line for uploading a package to a destination
+fn upload_invocation(destination: &UploadDestination, zip_path: &Path, secret: Option<String>) -> UploadInvocation {
+    let zip = zip_path.to_string_lossy().to_string();
+    match destination {
+        UploadDestination::Itch { target } => UploadInvocation {
+            program: "butler",
+            args: vec!["push".to_string(), zip, target.clone()],
+            env: secret.map(|key| vec![("BUTLER_API_KEY", key)]).unwrap_or_default(),
+            stdin: None,
+        },
+        UploadDestination::S3 {
+            bucket,
+            prefix,
+            endpoint,
+            region,
+            access_key_id,
+        } => {
+            let key = [prefix.trim_matches('/'), &file_name(zip_path)]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("/");
+            let mut args = vec!["s3".to_string(), "cp".to_string(), zip, format!("s3://{}/{}", bucket, key)];
+            if let Some(endpoint) = endpoint {
+                args.extend(["--endpoint-url".to_string(), endpoint.clone()]);
+            }
+            if let Some(region) = region {
+                args.extend(["--region".to_string(), region.clone()]);
+            }
+            let mut env = vec![("AWS_ACCESS_KEY_ID", access_key_id.clone())];
+            if let Some(secret) = secret {
+                env.push(("AWS_SECRET_ACCESS_KEY", secret));
+            }
+            UploadInvocation {
+                program: "aws",
+                args,
+                env,
+                stdin: None,
+            }
+        }
+        UploadDestination::Sftp {
+            host,
+            port,
+            user,
+            remote_dir,
+        } => {
+            let mut args = vec!["-b".to_string(), "-".to_string()];
+            if let Some(port) = port {
+                args.extend(["-P".to_string(), port.to_string()]);
+            }
+            args.push(format!("{}@{}", user, host));
+            UploadInvocation {
+                program: "sftp",
+                args,
+                env: Vec::new(),
+                stdin: Some(format!(
+                    "put {} {}\n",
+                    sftp_quote(&zip),
+                    sftp_quote(&format!("{}/", remote_dir.trim_end_matches('/')))
+                )),
+            }
+        }
+    }
+}
+
+async fn run_invocation(invocation: UploadInvocation, emit: &impl Fn(UploadStreamEvent)) -> Result<bool, String> {
+    let mut child = Command::new(invocation.program)
+        .args(&invocation.args)
+        .envs(invocation.env)
+        .env("PATH", super::get_extended_path())
+        .stdin(if invocation.stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {} (is it installed?): {}", invocation.program, e))?;
+
+    if let (Some(input), Some(mut stdin)) = (invocation.stdin, child.stdin.take()) {
+        stdin
+            .write_all(input.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to {}: {}", invocation.program, e))?;
+    }
+
+    let mut stdout_reader = BufReader::new(child.stdout.take().ok_or("Failed to capture stdout")?).lines();
+    let mut stderr_reader = BufReader::new(child.stderr.take().ok_or("Failed to capture stderr")?).lines();
+    let (mut stdout_open, mut stderr_open) = (true, true);
+    while stdout_open || stderr_open {
+        tokio::select! {
+            line = stdout_reader.next_line(), if stdout_open => match line {
+                Ok(Some(line)) => emit(UploadStreamEvent::Output { line }),
+                _ => stdout_open = false,
+            },
+            line = stderr_reader.next_line(), if stderr_open => match line {
+                Ok(Some(line)) => emit(UploadStreamEvent::Output { line }),
+                _ => stderr_open = false,
+            },
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for {}: {}", invocation.program, e))?;
+    Ok(status.success())
+}
+
+/// Upload targets configured for a project
+#[tauri::command]
+pub async fn get_upload_config(project_name: String) -> Result<UploadConfig, String> {
+    Ok(load_config(&project_dir(&project_name)))
+}
+
+#[tauri::command]
+pub async fn save_upload_config(project_name: String, config: UploadConfig) -> Result<(), String> {
+    for (i, target) in config.targets.iter().enumerate() {
+        if target.name.trim().is_empty() {
+            return Err("Upload targets need a name".to_string());
+        }
+        if config.targets[..i].iter().any(|t| t.name == target.name) {
+            return Err(format!("There's already an upload target named '{}'", target.name));
+        }
+        // A line break would end the sftp batch command early
+        if let UploadDestination::Sftp { remote_dir, .. } = &target.destination {
+            if remote_dir.chars().any(|c| c.is_control()) {
+                return Err("The remote folder can't contain line breaks or control characters".to_string());
+            }
+        }
+    }
+    let path = get_config_path(&project_dir(&project_name));
+    let json = serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize upload config: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to save upload config: {}", e))
+}
+
+/// Store a target's API key (itch.io) or secret access key (S3) in the keychain
+#[tauri::command]
+pub async fn set_upload_secret(project_name: String, target_name: String, secret: String) -> Result<(), String> {
+    store_secret(
+        KEYCHAIN_SERVICE,
+        "freqlab upload credential",
+        &keychain_account(&project_name, &target_name),
+        &secret,
+    )
+}
+
+/// Upload a package to one of the project's targets, streaming `upload-stream` events
+#[tauri::command]
+pub async fn upload_package(
+    project_name: String,
+    target_name: String,
+    zip_path: String,
+    window: tauri::Window,
+) -> Result<bool, String> {
+    let emit = |event: UploadStreamEvent| {
+        let _ = window.emit("upload-stream", event);
+    };
+    let result = run_upload(&project_name, &target_name, Path::new(&zip_path), &emit).await;
+    if let Err(message) = &result {
+        emit(UploadStreamEvent::Error {
+            message: message.clone(),
+        });
+    }
+    result
+}
+
+async fn run_upload(
+    project_name: &str,
+    target_name: &str,
+    zip_path: &Path,
+    emit: &impl Fn(UploadStreamEvent),
+) -> Result<bool, String> {
+    if !zip_path.is_file() {
+        return Err(format!("Package not found: {}", zip_path.display()));
+    }
+    let config = load_config(&project_dir(project_name));
+    let target = config
+        .targets
+        .iter()
+        .find(|t| t.name == target_name)
+        .ok_or_else(|| format!("No upload target named '{}'", target_name))?;
+
+    let secret = if needs_secret(&target.destination) {
+        let secret = load_secret(KEYCHAIN_SERVICE, &keychain_account(project_name, target_name));
+        // butler can fall back to its own login
+        if secret.is_none() && !matches!(target.destination, UploadDestination::Itch { .. }) {
+            return Err(format!("No credential stored for '{}' - add it in the upload settings", target_name));
+        }
+        secret
+    } else {
+        None
+    };
+
+    emit(UploadStreamEvent::Start {
+        target: target_name.to_string(),
+    });
+    let success = run_invocation(upload_invocation(&target.destination, zip_path, secret), emit).await?;
+    emit(UploadStreamEvent::Done {
+        target: target_name.to_string(),
+        success,
+    });
+    Ok(success)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_invocation() {
+        let zip = Path::new("/out/tape_deck_v3.zip");
+
+        let s3 = UploadDestination::S3 {
+            bucket: "releases".to_string(),
+            prefix: "/beta/".to_string(),
+            endpoint: Some("https://r2.example.com".to_string()),
+            region: None,
+            access_key_id: "AKID".to_string(),
+        };
+        let invocation = upload_invocation(&s3, zip, Some("secret".to_string()));
+        assert_eq!(invocation.program, "aws");
+        assert_eq!(
+            invocation.args,
+            vec!["s3", "cp", "/out/tape_deck_v3.zip", "s3://releases/beta/tape_deck_v3.zip", "--endpoint-url", "https://r2.example.com"]
+        );
+        assert_eq!(invocation.env, vec![("AWS_ACCESS_KEY_ID", "AKID".to_string()), ("AWS_SECRET_ACCESS_KEY", "secret".to_string())]);
+
+        let sftp = UploadDestination::Sftp {
+            host: "example.com".to_string(),
+            port: Some(2222),
+            user: "me".to_string(),
+            remote_dir: "/srv/builds/".to_string(),
+        };
+        let invocation = upload_invocation(&sftp, zip, None);
+        assert_eq!(invocation.args, vec!["-b", "-", "-P", "2222", "me@example.com"]);
+        assert_eq!(invocation.stdin.as_deref(), Some("put \"/out/tape_deck_v3.zip\" \"/srv/builds/\"\n"));
+
+        assert_eq!(sftp_quote(r#"/srv/my "beta" [1]*.zip"#), r#""/srv/my \"beta\" \[1\]\*.zip""#);
+        assert_eq!(sftp_quote(r"C:\out"), r#""C:\\out""#);
+    }
+
+    #[test]
+    fn test_config_format() {
+        let json = r#"{"targets":[{"name":"itch","type":"itch","target":"studio/tape-deck:mac"}]}"#;
+        let config: UploadConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.targets[0].destination,
+            UploadDestination::Itch {
+                target: "studio/tape-deck:mac".to_string()
+            }
+        );
+    }
+}
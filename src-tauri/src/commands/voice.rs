@@ -0,0 +1,275 @@
+//! Voice notes for the chat, transcribed locally
+//!
+//! Records from an input device on its own stream (separate from the preview's
+//! live input) and transcribes the note with whisper.cpp's command-line tool,
+//! so nothing leaves the machine. The tool is found on the PATH (Homebrew's
+//! `whisper-cpp` package installs it) and the model is a ggml file in
+//! `~/VSTWorkshop/models/`.
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use cpal::Sample;
+use ringbuf::{traits::*, HeapProd, HeapRb};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+use super::projects::get_workspace_path;
+use crate::audio::buffer::StereoSample;
+use crate::audio::device::{get_input_device, get_native_input_config};
+use crate::audio::samples::{AudioSample, SampleInfo};
+
+/// whisper.cpp expects 16 kHz audio
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// Longest voice note kept - anything after this is dropped
+const MAX_VOICE_NOTE_SECS: usize = 120;
+
+/// How often the recording thread moves samples out of the stream's ring buffer
+const DRAIN_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Names the whisper.cpp CLI has shipped under
+const WHISPER_BINARIES: [&str; 3] = ["whisper-cli", "whisper-cpp", "whisper"];
+
+/// Models tried in order of preference (English-only models are faster and more accurate for English)
+const PREFERRED_MODELS: [&str; 4] = ["ggml-base.en.bin", "ggml-small.en.bin", "ggml-base.bin", "ggml-tiny.en.bin"];
+
+struct VoiceRecording {
+    /// Closing this ends the recording thread (and its stream)
+    stop: mpsc::Sender<()>,
+    /// Returns the recorded samples
+    thread: std::thread::JoinHandle<Vec<f32>>,
+    sample_rate: u32,
+}
+
+static RECORDING: Mutex<Option<VoiceRecording>> = Mutex::new(None);
+
+fn get_models_dir() -> PathBuf {
+    get_workspace_path().join("models")
+}
+
+/// Whisper model to use, preferring the small English models
+fn find_model() -> Result<PathBuf, String> {
+    let dir = get_models_dir();
+    let missing = || {
+        format!(
+            "No speech model found. Download ggml-base.en.bin from \
+             https://huggingface.co/ggerganov/whisper.cpp into {}",
+            dir.display()
+        )
+    };
+    if let Some(model) = PREFERRED_MODELS.iter().map(|name| dir.join(name)).find(|p| p.exists()) {
+        return Ok(model);
+    }
+    std::fs::read_dir(&dir)
+        .map_err(|_| missing())?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|p| {
+            let name = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            name.starts_with("ggml-") && name.ends_with(".bin")
+        })
+        .ok_or_else(missing)
+}
+
+/// Transcript from whisper's stdout, without the markers it prints for non-speech
+fn transcript_text(stdout: &str) -> String {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| !(line.starts_with('[') && line.ends_with(']')) && !(line.starts_with('(') && line.ends_with(')')))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run whisper.cpp on a 16 kHz WAV file
+fn run_whisper(wav_path: &std::path::Path, model: &std::path::Path) -> Result<String, String> {
+    for binary in WHISPER_BINARIES {
+        let output = Command::new(binary)
+            .args(["-m", &model.to_string_lossy(), "-f", &wav_path.to_string_lossy(), "-nt", "-np"])
+            .env("PATH", super::get_extended_path())
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                return Ok(transcript_text(&String::from_utf8_lossy(&output.stdout)));
+            }
+            Ok(output) => {
+                return Err(format!(
+                    "Transcription failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to run {}: {}", binary, e)),
+        }
+    }
+    Err("whisper.cpp is not installed. Install it with `brew install whisper-cpp`.".to_string())
+}
+
+/// Input stream that mixes each frame to mono and pushes it into the ring buffer
+/// The callback never locks or allocates; samples are dropped if the recording
+/// thread falls a second behind.
+fn build_mono_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut producer: HeapProd<f32>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+{
+    let channels = config.channels as usize;
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            for frame in data.chunks(channels) {
+                let sum: f32 = frame.iter().map(|s| s.to_sample::<f32>()).sum();
+                let _ = producer.try_push(sum / frame.len() as f32);
+            }
+        },
+        |err| log::error!("Voice note stream error: {}", err),
+        None,
+    )
+}
+
+/// Start recording a voice note from an input device (None for the system default)
+#[tauri::command]
+pub async fn start_voice_note(device_name: Option<String>) -> Result<(), String> {
+    let mut recording = RECORDING.lock().unwrap();
+    if recording.is_some() {
+        return Err("Already recording a voice note".to_string());
+    }
+
+    let (stop, stopped) = mpsc::channel::<()>();
+    let (ready, started) = mpsc::channel::<Result<u32, String>>();
+
+    // cpal streams aren't Send, so the stream lives on its own thread until stopped
+    let thread = std::thread::spawn(move || {
+        let stream = get_input_device(device_name.as_deref()).and_then(|device| {
+            let sample_format = device
+                .default_input_config()
+                .map_err(|e| format!("Failed to get default input config: {}", e))?
+                .sample_format();
+            let config = get_native_input_config(&device)?;
+            let sample_rate = config.sample_rate.0;
+            let (producer, consumer) = HeapRb::<f32>::new(sample_rate as usize).split();
+            let stream = match sample_format {
+                cpal::SampleFormat::F32 => build_mono_stream::<f32>(&device, &config, producer),
+                cpal::SampleFormat::I16 => build_mono_stream::<i16>(&device, &config, producer),
+                cpal::SampleFormat::U16 => build_mono_stream::<u16>(&device, &config, producer),
+                other => return Err(format!("Unsupported input sample format: {:?}", other)),
+            }
+            .map_err(|e| format!("Failed to build input stream: {}", e))?;
+            stream.play().map_err(|e| format!("Failed to start input stream: {}", e))?;
+            Ok((stream, consumer, sample_rate))
+        });
+
+        match stream {
+            Ok((stream, mut consumer, sample_rate)) => {
+                let _ = ready.send(Ok(sample_rate));
+                let max_samples = sample_rate as usize * MAX_VOICE_NOTE_SECS;
+                let mut samples = Vec::new();
+                // Drain the ring until the sender is dropped
+                loop {
+                    let finished = !matches!(
+                        stopped.recv_timeout(DRAIN_INTERVAL),
+                        Err(mpsc::RecvTimeoutError::Timeout)
+                    );
+                    for sample in consumer.pop_iter() {
+                        if samples.len() < max_samples {
+                            samples.push(sample);
+                        }
+                    }
+                    if finished {
+                        break;
+                    }
+                }
+                drop(stream);
+                samples
+            }
+            Err(e) => {
+                let _ = ready.send(Err(e));
+                Vec::new()
+            }
+        }
+    });
+
+    let sample_rate = started
+        .recv()
+        .map_err(|_| "Voice recording thread exited unexpectedly".to_string())??;
+    log::info!("Recording voice note at {} Hz", sample_rate);
+    *recording = Some(VoiceRecording {
+        stop,
+        thread,
+        sample_rate,
+    });
+    Ok(())
+}
+
+/// Stop the recording and return its samples
+fn finish_recording() -> Result<(Vec<f32>, u32), String> {
+    let recording = RECORDING
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| "Not recording a voice note".to_string())?;
+    drop(recording.stop);
+    let samples = recording
+        .thread
+        .join()
+        .map_err(|_| "Voice recording thread panicked".to_string())?;
+    Ok((samples, recording.sample_rate))
+}
+
+/// Stop recording and discard the voice note
+#[tauri::command]
+pub async fn cancel_voice_note() -> Result<(), String> {
+    finish_recording().map(|_| ())
+}
+
+/// Stop recording and transcribe the voice note
+/// Returns the text for the chat box (empty if no speech was heard).
+#[tauri::command]
+pub async fn transcribe_voice_note() -> Result<String, String> {
+    let (samples, sample_rate) = finish_recording()?;
+    if samples.len() < sample_rate as usize / 4 {
+        return Err("The voice note is too short".to_string());
+    }
+    let model = find_model()?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut note = AudioSample {
+            info: SampleInfo {
+                name: "voice-note".to_string(),
+                path: String::new(),
+                sample_rate,
+                channels: 1,
+                duration_secs: samples.len() as f32 / sample_rate as f32,
+                num_samples: samples.len(),
+            },
+            data: samples.iter().map(|&s| StereoSample::new(s, s)).collect(),
+        };
+        note.resample_to(WHISPER_SAMPLE_RATE)?;
+
+        let wav_path = std::env::temp_dir().join(format!("freqlab-voice-{}.wav", uuid::Uuid::new_v4()));
+        note.write_wav(&wav_path)?;
+        let result = run_whisper(&wav_path, &model);
+        let _ = std::fs::remove_file(&wav_path);
+        result
+    })
+    .await
+    .map_err(|e| format!("Transcription task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_text() {
+        let stdout = "\n Make it warmer,\n like tape.\n[BLANK_AUDIO]\n (keyboard clicking)\n";
+        assert_eq!(transcript_text(stdout), "Make it warmer, like tape.");
+        assert_eq!(transcript_text(" [BLANK_AUDIO]\n"), "");
+    }
+}
@@ -0,0 +1,203 @@
+//! Workspace report
+//!
+//! A side-by-side summary of every project for people maintaining a suite of
+//! plugins: UI framework, the formats and version of the latest release build,
+//! bundle sizes, whether the bundles still match their artifact manifest, and
+//! the last thing that happened in the project. Returned as JSON with a
+//! Markdown table of the same data.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::activity::last_activity;
+use super::artifact_manifest::{verify_manifest, MANIFEST_FILE};
+use super::build_report::{load_history, BuildArtifact};
+use super::projects::{get_output_path, list_projects, ProjectMeta};
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationStatus {
+    /// The bundles match the build's artifact manifest
+    Passed,
+    /// The bundles were changed or partly overwritten since the build
+    Failed,
+    /// Built before manifests existed
+    Unchecked,
+    NotBuilt,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSummary {
+    pub name: String,
+    pub template: Option<String>,
+    pub ui_framework: Option<String>,
+    /// Version of the latest release build
+    pub latest_version: Option<u32>,
+    /// "VST3", "CLAP"
+    pub formats: Vec<String>,
+    pub artifacts: Vec<BuildArtifact>,
+    pub validation: ValidationStatus,
+    pub validation_message: Option<String>,
+    pub last_activity: Option<String>,
+    pub last_activity_at: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceReport {
+    pub generated_at: String,
+    pub projects: Vec<ProjectSummary>,
+    pub markdown: String,
+}
+
+fn format_of(artifact: &str) -> Option<&'static str> {
+    if artifact.ends_with(".vst3") {
+        Some("VST3")
+    } else if artifact.ends_with(".clap") {
+        Some("CLAP")
+    } else {
+        None
+    }
+}
+
+fn summarize(project: &ProjectMeta) -> ProjectSummary {
+    let project_output = get_output_path().join(&project.name);
+    let latest = load_history(&project_output).into_iter().rev().find(|r| r.profile == "release");
+    let artifacts = latest.as_ref().map(|r| r.artifacts.clone()).unwrap_or_default();
+    let formats = artifacts.iter().filter_map(|a| format_of(&a.name)).map(String::from).collect();
+
+    let (validation, validation_message) = match &latest {
+        None => (ValidationStatus::NotBuilt, None),
+        Some(report) => {
+            let output_path = project_output.join(format!("v{}", report.version));
+            if !output_path.join(MANIFEST_FILE).exists() {
+                (ValidationStatus::Unchecked, None)
+            } else {
+                match verify_manifest(&output_path) {
+                    Ok(()) => (ValidationStatus::Passed, None),
+                    Err(e) => (ValidationStatus::Failed, Some(e)),
+                }
+            }
+        }
+    };
+
+    let activity = last_activity(Path::new(&project.path));
+    ProjectSummary {
+        name: project.name.clone(),
+        template: project.template.clone(),
+        ui_framework: project.ui_framework.clone(),
+        latest_version: latest.map(|r| r.version),
+        formats,
+        artifacts,
+        validation,
+        validation_message,
+        last_activity: activity.as_ref().map(|e| e.summary.clone()),
+        last_activity_at: activity.map(|e| e.timestamp),
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{} KB", bytes.div_ceil(1024))
+    }
+}
+
+/// Table cells can't contain pipes or line breaks
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_markdown(projects: &[ProjectSummary], generated_at: &str) -> String {
+    let mut md = format!("## Workspace report\n\n{} projects, generated {}\n\n", projects.len(), generated_at);
+    md.push_str("| Project | Type | UI | Version | Formats | Size | Validation | Last activity |\n");
+    md.push_str("|---|---|---|---|---|---|---|---|\n");
+    for p in projects {
+        let bundles: Vec<String> = p
+            .artifacts
+            .iter()
+            .filter_map(|a| format_of(&a.name).map(|f| format!("{} {}", f, format_bytes(a.bytes))))
+            .collect();
+        let validation = match p.validation {
+            ValidationStatus::Passed => "passed",
+            ValidationStatus::Failed => "failed",
+            ValidationStatus::Unchecked => "unchecked",
+            ValidationStatus::NotBuilt => "-",
+        };
+        let activity = match (&p.last_activity, &p.last_activity_at) {
+            (Some(summary), Some(at)) => format!("{} ({})", summary, at.get(..10).unwrap_or(at)),
+            _ => "-".to_string(),
+        };
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            cell(&p.name),
+            p.template.as_deref().unwrap_or("-"),
+            p.ui_framework.as_deref().unwrap_or("-"),
+            p.latest_version.map_or("-".to_string(), |v| format!("v{}", v)),
+            if p.formats.is_empty() { "-".to_string() } else { p.formats.join(", ") },
+            if bundles.is_empty() { "-".to_string() } else { bundles.join(", ") },
+            validation,
+            cell(&activity),
+        ));
+    }
+    md
+}
+
+/// Summarize every project in the workspace
+/// With `destination` (a folder), also writes `workspace-report.json` and `workspace-report.md` there.
+#[tauri::command]
+pub async fn workspace_report(destination: Option<String>) -> Result<WorkspaceReport, String> {
+    let projects = list_projects().await?;
+    let summaries = tokio::task::spawn_blocking(move || projects.iter().map(summarize).collect::<Vec<_>>())
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?;
+
+    let generated_at = chrono::Utc::now().to_rfc3339();
+    let report = WorkspaceReport {
+        markdown: render_markdown(&summaries, &generated_at),
+        generated_at,
+        projects: summaries,
+    };
+
+    if let Some(destination) = destination {
+        let dir = PathBuf::from(destination);
+        let json = serde_json::to_string_pretty(&report.projects)
+            .map_err(|e| format!("Failed to serialize report: {}", e))?;
+        fs::write(dir.join("workspace-report.json"), json).map_err(|e| format!("Failed to write report: {}", e))?;
+        fs::write(dir.join("workspace-report.md"), &report.markdown)
+            .map_err(|e| format!("Failed to write report: {}", e))?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let project = ProjectSummary {
+            name: "warm-delay".to_string(),
+            template: Some("effect".to_string()),
+            ui_framework: Some("webview".to_string()),
+            latest_version: Some(3),
+            formats: vec!["CLAP".to_string(), "VST3".to_string()],
+            artifacts: vec![
+                BuildArtifact { name: "warm_delay.clap".to_string(), bytes: 3 * 1024 * 1024 },
+                BuildArtifact { name: "warm_delay.vst3".to_string(), bytes: 1000 },
+                BuildArtifact { name: "warm_delay.dSYM".to_string(), bytes: 9000 },
+            ],
+            validation: ValidationStatus::Passed,
+            validation_message: None,
+            last_activity: Some("Built v3 | release".to_string()),
+            last_activity_at: Some("2026-03-01T10:00:00+00:00".to_string()),
+        };
+        let md = render_markdown(&[project], "now");
+        assert!(md.contains(
+            "| warm-delay | effect | webview | v3 | CLAP, VST3 | CLAP 3.0 MB, VST3 1 KB | passed | Built v3 \\| release (2026-03-01) |\n"
+        ));
+    }
+}
@@ -1,13 +1,27 @@
 pub mod audio;
+pub mod cli;
 mod commands;
 
 use tauri::{Manager, RunEvent};
 
+pub use commands::permissions::HOOK_ARG as CLAUDE_PERMISSION_HOOK_ARG;
+
+/// Run as the Claude CLI's permission hook instead of starting the app
+pub fn run_claude_permission_hook() -> ! {
+    std::process::exit(commands::permissions::run_hook())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize file logging
     commands::logging::init_logging();
 
+    // Capture host panics and native crashes (separate from the plugin crash guard)
+    commands::crash_reports::install_crash_handlers();
+
+    // Projects and share imports half-staged when the app last quit or crashed
+    commands::projects::clear_staging();
+
     let app = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -19,13 +33,11 @@ pub fn run() {
             app.handle()
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
 
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
-            }
+            // Local JSON-RPC server for editor integrations (when enabled in settings)
+            commands::automation_api::init_automation(app.handle().clone());
+
+            // Install/build progress as notifications and a status file (when enabled in settings)
+            commands::stream_mirror::init_stream_mirror(app.handle().clone());
 
             Ok(())
         })
@@ -36,23 +48,110 @@ pub fn run() {
             commands::prerequisites::install_rust,
             commands::prerequisites::install_claude_cli,
             commands::prerequisites::start_claude_auth,
+            commands::prerequisites::set_claude_api_key,
+            commands::prerequisites::setup_all_prerequisites,
             commands::prerequisites::check_permissions,
             commands::prerequisites::request_accessibility_permission,
             commands::prerequisites::prime_admin_privileges,
             commands::projects::create_project,
+            commands::projects::preview_template,
             commands::projects::list_projects,
+            commands::projects::search_projects,
+            commands::projects::list_project_tags,
+            commands::projects::set_project_tags,
+            commands::projects::set_project_favorite,
             commands::projects::get_project,
             commands::projects::delete_project,
             commands::projects::update_project,
             commands::projects::open_project_folder,
             commands::projects::open_in_editor,
+            commands::projects::add_component,
+            commands::projects::migrate_ui_framework,
             commands::projects::get_workspace_path_string,
             commands::claude::send_to_claude,
             commands::claude::test_claude_cli,
             commands::claude::interrupt_claude,
+            commands::task_queue::queue_claude_task,
+            commands::task_queue::list_claude_tasks,
+            commands::task_queue::cancel_claude_task,
+            commands::task_queue::clear_finished_claude_tasks,
+            commands::permissions::respond_to_permission_request,
+            commands::permissions::get_permission_rules,
+            commands::permissions::remove_permission_rule,
+            commands::context_snapshot::build_context_snapshot,
+            commands::patches::set_review_mode,
+            commands::patches::get_review_mode,
+            commands::patches::list_pending_patches,
+            commands::patches::apply_patch,
+            commands::patches::discard_patch,
+            commands::voice::start_voice_note,
+            commands::voice::cancel_voice_note,
+            commands::voice::transcribe_voice_note,
+            commands::reference::analyze_reference,
+            commands::recipes::list_recipes,
+            commands::recipes::scaffold_recipe,
+            commands::smoothing_audit::run_smoothing_audit,
+            commands::editor_recording::record_editor_clip,
+            commands::session::save_session,
+            commands::session::restore_session,
+            commands::automation_api::get_automation_info,
+            commands::automation_api::set_automation_enabled,
+            commands::automation_api::regenerate_automation_token,
+            commands::editor_integration::generate_editor_config,
+            commands::editor_integration::open_in_editor_at,
+            commands::debug_preview::start_debug_preview,
+            commands::plugin_crashes::list_plugin_crash_reports,
+            commands::plugin_crashes::get_plugin_crash_report,
+            commands::plugin_crashes::symbolicate_crash_log,
+            commands::sanitizer::build_with_sanitizers,
+            commands::benchmarks::run_benchmarks,
+            commands::benchmarks::get_benchmark_history,
+            commands::param_docs::get_parameter_changes,
+            commands::library_usage::get_library_usage_stats,
+            commands::library_usage::set_library_usage_opt_in,
+            commands::build_report::get_build_history,
+            commands::package_signing::verify_package,
+            commands::upload::get_upload_config,
+            commands::upload::save_upload_config,
+            commands::upload::set_upload_secret,
+            commands::upload::upload_package,
+            commands::announcement::generate_announcement,
+            commands::update_manifest::get_update_settings,
+            commands::update_manifest::save_update_settings,
+            commands::update_manifest::generate_update_manifest,
+            commands::update_manifest::check_package_update,
+            commands::install_verify::verify_install,
+            commands::plugin_folders::scan_installed_copies,
+            commands::plugin_folders::remove_installed_copies,
+            commands::tutorial::create_tutorial_project,
+            commands::tutorial::get_tutorial_progress,
+            commands::i18n::list_languages,
+            commands::prefetch::prefetch_dependencies,
+            commands::dependency_bundle::export_dependency_bundle,
+            commands::dependency_bundle::import_dependency_bundle,
+            commands::dependency_bundle::get_dependency_bundle,
+            commands::dependency_bundle::remove_dependency_bundle,
+            commands::assignments::save_assignment,
+            commands::assignments::list_assignments,
+            commands::assignments::create_assignment_projects,
+            commands::assignments::unlock_assignment_settings,
+            commands::assignments::export_assignment_status,
+            commands::workspace_report::workspace_report,
+            commands::size_report::get_size_report,
+            commands::release_optimization::get_release_optimization,
+            commands::release_optimization::save_release_optimization,
+            commands::host_smoke_test::run_host_smoke_tests,
             commands::build::build_project,
+            commands::build::build_all_projects,
+            commands::build::cancel_build_all,
+            commands::build::get_last_build_all_report,
             commands::build::open_output_folder,
             commands::git::revert_to_commit,
+            commands::git::git_set_remote,
+            commands::git::git_remove_remote,
+            commands::git::git_push,
+            commands::git::git_pull,
+            commands::git::git_remote_status,
             commands::chat::save_chat_history,
             commands::chat::load_chat_history,
             commands::chat::set_active_version,
@@ -65,16 +164,47 @@ pub fn run() {
             commands::logging::read_log_file,
             commands::logging::clear_log_file,
             commands::logging::get_log_file_size,
+            commands::logging::get_recent_logs,
+            commands::logging::get_log_levels,
+            commands::logging::set_log_levels,
+            commands::diagnostics::export_diagnostics,
+            commands::crash_reports::list_crash_reports,
+            commands::crash_reports::get_crash_report,
+            commands::crash_reports::delete_crash_report,
+            commands::crash_reports::get_crash_report_settings,
+            commands::crash_reports::set_crash_reporting_opt_in,
+            commands::crash_reports::share_crash_report,
+            commands::settings::get_settings,
+            commands::settings::set_settings,
+            commands::settings::update_settings,
+            commands::settings::import_legacy_settings,
+            commands::manual::generate_manual,
+            commands::identity::get_plugin_identity,
+            commands::identity::update_plugin_identity,
+            commands::framework::upgrade_framework,
+            commands::plugin_ids::get_id_registry,
+            commands::plugin_ids::check_id_collisions,
+            commands::plugin_ids::regenerate_plugin_ids,
+            commands::activity::get_project_activity,
+            commands::activity::clear_project_activity,
             commands::files::store_chat_attachments,
             commands::share::export_project,
             commands::share::import_project,
             commands::share::check_import_conflict,
+            commands::share::export_share,
+            commands::share::inspect_share,
+            commands::share::import_share,
+            commands::share::start_lan_server,
+            commands::share::stop_lan_server,
+            commands::share::get_lan_server_info,
             // Preview/Audio commands
             commands::preview::init_audio_engine,
             commands::preview::shutdown_audio_engine,
             commands::preview::get_audio_devices,
             commands::preview::get_audio_sample_rate,
             commands::preview::get_system_sample_rate,
+            commands::preview::get_audio_latency,
+            commands::preview::get_output_buffer_size_range,
             commands::preview::set_audio_config,
             commands::preview::preview_play,
             commands::preview::preview_stop,
@@ -85,9 +215,19 @@ pub fn run() {
             commands::preview::preview_set_gate,
             commands::preview::preview_load_sample,
             commands::preview::preview_set_looping,
+            commands::preview::preview_seek,
+            commands::preview::preview_set_loop_region,
+            commands::preview::preview_get_playhead,
+            commands::preview::preview_set_tempo,
             commands::preview::preview_get_state,
             commands::preview::preview_get_levels,
             commands::preview::get_demo_samples,
+            commands::sample_library::sample_library_list,
+            commands::sample_library::sample_library_import,
+            commands::sample_library::sample_library_update,
+            commands::sample_library::sample_library_remove,
+            commands::sample_library::sample_library_set_project_sample,
+            commands::sample_library::sample_library_get_project_sample,
             commands::preview::start_level_meter,
             commands::preview::stop_level_meter,
             // Plugin commands
@@ -102,10 +242,52 @@ pub fn run() {
             commands::preview::plugin_open_editor,
             commands::preview::plugin_close_editor,
             commands::preview::plugin_is_editor_open,
+            commands::preview::plugin_set_editor_always_on_top,
+            commands::preview::plugin_set_editor_key_routing,
+            commands::preview::plugin_set_editor_scale,
+            commands::preview::plugin_get_editor_window_state,
+            commands::preview::plugin_get_isolation,
+            commands::preview::plugin_set_isolation,
+            commands::preview::preview_open_window,
+            commands::preview::preview_close_window,
+            commands::preview::preview_focus_window,
+            commands::preview::preview_list_windows,
             commands::preview::enable_performance_monitoring,
             commands::preview::is_performance_monitoring_enabled,
             commands::preview::plugin_idle,
             commands::preview::plugin_reload,
+            commands::preview::plugin_watch_artifacts,
+            commands::preview::plugin_unwatch_artifacts,
+            commands::preview::plugin_watcher_status,
+            commands::preview::plugin_export_preset,
+            commands::preview::plugin_import_preset,
+            commands::presets::save_factory_preset,
+            commands::presets::list_factory_presets,
+            commands::presets::delete_factory_preset,
+            // Parameter stress testing
+            commands::param_stress::plugin_list_params,
+            commands::param_stress::plugin_set_param,
+            commands::param_stress::plugin_capture_param_snapshot,
+            commands::param_stress::start_param_stress_test,
+            commands::param_stress::stop_param_stress_test,
+            commands::compare::compare_versions,
+            commands::analysis_jobs::list_analysis_jobs,
+            commands::analysis_jobs::cancel_analysis_job,
+            commands::distortion::measure_distortion,
+            commands::torture_test::run_torture_test,
+            commands::state_roundtrip::run_state_roundtrip_test,
+            commands::denormal_test::run_denormal_test,
+            commands::block_size_audit::run_block_size_audit,
+            commands::issues::list_project_issues,
+            commands::issues::create_project_issue,
+            commands::issues::resolve_project_issue,
+            commands::dsp_tests::generate_dsp_tests,
+            commands::dsp_tests::run_project_tests,
+            commands::automation::start_automation_recording,
+            commands::automation::stop_automation_recording,
+            commands::automation::export_automation_recording,
+            commands::auto_build::set_auto_build,
+            commands::auto_build::get_auto_build_status,
             // Live input commands
             commands::preview::get_input_devices,
             commands::preview::preview_set_live_input,
@@ -115,6 +297,12 @@ pub fn run() {
             // Master volume commands
             commands::preview::preview_set_master_volume,
             commands::preview::preview_get_master_volume,
+            commands::preview::preview_set_output_ceiling,
+            commands::preview::preview_get_output_ceiling,
+            commands::preview::preview_get_mixer,
+            commands::preview::preview_set_mixer_enabled,
+            commands::preview::preview_set_mixer_channel,
+            commands::preview::preview_set_mixer_master_gain,
             // MIDI commands (for instrument plugins)
             commands::preview::midi_batch,
             commands::preview::midi_note_on,
@@ -146,6 +334,12 @@ pub fn run() {
             commands::preview::midi_device_is_connected,
             commands::preview::midi_device_get_connected,
             commands::preview::midi_device_get_last_note,
+            commands::preview::start_midi_learn,
+            commands::preview::cancel_midi_learn,
+            commands::preview::get_midi_mappings,
+            commands::preview::remove_midi_mapping,
+            commands::preview::export_spectrogram,
+            commands::preview::clear_spectrum_history,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
@@ -153,10 +347,19 @@ pub fn run() {
     app.run(|app_handle, event| {
         match event {
             RunEvent::Exit => {
+                // Remember the preview setup for the next launch
+                commands::session::save_session_on_exit(app_handle);
                 // Clean up any spawned child processes when the app exits
                 commands::cleanup_child_processes();
             }
             #[cfg(target_os = "macos")]
+            RunEvent::Opened { urls } => {
+                // freqlab:// automation links
+                for url in urls {
+                    commands::automation_api::handle_deep_link(url.as_str(), app_handle);
+                }
+            }
+            #[cfg(target_os = "macos")]
             RunEvent::Reopen { .. } => {
                 // When user clicks dock icon, bring main window to front
                 if let Some(window) = app_handle.get_webview_window("main") {
@@ -2,5 +2,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+  // Claude sessions call back into this binary to ask the user for permission
+  if std::env::args().nth(1).as_deref() == Some(freqlab_lib::CLAUDE_PERMISSION_HOOK_ARG) {
+    freqlab_lib::run_claude_permission_hook();
+  }
   freqlab_lib::run();
 }
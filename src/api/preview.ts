@@ -228,6 +228,16 @@ export interface MeteringData {
   stereo_positions_input: [number, number][];
   /** INPUT stereo correlation coefficient (-1.0 to +1.0) - pre-FX */
   stereo_correlation_input: number;
+  /** Mid (L+R) RMS level in dB (-60 to 0) - post-FX output */
+  mid_db: number;
+  /** Side (L-R) RMS level in dB (-60 to 0) - post-FX output */
+  side_db: number;
+  /** Side-to-mid balance (0.0 = mono, 0.5 = equal, 1.0 = side only) - post-FX output */
+  mid_side_balance: number;
+  /** INPUT side-to-mid balance - pre-FX */
+  mid_side_balance_input: number;
+  /** Raw [left, right] sample pairs for a Lissajous/goniometer display - post-FX output */
+  lissajous: [number, number][];
   /** Plugin performance metrics (only present when monitoring is enabled) */
   plugin_performance?: PluginPerformance;
   /** True if the plugin has crashed during audio processing */
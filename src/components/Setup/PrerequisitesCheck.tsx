@@ -10,7 +10,7 @@ import {
   requestAccessibilityPermission,
   primeAdminPrivileges,
 } from '../../lib/tauri';
-import type { CheckResult, DiskSpaceInfo, PermissionStatus } from '../../types';
+import type { CheckResult, ClaudeAccount, DiskSpaceInfo, PermissionStatus } from '../../types';
 
 // ============================================================================
 // Types
@@ -20,6 +20,8 @@ interface InstallEvent {
   type: 'start' | 'output' | 'done' | 'error' | 'action_required';
   step?: string;
   line?: string;
+  /** Catalog key of a translated output line (absent for raw tool output) */
+  code?: string;
   success?: boolean;
   message?: string;
   action?: string;
@@ -40,8 +42,51 @@ interface InstallState {
 // Helper Functions
 // ============================================================================
 
+// Stages for the backend's translated progress lines, keyed by catalog key,
+// so progress tracking doesn't depend on the UI language
+const STAGE_BY_CODE: Record<string, { stage: InstallStage; message: string }> = {
+  'install.xcode.already_installed': { stage: 'done', message: 'Already installed!' },
+  'install.xcode.preparing': { stage: 'preparing', message: 'Preparing installation...' },
+  'install.xcode.finding_version': { stage: 'preparing', message: 'Preparing installation...' },
+  'install.xcode.found': { stage: 'downloading', message: 'Downloading (this may take 5-10 minutes)...' },
+  'install.xcode.installing': { stage: 'installing', message: 'Installing...' },
+  'install.xcode.gui_fallback': { stage: 'installing', message: 'Installing...' },
+  'install.xcode.silent_failed': { stage: 'installing', message: 'Installing...' },
+  'install.xcode.opening_dialog': { stage: 'installing', message: 'Installing...' },
+  'install.xcode.still_waiting': { stage: 'installing', message: 'Installing...' },
+  'install.xcode.success': { stage: 'finishing', message: 'Finishing up...' },
+  'install.xcode.complete': { stage: 'finishing', message: 'Finishing up...' },
+  'install.already_installed': { stage: 'done', message: 'Already installed!' },
+  'install.rust.downloading': { stage: 'downloading', message: 'Downloading Rust...' },
+  'install.rust.success': { stage: 'finishing', message: 'Finishing up...' },
+  'install.rust.recheck': { stage: 'finishing', message: 'Finishing up...' },
+  'install.claude.already_installed': { stage: 'done', message: 'Already installed!' },
+  'install.claude.downloading': { stage: 'downloading', message: 'Downloading Claude Code...' },
+  'install.claude.success': { stage: 'finishing', message: 'Finishing up...' },
+  'signin.opening_terminal': { stage: 'preparing', message: 'Opening browser for sign-in...' },
+  'signin.waiting': { stage: 'installing', message: 'Waiting for you to sign in...' },
+  'signin.still_waiting': { stage: 'installing', message: 'Waiting for you to sign in...' },
+  'signin.success': { stage: 'done', message: 'Sign-in complete!' },
+};
+
+const STEP_DEFAULT_MESSAGES: Record<InstallStep, string> = {
+  xcode: 'Setting up Apple Developer Tools...',
+  rust: 'Setting up Rust...',
+  claude_cli: 'Setting up Claude Code...',
+  claude_auth: 'Setting up sign-in...',
+};
+
 // Parse technical output to determine user-friendly stage
-function parseStageFromOutput(step: InstallStep, output: string[], currentStage: InstallStage): { stage: InstallStage; message: string } {
+// Translated lines are matched by code; keywords only apply to raw (English) tool output.
+function parseStageFromOutput(
+  step: InstallStep,
+  output: string[],
+  currentStage: InstallStage,
+  code?: string,
+): { stage: InstallStage; message: string } {
+  if (code) {
+    return STAGE_BY_CODE[code] ?? { stage: currentStage, message: STEP_DEFAULT_MESSAGES[step] };
+  }
   const lastLines = output.slice(-5).join(' ').toLowerCase();
 
   if (step === 'xcode') {
@@ -105,20 +150,13 @@ function parseStageFromOutput(step: InstallStep, output: string[], currentStage:
   }
 
   // Default based on current stage
-  const defaults: Record<InstallStep, string> = {
-    xcode: 'Setting up Apple Developer Tools...',
-    rust: 'Setting up Rust...',
-    claude_cli: 'Setting up Claude Code...',
-    claude_auth: 'Setting up sign-in...',
-  };
-
-  return { stage: currentStage, message: defaults[step] };
+  return { stage: currentStage, message: STEP_DEFAULT_MESSAGES[step] };
 }
 
 // Helper to wait for install completion
 async function waitForInstallComplete(
   installFn: () => Promise<unknown>,
-  onOutput: (line: string) => void,
+  onOutput: (line: string, code?: string) => void,
   onActionRequired: (message: string) => void,
   onDone: (success: boolean) => void,
   abortSignal?: AbortSignal,
@@ -162,7 +200,7 @@ async function waitForInstallComplete(
       const data = event.payload;
 
       if (data.type === 'output' && data.line) {
-        onOutput(data.line);
+        onOutput(data.line, data.code);
       } else if (data.type === 'action_required' && data.message) {
         onActionRequired(data.message);
       } else if (data.type === 'done') {
@@ -550,6 +588,25 @@ function DiskSpaceSection({ diskSpace }: { diskSpace: DiskSpaceInfo | null }) {
   return null; // Don't show if sufficient - less visual noise
 }
 
+function ArchitectureWarnings({ warnings }: { warnings: string[] | undefined }) {
+  if (!warnings || warnings.length === 0) return null;
+
+  return (
+    <div className="p-4 rounded-lg bg-warning/10 border border-warning/30">
+      <div className="flex items-start gap-3">
+        <svg className="w-5 h-5 text-warning flex-shrink-0 mt-0.5" fill="none" viewBox="0 0 24 24" stroke="currentColor" strokeWidth={2}>
+          <path strokeLinecap="round" strokeLinejoin="round" d="M12 9v2m0 4h.01m-6.938 4h13.856c1.54 0 2.502-1.667 1.732-3L13.732 4c-.77-1.333-2.694-1.333-3.464 0L3.34 16c-.77 1.333.192 3 1.732 3z" />
+        </svg>
+        <div className="space-y-1">
+          {warnings.map((warning) => (
+            <p key={warning} className="text-xs text-text-secondary">{warning}</p>
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+}
+
 // Progress Bar Component
 function ProgressBar({ stage }: { stage: InstallStage }) {
   const stages: InstallStage[] = ['preparing', 'downloading', 'installing', 'finishing', 'done'];
@@ -568,6 +625,14 @@ function ProgressBar({ stage }: { stage: InstallStage }) {
   );
 }
 
+/** "Signed in as dev@example.com · Max" from what the CLI reported */
+function formatClaudeAccount(account: ClaudeAccount | null | undefined): string | undefined {
+  if (!account) return undefined;
+  const who = account.email ?? account.organization;
+  const parts = [who ? `Signed in as ${who}` : 'Signed in', account.plan].filter(Boolean);
+  return parts.join(' · ');
+}
+
 // Install Item Component
 interface InstallItemProps {
   label: string;
@@ -579,6 +644,8 @@ interface InstallItemProps {
   disabledReason?: string;
   onInstall: () => void;
   installLabel?: string;
+  /** Shown instead of "Installed" once done (e.g. the signed-in account) */
+  installedDetail?: string;
 }
 
 function InstallItem({
@@ -591,6 +658,7 @@ function InstallItem({
   disabledReason,
   onInstall,
   installLabel = 'Install',
+  installedDetail,
 }: InstallItemProps) {
   const [showDetails, setShowDetails] = useState(false);
   const isInstalled = result?.status === 'installed';
@@ -650,7 +718,7 @@ function InstallItem({
               <p className="text-xs text-text-muted">{timeEstimate}</p>
             )}
             {(isInstalled || justCompletedSuccessfully) && (
-              <p className="text-xs text-success">{justCompletedSuccessfully && !isInstalled ? 'Complete!' : 'Installed'}</p>
+              <p className="text-xs text-success">{justCompletedSuccessfully && !isInstalled ? 'Complete!' : installedDetail ?? 'Installed'}</p>
             )}
           </div>
         </div>
@@ -869,14 +937,14 @@ export function PrerequisitesCheck({ onComplete, helpUrl = 'https://freqlab.app/
     await waitForInstallComplete(
       installFn,
       // On output
-      (line) => {
+      (line, code) => {
         if (!isMountedRef.current) return;
         setInstallStates(prev => {
           const current = prev[step];
           if (!current) return prev;
 
           const newOutput = [...current.technicalOutput, line];
-          const { stage, message } = parseStageFromOutput(step, newOutput, current.stage);
+          const { stage, message } = parseStageFromOutput(step, newOutput, current.stage, code);
 
           return {
             ...prev,
@@ -966,6 +1034,7 @@ export function PrerequisitesCheck({ onComplete, helpUrl = 'https://freqlab.app/
     result: CheckResult | undefined;
     onInstall: () => void;
     installLabel?: string;
+    installedDetail?: string;
   }> = [
     {
       key: 'xcode',
@@ -995,6 +1064,7 @@ export function PrerequisitesCheck({ onComplete, helpUrl = 'https://freqlab.app/
       result: status?.claude_auth,
       onInstall: handleClaudeAuthStart,
       installLabel: 'Sign In',
+      installedDetail: formatClaudeAccount(status?.claude_account),
     },
   ];
 
@@ -1032,6 +1102,9 @@ export function PrerequisitesCheck({ onComplete, helpUrl = 'https://freqlab.app/
         {/* Disk Space Check */}
         <DiskSpaceSection diskSpace={diskSpace} />
 
+        {/* Rosetta / toolchain architecture */}
+        <ArchitectureWarnings warnings={status?.warnings} />
+
         {/* Permission Section */}
         <PermissionSection
           permissions={permissions}
@@ -1058,6 +1131,7 @@ export function PrerequisitesCheck({ onComplete, helpUrl = 'https://freqlab.app/
               disabledReason={getDisabledReason(item.key)}
               onInstall={item.onInstall}
               installLabel={item.installLabel}
+              installedDetail={item.installedDetail}
             />
           ))}
         </div>
@@ -11,6 +11,15 @@ export interface PrerequisiteStatus {
   rust: CheckResult;
   claude_cli: CheckResult;
   claude_auth: CheckResult;
+  claude_account: ClaudeAccount | null;
+  /** Rosetta or a Rust toolchain that doesn't match the CPU */
+  warnings: string[];
+}
+
+export interface ClaudeAccount {
+  email: string | null;
+  organization: string | null;
+  plan: string | null;
 }
 
 export interface DiskSpaceBreakdown {